@@ -0,0 +1,153 @@
+//! Spawn a local governance network for manual testing.
+//!
+//! Brings up one of the zombienet topologies from `config`, waits for
+//! block production, then prints the WS endpoints and ready-to-paste tool
+//! commands. The network stays up until Ctrl-C.
+//!
+//! Usage:
+//!   cargo xtask spawn-net [polkadot|polkadot-ah|kusama]
+//!
+//! or, with the binary env vars set yourself:
+//!   POLKADOT_BINARY_PATH=../bin/polkadot \
+//!   POLKADOT_PARACHAIN_BINARY_PATH=../bin/polkadot-parachain \
+//!   cargo run --bin spawn-governance-net -- kusama
+
+use anyhow::{bail, Context, Result};
+
+use polkadot_referenda_tester_integration_tests::config;
+use polkadot_referenda_tester_integration_tests::context::{
+    GovernanceTestContext, KusamaTestContext, MultiChainTestContext,
+};
+use polkadot_referenda_tester_integration_tests::logging;
+use polkadot_referenda_tester_integration_tests::network::{initialize_network, verify_binaries};
+
+const USAGE: &str = "\
+Usage: spawn-governance-net [TOPOLOGY]
+
+Topologies:
+  polkadot     Polkadot relay + Asset Hub + Collectives (default)
+  polkadot-ah  Polkadot relay + Asset Hub only (faster to start)
+  kusama       Kusama relay + Asset Hub
+";
+
+/// Print an endpoint table followed by suggested `yarn cli test` invocations.
+fn print_playground(endpoints: &[(&str, String)], commands: &[String]) {
+    println!();
+    println!("━━━ Governance playground ready ━━━");
+    for (name, url) in endpoints {
+        println!("  {name:<12} {url}");
+    }
+    println!();
+    println!("Try (from the repository root):");
+    for command in commands {
+        println!("  {command}");
+    }
+    println!();
+    println!("Press Ctrl-C to stop the network.");
+}
+
+#[tokio::main(flavor = "multi_thread")]
+async fn main() -> Result<()> {
+    let topology = std::env::args().nth(1).unwrap_or_else(|| "polkadot".into());
+    if matches!(topology.as_str(), "-h" | "--help" | "help") {
+        print!("{USAGE}");
+        return Ok(());
+    }
+
+    logging::init("spawn_governance_net");
+    verify_binaries()?;
+
+    let network_config = match topology.as_str() {
+        "polkadot" => config::build_polkadot_with_system_parachains()?,
+        "polkadot-ah" => config::build_polkadot_with_asset_hub()?,
+        "kusama" => config::build_kusama_with_asset_hub()?,
+        other => {
+            eprint!("{USAGE}");
+            bail!("Unknown topology: {other}");
+        }
+    };
+
+    log::info!("Spawning {topology} network...");
+    let network = initialize_network(network_config)
+        .await
+        .with_context(|| format!("failed to spawn {topology} network"))?;
+
+    match topology.as_str() {
+        "polkadot" => {
+            let ctx = MultiChainTestContext::from_network(&network).await?;
+            print_playground(
+                &[
+                    ("Relay", ctx.relay_ws_uri.clone()),
+                    ("Asset Hub", ctx.asset_hub_ws_uri.clone()),
+                    ("Collectives", ctx.collectives_ws_uri.clone()),
+                ],
+                &[
+                    format!(
+                        "yarn cli test --governance-chain-url {} \
+                         --call-to-create-governance-referendum <hex>",
+                        ctx.asset_hub_ws_uri
+                    ),
+                    format!(
+                        "yarn cli test --fellowship-chain-url {} \
+                         --call-to-create-fellowship-referendum <hex>",
+                        ctx.collectives_ws_uri
+                    ),
+                    format!(
+                        "yarn cli test --governance-chain-url {} --fellowship-chain-url {} \
+                         --referendum <id> --fellowship <id>",
+                        ctx.asset_hub_ws_uri, ctx.collectives_ws_uri
+                    ),
+                ],
+            );
+        }
+        "polkadot-ah" => {
+            let ctx = GovernanceTestContext::from_network(&network).await?;
+            print_playground(
+                &[
+                    ("Relay", ctx.relay_ws_uri.clone()),
+                    ("Asset Hub", ctx.asset_hub_ws_uri.clone()),
+                ],
+                &[
+                    format!(
+                        "yarn cli test --governance-chain-url {} \
+                         --call-to-create-governance-referendum <hex>",
+                        ctx.asset_hub_ws_uri
+                    ),
+                    format!(
+                        "yarn cli test --governance-chain-url {} --referendum <id>",
+                        ctx.asset_hub_ws_uri
+                    ),
+                ],
+            );
+        }
+        _ => {
+            let ctx = KusamaTestContext::from_network(&network).await?;
+            print_playground(
+                &[
+                    ("Relay", ctx.relay_ws_uri.clone()),
+                    ("Asset Hub", ctx.asset_hub_ws_uri.clone()),
+                ],
+                &[
+                    format!(
+                        "yarn cli test --governance-chain-url {} \
+                         --call-to-create-governance-referendum <hex>",
+                        ctx.asset_hub_ws_uri
+                    ),
+                    // Kusama's Fellowship lives on the relay chain.
+                    format!(
+                        "yarn cli test --fellowship-chain-url {} \
+                         --call-to-create-fellowship-referendum <hex>",
+                        ctx.relay_ws_uri
+                    ),
+                ],
+            );
+        }
+    }
+
+    tokio::signal::ctrl_c()
+        .await
+        .context("failed to listen for Ctrl-C")?;
+    log::info!("Shutting down {topology} network...");
+    drop(network);
+    Ok(())
+}
@@ -0,0 +1,932 @@
+// Environment variables for binary paths.
+pub const POLKADOT_BINARY_ENV: &str = "POLKADOT_BINARY_PATH";
+pub const DEFAULT_POLKADOT_BINARY: &str = "polkadot";
+pub const PARACHAIN_BINARY_ENV: &str = "POLKADOT_PARACHAIN_BINARY_PATH";
+pub const DEFAULT_PARACHAIN_BINARY: &str = "polkadot-parachain";
+
+// Environment variable for fast-runtime WASM directory.
+// Default: ./runtimes/fast/ (relative to integration-tests crate root)
+pub const RUNTIMES_DIR_ENV: &str = "FAST_RUNTIMES_DIR";
+
+// Environment variable naming a versioned subdirectory of the runtimes directory
+// (e.g. `v2.1.0`). Both can be overridden per network; see `RuntimeNetwork`.
+pub const RUNTIMES_VERSION_ENV: &str = "FAST_RUNTIMES_VERSION";
+
+// Environment variable for pre-generated raw chain specs directory.
+// When set and the directory contains cached specs, zombienet skips spec generation.
+pub const CHAIN_SPECS_DIR_ENV: &str = "CHAIN_SPECS_DIR";
+
+// Environment variable for the per-suite report artifact directory.
+// Default: ./target/artifacts/ (relative to integration-tests crate root)
+pub const ARTIFACTS_DIR_ENV: &str = "ARTIFACTS_DIR";
+
+// Tool execution timeouts (seconds) per sub-test class, see `tool_runner::TimeoutClass`.
+// Each can be overridden with its environment variable.
+pub const VALIDATION_TIMEOUT_SECS: u64 = 60; // argument checks fail before forking
+pub const CREATE_TIMEOUT_SECS: u64 = 600; // 10 min for full referendum sim
+pub const MULTICHAIN_TIMEOUT_SECS: u64 = 1200; // 20 min with companion/additional forks
+pub const VALIDATION_TIMEOUT_ENV: &str = "TOOL_TIMEOUT_VALIDATION_SECS";
+pub const CREATE_TIMEOUT_ENV: &str = "TOOL_TIMEOUT_CREATE_SECS";
+pub const MULTICHAIN_TIMEOUT_ENV: &str = "TOOL_TIMEOUT_MULTICHAIN_SECS";
+
+// Optional cap on the tool's peak RSS (MiB), from its "Resource Usage" section.
+// Unset by default; when set, any run exceeding it fails.
+pub const MAX_RSS_ENV: &str = "TOOL_MAX_RSS_MIB";
+
+// Set to `1` to keep every tool run's command line, stdout and stderr under
+// <artifacts dir>/<suite>/runs/<sub_test>/<run id>/. Unset by default.
+pub const RUN_ARTIFACTS_ENV: &str = "TOOL_RUN_ARTIFACTS";
+
+// Prometheus metrics.
+pub const BEST_BLOCK_METRIC: &str = "block_height{status=\"best\"}";
+pub const FINALIZED_BLOCK_METRIC: &str = "block_height{status=\"finalized\"}";
+
+// Readiness: a node must be past these best and finalized heights before a suite starts.
+pub const READY_BEST_BLOCK: f64 = 5.0;
+pub const READY_FINALIZED_BLOCK: f64 = 2.0;
+// How long a node may take to reach each of them (parachains wait for the relay to back them).
+pub const READY_TIMEOUT_SECS: u64 = 300;
+
+// WASM filenames produced by substrate-wasm-builder when building from Fellows repo.
+const RELAY_WASM: &str = "polkadot_runtime.compact.compressed.wasm";
+const ASSET_HUB_WASM: &str = "asset_hub_polkadot_runtime.compact.compressed.wasm";
+const COLLECTIVES_WASM: &str = "collectives_polkadot_runtime.compact.compressed.wasm";
+
+// Kusama WASM filenames.
+const KUSAMA_RELAY_WASM: &str = "staging_kusama_runtime.compact.compressed.wasm";
+const KUSAMA_ASSET_HUB_WASM: &str = "asset_hub_kusama_runtime.compact.compressed.wasm";
+
+// Westend WASM filenames (built from polkadot-sdk, not the Fellows repo).
+const WESTEND_RELAY_WASM: &str = "westend_runtime.compact.compressed.wasm";
+const WESTEND_ASSET_HUB_WASM: &str = "asset_hub_westend_runtime.compact.compressed.wasm";
+
+// Environment variable selecting optional extra parachains (comma-separated
+// `ExtraParachain::name`s, e.g. `people,coretime`) for every topology offering
+// them. Unset: none.
+pub const EXTRA_PARACHAINS_ENV: &str = "EXTRA_PARACHAINS";
+
+use anyhow::anyhow;
+use serde_json::json;
+use std::path::PathBuf;
+use zombienet_configuration::shared::types::Arg;
+use zombienet_sdk::{NetworkConfig, NetworkConfigBuilder};
+
+use crate::network::{get_parachain_binary_path, get_polkadot_binary_path};
+use crate::port_allocator::{self, PortRange};
+use crate::raw_storage;
+
+/// Pin a node's RPC, P2P and Prometheus ports to the next three ports of a
+/// `PortRange`, so networks spawned side by side never collide. A macro
+/// because zombienet's node builder typestates aren't nameable here.
+macro_rules! with_ports {
+    ($node:expr, $ports:expr) => {
+        $node
+            .with_rpc_port($ports.take())
+            .with_p2p_port($ports.take())
+            .with_prometheus_port($ports.take())
+    };
+}
+
+/// Add the selected extra parachains to a network builder, each with one
+/// slot-based collator. A macro for the same reason as `with_ports!`.
+macro_rules! with_extra_parachains {
+    ($builder:expr, $extras:expr, $ports:expr, $para_binary:expr) => {
+        $extras.iter().fold($builder, |builder, extra| {
+            builder.with_parachain(|parachain| {
+                let p = parachain
+                    .with_id(extra.id)
+                    .with_chain(extra.chain)
+                    .with_default_command($para_binary);
+                let p = if let Some(spec) = cached_chain_spec(extra.chain) {
+                    log::info!("Using cached {} chain spec: {spec}", extra.label);
+                    p.with_chain_spec_path(spec.as_str())
+                } else {
+                    let url = runtime_file_path(extra.network, extra.wasm);
+                    log::info!("Generating {} chain spec from runtime: {url}", extra.label);
+                    p.with_chain_spec_runtime(url.as_str(), None)
+                };
+                let collator = format!("{}-collator", extra.name);
+                p.cumulus_based(true).with_collator(|c| {
+                    with_ports!(c.with_name(collator.as_str()), $ports)
+                        .with_command($para_binary)
+                        .with_args(vec![
+                            Arg::Option("--authoring".into(), "slot-based".into()),
+                            Arg::Option("--state-pruning".into(), "archive".into()),
+                        ])
+                })
+            })
+        })
+    };
+}
+
+/// An optional parachain a topology can carry besides its governance chains,
+/// selected with `EXTRA_PARACHAINS_ENV`.
+pub struct ExtraParachain {
+    /// Selection name, shared across topologies (e.g. `people`).
+    pub name: &'static str,
+    pub label: &'static str,
+    pub id: u32,
+    /// Zombienet chain name, also the cached chain spec's name.
+    pub chain: &'static str,
+    /// Runtime WASM, looked up in `network`'s runtimes directory.
+    pub wasm: &'static str,
+    pub network: RuntimeNetwork,
+}
+
+const POLKADOT_EXTRA_PARACHAINS: &[ExtraParachain] = &[
+    ExtraParachain {
+        name: "bridge-hub",
+        label: "Bridge Hub",
+        id: 1002,
+        chain: "bridge-hub-polkadot-local",
+        wasm: "bridge_hub_polkadot_runtime.compact.compressed.wasm",
+        network: RuntimeNetwork::Polkadot,
+    },
+    ExtraParachain {
+        name: "people",
+        label: "People",
+        id: 1004,
+        chain: "people-polkadot-local",
+        wasm: "people_polkadot_runtime.compact.compressed.wasm",
+        network: RuntimeNetwork::Polkadot,
+    },
+    ExtraParachain {
+        name: "coretime",
+        label: "Coretime",
+        id: 1005,
+        chain: "coretime-polkadot-local",
+        wasm: "coretime_polkadot_runtime.compact.compressed.wasm",
+        network: RuntimeNetwork::Polkadot,
+    },
+];
+
+const KUSAMA_EXTRA_PARACHAINS: &[ExtraParachain] = &[
+    ExtraParachain {
+        name: "bridge-hub",
+        label: "Kusama Bridge Hub",
+        id: 1002,
+        chain: "bridge-hub-kusama-local",
+        wasm: "bridge_hub_kusama_runtime.compact.compressed.wasm",
+        network: RuntimeNetwork::Kusama,
+    },
+    ExtraParachain {
+        name: "people",
+        label: "Kusama People",
+        id: 1004,
+        chain: "people-kusama-local",
+        wasm: "people_kusama_runtime.compact.compressed.wasm",
+        network: RuntimeNetwork::Kusama,
+    },
+    ExtraParachain {
+        name: "coretime",
+        label: "Kusama Coretime",
+        id: 1005,
+        chain: "coretime-kusama-local",
+        wasm: "coretime_kusama_runtime.compact.compressed.wasm",
+        network: RuntimeNetwork::Kusama,
+    },
+];
+
+const WESTEND_EXTRA_PARACHAINS: &[ExtraParachain] = &[
+    ExtraParachain {
+        name: "collectives",
+        label: "Westend Collectives",
+        id: 1001,
+        chain: "collectives-westend-local",
+        wasm: "collectives_westend_runtime.compact.compressed.wasm",
+        network: RuntimeNetwork::Westend,
+    },
+    ExtraParachain {
+        name: "bridge-hub",
+        label: "Westend Bridge Hub",
+        id: 1002,
+        chain: "bridge-hub-westend-local",
+        wasm: "bridge_hub_westend_runtime.compact.compressed.wasm",
+        network: RuntimeNetwork::Westend,
+    },
+    ExtraParachain {
+        name: "people",
+        label: "Westend People",
+        id: 1004,
+        chain: "people-westend-local",
+        wasm: "people_westend_runtime.compact.compressed.wasm",
+        network: RuntimeNetwork::Westend,
+    },
+    ExtraParachain {
+        name: "coretime",
+        label: "Westend Coretime",
+        id: 1005,
+        chain: "coretime-westend-local",
+        wasm: "coretime_westend_runtime.compact.compressed.wasm",
+        network: RuntimeNetwork::Westend,
+    },
+];
+
+/// Names selected in `EXTRA_PARACHAINS_ENV`.
+fn extra_parachain_names() -> Vec<String> {
+    std::env::var(EXTRA_PARACHAINS_ENV)
+        .unwrap_or_default()
+        .split(',')
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .collect()
+}
+
+/// The parachains of `offered` selected in `EXTRA_PARACHAINS_ENV`. Names a
+/// topology doesn't offer are skipped; see `check_extra_parachains`.
+fn selected_extra_parachains(offered: &'static [ExtraParachain]) -> Vec<&'static ExtraParachain> {
+    let names = extra_parachain_names();
+    offered
+        .iter()
+        .filter(|extra| names.iter().any(|name| name == extra.name))
+        .collect()
+}
+
+/// Fail on `EXTRA_PARACHAINS_ENV` names that no registered topology offers.
+pub fn check_extra_parachains() -> anyhow::Result<()> {
+    for name in extra_parachain_names() {
+        let offered = TOPOLOGIES
+            .iter()
+            .flat_map(|t| t.extra_parachains)
+            .any(|extra| extra.name == name);
+        let message = format!("Unknown extra parachain '{name}' in {EXTRA_PARACHAINS_ENV}");
+        anyhow::ensure!(offered, message);
+    }
+    Ok(())
+}
+
+/// A network topology whose raw chain specs `generate_chain_specs` can cache.
+pub struct NetworkTopology {
+    /// Selection name (e.g. `westend`).
+    pub name: &'static str,
+    pub build: fn() -> anyhow::Result<NetworkConfig>,
+    /// Chain specs the network always generates: (zombienet chain name, label).
+    pub chain_specs: &'static [(&'static str, &'static str)],
+    pub extra_parachains: &'static [ExtraParachain],
+}
+
+impl NetworkTopology {
+    /// `chain_specs` plus the selected extra parachains' specs.
+    pub fn generated_chain_specs(&self) -> Vec<(&'static str, &'static str)> {
+        let extras = selected_extra_parachains(self.extra_parachains);
+        self.chain_specs
+            .iter()
+            .copied()
+            .chain(extras.iter().map(|extra| (extra.chain, extra.label)))
+            .collect()
+    }
+}
+
+/// Every topology `generate_chain_specs` can generate specs for.
+pub const TOPOLOGIES: &[NetworkTopology] = &[
+    NetworkTopology {
+        name: "polkadot",
+        build: build_polkadot_with_system_parachains,
+        chain_specs: &[
+            ("polkadot-local", "Polkadot relay"),
+            ("asset-hub-polkadot-local", "Asset Hub"),
+            ("collectives-polkadot-local", "Collectives"),
+        ],
+        extra_parachains: POLKADOT_EXTRA_PARACHAINS,
+    },
+    NetworkTopology {
+        name: "kusama",
+        build: build_kusama_with_asset_hub,
+        chain_specs: &[
+            ("kusama-local", "Kusama relay"),
+            ("asset-hub-kusama-local", "Kusama Asset Hub"),
+        ],
+        extra_parachains: KUSAMA_EXTRA_PARACHAINS,
+    },
+    NetworkTopology {
+        name: "westend",
+        build: build_westend_with_asset_hub,
+        chain_specs: &[
+            ("westend-local", "Westend relay"),
+            ("asset-hub-westend-local", "Westend Asset Hub"),
+        ],
+        extra_parachains: WESTEND_EXTRA_PARACHAINS,
+    },
+];
+
+/// Look up a registered topology by name.
+pub fn find_topology(name: &str) -> anyhow::Result<&'static NetworkTopology> {
+    TOPOLOGIES
+        .iter()
+        .find(|topology| topology.name == name)
+        .ok_or_else(|| {
+            let known: Vec<_> = TOPOLOGIES.iter().map(|t| t.name).collect();
+            anyhow!("Unknown topology '{name}' (known: {})", known.join(", "))
+        })
+}
+
+/// Reserve a port range for a new network and log it.
+fn reserve_ports(network: &str) -> PortRange {
+    let ports = port_allocator::next_network_range();
+    log::info!("{network} network ports: {}..{}", ports.start, ports.end());
+    ports
+}
+
+/// Genesis overrides for the relay chain.
+///
+/// Core assignments are handled automatically by the `assign_coretime` call
+/// during genesis (triggered by `paras` pallet for each registered parachain).
+/// We only need to ensure `lookahead >= 2` so the claim queue has enough depth
+/// for async backing and the slot-based collator can see upcoming scheduling.
+fn relay_genesis_overrides() -> serde_json::Value {
+    json!({
+        "configuration": {
+            "config": {
+                "scheduler_params": {
+                    "lookahead": 2
+                }
+            }
+        }
+    })
+}
+
+/// Genesis overrides for Asset Hub parachains.
+///
+/// Disables `devStakers` which otherwise generates 27K+ test staker accounts
+/// during raw spec conversion, adding ~8 min and ~70 MB to the chain spec.
+/// Our governance tests don't need staking test data.
+fn parachain_genesis_overrides() -> serde_json::Value {
+    json!({
+        "staking": {
+            "devStakers": null
+        }
+    })
+}
+
+/// Resolve the directory containing pre-generated raw chain specs, if available.
+pub fn get_chain_specs_dir() -> Option<PathBuf> {
+    let dir = if let Ok(dir) = std::env::var(CHAIN_SPECS_DIR_ENV) {
+        PathBuf::from(dir)
+    } else {
+        let cwd = std::env::current_dir().expect("cannot get cwd");
+        cwd.join("chain-specs")
+    };
+    if dir.is_dir() {
+        Some(dir)
+    } else {
+        None
+    }
+}
+
+/// Get the path to a pre-generated raw chain spec, if it exists.
+///
+/// Returns `Some(path)` when a cached `<name>-raw.json` file is found in the
+/// chain specs directory. When present, zombienet loads the raw spec directly
+/// and skips WASM execution + raw conversion (~3-5 min savings per chain).
+///
+/// **Important**: Cached relay specs must include parachain genesis data.
+/// Use `generate_chain_specs` test to generate specs via zombienet, which
+/// bakes parachain genesis (code + head) into the relay spec automatically.
+fn cached_chain_spec(name: &str) -> Option<String> {
+    let dir = get_chain_specs_dir()?;
+    let path = dir.join(format!("{name}-raw.json"));
+    if path.exists() {
+        let abs = path.canonicalize().unwrap_or(path);
+        Some(abs.to_string_lossy().to_string())
+    } else {
+        None
+    }
+}
+
+/// Networks whose fast runtimes can be pinned separately, so suites can run
+/// different fellows releases for Polkadot and Kusama in one workspace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuntimeNetwork {
+    Polkadot,
+    Kusama,
+    Westend,
+}
+
+impl RuntimeNetwork {
+    pub const ALL: [RuntimeNetwork; 3] = [
+        RuntimeNetwork::Polkadot,
+        RuntimeNetwork::Kusama,
+        RuntimeNetwork::Westend,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            RuntimeNetwork::Polkadot => "polkadot",
+            RuntimeNetwork::Kusama => "kusama",
+            RuntimeNetwork::Westend => "westend",
+        }
+    }
+
+    /// Overrides `FAST_RUNTIMES_DIR` for this network (e.g. `KUSAMA_RUNTIMES_DIR`).
+    pub fn runtimes_dir_env(self) -> &'static str {
+        match self {
+            RuntimeNetwork::Polkadot => "POLKADOT_RUNTIMES_DIR",
+            RuntimeNetwork::Kusama => "KUSAMA_RUNTIMES_DIR",
+            RuntimeNetwork::Westend => "WESTEND_RUNTIMES_DIR",
+        }
+    }
+
+    /// Overrides `FAST_RUNTIMES_VERSION` for this network.
+    pub fn runtimes_version_env(self) -> &'static str {
+        match self {
+            RuntimeNetwork::Polkadot => "POLKADOT_RUNTIMES_VERSION",
+            RuntimeNetwork::Kusama => "KUSAMA_RUNTIMES_VERSION",
+            RuntimeNetwork::Westend => "WESTEND_RUNTIMES_VERSION",
+        }
+    }
+}
+
+/// Resolve the directory containing `network`'s fast-runtime WASM files:
+/// its own runtimes dir (or the shared one), then its versioned subdirectory
+/// when a version is set.
+pub fn get_network_runtimes_dir(network: RuntimeNetwork) -> PathBuf {
+    let dir = std::env::var(network.runtimes_dir_env())
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| get_runtimes_dir());
+    match std::env::var(network.runtimes_version_env())
+        .or_else(|_| std::env::var(RUNTIMES_VERSION_ENV))
+    {
+        Ok(version) => dir.join(version),
+        Err(_) => dir,
+    }
+}
+
+/// Resolve the directory containing fast-runtime WASM files.
+pub fn get_runtimes_dir() -> PathBuf {
+    if let Ok(dir) = std::env::var(RUNTIMES_DIR_ENV) {
+        PathBuf::from(dir)
+    } else {
+        // Default: ./runtimes/fast/ (relative to integration-tests crate root)
+        let cwd = std::env::current_dir().expect("cannot get cwd");
+        cwd.join("runtimes").join("fast")
+    }
+}
+
+/// Resolve the directory where a suite's report artifacts are written.
+pub fn get_artifacts_dir(suite: &str) -> PathBuf {
+    let base = if let Ok(dir) = std::env::var(ARTIFACTS_DIR_ENV) {
+        PathBuf::from(dir)
+    } else {
+        let cwd = std::env::current_dir().expect("cannot get cwd");
+        cwd.join("target").join("artifacts")
+    };
+    base.join(suite)
+}
+
+/// Get absolute path to a local WASM runtime file.
+///
+/// Zombienet's `with_chain_spec_runtime()` accepts plain paths (parsed as
+/// `AssetLocation::FilePath`) which are read via `tokio::fs::read`.
+fn runtime_file_path(network: RuntimeNetwork, filename: &str) -> String {
+    let runtimes_dir = get_network_runtimes_dir(network);
+    let wasm_path = runtimes_dir.join(filename);
+
+    if !wasm_path.exists() {
+        panic!(
+            "Fast-runtime WASM not found: {}\n\
+             Run `cargo xtask build-runtimes` to build them, or set {} (or {}) to point to the directory.",
+            wasm_path.display(),
+            RUNTIMES_DIR_ENV,
+            network.runtimes_dir_env()
+        );
+    }
+
+    // Canonicalize to get absolute path
+    let abs_path = wasm_path.canonicalize().unwrap_or(wasm_path);
+    abs_path.to_string_lossy().to_string()
+}
+
+pub fn polkadot_runtime_url() -> String {
+    runtime_file_path(RuntimeNetwork::Polkadot, RELAY_WASM)
+}
+
+pub fn asset_hub_runtime_url() -> String {
+    runtime_file_path(RuntimeNetwork::Polkadot, ASSET_HUB_WASM)
+}
+
+pub fn collectives_runtime_url() -> String {
+    runtime_file_path(RuntimeNetwork::Polkadot, COLLECTIVES_WASM)
+}
+
+pub fn kusama_runtime_url() -> String {
+    runtime_file_path(RuntimeNetwork::Kusama, KUSAMA_RELAY_WASM)
+}
+
+pub fn kusama_asset_hub_runtime_url() -> String {
+    runtime_file_path(RuntimeNetwork::Kusama, KUSAMA_ASSET_HUB_WASM)
+}
+
+pub fn westend_runtime_url() -> String {
+    runtime_file_path(RuntimeNetwork::Westend, WESTEND_RELAY_WASM)
+}
+
+pub fn westend_asset_hub_runtime_url() -> String {
+    runtime_file_path(RuntimeNetwork::Westend, WESTEND_ASSET_HUB_WASM)
+}
+
+/// Build a NetworkConfig with Polkadot relay + Asset Hub (para 1000) only.
+///
+/// Lighter config for governance-only tests (no Collectives needed).
+pub fn build_polkadot_with_asset_hub() -> anyhow::Result<NetworkConfig> {
+    let relay_binary = get_polkadot_binary_path();
+    let para_binary = get_parachain_binary_path();
+    let ports = reserve_ports("Polkadot");
+
+    log::info!("Relay binary: {relay_binary}");
+    log::info!("Parachain binary: {para_binary}");
+
+    let cached_relay = cached_chain_spec("polkadot-local");
+    let cached_ah = cached_chain_spec("asset-hub-polkadot-local");
+
+    let extras = selected_extra_parachains(POLKADOT_EXTRA_PARACHAINS);
+
+    let builder = NetworkConfigBuilder::new()
+        .with_relaychain(|relaychain| {
+            let r = relaychain
+                .with_chain("polkadot-local")
+                .with_default_command(relay_binary.as_str());
+            let r = if let Some(ref spec) = cached_relay {
+                log::info!("Using cached relay chain spec: {spec}");
+                r.with_chain_spec_path(spec.as_str())
+            } else {
+                let url = polkadot_runtime_url();
+                log::info!("Generating relay chain spec from runtime: {url}");
+                r.with_chain_spec_runtime(url.as_str(), None)
+                    .with_genesis_overrides(relay_genesis_overrides())
+            };
+            r.with_validator(|node| {
+                with_ports!(node.with_name("alice"), ports).with_args(vec![Arg::Option(
+                    "--state-pruning".into(),
+                    "archive".into(),
+                )])
+            })
+            .with_validator(|node| {
+                with_ports!(node.with_name("bob"), ports).with_args(vec![Arg::Option(
+                    "--state-pruning".into(),
+                    "archive".into(),
+                )])
+            })
+        })
+        .with_parachain(|parachain| {
+            let p = parachain
+                .with_id(1000)
+                .with_chain("asset-hub-polkadot-local")
+                .with_default_command(para_binary.as_str());
+            let p = if let Some(ref spec) = cached_ah {
+                log::info!("Using cached Asset Hub chain spec: {spec}");
+                p.with_chain_spec_path(spec.as_str())
+            } else {
+                let url = asset_hub_runtime_url();
+                log::info!("Generating Asset Hub chain spec from runtime: {url}");
+                p.with_chain_spec_runtime(url.as_str(), None)
+                    .with_genesis_overrides(parachain_genesis_overrides())
+            };
+            p.with_raw_spec_override(raw_storage::asset_hub_polkadot_override())
+                .cumulus_based(true)
+                .with_collator(|c| {
+                    with_ports!(c.with_name("asset-hub-collator"), ports)
+                        .with_command(para_binary.as_str())
+                        .with_args(vec![
+                            Arg::Option("--authoring".into(), "slot-based".into()),
+                            Arg::Option("--state-pruning".into(), "archive".into()),
+                        ])
+                })
+        });
+    with_extra_parachains!(builder, extras, ports, para_binary.as_str())
+        .build()
+        .map_err(|errs| {
+            let message = errs
+                .into_iter()
+                .map(|e| e.to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            anyhow!("NetworkConfig build errors: {message}")
+        })
+}
+
+/// Build a NetworkConfig with Polkadot relay + Asset Hub (para 1000) + Collectives (para 1001).
+///
+/// Uses `with_chain_spec_runtime()` to load real production runtimes from fellows releases,
+/// so the test chains have the actual governance pallets (Referenda, FellowshipReferenda, etc.).
+pub fn build_polkadot_with_system_parachains() -> anyhow::Result<NetworkConfig> {
+    let relay_binary = get_polkadot_binary_path();
+    let para_binary = get_parachain_binary_path();
+    let ports = reserve_ports("Polkadot");
+
+    log::info!("Relay binary: {relay_binary}");
+    log::info!("Parachain binary: {para_binary}");
+
+    let cached_relay = cached_chain_spec("polkadot-local");
+    let cached_ah = cached_chain_spec("asset-hub-polkadot-local");
+    let cached_coll = cached_chain_spec("collectives-polkadot-local");
+
+    let extras = selected_extra_parachains(POLKADOT_EXTRA_PARACHAINS);
+
+    let builder = NetworkConfigBuilder::new()
+        .with_relaychain(|relaychain| {
+            let r = relaychain
+                .with_chain("polkadot-local")
+                .with_default_command(relay_binary.as_str());
+            let r = if let Some(ref spec) = cached_relay {
+                log::info!("Using cached relay chain spec: {spec}");
+                r.with_chain_spec_path(spec.as_str())
+            } else {
+                let url = polkadot_runtime_url();
+                log::info!("Generating relay chain spec from runtime: {url}");
+                r.with_chain_spec_runtime(url.as_str(), None)
+                    .with_genesis_overrides(relay_genesis_overrides())
+            };
+            r.with_validator(|node| {
+                with_ports!(node.with_name("alice"), ports).with_args(vec![Arg::Option(
+                    "--state-pruning".into(),
+                    "archive".into(),
+                )])
+            })
+            .with_validator(|node| {
+                with_ports!(node.with_name("bob"), ports).with_args(vec![Arg::Option(
+                    "--state-pruning".into(),
+                    "archive".into(),
+                )])
+            })
+        })
+        .with_parachain(|parachain| {
+            let p = parachain
+                .with_id(1000)
+                .with_chain("asset-hub-polkadot-local")
+                .with_default_command(para_binary.as_str());
+            let p = if let Some(ref spec) = cached_ah {
+                log::info!("Using cached Asset Hub chain spec: {spec}");
+                p.with_chain_spec_path(spec.as_str())
+            } else {
+                let url = asset_hub_runtime_url();
+                log::info!("Generating Asset Hub chain spec from runtime: {url}");
+                p.with_chain_spec_runtime(url.as_str(), None)
+                    .with_genesis_overrides(parachain_genesis_overrides())
+            };
+            p.with_raw_spec_override(raw_storage::asset_hub_polkadot_override())
+                .cumulus_based(true)
+                .with_collator(|c| {
+                    with_ports!(c.with_name("asset-hub-collator"), ports)
+                        .with_command(para_binary.as_str())
+                        .with_args(vec![
+                            Arg::Option("--authoring".into(), "slot-based".into()),
+                            Arg::Option("--state-pruning".into(), "archive".into()),
+                        ])
+                })
+        })
+        .with_parachain(|parachain| {
+            let p = parachain
+                .with_id(1001)
+                .with_chain("collectives-polkadot-local")
+                .with_default_command(para_binary.as_str());
+            let p = if let Some(ref spec) = cached_coll {
+                log::info!("Using cached Collectives chain spec: {spec}");
+                p.with_chain_spec_path(spec.as_str())
+            } else {
+                let url = collectives_runtime_url();
+                log::info!("Generating Collectives chain spec from runtime: {url}");
+                p.with_chain_spec_runtime(url.as_str(), None)
+            };
+            p.with_raw_spec_override(raw_storage::collectives_override())
+                .cumulus_based(true)
+                .with_collator(|c| {
+                    with_ports!(c.with_name("collectives-collator"), ports)
+                        .with_command(para_binary.as_str())
+                        .with_args(vec![
+                            Arg::Option("--authoring".into(), "slot-based".into()),
+                            Arg::Option("--state-pruning".into(), "archive".into()),
+                        ])
+                })
+        });
+    with_extra_parachains!(builder, extras, ports, para_binary.as_str())
+        .build()
+        .map_err(|errs| {
+            let message = errs
+                .into_iter()
+                .map(|e| e.to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            anyhow!("NetworkConfig build errors: {message}")
+        })
+}
+
+/// Build a NetworkConfig with Kusama relay + Asset Hub (para 1000).
+///
+/// On Kusama, the Fellowship pallets (FellowshipReferenda, FellowshipCollective)
+/// live on the relay chain itself, so no Collectives parachain is needed.
+pub fn build_kusama_with_asset_hub() -> anyhow::Result<NetworkConfig> {
+    build_kusama("Kusama", false)
+}
+
+/// Build a NetworkConfig with Kusama relay + Asset Hub (para 1000) + the
+/// Polkadot Collectives runtime (para 1001).
+///
+/// Fellowship pallets then live both on the relay (Kusama layout) and on a
+/// parachain (Polkadot layout) of one network, so the tool's topology
+/// detection is exercised for both layouts independently of the network a
+/// runtime comes from. The parachains reuse the `kusama` and `polkadot`
+/// topologies' cached chain specs, but the relay spec is always generated:
+/// the cached `kusama-local` one has no genesis for para 1001. No extra
+/// parachains are added.
+pub fn build_kusama_with_collectives() -> anyhow::Result<NetworkConfig> {
+    build_kusama("Kusama+Collectives", true)
+}
+
+/// The Kusama network of [`build_kusama_with_asset_hub`], plus Collectives
+/// as para 1001 when `with_collectives` (see [`build_kusama_with_collectives`]).
+fn build_kusama(network: &str, with_collectives: bool) -> anyhow::Result<NetworkConfig> {
+    let relay_binary = get_polkadot_binary_path();
+    let para_binary = get_parachain_binary_path();
+    let ports = reserve_ports(network);
+
+    log::info!("Relay binary: {relay_binary}");
+    log::info!("Parachain binary: {para_binary}");
+
+    // A cached relay spec only carries genesis for its own topology's parachains
+    let cached_relay = if with_collectives {
+        None
+    } else {
+        cached_chain_spec("kusama-local")
+    };
+    let cached_ah = cached_chain_spec("asset-hub-kusama-local");
+    let cached_coll = cached_chain_spec("collectives-polkadot-local");
+
+    let extras = if with_collectives {
+        Vec::new()
+    } else {
+        selected_extra_parachains(KUSAMA_EXTRA_PARACHAINS)
+    };
+
+    let builder = NetworkConfigBuilder::new()
+        .with_relaychain(|relaychain| {
+            let r = relaychain
+                .with_chain("kusama-local")
+                .with_default_command(relay_binary.as_str());
+            let r = if let Some(ref spec) = cached_relay {
+                log::info!("Using cached Kusama relay chain spec: {spec}");
+                r.with_chain_spec_path(spec.as_str())
+            } else {
+                let url = kusama_runtime_url();
+                log::info!("Generating Kusama relay chain spec from runtime: {url}");
+                r.with_chain_spec_runtime(url.as_str(), None)
+                    .with_genesis_overrides(relay_genesis_overrides())
+            };
+            r.with_raw_spec_override(raw_storage::fellowship_collective_override())
+                .with_validator(|node| {
+                    with_ports!(node.with_name("alice"), ports).with_args(vec![Arg::Option(
+                        "--state-pruning".into(),
+                        "archive".into(),
+                    )])
+                })
+                .with_validator(|node| {
+                    with_ports!(node.with_name("bob"), ports).with_args(vec![Arg::Option(
+                        "--state-pruning".into(),
+                        "archive".into(),
+                    )])
+                })
+        })
+        .with_parachain(|parachain| {
+            let p = parachain
+                .with_id(1000)
+                .with_chain("asset-hub-kusama-local")
+                .with_default_command(para_binary.as_str());
+            let p = if let Some(ref spec) = cached_ah {
+                log::info!("Using cached Kusama Asset Hub chain spec: {spec}");
+                p.with_chain_spec_path(spec.as_str())
+            } else {
+                let url = kusama_asset_hub_runtime_url();
+                log::info!("Generating Kusama Asset Hub chain spec from runtime: {url}");
+                p.with_chain_spec_runtime(url.as_str(), None)
+                    .with_genesis_overrides(parachain_genesis_overrides())
+            };
+            p.with_raw_spec_override(raw_storage::ah_migrator_override())
+                .cumulus_based(true)
+                .with_collator(|c| {
+                    with_ports!(c.with_name("asset-hub-collator"), ports)
+                        .with_command(para_binary.as_str())
+                        .with_args(vec![
+                            Arg::Option("--authoring".into(), "slot-based".into()),
+                            Arg::Option("--state-pruning".into(), "archive".into()),
+                        ])
+                })
+        });
+    // A fold over zero or one item, as zombienet's builder typestates rule
+    // out a plain `if`
+    let builder = with_collectives
+        .then_some(())
+        .into_iter()
+        .fold(builder, |builder, ()| {
+            builder.with_parachain(|parachain| {
+                let p = parachain
+                    .with_id(1001)
+                    .with_chain("collectives-polkadot-local")
+                    .with_default_command(para_binary.as_str());
+                let p = if let Some(ref spec) = cached_coll {
+                    log::info!("Using cached Collectives chain spec: {spec}");
+                    p.with_chain_spec_path(spec.as_str())
+                } else {
+                    let url = collectives_runtime_url();
+                    log::info!("Generating Collectives chain spec from runtime: {url}");
+                    p.with_chain_spec_runtime(url.as_str(), None)
+                };
+                p.with_raw_spec_override(raw_storage::collectives_override())
+                    .cumulus_based(true)
+                    .with_collator(|c| {
+                        with_ports!(c.with_name("collectives-collator"), ports)
+                            .with_command(para_binary.as_str())
+                            .with_args(vec![
+                                Arg::Option("--authoring".into(), "slot-based".into()),
+                                Arg::Option("--state-pruning".into(), "archive".into()),
+                            ])
+                    })
+            })
+        });
+    with_extra_parachains!(builder, extras, ports, para_binary.as_str())
+        .build()
+        .map_err(|errs| {
+            let message = errs
+                .into_iter()
+                .map(|e| e.to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            anyhow!("NetworkConfig build errors: {message}")
+        })
+}
+
+/// Build a NetworkConfig with Westend relay + Asset Hub (para 1000).
+///
+/// Westend runtimes come from polkadot-sdk rather than the Fellows repo and
+/// need none of the raw spec overrides the Polkadot and Kusama networks use.
+pub fn build_westend_with_asset_hub() -> anyhow::Result<NetworkConfig> {
+    let relay_binary = get_polkadot_binary_path();
+    let para_binary = get_parachain_binary_path();
+    let ports = reserve_ports("Westend");
+
+    log::info!("Relay binary: {relay_binary}");
+    log::info!("Parachain binary: {para_binary}");
+
+    let cached_relay = cached_chain_spec("westend-local");
+    let cached_ah = cached_chain_spec("asset-hub-westend-local");
+    let extras = selected_extra_parachains(WESTEND_EXTRA_PARACHAINS);
+
+    let builder = NetworkConfigBuilder::new()
+        .with_relaychain(|relaychain| {
+            let r = relaychain
+                .with_chain("westend-local")
+                .with_default_command(relay_binary.as_str());
+            let r = if let Some(ref spec) = cached_relay {
+                log::info!("Using cached Westend relay chain spec: {spec}");
+                r.with_chain_spec_path(spec.as_str())
+            } else {
+                let url = westend_runtime_url();
+                log::info!("Generating Westend relay chain spec from runtime: {url}");
+                r.with_chain_spec_runtime(url.as_str(), None)
+                    .with_genesis_overrides(relay_genesis_overrides())
+            };
+            r.with_validator(|node| {
+                with_ports!(node.with_name("alice"), ports).with_args(vec![Arg::Option(
+                    "--state-pruning".into(),
+                    "archive".into(),
+                )])
+            })
+            .with_validator(|node| {
+                with_ports!(node.with_name("bob"), ports).with_args(vec![Arg::Option(
+                    "--state-pruning".into(),
+                    "archive".into(),
+                )])
+            })
+        })
+        .with_parachain(|parachain| {
+            let p = parachain
+                .with_id(1000)
+                .with_chain("asset-hub-westend-local")
+                .with_default_command(para_binary.as_str());
+            let p = if let Some(ref spec) = cached_ah {
+                log::info!("Using cached Westend Asset Hub chain spec: {spec}");
+                p.with_chain_spec_path(spec.as_str())
+            } else {
+                let url = westend_asset_hub_runtime_url();
+                log::info!("Generating Westend Asset Hub chain spec from runtime: {url}");
+                p.with_chain_spec_runtime(url.as_str(), None)
+                    .with_genesis_overrides(parachain_genesis_overrides())
+            };
+            p.cumulus_based(true).with_collator(|c| {
+                with_ports!(c.with_name("asset-hub-collator"), ports)
+                    .with_command(para_binary.as_str())
+                    .with_args(vec![
+                        Arg::Option("--authoring".into(), "slot-based".into()),
+                        Arg::Option("--state-pruning".into(), "archive".into()),
+                    ])
+            })
+        });
+    with_extra_parachains!(builder, extras, ports, para_binary.as_str())
+        .build()
+        .map_err(|errs| {
+            let message = errs
+                .into_iter()
+                .map(|e| e.to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            anyhow!("NetworkConfig build errors: {message}")
+        })
+}
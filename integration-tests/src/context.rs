@@ -0,0 +1,468 @@
+//! Shared test context structs that encapsulate zombienet network state.
+//!
+//! Created once per test suite after network spawn, then passed to all sub-tests.
+//! This avoids duplicating the wait-for-readiness + subxt-connect boilerplate.
+
+use anyhow::{Context, Result};
+use std::time::Duration;
+use subxt::dynamic::{self, Value};
+use subxt::utils::H256;
+use subxt::{OnlineClient, PolkadotConfig};
+use zombienet_sdk::{LocalFileSystem, Network, NetworkNode};
+
+use crate::config::{
+    BEST_BLOCK_METRIC, FINALIZED_BLOCK_METRIC, READY_BEST_BLOCK, READY_FINALIZED_BLOCK,
+    READY_TIMEOUT_SECS,
+};
+use crate::tool_args::ChainEndpoint;
+
+/// Tool endpoint of a zombienet node forked at `block`, labeled so the tool's
+/// output names chains after their role instead of spec names. Node URIs are
+/// always `ws://`, so [`ChainEndpoint::new`]'s check does not fail.
+pub fn labeled_endpoint(label: &str, ws_uri: &str, block: u32) -> ChainEndpoint {
+    ChainEndpoint::new(ws_uri)
+        .expect("zombienet node URIs are ws:// URLs")
+        .at_block(block)
+        .labeled(label)
+}
+
+/// Read `pallet.item` under `keys` (empty for plain items), decoded to a
+/// dynamic value, at block `at` or the latest block. `None` when nothing is
+/// stored there. Block numbers are resolved over `client`'s own connection;
+/// see [`block_hash`].
+pub async fn query_storage(
+    client: &OnlineClient<PolkadotConfig>,
+    pallet: &str,
+    item: &str,
+    keys: Vec<Value>,
+    at: Option<u32>,
+) -> Result<Option<Value>> {
+    let storage = match at {
+        Some(block) => client.storage().at(block_hash(client, block).await?),
+        None => client.storage().at_latest().await?,
+    };
+    let value = storage
+        .fetch(&dynamic::storage(pallet, item, keys))
+        .await
+        .with_context(|| format!("Failed to read {pallet}.{item}"))?;
+    value
+        .map(|value| value.to_value().map(Value::remove_context))
+        .transpose()
+        .with_context(|| format!("Failed to decode {pallet}.{item}"))
+}
+
+/// Hash of block `number`: the latest block's own, else `System.BlockHash`
+/// at the latest block, which keeps the last `BlockHashCount` (4096 on the
+/// fellows runtimes) hashes; plenty for fork blocks taken when a suite starts.
+async fn block_hash(client: &OnlineClient<PolkadotConfig>, number: u32) -> Result<H256> {
+    let latest = client.blocks().at_latest().await?;
+    if latest.number() == number {
+        return Ok(latest.hash());
+    }
+    let hash = client
+        .storage()
+        .at(latest.hash())
+        .fetch(&dynamic::storage(
+            "System",
+            "BlockHash",
+            vec![Value::u128(number.into())],
+        ))
+        .await
+        .with_context(|| format!("Failed to read System.BlockHash({number})"))?
+        .map(|hash| hash.as_type::<H256>())
+        .transpose()
+        .context("Failed to decode System.BlockHash")?
+        .filter(|hash| !hash.is_zero());
+    hash.with_context(|| {
+        format!(
+            "No hash of block #{number} kept at the latest block #{}",
+            latest.number()
+        )
+    })
+}
+
+/// Epoch length in blocks, from the runtime's `Babe.EpochDuration` constant.
+/// `None` for runtimes without BABE (parachains), which have no epochs to avoid.
+fn epoch_length(client: &OnlineClient<PolkadotConfig>) -> Option<u32> {
+    let duration = client
+        .constants()
+        .at(&dynamic::constant("Babe", "EpochDuration"))
+        .ok()?
+        .as_type::<u64>()
+        .ok()?;
+    u32::try_from(duration).ok()
+}
+
+/// Wait until `node` is ready to be forked: producing blocks and finalizing
+/// them. Collators only finalize parachain blocks that were backed and included
+/// in finalized relay blocks, so on a parachain this also waits for the relay
+/// to back it; a chain that merely produces blocks fails early tool runs.
+/// Each wait is bounded by [`READY_TIMEOUT_SECS`].
+async fn wait_ready(node: &NetworkNode, chain: &str) -> Result<()> {
+    wait_past(node, BEST_BLOCK_METRIC, READY_BEST_BLOCK)
+        .await
+        .with_context(|| format!("{chain} not producing blocks"))?;
+    wait_past(node, FINALIZED_BLOCK_METRIC, READY_FINALIZED_BLOCK)
+        .await
+        .with_context(|| format!("{chain} not finalizing blocks"))?;
+    log::info!("{chain} ready: producing and finalizing blocks");
+    Ok(())
+}
+
+/// Wait until `node` reports `metric` above `threshold`, for at most
+/// [`READY_TIMEOUT_SECS`].
+async fn wait_past(node: &NetworkNode, metric: &str, threshold: f64) -> Result<()> {
+    match tokio::time::timeout(
+        Duration::from_secs(READY_TIMEOUT_SECS),
+        node.wait_metric(metric, |value| value > threshold),
+    )
+    .await
+    {
+        Ok(result) => result
+            .map_err(|e| anyhow::anyhow!("node {}: reading {metric} failed: {e}", node.name())),
+        Err(_) => anyhow::bail!(
+            "node {} did not report {metric} above {threshold} within {READY_TIMEOUT_SECS}s",
+            node.name()
+        ),
+    }
+}
+
+/// Step `block` back by one if it is a session boundary (a multiple of
+/// `epoch_length`). Chopsticks has issues with preimage availability when the
+/// fork point is exactly on a session boundary; `ksm_fell_session_boundary`
+/// forks there on purpose to cover how the tool reports it.
+fn avoid_session_boundary(block: u32, epoch_length: Option<u32>) -> u32 {
+    match epoch_length {
+        Some(epoch) if epoch > 0 && block > 0 && block.is_multiple_of(epoch) => {
+            log::info!(
+                "Adjusted fork block #{block} to avoid session boundary (epoch {epoch} blocks)"
+            );
+            block - 1
+        }
+        _ => block,
+    }
+}
+
+/// The latest session boundary at or before `block`, or `None` when no epoch
+/// has ended yet (or the runtime has no epochs).
+pub fn last_session_boundary(block: u32, epoch_length: Option<u32>) -> Option<u32> {
+    let epoch = epoch_length.filter(|&epoch| epoch > 0)?;
+    let boundary = block - block % epoch;
+    (boundary > 0).then_some(boundary)
+}
+
+/// Shared context for governance-only test suites (relay + Asset Hub).
+pub struct GovernanceTestContext {
+    #[allow(dead_code)]
+    pub relay_ws_uri: String,
+    pub asset_hub_ws_uri: String,
+    pub ah_client: OnlineClient<PolkadotConfig>,
+    pub ah_fork_block: u32,
+}
+
+impl GovernanceTestContext {
+    /// Build context from a running zombienet network.
+    /// Waits for block production and finality, connects subxt clients, captures fork blocks.
+    pub async fn from_network(network: &Network<LocalFileSystem>) -> Result<Self> {
+        let alice = network.get_node("alice")?;
+        wait_ready(alice, "Relay").await?;
+
+        let ah_collator = network.get_node("asset-hub-collator")?;
+        wait_ready(ah_collator, "Asset Hub").await?;
+
+        log::info!("Network ready:");
+        log::info!("  Relay (alice): {}", alice.ws_uri());
+        log::info!("  Asset Hub: {}", ah_collator.ws_uri());
+
+        let ah_client = ah_collator
+            .wait_client::<PolkadotConfig>()
+            .await
+            .map_err(|e| anyhow::anyhow!("subxt connect to Asset Hub failed: {e}"))?;
+
+        let ah_fork_block = ah_client.blocks().at_latest().await?.number();
+        log::info!("Asset Hub fork block: #{ah_fork_block}");
+
+        Ok(Self {
+            relay_ws_uri: alice.ws_uri().to_string(),
+            asset_hub_ws_uri: ah_collator.ws_uri().to_string(),
+            ah_client,
+            ah_fork_block,
+        })
+    }
+
+    /// Governance chain endpoint, forked at its fork block.
+    pub fn governance_endpoint(&self) -> ChainEndpoint {
+        labeled_endpoint("AssetHub", &self.asset_hub_ws_uri, self.ah_fork_block)
+    }
+
+    /// Re-fetch the latest block number so Chopsticks doesn't try to fork from
+    /// a block whose state has already been pruned by the zombienet node.
+    pub async fn refresh_fork_blocks(&mut self) -> Result<()> {
+        self.ah_fork_block = self.ah_client.blocks().at_latest().await?.number();
+        log::info!("Refreshed fork blocks: AH=#{}", self.ah_fork_block);
+        Ok(())
+    }
+}
+
+/// Shared context for multi-chain test suites (relay + Asset Hub + Collectives).
+pub struct MultiChainTestContext {
+    pub relay_ws_uri: String,
+    pub asset_hub_ws_uri: String,
+    pub collectives_ws_uri: String,
+    pub ah_client: OnlineClient<PolkadotConfig>,
+    pub coll_client: OnlineClient<PolkadotConfig>,
+    pub relay_client: OnlineClient<PolkadotConfig>,
+    pub ah_fork_block: u32,
+    pub coll_fork_block: u32,
+    pub relay_fork_block: u32,
+}
+
+impl MultiChainTestContext {
+    /// Build context from a running zombienet network.
+    pub async fn from_network(network: &Network<LocalFileSystem>) -> Result<Self> {
+        let alice = network.get_node("alice")?;
+        wait_ready(alice, "Relay").await?;
+
+        let ah_collator = network.get_node("asset-hub-collator")?;
+        wait_ready(ah_collator, "Asset Hub").await?;
+
+        let coll_collator = network.get_node("collectives-collator")?;
+        wait_ready(coll_collator, "Collectives").await?;
+
+        log::info!("Network ready:");
+        log::info!("  Relay (alice): {}", alice.ws_uri());
+        log::info!("  Asset Hub: {}", ah_collator.ws_uri());
+        log::info!("  Collectives: {}", coll_collator.ws_uri());
+
+        let ah_client = ah_collator
+            .wait_client::<PolkadotConfig>()
+            .await
+            .map_err(|e| anyhow::anyhow!("subxt connect to Asset Hub failed: {e}"))?;
+        let coll_client = coll_collator
+            .wait_client::<PolkadotConfig>()
+            .await
+            .map_err(|e| anyhow::anyhow!("subxt connect to Collectives failed: {e}"))?;
+        let relay_client = alice
+            .wait_client::<PolkadotConfig>()
+            .await
+            .map_err(|e| anyhow::anyhow!("subxt connect to relay failed: {e}"))?;
+
+        let ah_fork_block = ah_client.blocks().at_latest().await?.number();
+        let coll_fork_block = coll_client.blocks().at_latest().await?.number();
+        let relay_fork_block = relay_client.blocks().at_latest().await?.number();
+
+        log::info!(
+            "Fork blocks: AH=#{ah_fork_block}, Coll=#{coll_fork_block}, Relay=#{relay_fork_block}"
+        );
+
+        Ok(Self {
+            relay_ws_uri: alice.ws_uri().to_string(),
+            asset_hub_ws_uri: ah_collator.ws_uri().to_string(),
+            collectives_ws_uri: coll_collator.ws_uri().to_string(),
+            ah_client,
+            coll_client,
+            relay_client,
+            ah_fork_block,
+            coll_fork_block,
+            relay_fork_block,
+        })
+    }
+
+    pub fn governance_endpoint(&self) -> ChainEndpoint {
+        labeled_endpoint("AssetHub", &self.asset_hub_ws_uri, self.ah_fork_block)
+    }
+
+    pub fn fellowship_endpoint(&self) -> ChainEndpoint {
+        labeled_endpoint(
+            "Collectives",
+            &self.collectives_ws_uri,
+            self.coll_fork_block,
+        )
+    }
+
+    pub fn relay_endpoint(&self) -> ChainEndpoint {
+        labeled_endpoint("Relay", &self.relay_ws_uri, self.relay_fork_block)
+    }
+
+    /// Re-fetch the latest block numbers so Chopsticks doesn't try to fork from
+    /// blocks whose state has already been pruned by the zombienet nodes.
+    pub async fn refresh_fork_blocks(&mut self) -> Result<()> {
+        self.ah_fork_block = self.ah_client.blocks().at_latest().await?.number();
+        self.coll_fork_block = self.coll_client.blocks().at_latest().await?.number();
+        self.relay_fork_block = self.relay_client.blocks().at_latest().await?.number();
+        log::info!(
+            "Refreshed fork blocks: AH=#{}, Coll=#{}, Relay=#{}",
+            self.ah_fork_block,
+            self.coll_fork_block,
+            self.relay_fork_block
+        );
+        Ok(())
+    }
+}
+
+/// Shared context for Kusama test suites (relay + Asset Hub).
+///
+/// On Kusama, FellowshipReferenda and FellowshipCollective pallets live on the
+/// relay chain itself, not on a separate Collectives parachain. This context
+/// reflects that topology: fellowship_endpoint() returns the relay.
+pub struct KusamaTestContext {
+    pub relay_ws_uri: String,
+    pub asset_hub_ws_uri: String,
+    pub relay_client: OnlineClient<PolkadotConfig>,
+    pub ah_client: OnlineClient<PolkadotConfig>,
+    pub relay_fork_block: u32,
+    pub ah_fork_block: u32,
+    /// Relay epoch length read from the runtime, so both fast and production
+    /// runtimes avoid forking on a session boundary.
+    pub relay_epoch_length: Option<u32>,
+}
+
+impl KusamaTestContext {
+    /// Build context from a running Kusama zombienet network.
+    pub async fn from_network(network: &Network<LocalFileSystem>) -> Result<Self> {
+        let alice = network.get_node("alice")?;
+        wait_ready(alice, "Kusama relay").await?;
+
+        let ah_collator = network.get_node("asset-hub-collator")?;
+        wait_ready(ah_collator, "Kusama Asset Hub").await?;
+
+        log::info!("Kusama network ready:");
+        log::info!("  Relay (alice): {}", alice.ws_uri());
+        log::info!("  Asset Hub: {}", ah_collator.ws_uri());
+
+        let relay_client = alice
+            .wait_client::<PolkadotConfig>()
+            .await
+            .map_err(|e| anyhow::anyhow!("subxt connect to Kusama relay failed: {e}"))?;
+
+        let ah_client = ah_collator
+            .wait_client::<PolkadotConfig>()
+            .await
+            .map_err(|e| anyhow::anyhow!("subxt connect to Kusama Asset Hub failed: {e}"))?;
+
+        let relay_epoch_length = epoch_length(&relay_client);
+        match relay_epoch_length {
+            Some(epoch) => log::info!("Kusama relay epoch length: {epoch} blocks"),
+            None => log::warn!("Kusama relay has no Babe.EpochDuration; not avoiding boundaries"),
+        }
+        let relay_fork_block = avoid_session_boundary(
+            relay_client.blocks().at_latest().await?.number(),
+            relay_epoch_length,
+        );
+        let ah_fork_block = ah_client.blocks().at_latest().await?.number();
+
+        log::info!("Kusama fork blocks: Relay=#{relay_fork_block}, AH=#{ah_fork_block}");
+
+        Ok(Self {
+            relay_ws_uri: alice.ws_uri().to_string(),
+            asset_hub_ws_uri: ah_collator.ws_uri().to_string(),
+            relay_client,
+            ah_client,
+            relay_fork_block,
+            ah_fork_block,
+            relay_epoch_length,
+        })
+    }
+
+    /// Governance chain endpoint (Asset Hub — has Referenda pallet).
+    pub fn governance_endpoint(&self) -> ChainEndpoint {
+        labeled_endpoint("AssetHub", &self.asset_hub_ws_uri, self.ah_fork_block)
+    }
+
+    /// Fellowship chain endpoint (Relay — has FellowshipReferenda pallet on Kusama).
+    pub fn fellowship_endpoint(&self) -> ChainEndpoint {
+        labeled_endpoint("Relay", &self.relay_ws_uri, self.relay_fork_block)
+    }
+
+    /// Fellowship chain endpoint forked exactly at the latest relay session boundary,
+    /// the fork point `avoid_session_boundary` otherwise steers clear of.
+    /// `None` when the relay has no epochs or none has ended yet.
+    pub async fn session_boundary_fellowship_endpoint(&self) -> Result<Option<ChainEndpoint>> {
+        let latest = self.relay_client.blocks().at_latest().await?.number();
+        Ok(
+            last_session_boundary(latest, self.relay_epoch_length).map(|boundary| {
+                log::info!(
+                    "Forking Kusama relay at session boundary #{boundary} (latest #{latest})"
+                );
+                labeled_endpoint("Relay", &self.relay_ws_uri, boundary)
+            }),
+        )
+    }
+
+    /// Re-fetch the latest block numbers so Chopsticks doesn't try to fork from
+    /// blocks whose state has already been pruned by the zombienet nodes.
+    pub async fn refresh_fork_blocks(&mut self) -> Result<()> {
+        self.relay_fork_block = avoid_session_boundary(
+            self.relay_client.blocks().at_latest().await?.number(),
+            self.relay_epoch_length,
+        );
+        self.ah_fork_block = self.ah_client.blocks().at_latest().await?.number();
+
+        log::info!(
+            "Refreshed Kusama fork blocks: Relay=#{}, AH=#{}",
+            self.relay_fork_block,
+            self.ah_fork_block
+        );
+        Ok(())
+    }
+}
+
+/// Shared context for the topology permutation suite: the Kusama network plus
+/// a Collectives parachain (see `config::build_kusama_with_collectives`), so
+/// fellowship pallets live both on the relay and on a parachain.
+pub struct TopologyPermutationContext {
+    pub kusama: KusamaTestContext,
+    pub collectives_ws_uri: String,
+    pub coll_client: OnlineClient<PolkadotConfig>,
+    pub coll_fork_block: u32,
+}
+
+impl TopologyPermutationContext {
+    /// Build context from a running Kusama + Collectives zombienet network.
+    pub async fn from_network(network: &Network<LocalFileSystem>) -> Result<Self> {
+        let kusama = KusamaTestContext::from_network(network).await?;
+
+        let coll_collator = network.get_node("collectives-collator")?;
+        wait_ready(coll_collator, "Collectives").await?;
+        log::info!("  Collectives: {}", coll_collator.ws_uri());
+
+        let coll_client = coll_collator
+            .wait_client::<PolkadotConfig>()
+            .await
+            .map_err(|e| anyhow::anyhow!("subxt connect to Collectives failed: {e}"))?;
+        let coll_fork_block = coll_client.blocks().at_latest().await?.number();
+        log::info!("Collectives fork block: #{coll_fork_block}");
+
+        Ok(Self {
+            kusama,
+            collectives_ws_uri: coll_collator.ws_uri().to_string(),
+            coll_client,
+            coll_fork_block,
+        })
+    }
+
+    /// Fellowship chain endpoint on the relay (Kusama layout).
+    pub fn relay_fellowship_endpoint(&self) -> ChainEndpoint {
+        self.kusama.fellowship_endpoint()
+    }
+
+    /// Fellowship chain endpoint on the Collectives parachain (Polkadot layout).
+    pub fn parachain_fellowship_endpoint(&self) -> ChainEndpoint {
+        labeled_endpoint(
+            "Collectives",
+            &self.collectives_ws_uri,
+            self.coll_fork_block,
+        )
+    }
+
+    /// Re-fetch the latest block numbers on every chain.
+    pub async fn refresh_fork_blocks(&mut self) -> Result<()> {
+        self.kusama.refresh_fork_blocks().await?;
+        self.coll_fork_block = self.coll_client.blocks().at_latest().await?.number();
+        log::info!(
+            "Refreshed Collectives fork block: #{}",
+            self.coll_fork_block
+        );
+        Ok(())
+    }
+}
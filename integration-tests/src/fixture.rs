@@ -0,0 +1,211 @@
+//! Fixtures for testing proposals kept in other repositories.
+//!
+//! Proposal repositories keep their calls as hex files. [`GovernanceNetwork`]
+//! spawns one of the zombienet topologies used by this crate's own suites,
+//! and [`Proposal`] turns call-data files into a [`Simulation`] against it,
+//! so a proposal repo's test is a few lines:
+//!
+//! ```no_run
+//! # async fn example() -> anyhow::Result<()> {
+//! use polkadot_referenda_tester_integration_tests::fixture::{
+//!     GovernanceNetwork, Proposal, Topology,
+//! };
+//!
+//! let mut network = GovernanceNetwork::spawn(Topology::PolkadotAssetHub).await?;
+//! let proposal = Proposal::governance("proposals/0042/submit.hex")?
+//!     .with_preimage("proposals/0042/preimage.hex")?;
+//! network.simulate(&proposal).await?.run().await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The same [`Proposal`] can be simulated on a fork of a live chain with
+//! [`Proposal::simulation`]. Binaries, runtimes and the tool checkout are
+//! found through the same env vars as for the integration tests
+//! (`POLKADOT_BINARY_PATH`, `FAST_RUNTIMES_DIR`, `TOOL_PROJECT_DIR`, ...).
+//!
+//! This module is the crate's stable interface for external repositories:
+//! its items only change in backwards-compatible ways.
+
+use anyhow::{bail, Context, Result};
+use std::path::Path;
+use zombienet_sdk::{LocalFileSystem, Network};
+
+use crate::config;
+use crate::context::{GovernanceTestContext, KusamaTestContext, MultiChainTestContext};
+use crate::network::{initialize_network, verify_binaries};
+use crate::port_allocator;
+use crate::simulation::{Simulation, SimulationConfig};
+use crate::tool_args::normalize_call_data;
+
+/// The zombienet networks a [`GovernanceNetwork`] can spawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Topology {
+    /// Polkadot relay + Asset Hub + Collectives: governance and fellowship.
+    Polkadot,
+    /// Polkadot relay + Asset Hub only: governance, and faster to start.
+    PolkadotAssetHub,
+    /// Kusama relay + Asset Hub: governance on Asset Hub, fellowship on the relay.
+    Kusama,
+}
+
+/// Which referenda a [`Proposal`] is submitted to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Referenda {
+    Governance,
+    Fellowship,
+}
+
+/// A proposal read from call-data files: the call that creates its
+/// referendum and, optionally, the call that notes its preimage.
+#[derive(Debug, Clone)]
+pub struct Proposal {
+    pub referenda: Referenda,
+    pub submit_call: String,
+    pub preimage_call: Option<String>,
+}
+
+/// Read a hex call-data file as the tool reads its call-data flags: whitespace
+/// is ignored and the `0x` prefix is optional.
+fn read_call_data(path: &Path) -> Result<String> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read call data from {}", path.display()))?;
+    normalize_call_data(&path.display().to_string(), &text)
+}
+
+impl Proposal {
+    /// A governance proposal created by the call in `submit_call_file`.
+    pub fn governance(submit_call_file: impl AsRef<Path>) -> Result<Self> {
+        Ok(Self {
+            referenda: Referenda::Governance,
+            submit_call: read_call_data(submit_call_file.as_ref())?,
+            preimage_call: None,
+        })
+    }
+
+    /// A fellowship proposal created by the call in `submit_call_file`.
+    pub fn fellowship(submit_call_file: impl AsRef<Path>) -> Result<Self> {
+        Ok(Self {
+            referenda: Referenda::Fellowship,
+            submit_call: read_call_data(submit_call_file.as_ref())?,
+            preimage_call: None,
+        })
+    }
+
+    /// Note the preimage with the call in `preimage_call_file` before submitting.
+    pub fn with_preimage(mut self, preimage_call_file: impl AsRef<Path>) -> Result<Self> {
+        self.preimage_call = Some(read_call_data(preimage_call_file.as_ref())?);
+        Ok(self)
+    }
+
+    /// Simulate the proposal on a fork of `chain_url` (`url` or `url,block`),
+    /// which must host its referenda pallet.
+    pub fn simulation(&self, chain_url: impl Into<String>) -> Simulation {
+        Simulation::new(self.to_config(chain_url))
+    }
+
+    /// The [`SimulationConfig`] behind [`Proposal::simulation`], for setting
+    /// further flags (e.g. `track_balance`) before running it.
+    pub fn to_config(&self, chain_url: impl Into<String>) -> SimulationConfig {
+        let chain_url = Some(chain_url.into());
+        match self.referenda {
+            Referenda::Governance => SimulationConfig {
+                governance_chain_url: chain_url,
+                call_to_create_governance_referendum: Some(self.submit_call.clone()),
+                call_to_note_preimage_for_governance_referendum: self.preimage_call.clone(),
+                ..Default::default()
+            },
+            Referenda::Fellowship => SimulationConfig {
+                fellowship_chain_url: chain_url,
+                call_to_create_fellowship_referendum: Some(self.submit_call.clone()),
+                call_to_note_preimage_for_fellowship_referendum: self.preimage_call.clone(),
+                ..Default::default()
+            },
+        }
+    }
+}
+
+enum TopologyContext {
+    Polkadot(MultiChainTestContext),
+    PolkadotAssetHub(GovernanceTestContext),
+    Kusama(KusamaTestContext),
+}
+
+/// A running zombienet network to simulate proposals on. The network is
+/// stopped when this is dropped.
+pub struct GovernanceNetwork {
+    context: TopologyContext,
+    // Kept alive for as long as the fixture; never read.
+    _network: Network<LocalFileSystem>,
+}
+
+impl GovernanceNetwork {
+    /// Spawn `topology` and wait until its chains produce blocks.
+    pub async fn spawn(topology: Topology) -> Result<Self> {
+        verify_binaries()?;
+        let network_config = match topology {
+            Topology::Polkadot => config::build_polkadot_with_system_parachains()?,
+            Topology::PolkadotAssetHub => config::build_polkadot_with_asset_hub()?,
+            Topology::Kusama => config::build_kusama_with_asset_hub()?,
+        };
+        let network = initialize_network(network_config)
+            .await
+            .with_context(|| format!("failed to spawn {topology:?} network"))?;
+        let context = match topology {
+            Topology::Polkadot => {
+                TopologyContext::Polkadot(MultiChainTestContext::from_network(&network).await?)
+            }
+            Topology::PolkadotAssetHub => TopologyContext::PolkadotAssetHub(
+                GovernanceTestContext::from_network(&network).await?,
+            ),
+            Topology::Kusama => {
+                TopologyContext::Kusama(KusamaTestContext::from_network(&network).await?)
+            }
+        };
+        Ok(Self {
+            context,
+            _network: network,
+        })
+    }
+
+    /// The chain hosting `referenda`, as a `--*-chain-url` value pinned to
+    /// its latest block.
+    pub async fn chain_url(&mut self, referenda: Referenda) -> Result<String> {
+        match (&mut self.context, referenda) {
+            (TopologyContext::Polkadot(ctx), Referenda::Governance) => {
+                ctx.refresh_fork_blocks().await?;
+                Ok(ctx.governance_endpoint().to_arg())
+            }
+            (TopologyContext::Polkadot(ctx), Referenda::Fellowship) => {
+                ctx.refresh_fork_blocks().await?;
+                Ok(ctx.fellowship_endpoint().to_arg())
+            }
+            (TopologyContext::PolkadotAssetHub(ctx), Referenda::Governance) => {
+                ctx.refresh_fork_blocks().await?;
+                Ok(ctx.governance_endpoint().to_arg())
+            }
+            (TopologyContext::PolkadotAssetHub(_), Referenda::Fellowship) => {
+                bail!("Topology::PolkadotAssetHub has no fellowship; use Topology::Polkadot")
+            }
+            (TopologyContext::Kusama(ctx), Referenda::Governance) => {
+                ctx.refresh_fork_blocks().await?;
+                Ok(ctx.governance_endpoint().to_arg())
+            }
+            (TopologyContext::Kusama(ctx), Referenda::Fellowship) => {
+                ctx.refresh_fork_blocks().await?;
+                Ok(ctx.fellowship_endpoint().to_arg())
+            }
+        }
+    }
+
+    /// Simulate `proposal` on a fork of the chain hosting its referenda, at
+    /// that chain's latest block and on ports of its own. Add `on_block`
+    /// callbacks to the returned [`Simulation`] before running it.
+    pub async fn simulate(&mut self, proposal: &Proposal) -> Result<Simulation> {
+        let chain_url = self.chain_url(proposal.referenda).await?;
+        Ok(Simulation::new(SimulationConfig {
+            port_range: Some(port_allocator::next_tool_range().to_arg()),
+            ..proposal.to_config(chain_url)
+        }))
+    }
+}
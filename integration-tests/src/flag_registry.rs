@@ -0,0 +1,214 @@
+//! CLI flag registry shared with the tool.
+//!
+//! `src/flag-rules.json` is the single source of truth for the tool's flag
+//! mutual-exclusion, at-least-one-of, dependency and integer-value rules. The
+//! tool enforces it via `src/utils/flag-rules.ts`; this module turns every rule
+//! into a validation case so `validation_test_suite` can't drift from the CLI,
+//! and [`check`] applies the same rules to `ToolArgs::builder` arguments.
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde_json::Value;
+
+use crate::tool_args::ToolArgs;
+
+/// The rule table, embedded at compile time from the TypeScript sources.
+const FLAG_RULES_JSON: &str = include_str!("../../src/flag-rules.json");
+
+/// One generated validation sub-test: invoke the tool with `args` and expect
+/// it to fail with error code `code` and `expected` somewhere in its output.
+pub struct ValidationCase {
+    pub name: String,
+    pub args: ToolArgs,
+    pub code: String,
+    pub expected: String,
+}
+
+fn rules() -> Result<Value> {
+    serde_json::from_str(FLAG_RULES_JSON).context("src/flag-rules.json is not valid JSON")
+}
+
+fn str_field<'a>(value: &'a Value, key: &str) -> Result<&'a str> {
+    value[key]
+        .as_str()
+        .with_context(|| format!("flag rule is missing string field '{key}': {value}"))
+}
+
+fn str_list(value: &Value, key: &str) -> Result<Vec<String>> {
+    value[key]
+        .as_array()
+        .with_context(|| format!("flag rule is missing list field '{key}': {value}"))?
+        .iter()
+        .map(|v| {
+            v.as_str()
+                .map(str::to_string)
+                .context("flag list entries must be strings")
+        })
+        .collect()
+}
+
+/// Sample (valid) value for `flag` from the table.
+fn sample(rules: &Value, flag: &str) -> Result<String> {
+    let definition = &rules["flags"][flag];
+    Ok(str_field(definition, "sample")
+        .with_context(|| format!("unknown flag {flag}"))?
+        .to_string())
+}
+
+/// Set every flag that `requires` rules demand once `flag` is present, so a
+/// case only trips the rule it is testing.
+fn satisfy_dependencies(rules: &Value, args: &mut ToolArgs, flag: &str) -> Result<()> {
+    for rule in rules["requires"].as_array().into_iter().flatten() {
+        if str_list(rule, "whenAny")?.iter().any(|f| f == flag) {
+            let required = str_field(rule, "requires")?;
+            args.set_flag(required, &sample(rules, required)?)?;
+        }
+    }
+    Ok(())
+}
+
+/// Give `args` a referendum for every `atLeastOneOf` rule it does not satisfy
+/// yet, so a `requires` case whose trigger is not a referendum flag still gets
+/// past that rule.
+fn satisfy_at_least_one_of(rules: &Value, args: &mut ToolArgs) -> Result<()> {
+    for rule in rules["atLeastOneOf"].as_array().into_iter().flatten() {
+        let set = str_list(rule, "flags")?;
+        let flags = args.flags();
+        if set.iter().any(|f| flags.iter().any(|(flag, _)| flag == f)) {
+            continue;
+        }
+        let flag = set
+            .first()
+            .context("atLeastOneOf rule has an empty flag list")?;
+        args.set_flag(flag, &sample(rules, flag)?)?;
+        satisfy_dependencies(rules, args, flag)?;
+    }
+    Ok(())
+}
+
+/// Generate one validation case per rule in the table.
+pub fn validation_cases() -> Result<Vec<ValidationCase>> {
+    let rules = rules()?;
+    let mut cases = Vec::new();
+
+    for rule in rules["mutuallyExclusive"].as_array().into_iter().flatten() {
+        let mut args = ToolArgs::default();
+        for flag in str_list(rule, "flags")? {
+            args.set_flag(&flag, &sample(&rules, &flag)?)?;
+            satisfy_dependencies(&rules, &mut args, &flag)?;
+        }
+        cases.push(ValidationCase {
+            name: str_field(rule, "name")?.to_string(),
+            args,
+            code: str_field(rule, "code")?.to_string(),
+            expected: str_field(rule, "message")?.to_string(),
+        });
+    }
+
+    for rule in rules["atLeastOneOf"].as_array().into_iter().flatten() {
+        cases.push(ValidationCase {
+            name: str_field(rule, "name")?.to_string(),
+            args: ToolArgs::default(),
+            code: str_field(rule, "code")?.to_string(),
+            expected: str_field(rule, "message")?.to_string(),
+        });
+    }
+
+    for rule in rules["requires"].as_array().into_iter().flatten() {
+        let trigger = str_list(rule, "whenAny")?
+            .into_iter()
+            .next()
+            .context("requires rule has an empty whenAny list")?;
+        let mut args = ToolArgs::default();
+        args.set_flag(&trigger, &sample(&rules, &trigger)?)?;
+        satisfy_at_least_one_of(&rules, &mut args)?;
+        cases.push(ValidationCase {
+            name: str_field(rule, "name")?.to_string(),
+            args,
+            code: str_field(rule, "code")?.to_string(),
+            expected: str_field(rule, "message")?.to_string(),
+        });
+    }
+
+    for (flag, definition) in rules["flags"].as_object().into_iter().flatten() {
+        let integer = &definition["integer"];
+        if integer.is_null() {
+            continue;
+        }
+        let invalid = str_field(integer, "invalidSample")?;
+        let mut args = ToolArgs::default();
+        args.set_flag(flag, invalid)?;
+        satisfy_dependencies(&rules, &mut args, flag)?;
+        cases.push(ValidationCase {
+            name: str_field(integer, "name")?.to_string(),
+            args,
+            code: str_field(integer, "code")?.to_string(),
+            expected: str_field(integer, "message")?.replace("{value}", invalid),
+        });
+    }
+
+    Ok(cases)
+}
+
+/// Apply the table to `args` in the tool's order (mutual exclusion,
+/// at-least-one-of, dependencies, integer values), failing with the tool's
+/// `E-CODE: message` for the first broken rule.
+pub fn check(args: &ToolArgs) -> Result<()> {
+    let rules = rules()?;
+    let flags = args.flags();
+    let value_of = |flag: &str| {
+        flags
+            .iter()
+            .find(|(f, _)| *f == flag)
+            .map(|(_, value)| value.as_deref().unwrap_or_default())
+    };
+    let broken = |rule: &Value| -> Result<anyhow::Error> {
+        Ok(anyhow!(
+            "{}: {}",
+            str_field(rule, "code")?,
+            str_field(rule, "message")?
+        ))
+    };
+
+    for rule in rules["mutuallyExclusive"].as_array().into_iter().flatten() {
+        let set = str_list(rule, "flags")?;
+        if set.iter().filter(|f| value_of(f).is_some()).count() > 1 {
+            return Err(broken(rule)?);
+        }
+    }
+
+    for rule in rules["atLeastOneOf"].as_array().into_iter().flatten() {
+        let set = str_list(rule, "flags")?;
+        if set.iter().all(|f| value_of(f).is_none()) {
+            return Err(broken(rule)?);
+        }
+    }
+
+    for rule in rules["requires"].as_array().into_iter().flatten() {
+        let triggers = str_list(rule, "whenAny")?;
+        let triggered = triggers.iter().any(|f| value_of(f).is_some());
+        if triggered && value_of(str_field(rule, "requires")?).is_none() {
+            return Err(broken(rule)?);
+        }
+    }
+
+    for (flag, definition) in rules["flags"].as_object().into_iter().flatten() {
+        let integer = &definition["integer"];
+        let Some(value) = value_of(flag).filter(|_| !integer.is_null()) else {
+            continue;
+        };
+        // `palletPrefix` flags also take `<pallet>:<id>`.
+        let id = match integer["palletPrefix"].as_bool() {
+            Some(true) => value.rsplit_once(':').map_or(value, |(_, id)| id),
+            _ => value,
+        };
+        if id.parse::<u32>().is_err() {
+            bail!(
+                "{}: {}",
+                str_field(integer, "code")?,
+                str_field(integer, "message")?.replace("{value}", value)
+            );
+        }
+    }
+
+    Ok(())
+}
@@ -1 +1,17 @@
-// This crate is a test-only crate. All logic lives in tests/.
+//! Library half of the integration-tests crate. The test suites live in
+//! tests/; this exposes the [`simulation`] API for embedding the tool in other
+//! Rust programs, and [`fixture`]s for testing proposals from other
+//! repositories against the same zombienet networks, with the topologies
+//! ([`config`], [`network`], [`context`]), [`tool_args`] and [`logging`] both
+//! build on.
+
+pub mod config;
+pub mod context;
+pub mod fixture;
+pub mod flag_registry;
+pub mod logging;
+pub mod network;
+pub mod port_allocator;
+pub mod raw_storage;
+pub mod simulation;
+pub mod tool_args;
@@ -0,0 +1,221 @@
+//! Logging setup: console output plus one log file per sub-test.
+//!
+//! A `tracing` subscriber replaces `env_logger`. Existing `log::` macros are
+//! bridged into it, so call sites don't change. Two layers are installed:
+//!
+//! - **console** — filtered by `RUST_LOG`, exactly like `env_logger` was.
+//! - **per-sub-test files** — every event emitted inside a `sub_test` span
+//!   (opened by `run_and_bail!`) is appended to
+//!   `<artifacts>/<suite>/logs/<sub_test>.log` at DEBUG level for this crate
+//!   and INFO for dependencies, independent of `RUST_LOG`. The tool's stdout
+//!   and stderr are logged by `ToolRunner` inside that span, so each file
+//!   holds the harness log and the tool output of one sub-test only.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::fs::File;
+use std::future::Future;
+use std::io::Write as _;
+use std::sync::{Mutex, OnceLock};
+
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id};
+use tracing::{Event, Level, Subscriber};
+use tracing_log::NormalizeEvent;
+use tracing_subscriber::filter::{EnvFilter, LevelFilter};
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::Layer;
+
+use crate::config::get_artifacts_dir;
+
+/// Name of the span opened around each sub-test.
+pub const SUB_TEST_SPAN: &str = "sub_test";
+
+tokio::task_local! {
+    /// Suite running on this task. Set by [`suite`], so concurrent suites on
+    /// shared worker threads each log into their own files.
+    static CURRENT_SUITE: String;
+}
+
+/// Suite name recorded by the last `init()` call. Used outside any [`suite`]
+/// scope, e.g. by a binary that runs a single suite.
+static LAST_SUITE: Mutex<String> = Mutex::new(String::new());
+static INIT: OnceLock<()> = OnceLock::new();
+
+/// Run a suite's body with its name in scope for [`current_suite`].
+pub async fn suite<F: Future>(name: &str, body: F) -> F::Output {
+    init(name);
+    CURRENT_SUITE.scope(name.to_string(), body).await
+}
+
+/// Keep the current suite in scope for `future`, which is about to be spawned
+/// onto another task.
+pub fn in_current_suite<F: Future>(future: F) -> impl Future<Output = F::Output> {
+    CURRENT_SUITE.scope(current_suite(), future)
+}
+
+/// Install the global subscriber (once per process) and remember `suite`.
+pub fn init(suite: &str) {
+    *LAST_SUITE.lock().expect("suite lock poisoned") = suite.to_string();
+    INIT.get_or_init(|| {
+        let console = tracing_subscriber::fmt::layer()
+            .with_writer(std::io::stderr)
+            .with_filter(EnvFilter::from_default_env());
+        let _ = tracing_subscriber::registry()
+            .with(console)
+            .with(SubTestFileLayer::default())
+            .try_init();
+    });
+}
+
+/// Suite running on the current task, or the last one initialized outside a
+/// [`suite`] scope.
+pub fn current_suite() -> String {
+    CURRENT_SUITE
+        .try_with(|suite| suite.clone())
+        .unwrap_or_else(|_| LAST_SUITE.lock().expect("suite lock poisoned").clone())
+}
+
+/// Span that routes all events emitted inside it to the sub-test's log file.
+pub fn sub_test_span(label: &str) -> tracing::Span {
+    tracing::info_span!(SUB_TEST_SPAN, suite = %current_suite(), name = %label)
+}
+
+// ── Per-sub-test file layer ──────────────────────────────────────────────────
+
+/// Identity of a sub-test span, stored in its span extensions.
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct SubTestKey {
+    suite: String,
+    name: String,
+}
+
+#[derive(Default)]
+struct SubTestFileLayer {
+    files: Mutex<HashMap<SubTestKey, File>>,
+}
+
+impl SubTestFileLayer {
+    fn open(&self, key: &SubTestKey) {
+        let mut files = self.files.lock().expect("log files lock poisoned");
+        if files.contains_key(key) {
+            return;
+        }
+        let dir = get_artifacts_dir(&key.suite).join("logs");
+        let path = dir.join(format!("{}.log", key.name));
+        match std::fs::create_dir_all(&dir).and_then(|_| File::create(&path)) {
+            Ok(file) => {
+                files.insert(key.clone(), file);
+            }
+            Err(e) => eprintln!("Failed to create sub-test log {}: {e}", path.display()),
+        }
+    }
+
+    fn write(&self, key: &SubTestKey, line: &str) {
+        let mut files = self.files.lock().expect("log files lock poisoned");
+        if let Some(file) = files.get_mut(key) {
+            let _ = file.write_all(line.as_bytes());
+        }
+    }
+}
+
+/// This crate (the test binaries and the library) logs at DEBUG into the
+/// files; dependencies at INFO.
+fn file_level_enabled(target: &str, level: &Level) -> bool {
+    let own = target.starts_with("tests")
+        || target.starts_with("generate_chain_specs")
+        || target.starts_with(env!("CARGO_CRATE_NAME"));
+    if own {
+        *level <= Level::DEBUG
+    } else {
+        *level <= Level::INFO
+    }
+}
+
+impl<S> Layer<S> for SubTestFileLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        if attrs.metadata().name() != SUB_TEST_SPAN {
+            return;
+        }
+        let mut visitor = FieldCollector::default();
+        attrs.record(&mut visitor);
+        let key = SubTestKey {
+            suite: visitor.get("suite"),
+            name: visitor.get("name"),
+        };
+        self.open(&key);
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(key);
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let Some(scope) = ctx.event_scope(event) else {
+            return;
+        };
+        let Some(key) = scope
+            .from_root()
+            .find_map(|span| span.extensions().get::<SubTestKey>().cloned())
+        else {
+            return;
+        };
+
+        let normalized = event.normalized_metadata();
+        let metadata = normalized.as_ref().unwrap_or_else(|| event.metadata());
+        if !file_level_enabled(metadata.target(), metadata.level()) {
+            return;
+        }
+
+        let mut visitor = FieldCollector::default();
+        event.record(&mut visitor);
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs_f64())
+            .unwrap_or_default();
+        let line = format!(
+            "{timestamp:.3} {:>5} {}: {}\n",
+            metadata.level(),
+            metadata.target(),
+            visitor.get("message")
+        );
+        self.write(&key, &line);
+    }
+
+    fn max_level_hint(&self) -> Option<LevelFilter> {
+        Some(LevelFilter::DEBUG)
+    }
+}
+
+/// Collects span/event fields as strings, skipping `log.*` bridge metadata.
+#[derive(Default)]
+struct FieldCollector {
+    fields: HashMap<String, String>,
+}
+
+impl FieldCollector {
+    fn get(&self, name: &str) -> String {
+        self.fields.get(name).cloned().unwrap_or_default()
+    }
+}
+
+impl Visit for FieldCollector {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if !field.name().starts_with("log.") {
+            self.fields
+                .insert(field.name().to_string(), value.to_string());
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if !field.name().starts_with("log.") {
+            let mut s = String::new();
+            let _ = write!(s, "{value:?}");
+            self.fields.insert(field.name().to_string(), s);
+        }
+    }
+}
@@ -2,7 +2,7 @@ use anyhow::{Context, Result};
 use std::path::PathBuf;
 use zombienet_sdk::{LocalFileSystem, Network, NetworkConfig, NetworkConfigExt};
 
-use super::config::*;
+use crate::config::*;
 
 /// Spawn a zombienet network using the native provider (local binaries, no Docker).
 pub async fn initialize_network(config: NetworkConfig) -> Result<Network<LocalFileSystem>> {
@@ -0,0 +1,90 @@
+//! Port allocator for test isolation.
+//!
+//! Each tool invocation gets its own block of ports from `next_tool_range()`,
+//! passed to the tool as `--port-range start:len`. The tool pins every forked
+//! chain to a port inside the block and fails fast (`E-PORT-UNAVAILABLE`) if
+//! any of them is already taken, so a collision is an error, never a silent
+//! cross-talk between concurrent runs.
+//!
+//! Zombienet networks get their own block of ports from `next_network_range()`,
+//! so the topologies `generate_chain_specs` spawns at once don't collide.
+//!
+//! Uses monotonically increasing global counters — no resets, so port ranges
+//! never overlap even if test suites run in parallel.
+
+use std::cell::Cell;
+use std::sync::atomic::{AtomicU16, Ordering};
+
+/// First port handed to tool invocations.
+const TOOL_PORT_BASE: u16 = 9000;
+
+/// Ports reserved per tool invocation: one per forked chain, with headroom for
+/// governance + fellowship + relay + additional chains.
+pub const TOOL_PORT_RANGE_LEN: u16 = 10;
+
+static NEXT_TOOL_PORT: AtomicU16 = AtomicU16::new(TOOL_PORT_BASE);
+
+/// First port handed to zombienet networks: far above the Chopsticks ports and
+/// below the Linux ephemeral range (32768+).
+const NETWORK_PORT_BASE: u16 = 20000;
+
+/// Ports reserved per network (RPC + P2P + Prometheus for each node).
+pub const NETWORK_PORT_RANGE_LEN: u16 = 100;
+
+static NEXT_NETWORK_PORT: AtomicU16 = AtomicU16::new(NETWORK_PORT_BASE);
+
+/// A contiguous block of reserved ports, for one zombienet network or one
+/// tool invocation.
+pub struct PortRange {
+    pub start: u16,
+    pub len: u16,
+    next: Cell<u16>,
+}
+
+impl PortRange {
+    /// One past the last port in the range.
+    pub fn end(&self) -> u16 {
+        self.start + self.len
+    }
+
+    /// The range as the tool's `--port-range` value (`start:len`).
+    pub fn to_arg(&self) -> String {
+        format!("{}:{}", self.start, self.len)
+    }
+
+    /// Hand out the next port of the range.
+    ///
+    /// Panics if the range is exhausted — that means a topology grew past
+    /// `NETWORK_PORT_RANGE_LEN / 3` nodes and the constant needs raising.
+    pub fn take(&self) -> u16 {
+        let port = self.next.get();
+        assert!(
+            port < self.end(),
+            "network port range {}..{} exhausted",
+            self.start,
+            self.end()
+        );
+        self.next.set(port + 1);
+        port
+    }
+}
+
+/// Reserve the next block of `TOOL_PORT_RANGE_LEN` ports for one tool run.
+pub fn next_tool_range() -> PortRange {
+    let start = NEXT_TOOL_PORT.fetch_add(TOOL_PORT_RANGE_LEN, Ordering::Relaxed);
+    PortRange {
+        start,
+        len: TOOL_PORT_RANGE_LEN,
+        next: Cell::new(start),
+    }
+}
+
+/// Reserve the next block of `NETWORK_PORT_RANGE_LEN` ports for a network.
+pub fn next_network_range() -> PortRange {
+    let start = NEXT_NETWORK_PORT.fetch_add(NETWORK_PORT_RANGE_LEN, Ordering::Relaxed);
+    PortRange {
+        start,
+        len: NETWORK_PORT_RANGE_LEN,
+        next: Cell::new(start),
+    }
+}
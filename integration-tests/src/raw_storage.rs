@@ -0,0 +1,257 @@
+//! Raw storage key computation for zombienet genesis injection via `with_raw_spec_override()`.
+//!
+//! Computes hex-encoded storage keys and SCALE-encoded values for:
+//! - `AhMigrator::AhMigrationStage` → `MigrationDone` (unlocks BaseCallFilter on Asset Hub)
+//! - `Whitelist::WhitelistedCall` → a pre-whitelisted `System.authorize_upgrade` on Polkadot Asset Hub
+//! - `FellowshipCollective::{Members, IdToIndex, IndexToId, MemberCount}` (registers Alice as fellow)
+//! - `AmbassadorCollective::{...}`, the same entries (registers Alice as ambassador)
+//!
+//! These are injected into `genesis.raw.top` so that by-number tests can submit
+//! referenda directly to live zombienet nodes. Each override is built from
+//! [`RawEntry`]s, which `override_audit` checks against chain specs, runtime
+//! metadata and genesis state.
+
+use serde_json::{json, Value};
+use sp_crypto_hashing::{blake2_256, twox_128, twox_64};
+use std::fmt;
+
+/// Alice's raw AccountId (Sr25519 public key bytes).
+const ALICE_ACCOUNT_ID: [u8; 32] = [
+    0xd4, 0x35, 0x93, 0xc7, 0x15, 0xfd, 0xd3, 0x1c, 0x61, 0x14, 0x1a, 0xbd, 0x04, 0xa9, 0x9f, 0xd6,
+    0x82, 0x2c, 0x85, 0x58, 0x85, 0x4c, 0xcd, 0xe3, 0x9a, 0x56, 0x84, 0xe7, 0xa5, 0x6d, 0xa2, 0x7d,
+];
+
+/// The rank to assign Alice in a ranked collective (covers all tracks up to
+/// Fellowship9Dan and MasterAmbassadorTier9).
+const ALICE_COLLECTIVE_RANK: u16 = 9;
+
+// ─── Storage key primitives ──────────────────────────────────────────────────
+
+/// Compute the 32-byte storage prefix for a pallet + item (two twox_128 hashes).
+fn storage_prefix(pallet: &str, item: &str) -> Vec<u8> {
+    let mut key = Vec::with_capacity(32);
+    key.extend_from_slice(&twox_128(pallet.as_bytes()));
+    key.extend_from_slice(&twox_128(item.as_bytes()));
+    key
+}
+
+/// Twox64Concat transparent hash: `twox_64(data) ++ data`.
+fn twox64_concat(data: &[u8]) -> Vec<u8> {
+    let hash = twox_64(data);
+    let mut result = Vec::with_capacity(8 + data.len());
+    result.extend_from_slice(&hash);
+    result.extend_from_slice(data);
+    result
+}
+
+/// Hex-encode bytes with `0x` prefix.
+fn to_hex(bytes: &[u8]) -> String {
+    format!("0x{}", hex::encode(bytes))
+}
+
+/// One storage entry written by an override, kept in logical form so its key
+/// and value can be audited.
+pub struct RawEntry {
+    pub pallet: &'static str,
+    pub item: &'static str,
+    /// SCALE-encoded map keys, one per Twox64Concat hasher; empty for a plain value.
+    pub keys: Vec<Vec<u8>>,
+    /// SCALE-encoded value.
+    pub value: Vec<u8>,
+}
+
+impl RawEntry {
+    /// `pallet.item[keys...] = value`, from SCALE-encoded keys (each hashed with
+    /// Twox64Concat) and value.
+    fn map(pallet: &'static str, item: &'static str, keys: &[&[u8]], value: &[u8]) -> Self {
+        Self {
+            pallet,
+            item,
+            keys: keys.iter().map(|key| key.to_vec()).collect(),
+            value: value.to_vec(),
+        }
+    }
+
+    /// A plain `pallet.item = value`.
+    fn plain(pallet: &'static str, item: &'static str, value: &[u8]) -> Self {
+        Self::map(pallet, item, &[], value)
+    }
+
+    /// The full storage key: pallet + item prefix, then each key Twox64Concat-hashed.
+    pub fn key_bytes(&self) -> Vec<u8> {
+        let mut key = storage_prefix(self.pallet, self.item);
+        for part in &self.keys {
+            key.extend_from_slice(&twox64_concat(part));
+        }
+        key
+    }
+
+    pub fn key(&self) -> String {
+        to_hex(&self.key_bytes())
+    }
+
+    pub fn value_hex(&self) -> String {
+        to_hex(&self.value)
+    }
+}
+
+impl fmt::Display for RawEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.pallet, self.item)?;
+        if !self.keys.is_empty() {
+            let keys: Vec<_> = self.keys.iter().map(|k| to_hex(k)).collect();
+            write!(f, "[{}]", keys.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Wrap entries into the full raw spec override structure (`genesis.raw.top`).
+fn build_raw_override(entries: &[RawEntry]) -> Value {
+    let top: serde_json::Map<String, Value> = entries
+        .iter()
+        .map(|entry| (entry.key(), Value::String(entry.value_hex())))
+        .collect();
+    json!({
+        "genesis": {
+            "raw": {
+                "top": top
+            }
+        }
+    })
+}
+
+/// The override entries `config`'s network builders apply to a chain spec,
+/// by zombienet chain name.
+pub fn chain_override_entries(chain: &str) -> Vec<RawEntry> {
+    match chain {
+        "asset-hub-polkadot-local" => asset_hub_polkadot_entries(),
+        "asset-hub-kusama-local" => ah_migrator_entries(),
+        "collectives-polkadot-local" => collectives_entries(),
+        "kusama-local" => fellowship_collective_entries(),
+        _ => Vec::new(),
+    }
+}
+
+// ─── AhMigrator ──────────────────────────────────────────────────────────────
+
+/// `AhMigrator::AhMigrationStage = MigrationDone`.
+///
+/// `MigrationDone` is enum variant index 2, SCALE-encoded as `0x02`.
+/// This unlocks Asset Hub's `BaseCallFilter`, allowing `Referenda.submit`.
+pub fn ah_migrator_entries() -> Vec<RawEntry> {
+    vec![RawEntry::plain("AhMigrator", "AhMigrationStage", &[0x02])]
+}
+
+/// Raw spec override: set `AhMigrator::AhMigrationStage = MigrationDone`.
+pub fn ah_migrator_override() -> Value {
+    build_raw_override(&ah_migrator_entries())
+}
+
+// ─── Whitelist ───────────────────────────────────────────────────────────────
+
+/// Code hash authorized by the call whitelisted in Polkadot Asset Hub's genesis.
+pub const PRE_WHITELISTED_CODE_HASH: [u8; 32] = [0x77; 32];
+
+/// `System.authorize_upgrade(PRE_WHITELISTED_CODE_HASH)`, encoded by hand since
+/// there is no metadata at config time: `System` is pallet 0 and
+/// `authorize_upgrade` its call 9 on every runtime.
+pub fn pre_whitelisted_call() -> Vec<u8> {
+    let mut call = vec![0x00, 0x09];
+    call.extend_from_slice(&PRE_WHITELISTED_CODE_HASH);
+    call
+}
+
+/// `Whitelist::WhitelistedCall[blake2_256(pre_whitelisted_call())] = ()`.
+///
+/// The value is `()`, encoded as no bytes; the key's presence is what counts.
+/// Lets governance referenda dispatch the call through
+/// `Whitelist.dispatch_whitelisted_call_with_preimage` without a fellowship
+/// referendum whitelisting it first.
+pub fn whitelist_entries() -> Vec<RawEntry> {
+    vec![RawEntry::map(
+        "Whitelist",
+        "WhitelistedCall",
+        &[&blake2_256(&pre_whitelisted_call())],
+        &[],
+    )]
+}
+
+/// `AhMigrator` unlock plus the pre-whitelisted call, for Polkadot Asset Hub.
+pub fn asset_hub_polkadot_entries() -> Vec<RawEntry> {
+    let mut entries = ah_migrator_entries();
+    entries.extend(whitelist_entries());
+    entries
+}
+
+/// Raw spec override: unlock `BaseCallFilter` and pre-whitelist a call on Polkadot Asset Hub.
+pub fn asset_hub_polkadot_override() -> Value {
+    build_raw_override(&asset_hub_polkadot_entries())
+}
+
+// ─── Ranked collectives ──────────────────────────────────────────────────────
+
+/// Alice as a rank-9 member of the `collective` ranked collective pallet.
+///
+/// Entries for `Members`, `MemberCount`, `IdToIndex`, and `IndexToId` for
+/// ranks 0 through 9 (a rank-N member is also a member at all lower ranks).
+fn ranked_collective_entries(collective: &'static str) -> Vec<RawEntry> {
+    let entry = |item, keys: &[&[u8]], value: &[u8]| RawEntry::map(collective, item, keys, value);
+
+    // Members[Alice] = MemberRecord { rank: 9 }
+    // MemberRecord is a struct with a single u16 field, SCALE-encoded as 2 bytes LE.
+    let mut entries = vec![entry(
+        "Members",
+        &[&ALICE_ACCOUNT_ID],
+        &ALICE_COLLECTIVE_RANK.to_le_bytes(),
+    )];
+
+    // For each rank 0..=9:
+    for rank in 0..=ALICE_COLLECTIVE_RANK {
+        let rank = rank.to_le_bytes(); // u16 LE
+
+        // MemberCount[rank] = 1u32
+        entries.push(entry("MemberCount", &[&rank], &1u32.to_le_bytes()));
+        // IdToIndex[rank, Alice] = 0u32
+        entries.push(entry(
+            "IdToIndex",
+            &[&rank, &ALICE_ACCOUNT_ID],
+            &0u32.to_le_bytes(),
+        ));
+        // IndexToId[rank, 0] = Alice
+        entries.push(entry(
+            "IndexToId",
+            &[&rank, &0u32.to_le_bytes()],
+            &ALICE_ACCOUNT_ID,
+        ));
+    }
+
+    entries
+}
+
+/// Alice as a rank-9 fellow in `FellowshipCollective`.
+pub fn fellowship_collective_entries() -> Vec<RawEntry> {
+    ranked_collective_entries("FellowshipCollective")
+}
+
+/// Raw spec override: register Alice as a rank-9 fellow in `FellowshipCollective`.
+pub fn fellowship_collective_override() -> Value {
+    build_raw_override(&fellowship_collective_entries())
+}
+
+/// Alice as a rank-9 ambassador in `AmbassadorCollective`.
+pub fn ambassador_collective_entries() -> Vec<RawEntry> {
+    ranked_collective_entries("AmbassadorCollective")
+}
+
+/// Alice in both of Polkadot Collectives' ranked collectives.
+pub fn collectives_entries() -> Vec<RawEntry> {
+    let mut entries = fellowship_collective_entries();
+    entries.extend(ambassador_collective_entries());
+    entries
+}
+
+/// Raw spec override: register Alice as a rank-9 fellow and ambassador on Collectives.
+pub fn collectives_override() -> Value {
+    build_raw_override(&collectives_entries())
+}
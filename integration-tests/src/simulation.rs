@@ -0,0 +1,718 @@
+//! Programmatic API for running a referendum simulation from Rust.
+//!
+//! Wraps `yarn cli test --emit-blocks` (or another [`ToolInvocation`]): every
+//! block the tool builds on a fork is reported as an `@@block {json}` stdout
+//! line, parsed into a [`Block`] and handed to the `on_block` callbacks while
+//! the simulation is still running.
+//! Where each fork started and ended is reported once, as an `@@heads [json]`
+//! line, and returned as [`SimulationReport::heads`].
+//!
+//! ```no_run
+//! # async fn example() -> anyhow::Result<()> {
+//! use polkadot_referenda_tester_integration_tests::simulation::{Simulation, SimulationConfig};
+//!
+//! let report = Simulation::new(SimulationConfig {
+//!     governance_chain_url: Some("wss://polkadot-asset-hub-rpc.polkadot.io".into()),
+//!     referendum: Some(1234),
+//!     ..Default::default()
+//! })
+//! .on_block(|block| {
+//!     println!("built #{} on {}", block.number, block.endpoint);
+//!     Ok(())
+//! })
+//! .run()
+//! .await?;
+//! assert!(!report.blocks.is_empty());
+//! # Ok(())
+//! # }
+//! ```
+
+use anyhow::{bail, ensure, Context, Result};
+use std::ops::{Deref, DerefMut};
+use std::path::{Path, PathBuf};
+use std::process::{ExitStatus, Stdio};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, BufReader, Lines};
+use tokio::process::{Child, ChildStdout};
+use tokio::task::JoinHandle;
+
+use crate::tool_args::{ChainEndpoint, ToolArgs};
+
+/// Prefix of the per-block lines printed by the tool with `--emit-blocks`.
+pub const BLOCK_LINE_PREFIX: &str = "@@block ";
+
+/// Prefix of the final heads line printed by the tool with `--emit-blocks`.
+pub const HEADS_LINE_PREFIX: &str = "@@heads ";
+
+/// Printed by the tool with `--no-cleanup` once its forks are paused.
+pub const KEPT_OPEN_MARKER: &str = "Press Ctrl+C to exit";
+
+/// Error code the tool reports when a signal stopped it after tearing down its forks.
+pub const INTERRUPTED_CODE: &str = "E-INTERRUPTED";
+
+/// How long [`KeptOpenSimulation::stop`] waits for the tool to exit.
+pub const STOP_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How long [`Simulation::run_kept_open`] waits for the tool to pause its
+/// forks, unless [`SimulationConfig::keep_open_timeout`] says otherwise.
+pub const KEEP_OPEN_TIMEOUT: Duration = Duration::from_secs(1200);
+
+/// Selects the [`ToolInvocation`]: `yarn` (default), `node`, or the path of an
+/// executable taking the CLI's arguments.
+pub const TOOL_INVOCATION_ENV: &str = "TOOL_INVOCATION";
+
+/// How the tool's CLI is started.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum ToolInvocation {
+    /// `yarn cli`: ts-node on the sources, nothing to build first.
+    #[default]
+    Yarn,
+    /// `node dist/cli.js`, built beforehand with `yarn build`. Skips yarn's
+    /// workspace resolution and ts-node's compile on every run.
+    Node,
+    /// An executable taking the CLI's arguments, e.g. an installed `prt`.
+    Binary(PathBuf),
+}
+
+impl ToolInvocation {
+    /// From [`TOOL_INVOCATION_ENV`], [`ToolInvocation::Yarn`] when unset.
+    pub fn from_env() -> Self {
+        match std::env::var(TOOL_INVOCATION_ENV).as_deref() {
+            Err(_) | Ok("") | Ok("yarn") => Self::Yarn,
+            Ok("node") => Self::Node,
+            Ok(path) => Self::Binary(PathBuf::from(path)),
+        }
+    }
+
+    /// A command in `project_dir` starting the CLI, for the subcommand and
+    /// its flags to be appended.
+    pub fn command(&self, project_dir: &Path) -> tokio::process::Command {
+        let mut cmd = match self {
+            Self::Yarn => {
+                let mut cmd = tokio::process::Command::new("yarn");
+                cmd.arg("cli");
+                cmd
+            }
+            Self::Node => {
+                let mut cmd = tokio::process::Command::new("node");
+                cmd.arg(project_dir.join("dist").join("cli.js"));
+                cmd
+            }
+            Self::Binary(path) => tokio::process::Command::new(path),
+        };
+        cmd.current_dir(project_dir);
+        cmd
+    }
+}
+
+/// What to simulate. Fields mirror the `yarn cli test` flags; they are passed
+/// to the tool as [`ToolArgs`], which checks them and names the flags.
+#[derive(Debug, Clone, Default)]
+pub struct SimulationConfig {
+    /// Tool checkout containing `package.json`. Defaults to `TOOL_PROJECT_DIR`,
+    /// else the parent of the current directory (as for the integration tests).
+    pub project_dir: Option<PathBuf>,
+    /// How to start the tool. Defaults to [`ToolInvocation::from_env`].
+    pub invocation: Option<ToolInvocation>,
+    pub governance_chain_url: Option<String>,
+    pub fellowship_chain_url: Option<String>,
+    pub additional_chains: Vec<String>,
+    /// Blocks to build on each additional chain after dispatch (`--settle-blocks`).
+    pub settle_blocks: Option<u32>,
+    /// Comma-separated `account@asset[=delta]` balance checks (`--track-balance`).
+    pub track_balance: Option<String>,
+    pub referendum: Option<u32>,
+    pub fellowship: Option<u32>,
+    pub call_to_create_governance_referendum: Option<String>,
+    pub call_to_note_preimage_for_governance_referendum: Option<String>,
+    /// `real[:type[:delay]]`: create the governance referendum through Alice as proxy.
+    pub submit_via_proxy: Option<String>,
+    /// `threshold:signatory,...`: create the governance referendum from a multisig.
+    pub submit_via_multisig: Option<String>,
+    pub call_to_create_fellowship_referendum: Option<String>,
+    pub call_to_note_preimage_for_fellowship_referendum: Option<String>,
+    pub pre_call: Option<String>,
+    pub pre_origin: Option<String>,
+    /// `start:len` block of ports for the forks (`--port-range`).
+    pub port_range: Option<String>,
+    /// Chopsticks storage cache (`--db`).
+    pub db: Option<PathBuf>,
+    pub verbose: bool,
+    /// `--run-id`, starting each of the tool's and Chopsticks' log lines.
+    /// Defaults to `simulation-<pid>-<n>`.
+    pub run_id: Option<String>,
+    /// How long [`Simulation::run_kept_open`] waits for the forks to pause.
+    /// Defaults to [`KEEP_OPEN_TIMEOUT`].
+    pub keep_open_timeout: Option<Duration>,
+}
+
+impl SimulationConfig {
+    /// The tool arguments for this config, checked by
+    /// [`ToolArgsBuilder::build`](crate::tool_args::ToolArgsBuilder::build).
+    fn tool_args(&self) -> Result<ToolArgs> {
+        let mut args = ToolArgs::builder();
+        if let Some(ref url) = self.governance_chain_url {
+            args = args.governance_chain_url(url.parse()?);
+        }
+        if let Some(ref url) = self.fellowship_chain_url {
+            args = args.fellowship_chain_url(url.parse()?);
+        }
+        for endpoints in &self.additional_chains {
+            for endpoint in ChainEndpoint::parse_list(endpoints)? {
+                args = args.additional_chains(endpoint);
+            }
+        }
+        if let Some(blocks) = self.settle_blocks {
+            args = args.settle_blocks(blocks);
+        }
+        if let Some(ref checks) = self.track_balance {
+            args = args.track_balance(checks);
+        }
+        if let Some(id) = self.referendum {
+            args = args.referendum(id);
+        }
+        if let Some(id) = self.fellowship {
+            args = args.fellowship(id);
+        }
+        if let Some(ref hex) = self.call_to_create_governance_referendum {
+            args = args.call_to_create_governance_referendum(hex);
+        }
+        if let Some(ref hex) = self.call_to_note_preimage_for_governance_referendum {
+            args = args.call_to_note_preimage_for_governance_referendum(hex);
+        }
+        if let Some(ref proxy) = self.submit_via_proxy {
+            args = args.submit_via_proxy(proxy);
+        }
+        if let Some(ref multisig) = self.submit_via_multisig {
+            args = args.submit_via_multisig(multisig);
+        }
+        if let Some(ref hex) = self.call_to_create_fellowship_referendum {
+            args = args.call_to_create_fellowship_referendum(hex);
+        }
+        if let Some(ref hex) = self.call_to_note_preimage_for_fellowship_referendum {
+            args = args.call_to_note_preimage_for_fellowship_referendum(hex);
+        }
+        if let Some(ref hex) = self.pre_call {
+            args = args.pre_call(hex);
+        }
+        if let Some(ref origin) = self.pre_origin {
+            args = args.pre_origin(origin);
+        }
+        if let Some(ref range) = self.port_range {
+            args = args.port_range(range);
+        }
+        if let Some(ref db) = self.db {
+            args = args.db(db.to_string_lossy());
+        }
+        if self.verbose {
+            args = args.verbose();
+        }
+        args.build()
+            .context("Invalid simulation config (SimulationConfig)")
+    }
+}
+
+/// A block built on one of the simulation's forks. The fork at `endpoint`
+/// stays alive until the simulation finishes, so callbacks may query it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Block {
+    pub endpoint: String,
+    pub number: u64,
+    pub hash: Option<String>,
+}
+
+impl Block {
+    /// Parse an `@@block {json}` line; `None` for any other line.
+    pub fn parse_line(line: &str) -> Option<Self> {
+        let json: serde_json::Value =
+            serde_json::from_str(line.trim().strip_prefix(BLOCK_LINE_PREFIX)?).ok()?;
+        Some(Self {
+            endpoint: json["endpoint"].as_str()?.to_string(),
+            number: json["number"].as_u64()?,
+            hash: json["hash"].as_str().map(str::to_string),
+        })
+    }
+}
+
+/// A block on a fork, by number and (when known) hash.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeadRef {
+    pub number: u64,
+    pub hash: Option<String>,
+}
+
+impl HeadRef {
+    fn from_json(json: &serde_json::Value) -> Option<Self> {
+        Some(Self {
+            number: json["number"].as_u64()?,
+            hash: json["hash"].as_str().map(str::to_string),
+        })
+    }
+}
+
+/// Where one fork started and where the run left it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainHead {
+    pub chain: String,
+    pub endpoint: String,
+    pub fork: HeadRef,
+    pub end: HeadRef,
+}
+
+impl ChainHead {
+    /// Parse an `@@heads [json]` line; `None` for any other line.
+    pub fn parse_line(line: &str) -> Option<Vec<Self>> {
+        let json: Vec<serde_json::Value> =
+            serde_json::from_str(line.trim().strip_prefix(HEADS_LINE_PREFIX)?).ok()?;
+        json.iter()
+            .map(|head| {
+                Some(Self {
+                    chain: head["chain"].as_str()?.to_string(),
+                    endpoint: head["endpoint"].as_str()?.to_string(),
+                    fork: HeadRef::from_json(&head["fork"])?,
+                    end: HeadRef::from_json(&head["final"])?,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Result of a successful simulation.
+#[derive(Debug, Clone)]
+pub struct SimulationReport {
+    /// `--run-id` the tool and Chopsticks prefixed their log lines with.
+    pub run_id: String,
+    /// Every block built, in order.
+    pub blocks: Vec<Block>,
+    /// Start and final head of every fork.
+    pub heads: Vec<ChainHead>,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+type BlockCallback = Box<dyn FnMut(&Block) -> Result<()> + Send>;
+
+/// Simulations started by this process, numbering their default run ids.
+static SIMULATIONS: AtomicU32 = AtomicU32::new(0);
+
+/// A referendum simulation with per-block callbacks.
+pub struct Simulation {
+    config: SimulationConfig,
+    run_id: String,
+    callbacks: Vec<BlockCallback>,
+}
+
+impl Simulation {
+    pub fn new(config: SimulationConfig) -> Self {
+        let run_id = config.run_id.clone().unwrap_or_else(|| {
+            let n = SIMULATIONS.fetch_add(1, Ordering::Relaxed) + 1;
+            format!("simulation-{}-{n}", std::process::id())
+        });
+        Self {
+            config,
+            run_id,
+            callbacks: Vec::new(),
+        }
+    }
+
+    /// `--run-id` of the tool run, to find its lines in interleaved logs.
+    pub fn run_id(&self) -> &str {
+        &self.run_id
+    }
+
+    /// Call `callback` for every block built, in order. An error aborts the
+    /// simulation (the tool is killed) and is returned from [`Simulation::run`].
+    pub fn on_block(mut self, callback: impl FnMut(&Block) -> Result<()> + Send + 'static) -> Self {
+        self.callbacks.push(Box::new(callback));
+        self
+    }
+
+    fn command(&self) -> Result<tokio::process::Command> {
+        let config = &self.config;
+        let project_dir = config.project_dir.clone().unwrap_or_else(|| {
+            std::env::var("TOOL_PROJECT_DIR")
+                .map(PathBuf::from)
+                .unwrap_or_else(|_| {
+                    let cwd = std::env::current_dir().expect("cannot get cwd");
+                    cwd.parent().unwrap_or(&cwd).to_path_buf()
+                })
+        });
+
+        let invocation = config
+            .invocation
+            .clone()
+            .unwrap_or_else(ToolInvocation::from_env);
+        let mut cmd = invocation.command(&project_dir);
+        cmd.arg("test")
+            .arg("--emit-blocks")
+            .arg("--run-id")
+            .arg(&self.run_id);
+        for (flag, value) in config.tool_args()?.flags() {
+            cmd.arg(flag);
+            if let Some(value) = value {
+                cmd.arg(value);
+            }
+        }
+        Ok(cmd)
+    }
+
+    /// Record one stdout line in `out`, running the callbacks on blocks.
+    fn collect_line(&mut self, line: &str, out: &mut Collected) -> Result<()> {
+        if let Some(block) = Block::parse_line(line) {
+            for callback in &mut self.callbacks {
+                callback(&block).with_context(|| {
+                    format!(
+                        "on_block callback failed at block #{} on {}",
+                        block.number, block.endpoint
+                    )
+                })?;
+            }
+            out.blocks.push(block);
+        } else if let Some(line_heads) = ChainHead::parse_line(line) {
+            out.heads = line_heads;
+        } else {
+            out.stdout.push_str(line);
+            out.stdout.push('\n');
+        }
+        Ok(())
+    }
+
+    /// Run the simulation to completion. Fails if the tool fails (with its
+    /// `E-...` error line) or if any callback returns an error, which kills
+    /// the tool.
+    pub async fn run(mut self) -> Result<SimulationReport> {
+        let mut cmd = self.command()?;
+        log::info!("Running simulation [{}]: {cmd:?}", self.run_id);
+        let (mut child, mut lines, stderr_task) = spawn_piped(&mut cmd)?;
+        let mut out = Collected::new(&self.run_id);
+        while let Some(line) = lines
+            .next_line()
+            .await
+            .context("Failed to read tool stdout")?
+        {
+            if let Err(e) = self.collect_line(&line, &mut out) {
+                child.kill_group().await;
+                return Err(e);
+            }
+        }
+
+        let status = child.wait().await.context("Tool process failed")?;
+        let stderr_text = stderr_task.await.unwrap_or_default();
+        if !status.success() {
+            bail!(
+                "Simulation [{}] failed (exit code {}): {}",
+                self.run_id,
+                status.code().unwrap_or(-1),
+                error_code(&stderr_text)
+            );
+        }
+
+        Ok(out.into_report(stderr_text))
+    }
+
+    /// Run the simulation with `--no-cleanup` and return once the tool has
+    /// reported its final heads and paused: every fork is still served at its
+    /// [`ChainHead::endpoint`] for post-run queries and extrinsics, until
+    /// [`KeptOpenSimulation::stop`]. A tool that exits instead, or that does
+    /// not pause within [`SimulationConfig::keep_open_timeout`], fails this.
+    pub async fn run_kept_open(mut self) -> Result<KeptOpenSimulation> {
+        let timeout = self.config.keep_open_timeout.unwrap_or(KEEP_OPEN_TIMEOUT);
+        let mut cmd = self.command()?;
+        cmd.arg("--no-cleanup");
+        log::info!("Running simulation [{}]: {cmd:?}", self.run_id);
+        let mut out = Collected::new(&self.run_id);
+        let tool =
+            PausedTool::start(&mut cmd, timeout, |line| self.collect_line(line, &mut out)).await?;
+        Ok(KeptOpenSimulation { tool, out })
+    }
+}
+
+type StderrTask = JoinHandle<String>;
+
+/// Spawn `cmd` as a [`ToolProcess`], with its stdout as lines and its stderr
+/// collected in the background.
+fn spawn_piped(
+    cmd: &mut tokio::process::Command,
+) -> Result<(ToolProcess, Lines<BufReader<ChildStdout>>, StderrTask)> {
+    let mut child = ToolProcess::spawn(cmd)?;
+    let stdout = child.stdout.take().context("tool stdout not captured")?;
+    let mut stderr = child.stderr.take().context("tool stderr not captured")?;
+    let stderr_task = tokio::spawn(async move {
+        let mut buf = String::new();
+        let _ = stderr.read_to_string(&mut buf).await;
+        buf
+    });
+    Ok((child, BufReader::new(stdout).lines(), stderr_task))
+}
+
+/// The tool's `E-...` error line in `stderr`.
+fn error_code(stderr: &str) -> &str {
+    stderr
+        .lines()
+        .find(|line| line.starts_with("E-"))
+        .unwrap_or("no error code reported")
+}
+
+/// What a simulation printed on stdout so far.
+struct Collected {
+    run_id: String,
+    blocks: Vec<Block>,
+    heads: Vec<ChainHead>,
+    stdout: String,
+}
+
+impl Collected {
+    fn new(run_id: &str) -> Self {
+        Self {
+            run_id: run_id.to_string(),
+            blocks: Vec::new(),
+            heads: Vec::new(),
+            stdout: String::new(),
+        }
+    }
+
+    fn into_report(self, stderr: String) -> SimulationReport {
+        SimulationReport {
+            run_id: self.run_id,
+            blocks: self.blocks,
+            heads: self.heads,
+            stdout: self.stdout,
+            stderr,
+        }
+    }
+}
+
+/// A finished simulation whose tool keeps its forks alive for inspection
+/// (see [`Simulation::run_kept_open`]). Dropping it kills the tool's whole
+/// process group, Chopsticks and node included.
+pub struct KeptOpenSimulation {
+    tool: PausedTool,
+    out: Collected,
+}
+
+impl KeptOpenSimulation {
+    /// `--run-id` of the paused tool run.
+    pub fn run_id(&self) -> &str {
+        &self.out.run_id
+    }
+
+    /// Start and final head of every fork; each is still served at its endpoint.
+    pub fn heads(&self) -> &[ChainHead] {
+        &self.out.heads
+    }
+
+    /// Every block built during the run, in order.
+    pub fn blocks(&self) -> &[Block] {
+        &self.out.blocks
+    }
+
+    /// Tool stdout up to the pause, without the `@@` lines.
+    pub fn stdout(&self) -> &str {
+        &self.out.stdout
+    }
+
+    /// The kept fork of `chain` (as labeled in the heads, e.g. `AssetHub`).
+    pub fn head(&self, chain: &str) -> Result<&ChainHead> {
+        self.out
+            .heads
+            .iter()
+            .find(|head| head.chain == chain)
+            .with_context(|| format!("No kept fork for chain '{chain}'"))
+    }
+
+    /// Interrupt the tool (SIGINT to its process group, like Ctrl+C) and wait
+    /// up to [`STOP_TIMEOUT`] for it to exit; a tool that doesn't, or that
+    /// exits without reporting [`INTERRUPTED_CODE`], fails this. Returns the
+    /// whole run's output.
+    pub async fn stop(mut self) -> Result<SimulationReport> {
+        let stopped = self.tool.stop().await?;
+        let reason = error_code(&stopped.stderr);
+        ensure!(
+            reason.starts_with(INTERRUPTED_CODE),
+            "Tool did not report {INTERRUPTED_CODE} after SIGINT: {reason}"
+        );
+        self.out.stdout.push_str(&stopped.rest);
+        Ok(self.out.into_report(stopped.stderr))
+    }
+}
+
+/// A tool run with `--no-cleanup` that has paused its forks after the run:
+/// the process handling behind [`KeptOpenSimulation`] and the harness's
+/// `ToolSession`, which parse the run's output each their own way.
+pub struct PausedTool {
+    child: ToolProcess,
+    /// Drains stdout printed after the pause, so the tool never blocks on it.
+    rest: JoinHandle<String>,
+    stderr_task: StderrTask,
+}
+
+/// How a [`PausedTool`] ended after [`PausedTool::stop`].
+pub struct StoppedTool {
+    pub status: ExitStatus,
+    /// Stdout printed after the pause.
+    pub rest: String,
+    pub stderr: String,
+}
+
+impl PausedTool {
+    /// Spawn `cmd`, a tool run with `--no-cleanup`, and hand every stdout
+    /// line to `on_line` until the tool has printed its final heads and
+    /// [`KEPT_OPEN_MARKER`]. A tool that exits instead, that does not pause
+    /// within `timeout` or whose line fails `on_line` fails this, and is
+    /// killed.
+    pub async fn start(
+        cmd: &mut tokio::process::Command,
+        timeout: Duration,
+        mut on_line: impl FnMut(&str) -> Result<()>,
+    ) -> Result<Self> {
+        let (mut child, mut lines, stderr_task) = spawn_piped(cmd)?;
+        let paused = tokio::time::timeout(timeout, async {
+            let mut has_heads = false;
+            while let Some(line) = lines
+                .next_line()
+                .await
+                .context("Failed to read tool stdout")?
+            {
+                on_line(&line)?;
+                if let Some(heads) = ChainHead::parse_line(&line) {
+                    has_heads = !heads.is_empty();
+                }
+                if has_heads && line.contains(KEPT_OPEN_MARKER) {
+                    return Ok(true);
+                }
+            }
+            Ok::<_, anyhow::Error>(false)
+        })
+        .await;
+
+        match paused {
+            Ok(Ok(true)) => {}
+            Ok(Ok(false)) => {
+                let status = child.wait().await.context("Tool process failed")?;
+                let stderr_text = stderr_task.await.unwrap_or_default();
+                bail!(
+                    "Tool exited (exit code {}) instead of keeping its forks open: {}",
+                    status.code().unwrap_or(-1),
+                    error_code(&stderr_text)
+                );
+            }
+            Ok(Err(e)) => {
+                child.kill_group().await;
+                return Err(e);
+            }
+            Err(_) => {
+                child.kill_group().await;
+                bail!("Tool did not pause its forks within {}s", timeout.as_secs());
+            }
+        }
+
+        let rest = tokio::spawn(async move {
+            let mut rest = String::new();
+            while let Ok(Some(line)) = lines.next_line().await {
+                rest.push_str(&line);
+                rest.push('\n');
+            }
+            rest
+        });
+        Ok(Self {
+            child,
+            rest,
+            stderr_task,
+        })
+    }
+
+    /// Interrupt the tool (SIGINT to its process group, like Ctrl+C) and wait
+    /// up to [`STOP_TIMEOUT`] for it to tear the forks down and exit; a tool
+    /// that doesn't is killed and this fails.
+    pub async fn stop(mut self) -> Result<StoppedTool> {
+        let pid = self.child.id().context("Tool already exited")?;
+        let kill = std::process::Command::new("kill")
+            .args(["-INT", "--", &format!("-{pid}")])
+            .status()
+            .context("Failed to run kill")?;
+        ensure!(kill.success(), "kill -INT of process group {pid} failed");
+
+        let status = match tokio::time::timeout(STOP_TIMEOUT, self.child.wait()).await {
+            Ok(status) => status.context("Tool process failed")?,
+            Err(_) => {
+                self.child.kill_group().await;
+                bail!(
+                    "Tool did not stop within {}s of SIGINT",
+                    STOP_TIMEOUT.as_secs()
+                );
+            }
+        };
+        log::info!("Tool stopped on SIGINT ({status})");
+
+        Ok(StoppedTool {
+            status,
+            rest: self.rest.await.unwrap_or_default(),
+            stderr: self.stderr_task.await.unwrap_or_default(),
+        })
+    }
+}
+
+/// A tool process spawned in a process group of its own, so that yarn, node
+/// and the Chopsticks forks can be signalled together. Dropping it while the
+/// tool still runs (a failed check, a timed out or cancelled run) kills the
+/// whole group: `kill_on_drop` alone only reaches `yarn`, and its forks would
+/// keep their ports.
+pub struct ToolProcess(Child);
+
+impl ToolProcess {
+    /// Spawn `cmd` in a new process group, with its stdout and stderr piped.
+    pub fn spawn(cmd: &mut tokio::process::Command) -> Result<Self> {
+        let child = cmd
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .process_group(0)
+            .kill_on_drop(true)
+            .spawn()
+            .context("Failed to spawn the tool process")?;
+        Ok(Self(child))
+    }
+
+    /// SIGKILL the tool's process group and reap the tool.
+    pub async fn kill_group(&mut self) {
+        if let Some(pgid) = self.0.id() {
+            kill_process_group(pgid);
+        }
+        let _ = self.0.kill().await;
+    }
+}
+
+impl Deref for ToolProcess {
+    type Target = Child;
+
+    fn deref(&self) -> &Child {
+        &self.0
+    }
+}
+
+impl DerefMut for ToolProcess {
+    fn deref_mut(&mut self) -> &mut Child {
+        &mut self.0
+    }
+}
+
+impl Drop for ToolProcess {
+    fn drop(&mut self) {
+        if let (Ok(None), Some(pgid)) = (self.0.try_wait(), self.0.id()) {
+            kill_process_group(pgid);
+        }
+    }
+}
+
+/// SIGKILL the process group `pgid` of a [`ToolProcess`]. Failures are only
+/// logged: the caller is already tearing the tool down.
+pub fn kill_process_group(pgid: u32) {
+    match std::process::Command::new("kill")
+        .args(["-KILL", "--", &format!("-{pgid}")])
+        .status()
+    {
+        Ok(status) if status.success() => log::warn!("Killed tool process group {pgid}"),
+        Ok(status) => log::warn!("kill -KILL of process group {pgid} failed: {status}"),
+        Err(e) => log::warn!("Failed to run kill for process group {pgid}: {e}"),
+    }
+}
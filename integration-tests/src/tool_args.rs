@@ -0,0 +1,896 @@
+//! Arguments for the polkadot-referenda-tester CLI: [`ToolArgs`], checked by
+//! [`ToolArgsBuilder::build`] the way the tool checks them, and the
+//! [`ChainEndpoint`]s it forks.
+
+use anyhow::{bail, ensure, Context, Result};
+use std::time::Duration;
+
+use crate::flag_registry;
+
+/// A chain for the tool to fork: its node's `url`, the block to fork it at
+/// (the latest block when `None`) and a name to show for it in the tool's
+/// output. Passed as `url[,block]`, or `name=...;url=...[;block=...]` when
+/// labeled.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChainEndpoint {
+    url: String,
+    at_block: Option<u32>,
+    label: Option<String>,
+}
+
+impl ChainEndpoint {
+    /// Fork `url` at its latest block; fails unless it is a `ws://` or
+    /// `wss://` URL.
+    pub fn new(url: impl Into<String>) -> Result<Self> {
+        let url = url.into();
+        check_ws_url("chain endpoint", &url)?;
+        Ok(Self {
+            url,
+            at_block: None,
+            label: None,
+        })
+    }
+
+    /// Fork at `block` instead of the latest block.
+    pub fn at_block(mut self, block: u32) -> Self {
+        self.at_block = Some(block);
+        self
+    }
+
+    /// Name the chain `label` in the tool's output instead of its spec name.
+    pub fn labeled(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// The node's URL.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// The block to fork at; `None` for the latest block.
+    pub fn block(&self) -> Option<u32> {
+        self.at_block
+    }
+
+    /// The chain's name in the tool's output, if labeled.
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    /// Parse a comma-separated `--additional-chains` value, as the tool
+    /// splits it.
+    pub fn parse_list(endpoints: &str) -> Result<Vec<Self>> {
+        parse_endpoints("chain endpoints", endpoints)
+    }
+
+    /// The endpoint as a `--*-chain-url` value.
+    pub fn to_arg(&self) -> String {
+        match (&self.label, self.at_block) {
+            (Some(label), Some(block)) => format!("name={label};url={};block={block}", self.url),
+            (Some(label), None) => format!("name={label};url={}", self.url),
+            (None, Some(block)) => format!("{},{block}", self.url),
+            (None, None) => self.url.clone(),
+        }
+    }
+}
+
+impl std::str::FromStr for ChainEndpoint {
+    type Err = anyhow::Error;
+
+    /// Parse a `--*-chain-url` value: `url`, `url,block` or
+    /// `name=...;url=...[;block=...]`.
+    fn from_str(endpoint: &str) -> Result<Self> {
+        parse_endpoint("chain endpoint", endpoint)
+    }
+}
+
+/// Arguments for `yarn cli test`.
+///
+/// Build them with [`ToolArgs::builder`], which checks them before any process
+/// is spawned. [`ToolArgsBuilder::build_unchecked`] and [`ToolArgs::set_flag`]
+/// skip those checks, for sub-tests that hand the tool invalid arguments on
+/// purpose.
+#[derive(Clone, Default)]
+pub struct ToolArgs {
+    governance_chain_url: Option<ChainEndpoint>,
+    fellowship_chain_url: Option<ChainEndpoint>,
+    /// Chains to monitor for XCM events, one `--additional-chains` each.
+    additional_chains: Vec<ChainEndpoint>,
+    /// Blocks to build on each additional chain after dispatch (`--settle-blocks`).
+    settle_blocks: Option<u32>,
+    /// Blocks to build on the governance chain after dispatch (`--blocks-after-dispatch`).
+    blocks_after_dispatch: Option<u32>,
+    /// Dispatch tasks the proposal schedules for later blocks (`--follow-scheduled`).
+    follow_scheduled: bool,
+    /// Retries to give an enactment task without a retry configuration (`--enactment-retries`).
+    enactment_retries: Option<u8>,
+    /// Comma-separated `account@asset[=delta]` balance checks (`--track-balance`).
+    track_balance: Option<String>,
+    referendum: Option<String>,
+    fellowship: Option<String>,
+    port: Option<u16>,
+    /// `start:len` block of ports for the tool's forks; see [`crate::port_allocator`].
+    port_range: Option<String>,
+    pre_call: Option<String>,
+    pre_origin: Option<String>,
+    /// Fault to inject into the simulation (`--scenario`, e.g. `bad-origin`).
+    scenario: Option<String>,
+    /// Phase to cancel the referendum at instead of dispatching it (`--cancel-at`).
+    cancel_at: Option<String>,
+    /// Approve by a real conviction vote at this conviction (`--vote-conviction`).
+    vote_conviction: Option<String>,
+    /// `Pallet.Item[key]=value` storage overrides, one `--set-storage` each.
+    set_storage: Vec<String>,
+    /// `Namespace.Name=value` runtime parameters, one `--override-parameter` each.
+    override_parameter: Vec<String>,
+    /// `ss58=amount` free balances, one `--fund` each.
+    fund: Vec<String>,
+    /// Lift the Asset Hub migration call filter (`--unlock-call-filter`).
+    unlock_call_filter: bool,
+    /// Decode and show the creation calls, then exit without forking (`--preview`).
+    preview: bool,
+    call_to_create_governance_referendum: Option<String>,
+    call_to_note_preimage_for_governance_referendum: Option<String>,
+    /// JSON file or preimage hash set as the created governance referendum's metadata.
+    metadata_for_governance_referendum: Option<String>,
+    /// `real[:type[:delay]]`: create the governance referendum through Alice as proxy.
+    submit_via_proxy: Option<String>,
+    /// `threshold:signatory,...`: create the governance referendum from a multisig.
+    submit_via_multisig: Option<String>,
+    call_to_create_fellowship_referendum: Option<String>,
+    call_to_note_preimage_for_fellowship_referendum: Option<String>,
+    /// JSON file or preimage hash set as the created fellowship referendum's metadata.
+    metadata_for_fellowship_referendum: Option<String>,
+    /// Chopsticks storage cache (`--db`). Defaults to the runner's fork cache.
+    db: Option<String>,
+    /// Where the tool stores each run's JSON result (`--history-dir`).
+    history_dir: Option<String>,
+    /// Print the changes since the previous stored run of the same
+    /// referendum (`--diff-with-last`).
+    diff_with_last: bool,
+    /// Parent of the run's temporary state (`--work-dir`). Defaults to the
+    /// sub-test's artifacts dir in the test harness.
+    work_dir: Option<String>,
+    /// `--output-format`; with [`OutputFormat::Json`] the tool also prints
+    /// an `@@result` line.
+    output_format: Option<OutputFormat>,
+    verbose: bool,
+    /// Kill the run after this long instead of its timeout class's.
+    /// Not a CLI flag.
+    timeout: Option<Duration>,
+    /// Environment variables set for the tool process, e.g. `LOG_LEVEL` or
+    /// `HTTPS_PROXY`. Not CLI flags.
+    env: Vec<(String, String)>,
+    /// Flags passed as written, after all others; see [`Self::set_raw_flag`].
+    raw_flags: Vec<(&'static str, String)>,
+}
+
+impl ToolArgs {
+    /// Start building checked arguments.
+    pub fn builder() -> ToolArgsBuilder {
+        ToolArgsBuilder::default()
+    }
+
+    /// The `--governance-chain-url` endpoint, if set.
+    pub fn governance_chain_url(&self) -> Option<&ChainEndpoint> {
+        self.governance_chain_url.as_ref()
+    }
+
+    /// The `--fellowship-chain-url` endpoint, if set.
+    pub fn fellowship_chain_url(&self) -> Option<&ChainEndpoint> {
+        self.fellowship_chain_url.as_ref()
+    }
+
+    /// The `--additional-chains` endpoints.
+    pub fn additional_chains(&self) -> &[ChainEndpoint] {
+        &self.additional_chains
+    }
+
+    /// The `--port-range` value, if set.
+    pub fn port_range(&self) -> Option<&str> {
+        self.port_range.as_deref()
+    }
+
+    /// Fork on the ports of `range` (`--port-range`, `start:len`).
+    pub fn set_port_range(&mut self, range: impl Into<String>) {
+        self.port_range = Some(range.into());
+    }
+
+    /// The `--db` Chopsticks cache, if set.
+    pub fn db(&self) -> Option<&str> {
+        self.db.as_deref()
+    }
+
+    /// Use `db` as the Chopsticks cache (`--db`).
+    pub fn set_db(&mut self, db: impl Into<String>) {
+        self.db = Some(db.into());
+    }
+
+    /// The `--history-dir` value, if set.
+    pub fn history_dir(&self) -> Option<&str> {
+        self.history_dir.as_deref()
+    }
+
+    /// Whether `--diff-with-last` is set.
+    pub fn diff_with_last(&self) -> bool {
+        self.diff_with_last
+    }
+
+    /// The `--work-dir` value, if set.
+    pub fn work_dir(&self) -> Option<&str> {
+        self.work_dir.as_deref()
+    }
+
+    /// The run's own timeout, if set; see [`ToolArgsBuilder::timeout`].
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Environment variables set for the tool process.
+    pub fn env(&self) -> &[(String, String)] {
+        &self.env
+    }
+
+    /// Set a field by its CLI flag name (e.g. `--referendum`). Used to build
+    /// arguments from the shared flag registry.
+    pub fn set_flag(&mut self, flag: &str, value: &str) -> Result<()> {
+        let value = value.to_string();
+        match flag {
+            "--governance-chain-url" => {
+                self.governance_chain_url = Some(parse_endpoint(flag, &value)?)
+            }
+            "--fellowship-chain-url" => {
+                self.fellowship_chain_url = Some(parse_endpoint(flag, &value)?)
+            }
+            "--additional-chains" => self
+                .additional_chains
+                .extend(parse_endpoints(flag, &value)?),
+            "--settle-blocks" => {
+                self.settle_blocks = Some(
+                    value
+                        .parse()
+                        .with_context(|| format!("invalid --settle-blocks value '{value}'"))?,
+                )
+            }
+            "--blocks-after-dispatch" => {
+                self.blocks_after_dispatch =
+                    Some(value.parse().with_context(|| {
+                        format!("invalid --blocks-after-dispatch value '{value}'")
+                    })?)
+            }
+            "--follow-scheduled" => self.follow_scheduled = true,
+            "--enactment-retries" => {
+                self.enactment_retries = Some(
+                    value
+                        .parse()
+                        .with_context(|| format!("invalid --enactment-retries value '{value}'"))?,
+                )
+            }
+            "--track-balance" => self.track_balance = Some(value),
+            "--referendum" => self.referendum = Some(value),
+            "--fellowship" => self.fellowship = Some(value),
+            "--port" => {
+                self.port = Some(
+                    value
+                        .parse()
+                        .with_context(|| format!("invalid --port value '{value}'"))?,
+                )
+            }
+            "--port-range" => self.port_range = Some(value),
+            "--pre-call" => self.pre_call = Some(value),
+            "--pre-origin" => self.pre_origin = Some(value),
+            "--scenario" => self.scenario = Some(value),
+            "--cancel-at" => self.cancel_at = Some(value),
+            "--vote-conviction" => self.vote_conviction = Some(value),
+            "--set-storage" => self.set_storage.push(value),
+            "--override-parameter" => self.override_parameter.push(value),
+            "--fund" => self.fund.push(value),
+            "--unlock-call-filter" => self.unlock_call_filter = true,
+            "--preview" => self.preview = true,
+            "--call-to-create-governance-referendum" => {
+                self.call_to_create_governance_referendum = Some(value)
+            }
+            "--call-to-note-preimage-for-governance-referendum" => {
+                self.call_to_note_preimage_for_governance_referendum = Some(value)
+            }
+            "--metadata-for-governance-referendum" => {
+                self.metadata_for_governance_referendum = Some(value)
+            }
+            "--submit-via-proxy" => self.submit_via_proxy = Some(value),
+            "--submit-via-multisig" => self.submit_via_multisig = Some(value),
+            "--call-to-create-fellowship-referendum" => {
+                self.call_to_create_fellowship_referendum = Some(value)
+            }
+            "--call-to-note-preimage-for-fellowship-referendum" => {
+                self.call_to_note_preimage_for_fellowship_referendum = Some(value)
+            }
+            "--metadata-for-fellowship-referendum" => {
+                self.metadata_for_fellowship_referendum = Some(value)
+            }
+            "--db" => self.db = Some(value),
+            "--history-dir" => self.history_dir = Some(value),
+            "--diff-with-last" => self.diff_with_last = true,
+            "--work-dir" => self.work_dir = Some(value),
+            "--output-format" => self.output_format = Some(value.parse()?),
+            "--verbose" => self.verbose = true,
+            other => anyhow::bail!("ToolArgs has no field for flag {other}"),
+        }
+        Ok(())
+    }
+
+    /// Pass `flag value` to the tool as written, for sub-tests that hand it a
+    /// value [`Self::set_flag`] would reject or rewrite, e.g. a malformed
+    /// endpoint or a block number in hex.
+    pub fn set_raw_flag(&mut self, flag: &'static str, value: impl Into<String>) {
+        self.raw_flags.push((flag, value.into()));
+    }
+
+    /// The arguments as `(flag, value)` pairs in the order they are passed to
+    /// the tool. Switches such as `--verbose` have no value; repeatable flags
+    /// such as `--set-storage` appear once per value.
+    pub fn flags(&self) -> Vec<(&'static str, Option<String>)> {
+        let values = [
+            (
+                "--governance-chain-url",
+                self.governance_chain_url
+                    .as_ref()
+                    .map(ChainEndpoint::to_arg),
+            ),
+            (
+                "--fellowship-chain-url",
+                self.fellowship_chain_url
+                    .as_ref()
+                    .map(ChainEndpoint::to_arg),
+            ),
+            ("--settle-blocks", self.settle_blocks.map(|b| b.to_string())),
+            (
+                "--blocks-after-dispatch",
+                self.blocks_after_dispatch.map(|b| b.to_string()),
+            ),
+            (
+                "--enactment-retries",
+                self.enactment_retries.map(|r| r.to_string()),
+            ),
+            ("--track-balance", self.track_balance.clone()),
+            ("--referendum", self.referendum.clone()),
+            ("--fellowship", self.fellowship.clone()),
+            ("--port", self.port.map(|p| p.to_string())),
+            ("--port-range", self.port_range.clone()),
+            ("--pre-call", self.pre_call.clone()),
+            ("--pre-origin", self.pre_origin.clone()),
+            ("--scenario", self.scenario.clone()),
+            ("--cancel-at", self.cancel_at.clone()),
+            ("--vote-conviction", self.vote_conviction.clone()),
+            (
+                "--call-to-create-governance-referendum",
+                self.call_to_create_governance_referendum.clone(),
+            ),
+            (
+                "--call-to-note-preimage-for-governance-referendum",
+                self.call_to_note_preimage_for_governance_referendum.clone(),
+            ),
+            (
+                "--metadata-for-governance-referendum",
+                self.metadata_for_governance_referendum.clone(),
+            ),
+            ("--submit-via-proxy", self.submit_via_proxy.clone()),
+            ("--submit-via-multisig", self.submit_via_multisig.clone()),
+            (
+                "--call-to-create-fellowship-referendum",
+                self.call_to_create_fellowship_referendum.clone(),
+            ),
+            (
+                "--call-to-note-preimage-for-fellowship-referendum",
+                self.call_to_note_preimage_for_fellowship_referendum.clone(),
+            ),
+            (
+                "--metadata-for-fellowship-referendum",
+                self.metadata_for_fellowship_referendum.clone(),
+            ),
+            ("--db", self.db.clone()),
+            ("--history-dir", self.history_dir.clone()),
+            ("--work-dir", self.work_dir.clone()),
+            (
+                "--output-format",
+                self.output_format.map(|f| f.as_arg().to_string()),
+            ),
+        ];
+        let mut flags: Vec<_> = values
+            .into_iter()
+            .filter_map(|(flag, value)| Some((flag, Some(value?))))
+            .collect();
+        for endpoint in &self.additional_chains {
+            flags.push(("--additional-chains", Some(endpoint.to_arg())));
+        }
+        for value in &self.set_storage {
+            flags.push(("--set-storage", Some(value.clone())));
+        }
+        for value in &self.override_parameter {
+            flags.push(("--override-parameter", Some(value.clone())));
+        }
+        for value in &self.fund {
+            flags.push(("--fund", Some(value.clone())));
+        }
+        if self.follow_scheduled {
+            flags.push(("--follow-scheduled", None));
+        }
+        if self.unlock_call_filter {
+            flags.push(("--unlock-call-filter", None));
+        }
+        if self.preview {
+            flags.push(("--preview", None));
+        }
+        if self.diff_with_last {
+            flags.push(("--diff-with-last", None));
+        }
+        if self.verbose {
+            flags.push(("--verbose", None));
+        }
+        for (flag, value) in &self.raw_flags {
+            flags.push((flag, Some(value.clone())));
+        }
+        flags
+    }
+}
+
+/// `--output-format` of the tool.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human-readable output only (the tool's default).
+    Text,
+    /// Also print the run's outcome as one `@@result {json}` line.
+    Json,
+}
+
+impl OutputFormat {
+    pub fn as_arg(self) -> &'static str {
+        match self {
+            Self::Text => "text",
+            Self::Json => "json",
+        }
+    }
+}
+
+impl std::str::FromStr for OutputFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            other => bail!("invalid --output-format value '{other}'"),
+        }
+    }
+}
+
+/// Typed builder for [`ToolArgs`]. [`ToolArgsBuilder::build`] mirrors the
+/// tool's own argument checks (flag rules, endpoint syntax, hex call data), so
+/// a harness-side mistake fails right away instead of after a `yarn` run.
+#[derive(Default)]
+pub struct ToolArgsBuilder {
+    args: ToolArgs,
+}
+
+impl ToolArgsBuilder {
+    pub fn governance_chain_url(mut self, endpoint: ChainEndpoint) -> Self {
+        self.args.governance_chain_url = Some(endpoint);
+        self
+    }
+
+    pub fn fellowship_chain_url(mut self, endpoint: ChainEndpoint) -> Self {
+        self.args.fellowship_chain_url = Some(endpoint);
+        self
+    }
+
+    /// A chain to monitor for XCM events; may be given several times.
+    pub fn additional_chains(mut self, endpoint: ChainEndpoint) -> Self {
+        self.args.additional_chains.push(endpoint);
+        self
+    }
+
+    pub fn settle_blocks(mut self, blocks: u32) -> Self {
+        self.args.settle_blocks = Some(blocks);
+        self
+    }
+
+    pub fn blocks_after_dispatch(mut self, blocks: u32) -> Self {
+        self.args.blocks_after_dispatch = Some(blocks);
+        self
+    }
+
+    pub fn follow_scheduled(mut self) -> Self {
+        self.args.follow_scheduled = true;
+        self
+    }
+
+    pub fn enactment_retries(mut self, retries: u8) -> Self {
+        self.args.enactment_retries = Some(retries);
+        self
+    }
+
+    pub fn track_balance(mut self, checks: impl Into<String>) -> Self {
+        self.args.track_balance = Some(checks.into());
+        self
+    }
+
+    pub fn referendum(mut self, id: u32) -> Self {
+        self.args.referendum = Some(id.to_string());
+        self
+    }
+
+    /// `--referendum <pallet>:<id>`: a referendum of another referenda pallet
+    /// of the governance chain, e.g. `AmbassadorReferenda`.
+    pub fn referendum_in(mut self, pallet: &str, id: u32) -> Self {
+        self.args.referendum = Some(format!("{pallet}:{id}"));
+        self
+    }
+
+    pub fn fellowship(mut self, id: u32) -> Self {
+        self.args.fellowship = Some(id.to_string());
+        self
+    }
+
+    pub fn port(mut self, port: u16) -> Self {
+        self.args.port = Some(port);
+        self
+    }
+
+    /// `start:len`, usually `PortRange::to_arg`.
+    pub fn port_range(mut self, range: impl Into<String>) -> Self {
+        self.args.port_range = Some(range.into());
+        self
+    }
+
+    pub fn pre_call(mut self, hex: impl Into<String>) -> Self {
+        self.args.pre_call = Some(hex.into());
+        self
+    }
+
+    pub fn pre_origin(mut self, origin: impl Into<String>) -> Self {
+        self.args.pre_origin = Some(origin.into());
+        self
+    }
+
+    pub fn scenario(mut self, scenario: impl Into<String>) -> Self {
+        self.args.scenario = Some(scenario.into());
+        self
+    }
+
+    /// Cancel the referendum via `ReferendumCanceller` at `phase` (`ongoing` or `confirming`).
+    pub fn cancel_at(mut self, phase: impl Into<String>) -> Self {
+        self.args.cancel_at = Some(phase.into());
+        self
+    }
+
+    /// Approve with a funded voter's `ConvictionVoting.vote` at `conviction`
+    /// (`None`, `Locked1x` ... `Locked6x`) and check the lock it leaves.
+    pub fn vote_conviction(mut self, conviction: impl Into<String>) -> Self {
+        self.args.vote_conviction = Some(conviction.into());
+        self
+    }
+
+    /// `Pallet.Item[key]=value`; may be given several times.
+    pub fn set_storage(mut self, value: impl Into<String>) -> Self {
+        self.args.set_storage.push(value.into());
+        self
+    }
+
+    /// Set the runtime parameter `name` (`Namespace.Name`) to `value`; may be
+    /// given several times.
+    pub fn override_parameter(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.args
+            .override_parameter
+            .push(format!("{}={}", name.into(), value.into()));
+        self
+    }
+
+    /// Give `account` a free balance of `amount` plancks; may be given several times.
+    pub fn fund(mut self, account: impl Into<String>, amount: u128) -> Self {
+        self.args.fund.push(format!("{}={amount}", account.into()));
+        self
+    }
+
+    pub fn unlock_call_filter(mut self) -> Self {
+        self.args.unlock_call_filter = true;
+        self
+    }
+
+    /// Only decode and show the `--call-to-create-*` calls; no fork is started.
+    pub fn preview(mut self) -> Self {
+        self.args.preview = true;
+        self
+    }
+
+    pub fn call_to_create_governance_referendum(mut self, hex: impl Into<String>) -> Self {
+        self.args.call_to_create_governance_referendum = Some(hex.into());
+        self
+    }
+
+    pub fn call_to_note_preimage_for_governance_referendum(
+        mut self,
+        hex: impl Into<String>,
+    ) -> Self {
+        self.args.call_to_note_preimage_for_governance_referendum = Some(hex.into());
+        self
+    }
+
+    pub fn metadata_for_governance_referendum(mut self, metadata: impl Into<String>) -> Self {
+        self.args.metadata_for_governance_referendum = Some(metadata.into());
+        self
+    }
+
+    pub fn submit_via_proxy(mut self, proxy: impl Into<String>) -> Self {
+        self.args.submit_via_proxy = Some(proxy.into());
+        self
+    }
+
+    pub fn submit_via_multisig(mut self, multisig: impl Into<String>) -> Self {
+        self.args.submit_via_multisig = Some(multisig.into());
+        self
+    }
+
+    pub fn call_to_create_fellowship_referendum(mut self, hex: impl Into<String>) -> Self {
+        self.args.call_to_create_fellowship_referendum = Some(hex.into());
+        self
+    }
+
+    pub fn call_to_note_preimage_for_fellowship_referendum(
+        mut self,
+        hex: impl Into<String>,
+    ) -> Self {
+        self.args.call_to_note_preimage_for_fellowship_referendum = Some(hex.into());
+        self
+    }
+
+    pub fn metadata_for_fellowship_referendum(mut self, metadata: impl Into<String>) -> Self {
+        self.args.metadata_for_fellowship_referendum = Some(metadata.into());
+        self
+    }
+
+    pub fn db(mut self, path: impl Into<String>) -> Self {
+        self.args.db = Some(path.into());
+        self
+    }
+
+    pub fn history_dir(mut self, dir: impl Into<String>) -> Self {
+        self.args.history_dir = Some(dir.into());
+        self
+    }
+
+    pub fn diff_with_last(mut self) -> Self {
+        self.args.diff_with_last = true;
+        self
+    }
+
+    pub fn work_dir(mut self, dir: impl Into<String>) -> Self {
+        self.args.work_dir = Some(dir.into());
+        self
+    }
+
+    /// Pass [`OutputFormat::Json`] to have the tool print its `@@result` line.
+    pub fn output_format(mut self, format: OutputFormat) -> Self {
+        self.args.output_format = Some(format);
+        self
+    }
+
+    /// Give this run its own timeout, e.g. seconds for a run that fails before
+    /// forking, regardless of the runner's timeout class.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.args.timeout = Some(timeout);
+        self
+    }
+
+    pub fn verbose(mut self) -> Self {
+        self.args.verbose = true;
+        self
+    }
+
+    /// Set `key` in the tool process's environment; later values win.
+    pub fn env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.args.env.push((key.into(), value.into()));
+        self
+    }
+
+    /// Check the arguments and return them, or the first problem found.
+    pub fn build(self) -> Result<ToolArgs> {
+        let args = self.args;
+        let endpoints = [
+            ("--governance-chain-url", &args.governance_chain_url),
+            ("--fellowship-chain-url", &args.fellowship_chain_url),
+        ];
+        for (flag, endpoint) in endpoints {
+            if let Some(endpoint) = endpoint {
+                check_endpoint(flag, endpoint)?;
+            }
+        }
+        for endpoint in &args.additional_chains {
+            check_endpoint("--additional-chains", endpoint)?;
+        }
+        let calls = [
+            ("--pre-call", &args.pre_call),
+            (
+                "--call-to-create-governance-referendum",
+                &args.call_to_create_governance_referendum,
+            ),
+            (
+                "--call-to-note-preimage-for-governance-referendum",
+                &args.call_to_note_preimage_for_governance_referendum,
+            ),
+            (
+                "--call-to-create-fellowship-referendum",
+                &args.call_to_create_fellowship_referendum,
+            ),
+            (
+                "--call-to-note-preimage-for-fellowship-referendum",
+                &args.call_to_note_preimage_for_fellowship_referendum,
+            ),
+        ];
+        for (flag, hex) in calls {
+            if let Some(hex) = hex {
+                normalize_call_data(flag, hex)?;
+            }
+        }
+        for value in &args.set_storage {
+            check_storage_override(value)?;
+        }
+        for value in &args.override_parameter {
+            ensure!(
+                !value.contains('[') && check_storage_override(value).is_ok(),
+                "--override-parameter: '{value}' is not Namespace.Name=value"
+            );
+        }
+        if let Some(ref range) = args.port_range {
+            let valid = range.split_once(':').is_some_and(|(start, len)| {
+                start.parse::<u16>().is_ok() && len.parse::<u16>().is_ok()
+            });
+            ensure!(valid, "--port-range: '{range}' is not start:len");
+        }
+        for (key, _) in &args.env {
+            ensure!(
+                !key.is_empty() && !key.contains(['=', '\0']),
+                "env: '{key}' is not a variable name"
+            );
+        }
+        flag_registry::check(&args)?;
+        Ok(args)
+    }
+
+    /// Return the arguments as set, without [`Self::build`]'s checks, for
+    /// sub-tests that check the tool's own rejection of them.
+    pub fn build_unchecked(self) -> ToolArgs {
+        self.args
+    }
+}
+
+/// Check a `ws://` or `wss://` URL with a host.
+fn check_ws_url(flag: &str, url: &str) -> Result<()> {
+    let host = url
+        .strip_prefix("ws://")
+        .or_else(|| url.strip_prefix("wss://"));
+    ensure!(
+        host.is_some_and(|host| !host.is_empty() && !host.contains(char::is_whitespace)),
+        "{flag}: '{url}' is not a ws:// or wss:// URL"
+    );
+    Ok(())
+}
+
+/// Parse a block number as the tool does: decimal, or `0x`-prefixed hex.
+fn parse_block(flag: &str, block: &str) -> Result<u32> {
+    let trimmed = block.trim();
+    let (digits, radix) = match trimmed
+        .strip_prefix("0x")
+        .or_else(|| trimmed.strip_prefix("0X"))
+    {
+        Some(hex) => (hex, 16),
+        None => (trimmed, 10),
+    };
+    ensure!(
+        !digits.is_empty() && digits.chars().all(|c| c.is_digit(radix)),
+        "{flag}: '{block}' is not a block number"
+    );
+    u32::from_str_radix(digits, radix)
+        .with_context(|| format!("{flag}: block '{block}' is larger than u32::MAX"))
+}
+
+/// Check an endpoint built field by field: its URL, and a label that would
+/// not split it (`;` separates fields, `,` endpoints).
+fn check_endpoint(flag: &str, endpoint: &ChainEndpoint) -> Result<()> {
+    check_ws_url(flag, &endpoint.url)?;
+    if let Some(ref label) = endpoint.label {
+        ensure!(
+            !label.is_empty() && !label.contains([';', ',']),
+            "{flag}: label '{label}' is empty or contains ';' or ','"
+        );
+    }
+    Ok(())
+}
+
+/// Parse one endpoint: `url`, `url,block` or `name=...;url=...;block=...`.
+fn parse_endpoint(flag: &str, endpoint: &str) -> Result<ChainEndpoint> {
+    if endpoint.contains("url=") {
+        let field = |key: &str| {
+            endpoint
+                .split(';')
+                .find_map(|field| field.trim().strip_prefix(key))
+                .map(str::trim)
+        };
+        let parsed = ChainEndpoint {
+            url: field("url=").unwrap_or_default().to_string(),
+            at_block: field("block=")
+                .map(|block| parse_block(flag, block))
+                .transpose()?,
+            label: field("name=").map(str::to_string),
+        };
+        check_endpoint(flag, &parsed)?;
+        return Ok(parsed);
+    }
+    let (url, block) = match endpoint.split_once(',') {
+        Some((url, block)) => (url, Some(parse_block(flag, block)?)),
+        None => (endpoint, None),
+    };
+    let parsed = ChainEndpoint {
+        url: url.trim().to_string(),
+        at_block: block,
+        label: None,
+    };
+    check_endpoint(flag, &parsed)?;
+    Ok(parsed)
+}
+
+/// Parse a comma-separated endpoint list the way the tool splits it: a plain
+/// URL may be followed by its block number.
+fn parse_endpoints(flag: &str, endpoints: &str) -> Result<Vec<ChainEndpoint>> {
+    let mut parsed = Vec::new();
+    let mut parts = endpoints.split(',').map(str::trim).peekable();
+    while let Some(part) = parts.next() {
+        let mut endpoint = parse_endpoint(flag, part)?;
+        if endpoint.label.is_none() {
+            if let Some(block) = parts.next_if(|next| !next.contains("://")) {
+                endpoint.at_block = Some(parse_block(flag, block)?);
+            }
+        }
+        parsed.push(endpoint);
+    }
+    Ok(parsed)
+}
+
+/// Check the shape of a `--set-storage` value: `Pallet.Item`, then any
+/// `[key]`s, then `=value`. Keys and values are only checked by the tool.
+fn check_storage_override(value: &str) -> Result<()> {
+    let name_end = value.find(['[', '=']).unwrap_or(value.len());
+    let is_ident = |s: &str| {
+        s.starts_with(|c: char| c.is_ascii_alphabetic())
+            && s.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    };
+    let valid_name = value[..name_end]
+        .trim()
+        .split_once('.')
+        .is_some_and(|(pallet, item)| is_ident(pallet) && is_ident(item));
+    let has_value = value
+        .rsplit_once('=')
+        .is_some_and(|(_, v)| !v.trim().is_empty());
+    ensure!(
+        valid_name && has_value,
+        "--set-storage: '{value}' is not Pallet.Item[key]=value"
+    );
+    Ok(())
+}
+
+/// Check hex-encoded call data as loosely as the tool accepts it (whitespace
+/// anywhere, an optional `0x` or `0X`, digits of either case) and return it
+/// the way the tool normalizes it: `0x` and lowercase digits.
+pub fn normalize_call_data(flag: &str, hex: &str) -> Result<String> {
+    let compact: String = hex.chars().filter(|c| !c.is_whitespace()).collect();
+    let digits = compact
+        .strip_prefix("0x")
+        .or_else(|| compact.strip_prefix("0X"))
+        .unwrap_or(&compact);
+    ensure!(
+        !digits.is_empty()
+            && digits.len().is_multiple_of(2)
+            && digits.chars().all(|c| c.is_ascii_hexdigit()),
+        "{flag}: '{hex}' is not hex-encoded bytes"
+    );
+    Ok(format!("0x{}", digits.to_ascii_lowercase()))
+}
@@ -2,30 +2,58 @@
 //!
 //! Each test function spawns a single zombienet network and runs all relevant
 //! sub-tests against it sequentially. This avoids the ~5 min network spawn
-//! overhead that would be incurred by separate test functions.
+//! overhead that would be incurred by separate test functions. Independent
+//! runs, like the Polkadot governance by-number runs, go through
+//! `ToolRunner::run_batch` concurrently, each still labeled as its own sub-test.
 //!
 //! Test suites:
 //! - `polkadot_governance_all_tracks` — 16 governance tracks + scenario tests on Polkadot AH
 //! - `polkadot_fellowship_tracks_part1` — fellowship tracks 1-15 on Polkadot Collectives
 //! - `polkadot_fellowship_tracks_part2` — fellowship tracks 21-33 + multi-chain scenarios
+//! - `ambassador_all_tracks` — 9 ambassador tracks on Polkadot Collectives
 //! - `kusama_governance_all_tracks` — 16 governance tracks + scenario tests on Kusama AH
 //! - `kusama_fellowship_all_tracks` — 10 fellowship tracks + scenario tests on Kusama relay
+//! - `kusama_topology_permutations` — fellowship on the relay and on a Collectives parachain
+//!   of one Kusama network
+//! - `keep_open_inspection` — queries and extends a fork kept alive with `--no-cleanup`
+//! - `whitelist_end_to_end` — fellowship whitelisting on Collectives, dispatch on AH,
+//!   effect on the relay, plus pre-whitelisted and not-whitelisted variants
 //!
 //! By-number tests are enabled by injecting raw storage into genesis via
 //! `with_raw_spec_override()`:
 //! - **AhMigrator**: `MigrationDone` unlocks `Referenda.submit` on Asset Hub
+//! - **Whitelist**: a `System.authorize_upgrade` call whitelisted on Polkadot Asset Hub
 //! - **FellowshipCollective**: Alice registered as rank-9 fellow on Collectives/relay
+//! - **AmbassadorCollective**: Alice registered as rank-9 ambassador on Collectives
 
-use anyhow::Result;
+use anyhow::{ensure, Context, Result};
+use polkadot_referenda_tester_integration_tests::fixture::Proposal;
+use polkadot_referenda_tester_integration_tests::simulation::{Simulation, SimulationConfig};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use subxt::dynamic::Value;
+use subxt::{OnlineClient, PolkadotConfig};
+use subxt_signer::sr25519::dev;
 
 use crate::common::call_data;
 use crate::common::config;
-use crate::common::context::{GovernanceTestContext, KusamaTestContext, MultiChainTestContext};
-use crate::common::extrinsic_submitter;
+use crate::common::context::{
+    query_storage, GovernanceTestContext, KusamaTestContext, MultiChainTestContext,
+    TopologyPermutationContext,
+};
+use crate::common::event_db::EventDb;
+use crate::common::extrinsic_submitter::{self, SubmittedReferendum};
+use crate::common::logging;
 use crate::common::network::{initialize_network, verify_binaries};
+use crate::common::override_audit;
 use crate::common::port_allocator;
+use crate::common::provenance;
+use crate::common::raw_storage;
 use crate::common::run_and_bail;
-use crate::common::tool_runner::{ToolArgs, ToolRunner};
+use crate::common::tool_runner::{
+    ChainEndpoint, OutputFormat, RetryPolicy, Stream, ToolArgs, ToolErrorKind, ToolOutput,
+    ToolRunner,
+};
 use crate::common::tracks;
 
 // ═══════════════════════════════════════════════════════════════════════════
@@ -34,92 +62,209 @@ use crate::common::tracks;
 
 #[tokio::test(flavor = "multi_thread")]
 async fn polkadot_governance_all_tracks() {
-    env_logger::try_init().ok();
-    verify_binaries().expect("binary verification failed");
+    logging::suite("polkadot_governance_all_tracks", async {
+        verify_binaries().expect("binary verification failed");
+        provenance::record("polkadot_governance_all_tracks").expect("failed to record provenance");
+
+        let network_config =
+            config::build_polkadot_with_asset_hub().expect("failed to build network config");
+        let network = initialize_network(network_config)
+            .await
+            .expect("failed to spawn zombienet");
+        let mut ctx = GovernanceTestContext::from_network(&network)
+            .await
+            .expect("failed to build context");
+
+        let event_db = EventDb::for_suite("polkadot_governance_all_tracks")
+            .expect("failed to create event db");
+        let runner = ToolRunner::new()
+            .with_event_db(Arc::new(event_db))
+            .with_fork_cache(
+                config::get_artifacts_dir("polkadot_governance_all_tracks")
+                    .join("chopsticks.sqlite"),
+            );
+        let mut errors: Vec<String> = Vec::new();
+
+        // By-number tests rely on the raw spec overrides; check them first.
+        run_and_bail!(
+            errors,
+            "gov_raw_override_audit",
+            run_governance_raw_override_audit(&ctx)
+        );
 
-    let network_config =
-        config::build_polkadot_with_asset_hub().expect("failed to build network config");
-    let network = initialize_network(network_config)
-        .await
-        .expect("failed to spawn zombienet");
-    let mut ctx = GovernanceTestContext::from_network(&network)
+        // Create every by-number referendum up-front in one pipelined submission.
+        let submitted = extrinsic_submitter::submit_governance_referenda(
+            &ctx.ah_client,
+            tracks::GOVERNANCE_TRACKS,
+            "Origins",
+        )
         .await
-        .expect("failed to build context");
+        .expect("failed to submit by-number referenda");
+
+        // ── Per-track tests (create for each track, by-number batched) ───────
+
+        for track in tracks::GOVERNANCE_TRACKS.iter() {
+            run_and_bail!(
+                errors,
+                format!("gov_create_{}", track.name),
+                run_gov_create_test(&ctx, &runner, track)
+            );
+        }
+        let outputs = run_gov_bynum_batch(&ctx, &runner, &submitted)
+            .await
+            .expect("failed to build by-number runs");
+        for ((track, submitted), output) in tracks::GOVERNANCE_TRACKS
+            .iter()
+            .zip(&submitted)
+            .zip(outputs)
+        {
+            let mut batched = Some(output);
+            run_and_bail!(
+                errors,
+                gov_bynum_label(track),
+                run_gov_bynum_test(&ctx, &runner, track, submitted, batched.take())
+            );
+        }
+
+        // ── Scenario tests ───────────────────────────────────────────────────
+
+        ctx.refresh_fork_blocks()
+            .await
+            .expect("failed to refresh fork blocks");
 
-    let runner = ToolRunner::new();
-    let mut errors: Vec<String> = Vec::new();
-
-    // ── Per-track tests (create + by-number for each track) ──────────────
-
-    for track in tracks::GOVERNANCE_TRACKS {
         run_and_bail!(
             errors,
-            format!("gov_create_{}", track.name),
-            run_gov_create_test(&ctx, &runner, track)
+            "gov_happy_path",
+            run_governance_happy_path(&ctx, &runner)
         );
         run_and_bail!(
             errors,
-            format!("gov_bynum_{}", track.name),
-            run_gov_bynum_test(&ctx, &runner, track)
+            "gov_set_storage",
+            run_governance_set_storage(&ctx, &runner)
         );
-    }
-
-    // ── Scenario tests ───────────────────────────────────────────────────
-
-    ctx.refresh_fork_blocks()
-        .await
-        .expect("failed to refresh fork blocks");
-
-    run_and_bail!(
-        errors,
-        "gov_happy_path",
-        run_governance_happy_path(&ctx, &runner)
-    );
-    run_and_bail!(
-        errors,
-        "gov_dispatch_failure",
-        run_governance_dispatch_failure(&ctx, &runner)
-    );
-    run_and_bail!(
-        errors,
-        "gov_pre_call_remark",
-        run_governance_with_pre_call(&ctx, &runner)
-    );
-    run_and_bail!(
-        errors,
-        "gov_remark_proposal",
-        run_governance_remark_proposal(&ctx, &runner)
-    );
-    run_and_bail!(
-        errors,
-        "gov_invalid_hex",
-        run_governance_invalid_hex(&ctx, &runner)
-    );
-    run_and_bail!(
-        errors,
-        "gov_pre_call_non_root_origin",
-        run_governance_pre_call_non_root_origin(&ctx, &runner)
-    );
-    run_and_bail!(
-        errors,
-        "gov_pre_call_invalid_origin",
-        run_governance_pre_call_invalid_origin(&ctx, &runner)
-    );
-    run_and_bail!(
-        errors,
-        "gov_create_no_preimage",
-        run_governance_create_no_preimage(&ctx, &runner)
-    );
-    run_and_bail!(
-        errors,
-        "gov_inline_create",
-        run_governance_inline_create(&ctx, &runner)
-    );
-    run_and_bail!(
-        errors,
-        "gov_inline_bynum",
-        run_governance_inline_bynum(&ctx, &runner)
-    );
+        run_and_bail!(
+            errors,
+            "gov_override_parameter",
+            run_governance_override_parameter(&ctx, &runner)
+        );
+        run_and_bail!(
+            errors,
+            "gov_fund_and_unlock",
+            run_governance_fund_and_unlock(&ctx, &runner)
+        );
+        run_and_bail!(
+            errors,
+            "gov_simulation_api",
+            run_governance_simulation_api(&ctx)
+        );
+        run_and_bail!(
+            errors,
+            "gov_fixture_proposal_files",
+            run_governance_fixture_proposal_files(&ctx)
+        );
+        run_and_bail!(
+            errors,
+            "gov_dispatch_failure",
+            run_governance_dispatch_failure(&ctx, &runner)
+        );
+        run_and_bail!(
+            errors,
+            "gov_pre_call_remark",
+            run_governance_with_pre_call(&ctx, &runner)
+        );
+        run_and_bail!(
+            errors,
+            "gov_remark_proposal",
+            run_governance_remark_proposal(&ctx, &runner)
+        );
+        run_and_bail!(
+            errors,
+            "gov_invalid_hex",
+            run_governance_invalid_hex(&ctx, &runner)
+        );
+        run_and_bail!(
+            errors,
+            "gov_pre_call_non_root_origin",
+            run_governance_pre_call_non_root_origin(&ctx, &runner)
+        );
+        run_and_bail!(
+            errors,
+            "gov_pre_call_invalid_origin",
+            run_governance_pre_call_invalid_origin(&ctx, &runner)
+        );
+        run_and_bail!(
+            errors,
+            "gov_create_no_preimage",
+            run_governance_create_no_preimage(&ctx, &runner)
+        );
+        run_and_bail!(
+            errors,
+            "gov_inline_create",
+            run_governance_inline_create(&ctx, &runner)
+        );
+        run_and_bail!(
+            errors,
+            "gov_inline_bynum",
+            run_governance_inline_bynum(&ctx, &runner)
+        );
+        run_and_bail!(
+            errors,
+            "gov_proxy_create",
+            run_governance_proxy_create(&ctx, &runner)
+        );
+        run_and_bail!(
+            errors,
+            "gov_proxy_bynum",
+            run_governance_proxy_bynum(&ctx, &runner)
+        );
+        run_and_bail!(
+            errors,
+            "gov_multisig_create",
+            run_governance_multisig_create(&ctx, &runner)
+        );
+        run_and_bail!(
+            errors,
+            "gov_multisig_bynum",
+            run_governance_multisig_bynum(&ctx, &runner)
+        );
+        run_and_bail!(
+            errors,
+            "gov_deterministic",
+            run_governance_deterministic(&ctx, &runner)
+        );
+        run_and_bail!(
+            errors,
+            "gov_bad_origin",
+            run_governance_bad_origin(&ctx, &runner)
+        );
+        run_and_bail!(
+            errors,
+            "gov_enactment_retries",
+            run_governance_enactment_retries(&ctx, &runner)
+        );
+        run_and_bail!(
+            errors,
+            "gov_cancel_confirming",
+            run_governance_cancel_confirming(&ctx, &runner)
+        );
+        run_and_bail!(
+            errors,
+            "gov_metadata",
+            run_governance_metadata(&ctx, &runner)
+        );
+        run_and_bail!(errors, "gov_preview", run_governance_preview(&ctx, &runner));
+        run_and_bail!(
+            errors,
+            "gov_sloppy_call_data",
+            run_governance_sloppy_call_data(&ctx, &runner)
+        );
+        run_and_bail!(
+            errors,
+            "gov_blocks_after_dispatch",
+            run_governance_blocks_after_dispatch(&ctx, &runner)
+        );
+    })
+    .await
 }
 
 // ═══════════════════════════════════════════════════════════════════════════
@@ -127,116 +272,233 @@ async fn polkadot_governance_all_tracks() {
 // Each spawns its own network; scenarios run in part2 only.
 // ═══════════════════════════════════════════════════════════════════════════
 
-/// Tracks 1-15 (Members through RetainAt5Dan): 15 tracks × 2 = 30 sub-tests.
+/// Tracks 1-15 (Members through RetainAt5Dan): 15 tracks × 2 = 30 sub-tests,
+/// after the raw override audit.
 #[tokio::test(flavor = "multi_thread")]
 async fn polkadot_fellowship_tracks_part1() {
-    env_logger::try_init().ok();
-    verify_binaries().expect("binary verification failed");
-
-    let network_config =
-        config::build_polkadot_with_system_parachains().expect("failed to build network config");
-    let network = initialize_network(network_config)
-        .await
-        .expect("failed to spawn zombienet");
-    let ctx = MultiChainTestContext::from_network(&network)
-        .await
-        .expect("failed to build context");
-
-    let runner = ToolRunner::new();
-    let mut errors: Vec<String> = Vec::new();
-
-    for track in &tracks::POLKADOT_FELLOWSHIP_TRACKS[..15] {
-        run_and_bail!(
-            errors,
-            format!("fell_create_{}", track.name),
-            run_polkadot_fellowship_create_test(&ctx, &runner, track)
-        );
+    logging::suite("polkadot_fellowship_tracks_part1", async {
+        verify_binaries().expect("binary verification failed");
+        provenance::record("polkadot_fellowship_tracks_part1")
+            .expect("failed to record provenance");
+
+        let network_config = config::build_polkadot_with_system_parachains()
+            .expect("failed to build network config");
+        let network = initialize_network(network_config)
+            .await
+            .expect("failed to spawn zombienet");
+        let ctx = MultiChainTestContext::from_network(&network)
+            .await
+            .expect("failed to build context");
+
+        let event_db = EventDb::for_suite("polkadot_fellowship_tracks_part1")
+            .expect("failed to create event db");
+        let runner = ToolRunner::new()
+            .with_event_db(Arc::new(event_db))
+            .with_fork_cache(
+                config::get_artifacts_dir("polkadot_fellowship_tracks_part1")
+                    .join("chopsticks.sqlite"),
+            );
+        let mut errors: Vec<String> = Vec::new();
+
+        // By-number tests rely on the raw spec overrides; check them first.
         run_and_bail!(
             errors,
-            format!("fell_bynum_{}", track.name),
-            run_polkadot_fellowship_bynum_test(&ctx, &runner, track)
+            "fell_raw_override_audit",
+            run_fellowship_raw_override_audit(&ctx)
         );
-    }
+
+        // Create every by-number referendum up-front in one pipelined submission.
+        let fellowship_tracks = &tracks::POLKADOT_FELLOWSHIP_TRACKS[..15];
+        let submitted = extrinsic_submitter::submit_fellowship_referenda(
+            &ctx.coll_client,
+            fellowship_tracks,
+            "FellowshipOrigins",
+        )
+        .await
+        .expect("failed to submit by-number referenda");
+
+        for (track, submitted) in fellowship_tracks.iter().zip(&submitted) {
+            run_and_bail!(
+                errors,
+                format!("fell_create_{}", track.name),
+                run_polkadot_fellowship_create_test(&ctx, &runner, track)
+            );
+            run_and_bail!(
+                errors,
+                format!("fell_bynum_{}", track.name),
+                run_polkadot_fellowship_bynum_test(&ctx, &runner, track, submitted)
+            );
+        }
+    })
+    .await
 }
 
 /// Tracks 21-33 (PromoteTo1Dan through FastPromoteTo3Dan): 9 tracks × 2 = 18 sub-tests
-/// + 6 multi-chain scenario tests = 24 sub-tests total.
+/// + 9 multi-chain scenario tests = 27 sub-tests total.
 #[tokio::test(flavor = "multi_thread")]
 async fn polkadot_fellowship_tracks_part2() {
-    env_logger::try_init().ok();
-    verify_binaries().expect("binary verification failed");
-
-    let network_config =
-        config::build_polkadot_with_system_parachains().expect("failed to build network config");
-    let network = initialize_network(network_config)
-        .await
-        .expect("failed to spawn zombienet");
-    let mut ctx = MultiChainTestContext::from_network(&network)
+    logging::suite("polkadot_fellowship_tracks_part2", async {
+        verify_binaries().expect("binary verification failed");
+        provenance::record("polkadot_fellowship_tracks_part2")
+            .expect("failed to record provenance");
+
+        let network_config = config::build_polkadot_with_system_parachains()
+            .expect("failed to build network config");
+        let network = initialize_network(network_config)
+            .await
+            .expect("failed to spawn zombienet");
+        let mut ctx = MultiChainTestContext::from_network(&network)
+            .await
+            .expect("failed to build context");
+
+        let event_db = EventDb::for_suite("polkadot_fellowship_tracks_part2")
+            .expect("failed to create event db");
+        let runner = ToolRunner::new()
+            .with_event_db(Arc::new(event_db))
+            .with_fork_cache(
+                config::get_artifacts_dir("polkadot_fellowship_tracks_part2")
+                    .join("chopsticks.sqlite"),
+            );
+        let mut errors: Vec<String> = Vec::new();
+
+        // Create every by-number referendum up-front in one pipelined submission.
+        let fellowship_tracks = &tracks::POLKADOT_FELLOWSHIP_TRACKS[15..];
+        let submitted = extrinsic_submitter::submit_fellowship_referenda(
+            &ctx.coll_client,
+            fellowship_tracks,
+            "FellowshipOrigins",
+        )
         .await
-        .expect("failed to build context");
+        .expect("failed to submit by-number referenda");
+
+        for (track, submitted) in fellowship_tracks.iter().zip(&submitted) {
+            run_and_bail!(
+                errors,
+                format!("fell_create_{}", track.name),
+                run_polkadot_fellowship_create_test(&ctx, &runner, track)
+            );
+            run_and_bail!(
+                errors,
+                format!("fell_bynum_{}", track.name),
+                run_polkadot_fellowship_bynum_test(&ctx, &runner, track, submitted)
+            );
+        }
+
+        // ── Multi-chain scenario tests ───────────────────────────────────────
+
+        ctx.refresh_fork_blocks()
+            .await
+            .expect("failed to refresh fork blocks");
 
-    let runner = ToolRunner::new();
-    let mut errors: Vec<String> = Vec::new();
-
-    for track in &tracks::POLKADOT_FELLOWSHIP_TRACKS[15..] {
         run_and_bail!(
             errors,
-            format!("fell_create_{}", track.name),
-            run_polkadot_fellowship_create_test(&ctx, &runner, track)
+            "multichain_happy_path",
+            run_multichain_happy_path(&ctx, &runner)
         );
         run_and_bail!(
             errors,
-            format!("fell_bynum_{}", track.name),
-            run_polkadot_fellowship_bynum_test(&ctx, &runner, track)
+            "multichain_partial_success",
+            run_multichain_partial_success(&ctx, &runner)
         );
-    }
+        run_and_bail!(
+            errors,
+            "fellowship_only",
+            run_fellowship_only(&ctx, &runner)
+        );
+        run_and_bail!(
+            errors,
+            "nonexistent_referendum",
+            run_nonexistent_referendum(&ctx, &runner)
+        );
+        run_and_bail!(
+            errors,
+            "fellowship_create_no_preimage",
+            run_fellowship_create_no_preimage(&ctx, &runner)
+        );
+        run_and_bail!(
+            errors,
+            "gov_with_additional_chains",
+            run_governance_with_additional_chains(&ctx, &runner)
+        );
+        run_and_bail!(
+            errors,
+            "fell_with_additional_chains",
+            run_fellowship_with_additional_chains(&ctx, &runner)
+        );
+        run_and_bail!(
+            errors,
+            "fell_inline_create",
+            run_fellowship_inline_create(&ctx, &runner)
+        );
+        run_and_bail!(
+            errors,
+            "fell_inline_bynum",
+            run_fellowship_inline_bynum(&ctx, &runner)
+        );
+    })
+    .await
+}
 
-    // ── Multi-chain scenario tests ───────────────────────────────────────
+// ═══════════════════════════════════════════════════════════════════════════
+// Polkadot Ambassador — all 9 tracks on Collectives
+// ═══════════════════════════════════════════════════════════════════════════
 
-    ctx.refresh_fork_blocks()
-        .await
-        .expect("failed to refresh fork blocks");
+/// 9 tracks × 2 = 18 sub-tests, after the raw override audit. Ambassador
+/// referenda are given to the tool as `--referendum AmbassadorReferenda:<id>`,
+/// or created from an `AmbassadorReferenda.submit` call, on the Collectives
+/// fork as governance chain.
+#[tokio::test(flavor = "multi_thread")]
+async fn ambassador_all_tracks() {
+    logging::suite("ambassador_all_tracks", async {
+        verify_binaries().expect("binary verification failed");
+        provenance::record("ambassador_all_tracks").expect("failed to record provenance");
+
+        let network_config = config::build_polkadot_with_system_parachains()
+            .expect("failed to build network config");
+        let network = initialize_network(network_config)
+            .await
+            .expect("failed to spawn zombienet");
+        let ctx = MultiChainTestContext::from_network(&network)
+            .await
+            .expect("failed to build context");
+
+        let event_db =
+            EventDb::for_suite("ambassador_all_tracks").expect("failed to create event db");
+        let runner = ToolRunner::new()
+            .with_event_db(Arc::new(event_db))
+            .with_fork_cache(
+                config::get_artifacts_dir("ambassador_all_tracks").join("chopsticks.sqlite"),
+            );
+        let mut errors: Vec<String> = Vec::new();
+
+        // By-number tests rely on the raw spec overrides; check them first.
+        run_and_bail!(
+            errors,
+            "amb_raw_override_audit",
+            run_ambassador_raw_override_audit(&ctx)
+        );
 
-    run_and_bail!(
-        errors,
-        "multichain_happy_path",
-        run_multichain_happy_path(&ctx, &runner)
-    );
-    run_and_bail!(
-        errors,
-        "fellowship_only",
-        run_fellowship_only(&ctx, &runner)
-    );
-    run_and_bail!(
-        errors,
-        "nonexistent_referendum",
-        run_nonexistent_referendum(&ctx, &runner)
-    );
-    run_and_bail!(
-        errors,
-        "fellowship_create_no_preimage",
-        run_fellowship_create_no_preimage(&ctx, &runner)
-    );
-    run_and_bail!(
-        errors,
-        "gov_with_additional_chains",
-        run_governance_with_additional_chains(&ctx, &runner)
-    );
-    run_and_bail!(
-        errors,
-        "fell_with_additional_chains",
-        run_fellowship_with_additional_chains(&ctx, &runner)
-    );
-    run_and_bail!(
-        errors,
-        "fell_inline_create",
-        run_fellowship_inline_create(&ctx, &runner)
-    );
-    run_and_bail!(
-        errors,
-        "fell_inline_bynum",
-        run_fellowship_inline_bynum(&ctx, &runner)
-    );
+        // Create every by-number referendum up-front in one pipelined submission.
+        let ambassador_tracks = tracks::POLKADOT_AMBASSADOR_TRACKS;
+        let submitted =
+            extrinsic_submitter::submit_ambassador_referenda(&ctx.coll_client, ambassador_tracks)
+                .await
+                .expect("failed to submit by-number referenda");
+
+        for (track, submitted) in ambassador_tracks.iter().zip(&submitted) {
+            run_and_bail!(
+                errors,
+                format!("amb_create_{}", track.name),
+                run_ambassador_create_test(&ctx, &runner, track)
+            );
+            run_and_bail!(
+                errors,
+                format!("amb_bynum_{}", track.name),
+                run_ambassador_bynum_test(&ctx, &runner, track, submitted)
+            );
+        }
+    })
+    .await
 }
 
 // ═══════════════════════════════════════════════════════════════════════════
@@ -245,127 +507,365 @@ async fn polkadot_fellowship_tracks_part2() {
 
 #[tokio::test(flavor = "multi_thread")]
 async fn kusama_governance_all_tracks() {
-    env_logger::try_init().ok();
-    verify_binaries().expect("binary verification failed");
+    logging::suite("kusama_governance_all_tracks", async {
+        verify_binaries().expect("binary verification failed");
+        provenance::record("kusama_governance_all_tracks").expect("failed to record provenance");
+
+        let network_config =
+            config::build_kusama_with_asset_hub().expect("failed to build network config");
+        let network = initialize_network(network_config)
+            .await
+            .expect("failed to spawn zombienet");
+        let mut ctx = KusamaTestContext::from_network(&network)
+            .await
+            .expect("failed to build context");
+
+        let event_db =
+            EventDb::for_suite("kusama_governance_all_tracks").expect("failed to create event db");
+        let runner = ToolRunner::new()
+            .with_event_db(Arc::new(event_db))
+            .with_fork_cache(
+                config::get_artifacts_dir("kusama_governance_all_tracks").join("chopsticks.sqlite"),
+            );
+        let mut errors: Vec<String> = Vec::new();
+
+        // By-number tests rely on the raw spec overrides; check them first.
+        run_and_bail!(
+            errors,
+            "ksm_raw_override_audit",
+            run_kusama_raw_override_audit(&ctx)
+        );
 
-    let network_config =
-        config::build_kusama_with_asset_hub().expect("failed to build network config");
-    let network = initialize_network(network_config)
-        .await
-        .expect("failed to spawn zombienet");
-    let mut ctx = KusamaTestContext::from_network(&network)
+        // Create every by-number referendum up-front in one pipelined submission.
+        let submitted = extrinsic_submitter::submit_governance_referenda(
+            &ctx.ah_client,
+            tracks::GOVERNANCE_TRACKS,
+            "Origins",
+        )
         .await
-        .expect("failed to build context");
+        .expect("failed to submit by-number referenda");
+
+        // ── Per-track tests (create + by-number for each track) ──────────────
+
+        for (track, submitted) in tracks::GOVERNANCE_TRACKS.iter().zip(&submitted) {
+            run_and_bail!(
+                errors,
+                format!("ksm_gov_create_{}", track.name),
+                run_kusama_gov_create_test(&ctx, &runner, track)
+            );
+            run_and_bail!(
+                errors,
+                format!("ksm_gov_bynum_{}", track.name),
+                run_kusama_gov_bynum_test(&ctx, &runner, track, submitted)
+            );
+        }
 
-    let runner = ToolRunner::new();
-    let mut errors: Vec<String> = Vec::new();
+        // ── Scenario test ────────────────────────────────────────────────────
 
-    // ── Per-track tests (create + by-number for each track) ──────────────
+        ctx.refresh_fork_blocks()
+            .await
+            .expect("failed to refresh fork blocks");
 
-    for track in tracks::GOVERNANCE_TRACKS {
         run_and_bail!(
             errors,
-            format!("ksm_gov_create_{}", track.name),
-            run_kusama_gov_create_test(&ctx, &runner, track)
+            "ksm_gov_happy_path",
+            run_kusama_governance_happy_path(&ctx, &runner)
         );
         run_and_bail!(
             errors,
-            format!("ksm_gov_bynum_{}", track.name),
-            run_kusama_gov_bynum_test(&ctx, &runner, track)
+            "ksm_gov_inline_create",
+            run_kusama_governance_inline_create(&ctx, &runner)
         );
-    }
+        run_and_bail!(
+            errors,
+            "ksm_gov_inline_bynum",
+            run_kusama_governance_inline_bynum(&ctx, &runner)
+        );
+    })
+    .await
+}
 
-    // ── Scenario test ────────────────────────────────────────────────────
+// ═══════════════════════════════════════════════════════════════════════════
+// Kusama Fellowship — all 10 tracks + scenario tests
+// ═══════════════════════════════════════════════════════════════════════════
 
-    ctx.refresh_fork_blocks()
-        .await
-        .expect("failed to refresh fork blocks");
+#[tokio::test(flavor = "multi_thread")]
+async fn kusama_fellowship_all_tracks() {
+    logging::suite("kusama_fellowship_all_tracks", async {
+        verify_binaries().expect("binary verification failed");
+        provenance::record("kusama_fellowship_all_tracks").expect("failed to record provenance");
+
+        let network_config =
+            config::build_kusama_with_asset_hub().expect("failed to build network config");
+        let network = initialize_network(network_config)
+            .await
+            .expect("failed to spawn zombienet");
+        let mut ctx = KusamaTestContext::from_network(&network)
+            .await
+            .expect("failed to build context");
+
+        let event_db =
+            EventDb::for_suite("kusama_fellowship_all_tracks").expect("failed to create event db");
+        let runner = ToolRunner::new()
+            .with_event_db(Arc::new(event_db))
+            .with_fork_cache(
+                config::get_artifacts_dir("kusama_fellowship_all_tracks").join("chopsticks.sqlite"),
+            );
+        let mut errors: Vec<String> = Vec::new();
+
+        // Note every by-number preimage in one batch up-front. Referenda are still
+        // submitted per sub-test: these are flaky near session boundaries, and a
+        // retry needs a fresh referendum at a fresh fork block.
+        extrinsic_submitter::note_fellowship_preimages(
+            &ctx.relay_client,
+            tracks::KUSAMA_FELLOWSHIP_TRACKS,
+        )
+        .await;
 
-    run_and_bail!(
-        errors,
-        "ksm_gov_happy_path",
-        run_kusama_governance_happy_path(&ctx, &runner)
-    );
-    run_and_bail!(
-        errors,
-        "ksm_gov_inline_create",
-        run_kusama_governance_inline_create(&ctx, &runner)
-    );
-    run_and_bail!(
-        errors,
-        "ksm_gov_inline_bynum",
-        run_kusama_governance_inline_bynum(&ctx, &runner)
-    );
+        // ── Per-track tests (create + by-number for each track) ──────────────
+
+        for track in tracks::KUSAMA_FELLOWSHIP_TRACKS {
+            run_and_bail!(
+                errors,
+                format!("ksm_fell_create_{}", track.name),
+                run_kusama_fellowship_create_test(&ctx, &runner, track)
+            );
+            run_and_bail!(
+                errors,
+                format!("ksm_fell_bynum_{}", track.name),
+                run_kusama_fellowship_bynum_test(&ctx, &runner, track)
+            );
+        }
+
+        // ── Scenario tests ───────────────────────────────────────────────────
+
+        ctx.refresh_fork_blocks()
+            .await
+            .expect("failed to refresh fork blocks");
+
+        run_and_bail!(
+            errors,
+            "ksm_multichain_happy_path",
+            run_kusama_multichain_happy_path(&ctx, &runner)
+        );
+        run_and_bail!(
+            errors,
+            "ksm_fellowship_on_relay",
+            run_kusama_fellowship_on_relay(&ctx, &runner)
+        );
+        run_and_bail!(
+            errors,
+            "ksm_fell_inline_create",
+            run_kusama_fellowship_inline_create(&ctx, &runner)
+        );
+        run_and_bail!(
+            errors,
+            "ksm_fell_inline_bynum",
+            run_kusama_fellowship_inline_bynum(&ctx, &runner)
+        );
+        run_and_bail!(
+            errors,
+            "ksm_fell_session_boundary",
+            run_kusama_fellowship_session_boundary(&ctx, &runner)
+        );
+    })
+    .await
 }
 
 // ═══════════════════════════════════════════════════════════════════════════
-// Kusama Fellowship — all 10 tracks + scenario tests
+// Kusama + Collectives — fellowship topology permutations
 // ═══════════════════════════════════════════════════════════════════════════
 
 #[tokio::test(flavor = "multi_thread")]
-async fn kusama_fellowship_all_tracks() {
-    env_logger::try_init().ok();
-    verify_binaries().expect("binary verification failed");
+async fn kusama_topology_permutations() {
+    logging::suite("kusama_topology_permutations", async {
+        verify_binaries().expect("binary verification failed");
+        provenance::record("kusama_topology_permutations").expect("failed to record provenance");
+
+        let network_config =
+            config::build_kusama_with_collectives().expect("failed to build network config");
+        let network = initialize_network(network_config)
+            .await
+            .expect("failed to spawn zombienet");
+        let mut ctx = TopologyPermutationContext::from_network(&network)
+            .await
+            .expect("failed to build context");
+
+        let event_db =
+            EventDb::for_suite("kusama_topology_permutations").expect("failed to create event db");
+        let runner = ToolRunner::new()
+            .with_event_db(Arc::new(event_db))
+            .with_fork_cache(
+                config::get_artifacts_dir("kusama_topology_permutations").join("chopsticks.sqlite"),
+            );
+        let mut errors: Vec<String> = Vec::new();
 
-    let network_config =
-        config::build_kusama_with_asset_hub().expect("failed to build network config");
-    let network = initialize_network(network_config)
-        .await
-        .expect("failed to spawn zombienet");
-    let mut ctx = KusamaTestContext::from_network(&network)
-        .await
-        .expect("failed to build context");
+        run_and_bail!(
+            errors,
+            "perm_fell_on_relay",
+            run_permutation_fellowship_on_relay(&ctx, &runner)
+        );
+        run_and_bail!(
+            errors,
+            "perm_fell_on_parachain",
+            run_permutation_fellowship_on_parachain(&ctx, &runner)
+        );
+
+        ctx.refresh_fork_blocks()
+            .await
+            .expect("failed to refresh fork blocks");
+
+        run_and_bail!(
+            errors,
+            "perm_multichain_fell_on_relay",
+            run_permutation_multichain_fellowship_on_relay(&ctx, &runner)
+        );
+        run_and_bail!(
+            errors,
+            "perm_multichain_fell_on_parachain",
+            run_permutation_multichain_fellowship_on_parachain(&ctx, &runner)
+        );
+    })
+    .await
+}
 
-    let runner = ToolRunner::new();
-    let mut errors: Vec<String> = Vec::new();
+// ═══════════════════════════════════════════════════════════════════════════
+// Keep-open — post-run inspection of kept-alive forks
+// ═══════════════════════════════════════════════════════════════════════════
 
-    // ── Per-track tests (create + by-number for each track) ──────────────
+#[tokio::test(flavor = "multi_thread")]
+async fn keep_open_inspection() {
+    logging::suite("keep_open_inspection", async {
+        verify_binaries().expect("binary verification failed");
+        provenance::record("keep_open_inspection").expect("failed to record provenance");
+
+        let network_config =
+            config::build_polkadot_with_asset_hub().expect("failed to build network config");
+        let network = initialize_network(network_config)
+            .await
+            .expect("failed to spawn zombienet");
+        let ctx = GovernanceTestContext::from_network(&network)
+            .await
+            .expect("failed to build context");
+
+        let event_db =
+            EventDb::for_suite("keep_open_inspection").expect("failed to create event db");
+        let runner = ToolRunner::new()
+            .with_event_db(Arc::new(event_db))
+            .with_fork_cache(
+                config::get_artifacts_dir("keep_open_inspection").join("chopsticks.sqlite"),
+            );
+        let mut errors: Vec<String> = Vec::new();
 
-    for track in tracks::KUSAMA_FELLOWSHIP_TRACKS {
         run_and_bail!(
             errors,
-            format!("ksm_fell_create_{}", track.name),
-            run_kusama_fellowship_create_test(&ctx, &runner, track)
+            "keep_open_post_state",
+            run_keep_open_post_state(&ctx)
         );
         run_and_bail!(
             errors,
-            format!("ksm_fell_bynum_{}", track.name),
-            run_kusama_fellowship_bynum_test(&ctx, &runner, track)
+            "session_post_state",
+            run_session_post_state(&ctx, &runner)
         );
-    }
+    })
+    .await
+}
 
-    // ── Scenario tests ───────────────────────────────────────────────────
+// ═══════════════════════════════════════════════════════════════════════════
+// Whitelist — fellowship-whitelisted governance calls end to end
+// ═══════════════════════════════════════════════════════════════════════════
 
-    ctx.refresh_fork_blocks()
-        .await
-        .expect("failed to refresh fork blocks");
+/// The whitelist flow across Polkadot's chains: a fellowship referendum on
+/// Collectives whitelists a call on Asset Hub over XCM, and a
+/// `WhitelistedCaller` referendum on Asset Hub dispatches it, authorizing a
+/// relay upgrade the relay fork must show. Variants rely on the call
+/// whitelisted in Asset Hub's genesis (`raw_storage::whitelist_entries`);
+/// negative cases dispatch a call nobody whitelisted.
+#[tokio::test(flavor = "multi_thread")]
+async fn whitelist_end_to_end() {
+    logging::suite("whitelist_end_to_end", async {
+        verify_binaries().expect("binary verification failed");
+        provenance::record("whitelist_end_to_end").expect("failed to record provenance");
+
+        let network_config = config::build_polkadot_with_system_parachains()
+            .expect("failed to build network config");
+        let network = initialize_network(network_config)
+            .await
+            .expect("failed to spawn zombienet");
+        let mut ctx = MultiChainTestContext::from_network(&network)
+            .await
+            .expect("failed to build context");
+
+        let event_db =
+            EventDb::for_suite("whitelist_end_to_end").expect("failed to create event db");
+        let runner = ToolRunner::new()
+            .with_event_db(Arc::new(event_db))
+            .with_fork_cache(
+                config::get_artifacts_dir("whitelist_end_to_end").join("chopsticks.sqlite"),
+            );
+        let mut errors: Vec<String> = Vec::new();
+
+        // The pre-whitelisted variants rely on the raw spec override; check it first.
+        run_and_bail!(
+            errors,
+            "wl_raw_override_audit",
+            run_whitelist_raw_override_audit(&ctx)
+        );
 
-    run_and_bail!(
-        errors,
-        "ksm_multichain_happy_path",
-        run_kusama_multichain_happy_path(&ctx, &runner)
-    );
-    run_and_bail!(
-        errors,
-        "ksm_fellowship_on_relay",
-        run_kusama_fellowship_on_relay(&ctx, &runner)
-    );
-    run_and_bail!(
-        errors,
-        "ksm_fell_inline_create",
-        run_kusama_fellowship_inline_create(&ctx, &runner)
-    );
-    run_and_bail!(
-        errors,
-        "ksm_fell_inline_bynum",
-        run_kusama_fellowship_inline_bynum(&ctx, &runner)
-    );
+        let proposal = call_data::whitelisted_dispatch_call(
+            &ctx.ah_client,
+            &raw_storage::pre_whitelisted_call(),
+        )
+        .expect("failed to encode the whitelisted dispatch");
+        let submitted =
+            extrinsic_submitter::submit_whitelisted_caller_referendum(&ctx.ah_client, proposal)
+                .await
+                .expect("failed to submit the by-number referendum");
+
+        ctx.refresh_fork_blocks()
+            .await
+            .expect("failed to refresh fork blocks");
+
+        run_and_bail!(
+            errors,
+            "wl_fellowship_whitelists",
+            run_whitelist_fellowship_whitelists(&ctx, &runner)
+        );
+        run_and_bail!(
+            errors,
+            "wl_pre_whitelisted_create",
+            run_whitelist_pre_whitelisted_create(&ctx, &runner)
+        );
+        run_and_bail!(
+            errors,
+            "wl_pre_whitelisted_bynum",
+            run_whitelist_pre_whitelisted_bynum(&ctx, &runner, &submitted)
+        );
+        run_and_bail!(
+            errors,
+            "wl_not_whitelisted",
+            run_whitelist_not_whitelisted(&ctx, &runner)
+        );
+        run_and_bail!(
+            errors,
+            "wl_fellowship_whitelists_other_call",
+            run_whitelist_fellowship_whitelists_other_call(&ctx, &runner)
+        );
+    })
+    .await
 }
 
 // ═══════════════════════════════════════════════════════════════════════════
 // Sub-test implementations — Polkadot Governance (per-track create)
 // ═══════════════════════════════════════════════════════════════════════════
 
+/// The AhMigrator and Whitelist overrides are in Asset Hub's genesis state
+/// and decode against its runtime metadata.
+async fn run_governance_raw_override_audit(ctx: &GovernanceTestContext) -> Result<()> {
+    log::info!(">>> gov_raw_override_audit");
+    override_audit::audit_chain(&ctx.ah_client, &raw_storage::asset_hub_polkadot_entries()).await
+}
+
 async fn run_gov_create_test(
     ctx: &GovernanceTestContext,
     runner: &ToolRunner,
@@ -376,16 +876,18 @@ async fn run_gov_create_test(
     let (preimage_hex, submit_hex) =
         call_data::generate_governance_track_call_data(&ctx.ah_client, track, "Origins").await?;
 
-    let port = port_allocator::next_port();
+    let ports = port_allocator::next_tool_range();
     let output = runner
-        .run_test_referendum(ToolArgs {
-            governance_chain_url: Some(ctx.governance_url_with_block()),
-            call_to_create_governance_referendum: Some(submit_hex),
-            call_to_note_preimage_for_governance_referendum: Some(preimage_hex),
-            port: Some(port),
-            verbose: true,
-            ..Default::default()
-        })
+        .run_with_retries(
+            ToolArgs::builder()
+                .governance_chain_url(ctx.governance_endpoint())
+                .call_to_create_governance_referendum(submit_hex)
+                .call_to_note_preimage_for_governance_referendum(preimage_hex)
+                .port_range(ports.to_arg())
+                .verbose()
+                .build()?,
+            RetryPolicy::default(),
+        )
         .await?;
 
     output.check_success()?;
@@ -398,59 +900,362 @@ async fn run_gov_create_test(
 // Sub-test implementations — Polkadot Governance (per-track by-number)
 // ═══════════════════════════════════════════════════════════════════════════
 
+/// Tool processes [`run_gov_bynum_batch`] runs at a time.
+const BYNUM_PARALLELISM: usize = 4;
+
+fn gov_bynum_label(track: &tracks::GovernanceTrack) -> String {
+    format!("gov_bynum_{}", track.name)
+}
+
+fn gov_bynum_args(
+    ctx: &GovernanceTestContext,
+    submitted: &SubmittedReferendum,
+) -> Result<ToolArgs> {
+    let fork = ChainEndpoint::new(&ctx.asset_hub_ws_uri)?.at_block(submitted.block_number);
+    ToolArgs::builder()
+        .governance_chain_url(fork)
+        .referendum(submitted.referendum_id)
+        .port_range(port_allocator::next_tool_range().to_arg())
+        .verbose()
+        .build()
+}
+
+/// Simulate every track's by-number referendum, [`BYNUM_PARALLELISM`] at a
+/// time: each run forks Asset Hub at its own submission block, so they are
+/// independent. Each run is labeled `gov_bynum_<track>`, the sub-test that
+/// [`run_gov_bynum_test`] then checks it in.
+async fn run_gov_bynum_batch(
+    ctx: &GovernanceTestContext,
+    runner: &ToolRunner,
+    submitted: &[SubmittedReferendum],
+) -> Result<Vec<Result<ToolOutput>>> {
+    log::info!("Simulating {} by-number referenda", submitted.len());
+    let batch = tracks::GOVERNANCE_TRACKS
+        .iter()
+        .zip(submitted)
+        .map(|(track, submitted)| Ok((gov_bynum_label(track), gov_bynum_args(ctx, submitted)?)))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(runner.run_batch(batch, BYNUM_PARALLELISM).await)
+}
+
+/// Check a track's by-number run from [`run_gov_bynum_batch`]; a retry of the
+/// sub-test (`batched` already taken) runs the tool again.
 async fn run_gov_bynum_test(
     ctx: &GovernanceTestContext,
     runner: &ToolRunner,
     track: &tracks::GovernanceTrack,
+    submitted: &SubmittedReferendum,
+    batched: Option<Result<ToolOutput>>,
 ) -> Result<()> {
     log::info!(">>> gov_bynum_{} (track_id={})", track.name, track.id);
 
-    let submitted =
-        extrinsic_submitter::submit_governance_referendum(&ctx.ah_client, track, "Origins").await?;
+    let output = match batched {
+        Some(output) => output?,
+        None => {
+            runner
+                .run_with_retries(gov_bynum_args(ctx, submitted)?, RetryPolicy::default())
+                .await?
+        }
+    };
+
+    output.check_success()?;
+    output.check_stdout_contains("executed successfully")?;
+
+    Ok(())
+}
 
-    let fork_url = format!("{},{}", ctx.asset_hub_ws_uri, submitted.block_number);
+// ═══════════════════════════════════════════════════════════════════════════
+// Sub-test implementations — Polkadot Governance (scenarios)
+// ═══════════════════════════════════════════════════════════════════════════
 
-    let port = port_allocator::next_port();
+/// Happy path: create and simulate a System.authorize_upgrade referendum. The
+/// tool reports the id it created and the block that dispatched it.
+async fn run_governance_happy_path(ctx: &GovernanceTestContext, runner: &ToolRunner) -> Result<()> {
+    log::info!("[gov_happy_path] Starting...");
+    let (preimage_hex, gov_submit_hex) =
+        call_data::generate_governance_call_data(&ctx.ah_client).await?;
+
+    let ports = port_allocator::next_tool_range();
     let output = runner
-        .run_test_referendum(ToolArgs {
-            governance_chain_url: Some(fork_url),
-            referendum: Some(submitted.referendum_id.to_string()),
-            port: Some(port),
-            verbose: true,
-            ..Default::default()
-        })
+        .run_test_referendum(
+            ToolArgs::builder()
+                .governance_chain_url(ctx.governance_endpoint())
+                .call_to_create_governance_referendum(gov_submit_hex)
+                .call_to_note_preimage_for_governance_referendum(preimage_hex)
+                .port_range(ports.to_arg())
+                .verbose()
+                .build()?,
+        )
         .await?;
 
     output.check_success()?;
     output.check_stdout_contains("executed successfully")?;
+    let created = output
+        .created_referendum()
+        .context("tool did not report the referendum it created")?;
+    ensure!(
+        created.kind == "Governance",
+        "unexpected created referendum {created:?}"
+    );
+    let dispatch_block = created.dispatch_block.context("no dispatch block")?;
+    log::info!("[gov_happy_path] created {created:?}");
+    // The fork starts at `ah_fork_block`, so the tool's referendum takes the
+    // next free index there.
+    let count = query_storage(
+        &ctx.ah_client,
+        "Referenda",
+        "ReferendumCount",
+        vec![],
+        Some(ctx.ah_fork_block),
+    )
+    .await?
+    .and_then(|count| count.as_u128());
+    ensure!(
+        output.referendum_index().map(u128::from) == count,
+        "tool created referendum {:?}, but ReferendumCount at the fork block is {count:?}",
+        output.referendum_index()
+    );
+    ensure!(
+        output.fellowship_referendum_index().is_none(),
+        "governance-only run reported a fellowship referendum"
+    );
+    let events = output.events();
+    let authorized = events
+        .iter()
+        .find(|e| e.is("System", "UpgradeAuthorized"))
+        .context("no System.UpgradeAuthorized event")?;
+    ensure!(
+        authorized.fields["code_hash"] == format!("0x{}", "01".repeat(32)),
+        "unexpected System.UpgradeAuthorized payload {}",
+        authorized.fields
+    );
+    output.check_event_sequence(&[("System", "UpgradeAuthorized"), ("Scheduler", "Dispatched")])?;
+    output.check_event_in_block(dispatch_block, "System", "UpgradeAuthorized")?;
+    Ok(())
+}
+
+/// `--set-storage` names are resolved against the fork's metadata: lower-case
+/// names are written under the runtime's spelling before the referendum is
+/// created. Moving `Referenda.ReferendumCount` far past its value on the fork
+/// must give the created referendum that index.
+/// `Referenda.ReferendumCount` [`run_governance_set_storage`] writes; no fork
+/// gets near it.
+const SET_REFERENDUM_COUNT: u32 = 4242;
+
+async fn run_governance_set_storage(
+    ctx: &GovernanceTestContext,
+    runner: &ToolRunner,
+) -> Result<()> {
+    log::info!("[gov_set_storage] Starting...");
+
+    let (preimage_hex, submit_hex) =
+        call_data::generate_governance_call_data(&ctx.ah_client).await?;
+
+    let ports = port_allocator::next_tool_range();
+    let output = runner
+        .run_test_referendum(
+            ToolArgs::builder()
+                .governance_chain_url(ctx.governance_endpoint())
+                .call_to_create_governance_referendum(submit_hex)
+                .call_to_note_preimage_for_governance_referendum(preimage_hex)
+                .set_storage(format!("referenda.referendumcount={SET_REFERENDUM_COUNT}"))
+                .port_range(ports.to_arg())
+                .verbose()
+                .build()?,
+        )
+        .await?;
+
+    output.check_success()?;
+    output.check_stdout_contains(&format!(
+        "Storage override: Referenda.ReferendumCount = {SET_REFERENDUM_COUNT}"
+    ))?;
+    ensure!(
+        output.referendum_index() == Some(SET_REFERENDUM_COUNT),
+        "expected referendum #{SET_REFERENDUM_COUNT} after overriding ReferendumCount, got {:?}",
+        output.referendum_index()
+    );
+    output.check_stdout_contains("executed successfully")?;
+    Ok(())
+}
+
+/// `--override-parameter` on a name the runtime has no parameter for falls back
+/// to the storage item of that name, logged as a plain storage override.
+async fn run_governance_override_parameter(
+    ctx: &GovernanceTestContext,
+    runner: &ToolRunner,
+) -> Result<()> {
+    log::info!("[gov_override_parameter] Starting...");
+
+    let (preimage_hex, submit_hex) =
+        call_data::generate_governance_call_data(&ctx.ah_client).await?;
+
+    let ports = port_allocator::next_tool_range();
+    let output = runner
+        .run_test_referendum(
+            ToolArgs::builder()
+                .governance_chain_url(ctx.governance_endpoint())
+                .call_to_create_governance_referendum(submit_hex)
+                .call_to_note_preimage_for_governance_referendum(preimage_hex)
+                .override_parameter("ahmigrator.ahmigrationstage", "MigrationDone")
+                .port_range(ports.to_arg())
+                .verbose()
+                .build()?,
+        )
+        .await?;
+
+    output.check_success()?;
+    output.check_stdout_contains(
+        "Storage override: AhMigrator.AhMigrationStage = \"MigrationDone\"",
+    )?;
+    output.check_stdout_contains("executed successfully")?;
+    Ok(())
+}
+
+/// `--fund` and `--unlock-call-filter` become `System.Account` and
+/// `AhMigrator.AhMigrationStage` overrides on the forked Asset Hub.
+async fn run_governance_fund_and_unlock(
+    ctx: &GovernanceTestContext,
+    runner: &ToolRunner,
+) -> Result<()> {
+    log::info!("[gov_fund_and_unlock] Starting...");
+    const BOB: &str = "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty";
+
+    let (preimage_hex, submit_hex) =
+        call_data::generate_governance_call_data(&ctx.ah_client).await?;
+
+    let ports = port_allocator::next_tool_range();
+    let output = runner
+        .run_test_referendum(
+            ToolArgs::builder()
+                .governance_chain_url(ctx.governance_endpoint())
+                .call_to_create_governance_referendum(submit_hex)
+                .call_to_note_preimage_for_governance_referendum(preimage_hex)
+                .fund(BOB, 1_000_000_000_000_000)
+                .unlock_call_filter()
+                .port_range(ports.to_arg())
+                .verbose()
+                .build()?,
+        )
+        .await?;
+
+    output.check_success()?;
+    output.check_stdout_contains(&format!("Storage override: System.Account[\"{BOB}\"]"))?;
+    output.check_stdout_contains("Storage override: AhMigrator.AhMigrationStage")?;
+    output.check_stdout_contains("executed successfully")?;
+    Ok(())
+}
+
+/// Happy path through the Rust `Simulation` API: the `on_block` callback must
+/// see every block the tool builds, in increasing order, on the forked chain,
+/// and the fork's final head must be the last of them.
+async fn run_governance_simulation_api(ctx: &GovernanceTestContext) -> Result<()> {
+    log::info!("[gov_simulation_api] Starting...");
+    let (preimage_hex, gov_submit_hex) =
+        call_data::generate_governance_call_data(&ctx.ah_client).await?;
+
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let ports = port_allocator::next_tool_range();
+    let report = Simulation::new(SimulationConfig {
+        governance_chain_url: Some(ctx.governance_endpoint().to_arg()),
+        call_to_create_governance_referendum: Some(gov_submit_hex),
+        call_to_note_preimage_for_governance_referendum: Some(preimage_hex),
+        port_range: Some(ports.to_arg()),
+        verbose: true,
+        ..Default::default()
+    })
+    .on_block({
+        let seen = Arc::clone(&seen);
+        move |block| {
+            let mut seen = seen.lock().unwrap();
+            if let Some(last) = seen.last() {
+                ensure!(
+                    block.number > *last,
+                    "block #{} after #{last}",
+                    block.number
+                );
+            }
+            seen.push(block.number);
+            Ok(())
+        }
+    })
+    .run()
+    .await?;
 
+    let seen = seen.lock().unwrap();
+    log::info!("[gov_simulation_api] on_block saw {} block(s)", seen.len());
+    ensure!(!seen.is_empty(), "on_block was never called");
+    ensure!(
+        seen.len() == report.blocks.len(),
+        "callback saw {} blocks, report has {}",
+        seen.len(),
+        report.blocks.len()
+    );
+    ensure!(
+        report.stdout.contains("executed successfully"),
+        "simulation did not report successful execution"
+    );
+    let tag = format!("[{}]", report.run_id);
+    ensure!(
+        report.stdout.lines().any(|line| line.contains(&tag)),
+        "no tool line is tagged with {tag}"
+    );
+    let [head] = report.heads.as_slice() else {
+        anyhow::bail!("expected one fork head, got {:?}", report.heads);
+    };
+    ensure!(
+        Some(&head.end.number) == seen.last(),
+        "final head #{} is not the last block built {:?}",
+        head.end.number,
+        seen.last()
+    );
     Ok(())
 }
 
-// ═══════════════════════════════════════════════════════════════════════════
-// Sub-test implementations — Polkadot Governance (scenarios)
-// ═══════════════════════════════════════════════════════════════════════════
-
-/// Happy path: create and simulate a System.authorize_upgrade referendum.
-async fn run_governance_happy_path(ctx: &GovernanceTestContext, runner: &ToolRunner) -> Result<()> {
-    log::info!("[gov_happy_path] Starting...");
+/// A proposal repository's call-data files, read through the `fixture` API
+/// (with a trailing newline, as editors save them, and the preimage without
+/// `0x` in uppercase), simulate like inline hex.
+async fn run_governance_fixture_proposal_files(ctx: &GovernanceTestContext) -> Result<()> {
+    log::info!("[gov_fixture_proposal_files] Starting...");
     let (preimage_hex, gov_submit_hex) =
         call_data::generate_governance_call_data(&ctx.ah_client).await?;
+    let proposal_dir = config::get_artifacts_dir("polkadot_governance_all_tracks").join("proposal");
+    std::fs::create_dir_all(&proposal_dir)?;
+    let submit_file = proposal_dir.join("submit.hex");
+    let preimage_file = proposal_dir.join("preimage.hex");
+    std::fs::write(&submit_file, format!("{gov_submit_hex}\n"))?;
+    let bare_preimage = preimage_hex.trim_start_matches("0x").to_uppercase();
+    std::fs::write(&preimage_file, format!("{bare_preimage}\n"))?;
+
+    let proposal = Proposal::governance(&submit_file)?.with_preimage(&preimage_file)?;
+    ensure!(
+        proposal.submit_call == gov_submit_hex,
+        "submit call read back as {}",
+        proposal.submit_call
+    );
+    ensure!(
+        proposal.preimage_call.as_deref() == Some(preimage_hex.as_str()),
+        "preimage call read back as {:?}",
+        proposal.preimage_call
+    );
 
-    let port = port_allocator::next_port();
-    let output = runner
-        .run_test_referendum(ToolArgs {
-            governance_chain_url: Some(ctx.governance_url_with_block()),
-            call_to_create_governance_referendum: Some(gov_submit_hex),
-            call_to_note_preimage_for_governance_referendum: Some(preimage_hex),
-            port: Some(port),
-            verbose: true,
-            ..Default::default()
-        })
-        .await?;
+    let ports = port_allocator::next_tool_range();
+    let report = Simulation::new(SimulationConfig {
+        port_range: Some(ports.to_arg()),
+        ..proposal.to_config(ctx.governance_endpoint().to_arg())
+    })
+    .run()
+    .await?;
+    ensure!(
+        report.stdout.contains("executed successfully"),
+        "simulation did not report successful execution"
+    );
 
-    output.check_success()?;
-    output.check_stdout_contains("executed successfully")?;
+    let not_hex_file = proposal_dir.join("not-hex.hex");
+    std::fs::write(&not_hex_file, "0xzz\n")?;
+    ensure!(
+        Proposal::governance(&not_hex_file).is_err(),
+        "non-hex call data was accepted"
+    );
     Ok(())
 }
 
@@ -463,20 +1268,22 @@ async fn run_governance_dispatch_failure(
     let (preimage_hex, gov_submit_hex) =
         call_data::generate_governance_call_data_with_wrong_preimage(&ctx.ah_client).await?;
 
-    let port = port_allocator::next_port();
+    let ports = port_allocator::next_tool_range();
     let output = runner
-        .run_test_referendum(ToolArgs {
-            governance_chain_url: Some(ctx.governance_url_with_block()),
-            call_to_create_governance_referendum: Some(gov_submit_hex),
-            call_to_note_preimage_for_governance_referendum: Some(preimage_hex),
-            port: Some(port),
-            verbose: true,
-            ..Default::default()
-        })
+        .run_test_referendum(
+            ToolArgs::builder()
+                .governance_chain_url(ctx.governance_endpoint())
+                .call_to_create_governance_referendum(gov_submit_hex)
+                .call_to_note_preimage_for_governance_referendum(preimage_hex)
+                .port_range(ports.to_arg())
+                .verbose()
+                .build()?,
+        )
         .await?;
 
     output.check_failure()?;
     output.check_stdout_contains("execution failed")?;
+    check_unavailable_call_code(&output)?;
     Ok(())
 }
 
@@ -490,18 +1297,19 @@ async fn run_governance_with_pre_call(
         call_data::generate_governance_call_data(&ctx.ah_client).await?;
     let pre_call_hex = call_data::generate_pre_call_remark_hex(&ctx.ah_client).await?;
 
-    let port = port_allocator::next_port();
+    let ports = port_allocator::next_tool_range();
     let output = runner
-        .run_test_referendum(ToolArgs {
-            governance_chain_url: Some(ctx.governance_url_with_block()),
-            call_to_create_governance_referendum: Some(gov_submit_hex),
-            call_to_note_preimage_for_governance_referendum: Some(preimage_hex),
-            pre_call: Some(pre_call_hex),
-            pre_origin: Some("Root".to_string()),
-            port: Some(port),
-            verbose: true,
-            ..Default::default()
-        })
+        .run_test_referendum(
+            ToolArgs::builder()
+                .governance_chain_url(ctx.governance_endpoint())
+                .call_to_create_governance_referendum(gov_submit_hex)
+                .call_to_note_preimage_for_governance_referendum(preimage_hex)
+                .pre_call(pre_call_hex)
+                .pre_origin("Root")
+                .port_range(ports.to_arg())
+                .verbose()
+                .build()?,
+        )
         .await?;
 
     output.check_success()?;
@@ -519,20 +1327,25 @@ async fn run_governance_remark_proposal(
     let (preimage_hex, gov_submit_hex) =
         call_data::generate_remark_referendum_call_data(&ctx.ah_client).await?;
 
-    let port = port_allocator::next_port();
+    let ports = port_allocator::next_tool_range();
     let output = runner
-        .run_test_referendum(ToolArgs {
-            governance_chain_url: Some(ctx.governance_url_with_block()),
-            call_to_create_governance_referendum: Some(gov_submit_hex),
-            call_to_note_preimage_for_governance_referendum: Some(preimage_hex),
-            port: Some(port),
-            verbose: true,
-            ..Default::default()
-        })
+        .run_test_referendum(
+            ToolArgs::builder()
+                .governance_chain_url(ctx.governance_endpoint())
+                .call_to_create_governance_referendum(&gov_submit_hex)
+                .call_to_note_preimage_for_governance_referendum(&preimage_hex)
+                .port_range(ports.to_arg())
+                .verbose()
+                .build()?,
+        )
         .await?;
 
     output.check_success()?;
     output.check_stdout_contains("executed successfully")?;
+    // The passing run ends with the calls to submit on the live chain
+    output.check_stdout_contains("Governance Submission Payloads")?;
+    output.check_stdout_contains(&preimage_hex)?;
+    output.check_stdout_contains(&gov_submit_hex)?;
     Ok(())
 }
 
@@ -542,15 +1355,16 @@ async fn run_governance_invalid_hex(
     runner: &ToolRunner,
 ) -> Result<()> {
     log::info!("[gov_invalid_hex] Starting...");
-    let port = port_allocator::next_port();
+    let ports = port_allocator::next_tool_range();
     let output = runner
-        .run_test_referendum(ToolArgs {
-            governance_chain_url: Some(ctx.governance_url_with_block()),
-            call_to_create_governance_referendum: Some("0xDEADBEEFCAFE".to_string()),
-            port: Some(port),
-            verbose: true,
-            ..Default::default()
-        })
+        .run_test_referendum(
+            ToolArgs::builder()
+                .governance_chain_url(ctx.governance_endpoint())
+                .call_to_create_governance_referendum("0xDEADBEEFCAFE")
+                .port_range(ports.to_arg())
+                .verbose()
+                .build()?,
+        )
         .await?;
 
     output.check_failure()?;
@@ -567,18 +1381,19 @@ async fn run_governance_pre_call_non_root_origin(
         call_data::generate_governance_call_data(&ctx.ah_client).await?;
     let pre_call_hex = call_data::generate_pre_call_remark_hex(&ctx.ah_client).await?;
 
-    let port = port_allocator::next_port();
+    let ports = port_allocator::next_tool_range();
     let output = runner
-        .run_test_referendum(ToolArgs {
-            governance_chain_url: Some(ctx.governance_url_with_block()),
-            call_to_create_governance_referendum: Some(gov_submit_hex),
-            call_to_note_preimage_for_governance_referendum: Some(preimage_hex),
-            pre_call: Some(pre_call_hex),
-            pre_origin: Some("Treasurer".to_string()),
-            port: Some(port),
-            verbose: true,
-            ..Default::default()
-        })
+        .run_test_referendum(
+            ToolArgs::builder()
+                .governance_chain_url(ctx.governance_endpoint())
+                .call_to_create_governance_referendum(gov_submit_hex)
+                .call_to_note_preimage_for_governance_referendum(preimage_hex)
+                .pre_call(pre_call_hex)
+                .pre_origin("Treasurer")
+                .port_range(ports.to_arg())
+                .verbose()
+                .build()?,
+        )
         .await?;
 
     output.check_success()?;
@@ -597,18 +1412,19 @@ async fn run_governance_pre_call_invalid_origin(
         call_data::generate_governance_call_data(&ctx.ah_client).await?;
     let pre_call_hex = call_data::generate_pre_call_remark_hex(&ctx.ah_client).await?;
 
-    let port = port_allocator::next_port();
+    let ports = port_allocator::next_tool_range();
     let output = runner
-        .run_test_referendum(ToolArgs {
-            governance_chain_url: Some(ctx.governance_url_with_block()),
-            call_to_create_governance_referendum: Some(gov_submit_hex),
-            call_to_note_preimage_for_governance_referendum: Some(preimage_hex),
-            pre_call: Some(pre_call_hex),
-            pre_origin: Some("NonExistentOrigin".to_string()),
-            port: Some(port),
-            verbose: true,
-            ..Default::default()
-        })
+        .run_test_referendum(
+            ToolArgs::builder()
+                .governance_chain_url(ctx.governance_endpoint())
+                .call_to_create_governance_referendum(gov_submit_hex)
+                .call_to_note_preimage_for_governance_referendum(preimage_hex)
+                .pre_call(pre_call_hex)
+                .pre_origin("NonExistentOrigin")
+                .port_range(ports.to_arg())
+                .verbose()
+                .build()?,
+        )
         .await?;
 
     output.check_failure()?;
@@ -625,19 +1441,21 @@ async fn run_governance_create_no_preimage(
     let (_preimage_hex, gov_submit_hex) =
         call_data::generate_governance_call_data(&ctx.ah_client).await?;
 
-    let port = port_allocator::next_port();
+    let ports = port_allocator::next_tool_range();
     let output = runner
-        .run_test_referendum(ToolArgs {
-            governance_chain_url: Some(ctx.governance_url_with_block()),
-            call_to_create_governance_referendum: Some(gov_submit_hex),
-            port: Some(port),
-            verbose: true,
-            ..Default::default()
-        })
+        .run_test_referendum(
+            ToolArgs::builder()
+                .governance_chain_url(ctx.governance_endpoint())
+                .call_to_create_governance_referendum(gov_submit_hex)
+                .port_range(ports.to_arg())
+                .verbose()
+                .build()?,
+        )
         .await?;
 
     output.check_failure()?;
     output.check_stdout_contains("execution failed")?;
+    check_unavailable_call_code(&output)?;
     Ok(())
 }
 
@@ -654,15 +1472,16 @@ async fn run_governance_inline_create(
     let gov_submit_hex =
         call_data::generate_governance_inline_call_data(&ctx.ah_client).await?;
 
-    let port = port_allocator::next_port();
+    let ports = port_allocator::next_tool_range();
     let output = runner
-        .run_test_referendum(ToolArgs {
-            governance_chain_url: Some(ctx.governance_url_with_block()),
-            call_to_create_governance_referendum: Some(gov_submit_hex),
-            port: Some(port),
-            verbose: true,
-            ..Default::default()
-        })
+        .run_test_referendum(
+            ToolArgs::builder()
+                .governance_chain_url(ctx.governance_endpoint())
+                .call_to_create_governance_referendum(gov_submit_hex)
+                .port_range(ports.to_arg())
+                .verbose()
+                .build()?,
+        )
         .await?;
 
     output.check_success()?;
@@ -670,6 +1489,244 @@ async fn run_governance_inline_create(
     Ok(())
 }
 
+/// Negative: `--scenario bad-origin` dispatches a valid proposal from a
+/// mismatched origin; the tool must report `BadOrigin` as `E-BAD-ORIGIN`.
+async fn run_governance_bad_origin(ctx: &GovernanceTestContext, runner: &ToolRunner) -> Result<()> {
+    log::info!("[gov_bad_origin] Starting...");
+    let gov_submit_hex = call_data::generate_governance_inline_call_data(&ctx.ah_client).await?;
+
+    let ports = port_allocator::next_tool_range();
+    let output = runner
+        .run_test_referendum(
+            ToolArgs::builder()
+                .governance_chain_url(ctx.governance_endpoint())
+                .call_to_create_governance_referendum(gov_submit_hex)
+                .scenario("bad-origin")
+                .port_range(ports.to_arg())
+                .verbose()
+                .build()?,
+        )
+        .await?;
+
+    output.check_failure()?;
+    output.check_stdout_contains("BadOrigin")?;
+    output.check_error_code("E-BAD-ORIGIN")?;
+    Ok(())
+}
+
+/// `--enactment-retries 2` on a dispatch that fails with `BadOrigin`: the
+/// scheduler retries the task twice, the retries keep the mismatched origin,
+/// and the run fails with every attempt's error.
+async fn run_governance_enactment_retries(
+    ctx: &GovernanceTestContext,
+    runner: &ToolRunner,
+) -> Result<()> {
+    log::info!("[gov_enactment_retries] Starting...");
+    let gov_submit_hex = call_data::generate_governance_inline_call_data(&ctx.ah_client).await?;
+
+    let ports = port_allocator::next_tool_range();
+    let output = runner
+        .run_test_referendum(
+            ToolArgs::builder()
+                .governance_chain_url(ctx.governance_endpoint())
+                .call_to_create_governance_referendum(gov_submit_hex)
+                .scenario("bad-origin")
+                .enactment_retries(2)
+                .port_range(ports.to_arg())
+                .verbose()
+                .build()?,
+        )
+        .await?;
+
+    output.check_failure()?;
+    output.check_error_code("E-BAD-ORIGIN")?;
+    output.check_stdout_contains("Enactment task retry configuration: 2 of 2 retries left")?;
+    output.check_any_output_contains("Retry 1 (task")?;
+    output.check_any_output_contains("Retry 2 (task")?;
+    output.check_any_output_contains("Retry 2 at block")?;
+    Ok(())
+}
+
+/// `--cancel-at confirming` cancels the referendum via `ReferendumCanceller`
+/// during its confirm period instead of dispatching it. The created referendum
+/// has only a submission deposit, which must be refunded in full.
+async fn run_governance_cancel_confirming(
+    ctx: &GovernanceTestContext,
+    runner: &ToolRunner,
+) -> Result<()> {
+    log::info!("[gov_cancel_confirming] Starting...");
+    let gov_submit_hex = call_data::generate_governance_inline_call_data(&ctx.ah_client).await?;
+
+    let ports = port_allocator::next_tool_range();
+    let output = runner
+        .run_test_referendum(
+            ToolArgs::builder()
+                .governance_chain_url(ctx.governance_endpoint())
+                .call_to_create_governance_referendum(gov_submit_hex)
+                .cancel_at("confirming")
+                .port_range(ports.to_arg())
+                .verbose()
+                .build()?,
+        )
+        .await?;
+
+    output.check_success()?;
+    let confirming = output
+        .check_any_output_matches(r"Referendum #(\d+) is confirming \(until block (\d+)\)")?;
+    let cancelled =
+        output.check_any_output_matches(r"Referendum #(\d+) cancelled at block (\d+)")?;
+    ensure!(
+        cancelled[1] == confirming[1],
+        "cancelled referendum #{} instead of the confirming #{}",
+        cancelled[1],
+        confirming[1]
+    );
+    let (cancelled_at, confirm_until): (u64, u64) = (cancelled[2].parse()?, confirming[2].parse()?);
+    ensure!(
+        cancelled_at < confirm_until,
+        "cancelled at block {cancelled_at}, after the confirm period ended at {confirm_until}"
+    );
+    output.check_stdout_contains("submission deposit of")?;
+    output.check_stdout_contains("cancelled and its deposits refunded")?;
+    Ok(())
+}
+
+/// A created referendum's metadata JSON is noted as a preimage and set with
+/// `set_metadata`, as a real submission would.
+async fn run_governance_metadata(ctx: &GovernanceTestContext, runner: &ToolRunner) -> Result<()> {
+    log::info!("[gov_metadata] Starting...");
+    let gov_submit_hex = call_data::generate_governance_inline_call_data(&ctx.ah_client).await?;
+    let artifacts_dir = config::get_artifacts_dir("polkadot_governance_all_tracks");
+    std::fs::create_dir_all(&artifacts_dir)?;
+    let metadata_path = artifacts_dir.join("metadata.json");
+    std::fs::write(&metadata_path, r#"{"title":"Integration test proposal"}"#)?;
+
+    let ports = port_allocator::next_tool_range();
+    let output = runner
+        .run_test_referendum(
+            ToolArgs::builder()
+                .governance_chain_url(ctx.governance_endpoint())
+                .call_to_create_governance_referendum(gov_submit_hex)
+                .metadata_for_governance_referendum(metadata_path.to_string_lossy())
+                .port_range(ports.to_arg())
+                .verbose()
+                .build()?,
+        )
+        .await?;
+
+    output.check_success()?;
+    output.check_stdout_contains("Metadata noted as preimage")?;
+    output.check_stdout_contains("metadata set to")?;
+    Ok(())
+}
+
+/// Preview: `--preview` decodes the creation calls and exits before forking,
+/// so it gets seconds rather than the create timeout.
+async fn run_governance_preview(ctx: &GovernanceTestContext, runner: &ToolRunner) -> Result<()> {
+    log::info!("[gov_preview] Starting...");
+    let (preimage_hex, gov_submit_hex) =
+        call_data::generate_remark_referendum_call_data(&ctx.ah_client).await?;
+
+    let ports = port_allocator::next_tool_range();
+    let output = runner
+        .run_test_referendum(
+            ToolArgs::builder()
+                .governance_chain_url(ctx.governance_endpoint())
+                .call_to_create_governance_referendum(gov_submit_hex)
+                .call_to_note_preimage_for_governance_referendum(preimage_hex)
+                .preview()
+                .port_range(ports.to_arg())
+                .timeout(Duration::from_secs(120))
+                .verbose()
+                .build()?,
+        )
+        .await?;
+
+    output.check_success()?;
+    output.check_stdout_contains("Governance Referendum Preview")?;
+    output.check_stdout_contains("Referenda.submit")?;
+    output.check_stdout_contains("Proposal System.remark")?;
+    ensure!(
+        !output.stdout.contains("executed successfully"),
+        "--preview should not execute the proposal:\n{}",
+        output.stdout
+    );
+    Ok(())
+}
+
+/// Call data as copied from PJS Apps: the submit call upper-cased without
+/// `0x`, the preimage call wrapped over several lines with surrounding
+/// whitespace. The tool normalizes both and creates the referendum as usual.
+async fn run_governance_sloppy_call_data(
+    ctx: &GovernanceTestContext,
+    runner: &ToolRunner,
+) -> Result<()> {
+    log::info!("[gov_sloppy_call_data] Starting...");
+    let (preimage_hex, gov_submit_hex) =
+        call_data::generate_remark_referendum_call_data(&ctx.ah_client).await?;
+
+    let submit = gov_submit_hex.trim_start_matches("0x").to_uppercase();
+    let wrapped: Vec<&str> = preimage_hex
+        .as_bytes()
+        .chunks(64)
+        .map(|line| std::str::from_utf8(line).expect("hex is ASCII"))
+        .collect();
+    let preimage = format!("  {}\n", wrapped.join("\n"));
+
+    let ports = port_allocator::next_tool_range();
+    let output = runner
+        .run_test_referendum(
+            ToolArgs::builder()
+                .governance_chain_url(ctx.governance_endpoint())
+                .call_to_create_governance_referendum(submit)
+                .call_to_note_preimage_for_governance_referendum(preimage)
+                .port_range(ports.to_arg())
+                .verbose()
+                .build()?,
+        )
+        .await?;
+
+    output.check_success()?;
+    output.check_any_output_contains("Preimage noted successfully")?;
+    output.check_stdout_matches(r"referendum #\d+ executed successfully at block \d+")?;
+    ensure!(
+        output.referendum_index().is_some(),
+        "tool did not report the referendum it created"
+    );
+    Ok(())
+}
+
+/// `--blocks-after-dispatch 2`: two more governance blocks are built after
+/// the dispatch block and their events shown.
+async fn run_governance_blocks_after_dispatch(
+    ctx: &GovernanceTestContext,
+    runner: &ToolRunner,
+) -> Result<()> {
+    log::info!("[gov_blocks_after_dispatch] Starting...");
+    let (preimage_hex, gov_submit_hex) =
+        call_data::generate_remark_referendum_call_data(&ctx.ah_client).await?;
+
+    let ports = port_allocator::next_tool_range();
+    let output = runner
+        .run_test_referendum(
+            ToolArgs::builder()
+                .governance_chain_url(ctx.governance_endpoint())
+                .call_to_create_governance_referendum(gov_submit_hex)
+                .call_to_note_preimage_for_governance_referendum(preimage_hex)
+                .blocks_after_dispatch(2)
+                .port_range(ports.to_arg())
+                .verbose()
+                .build()?,
+        )
+        .await?;
+
+    output.check_success()?;
+    output.check_stdout_contains("Events After Dispatch")?;
+    output.check_stdout_contains("Governance [after dispatch 1/2]")?;
+    output.check_stdout_contains("Governance [after dispatch 2/2]")?;
+    Ok(())
+}
+
 /// Inline proposal by-number: submit inline referendum on zombienet, then test with --referendum.
 async fn run_governance_inline_bynum(
     ctx: &GovernanceTestContext,
@@ -679,17 +1736,198 @@ async fn run_governance_inline_bynum(
     let submitted =
         extrinsic_submitter::submit_governance_referendum_inline(&ctx.ah_client).await?;
 
-    let fork_url = format!("{},{}", ctx.asset_hub_ws_uri, submitted.block_number);
+    let fork = ChainEndpoint::new(&ctx.asset_hub_ws_uri)?.at_block(submitted.block_number);
+
+    let ports = port_allocator::next_tool_range();
+    let output = runner
+        .run_test_referendum(
+            ToolArgs::builder()
+                .governance_chain_url(fork)
+                .referendum(submitted.referendum_id)
+                .port_range(ports.to_arg())
+                .verbose()
+                .build()?,
+        )
+        .await?;
+
+    output.check_success()?;
+    output.check_stdout_contains("executed successfully")?;
+    Ok(())
+}
+
+/// Proxy create: the tool submits the inline referendum as Bob through Alice
+/// as a time-delayed proxy (announce, then `Proxy.proxy_announced`).
+async fn run_governance_proxy_create(
+    ctx: &GovernanceTestContext,
+    runner: &ToolRunner,
+) -> Result<()> {
+    log::info!("[gov_proxy_create] Starting...");
+    let gov_submit_hex = call_data::generate_governance_inline_call_data(&ctx.ah_client).await?;
+    let bob = subxt_signer::sr25519::dev::bob()
+        .public_key()
+        .to_account_id();
+
+    let ports = port_allocator::next_tool_range();
+    let output = runner
+        .run_test_referendum(
+            ToolArgs::builder()
+                .governance_chain_url(ctx.governance_endpoint())
+                .call_to_create_governance_referendum(gov_submit_hex)
+                .submit_via_proxy(format!("{bob}:Any:10"))
+                .port_range(ports.to_arg())
+                .verbose()
+                .build()?,
+        )
+        .await?;
+
+    output.check_success()?;
+    output.check_stdout_contains("Announced referendum submit")?;
+    output.check_stdout_contains("executed successfully")?;
+    Ok(())
+}
+
+/// Proxy by-number: Bob's referendum is submitted on zombienet by Alice as
+/// his delayed proxy, then tested with --referendum.
+async fn run_governance_proxy_bynum(
+    ctx: &GovernanceTestContext,
+    runner: &ToolRunner,
+) -> Result<()> {
+    log::info!("[gov_proxy_bynum] Starting...");
+    let submitted =
+        extrinsic_submitter::submit_governance_referendum_inline_via_proxy(&ctx.ah_client, 2)
+            .await?;
+
+    let fork = ChainEndpoint::new(&ctx.asset_hub_ws_uri)?.at_block(submitted.block_number);
+
+    let ports = port_allocator::next_tool_range();
+    let output = runner
+        .run_test_referendum(
+            ToolArgs::builder()
+                .governance_chain_url(fork)
+                .referendum(submitted.referendum_id)
+                .port_range(ports.to_arg())
+                .verbose()
+                .build()?,
+        )
+        .await?;
+
+    output.check_success()?;
+    output.check_stdout_contains("executed successfully")?;
+    Ok(())
+}
 
-    let port = port_allocator::next_port();
+/// Multisig create: the tool submits the inline referendum from a 2-of-3
+/// multisig of Alice, Bob and Charlie (`approve_as_multi`, then `as_multi`).
+async fn run_governance_multisig_create(
+    ctx: &GovernanceTestContext,
+    runner: &ToolRunner,
+) -> Result<()> {
+    log::info!("[gov_multisig_create] Starting...");
+    let gov_submit_hex = call_data::generate_governance_inline_call_data(&ctx.ah_client).await?;
+    let signatories = [
+        subxt_signer::sr25519::dev::alice(),
+        subxt_signer::sr25519::dev::bob(),
+        subxt_signer::sr25519::dev::charlie(),
+    ]
+    .map(|keypair| keypair.public_key().to_account_id().to_string())
+    .join(",");
+
+    let ports = port_allocator::next_tool_range();
     let output = runner
-        .run_test_referendum(ToolArgs {
-            governance_chain_url: Some(fork_url),
-            referendum: Some(submitted.referendum_id.to_string()),
-            port: Some(port),
-            verbose: true,
-            ..Default::default()
+        .run_test_referendum(
+            ToolArgs::builder()
+                .governance_chain_url(ctx.governance_endpoint())
+                .call_to_create_governance_referendum(gov_submit_hex)
+                .submit_via_multisig(format!("2:{signatories}"))
+                .port_range(ports.to_arg())
+                .verbose()
+                .build()?,
+        )
+        .await?;
+
+    output.check_success()?;
+    output.check_stdout_contains("approval 1/2")?;
+    output.check_stdout_contains("executed successfully")?;
+    Ok(())
+}
+
+/// The same simulation, run twice from the same fork block, must store
+/// byte-identical JSON results in the run history, and `--diff-with-last`
+/// must report no changes.
+async fn run_governance_deterministic(
+    ctx: &GovernanceTestContext,
+    runner: &ToolRunner,
+) -> Result<()> {
+    log::info!("[gov_deterministic] Starting...");
+    let (preimage_hex, gov_submit_hex) =
+        call_data::generate_governance_call_data(&ctx.ah_client).await?;
+    let history_dir =
+        config::get_artifacts_dir("polkadot_governance_all_tracks").join("deterministic-history");
+    if history_dir.exists() {
+        std::fs::remove_dir_all(&history_dir)?;
+    }
+
+    let mut runs = Vec::new();
+    for _ in 0..2 {
+        let ports = port_allocator::next_tool_range();
+        let output = runner
+            .run_test_referendum(
+                ToolArgs::builder()
+                    .governance_chain_url(ctx.governance_endpoint())
+                    .call_to_create_governance_referendum(gov_submit_hex.clone())
+                    .call_to_note_preimage_for_governance_referendum(preimage_hex.clone())
+                    .port_range(ports.to_arg())
+                    .history_dir(history_dir.to_string_lossy())
+                    .diff_with_last()
+                    .build()?,
+            )
+            .await?;
+        output.check_success()?;
+        if !runs.is_empty() {
+            output.check_stdout_contains("No changes since")?;
+        }
+        runs.push(output.report_path()?);
+    }
+
+    let results = runs
+        .iter()
+        .map(|path| -> Result<String> {
+            let record: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+            Ok(serde_json::to_string_pretty(&record["result"])?)
         })
+        .collect::<Result<Vec<_>>>()?;
+    ensure!(
+        results[0] == results[1],
+        "results differ between identical runs:\n--- first ---\n{}\n--- second ---\n{}",
+        results[0],
+        results[1]
+    );
+    Ok(())
+}
+
+/// Multisig by-number: the referendum is submitted on zombienet by a 2-of-3
+/// multisig, then tested with --referendum.
+async fn run_governance_multisig_bynum(
+    ctx: &GovernanceTestContext,
+    runner: &ToolRunner,
+) -> Result<()> {
+    log::info!("[gov_multisig_bynum] Starting...");
+    let submitted =
+        extrinsic_submitter::submit_governance_referendum_inline_via_multisig(&ctx.ah_client)
+            .await?;
+
+    let fork = ChainEndpoint::new(&ctx.asset_hub_ws_uri)?.at_block(submitted.block_number);
+
+    let ports = port_allocator::next_tool_range();
+    let output = runner
+        .run_test_referendum(
+            ToolArgs::builder()
+                .governance_chain_url(fork)
+                .referendum(submitted.referendum_id)
+                .port_range(ports.to_arg())
+                .verbose()
+                .build()?,
+        )
         .await?;
 
     output.check_success()?;
@@ -701,6 +1939,17 @@ async fn run_governance_inline_bynum(
 // Sub-test implementations — Polkadot Fellowship (per-track create)
 // ═══════════════════════════════════════════════════════════════════════════
 
+/// The FellowshipCollective override is in Collectives' genesis state and
+/// decodes against its runtime metadata.
+async fn run_fellowship_raw_override_audit(ctx: &MultiChainTestContext) -> Result<()> {
+    log::info!(">>> fell_raw_override_audit");
+    override_audit::audit_chain(
+        &ctx.coll_client,
+        &raw_storage::fellowship_collective_entries(),
+    )
+    .await
+}
+
 async fn run_polkadot_fellowship_create_test(
     ctx: &MultiChainTestContext,
     runner: &ToolRunner,
@@ -715,17 +1964,19 @@ async fn run_polkadot_fellowship_create_test(
     )
     .await?;
 
-    let port = port_allocator::next_port();
+    let ports = port_allocator::next_tool_range();
     let output = runner
-        .run_test_referendum(ToolArgs {
-            governance_chain_url: Some(ctx.governance_url_with_block()),
-            fellowship_chain_url: Some(ctx.fellowship_url_with_block()),
-            call_to_create_fellowship_referendum: Some(submit_hex),
-            call_to_note_preimage_for_fellowship_referendum: Some(preimage_hex),
-            port: Some(port),
-            verbose: true,
-            ..Default::default()
-        })
+        .run_with_retries(
+            ToolArgs::builder()
+                .governance_chain_url(ctx.governance_endpoint())
+                .fellowship_chain_url(ctx.fellowship_endpoint())
+                .call_to_create_fellowship_referendum(submit_hex)
+                .call_to_note_preimage_for_fellowship_referendum(preimage_hex)
+                .port_range(ports.to_arg())
+                .verbose()
+                .build()?,
+            RetryPolicy::default(),
+        )
         .await?;
 
     output.check_success()?;
@@ -742,36 +1993,111 @@ async fn run_polkadot_fellowship_bynum_test(
     ctx: &MultiChainTestContext,
     runner: &ToolRunner,
     track: &tracks::FellowshipTrack,
+    submitted: &SubmittedReferendum,
 ) -> Result<()> {
     log::info!(">>> fell_bynum_{} (track_id={})", track.name, track.id);
 
-    let submitted = extrinsic_submitter::submit_fellowship_referendum(
+    let fellowship_fork =
+        ChainEndpoint::new(&ctx.collectives_ws_uri)?.at_block(submitted.block_number);
+
+    let ports = port_allocator::next_tool_range();
+    let output = runner
+        .run_with_retries(
+            ToolArgs::builder()
+                .governance_chain_url(ctx.governance_endpoint())
+                .fellowship_chain_url(fellowship_fork)
+                .fellowship(submitted.referendum_id)
+                .port_range(ports.to_arg())
+                .verbose()
+                .build()?,
+            RetryPolicy::default(),
+        )
+        .await?;
+
+    output.check_success()?;
+    output.check_stdout_contains("executed successfully")?;
+
+    Ok(())
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Sub-test implementations — Polkadot Ambassador (per-track)
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// The AmbassadorCollective override is in Collectives' genesis state and
+/// decodes against its runtime metadata.
+async fn run_ambassador_raw_override_audit(ctx: &MultiChainTestContext) -> Result<()> {
+    log::info!(">>> amb_raw_override_audit");
+    override_audit::audit_chain(
         &ctx.coll_client,
-        track,
-        "FellowshipOrigins",
+        &raw_storage::ambassador_collective_entries(),
     )
-    .await?;
+    .await
+}
+
+async fn run_ambassador_create_test(
+    ctx: &MultiChainTestContext,
+    runner: &ToolRunner,
+    track: &tracks::FellowshipTrack,
+) -> Result<()> {
+    log::info!(">>> amb_create_{} (track_id={})", track.name, track.id);
 
-    let fellowship_fork_url = format!("{},{}", ctx.collectives_ws_uri, submitted.block_number);
+    let (preimage_hex, submit_hex) =
+        call_data::generate_ambassador_track_call_data(&ctx.coll_client, track).await?;
 
-    let port = port_allocator::next_port();
+    let ports = port_allocator::next_tool_range();
     let output = runner
-        .run_test_referendum(ToolArgs {
-            governance_chain_url: Some(ctx.governance_url_with_block()),
-            fellowship_chain_url: Some(fellowship_fork_url),
-            fellowship: Some(submitted.referendum_id.to_string()),
-            port: Some(port),
-            verbose: true,
-            ..Default::default()
-        })
+        .run_with_retries(
+            ToolArgs::builder()
+                .governance_chain_url(ctx.fellowship_endpoint())
+                .call_to_create_governance_referendum(submit_hex)
+                .call_to_note_preimage_for_governance_referendum(preimage_hex)
+                .port_range(ports.to_arg())
+                .verbose()
+                .build()?,
+            RetryPolicy::default(),
+        )
         .await?;
 
     output.check_success()?;
+    output.check_stdout_contains("AmbassadorReferenda referendum #")?;
     output.check_stdout_contains("executed successfully")?;
 
     Ok(())
 }
 
+async fn run_ambassador_bynum_test(
+    ctx: &MultiChainTestContext,
+    runner: &ToolRunner,
+    track: &tracks::FellowshipTrack,
+    submitted: &SubmittedReferendum,
+) -> Result<()> {
+    log::info!(">>> amb_bynum_{} (track_id={})", track.name, track.id);
+
+    let fork = ChainEndpoint::new(&ctx.collectives_ws_uri)?.at_block(submitted.block_number);
+
+    let ports = port_allocator::next_tool_range();
+    let output = runner
+        .run_with_retries(
+            ToolArgs::builder()
+                .governance_chain_url(fork)
+                .referendum_in("AmbassadorReferenda", submitted.referendum_id)
+                .port_range(ports.to_arg())
+                .verbose()
+                .build()?,
+            RetryPolicy::default(),
+        )
+        .await?;
+
+    output.check_success()?;
+    output.check_stdout_contains(&format!(
+        "AmbassadorReferenda referendum #{} executed successfully",
+        submitted.referendum_id
+    ))?;
+
+    Ok(())
+}
+
 // ═══════════════════════════════════════════════════════════════════════════
 // Sub-test implementations — Polkadot Multi-chain scenarios
 // ═══════════════════════════════════════════════════════════════════════════
@@ -788,27 +2114,97 @@ async fn run_multichain_happy_path(ctx: &MultiChainTestContext, runner: &ToolRun
         )
         .await?;
 
-    let port = port_allocator::next_port();
+    // The longest run of the suite: follow it live instead of waiting for it
+    let ports = port_allocator::next_tool_range();
+    let mut streamed = String::new();
+    let output = runner
+        .run_streaming(
+            ToolArgs::builder()
+                .governance_chain_url(ctx.governance_endpoint())
+                .fellowship_chain_url(ctx.fellowship_endpoint())
+                .additional_chains(ctx.relay_endpoint())
+                .call_to_create_governance_referendum(gov_submit_hex)
+                .call_to_note_preimage_for_governance_referendum(gov_preimage_hex)
+                .call_to_create_fellowship_referendum(fellowship_submit_hex)
+                .call_to_note_preimage_for_fellowship_referendum(fellowship_preimage_hex)
+                .port_range(ports.to_arg())
+                .verbose()
+                .build()?,
+            |line| {
+                log::info!("[multichain_happy_path] {}", line.text);
+                if line.stream == Stream::Stdout {
+                    streamed.push_str(&line.text);
+                    streamed.push('\n');
+                }
+            },
+        )
+        .await?;
+
+    output.check_success()?;
+    ensure!(
+        streamed == output.raw_stdout,
+        "Streamed stdout differs from the captured stdout"
+    );
+    output.check_stdout_contains("executed successfully")?;
+    output.check_referendum_passed("fellowship")?;
+    output.check_referendum_passed("governance")?;
+    // Verify relay chain was monitored as an additional chain
+    output.check_stdout_contains("Additional Chain Events")?;
+    output.check_stdout_contains("Block #")?;
+    Ok(())
+}
+
+/// Partial success: the fellowship referendum is created without its preimage
+/// and fails, the unrelated governance one still runs and passes, and the run
+/// exits with `E-PARTIAL-SUCCESS` (exit code 3).
+async fn run_multichain_partial_success(
+    ctx: &MultiChainTestContext,
+    runner: &ToolRunner,
+) -> Result<()> {
+    log::info!("[multichain_partial_success] Starting...");
+    let (gov_preimage_hex, gov_submit_hex) =
+        call_data::generate_governance_call_data(&ctx.ah_client).await?;
+    let (_fellowship_preimage_hex, fellowship_submit_hex) =
+        call_data::generate_fellowship_only_call_data(&ctx.coll_client, "FellowshipOrigins")
+            .await?;
+
+    let ports = port_allocator::next_tool_range();
     let output = runner
-        .run_test_referendum(ToolArgs {
-            governance_chain_url: Some(ctx.governance_url_with_block()),
-            fellowship_chain_url: Some(ctx.fellowship_url_with_block()),
-            additional_chains: Some(ctx.relay_url_with_block()),
-            call_to_create_governance_referendum: Some(gov_submit_hex),
-            call_to_note_preimage_for_governance_referendum: Some(gov_preimage_hex),
-            call_to_create_fellowship_referendum: Some(fellowship_submit_hex),
-            call_to_note_preimage_for_fellowship_referendum: Some(fellowship_preimage_hex),
-            port: Some(port),
-            verbose: true,
-            ..Default::default()
-        })
+        .run_test_referendum(
+            ToolArgs::builder()
+                .governance_chain_url(ctx.governance_endpoint())
+                .fellowship_chain_url(ctx.fellowship_endpoint())
+                .call_to_create_governance_referendum(gov_submit_hex)
+                .call_to_note_preimage_for_governance_referendum(gov_preimage_hex)
+                .call_to_create_fellowship_referendum(fellowship_submit_hex)
+                .port_range(ports.to_arg())
+                .output_format(OutputFormat::Json)
+                .verbose()
+                .build()?,
+        )
         .await?;
 
-    output.check_success()?;
-    output.check_stdout_contains("executed successfully")?;
-    // Verify relay chain was monitored as an additional chain
-    output.check_stdout_contains("Additional Chain Events")?;
-    output.check_stdout_contains("Block #")?;
+    output.check_error_code("E-PARTIAL-SUCCESS")?;
+    let report: serde_json::Value = output.parse_json()?;
+    anyhow::ensure!(
+        report["exitCode"] == 3 && report["error"]["code"] == "E-PARTIAL-SUCCESS",
+        "Expected a partial success in the @@result line, got {report}"
+    );
+    anyhow::ensure!(
+        output.exit_code == 3,
+        "Expected exit code 3 for a partial success, got {}",
+        output.exit_code
+    );
+    output.check_referendum_passed("governance")?;
+    let results = output.referendum_results();
+    anyhow::ensure!(
+        results.iter().any(|r| r.role == "fellowship"
+            && matches!(
+                r.error_code.as_deref(),
+                Some("E-PREIMAGE-MISSING" | "E-EXECUTION-FAILED")
+            )),
+        "Expected the fellowship referendum to fail for its missing preimage, got {results:?}"
+    );
     Ok(())
 }
 
@@ -819,16 +2215,17 @@ async fn run_fellowship_only(ctx: &MultiChainTestContext, runner: &ToolRunner) -
         call_data::generate_fellowship_only_call_data(&ctx.coll_client, "FellowshipOrigins")
             .await?;
 
-    let port = port_allocator::next_port();
+    let ports = port_allocator::next_tool_range();
     let output = runner
-        .run_test_referendum(ToolArgs {
-            fellowship_chain_url: Some(ctx.fellowship_url_with_block()),
-            call_to_create_fellowship_referendum: Some(submit_hex),
-            call_to_note_preimage_for_fellowship_referendum: Some(preimage_hex),
-            port: Some(port),
-            verbose: true,
-            ..Default::default()
-        })
+        .run_test_referendum(
+            ToolArgs::builder()
+                .fellowship_chain_url(ctx.fellowship_endpoint())
+                .call_to_create_fellowship_referendum(submit_hex)
+                .call_to_note_preimage_for_fellowship_referendum(preimage_hex)
+                .port_range(ports.to_arg())
+                .verbose()
+                .build()?,
+        )
         .await?;
 
     output.check_success()?;
@@ -837,6 +2234,7 @@ async fn run_fellowship_only(ctx: &MultiChainTestContext, runner: &ToolRunner) -
 }
 
 /// Governance-only + additional chains: governance referendum on AH with relay as additional chain.
+/// Builds three settle blocks on the relay so late XCM effects are shown.
 async fn run_governance_with_additional_chains(
     ctx: &MultiChainTestContext,
     runner: &ToolRunner,
@@ -845,22 +2243,25 @@ async fn run_governance_with_additional_chains(
     let (preimage_hex, gov_submit_hex) =
         call_data::generate_governance_call_data(&ctx.ah_client).await?;
 
-    let port = port_allocator::next_port();
+    let ports = port_allocator::next_tool_range();
     let output = runner
-        .run_test_referendum(ToolArgs {
-            governance_chain_url: Some(ctx.governance_url_with_block()),
-            additional_chains: Some(ctx.relay_url_with_block()),
-            call_to_create_governance_referendum: Some(gov_submit_hex),
-            call_to_note_preimage_for_governance_referendum: Some(preimage_hex),
-            port: Some(port),
-            verbose: true,
-            ..Default::default()
-        })
+        .run_test_referendum(
+            ToolArgs::builder()
+                .governance_chain_url(ctx.governance_endpoint())
+                .additional_chains(ctx.relay_endpoint())
+                .settle_blocks(3)
+                .call_to_create_governance_referendum(gov_submit_hex)
+                .call_to_note_preimage_for_governance_referendum(preimage_hex)
+                .port_range(ports.to_arg())
+                .verbose()
+                .build()?,
+        )
         .await?;
 
     output.check_success()?;
     output.check_stdout_contains("executed successfully")?;
     output.check_stdout_contains("Additional Chain Events")?;
+    output.check_stdout_contains("[settle block 3/3]")?;
     output.check_stdout_contains("Block #")?;
     Ok(())
 }
@@ -875,17 +2276,18 @@ async fn run_fellowship_with_additional_chains(
         call_data::generate_fellowship_only_call_data(&ctx.coll_client, "FellowshipOrigins")
             .await?;
 
-    let port = port_allocator::next_port();
+    let ports = port_allocator::next_tool_range();
     let output = runner
-        .run_test_referendum(ToolArgs {
-            fellowship_chain_url: Some(ctx.fellowship_url_with_block()),
-            additional_chains: Some(ctx.relay_url_with_block()),
-            call_to_create_fellowship_referendum: Some(submit_hex),
-            call_to_note_preimage_for_fellowship_referendum: Some(preimage_hex),
-            port: Some(port),
-            verbose: true,
-            ..Default::default()
-        })
+        .run_test_referendum(
+            ToolArgs::builder()
+                .fellowship_chain_url(ctx.fellowship_endpoint())
+                .additional_chains(ctx.relay_endpoint())
+                .call_to_create_fellowship_referendum(submit_hex)
+                .call_to_note_preimage_for_fellowship_referendum(preimage_hex)
+                .port_range(ports.to_arg())
+                .verbose()
+                .build()?,
+        )
         .await?;
 
     output.check_success()?;
@@ -909,15 +2311,16 @@ async fn run_fellowship_inline_create(
         call_data::generate_fellowship_inline_call_data(&ctx.coll_client, "FellowshipOrigins")
             .await?;
 
-    let port = port_allocator::next_port();
+    let ports = port_allocator::next_tool_range();
     let output = runner
-        .run_test_referendum(ToolArgs {
-            fellowship_chain_url: Some(ctx.fellowship_url_with_block()),
-            call_to_create_fellowship_referendum: Some(submit_hex),
-            port: Some(port),
-            verbose: true,
-            ..Default::default()
-        })
+        .run_test_referendum(
+            ToolArgs::builder()
+                .fellowship_chain_url(ctx.fellowship_endpoint())
+                .call_to_create_fellowship_referendum(submit_hex)
+                .port_range(ports.to_arg())
+                .verbose()
+                .build()?,
+        )
         .await?;
 
     output.check_success()?;
@@ -937,18 +2340,20 @@ async fn run_fellowship_inline_bynum(
     )
     .await?;
 
-    let fellowship_fork_url = format!("{},{}", ctx.collectives_ws_uri, submitted.block_number);
+    let fellowship_fork =
+        ChainEndpoint::new(&ctx.collectives_ws_uri)?.at_block(submitted.block_number);
 
-    let port = port_allocator::next_port();
+    let ports = port_allocator::next_tool_range();
     let output = runner
-        .run_test_referendum(ToolArgs {
-            governance_chain_url: Some(ctx.governance_url_with_block()),
-            fellowship_chain_url: Some(fellowship_fork_url),
-            fellowship: Some(submitted.referendum_id.to_string()),
-            port: Some(port),
-            verbose: true,
-            ..Default::default()
-        })
+        .run_test_referendum(
+            ToolArgs::builder()
+                .governance_chain_url(ctx.governance_endpoint())
+                .fellowship_chain_url(fellowship_fork)
+                .fellowship(submitted.referendum_id)
+                .port_range(ports.to_arg())
+                .verbose()
+                .build()?,
+        )
         .await?;
 
     output.check_success()?;
@@ -962,15 +2367,16 @@ async fn run_nonexistent_referendum(
     runner: &ToolRunner,
 ) -> Result<()> {
     log::info!("[nonexistent_referendum] Starting...");
-    let port = port_allocator::next_port();
+    let ports = port_allocator::next_tool_range();
     let output = runner
-        .run_test_referendum(ToolArgs {
-            governance_chain_url: Some(ctx.governance_url_with_block()),
-            referendum: Some("999".to_string()),
-            port: Some(port),
-            verbose: true,
-            ..Default::default()
-        })
+        .run_test_referendum(
+            ToolArgs::builder()
+                .governance_chain_url(ctx.governance_endpoint())
+                .referendum(999)
+                .port_range(ports.to_arg())
+                .verbose()
+                .build()?,
+        )
         .await?;
 
     output.check_failure()?;
@@ -987,19 +2393,21 @@ async fn run_fellowship_create_no_preimage(
         call_data::generate_fellowship_only_call_data(&ctx.coll_client, "FellowshipOrigins")
             .await?;
 
-    let port = port_allocator::next_port();
+    let ports = port_allocator::next_tool_range();
     let output = runner
-        .run_test_referendum(ToolArgs {
-            fellowship_chain_url: Some(ctx.fellowship_url_with_block()),
-            call_to_create_fellowship_referendum: Some(submit_hex),
-            port: Some(port),
-            verbose: true,
-            ..Default::default()
-        })
+        .run_test_referendum(
+            ToolArgs::builder()
+                .fellowship_chain_url(ctx.fellowship_endpoint())
+                .call_to_create_fellowship_referendum(submit_hex)
+                .port_range(ports.to_arg())
+                .verbose()
+                .build()?,
+        )
         .await?;
 
     output.check_failure()?;
     output.check_stdout_contains("execution failed")?;
+    check_unavailable_call_code(&output)?;
     Ok(())
 }
 
@@ -1007,6 +2415,18 @@ async fn run_fellowship_create_no_preimage(
 // Sub-test implementations — Kusama Governance (per-track create)
 // ═══════════════════════════════════════════════════════════════════════════
 
+/// The FellowshipCollective override is in the relay's genesis state and the
+/// AhMigrator override in Asset Hub's, and both decode against their runtimes.
+async fn run_kusama_raw_override_audit(ctx: &KusamaTestContext) -> Result<()> {
+    log::info!(">>> ksm_raw_override_audit");
+    override_audit::audit_chain(
+        &ctx.relay_client,
+        &raw_storage::fellowship_collective_entries(),
+    )
+    .await?;
+    override_audit::audit_chain(&ctx.ah_client, &raw_storage::ah_migrator_entries()).await
+}
+
 async fn run_kusama_gov_create_test(
     ctx: &KusamaTestContext,
     runner: &ToolRunner,
@@ -1017,16 +2437,18 @@ async fn run_kusama_gov_create_test(
     let (preimage_hex, submit_hex) =
         call_data::generate_governance_track_call_data(&ctx.ah_client, track, "Origins").await?;
 
-    let port = port_allocator::next_port();
+    let ports = port_allocator::next_tool_range();
     let output = runner
-        .run_test_referendum(ToolArgs {
-            governance_chain_url: Some(ctx.governance_url_with_block()),
-            call_to_create_governance_referendum: Some(submit_hex),
-            call_to_note_preimage_for_governance_referendum: Some(preimage_hex),
-            port: Some(port),
-            verbose: true,
-            ..Default::default()
-        })
+        .run_with_retries(
+            ToolArgs::builder()
+                .governance_chain_url(ctx.governance_endpoint())
+                .call_to_create_governance_referendum(submit_hex)
+                .call_to_note_preimage_for_governance_referendum(preimage_hex)
+                .port_range(ports.to_arg())
+                .verbose()
+                .build()?,
+            RetryPolicy::default(),
+        )
         .await?;
 
     output.check_success()?;
@@ -1043,23 +2465,23 @@ async fn run_kusama_gov_bynum_test(
     ctx: &KusamaTestContext,
     runner: &ToolRunner,
     track: &tracks::GovernanceTrack,
+    submitted: &SubmittedReferendum,
 ) -> Result<()> {
     log::info!(">>> ksm_gov_bynum_{} (track_id={})", track.name, track.id);
 
-    let submitted =
-        extrinsic_submitter::submit_governance_referendum(&ctx.ah_client, track, "Origins").await?;
-
-    let fork_url = format!("{},{}", ctx.asset_hub_ws_uri, submitted.block_number);
+    let fork = ChainEndpoint::new(&ctx.asset_hub_ws_uri)?.at_block(submitted.block_number);
 
-    let port = port_allocator::next_port();
+    let ports = port_allocator::next_tool_range();
     let output = runner
-        .run_test_referendum(ToolArgs {
-            governance_chain_url: Some(fork_url),
-            referendum: Some(submitted.referendum_id.to_string()),
-            port: Some(port),
-            verbose: true,
-            ..Default::default()
-        })
+        .run_with_retries(
+            ToolArgs::builder()
+                .governance_chain_url(fork)
+                .referendum(submitted.referendum_id)
+                .port_range(ports.to_arg())
+                .verbose()
+                .build()?,
+            RetryPolicy::default(),
+        )
         .await?;
 
     output.check_success()?;
@@ -1081,16 +2503,17 @@ async fn run_kusama_governance_happy_path(
     let (preimage_hex, gov_submit_hex) =
         call_data::generate_governance_call_data(&ctx.ah_client).await?;
 
-    let port = port_allocator::next_port();
+    let ports = port_allocator::next_tool_range();
     let output = runner
-        .run_test_referendum(ToolArgs {
-            governance_chain_url: Some(ctx.governance_url_with_block()),
-            call_to_create_governance_referendum: Some(gov_submit_hex),
-            call_to_note_preimage_for_governance_referendum: Some(preimage_hex),
-            port: Some(port),
-            verbose: true,
-            ..Default::default()
-        })
+        .run_test_referendum(
+            ToolArgs::builder()
+                .governance_chain_url(ctx.governance_endpoint())
+                .call_to_create_governance_referendum(gov_submit_hex)
+                .call_to_note_preimage_for_governance_referendum(preimage_hex)
+                .port_range(ports.to_arg())
+                .verbose()
+                .build()?,
+        )
         .await?;
 
     output.check_success()?;
@@ -1111,15 +2534,16 @@ async fn run_kusama_governance_inline_create(
     let gov_submit_hex =
         call_data::generate_governance_inline_call_data(&ctx.ah_client).await?;
 
-    let port = port_allocator::next_port();
+    let ports = port_allocator::next_tool_range();
     let output = runner
-        .run_test_referendum(ToolArgs {
-            governance_chain_url: Some(ctx.governance_url_with_block()),
-            call_to_create_governance_referendum: Some(gov_submit_hex),
-            port: Some(port),
-            verbose: true,
-            ..Default::default()
-        })
+        .run_test_referendum(
+            ToolArgs::builder()
+                .governance_chain_url(ctx.governance_endpoint())
+                .call_to_create_governance_referendum(gov_submit_hex)
+                .port_range(ports.to_arg())
+                .verbose()
+                .build()?,
+        )
         .await?;
 
     output.check_success()?;
@@ -1136,17 +2560,18 @@ async fn run_kusama_governance_inline_bynum(
     let submitted =
         extrinsic_submitter::submit_governance_referendum_inline(&ctx.ah_client).await?;
 
-    let fork_url = format!("{},{}", ctx.asset_hub_ws_uri, submitted.block_number);
+    let fork = ChainEndpoint::new(&ctx.asset_hub_ws_uri)?.at_block(submitted.block_number);
 
-    let port = port_allocator::next_port();
+    let ports = port_allocator::next_tool_range();
     let output = runner
-        .run_test_referendum(ToolArgs {
-            governance_chain_url: Some(fork_url),
-            referendum: Some(submitted.referendum_id.to_string()),
-            port: Some(port),
-            verbose: true,
-            ..Default::default()
-        })
+        .run_test_referendum(
+            ToolArgs::builder()
+                .governance_chain_url(fork)
+                .referendum(submitted.referendum_id)
+                .port_range(ports.to_arg())
+                .verbose()
+                .build()?,
+        )
         .await?;
 
     output.check_success()?;
@@ -1169,17 +2594,19 @@ async fn run_kusama_fellowship_create_test(
     let (preimage_hex, submit_hex) =
         call_data::generate_fellowship_track_call_data(&ctx.relay_client, track, "Origins").await?;
 
-    let port = port_allocator::next_port();
+    let ports = port_allocator::next_tool_range();
     let output = runner
-        .run_test_referendum(ToolArgs {
-            governance_chain_url: Some(ctx.governance_url_with_block()),
-            fellowship_chain_url: Some(ctx.fellowship_url_with_block()),
-            call_to_create_fellowship_referendum: Some(submit_hex),
-            call_to_note_preimage_for_fellowship_referendum: Some(preimage_hex),
-            port: Some(port),
-            verbose: true,
-            ..Default::default()
-        })
+        .run_with_retries(
+            ToolArgs::builder()
+                .governance_chain_url(ctx.governance_endpoint())
+                .fellowship_chain_url(ctx.fellowship_endpoint())
+                .call_to_create_fellowship_referendum(submit_hex)
+                .call_to_note_preimage_for_fellowship_referendum(preimage_hex)
+                .port_range(ports.to_arg())
+                .verbose()
+                .build()?,
+            RetryPolicy::default(),
+        )
         .await?;
 
     output.check_success()?;
@@ -1204,18 +2631,20 @@ async fn run_kusama_fellowship_bynum_test(
         extrinsic_submitter::submit_fellowship_referendum(&ctx.relay_client, track, "Origins")
             .await?;
 
-    let fellowship_fork_url = format!("{},{}", ctx.relay_ws_uri, submitted.block_number);
+    let fellowship_fork = ChainEndpoint::new(&ctx.relay_ws_uri)?.at_block(submitted.block_number);
 
-    let port = port_allocator::next_port();
+    let ports = port_allocator::next_tool_range();
     let output = runner
-        .run_test_referendum(ToolArgs {
-            governance_chain_url: Some(ctx.governance_url_with_block()),
-            fellowship_chain_url: Some(fellowship_fork_url),
-            fellowship: Some(submitted.referendum_id.to_string()),
-            port: Some(port),
-            verbose: true,
-            ..Default::default()
-        })
+        .run_with_retries(
+            ToolArgs::builder()
+                .governance_chain_url(ctx.governance_endpoint())
+                .fellowship_chain_url(fellowship_fork)
+                .fellowship(submitted.referendum_id)
+                .port_range(ports.to_arg())
+                .verbose()
+                .build()?,
+            RetryPolicy::default(),
+        )
         .await?;
 
     output.check_success()?;
@@ -1238,23 +2667,26 @@ async fn run_kusama_multichain_happy_path(
         call_data::generate_relay_upgrade_call_data(&ctx.ah_client, &ctx.relay_client, "Origins")
             .await?;
 
-    let port = port_allocator::next_port();
+    let ports = port_allocator::next_tool_range();
     let output = runner
-        .run_test_referendum(ToolArgs {
-            governance_chain_url: Some(ctx.governance_url_with_block()),
-            fellowship_chain_url: Some(ctx.fellowship_url_with_block()),
-            call_to_create_governance_referendum: Some(gov_submit_hex),
-            call_to_note_preimage_for_governance_referendum: Some(gov_preimage_hex),
-            call_to_create_fellowship_referendum: Some(fellowship_submit_hex),
-            call_to_note_preimage_for_fellowship_referendum: Some(fellowship_preimage_hex),
-            port: Some(port),
-            verbose: true,
-            ..Default::default()
-        })
+        .run_test_referendum(
+            ToolArgs::builder()
+                .governance_chain_url(ctx.governance_endpoint())
+                .fellowship_chain_url(ctx.fellowship_endpoint())
+                .call_to_create_governance_referendum(gov_submit_hex)
+                .call_to_note_preimage_for_governance_referendum(gov_preimage_hex)
+                .call_to_create_fellowship_referendum(fellowship_submit_hex)
+                .call_to_note_preimage_for_fellowship_referendum(fellowship_preimage_hex)
+                .port_range(ports.to_arg())
+                .verbose()
+                .build()?,
+        )
         .await?;
 
     output.check_success()?;
     output.check_stdout_contains("executed successfully")?;
+    output.check_referendum_passed("fellowship")?;
+    output.check_referendum_passed("governance")?;
     Ok(())
 }
 
@@ -1271,15 +2703,16 @@ async fn run_kusama_fellowship_inline_create(
     let submit_hex =
         call_data::generate_fellowship_inline_call_data(&ctx.relay_client, "Origins").await?;
 
-    let port = port_allocator::next_port();
+    let ports = port_allocator::next_tool_range();
     let output = runner
-        .run_test_referendum(ToolArgs {
-            fellowship_chain_url: Some(ctx.fellowship_url_with_block()),
-            call_to_create_fellowship_referendum: Some(submit_hex),
-            port: Some(port),
-            verbose: true,
-            ..Default::default()
-        })
+        .run_test_referendum(
+            ToolArgs::builder()
+                .fellowship_chain_url(ctx.fellowship_endpoint())
+                .call_to_create_fellowship_referendum(submit_hex)
+                .port_range(ports.to_arg())
+                .verbose()
+                .build()?,
+        )
         .await?;
 
     output.check_success()?;
@@ -1297,18 +2730,19 @@ async fn run_kusama_fellowship_inline_bynum(
         extrinsic_submitter::submit_fellowship_referendum_inline(&ctx.relay_client, "Origins")
             .await?;
 
-    let fellowship_fork_url = format!("{},{}", ctx.relay_ws_uri, submitted.block_number);
+    let fellowship_fork = ChainEndpoint::new(&ctx.relay_ws_uri)?.at_block(submitted.block_number);
 
-    let port = port_allocator::next_port();
+    let ports = port_allocator::next_tool_range();
     let output = runner
-        .run_test_referendum(ToolArgs {
-            governance_chain_url: Some(ctx.governance_url_with_block()),
-            fellowship_chain_url: Some(fellowship_fork_url),
-            fellowship: Some(submitted.referendum_id.to_string()),
-            port: Some(port),
-            verbose: true,
-            ..Default::default()
-        })
+        .run_test_referendum(
+            ToolArgs::builder()
+                .governance_chain_url(ctx.governance_endpoint())
+                .fellowship_chain_url(fellowship_fork)
+                .fellowship(submitted.referendum_id)
+                .port_range(ports.to_arg())
+                .verbose()
+                .build()?,
+        )
         .await?;
 
     output.check_success()?;
@@ -1325,19 +2759,600 @@ async fn run_kusama_fellowship_on_relay(
     let (preimage_hex, submit_hex) =
         call_data::generate_fellowship_only_call_data(&ctx.relay_client, "Origins").await?;
 
-    let port = port_allocator::next_port();
+    let ports = port_allocator::next_tool_range();
     let output = runner
-        .run_test_referendum(ToolArgs {
-            fellowship_chain_url: Some(ctx.fellowship_url_with_block()),
-            call_to_create_fellowship_referendum: Some(submit_hex),
-            call_to_note_preimage_for_fellowship_referendum: Some(preimage_hex),
-            port: Some(port),
-            verbose: true,
-            ..Default::default()
-        })
+        .run_test_referendum(
+            ToolArgs::builder()
+                .fellowship_chain_url(ctx.fellowship_endpoint())
+                .call_to_create_fellowship_referendum(submit_hex)
+                .call_to_note_preimage_for_fellowship_referendum(preimage_hex)
+                .port_range(ports.to_arg())
+                .verbose()
+                .build()?,
+        )
+        .await?;
+
+    output.check_success()?;
+    output.check_stdout_contains("executed successfully")?;
+    Ok(())
+}
+
+/// Kusama fellowship forked exactly at a session boundary, where Chopsticks may
+/// not serve the noted preimage. The tool must warn about the fork point and
+/// either enact the proposal or fail with `E-PREIMAGE-MISSING` naming the
+/// limitation, never a bare `CallUnavailable`.
+async fn run_kusama_fellowship_session_boundary(
+    ctx: &KusamaTestContext,
+    runner: &ToolRunner,
+) -> Result<()> {
+    log::info!("[ksm_fell_session_boundary] Starting...");
+    let Some(boundary_fork) = ctx.session_boundary_fellowship_endpoint().await? else {
+        log::warn!("[ksm_fell_session_boundary] No relay session boundary to fork at, skipping");
+        return Ok(());
+    };
+    let (preimage_hex, submit_hex) =
+        call_data::generate_fellowship_only_call_data(&ctx.relay_client, "Origins").await?;
+
+    let ports = port_allocator::next_tool_range();
+    let output = runner
+        .run_test_referendum(
+            ToolArgs::builder()
+                .fellowship_chain_url(boundary_fork)
+                .call_to_create_fellowship_referendum(submit_hex)
+                .call_to_note_preimage_for_fellowship_referendum(preimage_hex)
+                .port_range(ports.to_arg())
+                .verbose()
+                .build()?,
+        )
+        .await?;
+
+    output.check_any_output_contains("Forked at session boundary")?;
+    if output.check_success().is_ok() {
+        return output.check_stdout_contains("executed successfully");
+    }
+    output.check_error_code("E-PREIMAGE-MISSING")?;
+    output.check_any_output_contains("starts on a session boundary")
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Sub-test implementations — Kusama + Collectives topology permutations
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Fellowship-only on the relay: the tool must detect the fellowship chain as
+/// a relay chain.
+async fn run_permutation_fellowship_on_relay(
+    ctx: &TopologyPermutationContext,
+    runner: &ToolRunner,
+) -> Result<()> {
+    log::info!("[perm_fell_on_relay] Starting...");
+    let (preimage_hex, submit_hex) =
+        call_data::generate_fellowship_only_call_data(&ctx.kusama.relay_client, "Origins").await?;
+
+    let ports = port_allocator::next_tool_range();
+    let output = runner
+        .run_test_referendum(
+            ToolArgs::builder()
+                .fellowship_chain_url(ctx.relay_fellowship_endpoint())
+                .call_to_create_fellowship_referendum(submit_hex)
+                .call_to_note_preimage_for_fellowship_referendum(preimage_hex)
+                .port_range(ports.to_arg())
+                .verbose()
+                .build()?,
+        )
+        .await?;
+
+    output.check_success()?;
+    output.check_stdout_contains("Fellowship: Relay (relay)")?;
+    output.check_stdout_contains("executed successfully")?;
+    Ok(())
+}
+
+/// Fellowship-only on the Collectives parachain of a Kusama relay: the tool
+/// must detect it as a parachain although its runtime is Polkadot's.
+async fn run_permutation_fellowship_on_parachain(
+    ctx: &TopologyPermutationContext,
+    runner: &ToolRunner,
+) -> Result<()> {
+    log::info!("[perm_fell_on_parachain] Starting...");
+    let (preimage_hex, submit_hex) =
+        call_data::generate_fellowship_only_call_data(&ctx.coll_client, "FellowshipOrigins")
+            .await?;
+
+    let ports = port_allocator::next_tool_range();
+    let output = runner
+        .run_test_referendum(
+            ToolArgs::builder()
+                .fellowship_chain_url(ctx.parachain_fellowship_endpoint())
+                .call_to_create_fellowship_referendum(submit_hex)
+                .call_to_note_preimage_for_fellowship_referendum(preimage_hex)
+                .port_range(ports.to_arg())
+                .verbose()
+                .build()?,
+        )
+        .await?;
+
+    output.check_success()?;
+    output.check_stdout_contains("Fellowship: Collectives (parachain)")?;
+    output.check_stdout_contains("executed successfully")?;
+    Ok(())
+}
+
+/// Governance on Asset Hub with the fellowship on the relay (Kusama layout):
+/// one parachain and its relay forked together.
+async fn run_permutation_multichain_fellowship_on_relay(
+    ctx: &TopologyPermutationContext,
+    runner: &ToolRunner,
+) -> Result<()> {
+    log::info!("[perm_multichain_fell_on_relay] Starting...");
+    let (gov_preimage_hex, gov_submit_hex, fellowship_preimage_hex, fellowship_submit_hex) =
+        call_data::generate_relay_upgrade_call_data(
+            &ctx.kusama.ah_client,
+            &ctx.kusama.relay_client,
+            "Origins",
+        )
+        .await?;
+
+    let ports = port_allocator::next_tool_range();
+    let output = runner
+        .run_test_referendum(
+            ToolArgs::builder()
+                .governance_chain_url(ctx.kusama.governance_endpoint())
+                .fellowship_chain_url(ctx.relay_fellowship_endpoint())
+                .call_to_create_governance_referendum(gov_submit_hex)
+                .call_to_note_preimage_for_governance_referendum(gov_preimage_hex)
+                .call_to_create_fellowship_referendum(fellowship_submit_hex)
+                .call_to_note_preimage_for_fellowship_referendum(fellowship_preimage_hex)
+                .port_range(ports.to_arg())
+                .verbose()
+                .build()?,
+        )
+        .await?;
+
+    output.check_success()?;
+    output.check_stdout_contains("Governance: AssetHub (parachain)")?;
+    output.check_stdout_contains("Fellowship: Relay (relay)")?;
+    output.check_stdout_contains("executed successfully")?;
+    Ok(())
+}
+
+/// Governance on Asset Hub with the fellowship on Collectives (Polkadot
+/// layout) on a Kusama relay: two parachains, with the relay as an additional
+/// chain. The proposals are independent: Kusama's Asset Hub doesn't take
+/// whitelisting from a Collectives parachain.
+async fn run_permutation_multichain_fellowship_on_parachain(
+    ctx: &TopologyPermutationContext,
+    runner: &ToolRunner,
+) -> Result<()> {
+    log::info!("[perm_multichain_fell_on_parachain] Starting...");
+    let (gov_preimage_hex, gov_submit_hex) =
+        call_data::generate_governance_call_data(&ctx.kusama.ah_client).await?;
+    let (fellowship_preimage_hex, fellowship_submit_hex) =
+        call_data::generate_fellowship_only_call_data(&ctx.coll_client, "FellowshipOrigins")
+            .await?;
+
+    let ports = port_allocator::next_tool_range();
+    let output = runner
+        .run_test_referendum(
+            ToolArgs::builder()
+                .governance_chain_url(ctx.kusama.governance_endpoint())
+                .fellowship_chain_url(ctx.parachain_fellowship_endpoint())
+                .additional_chains(ctx.kusama.fellowship_endpoint())
+                .call_to_create_governance_referendum(gov_submit_hex)
+                .call_to_note_preimage_for_governance_referendum(gov_preimage_hex)
+                .call_to_create_fellowship_referendum(fellowship_submit_hex)
+                .call_to_note_preimage_for_fellowship_referendum(fellowship_preimage_hex)
+                .port_range(ports.to_arg())
+                .verbose()
+                .build()?,
+        )
+        .await?;
+
+    output.check_success()?;
+    output.check_stdout_contains("Governance: AssetHub (parachain)")?;
+    output.check_stdout_contains("Fellowship: Collectives (parachain)")?;
+    output.check_stdout_contains("executed successfully")?;
+    output.check_stdout_contains("Additional Chain Events")?;
+    Ok(())
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Sub-test implementations — Keep-open
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Run a governance referendum with its fork kept open, then use the fork
+/// like any node: read the enacted state, submit a follow-up extrinsic on top
+/// of it, and stop the tool, which must take the fork down with it.
+async fn run_keep_open_post_state(ctx: &GovernanceTestContext) -> Result<()> {
+    log::info!("[keep_open_post_state] Starting...");
+    let (preimage_hex, gov_submit_hex) =
+        call_data::generate_governance_call_data(&ctx.ah_client).await?;
+
+    let ports = port_allocator::next_tool_range();
+    let kept = Simulation::new(SimulationConfig {
+        governance_chain_url: Some(ctx.governance_endpoint().to_arg()),
+        call_to_create_governance_referendum: Some(gov_submit_hex),
+        call_to_note_preimage_for_governance_referendum: Some(preimage_hex),
+        port_range: Some(ports.to_arg()),
+        verbose: true,
+        ..Default::default()
+    })
+    .run_kept_open()
+    .await?;
+    ensure!(
+        kept.stdout().contains("executed successfully"),
+        "simulation did not report successful execution"
+    );
+
+    let head = kept.head("AssetHub")?.clone();
+    log::info!(
+        "[keep_open_post_state] Fork kept at {} (#{})",
+        head.endpoint,
+        head.end.number
+    );
+    let fork = OnlineClient::<PolkadotConfig>::from_insecure_url(&head.endpoint)
+        .await
+        .context("connect to the kept fork")?;
+
+    // The proposal authorized an upgrade; the fork serves the enacted state.
+    let authorized = query_storage(&fork, "System", "AuthorizedUpgrade", vec![], None).await?;
+    ensure!(
+        authorized.is_some(),
+        "System.AuthorizedUpgrade not set on the kept fork"
+    );
+
+    let included = extrinsic_submitter::submit_call(
+        &fork,
+        "System",
+        "remark_with_event",
+        vec![Value::from_bytes(b"keep-open-follow-up")],
+        &dev::alice(),
+    )
+    .await?;
+    ensure!(
+        u64::from(included.block_number) > head.end.number,
+        "follow-up included at #{}, not after the run's final head #{}",
+        included.block_number,
+        head.end.number
+    );
+
+    let report = kept.stop().await?;
+    ensure!(
+        OnlineClient::<PolkadotConfig>::from_insecure_url(&head.endpoint)
+            .await
+            .is_err(),
+        "fork at {} still serving after the tool stopped",
+        head.endpoint
+    );
+    log::info!(
+        "[keep_open_post_state] Tool stopped after {} block(s)",
+        report.blocks.len()
+    );
+    Ok(())
+}
+
+/// Keep a created referendum's fork alive through a `ToolRunner` session,
+/// assert on the enacted state over RPC, then stop the session.
+async fn run_session_post_state(ctx: &GovernanceTestContext, runner: &ToolRunner) -> Result<()> {
+    log::info!(">>> session_post_state");
+    let (preimage_hex, gov_submit_hex) =
+        call_data::generate_governance_call_data(&ctx.ah_client).await?;
+
+    let ports = port_allocator::next_tool_range();
+    let session = runner
+        .spawn_session(
+            ToolArgs::builder()
+                .governance_chain_url(ctx.governance_endpoint())
+                .call_to_create_governance_referendum(gov_submit_hex)
+                .call_to_note_preimage_for_governance_referendum(preimage_hex)
+                .port_range(ports.to_arg())
+                .verbose()
+                .build()?,
+        )
+        .await?;
+    ensure!(
+        session.stdout().contains("executed successfully"),
+        "session [{}] did not report successful execution",
+        session.run_id()
+    );
+
+    let endpoint = session.endpoint("AssetHub")?.to_string();
+    let fork = OnlineClient::<PolkadotConfig>::from_insecure_url(&endpoint)
+        .await
+        .context("connect to the session's fork")?;
+    let authorized = query_storage(&fork, "System", "AuthorizedUpgrade", vec![], None).await?;
+    ensure!(
+        authorized.is_some(),
+        "System.AuthorizedUpgrade not set on the session's fork"
+    );
+
+    let output = session.stop().await?;
+    ensure!(
+        output.error_kind() == Some(ToolErrorKind::Interrupted) && output.exit_code == 130,
+        "session [{}] did not stop cleanly: exit code {}, {:?}",
+        output.run_id,
+        output.exit_code,
+        output.error_code()
+    );
+    ensure!(
+        output.created_referendum().is_some(),
+        "session [{}] output reports no created referendum",
+        output.run_id
+    );
+    Ok(())
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Sub-test implementations — Whitelist
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Code hash of the relay upgrade the fellowship-whitelisted call authorizes.
+const WHITELISTED_RELAY_CODE_HASH: [u8; 32] = [0x42; 32];
+
+/// Code hash of an Asset Hub upgrade that is never whitelisted.
+const NOT_WHITELISTED_CODE_HASH: [u8; 32] = [0x78; 32];
+
+/// The Whitelist override is in Asset Hub's genesis state and decodes against
+/// its runtime metadata, and the hand-encoded call it whitelists is the one
+/// the runtime encodes.
+async fn run_whitelist_raw_override_audit(ctx: &MultiChainTestContext) -> Result<()> {
+    log::info!(">>> wl_raw_override_audit");
+    override_audit::audit_chain(&ctx.ah_client, &raw_storage::whitelist_entries()).await?;
+    let encoded =
+        call_data::authorize_upgrade_call(&ctx.ah_client, raw_storage::PRE_WHITELISTED_CODE_HASH)?;
+    ensure!(
+        encoded == raw_storage::pre_whitelisted_call(),
+        "Asset Hub encodes the pre-whitelisted call as 0x{}, the override whitelists 0x{}",
+        hex::encode(&encoded),
+        hex::encode(raw_storage::pre_whitelisted_call())
+    );
+    Ok(())
+}
+
+/// The full flow: the fellowship referendum whitelists the call on Asset Hub
+/// over XCM, the `WhitelistedCaller` referendum dispatches it, and the call's
+/// XCM to the relay authorizes an upgrade there.
+async fn run_whitelist_fellowship_whitelists(
+    ctx: &MultiChainTestContext,
+    runner: &ToolRunner,
+) -> Result<()> {
+    log::info!("[wl_fellowship_whitelists] Starting...");
+    let call = call_data::relay_upgrade_via_xcm_call(
+        &ctx.ah_client,
+        &ctx.relay_client,
+        WHITELISTED_RELAY_CODE_HASH,
+    )?;
+    let (gov_preimage_hex, gov_submit_hex) =
+        call_data::generate_whitelisted_caller_call_data(&ctx.ah_client, &call).await?;
+    let (fellowship_preimage_hex, fellowship_submit_hex) =
+        call_data::generate_fellowship_whitelist_call_data(
+            &ctx.ah_client,
+            &ctx.coll_client,
+            sp_crypto_hashing::blake2_256(&call),
+        )
+        .await?;
+
+    let ports = port_allocator::next_tool_range();
+    let output = runner
+        .run_test_referendum(
+            ToolArgs::builder()
+                .governance_chain_url(ctx.governance_endpoint())
+                .fellowship_chain_url(ctx.fellowship_endpoint())
+                .additional_chains(ctx.relay_endpoint())
+                .settle_blocks(3)
+                .call_to_create_governance_referendum(gov_submit_hex)
+                .call_to_note_preimage_for_governance_referendum(gov_preimage_hex)
+                .call_to_create_fellowship_referendum(fellowship_submit_hex)
+                .call_to_note_preimage_for_fellowship_referendum(fellowship_preimage_hex)
+                .port_range(ports.to_arg())
+                .verbose()
+                .build()?,
+        )
+        .await?;
+
+    output.check_success()?;
+    output.check_referendum_passed("fellowship")?;
+    output.check_referendum_passed("governance")?;
+    output.check_stdout_contains("Whitelist.WhitelistedCallDispatched")?;
+    let events = output.events();
+    ensure!(
+        events
+            .iter()
+            .any(|e| e.chain.as_deref() == Some("Relay") && e.is("System", "UpgradeAuthorized")),
+        "The relay did not emit System.UpgradeAuthorized"
+    );
+    Ok(())
+}
+
+/// Governance only: the call whitelisted in genesis is dispatched without a
+/// fellowship referendum.
+async fn run_whitelist_pre_whitelisted_create(
+    ctx: &MultiChainTestContext,
+    runner: &ToolRunner,
+) -> Result<()> {
+    log::info!("[wl_pre_whitelisted_create] Starting...");
+    let (preimage_hex, gov_submit_hex) = call_data::generate_whitelisted_caller_call_data(
+        &ctx.ah_client,
+        &raw_storage::pre_whitelisted_call(),
+    )
+    .await?;
+
+    let ports = port_allocator::next_tool_range();
+    let output = runner
+        .run_test_referendum(
+            ToolArgs::builder()
+                .governance_chain_url(ctx.governance_endpoint())
+                .call_to_create_governance_referendum(gov_submit_hex)
+                .call_to_note_preimage_for_governance_referendum(preimage_hex)
+                .port_range(ports.to_arg())
+                .verbose()
+                .build()?,
+        )
         .await?;
 
     output.check_success()?;
     output.check_stdout_contains("executed successfully")?;
+    // The whitelisted call's events come before the dispatch that wraps it
+    output.check_event_sequence(&[
+        ("System", "UpgradeAuthorized"),
+        ("Whitelist", "WhitelistedCallDispatched"),
+    ])?;
+    ensure!(
+        output
+            .check_event_sequence(&[
+                ("Whitelist", "WhitelistedCallDispatched"),
+                ("System", "UpgradeAuthorized"),
+            ])
+            .is_err(),
+        "reversed event sequence matched the execution block's repeated listing"
+    );
+    let dispatch_block = output
+        .created_referendum()
+        .context("tool did not report the referendum it created")?
+        .dispatch_block
+        .context("no dispatch block")?;
+    output.check_event_in_block(dispatch_block, "Whitelist", "WhitelistedCallDispatched")?;
+    output.check_event_in_block(dispatch_block, "System", "UpgradeAuthorized")?;
+    Ok(())
+}
+
+/// The pre-whitelisted dispatch, submitted to the live Asset Hub and given to
+/// the tool by number.
+async fn run_whitelist_pre_whitelisted_bynum(
+    ctx: &MultiChainTestContext,
+    runner: &ToolRunner,
+    submitted: &SubmittedReferendum,
+) -> Result<()> {
+    log::info!(
+        "[wl_pre_whitelisted_bynum] referendum #{}",
+        submitted.referendum_id
+    );
+    let fork = ChainEndpoint::new(&ctx.asset_hub_ws_uri)?.at_block(submitted.block_number);
+
+    let ports = port_allocator::next_tool_range();
+    let output = runner
+        .run_test_referendum(
+            ToolArgs::builder()
+                .governance_chain_url(fork)
+                .referendum(submitted.referendum_id)
+                .port_range(ports.to_arg())
+                .verbose()
+                .build()?,
+        )
+        .await?;
+
+    output.check_success()?;
+    output.check_stdout_contains(&format!(
+        "referendum #{} executed successfully",
+        submitted.referendum_id
+    ))?;
+    output.check_stdout_contains("Whitelist.WhitelistedCallDispatched")?;
+    Ok(())
+}
+
+/// A `WhitelistedCaller` referendum whose call nobody whitelisted passes its
+/// vote but fails at dispatch with `Whitelist.CallIsNotWhitelisted`, so the
+/// upgrade it carries is never authorized.
+async fn run_whitelist_not_whitelisted(
+    ctx: &MultiChainTestContext,
+    runner: &ToolRunner,
+) -> Result<()> {
+    log::info!("[wl_not_whitelisted] Starting...");
+    let call = call_data::authorize_upgrade_call(&ctx.ah_client, NOT_WHITELISTED_CODE_HASH)?;
+    let (preimage_hex, gov_submit_hex) =
+        call_data::generate_whitelisted_caller_call_data(&ctx.ah_client, &call).await?;
+
+    let ports = port_allocator::next_tool_range();
+    let output = runner
+        .run_test_referendum(
+            ToolArgs::builder()
+                .governance_chain_url(ctx.governance_endpoint())
+                .call_to_create_governance_referendum(gov_submit_hex)
+                .call_to_note_preimage_for_governance_referendum(preimage_hex)
+                .port_range(ports.to_arg())
+                .verbose()
+                .build()?,
+        )
+        .await?;
+
+    output.check_failure()?;
+    output.check_error_code("E-EXECUTION-FAILED")?;
+    output.check_any_output_contains("CallIsNotWhitelisted")?;
+    output.check_event_absent("Whitelist", "WhitelistedCallDispatched")?;
+    output.check_event_absent("System", "UpgradeAuthorized")?;
+    Ok(())
+}
+
+/// The fellowship whitelists a different call than the one governance
+/// dispatches: the fellowship referendum passes, the governance one fails,
+/// and the run is a partial success.
+async fn run_whitelist_fellowship_whitelists_other_call(
+    ctx: &MultiChainTestContext,
+    runner: &ToolRunner,
+) -> Result<()> {
+    log::info!("[wl_fellowship_whitelists_other_call] Starting...");
+    let call = call_data::authorize_upgrade_call(&ctx.ah_client, NOT_WHITELISTED_CODE_HASH)?;
+    let other_call = call_data::relay_upgrade_via_xcm_call(
+        &ctx.ah_client,
+        &ctx.relay_client,
+        WHITELISTED_RELAY_CODE_HASH,
+    )?;
+    let (gov_preimage_hex, gov_submit_hex) =
+        call_data::generate_whitelisted_caller_call_data(&ctx.ah_client, &call).await?;
+    let (fellowship_preimage_hex, fellowship_submit_hex) =
+        call_data::generate_fellowship_whitelist_call_data(
+            &ctx.ah_client,
+            &ctx.coll_client,
+            sp_crypto_hashing::blake2_256(&other_call),
+        )
+        .await?;
+
+    let ports = port_allocator::next_tool_range();
+    let output = runner
+        .run_test_referendum(
+            ToolArgs::builder()
+                .governance_chain_url(ctx.governance_endpoint())
+                .fellowship_chain_url(ctx.fellowship_endpoint())
+                .call_to_create_governance_referendum(gov_submit_hex)
+                .call_to_note_preimage_for_governance_referendum(gov_preimage_hex)
+                .call_to_create_fellowship_referendum(fellowship_submit_hex)
+                .call_to_note_preimage_for_fellowship_referendum(fellowship_preimage_hex)
+                .port_range(ports.to_arg())
+                .verbose()
+                .build()?,
+        )
+        .await?;
+
+    output.check_error_code("E-PARTIAL-SUCCESS")?;
+    output.check_referendum_passed("fellowship")?;
+    output.check_referendum_failed("governance", "E-EXECUTION-FAILED")?;
+    output.check_any_output_contains("CallIsNotWhitelisted")?;
+    // Neither the dispatched call nor the one the fellowship whitelisted ran.
+    output.check_event_absent("Whitelist", "WhitelistedCallDispatched")?;
+    output.check_event_absent("System", "UpgradeAuthorized")?;
+    Ok(())
+}
+
+/// An enactment whose preimage can't be fetched fails with `E-PREIMAGE-MISSING`
+/// (exit code 2) when the scheduler reports `CallUnavailable`, or
+/// `E-EXECUTION-FAILED` when no dispatch is observed at all — either is a
+/// correct rejection.
+fn check_unavailable_call_code(output: &ToolOutput) -> Result<()> {
+    let kind = output.error_kind();
+    anyhow::ensure!(
+        matches!(
+            kind,
+            Some(ToolErrorKind::PreimageMissing | ToolErrorKind::DispatchFailed)
+        ),
+        "Expected a PreimageMissing or DispatchFailed failure, got {kind:?} ({:?}).\n--- stderr ---\n{}",
+        output.error_code(),
+        output.stderr,
+    );
+    if kind == Some(ToolErrorKind::PreimageMissing) {
+        anyhow::ensure!(
+            output.exit_code == 2,
+            "Expected exit code 2 for a proposal the scheduler never dispatched, got {}",
+            output.exit_code,
+        );
+    }
     Ok(())
 }
@@ -5,6 +5,7 @@
 
 use anyhow::{Context, Result};
 use subxt::dynamic::{self, Value};
+use subxt::tx::DynamicPayload;
 use subxt::{OnlineClient, PolkadotConfig};
 
 /// Generate governance-only call data for a simple referendum test.
@@ -42,7 +43,7 @@ pub async fn generate_governance_call_data(
 
     // Compute proposal hash and length.
     let proposal_hash = blake2_256(&authorize_bytes);
-    let proposal_len = authorize_bytes.len() as u32;
+    let proposal_len = u32::try_from(authorize_bytes.len())?;
 
     log::info!(
         "Proposal hash: 0x{}, len: {}",
@@ -118,7 +119,7 @@ pub async fn generate_relay_upgrade_call_data(
         .context("Failed to encode governance Preimage.note_preimage")?;
 
     let gov_proposal_hash = blake2_256(&authorize_bytes);
-    let gov_proposal_len = authorize_bytes.len() as u32;
+    let gov_proposal_len = u32::try_from(authorize_bytes.len())?;
 
     log::info!(
         "Gov proposal hash: 0x{}, len: {}",
@@ -170,7 +171,7 @@ pub async fn generate_relay_upgrade_call_data(
         .context("Failed to encode fellowship Preimage.note_preimage")?;
 
     let fellowship_proposal_hash = blake2_256(&remark_bytes);
-    let fellowship_proposal_len = remark_bytes.len() as u32;
+    let fellowship_proposal_len = u32::try_from(remark_bytes.len())?;
 
     log::info!(
         "Fellowship proposal hash: 0x{}, len: {}",
@@ -300,7 +301,7 @@ pub async fn generate_remark_referendum_call_data(
         .context("Failed to encode Preimage.note_preimage for remark")?;
 
     let proposal_hash = blake2_256(&remark_bytes);
-    let proposal_len = remark_bytes.len() as u32;
+    let proposal_len = u32::try_from(remark_bytes.len())?;
 
     log::info!(
         "Remark proposal hash: 0x{}, len: {}",
@@ -383,7 +384,7 @@ pub async fn generate_fellowship_only_call_data(
         .context("Failed to encode fellowship Preimage.note_preimage")?;
 
     let proposal_hash = blake2_256(&remark_bytes);
-    let proposal_len = remark_bytes.len() as u32;
+    let proposal_len = u32::try_from(remark_bytes.len())?;
 
     log::info!(
         "Fellowship-only proposal hash: 0x{}, len: {}",
@@ -455,7 +456,7 @@ pub async fn generate_governance_track_call_data(
         .context("Failed to encode Preimage.note_preimage")?;
 
     let proposal_hash = blake2_256(&remark_bytes);
-    let proposal_len = remark_bytes.len() as u32;
+    let proposal_len = u32::try_from(remark_bytes.len())?;
 
     // Build the proposal origin based on the track type
     let proposal_origin = if track.is_root {
@@ -499,11 +500,38 @@ pub async fn generate_fellowship_track_call_data(
     track: &super::tracks::FellowshipTrack,
     fellowship_origin_variant: &str,
 ) -> Result<(String, String)> {
+    generate_ranked_track_call_data(
+        client,
+        "FellowshipReferenda",
+        track,
+        fellowship_origin_variant,
+    )
+    .await
+}
+
+/// Generate ambassador call data for any track on Polkadot Collectives:
+/// an `AmbassadorReferenda.submit` with an `AmbassadorOrigins` origin.
+pub async fn generate_ambassador_track_call_data(
+    client: &OnlineClient<PolkadotConfig>,
+    track: &super::tracks::FellowshipTrack,
+) -> Result<(String, String)> {
+    generate_ranked_track_call_data(client, "AmbassadorReferenda", track, "AmbassadorOrigins").await
+}
+
+/// `(preimage_hex, submit_hex)` for a `System.remark` referendum on a track of
+/// the ranked collective whose referenda pallet is `pallet`.
+async fn generate_ranked_track_call_data(
+    client: &OnlineClient<PolkadotConfig>,
+    pallet: &str,
+    track: &super::tracks::FellowshipTrack,
+    origin_variant: &str,
+) -> Result<(String, String)> {
+    let collective = pallet.trim_end_matches("Referenda");
     let remark_call = dynamic::tx(
         "System",
         "remark",
         vec![Value::from_bytes(
-            format!("fellowship-track-{}-test", track.name).into_bytes(),
+            format!("{}-track-{}-test", collective.to_lowercase(), track.name).into_bytes(),
         )],
     );
     let remark_bytes = client
@@ -512,7 +540,7 @@ pub async fn generate_fellowship_track_call_data(
         .context("Failed to encode System.remark")?;
 
     log::info!(
-        "Fellowship track {} (id={}) remark call data: {} bytes",
+        "{collective} track {} (id={}) remark call data: {} bytes",
         track.name,
         track.id,
         remark_bytes.len()
@@ -527,14 +555,14 @@ pub async fn generate_fellowship_track_call_data(
         .context("Failed to encode Preimage.note_preimage")?;
 
     let proposal_hash = blake2_256(&remark_bytes);
-    let proposal_len = remark_bytes.len() as u32;
+    let proposal_len = u32::try_from(remark_bytes.len())?;
 
     let submit_call = dynamic::tx(
-        "FellowshipReferenda",
+        pallet,
         "submit",
         vec![
             Value::unnamed_variant(
-                fellowship_origin_variant,
+                origin_variant,
                 vec![Value::unnamed_variant(track.origin_variant, vec![])],
             ),
             Value::unnamed_variant(
@@ -548,7 +576,7 @@ pub async fn generate_fellowship_track_call_data(
         ],
     );
     let submit_hex = encode_call_hex(client, &submit_call)
-        .context("Failed to encode FellowshipReferenda.submit")?;
+        .with_context(|| format!("Failed to encode {pallet}.submit"))?;
 
     Ok((preimage_hex, submit_hex))
 }
@@ -629,6 +657,252 @@ pub async fn generate_fellowship_inline_call_data(
     Ok(submit_hex)
 }
 
+// ─── Whitelist ───────────────────────────────────────────────────────────────
+
+/// `Whitelist.dispatch_whitelisted_call_with_preimage(call)` on Asset Hub,
+/// `call` being encoded call data. Built from the metadata's indices rather
+/// than a dynamic value, as the argument is a whole `RuntimeCall`.
+pub fn whitelisted_dispatch_call(
+    ah_client: &OnlineClient<PolkadotConfig>,
+    call: &[u8],
+) -> Result<Vec<u8>> {
+    let metadata = ah_client.metadata();
+    let pallet = metadata
+        .pallet_by_name("Whitelist")
+        .context("Asset Hub has no Whitelist pallet")?;
+    let variant = pallet
+        .call_variant_by_name("dispatch_whitelisted_call_with_preimage")
+        .context("Whitelist has no dispatch_whitelisted_call_with_preimage call")?;
+    let mut bytes = vec![pallet.index(), variant.index];
+    bytes.extend_from_slice(call);
+    Ok(bytes)
+}
+
+/// Governance call data for a `WhitelistedCaller` referendum on Asset Hub
+/// whose proposal dispatches `call` (encoded call data) through
+/// [`whitelisted_dispatch_call`]. The referendum only passes if `call`'s hash
+/// is whitelisted by the time it is enacted.
+///
+/// Returns (preimage_hex, gov_submit_hex).
+pub async fn generate_whitelisted_caller_call_data(
+    ah_client: &OnlineClient<PolkadotConfig>,
+    call: &[u8],
+) -> Result<(String, String)> {
+    let proposal = whitelisted_dispatch_call(ah_client, call)?;
+
+    log::info!(
+        "Whitelisted call hash: 0x{}, proposal: {} bytes",
+        hex::encode(blake2_256(call)),
+        proposal.len()
+    );
+
+    let preimage_call = dynamic::tx(
+        "Preimage",
+        "note_preimage",
+        vec![Value::from_bytes(proposal.clone())],
+    );
+    let preimage_hex = encode_call_hex(ah_client, &preimage_call)
+        .context("Failed to encode governance Preimage.note_preimage")?;
+
+    let submit_call = dynamic::tx(
+        "Referenda",
+        "submit",
+        vec![
+            Value::unnamed_variant(
+                "Origins",
+                vec![Value::unnamed_variant("WhitelistedCaller", vec![])],
+            ),
+            Value::unnamed_variant(
+                "Lookup",
+                vec![
+                    Value::from_bytes(blake2_256(&proposal)),
+                    Value::u128(proposal.len() as u128),
+                ],
+            ),
+            Value::unnamed_variant("After", vec![Value::u128(0u128)]),
+        ],
+    );
+    let submit_hex =
+        encode_call_hex(ah_client, &submit_call).context("Failed to encode Referenda.submit")?;
+
+    Ok((preimage_hex, submit_hex))
+}
+
+/// `System.authorize_upgrade(code_hash)` on Asset Hub, as call data.
+pub fn authorize_upgrade_call(
+    ah_client: &OnlineClient<PolkadotConfig>,
+    code_hash: [u8; 32],
+) -> Result<Vec<u8>> {
+    let call = dynamic::tx(
+        "System",
+        "authorize_upgrade",
+        vec![Value::from_bytes(code_hash)],
+    );
+    ah_client
+        .tx()
+        .call_data(&call)
+        .context("Failed to encode System.authorize_upgrade call data")
+}
+
+/// An XCM v4 location: `parents` up, then `interior` (a `Junctions` variant).
+fn xcm_location(parents: u8, interior: Value) -> Value {
+    Value::named_composite([
+        ("parents", Value::u128(parents as u128)),
+        ("interior", interior),
+    ])
+}
+
+/// `PolkadotXcm.send` to `dest` of an unpaid `Transact` of `call`, dispatched
+/// there with `origin_kind` (`Superuser`, `Xcm`, ...). Unpaid execution is
+/// allowed for system parachains and the fellowship, which covers every
+/// sender here.
+fn xcm_transact_send(dest: Value, origin_kind: &str, call: Vec<u8>) -> DynamicPayload {
+    let instructions = vec![
+        Value::named_variant(
+            "UnpaidExecution",
+            [
+                ("weight_limit", Value::unnamed_variant("Unlimited", vec![])),
+                ("check_origin", Value::unnamed_variant("None", vec![])),
+            ],
+        ),
+        Value::named_variant(
+            "Transact",
+            [
+                ("origin_kind", Value::unnamed_variant(origin_kind, vec![])),
+                (
+                    "require_weight_at_most",
+                    Value::named_composite([
+                        ("ref_time", Value::u128(5_000_000_000)),
+                        ("proof_size", Value::u128(500_000)),
+                    ]),
+                ),
+                (
+                    "call",
+                    Value::named_composite([("encoded", Value::from_bytes(call))]),
+                ),
+            ],
+        ),
+    ];
+    dynamic::tx(
+        "PolkadotXcm",
+        "send",
+        vec![
+            Value::unnamed_variant("V4", vec![dest]),
+            Value::unnamed_variant(
+                "V4",
+                vec![Value::unnamed_composite(vec![Value::unnamed_composite(
+                    instructions,
+                )])],
+            ),
+        ],
+    )
+}
+
+/// Asset Hub call data that authorizes a relay runtime upgrade to
+/// `code_hash`: a `PolkadotXcm.send` to the relay of `System.authorize_upgrade`
+/// as `Superuser`, which the relay grants its system parachains. Must be
+/// dispatched as Root, e.g. as a whitelisted call.
+pub fn relay_upgrade_via_xcm_call(
+    ah_client: &OnlineClient<PolkadotConfig>,
+    relay_client: &OnlineClient<PolkadotConfig>,
+    code_hash: [u8; 32],
+) -> Result<Vec<u8>> {
+    let relay_call = dynamic::tx(
+        "System",
+        "authorize_upgrade",
+        vec![Value::from_bytes(code_hash)],
+    );
+    let relay_bytes = relay_client
+        .tx()
+        .call_data(&relay_call)
+        .context("Failed to encode relay System.authorize_upgrade call data")?;
+
+    let send = xcm_transact_send(
+        xcm_location(1, Value::unnamed_variant("Here", vec![])),
+        "Superuser",
+        relay_bytes,
+    );
+    ah_client
+        .tx()
+        .call_data(&send)
+        .context("Failed to encode PolkadotXcm.send to the relay")
+}
+
+/// Fellowship call data for a `Fellows` referendum on Collectives whose
+/// proposal whitelists `whitelisted_hash` on Asset Hub: a `PolkadotXcm.send` to
+/// Asset Hub (para 1000) of `Whitelist.whitelist_call` with an `Xcm` origin,
+/// which Asset Hub accepts from the Fellows plurality.
+///
+/// Returns (fellowship_preimage_hex, fellowship_submit_hex).
+pub async fn generate_fellowship_whitelist_call_data(
+    ah_client: &OnlineClient<PolkadotConfig>,
+    coll_client: &OnlineClient<PolkadotConfig>,
+    whitelisted_hash: [u8; 32],
+) -> Result<(String, String)> {
+    let whitelist_call = dynamic::tx(
+        "Whitelist",
+        "whitelist_call",
+        vec![Value::from_bytes(whitelisted_hash)],
+    );
+    let whitelist_bytes = ah_client
+        .tx()
+        .call_data(&whitelist_call)
+        .context("Failed to encode Whitelist.whitelist_call")?;
+
+    let asset_hub = xcm_location(
+        1,
+        Value::unnamed_variant(
+            "X1",
+            vec![Value::unnamed_composite(vec![Value::unnamed_variant(
+                "Parachain",
+                vec![Value::u128(1000)],
+            )])],
+        ),
+    );
+    let send = xcm_transact_send(asset_hub, "Xcm", whitelist_bytes);
+    let proposal = coll_client
+        .tx()
+        .call_data(&send)
+        .context("Failed to encode PolkadotXcm.send to Asset Hub")?;
+
+    log::info!(
+        "Fellowship whitelist proposal for 0x{}: {} bytes",
+        hex::encode(whitelisted_hash),
+        proposal.len()
+    );
+
+    let preimage_call = dynamic::tx(
+        "Preimage",
+        "note_preimage",
+        vec![Value::from_bytes(proposal.clone())],
+    );
+    let preimage_hex = encode_call_hex(coll_client, &preimage_call)
+        .context("Failed to encode fellowship Preimage.note_preimage")?;
+
+    let submit_call = dynamic::tx(
+        "FellowshipReferenda",
+        "submit",
+        vec![
+            Value::unnamed_variant(
+                "FellowshipOrigins",
+                vec![Value::unnamed_variant("Fellows", vec![])],
+            ),
+            Value::unnamed_variant(
+                "Lookup",
+                vec![
+                    Value::from_bytes(blake2_256(&proposal)),
+                    Value::u128(proposal.len() as u128),
+                ],
+            ),
+            Value::unnamed_variant("After", vec![Value::u128(0u128)]),
+        ],
+    );
+    let submit_hex = encode_call_hex(coll_client, &submit_call)
+        .context("Failed to encode FellowshipReferenda.submit")?;
+
+    Ok((preimage_hex, submit_hex))
+}
+
 /// Encode a dynamic transaction payload to hex call data bytes.
 fn encode_call_hex<Call: subxt::tx::Payload>(
     client: &OnlineClient<PolkadotConfig>,
@@ -0,0 +1,315 @@
+//! Suite-level event database.
+//!
+//! Every decoded event the tool prints in verbose mode (`• Section.Method`
+//! followed by an optional `Data: {...}` block) is parsed out of stdout and
+//! appended as one JSON line to `<EVENT_DB_DIR>/<suite>.jsonl`:
+//!
+//! ```json
+//! {"suite":"...","sub_test":"...","chain":"...","block":12,"event":"Scheduler.Dispatched","fields":{...}}
+//! ```
+//!
+//! This allows offline questions such as "which tracks emitted
+//! Scheduler.CallUnavailable" to be answered with `jq` without re-running
+//! the suite.
+
+use anyhow::{Context, Result};
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// Env var overriding the directory where suite event databases are written.
+pub const EVENT_DB_DIR_ENV: &str = "EVENT_DB_DIR";
+/// Default event database directory (relative to integration-tests/).
+pub const DEFAULT_EVENT_DB_DIR: &str = "target/event-db";
+
+tokio::task_local! {
+    /// Label of the sub-test currently running on this task. Set by
+    /// `run_and_bail!` so tool invocations can be attributed without threading
+    /// the label through every sub-test function.
+    pub static CURRENT_SUB_TEST: String;
+}
+
+/// Returns the label of the sub-test running on the current task, if any.
+pub fn current_sub_test() -> String {
+    CURRENT_SUB_TEST
+        .try_with(|label| label.clone())
+        .unwrap_or_else(|_| "unknown".to_string())
+}
+
+/// A single event decoded from tool output.
+#[derive(Debug, Clone)]
+pub struct ObservedEvent {
+    pub chain: Option<String>,
+    pub block: Option<u64>,
+    /// Position within the block's event listing; a block printed twice
+    /// (e.g. the execution block) repeats the same indices.
+    pub index: usize,
+    /// `Section.Method`, e.g. `Scheduler.Dispatched`.
+    pub event: String,
+    pub fields: serde_json::Value,
+}
+
+/// Append-only JSONL sink shared by all sub-tests of one suite.
+pub struct EventDb {
+    suite: String,
+    path: PathBuf,
+    file: Mutex<File>,
+}
+
+impl EventDb {
+    /// Create (truncating) the event database for `suite`.
+    pub fn for_suite(suite: &str) -> Result<Self> {
+        let dir = std::env::var(EVENT_DB_DIR_ENV)
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| PathBuf::from(DEFAULT_EVENT_DB_DIR));
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create event db dir {}", dir.display()))?;
+
+        let path = dir.join(format!("{suite}.jsonl"));
+        let file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(&path)
+            .with_context(|| format!("Failed to open event db {}", path.display()))?;
+
+        log::info!("Event database: {}", path.display());
+        Ok(Self {
+            suite: suite.to_string(),
+            path,
+            file: Mutex::new(file),
+        })
+    }
+
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+
+    /// Parse events out of `stdout` and append them, attributed to `sub_test`.
+    pub fn record(&self, sub_test: &str, stdout: &str) -> Result<usize> {
+        let events = parse_events(stdout);
+        let mut file = self.file.lock().expect("event db lock poisoned");
+        for event in &events {
+            let line = serde_json::json!({
+                "suite": self.suite,
+                "sub_test": sub_test,
+                "chain": event.chain,
+                "block": event.block,
+                "event": event.event,
+                "fields": event.fields,
+            });
+            writeln!(file, "{line}")
+                .with_context(|| format!("Failed to write to {}", self.path.display()))?;
+        }
+        file.flush()?;
+        Ok(events.len())
+    }
+}
+
+// ── Output parsing ───────────────────────────────────────────────────────────
+
+/// Strip the logger's `[<run id>]` tag and leading status glyph (ℹ ✓ ✖ ⚠ ▸),
+/// each with one space.
+fn strip_log_prefix(line: &str) -> &str {
+    let line = line
+        .strip_prefix('[')
+        .and_then(|rest| rest.split_once("] "))
+        .filter(|(id, _)| {
+            !id.is_empty()
+                && id
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "._-".contains(c))
+        })
+        .map_or(line, |(_, rest)| rest.trim_start());
+    for glyph in ['ℹ', '✓', '✖', '⚠', '▸'] {
+        if let Some(rest) = line.strip_prefix(glyph) {
+            return rest.strip_prefix(' ').unwrap_or(rest);
+        }
+    }
+    line
+}
+
+/// Extract `label` from lines like `Detected chain: <label> (<spec>)`.
+fn label_before_paren(rest: &str) -> String {
+    rest.split(" (").next().unwrap_or(rest).trim().to_string()
+}
+
+/// Parse all `• Section.Method` events (plus their `Data:` payloads) from
+/// verbose tool stdout, tracking which chain and block they belong to.
+pub fn parse_events(stdout: &str) -> Vec<ObservedEvent> {
+    let mut events: Vec<ObservedEvent> = Vec::new();
+    let mut chain: Option<String> = None;
+    let mut block: Option<u64> = None;
+    let mut index = 0;
+    let mut governance_chain: Option<String> = None;
+    let mut fellowship_chain: Option<String> = None;
+
+    let lines: Vec<&str> = stdout.lines().collect();
+    let mut i = 0;
+    while i < lines.len() {
+        let line = strip_log_prefix(lines[i].trim_start());
+        let trimmed = line.trim();
+
+        if let Some(title) = trimmed
+            .strip_prefix("━━━ ")
+            .and_then(|t| t.strip_suffix(" ━━━"))
+        {
+            if title.contains("Fellowship") && fellowship_chain.is_some() {
+                chain = fellowship_chain.clone();
+            } else if title.contains("Governance") && governance_chain.is_some() {
+                chain = governance_chain.clone();
+            }
+        } else if let Some(rest) = trimmed.strip_prefix("Detected chain: ") {
+            chain = Some(label_before_paren(rest));
+        } else if let Some(rest) = trimmed
+            .strip_prefix("Governance Chain: ")
+            .or_else(|| trimmed.strip_prefix("Governance: "))
+        {
+            governance_chain = Some(label_before_paren(rest));
+        } else if let Some(rest) = trimmed
+            .strip_prefix("Fellowship Chain: ")
+            .or_else(|| trimmed.strip_prefix("Fellowship: "))
+        {
+            fellowship_chain = Some(label_before_paren(rest));
+        } else if let Some(rest) = trimmed.strip_prefix("📡 ") {
+            // `📡 <label> (Block #<n>)`
+            if let Some((label, tail)) = rest.rsplit_once(" (Block #") {
+                chain = Some(label.to_string());
+                block = tail.trim_end_matches(')').parse().ok();
+                index = 0;
+            }
+        } else if let Some(rest) = trimmed.strip_prefix("Events count for block ") {
+            block = rest.split(':').next().and_then(|n| n.trim().parse().ok());
+            index = 0;
+        } else if let Some(name) = trimmed.strip_prefix("• ") {
+            // Some events carry a description: `• Democracy.Passed — ...`
+            let name = name.split(" — ").next().unwrap_or(name);
+            if name.contains('.') && !name.contains(' ') {
+                events.push(ObservedEvent {
+                    chain: chain.clone(),
+                    block,
+                    index,
+                    event: name.to_string(),
+                    fields: serde_json::Value::Null,
+                });
+                index += 1;
+            }
+        } else if let Some(first) = trimmed.strip_prefix("Data: ") {
+            // Data is pretty-printed JSON spanning several raw lines; keep
+            // appending until it parses.
+            let mut buf = first.to_string();
+            let mut parsed = serde_json::from_str::<serde_json::Value>(&buf).ok();
+            let mut j = i;
+            while parsed.is_none() && j + 1 < lines.len() {
+                j += 1;
+                buf.push('\n');
+                buf.push_str(lines[j]);
+                parsed = serde_json::from_str(&buf).ok();
+            }
+            if let Some(last) = events.last_mut() {
+                match parsed {
+                    Some(value) => {
+                        last.fields = value;
+                        i = j;
+                    }
+                    None => last.fields = serde_json::Value::String(first.to_string()),
+                }
+            }
+        }
+        i += 1;
+    }
+
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strip_log_prefix_removes_run_id_and_glyph() {
+        assert_eq!(
+            strip_log_prefix("[sub-1.2_a] ℹ Detected chain: x"),
+            "Detected chain: x"
+        );
+        assert_eq!(strip_log_prefix("▸   • A.B"), "  • A.B");
+        assert_eq!(
+            strip_log_prefix("[not a run id] ✓ done"),
+            "[not a run id] ✓ done"
+        );
+        assert_eq!(strip_log_prefix("plain line"), "plain line");
+    }
+
+    #[test]
+    fn multi_line_data_is_attached_to_its_event() {
+        let stdout = "\
+[run-1] ℹ 📡 AssetHub (Block #12)
+[run-1] ▸   • Balances.Transfer
+[run-1] ▸     Data: {
+  \"from\": \"5Grw\",
+  \"amount\": \"10\"
+}
+[run-1] ▸   • System.Remarked
+[run-1] ▸     Data: not json
+";
+        let events = parse_events(stdout);
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].event, "Balances.Transfer");
+        assert_eq!(
+            events[0].fields,
+            serde_json::json!({ "from": "5Grw", "amount": "10" })
+        );
+        assert_eq!((events[1].block, events[1].index), (Some(12), 1));
+        assert_eq!(events[1].fields, serde_json::json!("not json"));
+    }
+
+    #[test]
+    fn chain_and_block_follow_the_output() {
+        let stdout = "\
+ℹ Governance Chain: AssetHub (asset-hub-polkadot)
+ℹ Fellowship Chain: Collectives (collectives-polkadot)
+━━━ Fellowship Referendum ━━━
+▸ Events count for block 30: 1
+▸   • FellowshipReferenda.Confirmed
+━━━ Governance Referendum ━━━
+▸ Events count for block 12: 2
+▸   • Referenda.Confirmed
+▸   • Democracy.Passed — a description
+ℹ Detected chain: Relay (polkadot)
+▸ Events count for block 40: 1
+▸   • Not an event
+▸   • System.Remarked
+";
+        let seen: Vec<_> = parse_events(stdout)
+            .into_iter()
+            .map(|e| (e.chain, e.block, e.index, e.event))
+            .collect();
+        let collectives = Some("Collectives".to_string());
+        let asset_hub = Some("AssetHub".to_string());
+        assert_eq!(
+            seen,
+            [
+                (
+                    collectives,
+                    Some(30),
+                    0,
+                    "FellowshipReferenda.Confirmed".to_string()
+                ),
+                (
+                    asset_hub.clone(),
+                    Some(12),
+                    0,
+                    "Referenda.Confirmed".to_string()
+                ),
+                (asset_hub, Some(12), 1, "Democracy.Passed".to_string()),
+                (
+                    Some("Relay".to_string()),
+                    Some(40),
+                    0,
+                    "System.Remarked".to_string()
+                ),
+            ]
+        );
+    }
+}
@@ -2,11 +2,19 @@
 //!
 //! These functions create referenda on-chain (not via Chopsticks) so that the tool
 //! can be tested with `--referendum <id>` or `--fellowship <id>` flags.
+//!
+//! Per-track referenda are created in throughput mode: the preimage notes and
+//! every `submit` extrinsic go out back-to-back with explicitly managed nonces,
+//! and finalization is awaited once at the end rather than per extrinsic.
 
 use anyhow::{Context, Result};
+use subxt::blocks::ExtrinsicEvents;
+use subxt::config::DefaultExtrinsicParamsBuilder;
 use subxt::dynamic::{self, Value};
+use subxt::ext::codec::{Compact, Encode};
+use subxt::tx::DynamicPayload;
 use subxt::{OnlineClient, PolkadotConfig};
-use subxt_signer::sr25519::dev;
+use subxt_signer::sr25519::{dev, Keypair};
 
 use super::tracks::{FellowshipTrack, GovernanceTrack};
 
@@ -18,133 +26,402 @@ pub struct SubmittedReferendum {
     pub block_number: u32,
 }
 
-/// Submit a governance referendum on Asset Hub for the given track.
-///
-/// Notes a preimage and submits a `Referenda.submit` extrinsic signed by Alice.
-/// Returns the referendum ID and the block number to use as fork point.
-///
-/// Requires `AhMigrator::AhMigrationStage = MigrationDone` to be set in genesis
-/// via `with_raw_spec_override()`, otherwise `Referenda.submit` is blocked by BaseCallFilter.
-///
-/// * `gov_origin_variant` — outer OriginCaller variant for non-Root governance origins
-///   (e.g. `"Origins"` on both Polkadot AH and Kusama AH).
-pub async fn submit_governance_referendum(
+/// Outcome of one finalized extrinsic.
+pub struct Included {
+    pub block_number: u32,
+    pub events: ExtrinsicEvents<PolkadotConfig>,
+}
+
+/// Encode the `System.remark` proposal used by a governance track's by-number test.
+fn governance_proposal(
     client: &OnlineClient<PolkadotConfig>,
     track: &GovernanceTrack,
-    gov_origin_variant: &str,
-) -> Result<SubmittedReferendum> {
-    let alice = dev::alice();
+) -> Result<Vec<u8>> {
+    remark_call_data(client, format!("bynum-gov-{}", track.name))
+}
 
-    // Build a System.remark call as the proposal
-    let remark_call = dynamic::tx(
-        "System",
-        "remark",
-        vec![Value::from_bytes(
-            format!("bynum-gov-{}", track.name).into_bytes(),
-        )],
-    );
-    let remark_bytes = client
+/// Encode the `System.remark` proposal used by a fellowship track's by-number test.
+fn fellowship_proposal(
+    client: &OnlineClient<PolkadotConfig>,
+    track: &FellowshipTrack,
+) -> Result<Vec<u8>> {
+    remark_call_data(client, format!("bynum-fell-{}", track.name))
+}
+
+/// Encode the `System.remark` proposal used by an ambassador track's by-number test.
+fn ambassador_proposal(
+    client: &OnlineClient<PolkadotConfig>,
+    track: &FellowshipTrack,
+) -> Result<Vec<u8>> {
+    remark_call_data(client, format!("bynum-amb-{}", track.name))
+}
+
+fn remark_call_data(client: &OnlineClient<PolkadotConfig>, remark: String) -> Result<Vec<u8>> {
+    let remark_call = dynamic::tx("System", "remark", vec![Value::from_bytes(remark)]);
+    client
         .tx()
         .call_data(&remark_call)
-        .context("Failed to encode System.remark")?;
+        .context("Failed to encode System.remark")
+}
 
-    // Note preimage
-    let preimage_tx = dynamic::tx(
+/// Whether a preimage for `call_data` is already noted on chain.
+async fn preimage_noted(client: &OnlineClient<PolkadotConfig>, call_data: &[u8]) -> Result<bool> {
+    let hash = sp_crypto_hashing::blake2_256(call_data);
+    let status_query = dynamic::storage(
         "Preimage",
-        "note_preimage",
-        vec![Value::from_bytes(remark_bytes.clone())],
+        "RequestStatusFor",
+        vec![Value::from_bytes(hash)],
     );
-    client
-        .tx()
-        .sign_and_submit_then_watch_default(&preimage_tx, &alice)
-        .await
-        .context("Failed to submit Preimage.note_preimage")?
-        .wait_for_finalized_success()
+    let status = client
+        .storage()
+        .at_latest()
+        .await?
+        .fetch(&status_query)
         .await
-        .context("Preimage.note_preimage not finalized")?;
+        .context("Failed to read Preimage.RequestStatusFor")?;
+    Ok(status.is_some())
+}
+
+/// A single `Utility.batch_all` noting every preimage in `proposals` that is
+/// not on chain yet, or `None` if they all are.
+async fn preimage_batch(
+    client: &OnlineClient<PolkadotConfig>,
+    proposals: &[Vec<u8>],
+) -> Result<Option<DynamicPayload>> {
+    let mut calls = Vec::new();
+    for call_data in proposals {
+        if !preimage_noted(client, call_data).await? {
+            calls.push(Value::unnamed_variant(
+                "Preimage",
+                vec![Value::unnamed_variant(
+                    "note_preimage",
+                    vec![Value::from_bytes(call_data)],
+                )],
+            ));
+        }
+    }
+    if calls.is_empty() {
+        return Ok(None);
+    }
+    log::info!("Noting {} preimage(s) in one batch", calls.len());
+    Ok(Some(dynamic::tx(
+        "Utility",
+        "batch_all",
+        vec![Value::unnamed_composite(calls)],
+    )))
+}
 
+/// `Lookup` bound for a noted proposal.
+fn lookup(call_data: &[u8]) -> Value {
+    Value::unnamed_variant(
+        "Lookup",
+        vec![
+            Value::from_bytes(sp_crypto_hashing::blake2_256(call_data)),
+            Value::u128(call_data.len() as u128),
+        ],
+    )
+}
+
+/// Sign `payloads` as Alice with consecutive nonces and submit them all
+/// without waiting in between, then wait for each to be finalized and succeed.
+///
+/// Each payload is paired with a label for error messages. Results are
+/// returned in submission order.
+async fn submit_pipelined(
+    client: &OnlineClient<PolkadotConfig>,
+    payloads: Vec<(String, DynamicPayload)>,
+) -> Result<Vec<Included>> {
+    let alice = dev::alice();
+    let mut nonce = client
+        .tx()
+        .account_nonce(&alice.public_key().to_account_id())
+        .await
+        .context("Failed to fetch Alice's nonce")?;
+
+    let mut in_flight = Vec::with_capacity(payloads.len());
+    for (what, payload) in payloads {
+        let params = DefaultExtrinsicParamsBuilder::<PolkadotConfig>::new()
+            .nonce(nonce)
+            .build();
+        let progress = client
+            .tx()
+            .create_signed(&payload, &alice, params)
+            .await
+            .with_context(|| format!("Failed to sign {what}"))?
+            .submit_and_watch()
+            .await
+            .with_context(|| format!("Failed to submit {what}"))?;
+        in_flight.push((what, progress));
+        nonce += 1;
+    }
     log::info!(
-        "Preimage noted for governance track {} (id={})",
-        track.name,
-        track.id
+        "Submitted {} extrinsic(s) back-to-back; waiting for finalization",
+        in_flight.len()
     );
 
-    // Build proposal origin
-    let proposal_origin = if track.is_root {
-        Value::unnamed_variant("system", vec![Value::unnamed_variant("Root", vec![])])
+    let mut included = Vec::with_capacity(in_flight.len());
+    for (what, progress) in in_flight {
+        let tx_in_block = progress
+            .wait_for_finalized()
+            .await
+            .with_context(|| format!("{what} not finalized"))?;
+        let block_number = client.blocks().at(tx_in_block.block_hash()).await?.number();
+        let events = tx_in_block
+            .wait_for_success()
+            .await
+            .with_context(|| format!("{what} dispatch failed"))?;
+        included.push(Included {
+            block_number,
+            events,
+        });
+    }
+    Ok(included)
+}
+
+/// Referendum index from the `<pallet>.Submitted` event of a submission.
+fn submitted_index(events: &ExtrinsicEvents<PolkadotConfig>, pallet: &str) -> Result<u32> {
+    for event in events.iter() {
+        let event = event?;
+        if event.pallet_name() == pallet && event.variant_name() == "Submitted" {
+            let index = event
+                .field_values()?
+                .into_values()
+                .next()
+                .and_then(|value| value.as_u128())
+                .with_context(|| format!("{pallet}.Submitted has no index field"))?;
+            return u32::try_from(index)
+                .with_context(|| format!("{pallet}.Submitted index {index} is not a u32"));
+        }
+    }
+    anyhow::bail!("No {pallet}.Submitted event found")
+}
+
+/// Submit one referendum per `(label, origin, proposal)` to `pallet` in a single
+/// pipeline, preceded by a batched note of any missing preimages.
+async fn submit_referenda(
+    client: &OnlineClient<PolkadotConfig>,
+    pallet: &str,
+    referenda: Vec<(String, Value, Vec<u8>)>,
+) -> Result<Vec<SubmittedReferendum>> {
+    let proposals: Vec<Vec<u8>> = referenda.iter().map(|(_, _, p)| p.clone()).collect();
+
+    let mut payloads = Vec::new();
+    let batch = preimage_batch(client, &proposals).await?;
+    let has_batch = batch.is_some();
+    if let Some(batch) = batch {
+        payloads.push(("Preimage.note_preimage batch".to_string(), batch));
+    }
+    for (label, origin, proposal) in &referenda {
+        let submit_tx = dynamic::tx(
+            pallet,
+            "submit",
+            vec![
+                origin.clone(),
+                lookup(proposal),
+                Value::unnamed_variant("After", vec![Value::u128(0u128)]),
+            ],
+        );
+        payloads.push((format!("{pallet}.submit for {label}"), submit_tx));
+    }
+
+    let included = submit_pipelined(client, payloads).await?;
+    let submissions = if has_batch {
+        &included[1..]
     } else {
-        Value::unnamed_variant(
-            gov_origin_variant,
-            vec![Value::unnamed_variant(track.origin_variant, vec![])],
-        )
+        &included[..]
     };
 
-    let proposal_hash = sp_crypto_hashing::blake2_256(&remark_bytes);
-    let proposal_len = remark_bytes.len() as u32;
+    submissions
+        .iter()
+        .zip(&referenda)
+        .map(|(inc, (label, _, _))| {
+            let referendum_id = submitted_index(&inc.events, pallet)?;
+            log::info!(
+                "{pallet} referendum #{referendum_id} created for {label} at block #{}",
+                inc.block_number
+            );
+            Ok(SubmittedReferendum {
+                referendum_id,
+                block_number: inc.block_number,
+            })
+        })
+        .collect()
+}
 
-    // Submit referendum
-    let submit_tx = dynamic::tx(
-        "Referenda",
-        "submit",
-        vec![
-            proposal_origin,
+/// Submit governance referenda on Asset Hub for all `tracks` in throughput mode.
+///
+/// Notes the missing `System.remark` preimages in one batch and submits one
+/// `Referenda.submit` per track signed by Alice, all back-to-back. Returns
+/// the referendum ID and fork block for each track, in `tracks` order.
+///
+/// Requires `AhMigrator::AhMigrationStage = MigrationDone` to be set in genesis
+/// via `with_raw_spec_override()`, otherwise `Referenda.submit` is blocked by BaseCallFilter.
+///
+/// * `gov_origin_variant` — outer OriginCaller variant for non-Root governance origins
+///   (e.g. `"Origins"` on both Polkadot AH and Kusama AH).
+pub async fn submit_governance_referenda(
+    client: &OnlineClient<PolkadotConfig>,
+    tracks: &[GovernanceTrack],
+    gov_origin_variant: &str,
+) -> Result<Vec<SubmittedReferendum>> {
+    let mut referenda = Vec::with_capacity(tracks.len());
+    for track in tracks {
+        let proposal_origin = if track.is_root {
+            Value::unnamed_variant("system", vec![Value::unnamed_variant("Root", vec![])])
+        } else {
             Value::unnamed_variant(
-                "Lookup",
-                vec![
-                    Value::from_bytes(proposal_hash),
-                    Value::u128(proposal_len as u128),
-                ],
-            ),
-            Value::unnamed_variant("After", vec![Value::u128(0u128)]),
-        ],
-    );
+                gov_origin_variant,
+                vec![Value::unnamed_variant(track.origin_variant, vec![])],
+            )
+        };
+        referenda.push((
+            format!("governance track {} (id={})", track.name, track.id),
+            proposal_origin,
+            governance_proposal(client, track)?,
+        ));
+    }
+    submit_referenda(client, "Referenda", referenda).await
+}
 
-    let tx_in_block = client
-        .tx()
-        .sign_and_submit_then_watch_default(&submit_tx, &alice)
-        .await
-        .context("Failed to submit Referenda.submit")?
-        .wait_for_finalized()
-        .await
-        .context("Referenda.submit not finalized")?;
+/// Submit a governance referendum on Asset Hub for the given track.
+///
+/// Single-track form of `submit_governance_referenda`.
+pub async fn submit_governance_referendum(
+    client: &OnlineClient<PolkadotConfig>,
+    track: &GovernanceTrack,
+    gov_origin_variant: &str,
+) -> Result<SubmittedReferendum> {
+    submit_governance_referenda(client, std::slice::from_ref(track), gov_origin_variant)
+        .await?
+        .pop()
+        .context("No referendum submitted")
+}
 
-    let block_hash = tx_in_block.block_hash();
-    let block = client.blocks().at(block_hash).await?;
-    let block_number = block.number();
+/// Submit a `WhitelistedCaller` referendum on Asset Hub for `proposal`
+/// (encoded call data, usually a `Whitelist.dispatch_whitelisted_call_with_preimage`),
+/// noting its preimage first if needed.
+pub async fn submit_whitelisted_caller_referendum(
+    client: &OnlineClient<PolkadotConfig>,
+    proposal: Vec<u8>,
+) -> Result<SubmittedReferendum> {
+    let origin = Value::unnamed_variant(
+        "Origins",
+        vec![Value::unnamed_variant("WhitelistedCaller", vec![])],
+    );
+    submit_referenda(
+        client,
+        "Referenda",
+        vec![("WhitelistedCaller".to_string(), origin, proposal)],
+    )
+    .await?
+    .pop()
+    .context("No referendum submitted")
+}
 
-    // Verify extrinsic succeeded
-    tx_in_block
-        .wait_for_success()
-        .await
-        .context("Referenda.submit dispatch failed")?;
+/// Note the by-number proposal preimages of all fellowship `tracks` up-front
+/// in one batch, so each `submit_fellowship_referendum` only has to submit
+/// the referendum.
+///
+/// Best-effort: on failure it logs a warning and each submission notes its
+/// own preimage as before.
+pub async fn note_fellowship_preimages(
+    client: &OnlineClient<PolkadotConfig>,
+    tracks: &[FellowshipTrack],
+) {
+    let noted = async {
+        let proposals = tracks
+            .iter()
+            .map(|track| fellowship_proposal(client, track))
+            .collect::<Result<Vec<_>>>()?;
+        if let Some(batch) = preimage_batch(client, &proposals).await? {
+            submit_pipelined(client, vec![("Preimage.note_preimage batch".into(), batch)]).await?;
+        }
+        anyhow::Ok(())
+    };
+    if let Err(e) = noted.await {
+        log::warn!("Batched fellowship preimage noting failed, noting per track: {e:#}");
+    }
+}
 
-    // Determine referendum ID from ReferendumCount
-    let count_query = dynamic::storage("Referenda", "ReferendumCount", ());
-    let count_val = client
-        .storage()
-        .at_latest()
-        .await?
-        .fetch(&count_query)
-        .await
-        .context("Failed to read ReferendumCount")?
-        .context("ReferendumCount not found")?;
-    let referendum_id = count_val
-        .as_type::<u32>()
-        .context("Failed to decode ReferendumCount")?
-        - 1;
+/// Submit fellowship referenda for all `tracks` in throughput mode.
+///
+/// Notes the missing `System.remark` preimages in one batch and submits one
+/// `FellowshipReferenda.submit` per track signed by Alice, all back-to-back.
+/// Alice must be registered as a fellow with sufficient rank in genesis.
+///
+/// * `fellowship_origin_variant` — outer OriginCaller variant for fellowship origins
+///   (e.g. `"FellowshipOrigins"` on Polkadot Collectives, `"Origins"` on Kusama relay).
+pub async fn submit_fellowship_referenda(
+    client: &OnlineClient<PolkadotConfig>,
+    tracks: &[FellowshipTrack],
+    fellowship_origin_variant: &str,
+) -> Result<Vec<SubmittedReferendum>> {
+    submit_ranked_referenda(
+        client,
+        "FellowshipReferenda",
+        tracks,
+        fellowship_origin_variant,
+        fellowship_proposal,
+    )
+    .await
+}
 
-    log::info!(
-        "Governance referendum #{} created on track {} (id={}) at block #{}",
-        referendum_id,
-        track.name,
-        track.id,
-        block_number
-    );
+/// Submit ambassador referenda on Polkadot Collectives for all `tracks` in
+/// throughput mode, like `submit_fellowship_referenda` but to
+/// `AmbassadorReferenda` with `AmbassadorOrigins` origins. Alice must be
+/// registered as an ambassador with sufficient rank in genesis.
+pub async fn submit_ambassador_referenda(
+    client: &OnlineClient<PolkadotConfig>,
+    tracks: &[FellowshipTrack],
+) -> Result<Vec<SubmittedReferendum>> {
+    submit_ranked_referenda(
+        client,
+        "AmbassadorReferenda",
+        tracks,
+        "AmbassadorOrigins",
+        ambassador_proposal,
+    )
+    .await
+}
 
-    Ok(SubmittedReferendum {
-        referendum_id,
-        block_number,
-    })
+/// Submit one referendum per track of a ranked collective to `pallet`.
+async fn submit_ranked_referenda(
+    client: &OnlineClient<PolkadotConfig>,
+    pallet: &str,
+    tracks: &[FellowshipTrack],
+    origin_variant: &str,
+    proposal: fn(&OnlineClient<PolkadotConfig>, &FellowshipTrack) -> Result<Vec<u8>>,
+) -> Result<Vec<SubmittedReferendum>> {
+    let mut referenda = Vec::with_capacity(tracks.len());
+    for track in tracks {
+        referenda.push((
+            format!("{pallet} track {} (id={})", track.name, track.id),
+            Value::unnamed_variant(
+                origin_variant,
+                vec![Value::unnamed_variant(track.origin_variant, vec![])],
+            ),
+            proposal(client, track)?,
+        ));
+    }
+    submit_referenda(client, pallet, referenda).await
+}
+
+/// Submit a fellowship referendum for the given track.
+///
+/// Single-track form of `submit_fellowship_referenda`.
+pub async fn submit_fellowship_referendum(
+    client: &OnlineClient<PolkadotConfig>,
+    track: &FellowshipTrack,
+    fellowship_origin_variant: &str,
+) -> Result<SubmittedReferendum> {
+    submit_fellowship_referenda(
+        client,
+        std::slice::from_ref(track),
+        fellowship_origin_variant,
+    )
+    .await?
+    .pop()
+    .context("No fellowship referendum submitted")
 }
 
 /// Submit a governance referendum with an Inline proposal (no preimage).
@@ -220,122 +497,280 @@ pub async fn submit_governance_referendum_inline(
     })
 }
 
-/// Submit a fellowship referendum for the given track.
-///
-/// Notes a preimage and submits a `FellowshipReferenda.submit` extrinsic signed by Alice.
-/// Alice must be registered as a fellow with sufficient rank in genesis.
+/// `MultiAddress::Id` of a dev keypair.
+fn multi_address(keypair: &Keypair) -> Value {
+    Value::unnamed_variant(
+        "Id",
+        vec![Value::from_bytes(keypair.public_key().to_account_id())],
+    )
+}
+
+/// Sign `payload` with `signer`, wait for finalization and a successful dispatch.
+async fn submit_signed(
+    client: &OnlineClient<PolkadotConfig>,
+    what: &str,
+    payload: &DynamicPayload,
+    signer: &Keypair,
+) -> Result<Included> {
+    let tx_in_block = client
+        .tx()
+        .sign_and_submit_then_watch_default(payload, signer)
+        .await
+        .with_context(|| format!("Failed to submit {what}"))?
+        .wait_for_finalized()
+        .await
+        .with_context(|| format!("{what} not finalized"))?;
+    let block_number = client.blocks().at(tx_in_block.block_hash()).await?.number();
+    let events = tx_in_block
+        .wait_for_success()
+        .await
+        .with_context(|| format!("{what} dispatch failed"))?;
+    Ok(Included {
+        block_number,
+        events,
+    })
+}
+
+/// Submit `pallet.call(args)` signed by `signer` and wait for it to be
+/// finalized and dispatched successfully. For scenario setup that genesis
+/// overrides (`raw_storage`) can't express.
+pub async fn submit_call(
+    client: &OnlineClient<PolkadotConfig>,
+    pallet: &str,
+    call: &str,
+    args: Vec<Value>,
+    signer: &Keypair,
+) -> Result<Included> {
+    let payload = dynamic::tx(pallet, call, args);
+    submit_signed(client, &format!("{pallet}.{call}"), &payload, signer).await
+}
+
+/// Wait until `count` more blocks are finalized after `after`.
+async fn wait_for_blocks(
+    client: &OnlineClient<PolkadotConfig>,
+    after: u32,
+    count: u32,
+) -> Result<()> {
+    let mut finalized = client.blocks().subscribe_finalized().await?;
+    while let Some(block) = finalized.next().await {
+        if block?.number() >= after + count {
+            return Ok(());
+        }
+    }
+    anyhow::bail!(
+        "Finalized block subscription ended before block #{}",
+        after + count
+    )
+}
+
+/// Submit a governance referendum with an Inline proposal as Bob, through
+/// Alice as Bob's `Any` proxy.
 ///
-/// * `fellowship_origin_variant` — outer OriginCaller variant for fellowship origins
-///   (e.g. `"FellowshipOrigins"` on Polkadot Collectives, `"Origins"` on Kusama relay).
-pub async fn submit_fellowship_referendum(
+/// Bob adds Alice as a proxy with `delay`. Without a delay Alice submits
+/// `Proxy.proxy(Bob, None, Referenda.submit(..))`; with one she first
+/// announces the call's hash, waits `delay` blocks and then submits
+/// `Proxy.proxy_announced`. One extra block is waited for, since the Proxy
+/// pallet may count the delay in relay chain blocks on parachains.
+pub async fn submit_governance_referendum_inline_via_proxy(
     client: &OnlineClient<PolkadotConfig>,
-    track: &FellowshipTrack,
-    fellowship_origin_variant: &str,
+    delay: u32,
 ) -> Result<SubmittedReferendum> {
     let alice = dev::alice();
+    let bob = dev::bob();
 
-    // Build a System.remark call as the proposal
-    let remark_call = dynamic::tx(
-        "System",
-        "remark",
-        vec![Value::from_bytes(
-            format!("bynum-fell-{}", track.name).into_bytes(),
-        )],
+    let add_proxy = dynamic::tx(
+        "Proxy",
+        "add_proxy",
+        vec![
+            multi_address(&alice),
+            Value::unnamed_variant("Any", vec![]),
+            Value::u128(delay as u128),
+        ],
     );
-    let remark_bytes = client
-        .tx()
-        .call_data(&remark_call)
-        .context("Failed to encode System.remark")?;
-
-    // Note preimage
-    let preimage_tx = dynamic::tx(
-        "Preimage",
-        "note_preimage",
-        vec![Value::from_bytes(remark_bytes.clone())],
+    submit_signed(client, "Proxy.add_proxy (Bob -> Alice)", &add_proxy, &bob).await?;
+
+    let remark_bytes = remark_call_data(client, "bynum-proxy-gov".to_string())?;
+    let submit_args = vec![
+        Value::unnamed_variant("system", vec![Value::unnamed_variant("Root", vec![])]),
+        Value::unnamed_variant("Inline", vec![Value::from_bytes(remark_bytes)]),
+        Value::unnamed_variant("After", vec![Value::u128(0u128)]),
+    ];
+    let submit_call = Value::unnamed_variant(
+        "Referenda",
+        vec![Value::unnamed_variant("submit", submit_args.clone())],
     );
-    client
-        .tx()
-        .sign_and_submit_then_watch_default(&preimage_tx, &alice)
-        .await
-        .context("Failed to submit Preimage.note_preimage")?
-        .wait_for_finalized_success()
-        .await
-        .context("Preimage.note_preimage not finalized")?;
 
+    let proxied = if delay == 0 {
+        dynamic::tx(
+            "Proxy",
+            "proxy",
+            vec![
+                multi_address(&bob),
+                Value::unnamed_variant("None", vec![]),
+                submit_call,
+            ],
+        )
+    } else {
+        let call_data = client
+            .tx()
+            .call_data(&dynamic::tx("Referenda", "submit", submit_args))
+            .context("Failed to encode Referenda.submit")?;
+        let announce = dynamic::tx(
+            "Proxy",
+            "announce",
+            vec![
+                multi_address(&bob),
+                Value::from_bytes(sp_crypto_hashing::blake2_256(&call_data)),
+            ],
+        );
+        let announced = submit_signed(client, "Proxy.announce", &announce, &alice).await?;
+        log::info!(
+            "Referenda.submit announced at block #{}; waiting {} block(s)",
+            announced.block_number,
+            delay + 1
+        );
+        wait_for_blocks(client, announced.block_number, delay + 1).await?;
+        dynamic::tx(
+            "Proxy",
+            "proxy_announced",
+            vec![
+                multi_address(&alice),
+                multi_address(&bob),
+                Value::unnamed_variant("None", vec![]),
+                submit_call,
+            ],
+        )
+    };
+
+    let included = submit_signed(client, "proxied Referenda.submit", &proxied, &alice).await?;
+    let referendum_id = submitted_index(&included.events, "Referenda")
+        .context("Proxied Referenda.submit did not create a referendum")?;
     log::info!(
-        "Preimage noted for fellowship track {} (id={})",
-        track.name,
-        track.id
+        "Governance referendum #{} created by Bob via proxy at block #{}",
+        referendum_id,
+        included.block_number
     );
 
-    let proposal_hash = sp_crypto_hashing::blake2_256(&remark_bytes);
-    let proposal_len = remark_bytes.len() as u32;
+    Ok(SubmittedReferendum {
+        referendum_id,
+        block_number: included.block_number,
+    })
+}
 
-    // Submit fellowship referendum
-    let submit_tx = dynamic::tx(
-        "FellowshipReferenda",
-        "submit",
+/// `Multisig` pallet account of `signatories` (sorted) with `threshold`.
+fn multisig_account(signatories: &[[u8; 32]], threshold: u16) -> [u8; 32] {
+    let mut entropy = b"modlpy/utilisuba".to_vec();
+    entropy.extend(Compact(signatories.len() as u32).encode());
+    for signatory in signatories {
+        entropy.extend_from_slice(signatory);
+    }
+    entropy.extend(threshold.to_le_bytes());
+    sp_crypto_hashing::blake2_256(&entropy)
+}
+
+/// Upper bound for the weight of the multisig's `Referenda.submit`.
+fn multisig_max_weight() -> Value {
+    Value::named_composite([
+        ("ref_time", Value::u128(10_000_000_000)),
+        ("proof_size", Value::u128(1_000_000)),
+    ])
+}
+
+/// Submit an inline governance referendum from a 2-of-3 multisig of Alice,
+/// Bob and Charlie, sign by sign: Alice `approve_as_multi`s the call hash,
+/// then Bob executes the call with `as_multi` at Alice's timepoint. The
+/// multisig account is funded by Alice for the submission deposit.
+pub async fn submit_governance_referendum_inline_via_multisig(
+    client: &OnlineClient<PolkadotConfig>,
+) -> Result<SubmittedReferendum> {
+    const THRESHOLD: u16 = 2;
+    let alice = dev::alice();
+    let bob = dev::bob();
+    let mut signatories: Vec<[u8; 32]> = [&alice, &bob, &dev::charlie()]
+        .iter()
+        .map(|keypair| keypair.public_key().0)
+        .collect();
+    signatories.sort();
+    let multisig = multisig_account(&signatories, THRESHOLD);
+    let others_of = |keypair: &Keypair| {
+        let own = keypair.public_key().0;
+        Value::unnamed_composite(
+            signatories
+                .iter()
+                .filter(|id| **id != own)
+                .map(Value::from_bytes),
+        )
+    };
+
+    let fund = dynamic::tx(
+        "Balances",
+        "transfer_keep_alive",
         vec![
-            Value::unnamed_variant(
-                fellowship_origin_variant,
-                vec![Value::unnamed_variant(track.origin_variant, vec![])],
-            ),
-            Value::unnamed_variant(
-                "Lookup",
-                vec![
-                    Value::from_bytes(proposal_hash),
-                    Value::u128(proposal_len as u128),
-                ],
-            ),
-            Value::unnamed_variant("After", vec![Value::u128(0u128)]),
+            Value::unnamed_variant("Id", vec![Value::from_bytes(multisig)]),
+            Value::u128(1_000_000_000_000_000),
         ],
     );
-
-    let tx_in_block = client
+    submit_signed(client, "Balances.transfer_keep_alive", &fund, &alice).await?;
+
+    let remark_bytes = remark_call_data(client, "bynum-multisig-gov".to_string())?;
+    let submit_args = vec![
+        Value::unnamed_variant("system", vec![Value::unnamed_variant("Root", vec![])]),
+        Value::unnamed_variant("Inline", vec![Value::from_bytes(remark_bytes)]),
+        Value::unnamed_variant("After", vec![Value::u128(0u128)]),
+    ];
+    let call_data = client
         .tx()
-        .sign_and_submit_then_watch_default(&submit_tx, &alice)
-        .await
-        .context("Failed to submit FellowshipReferenda.submit")?
-        .wait_for_finalized()
-        .await
-        .context("FellowshipReferenda.submit not finalized")?;
+        .call_data(&dynamic::tx("Referenda", "submit", submit_args.clone()))
+        .context("Failed to encode Referenda.submit")?;
 
-    let block_hash = tx_in_block.block_hash();
-    let block = client.blocks().at(block_hash).await?;
-    let block_number = block.number();
-
-    // Verify extrinsic succeeded
-    tx_in_block
-        .wait_for_success()
-        .await
-        .context("FellowshipReferenda.submit dispatch failed")?;
-
-    // Determine referendum ID from FellowshipReferendumCount
-    let count_query = dynamic::storage("FellowshipReferenda", "ReferendumCount", ());
-    let count_val = client
-        .storage()
-        .at_latest()
-        .await?
-        .fetch(&count_query)
-        .await
-        .context("Failed to read FellowshipReferenda.ReferendumCount")?
-        .context("FellowshipReferenda.ReferendumCount not found")?;
-    let referendum_id = count_val
-        .as_type::<u32>()
-        .context("Failed to decode FellowshipReferenda.ReferendumCount")?
-        - 1;
+    let approve = dynamic::tx(
+        "Multisig",
+        "approve_as_multi",
+        vec![
+            Value::u128(THRESHOLD as u128),
+            others_of(&alice),
+            Value::unnamed_variant("None", vec![]),
+            Value::from_bytes(sp_crypto_hashing::blake2_256(&call_data)),
+            multisig_max_weight(),
+        ],
+    );
+    let approved = submit_signed(client, "Multisig.approve_as_multi", &approve, &alice).await?;
+    let index = approved.events.extrinsic_index();
+    let timepoint = Value::named_composite([
+        ("height", Value::u128(approved.block_number as u128)),
+        ("index", Value::u128(index as u128)),
+    ]);
+    log::info!(
+        "Referenda.submit approved by Alice at block #{}",
+        approved.block_number
+    );
 
+    let execute = dynamic::tx(
+        "Multisig",
+        "as_multi",
+        vec![
+            Value::u128(THRESHOLD as u128),
+            others_of(&bob),
+            Value::unnamed_variant("Some", vec![timepoint]),
+            Value::unnamed_variant(
+                "Referenda",
+                vec![Value::unnamed_variant("submit", submit_args)],
+            ),
+            multisig_max_weight(),
+        ],
+    );
+    let included = submit_signed(client, "multisig Referenda.submit", &execute, &bob).await?;
+    let referendum_id = submitted_index(&included.events, "Referenda")
+        .context("Multisig Referenda.submit did not create a referendum")?;
     log::info!(
-        "Fellowship referendum #{} created on track {} (id={}) at block #{}",
+        "Governance referendum #{} created by the Alice/Bob/Charlie multisig at block #{}",
         referendum_id,
-        track.name,
-        track.id,
-        block_number
+        included.block_number
     );
 
     Ok(SubmittedReferendum {
         referendum_id,
-        block_number,
+        block_number: included.block_number,
     })
 }
 
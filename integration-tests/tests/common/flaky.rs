@@ -0,0 +1,75 @@
+//! Known-flaky sub-test quarantine with automatic retry.
+//!
+//! A sub-test is flaky when its label matches an entry in `KNOWN_FLAKY` or in
+//! the comma-separated `FLAKY_SUB_TESTS` env var (entries ending in `*` match
+//! by prefix). Flaky sub-tests are retried by `run_and_bail!` up to
+//! `FLAKY_MAX_ATTEMPTS` times (default 3); everything else runs exactly once.
+//!
+//! Passes after a retry are logged as `FLAKY PASS` and appended to
+//! `<artifacts>/<suite>/flaky.jsonl`, so nightly noise stays visible without
+//! failing the suite. A flaky sub-test that fails every attempt still fails.
+
+use std::io::Write;
+
+use super::config::get_artifacts_dir;
+use super::logging::current_suite;
+
+/// Env var with extra comma-separated flaky labels / `prefix*` patterns.
+pub const FLAKY_SUB_TESTS_ENV: &str = "FLAKY_SUB_TESTS";
+/// Env var overriding the number of attempts for flaky sub-tests.
+pub const FLAKY_MAX_ATTEMPTS_ENV: &str = "FLAKY_MAX_ATTEMPTS";
+pub const DEFAULT_FLAKY_MAX_ATTEMPTS: u32 = 3;
+
+/// Sub-tests tagged as known-flaky in code.
+///
+/// Kusama fellowship by-number tests fork the relay chain, where Chopsticks
+/// occasionally loses preimage availability near a session boundary (see
+/// `KusamaTestContext::refresh_fork_blocks`).
+pub const KNOWN_FLAKY: &[&str] = &["ksm_fell_bynum_*", "ksm_fellowship_on_relay"];
+
+fn matches(pattern: &str, label: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => label.starts_with(prefix),
+        None => pattern == label,
+    }
+}
+
+/// Whether `label` is tagged as flaky (in code or via `FLAKY_SUB_TESTS`).
+pub fn is_flaky(label: &str) -> bool {
+    let from_env = std::env::var(FLAKY_SUB_TESTS_ENV).unwrap_or_default();
+    KNOWN_FLAKY
+        .iter()
+        .copied()
+        .chain(from_env.split(',').map(str::trim).filter(|p| !p.is_empty()))
+        .any(|pattern| matches(pattern, label))
+}
+
+/// Number of attempts `run_and_bail!` gives `label`: 1 unless flaky.
+pub fn max_attempts(label: &str) -> u32 {
+    if !is_flaky(label) {
+        return 1;
+    }
+    std::env::var(FLAKY_MAX_ATTEMPTS_ENV)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|n| *n >= 1)
+        .unwrap_or(DEFAULT_FLAKY_MAX_ATTEMPTS)
+}
+
+/// Log a pass that needed retries and append it to the suite's flaky report.
+pub fn record_flaky_pass(label: &str, attempts: u32) {
+    log::warn!("FLAKY PASS: {label} (passed on attempt {attempts})");
+
+    let dir = get_artifacts_dir(&current_suite());
+    let line = serde_json::json!({ "sub_test": label, "attempts": attempts });
+    let written = std::fs::create_dir_all(&dir).and_then(|_| {
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(dir.join("flaky.jsonl"))?;
+        writeln!(file, "{line}")
+    });
+    if let Err(e) = written {
+        log::warn!("Failed to record flaky pass for {label}: {e}");
+    }
+}
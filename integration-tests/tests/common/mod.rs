@@ -1,18 +1,49 @@
 // Shared test infrastructure used across multiple test binaries (tests.rs,
 // all_tracks.rs, scenarios.rs, generate_chain_specs.rs). Each binary only
-// uses a subset, so Rust reports false "dead_code" warnings for items that
-// are used by other binaries.
-#![allow(dead_code)]
+// uses a subset, so Rust reports false "dead_code" (and unused macro and
+// re-export) warnings for items that are used by other binaries.
+#![allow(dead_code, unused_imports, unused_macros)]
 
 /// Run an async sub-test expression, log PASS/FAIL, push errors, and bail on first failure.
 ///
+/// The label is also exposed to the running future via
+/// `event_db::CURRENT_SUB_TEST`, so tool output can be attributed to it, and
+/// the sub-test runs inside a `logging::sub_test_span` so its logs land in
+/// their own file.
+///
+/// Sub-tests tagged as known-flaky (see `flaky`) are re-run up to
+/// `flaky::max_attempts` times; a pass after a retry is logged and recorded
+/// as a "flaky pass", while a failure on the last attempt still fails the suite.
+///
 /// Usage: `run_and_bail!(errors, "label", some_async_fn(args));`
 macro_rules! run_and_bail {
     ($errors:expr, $label:expr, $expr:expr) => {
-        match $expr.await {
-            Ok(()) => log::info!("PASS: {}", $label),
+        let label = $label.to_string();
+        let max_attempts = $crate::common::flaky::max_attempts(&label);
+        let mut attempt = 1;
+        let result = loop {
+            let result: anyhow::Result<()> = tracing::Instrument::instrument(
+                $crate::common::event_db::CURRENT_SUB_TEST.scope(label.clone(), $expr),
+                $crate::common::logging::sub_test_span(&label),
+            )
+            .await;
+            match result {
+                Err(e) if attempt < max_attempts => {
+                    log::warn!("RETRY: {label} (attempt {attempt}/{max_attempts} failed: {e:#})");
+                    attempt += 1;
+                }
+                result => break result,
+            }
+        };
+        match result {
+            Ok(()) if attempt > 1 => $crate::common::flaky::record_flaky_pass(&label, attempt),
+            Ok(()) => log::info!("PASS: {label}"),
             Err(e) => {
-                let msg = format!("FAIL: {}: {e:#}", $label);
+                let msg = if max_attempts > 1 {
+                    format!("FAIL: {label} (after {attempt} attempts): {e:#}")
+                } else {
+                    format!("FAIL: {label}: {e:#}")
+                };
                 log::error!("{msg}");
                 $errors.push(msg);
             }
@@ -30,11 +61,16 @@ macro_rules! run_and_bail {
 pub(crate) use run_and_bail;
 
 pub mod call_data;
-pub mod config;
-pub mod context;
+pub mod event_db;
 pub mod extrinsic_submitter;
-pub mod network;
-pub mod port_allocator;
-pub mod raw_storage;
+pub mod flaky;
+pub mod override_audit;
+pub mod provenance;
 pub mod tool_runner;
 pub mod tracks;
+
+// Network topologies, contexts and tool arguments live in the library, which
+// other repositories build on too.
+pub use polkadot_referenda_tester_integration_tests::{
+    config, context, flag_registry, logging, network, port_allocator, raw_storage,
+};
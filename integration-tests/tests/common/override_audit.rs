@@ -0,0 +1,118 @@
+//! Audit of the raw spec overrides built by `raw_storage`.
+//!
+//! A wrong hasher or encoding in `raw_storage` doesn't fail the network spawn:
+//! the entry just lands under a key the runtime never reads, and by-number
+//! tests fail much later with a `BaseCallFilter` or origin error. These checks
+//! compare every `RawEntry` against a raw chain spec file, the runtime metadata
+//! and the chain's genesis state, and name the entry that is wrong.
+
+use anyhow::{anyhow, bail, ensure, Context, Result};
+use std::path::Path;
+use subxt::ext::scale_value;
+use subxt::metadata::types::{StorageEntryType, StorageHasher};
+use subxt::{Metadata, OnlineClient, PolkadotConfig};
+
+use super::raw_storage::RawEntry;
+
+/// Check that the raw chain spec at `path` holds every entry with its value.
+pub fn audit_raw_spec(path: &Path, entries: &[RawEntry]) -> Result<()> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let spec: serde_json::Value = serde_json::from_str(&text)
+        .with_context(|| format!("{} is not valid JSON", path.display()))?;
+    let top = spec["genesis"]["raw"]["top"]
+        .as_object()
+        .with_context(|| format!("{} is not a raw chain spec", path.display()))?;
+
+    for entry in entries {
+        let expected = entry.value_hex();
+        let actual = top.get(&entry.key()).and_then(|v| v.as_str());
+        ensure!(
+            actual == Some(expected.as_str()),
+            "{entry}: {} holds {actual:?}, expected {expected}",
+            path.display()
+        );
+    }
+    Ok(())
+}
+
+/// Check every entry's hashers, key and value encoding against `metadata`.
+pub fn audit_metadata(metadata: &Metadata, entries: &[RawEntry]) -> Result<()> {
+    for entry in entries {
+        let storage_entry = metadata
+            .pallet_by_name(entry.pallet)
+            .and_then(|pallet| pallet.storage())
+            .and_then(|storage| storage.entry_by_name(entry.item))
+            .with_context(|| format!("{entry}: no such storage item in the runtime metadata"))?;
+
+        let value_ty = match storage_entry.entry_type() {
+            StorageEntryType::Plain(value_ty) => {
+                ensure!(
+                    entry.keys.is_empty(),
+                    "{entry}: a plain storage value takes no keys"
+                );
+                *value_ty
+            }
+            StorageEntryType::Map {
+                hashers,
+                key_ty,
+                value_ty,
+            } => {
+                ensure!(
+                    hashers.len() == entry.keys.len(),
+                    "{entry}: the runtime has {} hasher(s) for {} key(s)",
+                    hashers.len(),
+                    entry.keys.len()
+                );
+                let other = hashers
+                    .iter()
+                    .find(|h| !matches!(h, StorageHasher::Twox64Concat));
+                if let Some(hasher) = other {
+                    bail!("{entry}: the runtime hashes keys with {hasher:?}, not Twox64Concat");
+                }
+                // With several hashers `key_ty` is a tuple, which decodes from
+                // the concatenated keys.
+                decode_exactly(metadata, &entry.keys.concat(), *key_ty)
+                    .with_context(|| format!("{entry}: key does not decode"))?;
+                *value_ty
+            }
+        };
+        decode_exactly(metadata, &entry.value, value_ty)
+            .with_context(|| format!("{entry}: value does not decode"))?;
+    }
+    Ok(())
+}
+
+/// Decode `bytes` as type `ty`, failing on leftover bytes.
+fn decode_exactly(metadata: &Metadata, bytes: &[u8], ty: u32) -> Result<()> {
+    let mut input = bytes;
+    scale_value::scale::decode_as_type(&mut input, ty, metadata.types())
+        .map_err(|e| anyhow!("{e}"))?;
+    ensure!(input.is_empty(), "{} trailing byte(s)", input.len());
+    Ok(())
+}
+
+/// Audit `entries` against a running chain: its metadata, and its genesis
+/// state, which must hold every override value.
+pub async fn audit_chain(
+    client: &OnlineClient<PolkadotConfig>,
+    entries: &[RawEntry],
+) -> Result<()> {
+    audit_metadata(&client.metadata(), entries)?;
+
+    let genesis = client.storage().at(client.genesis_hash());
+    for entry in entries {
+        let actual = genesis
+            .fetch_raw(entry.key_bytes())
+            .await
+            .with_context(|| format!("Failed to read {entry} at genesis"))?;
+        ensure!(
+            actual.as_deref() == Some(entry.value.as_slice()),
+            "{entry}: genesis holds {:?}, expected {}",
+            actual.map(|v| format!("0x{}", hex::encode(v))),
+            entry.value_hex()
+        );
+    }
+    log::info!("Audited {} raw override entries", entries.len());
+    Ok(())
+}
@@ -0,0 +1,206 @@
+//! Binary, runtime and chain-spec provenance for a suite run.
+//!
+//! Written to `<artifacts>/<suite>/provenance.json` before the network is
+//! spawned, so a failing nightly can be reproduced bit-for-bit later: the
+//! exact node binaries (version + hash), every fast-runtime WASM and cached
+//! chain spec (hash + size), and the CLI's git commit.
+
+use anyhow::{Context, Result};
+use serde::ser::{SerializeMap, Serializer};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+use super::config::{
+    get_artifacts_dir, get_chain_specs_dir, get_network_runtimes_dir, RuntimeNetwork,
+    CHAIN_SPECS_DIR_ENV, PARACHAIN_BINARY_ENV, POLKADOT_BINARY_ENV, RUNTIMES_DIR_ENV,
+    RUNTIMES_VERSION_ENV,
+};
+use super::network::{get_parachain_binary_path, get_polkadot_binary_path};
+
+/// `provenance.json`. Fields are written in declaration order, which is the
+/// order the README documents them in.
+#[derive(Serialize)]
+struct Provenance<'a> {
+    suite: &'a str,
+    recorded_at_unix: u64,
+    binaries: Vec<BinaryProvenance>,
+    /// Runtime WASMs per network, since each network may use its own directory.
+    runtimes: Ordered<Vec<FileProvenance>>,
+    chain_specs: Vec<FileProvenance>,
+    tool: ToolProvenance,
+    env: Ordered<Option<String>>,
+}
+
+#[derive(Serialize)]
+struct BinaryProvenance {
+    binary: String,
+    resolved_path: Option<String>,
+    version: Option<String>,
+    blake2_256: Option<String>,
+}
+
+#[derive(Serialize)]
+struct FileProvenance {
+    file: Option<String>,
+    path: String,
+    blake2_256: Option<String>,
+    size: Option<u64>,
+}
+
+#[derive(Serialize)]
+struct ToolProvenance {
+    project_dir: String,
+    git_commit: Option<String>,
+    git_dirty: Option<bool>,
+}
+
+/// A JSON object that keeps its keys in insertion order.
+struct Ordered<T>(Vec<(String, T)>);
+
+impl<T: Serialize> Serialize for Ordered<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (key, value) in &self.0 {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+
+/// Record provenance for `suite` and return the path of the written file.
+pub fn record(suite: &str) -> Result<PathBuf> {
+    let dir = get_artifacts_dir(suite);
+    std::fs::create_dir_all(&dir)
+        .with_context(|| format!("Failed to create artifacts dir {}", dir.display()))?;
+
+    let report = Provenance {
+        suite,
+        recorded_at_unix: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default(),
+        binaries: vec![
+            binary_provenance(&get_polkadot_binary_path()),
+            binary_provenance(&get_parachain_binary_path()),
+        ],
+        runtimes: runtimes_provenance(),
+        chain_specs: get_chain_specs_dir()
+            .map(|d| files_provenance(&d, "json"))
+            .unwrap_or_default(),
+        tool: tool_provenance(),
+        env: env_snapshot(),
+    };
+
+    let path = dir.join("provenance.json");
+    std::fs::write(&path, serde_json::to_string_pretty(&report)?)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    log::info!("Provenance recorded: {}", path.display());
+    Ok(path)
+}
+
+fn hash_file(path: &Path) -> Option<String> {
+    let bytes = std::fs::read(path).ok()?;
+    Some(format!(
+        "0x{}",
+        hex::encode(sp_crypto_hashing::blake2_256(&bytes))
+    ))
+}
+
+/// Locate a bare binary name (e.g. `polkadot`) on `PATH`.
+fn which(binary: &str) -> Option<PathBuf> {
+    let path = Path::new(binary);
+    if path.components().count() > 1 {
+        return path.exists().then(|| path.to_path_buf());
+    }
+    std::env::split_paths(&std::env::var_os("PATH")?)
+        .map(|dir| dir.join(binary))
+        .find(|candidate| candidate.is_file())
+}
+
+fn binary_provenance(binary: &str) -> BinaryProvenance {
+    let version = std::process::Command::new(binary)
+        .arg("--version")
+        .output()
+        .ok()
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string());
+    let resolved = which(binary);
+    BinaryProvenance {
+        binary: binary.to_string(),
+        resolved_path: resolved.as_ref().map(|p| p.display().to_string()),
+        version,
+        blake2_256: resolved.as_deref().and_then(hash_file),
+    }
+}
+
+fn runtimes_provenance() -> Ordered<Vec<FileProvenance>> {
+    Ordered(
+        RuntimeNetwork::ALL
+            .iter()
+            .map(|network| {
+                let dir = get_network_runtimes_dir(*network);
+                (network.name().to_string(), files_provenance(&dir, "wasm"))
+            })
+            .collect(),
+    )
+}
+
+fn files_provenance(dir: &Path, ext: &str) -> Vec<FileProvenance> {
+    let mut files: Vec<PathBuf> = std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok().map(|e| e.path()))
+                .filter(|p| p.extension().is_some_and(|e| e == ext))
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort();
+
+    files
+        .iter()
+        .map(|path| FileProvenance {
+            file: path.file_name().map(|n| n.to_string_lossy().to_string()),
+            path: path.display().to_string(),
+            blake2_256: hash_file(path),
+            size: std::fs::metadata(path).map(|m| m.len()).ok(),
+        })
+        .collect()
+}
+
+/// Git commit of the CLI under test (TOOL_PROJECT_DIR or the parent dir).
+fn tool_provenance() -> ToolProvenance {
+    let project_dir = std::env::var("TOOL_PROJECT_DIR").unwrap_or_else(|_| "..".to_string());
+    let git = |args: &[&str]| {
+        std::process::Command::new("git")
+            .current_dir(&project_dir)
+            .args(args)
+            .output()
+            .ok()
+            .filter(|o| o.status.success())
+            .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+    };
+    ToolProvenance {
+        git_commit: git(&["rev-parse", "HEAD"]),
+        git_dirty: git(&["status", "--porcelain"]).map(|s| !s.is_empty()),
+        project_dir,
+    }
+}
+
+fn env_snapshot() -> Ordered<Option<String>> {
+    let mut vars = vec![
+        POLKADOT_BINARY_ENV,
+        PARACHAIN_BINARY_ENV,
+        RUNTIMES_DIR_ENV,
+        RUNTIMES_VERSION_ENV,
+        CHAIN_SPECS_DIR_ENV,
+        "TOOL_PROJECT_DIR",
+    ];
+    for network in RuntimeNetwork::ALL {
+        vars.push(network.runtimes_dir_env());
+        vars.push(network.runtimes_version_env());
+    }
+    Ordered(
+        vars.iter()
+            .map(|v| (v.to_string(), std::env::var(v).ok()))
+            .collect(),
+    )
+}
@@ -1,37 +1,240 @@
 //! Invokes the polkadot-referenda-tester CLI as a subprocess and captures output.
 
-use anyhow::{Context, Result};
-use std::process::Stdio;
+use anyhow::{bail, ensure, Context, Result};
+use futures::StreamExt;
+use polkadot_referenda_tester_integration_tests::simulation::{
+    ChainHead, PausedTool, ToolInvocation, ToolProcess,
+};
+pub use polkadot_referenda_tester_integration_tests::tool_args::{
+    ChainEndpoint, OutputFormat, ToolArgs, ToolArgsBuilder,
+};
+use regex::Regex;
+use serde::de::DeserializeOwned;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, LazyLock, Mutex};
 use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tracing::Instrument;
 
-use super::config::TOOL_EXECUTION_TIMEOUT_SECS;
-
-/// Arguments for `yarn cli test`.
-#[derive(Default)]
-pub struct ToolArgs {
-    pub governance_chain_url: Option<String>,
-    pub fellowship_chain_url: Option<String>,
-    pub additional_chains: Option<String>,
-    pub referendum: Option<String>,
-    pub fellowship: Option<String>,
-    pub port: Option<u16>,
-    pub pre_call: Option<String>,
-    pub pre_origin: Option<String>,
-    pub call_to_create_governance_referendum: Option<String>,
-    pub call_to_note_preimage_for_governance_referendum: Option<String>,
-    pub call_to_create_fellowship_referendum: Option<String>,
-    pub call_to_note_preimage_for_fellowship_referendum: Option<String>,
-    pub verbose: bool,
+use super::config::{
+    get_artifacts_dir, CREATE_TIMEOUT_ENV, CREATE_TIMEOUT_SECS, MAX_RSS_ENV,
+    MULTICHAIN_TIMEOUT_ENV, MULTICHAIN_TIMEOUT_SECS, RUN_ARTIFACTS_ENV, VALIDATION_TIMEOUT_ENV,
+    VALIDATION_TIMEOUT_SECS,
+};
+use super::event_db::{self, EventDb};
+use super::logging::{current_suite, sub_test_span};
+use super::port_allocator;
+
+/// Prefix of the result line the tool prints with [`OutputFormat::Json`].
+pub const RESULT_LINE_PREFIX: &str = "@@result ";
+
+/// A referendum the tool created, as reported on its stdout.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CreatedReferendum {
+    /// `Governance` or `Fellowship`.
+    pub kind: String,
+    pub id: u32,
+    /// Block that dispatched its proposal; `None` when the run stopped before
+    /// (or cancelled the referendum instead).
+    pub dispatch_block: Option<u64>,
+}
+
+/// A match's capture groups as strings; a group that did not take part in
+/// the match is empty.
+fn capture_groups(captures: &regex::Captures) -> Vec<String> {
+    captures
+        .iter()
+        .map(|group| group.map_or_else(String::new, |m| m.as_str().to_string()))
+        .collect()
+}
+
+/// An event the tool printed (`• Section.Method` plus its `Data:` payload).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedEvent {
+    /// Label of the chain it was emitted on, when the output named one.
+    pub chain: Option<String>,
+    pub block: Option<u64>,
+    /// Pallet, e.g. `Referenda`.
+    pub section: String,
+    /// Event name, e.g. `Confirmed`.
+    pub method: String,
+    /// Decoded payload, hex for bytes; `Null` without `--verbose`.
+    pub fields: serde_json::Value,
+}
+
+impl ParsedEvent {
+    /// Whether this is `section.method`.
+    pub fn is(&self, section: &str, method: &str) -> bool {
+        self.section == section && self.method == method
+    }
+}
+
+/// What a tool run used, as reported in its "Resource Usage" section.
+/// Chopsticks runs inside the tool's process, so every fork is included.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ResourceUsage {
+    pub wall_secs: f64,
+    pub cpu_secs: f64,
+    pub peak_rss_mib: f64,
+}
+
+/// One referendum's line in the tool's "Referendum Results" section, printed
+/// by runs with both a fellowship and a governance referendum.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReferendumResult {
+    /// `fellowship` or `governance`.
+    pub role: String,
+    pub referendum_id: u32,
+    /// Code it failed with; `None` when it passed.
+    pub error_code: Option<String>,
+}
+
+/// The peak RSS cap from [`MAX_RSS_ENV`], if set.
+fn max_rss_mib() -> Option<f64> {
+    std::env::var(MAX_RSS_ENV)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|mib: &f64| *mib > 0.0)
 }
 
 /// Captured output from a tool invocation.
 pub struct ToolOutput {
+    /// `--run-id` the tool prefixed its log lines with.
+    pub run_id: String,
     pub exit_code: i32,
+    /// Stdout with ANSI escape sequences stripped, so a color code never
+    /// splits the text a check looks for.
     pub stdout: String,
+    /// Stderr, stripped like `stdout`.
     pub stderr: String,
+    /// Stdout as the tool printed it, colors included; kept in the run's
+    /// artifacts.
+    pub raw_stdout: String,
+    pub raw_stderr: String,
+    /// Directory the tool stored the run's JSON result in (`--history-dir`);
+    /// set by [`ToolRunner`] for runs that keep history.
+    pub history_dir: Option<PathBuf>,
+}
+
+/// `text` without ANSI escape sequences: CSI (colors, cursor moves, line
+/// clears), OSC (titles, hyperlinks) and other two-byte escapes.
+pub fn strip_ansi(text: &str) -> String {
+    ANSI_ESCAPES.replace_all(text, "").into_owned()
+}
+
+static ANSI_ESCAPES: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\x1b\[[0-?]*[ -/]*[@-~]|\x1b\][^\x07\x1b]*(?:\x07|\x1b\\)|\x1b[@-Z\\-_]")
+        .expect("valid regex")
+});
+
+/// `<Kind> referendum #<id> created successfully`.
+static CREATED_REFERENDUM: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(Governance|Fellowship) referendum #(\d+) created successfully")
+        .expect("valid regex")
+});
+
+/// `referendum #<id> executed successfully at block <n>`.
+static DISPATCHED_REFERENDUM: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"referendum #(\d+) executed successfully at block (\d+)").expect("valid regex")
+});
+
+/// One value of the "Resource Usage" section.
+static RESOURCE_USAGE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(Wall time|CPU time|Peak RSS): ([\d.]+)(?:s| MiB)").expect("valid regex")
+});
+
+/// One line of the "Referendum Results" section.
+static REFERENDUM_RESULT: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(fellowship|governance) #(\d+): (?:passed|failed, (E-[A-Z0-9-]+):)")
+        .expect("valid regex")
+});
+
+/// Which of the tool's output streams a [`LogLine`] was printed to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stream {
+    Stdout,
+    Stderr,
+}
+
+/// One line of tool output, handed to [`ToolRunner::run_streaming`]'s callback
+/// as soon as the tool prints it.
+#[derive(Debug, Clone)]
+pub struct LogLine {
+    pub stream: Stream,
+    /// The line without its trailing newline.
+    pub text: String,
 }
 
 impl ToolOutput {
+    /// A run's output from its raw streams; see [`strip_ansi`].
+    pub fn new(run_id: String, exit_code: i32, raw_stdout: String, raw_stderr: String) -> Self {
+        Self {
+            run_id,
+            exit_code,
+            stdout: strip_ansi(&raw_stdout),
+            stderr: strip_ansi(&raw_stderr),
+            raw_stdout,
+            raw_stderr,
+            history_dir: None,
+        }
+    }
+
+    /// The run-history records this run stored, one per simulated referendum,
+    /// ordered by path: `<history dir>/<chain and referendum>/<nnnn>.json`,
+    /// what `--diff-with-last` compares against.
+    pub fn report_paths(&self) -> Result<Vec<PathBuf>> {
+        let dir = self.history_dir.as_deref().with_context(|| {
+            format!(
+                "Run [{}] kept no run history; set ToolArgs::history_dir or diff_with_last",
+                self.run_id
+            )
+        })?;
+        let mut paths = Vec::new();
+        if !dir.exists() {
+            return Ok(paths);
+        }
+        for key_dir in
+            std::fs::read_dir(dir).with_context(|| format!("reading {}", dir.display()))?
+        {
+            let key_dir = key_dir?.path();
+            if !key_dir.is_dir() {
+                continue;
+            }
+            for file in std::fs::read_dir(&key_dir)? {
+                let path = file?.path();
+                // Skips `.<record>.json.tmp` files of a write in progress
+                if path.extension().is_none_or(|ext| ext != "json") {
+                    continue;
+                }
+                let record: serde_json::Value = serde_json::from_str(
+                    &std::fs::read_to_string(&path)
+                        .with_context(|| format!("reading {}", path.display()))?,
+                )
+                .with_context(|| format!("{} is not valid JSON", path.display()))?;
+                if record["runId"].as_str() == Some(self.run_id.as_str()) {
+                    paths.push(path);
+                }
+            }
+        }
+        paths.sort();
+        Ok(paths)
+    }
+
+    /// The run-history record of a run that simulated one referendum; see
+    /// [`Self::report_paths`]. Fails when the run stored none or several.
+    pub fn report_path(&self) -> Result<PathBuf> {
+        let mut paths = self.report_paths()?;
+        ensure!(
+            paths.len() == 1,
+            "Expected run [{}] to store one history record, found {}: {paths:?}",
+            self.run_id,
+            paths.len()
+        );
+        Ok(paths.remove(0))
+    }
+
     /// Check the tool exited successfully (code 0).
     pub fn check_success(&self) -> Result<()> {
         anyhow::ensure!(
@@ -82,12 +285,391 @@ impl ToolOutput {
         );
         Ok(())
     }
+
+    /// Check stdout or stderr matches a regex, and return the first match's
+    /// capture groups: `[0]` is the whole match, a group that did not take
+    /// part in it is empty. Matching is case-sensitive unless the pattern
+    /// starts with `(?i)`. Lets a suite assert on values the tool produced
+    /// mid-run, e.g. `output.check_any_output_matches(r"Referendum #(\d+) created")?[1]`.
+    pub fn check_any_output_matches(&self, pattern: &str) -> Result<Vec<String>> {
+        let regex = Regex::new(pattern).with_context(|| format!("Invalid regex '{pattern}'"))?;
+        let captures = regex
+            .captures(&self.stdout)
+            .or_else(|| regex.captures(&self.stderr))
+            .with_context(|| {
+                format!(
+                    "Expected output to match '{}', but it didn't.\n--- stdout ---\n{}\n--- stderr ---\n{}",
+                    pattern, self.stdout, self.stderr,
+                )
+            })?;
+        Ok(capture_groups(&captures))
+    }
+
+    /// Like [`Self::check_any_output_matches`], for stdout only, e.g.
+    /// `output.check_stdout_matches(r"referendum #\d+ executed successfully at block \d+")`.
+    pub fn check_stdout_matches(&self, pattern: &str) -> Result<Vec<String>> {
+        let regex = Regex::new(pattern).with_context(|| format!("Invalid regex '{pattern}'"))?;
+        let captures = regex.captures(&self.stdout).with_context(|| {
+            format!(
+                "Expected stdout to match '{}', but it didn't.\n--- stdout ---\n{}",
+                pattern, self.stdout,
+            )
+        })?;
+        Ok(capture_groups(&captures))
+    }
+
+    /// The referendum the tool created (the governance one, when it created
+    /// a fellowship referendum first), and the block that dispatched it, so a
+    /// follow-up run can re-attach to the post-state.
+    pub fn created_referendum(&self) -> Option<CreatedReferendum> {
+        let captures = CREATED_REFERENDUM.captures_iter(&self.stdout).last()?;
+        let id: u32 = captures[2].parse().ok()?;
+        let dispatch_block = DISPATCHED_REFERENDUM
+            .captures_iter(&self.stdout)
+            .filter(|captures| captures[1].parse() == Ok(id))
+            .last()
+            .and_then(|captures| captures[2].parse().ok());
+        Some(CreatedReferendum {
+            kind: captures[1].to_string(),
+            id,
+            dispatch_block,
+        })
+    }
+
+    /// Index the tool assigned to the governance referendum it created from
+    /// call data, parsed from its `Governance referendum #N created
+    /// successfully` line. `None` when it created none.
+    pub fn referendum_index(&self) -> Option<u32> {
+        self.created_index("Governance")
+    }
+
+    /// Like [`Self::referendum_index`], for the fellowship referendum.
+    pub fn fellowship_referendum_index(&self) -> Option<u32> {
+        self.created_index("Fellowship")
+    }
+
+    fn created_index(&self, kind: &str) -> Option<u32> {
+        CREATED_REFERENDUM
+            .captures_iter(&self.stdout)
+            .filter(|captures| &captures[1] == kind)
+            .last()?[2]
+            .parse()
+            .ok()
+    }
+
+    /// The run's "Resource Usage" section; `None` when the tool exited before
+    /// printing it.
+    pub fn resource_usage(&self) -> Option<ResourceUsage> {
+        let value = |label: &str| -> Option<f64> {
+            RESOURCE_USAGE
+                .captures_iter(&self.stdout)
+                .filter(|captures| &captures[1] == label)
+                .last()?[2]
+                .parse()
+                .ok()
+        };
+        Some(ResourceUsage {
+            wall_secs: value("Wall time")?,
+            cpu_secs: value("CPU time")?,
+            peak_rss_mib: value("Peak RSS")?,
+        })
+    }
+
+    /// Check the run's peak RSS stayed within `max_mib`. Fails when the tool
+    /// exited before reporting its resource usage.
+    pub fn check_peak_rss(&self, max_mib: f64) -> Result<()> {
+        let usage = self
+            .resource_usage()
+            .with_context(|| format!("Tool run {} reported no resource usage", self.run_id))?;
+        ensure!(
+            usage.peak_rss_mib <= max_mib,
+            "Tool run {} peaked at {:.1} MiB RSS, over the {max_mib:.1} MiB limit",
+            self.run_id,
+            usage.peak_rss_mib
+        );
+        Ok(())
+    }
+
+    /// The stable error code the tool printed for a fatal error, i.e. the
+    /// `E-...` token at the start of a `E-CODE: message` stderr line.
+    pub fn error_code(&self) -> Option<&str> {
+        self.stderr.lines().find_map(|line| {
+            let (code, _) = line.trim().split_once(": ")?;
+            let is_code = code.starts_with("E-")
+                && code.len() > 2
+                && code[2..]
+                    .chars()
+                    .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '-');
+            is_code.then_some(code)
+        })
+    }
+
+    /// Why the run failed for reasons outside the referendum, making it worth
+    /// retrying: Chopsticks failing to fork (`E-CHOPSTICKS-FAILED`) or a
+    /// WebSocket connection refused or dropped under an uncoded error. `None`
+    /// for runs that passed or failed on their merits, such as a failed
+    /// dispatch or a rejected argument.
+    pub fn transient_failure(&self) -> Option<&'static str> {
+        if self.exit_code == 0 {
+            return None;
+        }
+        match self.error_code() {
+            Some("E-CHOPSTICKS-FAILED") => Some("Chopsticks failed to fork"),
+            Some(code) if code != "E-UNKNOWN" => None,
+            _ => TRANSIENT_FAILURE_PATTERNS
+                .iter()
+                .find(|(pattern, _)| self.stderr.contains(pattern) || self.stdout.contains(pattern))
+                .map(|(_, reason)| *reason),
+        }
+    }
+
+    /// What kind of failure the run ended in, from its error code, or from
+    /// its output when it printed none. `None` for a run that passed.
+    pub fn error_kind(&self) -> Option<ToolErrorKind> {
+        if self.exit_code == 0 {
+            return None;
+        }
+        if self.exit_code == -1 {
+            return Some(ToolErrorKind::Killed);
+        }
+        let kind = match self.error_code() {
+            Some(code) if code.starts_with("E-INVALID-") => ToolErrorKind::Validation,
+            Some(
+                "E-FLAG-CONFLICT"
+                | "E-NO-REFERENDUM"
+                | "E-MISSING-CHAIN-URL"
+                | "E-CALL-DECODE-FAILED"
+                | "E-UNKNOWN-REFERENDA-PALLET",
+            ) => ToolErrorKind::Validation,
+            Some("E-CHOPSTICKS-FAILED" | "E-PORT-UNAVAILABLE") => ToolErrorKind::Connection,
+            Some("E-MISSING-CAPABILITY") => ToolErrorKind::MissingCapability,
+            Some(
+                "E-REFERENDUM-NOT-CREATED"
+                | "E-REFERENDUM-NOT-FOUND"
+                | "E-REFERENDUM-NOT-ONGOING"
+                | "E-REFERENDUM-NOT-APPROVED"
+                | "E-SCHEDULED-CALL-NOT-FOUND",
+            ) => ToolErrorKind::Referendum,
+            Some("E-PREIMAGE-MISSING") => ToolErrorKind::PreimageMissing,
+            Some("E-PERMANENTLY-OVERWEIGHT" | "E-ENACTMENT-POSTPONED") => {
+                ToolErrorKind::NotDispatched
+            }
+            Some("E-EXECUTION-FAILED" | "E-BAD-ORIGIN") => ToolErrorKind::DispatchFailed,
+            Some(
+                "E-TREASURY-PAYOUT-FAILED"
+                | "E-BALANCE-CHECK-FAILED"
+                | "E-CONVICTION-LOCK-FAILED"
+                | "E-CANCELLATION-FAILED"
+                | "E-LINT-DENIED"
+                | "E-HOOK-FAILED",
+            ) => ToolErrorKind::CheckFailed,
+            Some("E-PARTIAL-SUCCESS") => ToolErrorKind::PartialSuccess,
+            Some("E-INTERRUPTED") => ToolErrorKind::Interrupted,
+            _ if self.transient_failure().is_some() => ToolErrorKind::Connection,
+            _ => ToolErrorKind::Unknown,
+        };
+        Some(kind)
+    }
+
+    /// Check that the tool failed with an error of the given kind.
+    pub fn check_error_kind(&self, expected: ToolErrorKind) -> Result<()> {
+        ensure!(
+            self.error_kind() == Some(expected),
+            "Expected a {expected:?} failure, got {:?} (error code {:?}, exit code {}).\n--- stderr ---\n{}",
+            self.error_kind(),
+            self.error_code(),
+            self.exit_code,
+            self.stderr,
+        );
+        Ok(())
+    }
+
+    /// Check that the tool failed with the given error code.
+    pub fn check_error_code(&self, expected: &str) -> Result<()> {
+        anyhow::ensure!(
+            self.error_code() == Some(expected),
+            "Expected error code {}, got {:?}.\n--- stderr ---\n{}",
+            expected,
+            self.error_code(),
+            self.stderr,
+        );
+        Ok(())
+    }
+
+    /// Every event the tool printed, in output order, for asserting payloads
+    /// rather than substrings. Events of the execution block may be listed
+    /// twice: once when dispatched and once in the post-execution summary;
+    /// only the first listing is kept, keyed by chain, block and index.
+    pub fn events(&self) -> Vec<ParsedEvent> {
+        let mut seen = HashSet::new();
+        event_db::parse_events(&self.stdout)
+            .into_iter()
+            .filter(|event| seen.insert((event.chain.clone(), event.block, event.index)))
+            .filter_map(|event| {
+                let (section, method) = event.event.split_once('.')?;
+                Some(ParsedEvent {
+                    chain: event.chain,
+                    block: event.block,
+                    section: section.to_string(),
+                    method: method.to_string(),
+                    fields: event.fields,
+                })
+            })
+            .collect()
+    }
+
+    /// Check the `(section, method)` events were printed in the order given,
+    /// with any other events in between, e.g.
+    /// `&[("Referenda", "Submitted"), ("Scheduler", "Dispatched")]`. Each
+    /// event is looked for after the one matching the previous step, so an
+    /// event that only precedes it does not count.
+    pub fn check_event_sequence(&self, expected: &[(&str, &str)]) -> Result<()> {
+        let events = self.events();
+        let mut remaining = events.iter();
+        for (step, (section, method)) in expected.iter().enumerate() {
+            if remaining.any(|e| e.is(section, method)) {
+                continue;
+            }
+            let after = step
+                .checked_sub(1)
+                .map(|previous| format!(" after {}.{}", expected[previous].0, expected[previous].1))
+                .unwrap_or_default();
+            let printed: Vec<String> = events
+                .iter()
+                .map(|e| format!("{}.{}", e.section, e.method))
+                .collect();
+            anyhow::bail!(
+                "Event {section}.{method} was not printed{after}.\nPrinted events: {}",
+                printed.join(", ")
+            );
+        }
+        Ok(())
+    }
+
+    /// Check `section.method` was emitted in block `block`, on any chain,
+    /// rather than anywhere in the run. Events get their block from the
+    /// `📡 <chain> (Block #n)` headers and, with `--verbose`, the
+    /// `Events count for block n` lines; an event printed under neither
+    /// never matches.
+    pub fn check_event_in_block(&self, block: u64, section: &str, method: &str) -> Result<()> {
+        let events = self.events();
+        if events
+            .iter()
+            .any(|e| e.block == Some(block) && e.is(section, method))
+        {
+            return Ok(());
+        }
+        let blocks: Vec<String> = events
+            .iter()
+            .filter(|e| e.is(section, method))
+            .map(|e| match e.block {
+                Some(b) => format!("#{b}"),
+                None => "an unknown block".to_string(),
+            })
+            .collect();
+        anyhow::bail!(
+            "Event {section}.{method} was not emitted in block #{block}; {}",
+            if blocks.is_empty() {
+                "it was not printed at all".to_string()
+            } else {
+                format!("it was printed in {}", blocks.join(", "))
+            }
+        )
+    }
+
+    /// Check `section.method` was not printed anywhere in the run, e.g. that a
+    /// proposal whose dispatch failed emitted no `System.UpgradeAuthorized`.
+    pub fn check_event_absent(&self, section: &str, method: &str) -> Result<()> {
+        let blocks: Vec<String> = self
+            .events()
+            .iter()
+            .filter(|e| e.is(section, method))
+            .map(|e| match e.block {
+                Some(b) => format!("#{b}"),
+                None => "an unknown block".to_string(),
+            })
+            .collect();
+        anyhow::ensure!(
+            blocks.is_empty(),
+            "Expected no {section}.{method} event, but it was printed in {}",
+            blocks.join(", ")
+        );
+        Ok(())
+    }
+
+    /// Deserialize the `@@result {json}` line printed with
+    /// [`OutputFormat::Json`], e.g. into a `serde_json::Value` holding
+    /// `runId`, `exitCode`, `error` (`{code, message}` or `null`) and
+    /// `resourceUsage`.
+    pub fn parse_json<T: DeserializeOwned>(&self) -> Result<T> {
+        let json = self
+            .stdout
+            .lines()
+            .rev()
+            .find_map(|line| line.trim().strip_prefix(RESULT_LINE_PREFIX))
+            .with_context(|| {
+                format!(
+                    "no '{RESULT_LINE_PREFIX}' line; was the tool run with \
+                     OutputFormat::Json?\n--- stdout ---\n{}",
+                    self.stdout
+                )
+            })?;
+        serde_json::from_str(json).with_context(|| format!("invalid result line: {json}"))
+    }
+
+    /// Every referendum listed in the "Referendum Results" section, in run
+    /// order; empty for single-referendum runs.
+    pub fn referendum_results(&self) -> Vec<ReferendumResult> {
+        let Some((_, section)) = self.stdout.rsplit_once("Referendum Results") else {
+            return Vec::new();
+        };
+        section
+            .lines()
+            .filter_map(|l| {
+                let c = REFERENDUM_RESULT.captures(l)?;
+                Some(ReferendumResult {
+                    role: c[1].to_string(),
+                    referendum_id: c[2].parse().ok()?,
+                    error_code: c.get(3).map(|m| m.as_str().to_string()),
+                })
+            })
+            .collect()
+    }
+
+    /// Check the `role` referendum (`fellowship` or `governance`) passed.
+    pub fn check_referendum_passed(&self, role: &str) -> Result<()> {
+        self.check_referendum_result(role, None)
+    }
+
+    /// Check the `role` referendum failed with `code`, whatever the run's
+    /// overall code.
+    pub fn check_referendum_failed(&self, role: &str, code: &str) -> Result<()> {
+        self.check_referendum_result(role, Some(code))
+    }
+
+    fn check_referendum_result(&self, role: &str, code: Option<&str>) -> Result<()> {
+        let results = self.referendum_results();
+        let result = results.iter().find(|r| r.role == role).with_context(|| {
+            format!(
+                "No {role} referendum result.\n--- stdout ---\n{}",
+                self.stdout
+            )
+        })?;
+        anyhow::ensure!(
+            result.error_code.as_deref() == code,
+            "Expected {role} referendum #{} to {}, got {:?}",
+            result.referendum_id,
+            code.map_or("pass".to_string(), |c| format!("fail with {c}")),
+            result.error_code,
+        );
+        Ok(())
+    }
 }
 
 // ── Test suite infrastructure ────────────────────────────────────────────────
 
 /// A single sub-test result: name + outcome.
-pub type SubTestResult = (&'static str, Result<()>);
+pub type SubTestResult = (String, Result<()>);
 
 /// Report all sub-test results. Logs each, then panics if any failed.
 pub fn report_results(results: &[SubTestResult]) {
@@ -97,7 +679,7 @@ pub fn report_results(results: &[SubTestResult]) {
             Ok(()) => log::info!("  PASS: {name}"),
             Err(e) => {
                 log::error!("  FAIL: {name} -- {e:#}");
-                failures.push(*name);
+                failures.push(name.as_str());
             }
         }
     }
@@ -112,9 +694,185 @@ pub fn report_results(results: &[SubTestResult]) {
     log::info!("All {}/{} sub-tests passed!", results.len(), results.len());
 }
 
+/// How long a tool run may take, by the kind of sub-test it belongs to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimeoutClass {
+    /// Argument checks that fail before anything is forked.
+    Validation,
+    /// One referendum simulated on one fork, created or by number.
+    Create,
+    /// Runs that also fork a fellowship companion or additional chains.
+    Multichain,
+}
+
+impl TimeoutClass {
+    /// Class of a run from its arguments: `Multichain` when it forks more than
+    /// one chain, otherwise `Create`. Validation is never inferred; runners opt
+    /// in with [`ToolRunner::with_timeout_class`].
+    pub fn of(args: &ToolArgs) -> Self {
+        let companion =
+            args.governance_chain_url().is_some() && args.fellowship_chain_url().is_some();
+        if companion || !args.additional_chains().is_empty() {
+            Self::Multichain
+        } else {
+            Self::Create
+        }
+    }
+
+    /// Environment variable overriding this class's timeout, in seconds.
+    pub fn env_var(self) -> &'static str {
+        match self {
+            Self::Validation => VALIDATION_TIMEOUT_ENV,
+            Self::Create => CREATE_TIMEOUT_ENV,
+            Self::Multichain => MULTICHAIN_TIMEOUT_ENV,
+        }
+    }
+
+    /// The timeout from [`Self::env_var`], or the class default.
+    pub fn timeout(self) -> Duration {
+        let default = match self {
+            Self::Validation => VALIDATION_TIMEOUT_SECS,
+            Self::Create => CREATE_TIMEOUT_SECS,
+            Self::Multichain => MULTICHAIN_TIMEOUT_SECS,
+        };
+        let secs = std::env::var(self.env_var())
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|secs| *secs > 0)
+            .unwrap_or(default);
+        Duration::from_secs(secs)
+    }
+}
+
+/// `<artifacts>/<suite>/work/<sub-test>`: where runs of the current sub-test
+/// keep their temporary state, so a failed run's is uploaded with the logs.
+fn sub_test_work_dir() -> PathBuf {
+    get_artifacts_dir(&current_suite())
+        .join("work")
+        .join(event_db::current_sub_test())
+}
+
+/// A fresh Chopsticks cache of a run's own, in the sub-test's work dir, for
+/// runs that can't wait for the runner's fork cache ([`ToolRunner::run_batch`],
+/// [`ToolRunner::spawn_session`]); one left by an earlier run is removed.
+fn own_run_cache() -> Result<PathBuf> {
+    let dir = sub_test_work_dir();
+    std::fs::create_dir_all(&dir).with_context(|| format!("creating {}", dir.display()))?;
+    let path = dir.join("chopsticks.sqlite");
+    if path.exists() {
+        std::fs::remove_file(&path).with_context(|| format!("removing {}", path.display()))?;
+    }
+    Ok(path)
+}
+
+/// Writer lock of every fork cache of this process, by path.
+static FORK_CACHE_WRITERS: LazyLock<Mutex<HashMap<PathBuf, CacheWriter>>> =
+    LazyLock::new(Mutex::default);
+
+/// Held by the run writing to a fork cache.
+type CacheWriter = Arc<tokio::sync::Mutex<()>>;
+
+/// Tool runs started by this test process, numbering their run ids.
+static RUNS: AtomicU32 = AtomicU32::new(0);
+
+/// `<sub-test>-<pid>-<n>`: a run id (`--run-id`) unique across the parallel
+/// suites of a CI job. The tool prefixes each of its log lines with it.
+fn next_run_id() -> String {
+    let n = RUNS.fetch_add(1, Ordering::Relaxed) + 1;
+    let sub_test: String = event_db::current_sub_test()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || "._-".contains(c) {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    format!("{sub_test}-{}-{n}", std::process::id())
+}
+
+/// The kind of failure a run ended in, as [`ToolOutput::error_kind`]
+/// classifies it, for negative sub-tests that accept any error of a kind
+/// rather than one exact code.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ToolErrorKind {
+    /// Arguments the tool rejected: malformed (`E-INVALID-*`), conflicting or
+    /// missing flags, or call data the runtime cannot decode.
+    Validation,
+    /// Forking failed: Chopsticks did not start, a port was taken, or a
+    /// WebSocket was refused or dropped under an uncoded error.
+    Connection,
+    /// The forked chain lacks a pallet or runtime API the run needs.
+    MissingCapability,
+    /// The referendum was not created, found, ongoing or approved on the
+    /// fork, or its enactment task was not found in the agenda.
+    Referendum,
+    /// The scheduler could not load the proposal (`E-PREIMAGE-MISSING`).
+    PreimageMissing,
+    /// The scheduler never dispatched the proposal: too heavy for any block,
+    /// or postponed while blocks ran out of weight.
+    NotDispatched,
+    /// The proposal was dispatched and failed (`E-EXECUTION-FAILED`,
+    /// `E-BAD-ORIGIN`).
+    DispatchFailed,
+    /// The proposal executed, but a check of its effects failed: a treasury
+    /// payout, tracked balance, conviction lock, cancellation refund, deny
+    /// lint or hook.
+    CheckFailed,
+    /// Only one referendum of a fellowship + governance pair passed.
+    PartialSuccess,
+    /// Stopped by SIGINT, SIGTERM or `dev_stop` (`E-INTERRUPTED`).
+    Interrupted,
+    /// Killed without an exit code, e.g. by the harness at its timeout.
+    Killed,
+    /// Failed with a code or output not classified above.
+    Unknown,
+}
+
+/// Output of an uncoded failure that says a WebSocket broke under the run,
+/// with the reason [`ToolOutput::transient_failure`] gives for it.
+const TRANSIENT_FAILURE_PATTERNS: &[(&str, &str)] = &[
+    ("ECONNREFUSED", "WebSocket connection refused"),
+    ("ECONNRESET", "WebSocket connection reset"),
+    ("socket hang up", "WebSocket connection dropped"),
+    ("WebSocket is not open", "WebSocket connection dropped"),
+];
+
+/// How [`ToolRunner::run_with_retries`] retries transient failures.
+#[derive(Clone, Copy, Debug)]
+pub struct RetryPolicy {
+    /// Runs at most, the first one included.
+    pub max_attempts: u32,
+    /// Wait before each retry, for ports and forks of the failed run to go.
+    pub delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            delay: Duration::from_secs(5),
+        }
+    }
+}
+
 /// Runs the polkadot-referenda-tester CLI tool as a child process.
 pub struct ToolRunner {
     project_dir: String,
+    event_db: Option<Arc<EventDb>>,
+    /// The shared `--db` and the lock its runs take, see [`Self::with_fork_cache`].
+    fork_cache: Option<(PathBuf, CacheWriter)>,
+    /// Timeout class for every run; inferred per run when unset.
+    timeout_class: Option<TimeoutClass>,
+    /// Keep each run's command line and output ([`RUN_ARTIFACTS_ENV`]).
+    run_artifacts: bool,
+    /// How the tool is started; `TOOL_INVOCATION` unless set with
+    /// [`ToolRunner::with_invocation`].
+    invocation: ToolInvocation,
+    /// Run history of `--diff-with-last` runs without their own
+    /// `--history-dir`; the sub-test's work dir when unset.
+    history_dir: Option<PathBuf>,
 }
 
 impl ToolRunner {
@@ -127,84 +885,732 @@ impl ToolRunner {
             let parent = cwd.parent().unwrap_or(&cwd);
             parent.to_string_lossy().to_string()
         });
-        Self { project_dir }
+        Self {
+            project_dir,
+            event_db: None,
+            fork_cache: None,
+            timeout_class: None,
+            run_artifacts: std::env::var(RUN_ARTIFACTS_ENV).is_ok_and(|v| v == "1"),
+            invocation: ToolInvocation::from_env(),
+            history_dir: None,
+        }
     }
 
-    /// Run `yarn cli test` with the given arguments.
-    pub async fn run_test_referendum(&self, args: ToolArgs) -> Result<ToolOutput> {
-        let mut cmd = tokio::process::Command::new("yarn");
-        cmd.current_dir(&self.project_dir).arg("cli").arg("test");
+    /// Start the tool with `invocation` instead of the one `TOOL_INVOCATION`
+    /// selects, e.g. [`ToolInvocation::Node`] where yarn is not installed.
+    pub fn with_invocation(mut self, invocation: ToolInvocation) -> Self {
+        self.invocation = invocation;
+        self
+    }
 
-        if let Some(ref url) = args.governance_chain_url {
-            cmd.arg("--governance-chain-url").arg(url);
-        }
-        if let Some(ref url) = args.fellowship_chain_url {
-            cmd.arg("--fellowship-chain-url").arg(url);
-        }
-        if let Some(ref chains) = args.additional_chains {
-            cmd.arg("--additional-chains").arg(chains);
+    /// Keep every run's command line, stdout and stderr in its sub-test's
+    /// artifacts dir, as [`RUN_ARTIFACTS_ENV`] does.
+    pub fn with_run_artifacts(mut self) -> Self {
+        self.run_artifacts = true;
+        self
+    }
+
+    /// Give every run this runner starts the timeout of `class` instead of the
+    /// class inferred from its arguments (see [`TimeoutClass::of`]).
+    pub fn with_timeout_class(mut self, class: TimeoutClass) -> Self {
+        self.timeout_class = Some(class);
+        self
+    }
+
+    /// Keep the run history of `--diff-with-last` runs that don't set their
+    /// own `--history-dir` in `dir`.
+    pub fn with_history_dir(mut self, dir: PathBuf) -> Self {
+        self.history_dir = Some(dir);
+        self
+    }
+
+    /// Record every event decoded from this runner's tool output into `db`.
+    pub fn with_event_db(mut self, db: Arc<EventDb>) -> Self {
+        self.event_db = Some(db);
+        self
+    }
+
+    /// Share one Chopsticks storage cache (`--db`) across every run that doesn't
+    /// set its own. Create-mode sub-tests all fork the same block, so only the
+    /// first one fetches state from the zombienet node; the rest read it from
+    /// the cache. Any stale cache at `path` is removed first.
+    ///
+    /// The cache takes a single writer: a run using it holds a lock on `path`,
+    /// shared by every runner of this process, until it exits, so concurrent
+    /// runs on the same cache wait for each other. Sessions, which keep their
+    /// forks alive, and [`Self::run_batch`] runs get a cache of their own.
+    pub fn with_fork_cache(mut self, path: PathBuf) -> Self {
+        if let Some(parent) = path.parent() {
+            if let Err(e) = std::fs::create_dir_all(parent) {
+                log::warn!("Failed to create {}: {e}", parent.display());
+            }
         }
-        if let Some(ref id) = args.referendum {
-            cmd.arg("--referendum").arg(id);
+        if path.exists() {
+            if let Err(e) = std::fs::remove_file(&path) {
+                log::warn!("Failed to remove stale fork cache {}: {e}", path.display());
+            }
         }
-        if let Some(ref id) = args.fellowship {
-            cmd.arg("--fellowship").arg(id);
+        let writer = FORK_CACHE_WRITERS
+            .lock()
+            .expect("fork cache lock poisoned")
+            .entry(path.clone())
+            .or_default()
+            .clone();
+        self.fork_cache = Some((path, writer));
+        self
+    }
+
+    /// Wait until a run with `args` may write to the fork cache, if it uses it.
+    /// The run holds the returned guard until it exits.
+    async fn lock_fork_cache(&self, args: &ToolArgs) -> Option<tokio::sync::MutexGuard<'_, ()>> {
+        match &self.fork_cache {
+            Some((path, writer)) if args.db().is_none() => {
+                let guard = writer.lock().await;
+                log::debug!("Holding fork cache {}", path.display());
+                Some(guard)
+            }
+            _ => None,
         }
-        if let Some(port) = args.port {
-            cmd.arg("--port").arg(port.to_string());
+    }
+
+    /// Where a run with `args` stores its results, if it keeps any: its
+    /// `--history-dir` resolved against the project dir (the tool's cwd),
+    /// else, with `--diff-with-last`, the runner's history dir, which
+    /// [`Self::command`] passes to the tool.
+    fn history_dir(&self, args: &ToolArgs) -> Option<PathBuf> {
+        match args.history_dir() {
+            Some(dir) => Some(Path::new(&self.project_dir).join(dir)),
+            None if args.diff_with_last() => Some(
+                self.history_dir
+                    .clone()
+                    .unwrap_or_else(|| sub_test_work_dir().join("history")),
+            ),
+            None => None,
         }
-        if let Some(ref hex) = args.pre_call {
-            cmd.arg("--pre-call").arg(hex);
+    }
+
+    /// `yarn cli test` (or the runner's other [`ToolInvocation`]) with `args`,
+    /// the runner's fork cache and work dir, and a fresh run id (returned with
+    /// the command), to spawn as a [`ToolProcess`].
+    fn command(&self, args: &ToolArgs) -> (tokio::process::Command, String) {
+        let run_id = next_run_id();
+        let mut cmd = self.invocation.command(Path::new(&self.project_dir));
+        cmd.arg("test").arg("--run-id").arg(&run_id);
+
+        for (flag, value) in args.flags() {
+            cmd.arg(flag);
+            if let Some(value) = value {
+                cmd.arg(value);
+            }
         }
-        if let Some(ref origin) = args.pre_origin {
-            cmd.arg("--pre-origin").arg(origin);
+        if args.db().is_none() {
+            if let Some((cache, _)) = &self.fork_cache {
+                cmd.arg("--db").arg(cache);
+            }
         }
-        if let Some(ref hex) = args.call_to_create_governance_referendum {
-            cmd.arg("--call-to-create-governance-referendum").arg(hex);
+        if args.work_dir().is_none() {
+            cmd.arg("--work-dir").arg(sub_test_work_dir());
         }
-        if let Some(ref hex) = args.call_to_note_preimage_for_governance_referendum {
-            cmd.arg("--call-to-note-preimage-for-governance-referendum")
-                .arg(hex);
+        if args.history_dir().is_none() {
+            if let Some(dir) = self.history_dir(args) {
+                cmd.arg("--history-dir").arg(dir);
+            }
         }
-        if let Some(ref hex) = args.call_to_create_fellowship_referendum {
-            cmd.arg("--call-to-create-fellowship-referendum").arg(hex);
+        cmd.envs(args.env().iter().map(|(key, value)| (key, value)));
+        (cmd, run_id)
+    }
+
+    /// Command line of `cmd` to keep with the run's artifacts, when they are kept.
+    fn artifacts_command(&self, cmd: &tokio::process::Command) -> Option<String> {
+        self.run_artifacts.then(|| command_line(cmd))
+    }
+
+    /// Timeout class of a run with `args`: the runner's class, else the
+    /// inferred one.
+    fn timeout_class(&self, args: &ToolArgs) -> TimeoutClass {
+        self.timeout_class.unwrap_or_else(|| TimeoutClass::of(args))
+    }
+
+    /// Timeout of a run with `args`, its own [`ToolArgs::timeout`] first, and
+    /// where it came from for the timeout error.
+    fn timeout(&self, args: &ToolArgs) -> (Duration, String) {
+        match args.timeout() {
+            Some(timeout) => (timeout, "ToolArgs::timeout".to_string()),
+            None => {
+                let class = self.timeout_class(args);
+                (
+                    class.timeout(),
+                    format!("{class:?} timeout, set {} to change it", class.env_var()),
+                )
+            }
         }
-        if let Some(ref hex) = args.call_to_note_preimage_for_fellowship_referendum {
-            cmd.arg("--call-to-note-preimage-for-fellowship-referendum")
-                .arg(hex);
+    }
+
+    /// Run `yarn cli test` with the given arguments.
+    pub async fn run_test_referendum(&self, args: ToolArgs) -> Result<ToolOutput> {
+        self.run_streaming(args, |_| {}).await
+    }
+
+    /// Like [`Self::run_test_referendum`], but runs the tool again, with a
+    /// fresh run id and a fresh `--port-range` from
+    /// [`port_allocator::next_tool_range`], while it fails for a transient
+    /// reason (see [`ToolOutput::transient_failure`]), up to
+    /// `policy.max_attempts` runs. Genuine failures and timeouts are returned
+    /// from the first run as is.
+    pub async fn run_with_retries(
+        &self,
+        args: ToolArgs,
+        policy: RetryPolicy,
+    ) -> Result<ToolOutput> {
+        let mut attempt = 1;
+        loop {
+            let mut args = args.clone();
+            if attempt > 1 {
+                // The failed run's ports may still be held by its Chopsticks
+                // instances on their way down.
+                args.set_port_range(port_allocator::next_tool_range().to_arg());
+            }
+            let output = self.run_test_referendum(args).await?;
+            match output.transient_failure() {
+                Some(reason) if attempt < policy.max_attempts => {
+                    log::warn!(
+                        "RETRY: tool run {} failed transiently ({reason}), attempt {attempt}/{}",
+                        output.run_id,
+                        policy.max_attempts
+                    );
+                    tokio::time::sleep(policy.delay).await;
+                    attempt += 1;
+                }
+                _ => return Ok(output),
+            }
         }
-        if args.verbose {
-            cmd.arg("--verbose");
+    }
+
+    /// Run every `(label, args)` of `batch`, at most `max_parallel` tool
+    /// processes at a time, and return their results in `batch` order. Each run
+    /// is attributed to the sub-test `label` (event db, log file, artifacts and
+    /// work dir) as if `run_and_bail!` had started it. A run without a
+    /// `--port-range` gets its own block from [`port_allocator::next_tool_range`],
+    /// and one without a `--db` gets its own Chopsticks cache in its work dir
+    /// instead of the runner's fork cache, which only takes one writer at a
+    /// time. Each run is retried like [`Self::run_with_retries`] with the
+    /// default [`RetryPolicy`]; one run failing or timing out does not stop
+    /// the others.
+    ///
+    /// The runs are polled on the caller's task rather than spawned, so its
+    /// suite (see [`logging::suite`](super::logging::suite)) carries over.
+    pub async fn run_batch(
+        &self,
+        batch: Vec<(String, ToolArgs)>,
+        max_parallel: usize,
+    ) -> Vec<Result<ToolOutput>> {
+        log::info!(
+            "Running a batch of {} tool run(s), {} at a time",
+            batch.len(),
+            max_parallel.max(1)
+        );
+        let runs = batch
+            .into_iter()
+            .enumerate()
+            .map(|(index, (label, mut args))| {
+                if args.port_range().is_none() {
+                    args.set_port_range(port_allocator::next_tool_range().to_arg());
+                }
+                let span = sub_test_span(&label);
+                let run = event_db::CURRENT_SUB_TEST.scope(label, async move {
+                    if args.db().is_none() {
+                        args.set_db(own_run_cache()?.to_string_lossy());
+                    }
+                    self.run_with_retries(args, RetryPolicy::default()).await
+                });
+                async move { (index, run.instrument(span).await) }
+            });
+        let mut results: Vec<_> = futures::stream::iter(runs)
+            .buffer_unordered(max_parallel.max(1))
+            .collect()
+            .await;
+        results.sort_by_key(|(index, _)| *index);
+        results.into_iter().map(|(_, result)| result).collect()
+    }
+
+    /// Like [`Self::run_test_referendum`], but hands every stdout and stderr
+    /// line to `on_line` as the tool prints it, e.g. to follow a long
+    /// simulation live in CI. The returned output still holds all of it.
+    pub async fn run_streaming(
+        &self,
+        args: ToolArgs,
+        mut on_line: impl FnMut(LogLine),
+    ) -> Result<ToolOutput> {
+        let (timeout, timeout_source) = self.timeout(&args);
+        let _fork_cache = self.lock_fork_cache(&args).await;
+        let (mut cmd, run_id) = self.command(&args);
+        let artifacts = self.artifacts_command(&cmd);
+
+        log::info!(
+            "Running tool [{run_id}] ({}s timeout): {}",
+            timeout.as_secs(),
+            command_line(&cmd)
+        );
+
+        // Dropping the child, also when this future is cancelled, kills its group
+        let mut child = ToolProcess::spawn(&mut cmd)?;
+        // `next_segment` is cancel safe, unlike `read_until`, so a line half
+        // read when the other stream wins the select is not lost
+        let mut stdout_lines =
+            BufReader::new(child.stdout.take().context("tool stdout not captured")?).split(b'\n');
+        let mut stderr_lines =
+            BufReader::new(child.stderr.take().context("tool stderr not captured")?).split(b'\n');
+        let (mut stdout, mut stderr) = (String::new(), String::new());
+        let streamed = tokio::time::timeout(timeout, async {
+            let (mut stdout_open, mut stderr_open) = (true, true);
+            while stdout_open || stderr_open {
+                let (stream, line) = tokio::select! {
+                    line = stdout_lines.next_segment(), if stdout_open => {
+                        (Stream::Stdout, line?)
+                    }
+                    line = stderr_lines.next_segment(), if stderr_open => {
+                        (Stream::Stderr, line?)
+                    }
+                };
+                let (buf, open) = match stream {
+                    Stream::Stdout => (&mut stdout, &mut stdout_open),
+                    Stream::Stderr => (&mut stderr, &mut stderr_open),
+                };
+                match line {
+                    Some(bytes) => {
+                        let text = String::from_utf8_lossy(&bytes).into_owned();
+                        buf.push_str(&text);
+                        buf.push('\n');
+                        on_line(LogLine { stream, text });
+                    }
+                    None => *open = false,
+                }
+            }
+            child.wait().await
+        })
+        .await;
+
+        let status = match streamed {
+            Ok(status) => status.context("Tool process failed")?,
+            Err(_) => {
+                child.kill_group().await;
+                if let Some(command) = &artifacts {
+                    let partial = ToolOutput::new(run_id.clone(), -1, stdout, stderr);
+                    write_run_artifacts(command, &partial);
+                }
+                bail!(
+                    "Tool run {run_id} timed out after {}s ({timeout_source})",
+                    timeout.as_secs()
+                );
+            }
+        };
+
+        let mut tool_output = ToolOutput::new(run_id, status.code().unwrap_or(-1), stdout, stderr);
+        tool_output.history_dir = self.history_dir(&args);
+        finish_run(self.event_db.as_deref(), artifacts.as_deref(), &tool_output)?;
+        Ok(tool_output)
+    }
+
+    /// Run `yarn cli test` with `--no-cleanup` and return once the run is over
+    /// and the tool paused its forks. Each fork is then a live Chopsticks node
+    /// at [`ToolSession::endpoint`], for post-enactment queries and
+    /// extrinsics, until [`ToolSession::stop`]. The tool pauses after a failed
+    /// run too, so check [`ToolSession::stdout`]; a tool that exits instead
+    /// fails this. Dropping the session kills the tool.
+    pub async fn spawn_session(&self, mut args: ToolArgs) -> Result<ToolSession> {
+        // A paused fork keeps writing to its cache, so it can't share the runner's
+        if args.db().is_none() && self.fork_cache.is_some() {
+            args.set_db(own_run_cache()?.to_string_lossy());
         }
+        let (timeout, timeout_source) = self.timeout(&args);
+        let (mut cmd, run_id) = self.command(&args);
+        cmd.arg("--no-cleanup").arg("--emit-blocks");
+        let artifacts = self.artifacts_command(&cmd);
 
-        cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
+        log::info!(
+            "Starting tool session [{run_id}] ({}s timeout): {}",
+            timeout.as_secs(),
+            command_line(&cmd)
+        );
 
-        log::info!("Running tool: {cmd:?}");
+        let mut heads = Vec::new();
+        let mut out = String::new();
+        let tool = PausedTool::start(&mut cmd, timeout, |line| {
+            if let Some(line_heads) = ChainHead::parse_line(line) {
+                heads = line_heads;
+            }
+            out.push_str(line);
+            out.push('\n');
+            Ok(())
+        })
+        .await
+        .with_context(|| format!("Tool session [{run_id}] ({timeout_source})"))?;
 
-        let child = cmd.spawn().context("Failed to spawn yarn cli process")?;
+        for head in &heads {
+            log::info!(
+                "Tool session [{run_id}] keeps {} at {} (#{})",
+                head.chain,
+                head.endpoint,
+                head.end.number
+            );
+        }
+        Ok(ToolSession {
+            run_id,
+            tool,
+            heads,
+            stdout: out,
+            event_db: self.event_db.clone(),
+            artifacts,
+            history_dir: self.history_dir(&args),
+        })
+    }
+}
 
-        let output = tokio::time::timeout(
-            Duration::from_secs(TOOL_EXECUTION_TIMEOUT_SECS),
-            child.wait_with_output(),
+/// `cmd` as one shell command line, `cd`-ing into its directory first and
+/// prefixed with the variables it sets in the environment. Their values are
+/// redacted, as they may hold credentials (e.g. a proxy URL or an API key),
+/// so the line is safe for logs and artifacts but not to paste as is.
+fn command_line(cmd: &tokio::process::Command) -> String {
+    let cmd = cmd.as_std();
+    let env = cmd
+        .get_envs()
+        .filter(|(_, value)| value.is_some())
+        .map(|(key, _)| format!("{}=<redacted>", key.to_string_lossy()));
+    let program = env
+        .chain(
+            std::iter::once(cmd.get_program())
+                .chain(cmd.get_args())
+                .map(|arg| shell_quote(&arg.to_string_lossy())),
         )
-        .await
-        .context("Tool execution timed out")?
-        .context("Tool process failed")?;
+        .collect::<Vec<_>>()
+        .join(" ");
+    match cmd.get_current_dir() {
+        Some(dir) => format!("cd {} && {program}", shell_quote(&dir.to_string_lossy())),
+        None => program,
+    }
+}
 
-        let tool_output = ToolOutput {
-            exit_code: output.status.code().unwrap_or(-1),
-            stdout: String::from_utf8_lossy(&output.stdout).to_string(),
-            stderr: String::from_utf8_lossy(&output.stderr).to_string(),
-        };
+fn shell_quote(arg: &str) -> String {
+    let plain = |c: char| c.is_ascii_alphanumeric() || "-_./:=,@+%".contains(c);
+    if !arg.is_empty() && arg.chars().all(plain) {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', r"'\''"))
+    }
+}
+
+/// Write a run's command line, stdout and stderr to
+/// `<suite>/runs/<sub_test>/<run id>/` in the artifacts dir. Failures are only
+/// logged.
+fn write_run_artifacts(command: &str, output: &ToolOutput) {
+    let dir = get_artifacts_dir(&current_suite())
+        .join("runs")
+        .join(event_db::current_sub_test())
+        .join(&output.run_id);
+    let written = std::fs::create_dir_all(&dir).and_then(|()| {
+        std::fs::write(dir.join("command.txt"), format!("{command}\n"))?;
+        std::fs::write(dir.join("stdout.log"), &output.raw_stdout)?;
+        std::fs::write(dir.join("stderr.log"), &output.raw_stderr)
+    });
+    match written {
+        Ok(()) => log::info!("Tool [{}] artifacts: {}", output.run_id, dir.display()),
+        Err(e) => log::warn!("Failed to write artifacts to {}: {e}", dir.display()),
+    }
+}
+
+/// Log a finished run's output, keep its artifacts when `artifacts` holds its
+/// command line and record its events into `event_db`. Fails when the run
+/// exceeded the [`MAX_RSS_ENV`] cap.
+fn finish_run(
+    event_db: Option<&EventDb>,
+    artifacts: Option<&str>,
+    output: &ToolOutput,
+) -> Result<()> {
+    let run_id = &output.run_id;
+    log::info!("Tool [{run_id}] exit code: {}", output.exit_code);
+    if let Some(command) = artifacts {
+        write_run_artifacts(command, output);
+    }
+    let usage = output.resource_usage();
+    if let Some(usage) = usage {
+        log::info!(
+            "Tool [{run_id}] took {:.1}s, {:.1}s CPU, {:.1} MiB peak RSS",
+            usage.wall_secs,
+            usage.cpu_secs,
+            usage.peak_rss_mib
+        );
+    }
+    if !output.stdout.is_empty() {
+        log::debug!("Tool [{run_id}] stdout:\n{}", output.stdout);
+    }
+    if !output.stderr.is_empty() {
+        log::debug!("Tool [{run_id}] stderr:\n{}", output.stderr);
+    }
 
-        log::info!("Tool exit code: {}", tool_output.exit_code);
-        if !tool_output.stdout.is_empty() {
-            log::debug!("Tool stdout:\n{}", tool_output.stdout);
+    if let Some(db) = event_db {
+        let sub_test = event_db::current_sub_test();
+        match db.record(&sub_test, &output.stdout) {
+            Ok(n) => log::debug!("Recorded {n} event(s) for {sub_test}"),
+            Err(e) => log::warn!("Failed to record events for {sub_test}: {e:#}"),
         }
-        if !tool_output.stderr.is_empty() {
-            log::debug!("Tool stderr:\n{}", tool_output.stderr);
+    }
+
+    if let (Some(max_mib), Some(_)) = (max_rss_mib(), usage) {
+        output
+            .check_peak_rss(max_mib)
+            .with_context(|| format!("{MAX_RSS_ENV} exceeded"))?;
+    }
+    Ok(())
+}
+
+/// A tool run whose forks are kept alive after the referendum was enacted
+/// (see [`ToolRunner::spawn_session`]).
+pub struct ToolSession {
+    run_id: String,
+    tool: PausedTool,
+    heads: Vec<ChainHead>,
+    /// Tool stdout up to the pause.
+    stdout: String,
+    event_db: Option<Arc<EventDb>>,
+    /// Command line to keep with the run's artifacts, when they are kept.
+    artifacts: Option<String>,
+    /// See [`ToolOutput::history_dir`].
+    history_dir: Option<PathBuf>,
+}
+
+impl ToolSession {
+    /// `--run-id` of the session's tool run.
+    pub fn run_id(&self) -> &str {
+        &self.run_id
+    }
+
+    /// Start and final head of every kept fork.
+    pub fn heads(&self) -> &[ChainHead] {
+        &self.heads
+    }
+
+    /// Live RPC endpoint of the kept fork of `chain` (as labeled in the heads,
+    /// e.g. `AssetHub`).
+    pub fn endpoint(&self, chain: &str) -> Result<&str> {
+        self.heads
+            .iter()
+            .find(|head| head.chain == chain)
+            .map(|head| head.endpoint.as_str())
+            .with_context(|| {
+                let chains: Vec<_> = self.heads.iter().map(|head| head.chain.as_str()).collect();
+                format!(
+                    "Tool session [{}] kept no fork for chain '{chain}' (kept: {chains:?})",
+                    self.run_id
+                )
+            })
+    }
+
+    /// Tool stdout up to the pause, e.g. to check the run succeeded.
+    pub fn stdout(&self) -> &str {
+        &self.stdout
+    }
+
+    /// Interrupt the tool and wait for it to tear the forks down, see
+    /// [`PausedTool::stop`]. The returned output covers the whole run; a
+    /// clean stop reports `E-INTERRUPTED`.
+    pub async fn stop(self) -> Result<ToolOutput> {
+        let run_id = self.run_id;
+        let stopped = self
+            .tool
+            .stop()
+            .await
+            .with_context(|| format!("Tool session [{run_id}]"))?;
+        let mut output = ToolOutput::new(
+            run_id,
+            stopped.status.code().unwrap_or(-1),
+            self.stdout + &stopped.rest,
+            stopped.stderr,
+        );
+        output.history_dir = self.history_dir;
+        finish_run(self.event_db.as_deref(), self.artifacts.as_deref(), &output)?;
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn output(exit_code: i32, stdout: &str, stderr: &str) -> ToolOutput {
+        ToolOutput {
+            run_id: "run-1".to_string(),
+            exit_code,
+            stdout: strip_ansi(stdout),
+            stderr: strip_ansi(stderr),
+            raw_stdout: stdout.to_string(),
+            raw_stderr: stderr.to_string(),
+            history_dir: None,
         }
+    }
 
-        Ok(tool_output)
+    /// A verbose run that created and dispatched governance referendum #7.
+    const DISPATCHED_RUN: &str = "\
+[run-1] ℹ Detected chain: AssetHub (asset-hub-polkadot)
+[run-1] ✓ Governance referendum #7 created successfully
+[run-1] ℹ 📡 AssetHub (Block #12)
+[run-1] ▸   • Referenda.Submitted
+[run-1] ▸     Data: {
+  \"index\": 7
+}
+[run-1] ▸   • Referenda.Confirmed
+[run-1] ℹ 📡 AssetHub (Block #14)
+[run-1] ▸   • Scheduler.Dispatched
+[run-1] ▸   • Whitelist.WhitelistedCallDispatched
+[run-1] ℹ 📡 AssetHub (Block #14)
+[run-1] ▸   • Scheduler.Dispatched
+[run-1] ✓ ✓ Governance referendum #7 executed successfully at block 14!
+[run-1] ℹ Wall time: 12.5s
+[run-1] ℹ CPU time: 30.0s
+[run-1] ℹ Peak RSS: 812.4 MiB
+";
+
+    #[test]
+    fn strip_ansi_removes_colors_and_hyperlinks() {
+        let text = "\x1b[90m[run-1]\x1b[39m \x1b[31m✖\x1b[0m \x1b]8;;https://x\x07link\x1b]8;;\x07";
+        assert_eq!(strip_ansi(text), "[run-1] ✖ link");
+    }
+
+    #[test]
+    fn events_keep_the_first_listing_of_each_block() {
+        let events = output(0, DISPATCHED_RUN, "").events();
+        let names: Vec<_> = events
+            .iter()
+            .map(|e| (e.block, format!("{}.{}", e.section, e.method)))
+            .collect();
+        assert_eq!(
+            names,
+            [
+                (Some(12), "Referenda.Submitted".to_string()),
+                (Some(12), "Referenda.Confirmed".to_string()),
+                (Some(14), "Scheduler.Dispatched".to_string()),
+                (Some(14), "Whitelist.WhitelistedCallDispatched".to_string()),
+            ]
+        );
+        assert_eq!(events[0].chain.as_deref(), Some("AssetHub"));
+        assert_eq!(events[0].fields, serde_json::json!({ "index": 7 }));
+    }
+
+    #[test]
+    fn event_checks_match_order_block_and_absence() {
+        let run = output(0, DISPATCHED_RUN, "");
+        run.check_event_sequence(&[("Referenda", "Submitted"), ("Scheduler", "Dispatched")])
+            .unwrap();
+        assert!(run
+            .check_event_sequence(&[("Scheduler", "Dispatched"), ("Referenda", "Submitted")])
+            .is_err());
+        run.check_event_in_block(14, "Scheduler", "Dispatched")
+            .unwrap();
+        let wrong_block = run
+            .check_event_in_block(12, "Scheduler", "Dispatched")
+            .unwrap_err();
+        assert!(wrong_block.to_string().contains("printed in #14"));
+        run.check_event_absent("System", "UpgradeAuthorized")
+            .unwrap();
+        assert!(run.check_event_absent("Referenda", "Confirmed").is_err());
+    }
+
+    #[test]
+    fn created_referendum_reads_id_and_dispatch_block() {
+        let run = output(0, DISPATCHED_RUN, "");
+        assert_eq!(
+            run.created_referendum(),
+            Some(CreatedReferendum {
+                kind: "Governance".to_string(),
+                id: 7,
+                dispatch_block: Some(14),
+            })
+        );
+        assert_eq!(run.referendum_index(), Some(7));
+        assert_eq!(run.fellowship_referendum_index(), None);
+        assert_eq!(
+            run.resource_usage(),
+            Some(ResourceUsage {
+                wall_secs: 12.5,
+                cpu_secs: 30.0,
+                peak_rss_mib: 812.4,
+            })
+        );
+    }
+
+    #[test]
+    fn created_referendum_ignores_other_referenda_dispatches() {
+        let stdout = "\
+✓ Fellowship referendum #3 created successfully
+✓ Fellowship referendum #3 executed successfully at block 20!
+✓ Governance referendum #4 created successfully
+";
+        let run = output(1, stdout, "");
+        assert_eq!(run.fellowship_referendum_index(), Some(3));
+        let created = run.created_referendum().unwrap();
+        assert_eq!((created.id, created.dispatch_block), (4, None));
+    }
+
+    #[test]
+    fn referendum_results_skip_ids_that_do_not_fit() {
+        let stdout = "\
+━━━ Referendum Results ━━━
+✓ fellowship #3: passed
+✖ governance #4: failed, E-EXECUTION-FAILED: dispatch failed
+✓ governance #99999999999: passed
+";
+        assert_eq!(
+            output(1, stdout, "").referendum_results(),
+            [
+                ReferendumResult {
+                    role: "fellowship".to_string(),
+                    referendum_id: 3,
+                    error_code: None,
+                },
+                ReferendumResult {
+                    role: "governance".to_string(),
+                    referendum_id: 4,
+                    error_code: Some("E-EXECUTION-FAILED".to_string()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn error_kind_classifies_codes_and_uncoded_output() {
+        let failed = |stderr: &str| output(1, "", stderr).error_kind();
+        assert_eq!(output(0, "", "").error_kind(), None);
+        assert_eq!(output(-1, "", "").error_kind(), Some(ToolErrorKind::Killed));
+        assert_eq!(
+            failed("E-INVALID-HEX: not hex"),
+            Some(ToolErrorKind::Validation)
+        );
+        assert_eq!(
+            failed("\x1b[31mE-EXECUTION-FAILED: BadOrigin\x1b[0m"),
+            Some(ToolErrorKind::DispatchFailed)
+        );
+        assert_eq!(
+            failed("Error: socket hang up"),
+            Some(ToolErrorKind::Connection)
+        );
+        assert_eq!(failed("Error: boom"), Some(ToolErrorKind::Unknown));
+    }
+
+    #[test]
+    fn transient_failure_only_for_forking_and_uncoded_socket_errors() {
+        let failed = |stderr: &str| output(1, "", stderr).transient_failure();
+        assert_eq!(
+            failed("E-CHOPSTICKS-FAILED: fork failed"),
+            Some("Chopsticks failed to fork")
+        );
+        assert_eq!(
+            failed("Error: connect ECONNREFUSED 127.0.0.1:9944"),
+            Some("WebSocket connection refused")
+        );
+        assert_eq!(
+            failed("E-UNKNOWN: read ECONNRESET"),
+            Some("WebSocket connection reset")
+        );
+        assert_eq!(failed("E-EXECUTION-FAILED: ECONNRESET"), None);
+        assert_eq!(output(0, "", "ECONNREFUSED").transient_failure(), None);
     }
 }
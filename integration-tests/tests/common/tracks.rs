@@ -1,4 +1,4 @@
-//! Track definitions for all governance and fellowship referendum tracks.
+//! Track definitions for all governance, fellowship and ambassador referendum tracks.
 //!
 //! Shared by Polkadot and Kusama networks. The origin variant names must exactly
 //! match the runtime's `OriginCaller` enum variants.
@@ -15,7 +15,8 @@ pub struct GovernanceTrack {
     pub is_root: bool,
 }
 
-/// A fellowship referendum track.
+/// A fellowship referendum track, or a track of another ranked collective
+/// (the Ambassador collective's tracks use the same shape).
 pub struct FellowshipTrack {
     pub id: u16,
     pub name: &'static str,
@@ -348,3 +349,65 @@ pub const KUSAMA_FELLOWSHIP_TRACKS: &[FellowshipTrack] = &[
         min_rank: 9,
     },
 ];
+
+// ---------------------------------------------------------------------------
+// Polkadot Collectives ambassador tracks (9 tracks)
+// Origin caller outer variant: "AmbassadorOrigins"
+// ---------------------------------------------------------------------------
+
+pub const POLKADOT_AMBASSADOR_TRACKS: &[FellowshipTrack] = &[
+    FellowshipTrack {
+        id: 1,
+        name: "AmbassadorTier1",
+        origin_variant: "AmbassadorTier1",
+        min_rank: 1,
+    },
+    FellowshipTrack {
+        id: 2,
+        name: "AmbassadorTier2",
+        origin_variant: "AmbassadorTier2",
+        min_rank: 2,
+    },
+    FellowshipTrack {
+        id: 3,
+        name: "SeniorAmbassadorTier3",
+        origin_variant: "SeniorAmbassadorTier3",
+        min_rank: 3,
+    },
+    FellowshipTrack {
+        id: 4,
+        name: "SeniorAmbassadorTier4",
+        origin_variant: "SeniorAmbassadorTier4",
+        min_rank: 4,
+    },
+    FellowshipTrack {
+        id: 5,
+        name: "HeadAmbassadorTier5",
+        origin_variant: "HeadAmbassadorTier5",
+        min_rank: 5,
+    },
+    FellowshipTrack {
+        id: 6,
+        name: "HeadAmbassadorTier6",
+        origin_variant: "HeadAmbassadorTier6",
+        min_rank: 6,
+    },
+    FellowshipTrack {
+        id: 7,
+        name: "HeadAmbassadorTier7",
+        origin_variant: "HeadAmbassadorTier7",
+        min_rank: 7,
+    },
+    FellowshipTrack {
+        id: 8,
+        name: "MasterAmbassadorTier8",
+        origin_variant: "MasterAmbassadorTier8",
+        min_rank: 8,
+    },
+    FellowshipTrack {
+        id: 9,
+        name: "MasterAmbassadorTier9",
+        origin_variant: "MasterAmbassadorTier9",
+        min_rank: 9,
+    },
+];
@@ -6,19 +6,49 @@
 //! tests load these cached specs via `with_chain_spec_path()` and skip the
 //! expensive WASM execution + raw conversion (~3-5 min per chain).
 //!
+//! `CHAIN_SPEC_TOPOLOGIES` selects the topologies to generate, by name from
+//! `config::TOPOLOGIES` (default `polkadot,kusama`; `westend` is also
+//! registered), and `EXTRA_PARACHAINS` adds optional parachains (e.g.
+//! `people,coretime`) to every selected topology that offers them. The selected
+//! networks spawn concurrently (each in its own port range) and the resulting
+//! specs are copied in parallel.
+//!
 //! Usage:
 //!   POLKADOT_BINARY_PATH=../bin/polkadot \
 //!   POLKADOT_PARACHAIN_BINARY_PATH=../bin/polkadot-parachain \
 //!   CHAIN_SPECS_DIR=./chain-specs \
+//!   CHAIN_SPEC_TOPOLOGIES=polkadot,westend \
 //!   cargo test --test generate_chain_specs -- --nocapture
 
 mod common;
 
 use anyhow::{Context, Result};
 use std::path::{Path, PathBuf};
+use tokio::task::JoinSet;
 
-use common::config;
+use common::config::{self, NetworkTopology};
 use common::network::{initialize_network, verify_binaries};
+use common::{override_audit, raw_storage};
+
+/// Comma-separated `config::TOPOLOGIES` names to generate chain specs for.
+const TOPOLOGIES_ENV: &str = "CHAIN_SPEC_TOPOLOGIES";
+const DEFAULT_TOPOLOGIES: &str = "polkadot,kusama";
+
+/// Resolve the topologies selected in `CHAIN_SPEC_TOPOLOGIES`.
+fn selected_topologies() -> Result<Vec<&'static NetworkTopology>> {
+    let names = std::env::var(TOPOLOGIES_ENV).unwrap_or_else(|_| DEFAULT_TOPOLOGIES.to_string());
+    let topologies = names
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(config::find_topology)
+        .collect::<Result<Vec<_>>>()?;
+    anyhow::ensure!(
+        !topologies.is_empty(),
+        "{TOPOLOGIES_ENV} selects no topology"
+    );
+    Ok(topologies)
+}
 
 /// Resolve the output directory for cached chain specs.
 fn output_dir() -> PathBuf {
@@ -54,6 +84,9 @@ fn save_spec(base_dir: &str, spec_name: &str, output_name: &str, out_dir: &Path)
         dst.display(),
         size as f64 / 1_048_576.0
     );
+
+    override_audit::audit_raw_spec(&dst, &raw_storage::chain_override_entries(spec_name))
+        .with_context(|| format!("Raw overrides missing from {}", dst.display()))?;
     Ok(())
 }
 
@@ -73,75 +106,68 @@ fn list_json_files(dir: &str) -> Vec<String> {
 
 #[tokio::test(flavor = "multi_thread")]
 async fn generate_chain_specs() {
-    env_logger::try_init().ok();
-    verify_binaries().expect("binary verification failed");
-
-    let out_dir = output_dir();
-    std::fs::create_dir_all(&out_dir).expect("failed to create output dir");
-
-    log::info!("Output directory: {}", out_dir.display());
-
-    // ── Polkadot (relay + Asset Hub + Collectives) ──────────────────────
-    log::info!("Spawning Polkadot network to generate chain specs...");
-    let polkadot_config = config::build_polkadot_with_system_parachains()
-        .expect("failed to build Polkadot network config");
-    let polkadot_network = initialize_network(polkadot_config)
-        .await
-        .expect("failed to spawn Polkadot network");
-
-    let base_dir = polkadot_network
-        .base_dir()
-        .expect("no base_dir from zombienet");
-    log::info!("Polkadot base_dir: {base_dir}");
-    log::info!("  Files: {:?}", list_json_files(base_dir));
-
-    save_spec(base_dir, "polkadot-local", "polkadot-local", &out_dir)
-        .expect("failed to save Polkadot relay spec");
-    save_spec(
-        base_dir,
-        "asset-hub-polkadot-local",
-        "asset-hub-polkadot-local",
-        &out_dir,
-    )
-    .expect("failed to save Asset Hub spec");
-    save_spec(
-        base_dir,
-        "collectives-polkadot-local",
-        "collectives-polkadot-local",
-        &out_dir,
-    )
-    .expect("failed to save Collectives spec");
-
-    // Drop Polkadot network before spawning Kusama
-    drop(polkadot_network);
-    log::info!("Polkadot network dropped.");
-
-    // ── Kusama (relay + Asset Hub) ──────────────────────────────────────
-    log::info!("Spawning Kusama network to generate chain specs...");
-    let kusama_config =
-        config::build_kusama_with_asset_hub().expect("failed to build Kusama network config");
-    let kusama_network = initialize_network(kusama_config)
-        .await
-        .expect("failed to spawn Kusama network");
-
-    let base_dir = kusama_network
-        .base_dir()
-        .expect("no base_dir from zombienet");
-    log::info!("Kusama base_dir: {base_dir}");
-    log::info!("  Files: {:?}", list_json_files(base_dir));
-
-    save_spec(base_dir, "kusama-local", "kusama-local", &out_dir)
-        .expect("failed to save Kusama relay spec");
-    save_spec(
-        base_dir,
-        "asset-hub-kusama-local",
-        "asset-hub-kusama-local",
-        &out_dir,
-    )
-    .expect("failed to save Kusama Asset Hub spec");
-
-    drop(kusama_network);
-    log::info!("Kusama network dropped.");
-
-    log::info!("All chain specs saved to {}", out_dir.display());
+    common::logging::suite("generate_chain_specs", async {
+        verify_binaries().expect("binary verification failed");
+
+        let out_dir = output_dir();
+        std::fs::create_dir_all(&out_dir).expect("failed to create output dir");
+
+        log::info!("Output directory: {}", out_dir.display());
+
+        let topologies = selected_topologies().expect("invalid topology selection");
+        config::check_extra_parachains().expect("invalid extra parachain selection");
+        let names: Vec<_> = topologies.iter().map(|t| t.name).collect();
+
+        // Each network gets its own port range, so all of them spawn at once.
+        log::info!("Spawning {} to generate chain specs...", names.join(", "));
+        let mut spawns = JoinSet::new();
+        for (index, topology) in topologies.iter().enumerate() {
+            let network_config = (topology.build)().unwrap_or_else(|e| {
+                panic!("failed to build {} network config: {e:#}", topology.name)
+            });
+            spawns.spawn(async move { (index, initialize_network(network_config).await) });
+        }
+        let mut networks = Vec::new();
+        while let Some(joined) = spawns.join_next().await {
+            let (index, network) = joined.expect("network spawn task panicked");
+            let topology = topologies[index];
+            let network = network
+                .unwrap_or_else(|e| panic!("failed to spawn {} network: {e:#}", topology.name));
+            networks.push((topology, network));
+        }
+
+        let mut jobs = Vec::new();
+        for (topology, network) in &networks {
+            let base_dir = network.base_dir().expect("no base_dir from zombienet");
+            log::info!("{} base_dir: {base_dir}", topology.name);
+            log::info!("  Files: {:?}", list_json_files(base_dir));
+            for (spec, label) in topology.generated_chain_specs() {
+                jobs.push((base_dir, spec, label));
+            }
+        }
+
+        // Raw specs are tens of MB each; copy them all in parallel.
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = jobs
+                .into_iter()
+                .map(|(base_dir, spec, label)| {
+                    let out_dir = &out_dir;
+                    let handle = scope.spawn(move || save_spec(base_dir, spec, spec, out_dir));
+                    (label, handle)
+                })
+                .collect();
+            for (label, handle) in handles {
+                handle
+                    .join()
+                    .expect("chain spec copy thread panicked")
+                    .unwrap_or_else(|e| panic!("failed to save {label} spec: {e:#}"));
+            }
+        });
+
+        drop(networks);
+        log::info!("Networks dropped.");
+
+        log::info!("All chain specs saved to {}", out_dir.display());
+    })
+    .await
 }
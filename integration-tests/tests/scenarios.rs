@@ -3,188 +3,506 @@
 //! The `validation_test_suite` requires NO network spawn — it exercises
 //! CLI argument validation that fails before any connection is attempted.
 //! All sub-tests run concurrently since they have no shared state.
+//!
+//! `port_range_conflict_test`, `storage_override_syntax_test` and
+//! `call_data_sanitization_test` likewise need no network: the tool checks
+//! `--port-range`, `--set-storage` and call data before forking anything.
+//! `tool_env_test` runs one such failing run with `ToolArgs::env` set.
+//! `tool_args_endpoint_checks_test` and `tool_args_hex_checks_test` don't run
+//! the tool at all: they check `ToolArgs::builder`'s own rejections.
 
-use anyhow::Result;
+use anyhow::{ensure, Result};
+use tokio::task::JoinSet;
+use tracing::Instrument;
 
-use crate::common::tool_runner::{report_results, SubTestResult, ToolArgs, ToolRunner};
+use crate::common::flag_registry::{self, ValidationCase};
+use crate::common::logging;
+use crate::common::tool_runner::{
+    report_results, ChainEndpoint, OutputFormat, SubTestResult, TimeoutClass, ToolArgs, ToolRunner,
+};
 
 // ── Validation Test Suite ───────────────────────────────────────────────────
 
 /// Suite: CLI argument validation tests — no network required.
 ///
-/// Each sub-test invokes `yarn cli test` with intentionally invalid or
-/// incomplete arguments and asserts that the tool fails with the expected
-/// error message. All sub-tests run concurrently.
+/// Sub-tests are generated from the shared flag registry
+/// (`src/flag-rules.json`), one per mutual-exclusion, at-least-one-of,
+/// dependency and integer rule. Each invokes `yarn cli test` with arguments
+/// that violate exactly that rule and asserts the tool fails with the rule's
+/// error code and message, and that `ToolArgs::builder`'s local checks reject
+/// them with the same code. One more sub-test per malformed `url,block` form
+/// checks the tool's endpoint parsing the same way, and one that a hex block is
+/// reported in decimal in the `@@result` line. All sub-tests run concurrently.
 #[tokio::test(flavor = "multi_thread")]
 async fn validation_test_suite() -> Result<()> {
-    let _ = env_logger::try_init();
-
-    log::info!("=== Validation Test Suite ===");
-
-    // Run all validation tests concurrently — they are completely independent
-    // (no shared ports, no network, no state).
-    let (r1, r2, r3, r4, r5, r6, r7) = tokio::join!(
-        run_no_args(),
-        run_mutually_exclusive_gov(),
-        run_mutually_exclusive_fellowship(),
-        run_missing_governance_url(),
-        run_missing_fellowship_url(),
-        run_invalid_referendum_id(),
-        run_invalid_fellowship_id(),
-    );
+    logging::suite("validation_test_suite", async {
+        log::info!("=== Validation Test Suite ===");
 
-    let results: Vec<SubTestResult> = vec![
-        ("no_args", r1),
-        ("mutually_exclusive_gov", r2),
-        ("mutually_exclusive_fellowship", r3),
-        ("missing_governance_url", r4),
-        ("missing_fellowship_url", r5),
-        ("invalid_referendum_id", r6),
-        ("invalid_fellowship_id", r7),
-    ];
-
-    log::info!("=== Validation Suite Results ===");
-    report_results(&results);
-    Ok(())
+        let cases = flag_registry::validation_cases()?;
+        log::info!(
+            "Generated {} validation case(s) from flag registry",
+            cases.len()
+        );
+
+        // Run all validation tests concurrently — they are completely independent
+        // (no shared ports, no network, no state).
+        let registry_cases = cases.len();
+        let mut set = JoinSet::new();
+        for (index, case) in cases.into_iter().enumerate() {
+            let span = logging::sub_test_span(&case.name);
+            set.spawn(logging::in_current_suite(
+                async move {
+                    let name = case.name.clone();
+                    (index, name, run_validation_case(case).await)
+                }
+                .instrument(span),
+            ));
+        }
+
+        for (offset, case) in endpoint_cases().into_iter().enumerate() {
+            let span = logging::sub_test_span(case.name);
+            set.spawn(logging::in_current_suite(
+                async move {
+                    let name = case.name.to_string();
+                    (registry_cases + offset, name, run_endpoint_case(case).await)
+                }
+                .instrument(span),
+            ));
+        }
+        let span = logging::sub_test_span("endpoint_hex_block_reported");
+        set.spawn(logging::in_current_suite(
+            async move {
+                let name = "endpoint_hex_block_reported".to_string();
+                (usize::MAX, name, run_endpoint_hex_block_reported().await)
+            }
+            .instrument(span),
+        ));
+
+        let mut indexed = Vec::new();
+        while let Some(joined) = set.join_next().await {
+            indexed.push(joined?);
+        }
+        indexed.sort_by_key(|(index, _, _)| *index);
+        let results: Vec<SubTestResult> = indexed
+            .into_iter()
+            .map(|(_, name, result)| (name, result))
+            .collect();
+
+        log::info!("=== Validation Suite Results ===");
+        report_results(&results);
+        Ok(())
+    })
+    .await
 }
 
-/// No arguments at all — should fail with "at least one referendum must be specified".
-async fn run_no_args() -> Result<()> {
-    log::info!("[no_args] Starting...");
-    let runner = ToolRunner::new();
-    let output = runner
-        .run_test_referendum(ToolArgs {
-            verbose: true,
-            ..Default::default()
-        })
-        .await?;
+// ── Endpoint Syntax ─────────────────────────────────────────────────────────
 
-    log::info!("[no_args] exit code: {}", output.exit_code);
-    output.check_failure()?;
-    output.check_any_output_contains("at least one referendum must be specified")?;
-    log::info!("[no_args] PASSED");
-    Ok(())
+/// A malformed `--governance-chain-url` and the message the tool must fail with.
+struct EndpointCase {
+    name: &'static str,
+    endpoint: String,
+    expected: String,
 }
 
-/// Both --referendum and --call-to-create-governance-referendum — mutually exclusive.
-async fn run_mutually_exclusive_gov() -> Result<()> {
-    log::info!("[mutually_exclusive_gov] Starting...");
-    let runner = ToolRunner::new();
-    let output = runner
-        .run_test_referendum(ToolArgs {
-            governance_chain_url: Some("ws://127.0.0.1:1,1".to_string()),
-            referendum: Some("0".to_string()),
-            call_to_create_governance_referendum: Some("0x00".to_string()),
-            verbose: true,
-            ..Default::default()
-        })
-        .await?;
+const UNREACHABLE_URL: &str = "ws://127.0.0.1:1";
 
-    log::info!("[mutually_exclusive_gov] exit code: {}", output.exit_code);
-    output.check_failure()?;
-    output.check_any_output_contains("cannot specify both")?;
-    log::info!("[mutually_exclusive_gov] PASSED");
-    Ok(())
+/// One case per way a `url,block` or labeled endpoint's block can be malformed.
+fn endpoint_cases() -> Vec<EndpointCase> {
+    let case = |name, block: &str, reason: &str| EndpointCase {
+        name,
+        endpoint: format!("{UNREACHABLE_URL},{block}"),
+        expected: format!(
+            "Invalid block number \"{block}\" in {UNREACHABLE_URL},{block}: {reason}"
+        ),
+    };
+    let not_a_number = "expected a decimal or 0x-prefixed hex number";
+    vec![
+        EndpointCase {
+            name: "endpoint_empty_block",
+            endpoint: format!("{UNREACHABLE_URL},"),
+            expected: format!("Missing block number in {UNREACHABLE_URL},"),
+        },
+        case(
+            "endpoint_negative_block",
+            "-5",
+            "block numbers cannot be negative",
+        ),
+        case("endpoint_trailing_garbage_block", "12abc", not_a_number),
+        case("endpoint_fractional_block", "1.5", not_a_number),
+        case("endpoint_bad_hex_block", "0xzz", not_a_number),
+        case(
+            "endpoint_block_too_large",
+            "4294967296",
+            "larger than the largest block number 4294967295",
+        ),
+        EndpointCase {
+            name: "endpoint_two_blocks",
+            endpoint: format!("{UNREACHABLE_URL},1,2"),
+            expected: format!("Invalid endpoint format: {UNREACHABLE_URL},1,2"),
+        },
+        EndpointCase {
+            name: "endpoint_labeled_bad_block",
+            endpoint: format!("name=AH;url={UNREACHABLE_URL};block=abc"),
+            expected: format!(
+                "Invalid block number \"abc\" in name=AH;url={UNREACHABLE_URL};block=abc: {not_a_number}"
+            ),
+        },
+    ]
 }
 
-/// Both --fellowship and --call-to-create-fellowship-referendum — mutually exclusive.
-async fn run_mutually_exclusive_fellowship() -> Result<()> {
-    log::info!("[mutually_exclusive_fellowship] Starting...");
-    let runner = ToolRunner::new();
-    let output = runner
-        .run_test_referendum(ToolArgs {
-            fellowship_chain_url: Some("ws://127.0.0.1:1,1".to_string()),
-            fellowship: Some("0".to_string()),
-            call_to_create_fellowship_referendum: Some("0x00".to_string()),
-            verbose: true,
-            ..Default::default()
-        })
+/// Expect `ToolArgs::set_flag` to reject the endpoint, then the tool to fail
+/// with `E-INVALID-ENDPOINT` and the case's message.
+async fn run_endpoint_case(case: EndpointCase) -> Result<()> {
+    let name = case.name;
+    log::info!("[{name}] Starting...");
+    ensure!(
+        ToolArgs::default()
+            .set_flag("--governance-chain-url", &case.endpoint)
+            .is_err(),
+        "Expected the local check to reject '{}'",
+        case.endpoint
+    );
+    // Passed raw: `set_flag` and `ChainEndpoint::new` reject the malformed block.
+    let mut args = ToolArgs::builder()
+        .referendum(0)
+        .verbose()
+        .build_unchecked();
+    args.set_raw_flag("--governance-chain-url", case.endpoint);
+    let output = ToolRunner::new()
+        .with_timeout_class(TimeoutClass::Validation)
+        .run_test_referendum(args)
         .await?;
 
-    log::info!(
-        "[mutually_exclusive_fellowship] exit code: {}",
-        output.exit_code
-    );
     output.check_failure()?;
-    output.check_any_output_contains("cannot specify both")?;
-    log::info!("[mutually_exclusive_fellowship] PASSED");
+    output.check_error_code("E-INVALID-ENDPOINT")?;
+    output.check_any_output_contains(&case.expected)?;
+    log::info!("[{name}] PASSED");
     Ok(())
 }
 
-/// --referendum without --governance-chain-url.
-async fn run_missing_governance_url() -> Result<()> {
-    log::info!("[missing_governance_url] Starting...");
-    let runner = ToolRunner::new();
-    let output = runner
-        .run_test_referendum(ToolArgs {
-            referendum: Some("0".to_string()),
-            verbose: true,
-            ..Default::default()
-        })
+/// A hex block is accepted and reported in decimal in the `@@result` line's
+/// `endpoints`, next to a URL without a block (`null`: latest finalized).
+async fn run_endpoint_hex_block_reported() -> Result<()> {
+    let mut args = ToolArgs::default();
+    args.set_flag("--governance-chain-url", &format!("{UNREACHABLE_URL},0xff"))?;
+    args.set_flag("--fellowship-chain-url", UNREACHABLE_URL)?;
+    ensure!(
+        args.governance_chain_url().and_then(ChainEndpoint::block) == Some(255),
+        "ToolArgs::set_flag should read 0xff as block 255"
+    );
+    // The `0xff` suffix is passed through as written.
+    let mut args = ToolArgs::builder()
+        .fellowship_chain_url(ChainEndpoint::new(UNREACHABLE_URL)?)
+        .referendum(0)
+        .output_format(OutputFormat::Json)
+        .verbose()
+        .build_unchecked();
+    args.set_raw_flag("--governance-chain-url", format!("{UNREACHABLE_URL},0xff"));
+    let output = ToolRunner::new()
+        .with_timeout_class(TimeoutClass::Validation)
+        .run_test_referendum(args)
         .await?;
 
-    log::info!("[missing_governance_url] exit code: {}", output.exit_code);
     output.check_failure()?;
-    output.check_any_output_contains("governance-chain-url is required")?;
-    log::info!("[missing_governance_url] PASSED");
+    let report: serde_json::Value = output.parse_json()?;
+    let endpoints = &report["endpoints"];
+    ensure!(
+        endpoints["governance"]["url"] == UNREACHABLE_URL
+            && endpoints["governance"]["block"] == 255
+            && endpoints["fellowship"]["block"].is_null(),
+        "unexpected endpoints in @@result: {endpoints}"
+    );
     Ok(())
 }
 
-/// --fellowship without --fellowship-chain-url.
-async fn run_missing_fellowship_url() -> Result<()> {
-    log::info!("[missing_fellowship_url] Starting...");
-    let runner = ToolRunner::new();
-    let output = runner
-        .run_test_referendum(ToolArgs {
-            fellowship: Some("0".to_string()),
-            verbose: true,
-            ..Default::default()
-        })
-        .await?;
+// ── Port Range Negotiation ──────────────────────────────────────────────────
 
-    log::info!("[missing_fellowship_url] exit code: {}", output.exit_code);
-    output.check_failure()?;
-    output.check_any_output_contains("fellowship-chain-url is required")?;
-    log::info!("[missing_fellowship_url] PASSED");
-    Ok(())
+/// The tool must refuse a `--port-range` that contains a port already in use,
+/// rather than letting two runs share a Chopsticks port.
+#[tokio::test(flavor = "multi_thread")]
+async fn port_range_conflict_test() -> Result<()> {
+    logging::suite("port_range_conflict_test", async {
+        let listener = std::net::TcpListener::bind("0.0.0.0:0")?;
+        let taken = listener.local_addr()?.port();
+        log::info!("Holding port {taken}");
+
+        let output = ToolRunner::new()
+            .with_timeout_class(TimeoutClass::Validation)
+            .run_test_referendum(
+                ToolArgs::builder()
+                    .governance_chain_url(ChainEndpoint::new("ws://127.0.0.1:1")?.at_block(1))
+                    .referendum(0)
+                    .port_range(format!("{taken}:1"))
+                    .verbose()
+                    .build()?,
+            )
+            .await?;
+        drop(listener);
+
+        output.check_failure()?;
+        output.check_error_code("E-PORT-UNAVAILABLE")?;
+        output.check_any_output_contains(&format!("Port {taken}"))?;
+        Ok(())
+    })
+    .await
 }
 
-/// --referendum abc — non-numeric ID.
-async fn run_invalid_referendum_id() -> Result<()> {
-    log::info!("[invalid_referendum_id] Starting...");
-    let runner = ToolRunner::new();
-    let output = runner
-        .run_test_referendum(ToolArgs {
-            governance_chain_url: Some("ws://127.0.0.1:1,1".to_string()),
-            referendum: Some("abc".to_string()),
-            verbose: true,
-            ..Default::default()
-        })
-        .await?;
+// ── Storage Overrides ───────────────────────────────────────────────────────
 
-    log::info!("[invalid_referendum_id] exit code: {}", output.exit_code);
-    output.check_failure()?;
-    output.check_any_output_contains("invalid referendum id")?;
-    log::info!("[invalid_referendum_id] PASSED");
+/// A malformed `--set-storage`, `--fund` or `--override-parameter` value must
+/// fail before forking, with its own error code.
+#[tokio::test(flavor = "multi_thread")]
+async fn storage_override_syntax_test() -> Result<()> {
+    logging::suite("storage_override_syntax_test", async {
+        let unreachable = ChainEndpoint::new("ws://127.0.0.1:1")?.at_block(1);
+        let base = || {
+            ToolArgs::builder()
+                .governance_chain_url(unreachable.clone())
+                .referendum(0)
+                .verbose()
+        };
+        // Built unchecked: `ToolArgs::builder` would reject the `--set-storage` value itself.
+        let cases = [
+            (
+                base()
+                    .set_storage("AhMigrator.AhMigrationStage")
+                    .build_unchecked(),
+                "Invalid --set-storage value: AhMigrator.AhMigrationStage",
+            ),
+            (
+                base().fund("alice", 100).build_unchecked(),
+                "Invalid --fund value: alice=100",
+            ),
+            (
+                base()
+                    .override_parameter("BurnPortion", "0")
+                    .build_unchecked(),
+                "Invalid --override-parameter value: BurnPortion=0",
+            ),
+        ];
+        for (args, expected) in cases {
+            let output = ToolRunner::new()
+                .with_timeout_class(TimeoutClass::Validation)
+                .run_test_referendum(args)
+                .await?;
+            output.check_failure()?;
+            output.check_error_code("E-INVALID-STORAGE-OVERRIDE")?;
+            output.check_any_output_contains(expected)?;
+        }
+        Ok(())
+    })
+    .await
+}
+
+// ── Call Data Sanitization ──────────────────────────────────────────────────
+
+/// Call data pasted from PJS Apps or a terminal (surrounding whitespace, line
+/// breaks, no `0x`, upper-case digits) is normalized before it is checked;
+/// only what is still not hex fails, with `E-INVALID-HEX` naming the flag.
+/// `gov_sloppy_call_data` runs such inputs end to end.
+#[tokio::test(flavor = "multi_thread")]
+async fn call_data_sanitization_test() -> Result<()> {
+    logging::suite("call_data_sanitization_test", async {
+        let unreachable = ChainEndpoint::new("ws://127.0.0.1:1")?.at_block(1);
+        let args = |submit: &str, preimage: Option<&str>| {
+            let builder = ToolArgs::builder()
+                .governance_chain_url(unreachable.clone())
+                .call_to_create_governance_referendum(submit)
+                .verbose();
+            match preimage {
+                Some(preimage) => builder.call_to_note_preimage_for_governance_referendum(preimage),
+                None => builder,
+            }
+            .build_unchecked()
+        };
+        let runner = ToolRunner::new().with_timeout_class(TimeoutClass::Validation);
+
+        // Accepted, by `ToolArgs::builder` as by the tool: the run gets past the
+        // hex check and fails on the endpoint.
+        let accepted = [
+            ("  0x3E00ab\n", None),
+            ("3E00AB", Some("\t0X0500\r\n")),
+            ("0x3e00\nab\n", Some("05 00")),
+        ];
+        for (submit, preimage) in accepted {
+            ToolArgs::builder()
+                .governance_chain_url(unreachable.clone())
+                .call_to_create_governance_referendum(submit)
+                .build()?;
+            let output = runner.run_test_referendum(args(submit, preimage)).await?;
+            output.check_failure()?;
+            ensure!(
+                output.error_code() != Some("E-INVALID-HEX"),
+                "{submit:?} / {preimage:?} should be normalized, got:\n{}",
+                output.stderr
+            );
+        }
+
+        let rejected = [
+            ("0x3e00zz", None, "--call-to-create-governance-referendum"),
+            (
+                "0x3e00",
+                Some("0x05-00"),
+                "--call-to-note-preimage-for-governance-referendum",
+            ),
+        ];
+        for (submit, preimage, flag) in rejected {
+            let output = runner.run_test_referendum(args(submit, preimage)).await?;
+            output.check_failure()?;
+            output.check_error_code("E-INVALID-HEX")?;
+            output.check_any_output_contains(&format!("Invalid hex string for {flag}"))?;
+        }
+        Ok(())
+    })
+    .await
+}
+
+// ── Local Argument Checks ───────────────────────────────────────────────────
+
+/// `ChainEndpoint::new` and `ToolArgs::builder` reject endpoints that are not
+/// `ws://` or `wss://` URLs with a host, and labels that would split them.
+#[test]
+fn tool_args_endpoint_checks_test() -> Result<()> {
+    for url in [
+        "http://127.0.0.1:9944",
+        "127.0.0.1:9944",
+        "ws://",
+        "wss://",
+        "ws://host name",
+        "",
+    ] {
+        ensure!(
+            ChainEndpoint::new(url).is_err(),
+            "{url:?} should be rejected"
+        );
+        ensure!(
+            ToolArgs::default()
+                .set_flag("--governance-chain-url", url)
+                .is_err(),
+            "set_flag should reject {url:?}"
+        );
+    }
+    for label in ["", "Asset;Hub", "Asset,Hub"] {
+        let built = ToolArgs::builder()
+            .governance_chain_url(ChainEndpoint::new(UNREACHABLE_URL)?.labeled(label))
+            .referendum(0)
+            .build();
+        ensure!(built.is_err(), "builder should reject label {label:?}");
+    }
+    ToolArgs::builder()
+        .governance_chain_url(ChainEndpoint::new("wss://rpc.example.org")?.labeled("AssetHub"))
+        .referendum(0)
+        .build()?;
     Ok(())
 }
 
-/// --fellowship xyz — non-numeric ID.
-async fn run_invalid_fellowship_id() -> Result<()> {
-    log::info!("[invalid_fellowship_id] Starting...");
-    let runner = ToolRunner::new();
-    let output = runner
-        .run_test_referendum(ToolArgs {
-            fellowship_chain_url: Some("ws://127.0.0.1:1,1".to_string()),
-            fellowship: Some("xyz".to_string()),
-            verbose: true,
-            ..Default::default()
-        })
-        .await?;
+/// `ToolArgs::builder` rejects call data that is not hex-encoded bytes, and
+/// accepts it as loosely formatted as the tool does.
+#[test]
+fn tool_args_hex_checks_test() -> Result<()> {
+    let unreachable = ChainEndpoint::new(UNREACHABLE_URL)?;
+    let build = |submit: &str, preimage: Option<&str>| {
+        let builder = ToolArgs::builder()
+            .governance_chain_url(unreachable.clone())
+            .call_to_create_governance_referendum(submit);
+        match preimage {
+            Some(preimage) => builder.call_to_note_preimage_for_governance_referendum(preimage),
+            None => builder,
+        }
+        .build()
+    };
+    for submit in ["", "0x", "0x3e0", "0x3e00zz", "3e 0", "0x0x3e00"] {
+        let built = build(submit, None);
+        ensure!(
+            built.as_ref().is_err_and(|e| e
+                .to_string()
+                .starts_with("--call-to-create-governance-referendum: ")),
+            "{submit:?} should be rejected naming the flag, got {:?}",
+            built.err()
+        );
+    }
+    let built = build("0x3e00", Some("0x05-00"));
+    ensure!(
+        built.as_ref().is_err_and(|e| e
+            .to_string()
+            .starts_with("--call-to-note-preimage-for-governance-referendum: ")),
+        "the preimage call should be rejected, got {:?}",
+        built.err()
+    );
+    for (submit, preimage) in [("  0x3E00ab\n", None), ("3E00AB", Some("\t0X0500\r\n"))] {
+        build(submit, preimage)?;
+    }
+    Ok(())
+}
+
+// ── Tool Environment ────────────────────────────────────────────────────────
+
+/// `ToolArgs::env` reaches the tool process: with `FORCE_COLOR=1` the tool
+/// colors its output even though it is piped, and `ToolOutput` strips the
+/// escapes only from `stdout`/`stderr`, not the raw streams.
+#[tokio::test(flavor = "multi_thread")]
+async fn tool_env_test() -> Result<()> {
+    logging::suite("tool_env_test", async {
+        let unreachable = ChainEndpoint::new(UNREACHABLE_URL)?.at_block(1);
+        let built = ToolArgs::builder()
+            .governance_chain_url(unreachable.clone())
+            .referendum(0)
+            .env("FORCE=COLOR", "1")
+            .build();
+        ensure!(
+            built
+                .as_ref()
+                .is_err_and(|e| e.to_string().starts_with("env: 'FORCE=COLOR'")),
+            "an env key containing '=' should be rejected, got {:?}",
+            built.err()
+        );
+
+        let args = ToolArgs::builder()
+            .governance_chain_url(unreachable)
+            .referendum(0)
+            .env("FORCE_COLOR", "1")
+            .build()?;
+        let output = ToolRunner::new()
+            .with_timeout_class(TimeoutClass::Validation)
+            .run_test_referendum(args)
+            .await?;
+        output.check_failure()?;
+        let raw = format!("{}{}", output.raw_stdout, output.raw_stderr);
+        ensure!(
+            raw.contains("\x1b["),
+            "FORCE_COLOR=1 should color the tool's output, got:\n{raw}"
+        );
+        ensure!(
+            !output.stdout.contains('\x1b') && !output.stderr.contains('\x1b'),
+            "ANSI escapes should be stripped from stdout and stderr"
+        );
+        Ok(())
+    })
+    .await
+}
+
+/// Invoke the tool with the case's arguments and expect the rule's error code
+/// and message.
+async fn run_validation_case(case: ValidationCase) -> Result<()> {
+    let name = case.name;
+    log::info!("[{name}] Starting...");
+    let local = flag_registry::check(&case.args);
+    ensure!(
+        local
+            .as_ref()
+            .is_err_and(|e| e.to_string().starts_with(&format!("{}: ", case.code))),
+        "Expected local check to fail with {}, got {local:?}",
+        case.code
+    );
+    let runner = ToolRunner::new().with_timeout_class(TimeoutClass::Validation);
+    let mut args = case.args;
+    args.set_flag("--verbose", "")?;
+    let output = runner.run_test_referendum(args).await?;
 
-    log::info!("[invalid_fellowship_id] exit code: {}", output.exit_code);
+    log::info!("[{name}] exit code: {}", output.exit_code);
     output.check_failure()?;
-    output.check_any_output_contains("invalid fellowship referendum id")?;
-    log::info!("[invalid_fellowship_id] PASSED");
+    output.check_error_code(&case.code)?;
+    output.check_any_output_contains(&case.expected)?;
+    log::info!("[{name}] PASSED");
     Ok(())
 }
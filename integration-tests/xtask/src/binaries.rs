@@ -0,0 +1,134 @@
+//! Download Polkadot SDK binaries (Linux x86_64 and macOS ARM64).
+
+use anyhow::{bail, Context, Result};
+use std::path::Path;
+use std::process::Command;
+
+use crate::paths;
+use crate::util;
+
+const DEFAULT_SDK_VERSION: &str = "polkadot-stable2512";
+
+const BINARIES: &[&str] = &[
+    "polkadot",
+    "polkadot-prepare-worker",
+    "polkadot-execute-worker",
+    "polkadot-parachain",
+    "chain-spec-builder",
+];
+
+/// Release asset suffix for the current platform.
+fn platform_suffix() -> Result<&'static str> {
+    match (std::env::consts::OS, std::env::consts::ARCH) {
+        ("linux", "x86_64") => Ok(""),
+        ("macos", "aarch64") => Ok("-aarch64-apple-darwin"),
+        ("linux", arch) => bail!("Only x86_64 Linux is supported, got {arch}"),
+        ("macos", arch) => bail!("Only ARM64 macOS is supported, got {arch}"),
+        (os, _) => bail!("Unsupported OS: {os}"),
+    }
+}
+
+pub fn download(sdk_version: Option<&str>) -> Result<()> {
+    let version = sdk_version
+        .map(str::to_string)
+        .or_else(|| std::env::var("POLKADOT_SDK_VERSION").ok())
+        .unwrap_or_else(|| DEFAULT_SDK_VERSION.to_string());
+    let bin_dir = paths::bin_dir();
+    let base_url =
+        format!("https://github.com/paritytech/polkadot-sdk/releases/download/{version}");
+
+    println!("Polkadot SDK Binary Downloader");
+    println!("  Version: {version}");
+    println!("  Target:  {}", bin_dir.display());
+    println!();
+
+    let suffix = platform_suffix()?;
+    std::fs::create_dir_all(&bin_dir)?;
+
+    println!("Downloading binaries...");
+    for name in BINARIES {
+        download_binary(&base_url, &bin_dir, name, suffix)?;
+    }
+    println!();
+
+    println!("Verifying binaries...");
+    for name in BINARIES {
+        let path = bin_dir.join(name);
+        let version = util::capture(Command::new(&path).arg("--version"))
+            .with_context(|| format!("{name} --version failed"))?;
+        println!(
+            "  [ok] {name}: {}",
+            version.lines().next().unwrap_or_default()
+        );
+    }
+    println!();
+
+    println!("All binaries ready in {}", bin_dir.display());
+    Ok(())
+}
+
+fn download_binary(base_url: &str, bin_dir: &Path, name: &str, suffix: &str) -> Result<()> {
+    let local_path = bin_dir.join(name);
+    if local_path.exists() {
+        println!("  [skip] {name} already exists");
+        return Ok(());
+    }
+
+    let url = format!("{base_url}/{name}{suffix}");
+    println!("  [download] {name} from {url}");
+    util::run(
+        Command::new("curl")
+            .args(["-fSL", "--retry", "3", "--retry-delay", "5", "-o"])
+            .arg(&local_path)
+            .arg(&url),
+    )?;
+    set_executable(&local_path)?;
+
+    // Verify the SHA256 checksum when the release publishes one.
+    let sha_url = format!("{url}.sha256");
+    let expected =
+        match util::capture(Command::new("curl").args(["-fsSL", "--retry", "2", &sha_url])) {
+            Ok(body) => body
+                .split_whitespace()
+                .next()
+                .unwrap_or_default()
+                .to_string(),
+            Err(_) => {
+                println!("  [info] No SHA256 file available, skipping checksum verification");
+                return Ok(());
+            }
+        };
+
+    let actual = sha256_of(&local_path)?;
+    if expected != actual {
+        std::fs::remove_file(&local_path)?;
+        bail!("SHA256 mismatch for {name}!\n    expected: {expected}\n    actual:   {actual}");
+    }
+    println!("  [ok] SHA256 verified: {actual}");
+    Ok(())
+}
+
+fn sha256_of(path: &Path) -> Result<String> {
+    let output = if util::command_exists("sha256sum") {
+        util::capture(Command::new("sha256sum").arg(path))?
+    } else {
+        util::capture(Command::new("shasum").args(["-a", "256"]).arg(path))?
+    };
+    Ok(output
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .to_string())
+}
+
+#[cfg(unix)]
+fn set_executable(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755))?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn set_executable(_path: &Path) -> Result<()> {
+    Ok(())
+}
@@ -0,0 +1,73 @@
+//! Regenerate cached raw chain specs via the `generate_chain_specs` test binary.
+//!
+//! `cargo xtask chain-specs [TOPOLOGY...] [--extra-parachains LIST]` passes the
+//! selection on as `CHAIN_SPEC_TOPOLOGIES` / `EXTRA_PARACHAINS`; without
+//! arguments the test binary's defaults (Polkadot and Kusama) apply.
+
+use anyhow::{bail, Result};
+use std::process::Command;
+
+use crate::paths;
+use crate::util;
+
+pub fn generate(args: &[String]) -> Result<()> {
+    let bin_dir = paths::bin_dir();
+    let chain_specs_dir = paths::chain_specs_dir();
+    let extra_parachains = util::take_option(args, "--extra-parachains")?;
+    let mut topologies = Vec::new();
+    let mut rest = args.iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--extra-parachains" => {
+                rest.next();
+            }
+            other if other.starts_with("--") => bail!("Unknown chain-specs option: {other}"),
+            topology => topologies.push(topology),
+        }
+    }
+
+    println!("Chain Spec Generator (zombienet-based)");
+    println!("  Output:   {}", chain_specs_dir.display());
+    println!("  Binaries: {}", bin_dir.display());
+    println!();
+
+    for name in ["polkadot", "polkadot-parachain"] {
+        if !bin_dir.join(name).is_file() {
+            bail!(
+                "{name} not found at {}\nRun `cargo xtask download-binaries` first.",
+                bin_dir.join(name).display()
+            );
+        }
+    }
+
+    std::fs::create_dir_all(&chain_specs_dir)?;
+
+    println!("Running zombienet chain spec generator...");
+    let mut cmd = Command::new("cargo");
+    cmd.current_dir(paths::integration_tests_dir())
+        .args([
+            "test",
+            "--test",
+            "generate_chain_specs",
+            "--",
+            "--nocapture",
+        ])
+        .env("POLKADOT_BINARY_PATH", bin_dir.join("polkadot"))
+        .env(
+            "POLKADOT_PARACHAIN_BINARY_PATH",
+            bin_dir.join("polkadot-parachain"),
+        )
+        .env("CHAIN_SPECS_DIR", &chain_specs_dir)
+        .env("RUST_LOG", "info");
+    if !topologies.is_empty() {
+        cmd.env("CHAIN_SPEC_TOPOLOGIES", topologies.join(","));
+    }
+    if let Some(extra_parachains) = extra_parachains {
+        cmd.env("EXTRA_PARACHAINS", extra_parachains);
+    }
+    util::run(&mut cmd)?;
+
+    println!();
+    println!("Chain specs generated:");
+    util::list_files_with_extension(&chain_specs_dir, "json")
+}
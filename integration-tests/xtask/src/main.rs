@@ -0,0 +1,120 @@
+//! Workspace automation for the integration tests.
+//!
+//! Replaces the former `integration-tests/scripts/*.sh` helpers so the Rust
+//! workspace can bootstrap itself:
+//!
+//! ```text
+//! cargo xtask download-binaries [--sdk polkadot-stable2512]
+//! cargo xtask build-runtimes    [--fellows v2.0.7] [--force]
+//! cargo xtask chain-specs       [TOPOLOGY...] [--extra-parachains LIST]
+//! cargo xtask update-runtimes   [--fellows VERSION] [--sdk VERSION]
+//! cargo xtask test              [SUITE...]
+//! cargo xtask spawn-net         [polkadot|polkadot-ah|kusama]
+//! ```
+//!
+//! All paths default to the same locations the scripts used and can still be
+//! overridden with the same environment variables (`BIN_DIR`, `RUNTIMES_DIR`,
+//! `CACHE_DIR`, `CHAIN_SPECS_DIR`, `FELLOWS_VERSION`, `POLKADOT_SDK_VERSION`).
+
+mod binaries;
+mod chain_specs;
+mod paths;
+mod runtimes;
+mod suites;
+mod util;
+
+use anyhow::{bail, Result};
+
+const USAGE: &str = "\
+Usage: cargo xtask <COMMAND> [OPTIONS]
+
+Commands:
+  download-binaries [--sdk VERSION]              Download Polkadot SDK binaries into bin/
+  build-runtimes [--fellows VERSION] [--force]   Build fast-runtime WASMs from polkadot-fellows/runtimes
+  chain-specs [TOPOLOGY...] [--extra-parachains LIST]
+                                                 Regenerate raw chain specs via zombienet
+                                                 (topologies: polkadot, kusama, westend; default polkadot kusama)
+  update-runtimes [--fellows VERSION] [--sdk VERSION]
+                                                 Rebuild runtimes, (re)download binaries, regenerate chain specs
+  test [SUITE...]                                Run integration test suites with the right env vars
+                                                 (default: all suites; `validation` runs the network-free suite)
+  spawn-net [polkadot|polkadot-ah|kusama]        Spawn a local governance network and print its endpoints
+";
+
+fn main() -> Result<()> {
+    let mut args = std::env::args().skip(1);
+    let Some(command) = args.next() else {
+        eprint!("{USAGE}");
+        std::process::exit(1);
+    };
+    let rest: Vec<String> = args.collect();
+
+    match command.as_str() {
+        "download-binaries" => {
+            let sdk = util::take_option(&rest, "--sdk")?;
+            binaries::download(sdk.as_deref())
+        }
+        "build-runtimes" => {
+            let fellows = util::take_option(&rest, "--fellows")?;
+            let force = rest.iter().any(|a| a == "--force");
+            runtimes::build(fellows.as_deref(), force)
+        }
+        "chain-specs" => chain_specs::generate(&rest),
+        "update-runtimes" => {
+            let fellows = util::take_option(&rest, "--fellows")?;
+            let sdk = util::take_option(&rest, "--sdk")?;
+            update_runtimes(fellows.as_deref(), sdk.as_deref())
+        }
+        "test" => suites::run(&rest),
+        "spawn-net" => suites::spawn_net(&rest),
+        "-h" | "--help" | "help" => {
+            print!("{USAGE}");
+            Ok(())
+        }
+        other => {
+            eprint!("{USAGE}");
+            bail!("Unknown command: {other}")
+        }
+    }
+}
+
+/// Full refresh pipeline for a new Fellows (and optionally SDK) release.
+fn update_runtimes(fellows: Option<&str>, sdk: Option<&str>) -> Result<()> {
+    println!("=== Runtime & Chain Spec Updater ===");
+    println!();
+    println!("  Project:  {}", paths::project_dir().display());
+    println!("  Fellows:  {}", fellows.unwrap_or("(current default)"));
+    println!("  SDK:      {}", sdk.unwrap_or("(no change)"));
+    println!();
+
+    println!("=== Step 1/3: Build fast-runtime WASMs ===");
+    runtimes::build(fellows, true)?;
+    println!();
+
+    println!("=== Step 2/3: Download Polkadot SDK binaries ===");
+    if let Some(sdk) = sdk {
+        let bin_dir = paths::bin_dir();
+        if bin_dir.is_dir() {
+            println!("Removing existing binaries for SDK version change...");
+            std::fs::remove_dir_all(&bin_dir)?;
+        }
+        binaries::download(Some(sdk))?;
+    } else {
+        println!("No --sdk specified, skipping binary download.");
+        println!("Using existing binaries in {}", paths::bin_dir().display());
+    }
+    println!();
+
+    println!("=== Step 3/3: Regenerate chain specs ===");
+    util::remove_files_with_extension(&paths::chain_specs_dir(), "json")?;
+    chain_specs::generate(&[])?;
+    println!();
+
+    println!("=== Update Complete ===");
+    println!();
+    println!("Next steps:");
+    println!("  1. Review the changes: git diff --stat");
+    println!("  2. Run integration tests locally to verify");
+    println!("  3. Commit the updated files");
+    Ok(())
+}
@@ -0,0 +1,48 @@
+//! Default locations, each overridable with the env var the old scripts used.
+
+use std::path::PathBuf;
+
+/// `integration-tests/` (the workspace root).
+pub fn integration_tests_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("xtask lives inside integration-tests/")
+        .to_path_buf()
+}
+
+/// Repository root (contains package.json and the TypeScript CLI).
+pub fn project_dir() -> PathBuf {
+    integration_tests_dir()
+        .parent()
+        .expect("integration-tests lives inside the project root")
+        .to_path_buf()
+}
+
+fn env_path_or(var: &str, default: PathBuf) -> PathBuf {
+    std::env::var(var).map(PathBuf::from).unwrap_or(default)
+}
+
+pub fn bin_dir() -> PathBuf {
+    env_path_or("BIN_DIR", project_dir().join("bin"))
+}
+
+pub fn runtimes_dir() -> PathBuf {
+    env_path_or(
+        "RUNTIMES_DIR",
+        integration_tests_dir().join("runtimes").join("fast"),
+    )
+}
+
+pub fn cache_dir() -> PathBuf {
+    env_path_or(
+        "CACHE_DIR",
+        project_dir().join(".cache").join("fellows-runtimes"),
+    )
+}
+
+pub fn chain_specs_dir() -> PathBuf {
+    env_path_or(
+        "CHAIN_SPECS_DIR",
+        integration_tests_dir().join("chain-specs"),
+    )
+}
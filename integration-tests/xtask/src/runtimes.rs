@@ -0,0 +1,189 @@
+//! Build Polkadot Fellows runtimes with the `fast-runtime` feature.
+//!
+//! Fast-runtime reduces session lengths from 4 hours to ~60 seconds, which is
+//! essential for integration tests where parachains need a session change
+//! before they start producing blocks.
+
+use anyhow::{bail, Result};
+use std::path::Path;
+use std::process::Command;
+
+use crate::paths;
+use crate::util;
+
+const DEFAULT_FELLOWS_VERSION: &str = "v2.0.7";
+const REPO_URL: &str = "https://github.com/polkadot-fellows/runtimes.git";
+
+/// A runtime to build: (cargo package, fast-runtime supported, output WASM filename).
+struct RuntimeBuild {
+    package: &'static str,
+    fast_runtime: bool,
+    wasm: &'static str,
+}
+
+/// Runtimes used by the suites. Collectives and Kusama Asset Hub have no
+/// fast-runtime feature at v2.0.7 and are built standard.
+const RUNTIMES: &[RuntimeBuild] = &[
+    RuntimeBuild {
+        package: "polkadot-runtime",
+        fast_runtime: true,
+        wasm: "polkadot_runtime.compact.compressed.wasm",
+    },
+    RuntimeBuild {
+        package: "asset-hub-polkadot-runtime",
+        fast_runtime: true,
+        wasm: "asset_hub_polkadot_runtime.compact.compressed.wasm",
+    },
+    RuntimeBuild {
+        package: "collectives-polkadot-runtime",
+        fast_runtime: false,
+        wasm: "collectives_polkadot_runtime.compact.compressed.wasm",
+    },
+    RuntimeBuild {
+        package: "staging-kusama-runtime",
+        fast_runtime: true,
+        wasm: "staging_kusama_runtime.compact.compressed.wasm",
+    },
+    RuntimeBuild {
+        package: "asset-hub-kusama-runtime",
+        fast_runtime: false,
+        wasm: "asset_hub_kusama_runtime.compact.compressed.wasm",
+    },
+];
+
+pub fn build(fellows_version: Option<&str>, force: bool) -> Result<()> {
+    let version = fellows_version
+        .map(str::to_string)
+        .or_else(|| std::env::var("FELLOWS_VERSION").ok())
+        .unwrap_or_else(|| DEFAULT_FELLOWS_VERSION.to_string());
+    let runtimes_dir = paths::runtimes_dir();
+    let cache_dir = paths::cache_dir();
+
+    println!("Fellows Runtime Builder (fast-runtime)");
+    println!("  Version: {version}");
+    println!("  Output:  {}", runtimes_dir.display());
+    println!("  Cache:   {}", cache_dir.display());
+    println!();
+
+    if force {
+        util::remove_files_with_extension(&runtimes_dir, "wasm")?;
+    } else if RUNTIMES.iter().all(|r| runtimes_dir.join(r.wasm).is_file()) {
+        println!(
+            "All WASM files already exist in {}.",
+            runtimes_dir.display()
+        );
+        println!("  Pass --force (or delete them) to rebuild.");
+        return util::list_files_with_extension(&runtimes_dir, "wasm");
+    }
+
+    check_prerequisites()?;
+    let repo_dir = cache_dir.join(&version);
+    clone_or_update(&repo_dir, &version)?;
+
+    println!("Building runtimes with fast-runtime feature...");
+    println!("  This may take 10-30 minutes on first build.");
+    for (i, runtime) in RUNTIMES.iter().enumerate() {
+        let mode = if runtime.fast_runtime {
+            "fast-runtime"
+        } else {
+            "standard"
+        };
+        println!(
+            "[{}/{}] Building {} ({mode})...",
+            i + 1,
+            RUNTIMES.len(),
+            runtime.package
+        );
+        let mut cmd = Command::new("cargo");
+        cmd.current_dir(&repo_dir)
+            .args(["build", "--release", "-p", runtime.package]);
+        if runtime.fast_runtime {
+            cmd.args(["--features", "fast-runtime"]);
+        }
+        util::run(&mut cmd)?;
+    }
+    println!();
+
+    copy_wasm_files(&repo_dir, &runtimes_dir)?;
+    println!("All runtimes built successfully!");
+    util::list_files_with_extension(&runtimes_dir, "wasm")
+}
+
+fn check_prerequisites() -> Result<()> {
+    println!("Checking prerequisites...");
+    if !util::command_exists("cargo") {
+        bail!("cargo not found. Install Rust: https://rustup.rs");
+    }
+    let installed = util::capture(Command::new("rustup").args(["target", "list", "--installed"]))?;
+    if !installed.lines().any(|t| t == "wasm32-unknown-unknown") {
+        println!("Adding wasm32-unknown-unknown target...");
+        util::run(Command::new("rustup").args(["target", "add", "wasm32-unknown-unknown"]))?;
+    }
+    println!(
+        "  Rust: {}",
+        util::capture(Command::new("rustc").arg("--version"))?
+    );
+    println!();
+    Ok(())
+}
+
+fn clone_or_update(repo_dir: &Path, version: &str) -> Result<()> {
+    if repo_dir.join(".git").is_dir() {
+        println!("Using cached clone at {}", repo_dir.display());
+        let current_tag = util::capture(Command::new("git").current_dir(repo_dir).args([
+            "describe",
+            "--tags",
+            "--exact-match",
+        ]))
+        .unwrap_or_default();
+        if current_tag != version {
+            println!("  Checking out tag {version}...");
+            util::run(
+                Command::new("git")
+                    .current_dir(repo_dir)
+                    .args(["fetch", "--tags"]),
+            )?;
+            util::run(
+                Command::new("git")
+                    .current_dir(repo_dir)
+                    .args(["checkout", version]),
+            )?;
+        }
+    } else {
+        println!("Cloning {REPO_URL} at tag {version}...");
+        if let Some(parent) = repo_dir.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        util::run(
+            Command::new("git")
+                .args(["clone", "--depth", "1", "--branch", version, REPO_URL])
+                .arg(repo_dir),
+        )?;
+    }
+    let commit = util::capture(Command::new("git").current_dir(repo_dir).args([
+        "rev-parse",
+        "--short",
+        "HEAD",
+    ]))?;
+    println!("  At commit: {commit}");
+    println!();
+    Ok(())
+}
+
+fn copy_wasm_files(repo_dir: &Path, runtimes_dir: &Path) -> Result<()> {
+    let wbuild_dir = repo_dir.join("target").join("release").join("wbuild");
+    println!("Copying WASM files to {}...", runtimes_dir.display());
+    std::fs::create_dir_all(runtimes_dir)?;
+
+    for runtime in RUNTIMES {
+        let src = wbuild_dir.join(runtime.package).join(runtime.wasm);
+        if !src.is_file() {
+            bail!(
+                "Expected WASM not found: {}\n  Build may have failed. Check cargo output above.",
+                src.display()
+            );
+        }
+        std::fs::copy(&src, runtimes_dir.join(runtime.wasm))?;
+    }
+    Ok(())
+}
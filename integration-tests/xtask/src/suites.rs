@@ -0,0 +1,109 @@
+//! Run integration test suites with the env vars they expect.
+
+use anyhow::{bail, Result};
+use std::process::Command;
+
+use crate::paths;
+use crate::util;
+
+/// `cargo test --test tests` in the integration-tests crate.
+fn test_command() -> Command {
+    let mut cmd = Command::new("cargo");
+    cmd.current_dir(paths::integration_tests_dir())
+        .args(["test", "--test", "tests"]);
+    cmd
+}
+
+/// Every test in the `tests` binary by its module-qualified name (e.g.
+/// `all_tracks::polkadot_governance_all_tracks`), as libtest lists them, so
+/// suites added to the binary need no entry here.
+fn list_suites() -> Result<Vec<String>> {
+    let mut cmd = test_command();
+    cmd.args(["--", "--list", "--format", "terse"]);
+    let listed = util::capture(&mut cmd)?;
+    let suites: Vec<String> = listed
+        .lines()
+        .filter_map(|line| line.strip_suffix(": test"))
+        .map(str::to_string)
+        .collect();
+    if suites.is_empty() {
+        bail!("`cargo test --test tests -- --list` listed no tests");
+    }
+    Ok(suites)
+}
+
+/// The qualified name of suite `name`, given bare (`governance_all_tracks`)
+/// or qualified. `validation` is an alias for the network-free CLI
+/// validation suite.
+fn resolve(suites: &[String], name: &str) -> Result<String> {
+    let name = if name == "validation" {
+        "validation_test_suite"
+    } else {
+        name
+    };
+    let suffix = format!("::{name}");
+    match suites
+        .iter()
+        .find(|s| s.as_str() == name || s.ends_with(&suffix))
+    {
+        Some(suite) => Ok(suite.clone()),
+        None => bail!("Unknown suite '{name}'. Available: {}", suites.join(", ")),
+    }
+}
+
+/// Point `cmd` at the tool, cached chain specs, runtimes and binaries, the
+/// way the integration tests expect.
+pub fn apply_env(cmd: &mut Command) {
+    let bin_dir = paths::bin_dir();
+    cmd.env("TOOL_PROJECT_DIR", paths::project_dir())
+        .env("CHAIN_SPECS_DIR", paths::chain_specs_dir())
+        .env("FAST_RUNTIMES_DIR", paths::runtimes_dir());
+    // Respect explicit binary overrides; otherwise point at bin/.
+    if std::env::var_os("POLKADOT_BINARY_PATH").is_none() {
+        cmd.env("POLKADOT_BINARY_PATH", bin_dir.join("polkadot"));
+    }
+    if std::env::var_os("POLKADOT_PARACHAIN_BINARY_PATH").is_none() {
+        cmd.env(
+            "POLKADOT_PARACHAIN_BINARY_PATH",
+            bin_dir.join("polkadot-parachain"),
+        );
+    }
+    if std::env::var_os("RUST_LOG").is_none() {
+        cmd.env("RUST_LOG", "info");
+    }
+}
+
+pub fn run(names: &[String]) -> Result<()> {
+    let available = list_suites()?;
+    let suites: Vec<String> = if names.is_empty() {
+        available
+    } else {
+        names
+            .iter()
+            .map(|n| resolve(&available, n))
+            .collect::<Result<_>>()?
+    };
+
+    for suite in suites {
+        println!("=== Running {suite} ===");
+        let mut cmd = test_command();
+        cmd.args([suite.as_str(), "--", "--exact", "--nocapture"]);
+        apply_env(&mut cmd);
+        let ran = util::run_tests(&mut cmd)?;
+        if ran == 0 {
+            bail!("{suite} matched no tests");
+        }
+        println!();
+    }
+    Ok(())
+}
+
+/// Spawn a local governance network via the `spawn-governance-net` binary.
+pub fn spawn_net(args: &[String]) -> Result<()> {
+    let mut cmd = Command::new("cargo");
+    cmd.current_dir(paths::integration_tests_dir())
+        .args(["run", "--bin", "spawn-governance-net", "--"])
+        .args(args);
+    apply_env(&mut cmd);
+    util::run(&mut cmd)
+}
@@ -0,0 +1,113 @@
+use anyhow::{bail, Context, Result};
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+/// Return the value following `flag` in `args`, if present.
+pub fn take_option(args: &[String], flag: &str) -> Result<Option<String>> {
+    match args.iter().position(|a| a == flag) {
+        Some(i) => match args.get(i + 1) {
+            Some(value) if !value.starts_with("--") => Ok(Some(value.clone())),
+            _ => bail!("{flag} requires a value"),
+        },
+        None => Ok(None),
+    }
+}
+
+/// Run a command to completion, failing if it exits non-zero.
+pub fn run(cmd: &mut Command) -> Result<()> {
+    let status = cmd
+        .status()
+        .with_context(|| format!("Failed to spawn {cmd:?}"))?;
+    if !status.success() {
+        bail!("{cmd:?} exited with {status}");
+    }
+    Ok(())
+}
+
+/// Run a libtest command to completion, echoing its stdout, and return how
+/// many tests it ran (the sum of its `running N tests` lines). Fails if it
+/// exits non-zero.
+pub fn run_tests(cmd: &mut Command) -> Result<usize> {
+    let mut child = cmd
+        .stdout(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to spawn {cmd:?}"))?;
+    let stdout = child.stdout.take().context("stdout not captured")?;
+    let mut ran = 0;
+    for line in BufReader::new(stdout).lines() {
+        let line = line?;
+        println!("{line}");
+        if let Some(count) = line
+            .strip_prefix("running ")
+            .and_then(|rest| rest.strip_suffix(" tests").or(rest.strip_suffix(" test")))
+            .and_then(|count| count.parse::<usize>().ok())
+        {
+            ran += count;
+        }
+    }
+    let status = child.wait()?;
+    if !status.success() {
+        bail!("{cmd:?} exited with {status}");
+    }
+    Ok(ran)
+}
+
+/// Run a command and return its trimmed stdout.
+pub fn capture(cmd: &mut Command) -> Result<String> {
+    let output = cmd
+        .output()
+        .with_context(|| format!("Failed to spawn {cmd:?}"))?;
+    if !output.status.success() {
+        bail!(
+            "{cmd:?} exited with {}:\n{}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+pub fn command_exists(name: &str) -> bool {
+    Command::new(name)
+        .arg("--version")
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false)
+}
+
+/// Delete every `*.{ext}` file directly inside `dir` (no-op if `dir` is missing).
+pub fn remove_files_with_extension(dir: &Path, ext: &str) -> Result<()> {
+    if !dir.is_dir() {
+        return Ok(());
+    }
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|e| e == ext) {
+            println!("  Removing {}", path.display());
+            std::fs::remove_file(&path)?;
+        }
+    }
+    Ok(())
+}
+
+/// Print `ls -lh`-style size lines for every `*.{ext}` file in `dir`.
+pub fn list_files_with_extension(dir: &Path, ext: &str) -> Result<()> {
+    let mut found = false;
+    if dir.is_dir() {
+        let mut entries: Vec<_> = std::fs::read_dir(dir)?
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .filter(|p| p.extension().is_some_and(|e| e == ext))
+            .collect();
+        entries.sort();
+        for path in entries {
+            let size = std::fs::metadata(&path)?.len();
+            println!("  {} ({:.1} MB)", path.display(), size as f64 / 1_048_576.0);
+            found = true;
+        }
+    }
+    if !found {
+        println!("  (none found)");
+    }
+    Ok(())
+}
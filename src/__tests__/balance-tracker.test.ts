@@ -0,0 +1,113 @@
+import { Binary } from 'polkadot-api';
+import { describe, expect, it, vi } from 'vitest';
+import {
+  assetLabel,
+  BalanceTracker,
+  parseBalanceChecks,
+  readBalance,
+} from '../services/balance-tracker';
+import { ErrorCode } from '../utils/error-codes';
+import { formatLocation, parseLocation } from '../utils/xcm-location';
+
+const ALICE = '15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5';
+const WETH =
+  '../../GlobalConsensus(Ethereum:1)/AccountKey20(0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2)';
+
+describe('parseLocation', () => {
+  it('round-trips through formatLocation', () => {
+    for (const path of [WETH, '../Parachain(2030)/GeneralIndex(0)', 'PalletInstance(50)', '..']) {
+      expect(formatLocation(parseLocation(path))).toBe(path);
+    }
+  });
+
+  it('builds v4 junctions', () => {
+    expect(parseLocation('../Parachain(2030)/GeneralIndex(0)')).toEqual({
+      parents: 1,
+      interior: {
+        type: 'X2',
+        value: [
+          { type: 'Parachain', value: 2030 },
+          { type: 'GeneralIndex', value: 0n },
+        ],
+      },
+    });
+  });
+
+  it('rejects unsupported junctions', () => {
+    expect(() => parseLocation('../GeneralKey(0x00)')).toThrow('unsupported junction');
+  });
+});
+
+describe('parseBalanceChecks', () => {
+  it('parses native, Assets and ForeignAssets checks', () => {
+    const checks = parseBalanceChecks(
+      `${ALICE}@native,${ALICE}@assets:1984=+1000000,${ALICE}@foreign:${WETH}=-5`
+    );
+
+    expect(checks.map((c) => [c.asset.type, c.expectedDelta])).toEqual([
+      ['native', undefined],
+      ['assets', 1_000_000n],
+      ['foreign', -5n],
+    ]);
+    expect(checks[1].asset).toEqual({ type: 'assets', id: 1984 });
+  });
+
+  it('rejects malformed entries, accounts and assets', () => {
+    for (const value of [ALICE, 'not-an-address@native', `${ALICE}@assets:usdt`]) {
+      expect(() => parseBalanceChecks(value)).toThrow(
+        expect.objectContaining({ code: ErrorCode.InvalidBalanceCheck })
+      );
+    }
+  });
+});
+
+describe('readBalance', () => {
+  it('reads ForeignAssets balances by location', async () => {
+    const getValue = vi.fn().mockResolvedValue({ balance: 42n });
+    const api = { query: { ForeignAssets: { Account: { getValue } } } } as any;
+    const location = parseLocation(WETH);
+
+    expect(await readBalance(api, { type: 'foreign', location }, ALICE)).toBe(42n);
+    expect(getValue).toHaveBeenCalledWith([location, ALICE]);
+  });
+
+  it('fails on a chain without the asset pallet', async () => {
+    const api = { query: {} } as any;
+    await expect(readBalance(api, { type: 'assets', id: 1984 }, ALICE)).rejects.toThrow(
+      'Chain has no Assets pallet'
+    );
+  });
+});
+
+describe('assetLabel', () => {
+  it('adds the symbol from ForeignAssets metadata', async () => {
+    const api = {
+      query: {
+        ForeignAssets: {
+          Metadata: { getValue: vi.fn().mockResolvedValue({ symbol: Binary.fromText('WETH') }) },
+        },
+      },
+    } as any;
+
+    expect(await assetLabel(api, { type: 'foreign', location: parseLocation(WETH) })).toBe(
+      `ForeignAssets ${WETH} (WETH)`
+    );
+  });
+});
+
+describe('BalanceTracker', () => {
+  it('reports the change of each snapshotted balance', async () => {
+    const getValue = vi
+      .fn()
+      .mockResolvedValueOnce({ data: { free: 100n } })
+      .mockResolvedValueOnce({ data: { free: 70n } });
+    const tracker = new BalanceTracker({ query: { System: { Account: { getValue } } } } as any);
+
+    await tracker.snapshot({ type: 'native' }, ALICE);
+    await tracker.snapshot({ type: 'native' }, ALICE);
+
+    expect(await tracker.deltas()).toEqual([
+      { asset: { type: 'native' }, account: ALICE, before: 100n, after: 70n, delta: -30n },
+    ]);
+  });
+});
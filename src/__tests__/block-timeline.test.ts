@@ -0,0 +1,103 @@
+import { describe, expect, it, vi } from 'vitest';
+import { alignTimeline, BlockTimeline, type TimelineBlock } from '../services/block-timeline';
+import type { ChopsticksManager } from '../services/chopsticks-manager';
+import type { Logger } from '../utils/logger';
+
+const EVENTS: Record<string, unknown[]> = {
+  'relay-101': [{ type: 'XcmPallet', value: { type: 'Sent', value: {} } }],
+  'para-501': [
+    { type: 'ParachainSystem', value: { type: 'DownwardMessagesReceived', value: {} } },
+    { type: 'MessageQueue', value: { type: 'Processed', value: {} } },
+    { type: 'Balances', value: { type: 'Deposit', value: {} } },
+  ],
+};
+
+vi.mock('../services/chain-registry', () => ({
+  createPolkadotClient: (endpoint: string) => ({
+    _request: async (_method: string, [number]: [number]) => `${endpoint}-${number}`,
+    destroy: vi.fn(),
+    endpoint,
+  }),
+  createApiForChain: (client: { endpoint: string }) => ({
+    query: {
+      System: {
+        Events: { getValue: async ({ at }: { at: string }) => EVENTS[at] ?? [] },
+      },
+      ParachainSystem: {
+        LastRelayChainBlockNumber: {
+          getValue: async ({ at }: { at: string }) => {
+            if (client.endpoint === 'relay') throw new Error('no ParachainSystem');
+            return at === 'para-501' ? 101 : 100;
+          },
+        },
+      },
+    },
+  }),
+}));
+
+function createMockLogger(): Logger {
+  return { info: vi.fn(), warn: vi.fn(), section: vi.fn() } as unknown as Logger;
+}
+
+function mockManager(endpoint: string, blocks: number[]): ChopsticksManager {
+  return {
+    getContext: () => ({ ws: { endpoint } }),
+    getBuiltBlocks: () => blocks,
+  } as unknown as ChopsticksManager;
+}
+
+describe('alignTimeline', () => {
+  it('groups parachain blocks under their relay parent, relay block first', () => {
+    const blocks: TimelineBlock[] = [
+      { chain: 'AssetHub', number: 501, relayParent: 101, messages: [] },
+      { chain: 'Collectives', number: 301, relayParent: 100, messages: [] },
+      { chain: 'Polkadot', number: 101, messages: ['XcmPallet.Sent'] },
+      { chain: 'Polkadot', number: 100, messages: [] },
+    ];
+
+    const rows = alignTimeline(blocks);
+
+    expect(rows.map((r) => r.relayNumber)).toEqual([100, 101]);
+    expect(rows[1].blocks.map((b) => `${b.chain} #${b.number}`)).toEqual([
+      'Polkadot #101',
+      'AssetHub #501',
+    ]);
+  });
+});
+
+describe('BlockTimeline', () => {
+  it('collects relay parents and XCM events of built blocks', async () => {
+    const timeline = new BlockTimeline(createMockLogger());
+
+    const blocks = await timeline.collect([
+      { label: 'Polkadot', manager: mockManager('relay', [101]) },
+      { label: 'AssetHub', manager: mockManager('para', [500, 501]) },
+    ]);
+
+    expect(blocks).toEqual([
+      { chain: 'Polkadot', number: 101, relayParent: undefined, messages: ['XcmPallet.Sent'] },
+      { chain: 'AssetHub', number: 500, relayParent: 100, messages: [] },
+      {
+        chain: 'AssetHub',
+        number: 501,
+        relayParent: 101,
+        messages: ['ParachainSystem.DownwardMessagesReceived', 'MessageQueue.Processed'],
+      },
+    ]);
+  });
+
+  it('prints one row per relay height', () => {
+    const logger = createMockLogger();
+    new BlockTimeline(logger).display(
+      alignTimeline([
+        { chain: 'Polkadot', number: 101, messages: ['XcmPallet.Sent'] },
+        { chain: 'AssetHub', number: 501, relayParent: 101, messages: ['MessageQueue.Processed'] },
+      ])
+    );
+
+    expect(logger.section).toHaveBeenCalledWith('Block Timeline');
+    expect(logger.info).toHaveBeenCalledWith('Relay #101');
+    expect(logger.info).toHaveBeenCalledWith('  Polkadot #101: XcmPallet.Sent');
+    expect(logger.info).toHaveBeenCalledWith('  AssetHub #501: MessageQueue.Processed');
+  });
+});
@@ -0,0 +1,96 @@
+import { describe, expect, it, vi } from 'vitest';
+import {
+  CallDriftDetector,
+  type CallMetadata,
+  callNameAt,
+  formatCallDrift,
+} from '../services/call-drift-detector';
+import { createSilentLogger } from './test-logger';
+
+// Metadata "hex" is looked up by name so tests can hand-build the unified shape
+const METADATA: Record<string, CallMetadata> = {
+  current: {
+    lookup: [{ id: 1, def: { tag: 'variant', value: [{ name: 'transfer_all', index: 4 }] } }],
+    pallets: [{ name: 'Balances', index: 10, calls: { type: 1 } }],
+  },
+  previous: {
+    lookup: [{ id: 1, def: { tag: 'variant', value: [{ name: 'batch', index: 4 }] } }],
+    pallets: [{ name: 'Utility', index: 10, calls: 1 }],
+  },
+};
+
+vi.mock('@polkadot-api/substrate-bindings', () => ({
+  decAnyMetadata: (hex: string) => METADATA[hex],
+  unifyMetadata: (metadata: unknown) => metadata,
+}));
+
+/** Fake RPC for a chain upgraded from spec 100 to 200 at block `upgradeAt`. */
+function mockRpc(head: number, upgradeAt: number) {
+  return vi.fn(async (method: string, params: unknown[]) => {
+    switch (method) {
+      case 'chain_getHeader':
+        return { number: `0x${head.toString(16)}` };
+      case 'chain_getBlockHash':
+        return `block-${params[0]}`;
+      case 'state_getRuntimeVersion': {
+        const number = Number(String(params[0]).replace('block-', ''));
+        return { specVersion: number >= upgradeAt ? 200 : 100 };
+      }
+      case 'state_getMetadata':
+        return params.length === 0 ? 'current' : 'previous';
+      default:
+        throw new Error(`unexpected ${method}`);
+    }
+  });
+}
+
+describe('callNameAt', () => {
+  it('names the call variant at the pallet and call indices', () => {
+    expect(callNameAt(METADATA.current, 10, 4)).toBe('Balances.transfer_all');
+    expect(callNameAt(METADATA.previous, 10, 4)).toBe('Utility.batch');
+  });
+
+  it('returns undefined for unknown pallets or calls', () => {
+    expect(callNameAt(METADATA.current, 11, 4)).toBeUndefined();
+    expect(callNameAt(METADATA.current, 10, 5)).toBeUndefined();
+  });
+});
+
+describe('CallDriftDetector', () => {
+  it('finds the last block of the previous runtime', async () => {
+    const detector = new CallDriftDetector(createSilentLogger(), mockRpc(5000, 4321));
+    expect(await detector.findPreviousRuntimeBlock(5000, 200)).toBe(4320);
+  });
+
+  it('returns undefined when no upgrade happened within the window', async () => {
+    const detector = new CallDriftDetector(createSilentLogger(), mockRpc(5000, 0));
+    expect(await detector.findPreviousRuntimeBlock(5000, 200)).toBeUndefined();
+  });
+
+  it('reports what the index bytes decode to under both runtimes', async () => {
+    const detector = new CallDriftDetector(createSilentLogger(), mockRpc(5000, 4321));
+    const report = await detector.explain('0x0a04deadbeef');
+
+    expect(report).toEqual({
+      indices: '0x0a04',
+      current: { specVersion: 200, call: 'Balances.transfer_all' },
+      previous: { specVersion: 100, block: 4320, call: 'Utility.batch' },
+    });
+    const text = formatCallDrift(report!);
+    expect(text).toContain('indices may have shifted');
+    expect(text).toContain('current runtime (spec 200): Balances.transfer_all');
+    expect(text).toContain('previous runtime (spec 100, until block #4320): Utility.batch');
+  });
+
+  it('reports nothing when the call is known and there was no upgrade', async () => {
+    const detector = new CallDriftDetector(createSilentLogger(), mockRpc(5000, 0));
+    expect(await detector.explain('0x0a04')).toBeUndefined();
+  });
+
+  it('reports nothing when the RPCs fail', async () => {
+    const logger = createSilentLogger();
+    const detector = new CallDriftDetector(logger, vi.fn().mockRejectedValue(new Error('down')));
+    expect(await detector.explain('0x0a04')).toBeUndefined();
+    expect(logger.debug).toHaveBeenCalledWith(expect.stringContaining('down'));
+  });
+});
@@ -1,5 +1,52 @@
 import { describe, expect, it } from 'vitest';
-import { parseEndpoint, parseMultipleEndpoints } from '../utils/chain-endpoint-parser';
+import {
+  parseBlockNumber,
+  parseEndpoint,
+  parseMultipleEndpoints,
+  reportEndpoint,
+} from '../utils/chain-endpoint-parser';
+
+describe('parseBlockNumber', () => {
+  it('parses decimal and hex block numbers', () => {
+    expect(parseBlockNumber('12345', 'e')).toBe(12345);
+    expect(parseBlockNumber(' 0x3039 ', 'e')).toBe(12345);
+    expect(parseBlockNumber('0X0', 'e')).toBe(0);
+    expect(parseBlockNumber('4294967295', 'e')).toBe(4294967295);
+  });
+
+  it('names what is wrong with a malformed block', () => {
+    expect(() => parseBlockNumber('', 'wss://a,')).toThrow('Missing block number in wss://a,');
+    expect(() => parseBlockNumber('-5', 'e')).toThrow(
+      'Invalid block number "-5" in e: block numbers cannot be negative'
+    );
+    for (const block of ['12abc', '1.5', '0x', '0xzz', '1e3']) {
+      expect(() => parseBlockNumber(block, 'e')).toThrow(
+        `Invalid block number "${block}" in e: expected a decimal or 0x-prefixed hex number`
+      );
+    }
+    expect(() => parseBlockNumber('4294967296', 'e')).toThrow(
+      'larger than the largest block number 4294967295'
+    );
+    expect(() => parseBlockNumber('abc', 'e')).toThrow(
+      expect.objectContaining({ code: 'E-INVALID-ENDPOINT' })
+    );
+  });
+});
+
+describe('reportEndpoint', () => {
+  it('reports the latest finalized block as null', () => {
+    expect(reportEndpoint({ url: 'wss://a' })).toEqual({
+      url: 'wss://a',
+      block: null,
+      label: null,
+    });
+    expect(reportEndpoint({ url: 'wss://a', block: 0, label: 'Relay' })).toEqual({
+      url: 'wss://a',
+      block: 0,
+      label: 'Relay',
+    });
+  });
+});
 
 describe('parseEndpoint', () => {
   it('parses a plain URL', () => {
@@ -25,8 +72,17 @@ describe('parseEndpoint', () => {
     expect(() => parseEndpoint('   ')).toThrow('cannot be empty');
   });
 
+  it('parses a hex block number', () => {
+    expect(parseEndpoint('wss://polkadot.io,0x3039')).toEqual({
+      url: 'wss://polkadot.io',
+      block: 12345,
+    });
+  });
+
   it('throws on invalid block number', () => {
     expect(() => parseEndpoint('wss://polkadot.io,abc')).toThrow('Invalid block number');
+    expect(() => parseEndpoint('wss://polkadot.io,12abc')).toThrow('Invalid block number');
+    expect(() => parseEndpoint('wss://polkadot.io,')).toThrow('Missing block number');
   });
 
   it('throws on negative block number', () => {
@@ -36,6 +92,23 @@ describe('parseEndpoint', () => {
   it('throws on too many commas', () => {
     expect(() => parseEndpoint('wss://polkadot.io,123,456')).toThrow('Invalid endpoint format');
   });
+
+  it('parses the labeled form', () => {
+    expect(parseEndpoint('name=Relay;url=wss://polkadot.io;block=12345')).toEqual({
+      url: 'wss://polkadot.io',
+      block: 12345,
+      label: 'Relay',
+    });
+    expect(parseEndpoint(' url=wss://polkadot.io ')).toEqual({ url: 'wss://polkadot.io' });
+  });
+
+  it('throws on labeled endpoints with unknown fields, no url or a bad block', () => {
+    expect(() => parseEndpoint('name=Relay;url=wss://polkadot.io;role=relay')).toThrow(
+      'Invalid endpoint field "role=relay"'
+    );
+    expect(() => parseEndpoint('name=Relay;url=')).toThrow('Missing url=');
+    expect(() => parseEndpoint('url=wss://polkadot.io;block=-1')).toThrow('Invalid block number');
+  });
 });
 
 describe('parseMultipleEndpoints', () => {
@@ -69,6 +142,24 @@ describe('parseMultipleEndpoints', () => {
     ]);
   });
 
+  it('parses labeled endpoints among plain ones', () => {
+    expect(
+      parseMultipleEndpoints('name=People;url=wss://chain1.io;block=100,wss://chain2.io,200')
+    ).toEqual([
+      { url: 'wss://chain1.io', block: 100, label: 'People' },
+      { url: 'wss://chain2.io', block: 200 },
+    ]);
+  });
+
+  it('rejects a malformed block instead of reading it as a URL', () => {
+    expect(() => parseMultipleEndpoints('wss://chain1.io,12abc,wss://chain2.io')).toThrow(
+      'Invalid block number "12abc" in wss://chain1.io,12abc'
+    );
+    expect(parseMultipleEndpoints('wss://chain1.io,0x64')).toEqual([
+      { url: 'wss://chain1.io', block: 100 },
+    ]);
+  });
+
   it('throws on non-URL at start', () => {
     expect(() => parseMultipleEndpoints('notaurl')).toThrow('Expected URL');
   });
@@ -0,0 +1,64 @@
+import { describe, expect, it, vi } from 'vitest';
+import { collectChainHeads, displayChainHeads } from '../services/chain-heads';
+import type { ChopsticksManager } from '../services/chopsticks-manager';
+import type { HeadRef, Logger } from '../utils/logger';
+
+const ASSET_HUB = {
+  chain: 'Asset Hub',
+  endpoint: 'ws://127.0.0.1:8000',
+  fork: { number: 100, hash: '0xaa' },
+  final: { number: 104, hash: '0xbb' },
+};
+const PEOPLE = {
+  chain: 'People',
+  endpoint: 'ws://127.0.0.1:8001',
+  fork: { number: 7 },
+  final: { number: 7 },
+};
+
+function mockManager(endpoint: string, fork?: HeadRef, final?: HeadRef): ChopsticksManager {
+  return {
+    getForkHead: () => fork,
+    getHead: () => final,
+    getContext: () => ({ ws: { endpoint } }),
+  } as unknown as ChopsticksManager;
+}
+
+describe('collectChainHeads', () => {
+  it("reports each fork's start and final head", () => {
+    const heads = collectChainHeads(
+      [ASSET_HUB, PEOPLE].map(({ chain, endpoint, fork, final }) => ({
+        label: chain,
+        manager: mockManager(endpoint, fork, final),
+      }))
+    );
+
+    expect(heads).toEqual([ASSET_HUB, PEOPLE]);
+  });
+
+  it('leaves out forks that never came up', () => {
+    const manager = mockManager(ASSET_HUB.endpoint);
+    expect(collectChainHeads([{ label: 'Asset Hub', manager }])).toEqual([]);
+  });
+});
+
+describe('displayChainHeads', () => {
+  it('prints a row per chain and emits the heads line', () => {
+    const logger = { section: vi.fn(), info: vi.fn(), heads: vi.fn() } as unknown as Logger;
+
+    displayChainHeads(logger, [ASSET_HUB, PEOPLE]);
+
+    expect(logger.info).toHaveBeenCalledWith(
+      'Asset Hub  forked at #100 (0xaa), ended at #104 (0xbb) (+4 block(s))'
+    );
+    expect(logger.info).toHaveBeenCalledWith('People     forked at #7, ended at #7 (+0 block(s))');
+    expect(logger.heads).toHaveBeenCalledWith([ASSET_HUB, PEOPLE]);
+  });
+
+  it('prints nothing without heads', () => {
+    const logger = { section: vi.fn(), heads: vi.fn() } as unknown as Logger;
+    displayChainHeads(logger, []);
+    expect(logger.section).not.toHaveBeenCalled();
+    expect(logger.heads).not.toHaveBeenCalled();
+  });
+});
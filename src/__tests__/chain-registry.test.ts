@@ -1,5 +1,5 @@
-import { describe, expect, it } from 'vitest';
-import { buildChainInfoFromSpecName } from '../services/chain-registry';
+import { describe, expect, it, vi } from 'vitest';
+import { buildChainInfoFromSpecName, getChainProperties } from '../services/chain-registry';
 
 describe('buildChainInfoFromSpecName', () => {
   it('identifies polkadot relay chain', () => {
@@ -58,6 +58,12 @@ describe('buildChainInfoFromSpecName', () => {
     expect(info.label).toBe('asset-hub-polkadot');
   });
 
+  it('uses a given label but keeps the spec-derived id', () => {
+    const info = buildChainInfoFromSpecName('asset-hub-polkadot', 'wss://example.io', 'AssetHub');
+    expect(info.label).toBe('AssetHub');
+    expect(info.id).toBe('asset-hub-polkadot');
+  });
+
   it('preserves endpoint in result', () => {
     const endpoint = 'wss://polkadot-rpc.dwellir.com';
     const info = buildChainInfoFromSpecName('polkadot', endpoint);
@@ -71,3 +77,25 @@ describe('buildChainInfoFromSpecName', () => {
     expect(info.network).toBe('polkadot');
   });
 });
+
+describe('getChainProperties', () => {
+  it('reads the chain name and the first of several tokens', async () => {
+    const request = vi.fn(async (method: string) =>
+      method === 'system_chain'
+        ? 'Karura'
+        : { tokenSymbol: ['KAR', 'KUSD'], tokenDecimals: [12, 12], ss58Format: 8 }
+    );
+    expect(await getChainProperties(request)).toEqual({
+      name: 'Karura',
+      token: { symbol: 'KAR', decimals: 12 },
+    });
+  });
+
+  it('leaves fields unset when the node does not report them', async () => {
+    const request = vi.fn(async (method: string) => {
+      if (method === 'system_chain') throw new Error('Method not found');
+      return { ss58Format: 42 };
+    });
+    expect(await getChainProperties(request)).toEqual({ name: undefined, token: undefined });
+  });
+});
@@ -0,0 +1,23 @@
+import * as path from 'path';
+import { describe, expect, it } from 'vitest';
+import { ChainTopologyBuilder } from '../services/chain-topology-builder';
+import { createSilentLogger } from './test-logger';
+
+describe('ChainTopologyBuilder storage cache', () => {
+  it('defaults to .chopsticks-db in the working directory', () => {
+    const topology = new ChainTopologyBuilder(createSilentLogger(), {});
+    expect(topology.getDbPath()).toBe(path.join(process.cwd(), '.chopsticks-db'));
+  });
+
+  it('resolves a custom --db path', () => {
+    const topology = new ChainTopologyBuilder(createSilentLogger(), { db: 'cache/fork.sqlite' });
+    expect(topology.getDbPath()).toBe(path.resolve('cache/fork.sqlite'));
+  });
+
+  it('uses the cache for every Chopsticks config it builds', () => {
+    const topology = new ChainTopologyBuilder(createSilentLogger(), { db: '/tmp/fork.sqlite' });
+    const config = topology.buildConfig('ws://127.0.0.1:9944', 42);
+    expect(config.db).toBe('/tmp/fork.sqlite');
+    expect(config.block).toBe(42);
+  });
+});
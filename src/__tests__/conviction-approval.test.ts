@@ -0,0 +1,233 @@
+import { describe, expect, it, vi } from 'vitest';
+import {
+  ConvictionApprover,
+  convictionLockErrors,
+  lockPeriods,
+  parseConviction,
+} from '../services/conviction-approval';
+import type { ConvictionLock } from '../types';
+import { ALICE_ADDRESS } from '../utils/storage-constants';
+import { createSilentLogger } from './test-logger';
+
+vi.mock('../services/delegated-submission', () => ({
+  mockSigner: vi.fn(() => 'alice-signer'),
+}));
+
+function createMockChopsticks() {
+  return {
+    newBlock: vi.fn().mockResolvedValue(undefined),
+    setStorageBatch: vi.fn().mockResolvedValue(undefined),
+  } as any;
+}
+
+const extrinsicFailed = {
+  type: 'System',
+  value: {
+    type: 'ExtrinsicFailed',
+    value: {
+      dispatch_error: {
+        type: 'Module',
+        value: { type: 'ConvictionVoting', value: { type: 'InsufficientFunds' } },
+      },
+    },
+  },
+};
+
+/**
+ * A chain where the referendum ended at block 1000, votes lock for 100 blocks
+ * per period, and the voter's class lock and `prior` are as given.
+ */
+function createMocks(locks: { afterDispatch: bigint; prior: number; afterUnlock: bigint }) {
+  const sign = vi.fn().mockResolvedValue('0xsigned');
+  const tx = {
+    vote: vi.fn(() => ({ sign })),
+    remove_vote: vi.fn(() => ({ sign })),
+    unlock: vi.fn(() => ({ sign })),
+  };
+  const ClassLocksFor = {
+    getValue: vi
+      .fn()
+      .mockResolvedValueOnce([[3, locks.afterDispatch]])
+      .mockResolvedValueOnce(locks.afterUnlock > 0n ? [[3, locks.afterUnlock]] : []),
+  };
+  const api = {
+    query: {
+      System: { Events: { getValue: vi.fn().mockResolvedValue([]) } },
+      ConvictionVoting: {
+        ClassLocksFor,
+        VotingFor: {
+          getValue: vi.fn().mockResolvedValue({
+            type: 'Casting',
+            value: { votes: [], prior: [locks.prior, locks.prior > 0 ? 1000n : 0n] },
+          }),
+        },
+      },
+    },
+    constants: { ConvictionVoting: { VoteLockingPeriod: vi.fn().mockResolvedValue(100) } },
+    tx: { ConvictionVoting: tx },
+  } as any;
+  const referenda = {
+    ReferendumInfoFor: {
+      getValue: vi
+        .fn()
+        .mockResolvedValue({ type: 'Approved', value: [1000, undefined, undefined] }),
+    },
+  } as any;
+  return { api, referenda, tx };
+}
+
+describe('parseConviction', () => {
+  it('accepts each conviction, case-insensitively', () => {
+    expect(parseConviction('Locked1x')).toBe('Locked1x');
+    expect(parseConviction('none')).toBe('None');
+    expect(parseConviction(undefined)).toBeUndefined();
+  });
+
+  it('rejects unknown convictions', () => {
+    expect(() => parseConviction('Locked7x')).toThrow('Invalid --vote-conviction value: Locked7x');
+  });
+});
+
+describe('lockPeriods', () => {
+  it('doubles with each conviction level', () => {
+    expect(lockPeriods('None')).toBe(0);
+    expect(lockPeriods('Locked1x')).toBe(1);
+    expect(lockPeriods('Locked2x')).toBe(2);
+    expect(lockPeriods('Locked6x')).toBe(32);
+  });
+});
+
+describe('convictionLockErrors', () => {
+  const lock: ConvictionLock = {
+    who: ALICE_ADDRESS,
+    track: 3,
+    conviction: 'Locked2x',
+    balance: 1000n,
+    classLock: 1000n,
+    unlockAt: 1200,
+    expectedUnlockAt: 1200,
+    lockedAfterUnlock: 1000n,
+  };
+
+  it('accepts a lock that holds the vote until its unlock block', () => {
+    expect(convictionLockErrors(lock)).toEqual([]);
+  });
+
+  it('reports a lock released too early', () => {
+    expect(convictionLockErrors({ ...lock, unlockAt: 1100, lockedAfterUnlock: 0n })).toEqual([
+      'vote unlocks at block #1100, expected #1200',
+      'class lock is 0 after unlocking right away, expected 1000',
+    ]);
+  });
+});
+
+describe('ConvictionApprover', () => {
+  it('funds the voter and clears their earlier votes on the track', async () => {
+    const chopsticks = createMockChopsticks();
+    const { api, referenda } = createMocks({ afterDispatch: 0n, prior: 0, afterUnlock: 0n });
+    const approver = new ConvictionApprover(
+      createSilentLogger(),
+      chopsticks,
+      api,
+      referenda,
+      'Locked1x'
+    );
+
+    await approver.injectVoter(3, 2000n);
+
+    const storage = chopsticks.setStorageBatch.mock.calls[0][0];
+    expect(storage.System.Account[0][1].data.free).toBe('2000');
+    expect(storage.ConvictionVoting.VotingFor).toEqual([[[ALICE_ADDRESS, 3], null]]);
+  });
+
+  it('votes aye at the conviction and reports the conviction-scaled votes', async () => {
+    const chopsticks = createMockChopsticks();
+    const { api, referenda, tx } = createMocks({ afterDispatch: 0n, prior: 0, afterUnlock: 0n });
+    const approver = new ConvictionApprover(
+      createSilentLogger(),
+      chopsticks,
+      api,
+      referenda,
+      'Locked2x'
+    );
+
+    const votes = await approver.approve(7, 3, 1000n);
+
+    expect(tx.vote).toHaveBeenCalledWith({
+      poll_index: 7,
+      vote: expect.objectContaining({ type: 'Standard', value: { vote: 0x82, balance: 1000n } }),
+    });
+    expect(chopsticks.newBlock).toHaveBeenCalledWith({ transactions: ['0xsigned'] });
+    expect(votes).toEqual([
+      { who: ALICE_ADDRESS, conviction: 'Locked2x', balance: 1000n, votes: 2000n },
+    ]);
+  });
+
+  it('fails when the vote is rejected', async () => {
+    const { api, referenda } = createMocks({ afterDispatch: 0n, prior: 0, afterUnlock: 0n });
+    api.query.System.Events.getValue.mockResolvedValue([extrinsicFailed]);
+    const approver = new ConvictionApprover(
+      createSilentLogger(),
+      createMockChopsticks(),
+      api,
+      referenda,
+      'Locked1x'
+    );
+
+    await expect(approver.approve(7, 3, 1000n)).rejects.toThrow(
+      'ConvictionVoting.vote on referendum #7 failed'
+    );
+  });
+
+  it('checks the lock stays until the end plus the lock periods', async () => {
+    const { api, referenda, tx } = createMocks({
+      afterDispatch: 1000n,
+      prior: 1200,
+      afterUnlock: 1000n,
+    });
+    const approver = new ConvictionApprover(
+      createSilentLogger(),
+      createMockChopsticks(),
+      api,
+      referenda,
+      'Locked2x'
+    );
+    await approver.approve(7, 3, 1000n);
+
+    const lock = await approver.checkLock();
+
+    expect(tx.remove_vote).toHaveBeenCalledWith({ class: 3, index: 7 });
+    expect(tx.unlock).toHaveBeenCalledWith({ class: 3, target: expect.anything() });
+    expect(lock).toMatchObject({ classLock: 1000n, unlockAt: 1200, expectedUnlockAt: 1200 });
+    expect(lock?.errors).toBeUndefined();
+  });
+
+  it('reports a vote without conviction that stays locked after unlocking', async () => {
+    const { api, referenda } = createMocks({ afterDispatch: 1000n, prior: 0, afterUnlock: 1000n });
+    const approver = new ConvictionApprover(
+      createSilentLogger(),
+      createMockChopsticks(),
+      api,
+      referenda,
+      'None'
+    );
+    await approver.approve(7, 3, 1000n);
+
+    const lock = await approver.checkLock();
+
+    expect(lock?.errors).toEqual(['class lock is 1000 after unlocking right away, expected 0']);
+  });
+
+  it('checks nothing when no vote was cast', async () => {
+    const { api, referenda } = createMocks({ afterDispatch: 0n, prior: 0, afterUnlock: 0n });
+    const approver = new ConvictionApprover(
+      createSilentLogger(),
+      createMockChopsticks(),
+      api,
+      referenda,
+      'Locked1x'
+    );
+
+    expect(await approver.checkLock()).toBeUndefined();
+  });
+});
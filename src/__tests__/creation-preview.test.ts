@@ -0,0 +1,210 @@
+import { Binary, Blake2256 } from '@polkadot-api/substrate-bindings';
+import { describe, expect, it, vi } from 'vitest';
+import {
+  buildCreationPreview,
+  describeEnactment,
+  displayCreationPreview,
+  originName,
+  trackForOrigin,
+} from '../services/creation-preview';
+import type { TrackInfo } from '../types/substrate-api';
+import type { Logger } from '../utils/logger';
+
+const TRACKS: TrackInfo[] = [
+  [0, { name: 'root', decision_deposit: 100n }],
+  [1, { name: 'whitelisted_caller', decision_deposit: 10n }],
+];
+
+const WHITELISTED = { type: 'Origins', value: { type: 'WhitelistedCaller' } };
+
+const SUBMIT_HEX = '0x1500';
+const PREIMAGE_HEX = '0x0500';
+const REMARK_HEX = '0x0001';
+const REMARK_HASH = Binary.fromBytes(Blake2256(Binary.fromHex(REMARK_HEX).asBytes())).asHex();
+
+function call(pallet: string, method: string, args: Record<string, unknown>) {
+  return { type: pallet, value: { type: method, value: args } };
+}
+
+function mockApi(calls: Record<string, ReturnType<typeof call>>) {
+  return {
+    txFromCallData: vi.fn(async (data: Binary) => {
+      const decodedCall = calls[data.asHex()];
+      if (!decodedCall) throw new Error('unknown call');
+      return { decodedCall };
+    }),
+    constants: {
+      Referenda: {
+        Tracks: vi.fn().mockResolvedValue(TRACKS),
+        SubmissionDeposit: vi.fn().mockResolvedValue(5n),
+      },
+    },
+  } as any;
+}
+
+function submitCall(proposal: unknown, origin: unknown = WHITELISTED) {
+  return call('Referenda', 'submit', {
+    proposal_origin: origin,
+    proposal,
+    enactment_moment: { type: 'After', value: 10 },
+  });
+}
+
+const remark = call('System', 'remark', { remark: Binary.fromText('hi') });
+const notePreimage = call('Preimage', 'note_preimage', { bytes: Binary.fromHex(REMARK_HEX) });
+
+describe('originName', () => {
+  it('joins the origin and its variant', () => {
+    expect(originName(WHITELISTED)).toBe('Origins.WhitelistedCaller');
+    expect(originName({ type: 'system', value: { type: 'Root' } })).toBe('system.Root');
+    expect(originName(undefined)).toBe('unknown');
+  });
+});
+
+describe('trackForOrigin', () => {
+  it('reads the track the origin is mapped to from Tracks', () => {
+    expect(trackForOrigin(TRACKS, WHITELISTED, 'Referenda')).toEqual({
+      id: 1,
+      name: 'whitelisted_caller',
+      decisionDeposit: 10n,
+    });
+    const root = { type: 'system', value: { type: 'Root' } };
+    expect(trackForOrigin(TRACKS, root, 'Referenda')?.id).toBe(0);
+  });
+
+  it('does not match tracks by name', () => {
+    const renamed: TrackInfo[] = [[1, { name: 'whitelisted', decision_deposit: 10n }]];
+    expect(trackForOrigin(renamed, WHITELISTED, 'Referenda')?.name).toBe('whitelisted');
+    const caller = { type: 'Origins', value: { type: 'WhitelistedCaller' } };
+    expect(trackForOrigin(TRACKS, caller, 'FellowshipReferenda')).toBeUndefined();
+  });
+
+  it('returns undefined for an origin whose track the runtime lacks', () => {
+    const treasurer = { type: 'Origins', value: { type: 'Treasurer' } };
+    expect(trackForOrigin(TRACKS, treasurer, 'Referenda')).toBeUndefined();
+    const unmapped = { type: 'Origins', value: { type: 'Unknown' } };
+    expect(trackForOrigin(TRACKS, unmapped, 'Referenda')).toBeUndefined();
+  });
+});
+
+describe('describeEnactment', () => {
+  it('describes After and At moments', () => {
+    expect(describeEnactment({ type: 'After', value: 10 })).toBe('After 10 blocks');
+    expect(describeEnactment({ type: 'At', value: 500 })).toBe('At block 500');
+  });
+});
+
+describe('buildCreationPreview', () => {
+  it('decodes a Lookup submit and its preimage', async () => {
+    const api = mockApi({
+      [SUBMIT_HEX]: submitCall({ type: 'Lookup', value: { hash: REMARK_HASH, len: 2 } }),
+      [PREIMAGE_HEX]: notePreimage,
+      [REMARK_HEX]: remark,
+    });
+
+    const preview = await buildCreationPreview(api, SUBMIT_HEX, PREIMAGE_HEX, false);
+
+    expect(preview.submit.name).toBe('Referenda.submit');
+    expect(preview.origin).toBe('Origins.WhitelistedCaller');
+    expect(preview.track).toEqual({ id: 1, name: 'whitelisted_caller', decisionDeposit: 10n });
+    expect(preview.enactment).toBe('After 10 blocks');
+    expect(preview.preimage).toMatchObject({ hash: REMARK_HASH, len: 2 });
+    expect(preview.proposalCall?.name).toBe('System.remark');
+    expect(preview.submissionDeposit).toBe(5n);
+    expect(preview.warnings).toEqual([]);
+  });
+
+  it('decodes an Inline proposal without a preimage', async () => {
+    const api = mockApi({
+      [SUBMIT_HEX]: submitCall({ type: 'Inline', value: Binary.fromHex(REMARK_HEX) }),
+      [REMARK_HEX]: remark,
+    });
+
+    const preview = await buildCreationPreview(api, SUBMIT_HEX, undefined, false);
+
+    expect(preview.proposal).toEqual({ type: 'Inline', len: 2 });
+    expect(preview.proposalCall?.name).toBe('System.remark');
+    expect(preview.warnings).toEqual([]);
+  });
+
+  it('warns when the preimage does not match the proposal', async () => {
+    const api = mockApi({
+      [SUBMIT_HEX]: submitCall({ type: 'Lookup', value: { hash: `0x${'00'.repeat(32)}`, len: 3 } }),
+      [PREIMAGE_HEX]: notePreimage,
+      [REMARK_HEX]: remark,
+    });
+
+    const { warnings } = await buildCreationPreview(api, SUBMIT_HEX, PREIMAGE_HEX, false);
+
+    expect(warnings).toEqual([
+      `The proposal's hash 0x${'00'.repeat(32)} is not the preimage's ${REMARK_HASH}`,
+      "The proposal's length 3 is not the preimage's 2",
+    ]);
+  });
+
+  it('warns about a missing preimage and an origin without a track', async () => {
+    const treasurer = { type: 'Origins', value: { type: 'Treasurer' } };
+    const api = mockApi({
+      [SUBMIT_HEX]: submitCall({ type: 'Lookup', value: { hash: REMARK_HASH, len: 2 } }, treasurer),
+    });
+
+    const { warnings, track } = await buildCreationPreview(api, SUBMIT_HEX, undefined, false);
+
+    expect(track).toBeUndefined();
+    expect(warnings).toEqual([
+      'No preimage call: the proposal must already be noted on chain',
+      'Origin Origins.Treasurer has no Referenda track on this runtime',
+    ]);
+  });
+
+  it('warns about an Inline proposal close to its bound', async () => {
+    const inlineHex = `0x0001${'00'.repeat(118)}`;
+    const api = mockApi({
+      [SUBMIT_HEX]: submitCall({ type: 'Inline', value: Binary.fromHex(inlineHex) }),
+      [inlineHex]: remark,
+    });
+
+    const { warnings } = await buildCreationPreview(api, SUBMIT_HEX, undefined, false);
+
+    expect(warnings).toEqual(['Inline proposal of 120 bytes is at 94% of the 128-byte bound']);
+  });
+
+  it('rejects call data the runtime cannot decode', async () => {
+    await expect(buildCreationPreview(mockApi({}), '0xff', undefined, false)).rejects.toThrow(
+      expect.objectContaining({ code: 'E-CALL-DECODE-FAILED' })
+    );
+  });
+});
+
+describe('displayCreationPreview', () => {
+  it('prints the summary table and warnings', async () => {
+    const logger = {
+      section: vi.fn(),
+      table: vi.fn(),
+      info: vi.fn(),
+      warn: vi.fn(),
+    } as unknown as Logger;
+    const api = mockApi({
+      [SUBMIT_HEX]: submitCall({ type: 'Lookup', value: { hash: REMARK_HASH, len: 2 } }),
+    });
+
+    displayCreationPreview(
+      logger,
+      'Governance',
+      await buildCreationPreview(api, SUBMIT_HEX, undefined, false)
+    );
+
+    expect(logger.section).toHaveBeenCalledWith('Governance Referendum Preview');
+    expect(logger.table).toHaveBeenCalledWith(
+      expect.objectContaining({
+        'Submit call': 'Referenda.submit',
+        Track: '#1 whitelisted_caller',
+        Proposal: `Lookup ${REMARK_HASH} (2 bytes)`,
+        Preimage: 'none',
+      })
+    );
+    expect(logger.warn).toHaveBeenCalledWith(
+      'No preimage call: the proposal must already be noted on chain'
+    );
+  });
+});
@@ -0,0 +1,178 @@
+import { Binary } from 'polkadot-api';
+import { describe, expect, it, vi } from 'vitest';
+import {
+  DelegatedSubmitter,
+  delegatedSteps,
+  multisigAccountId,
+  parseDelegatedSubmission,
+  parseMultisigSubmission,
+  parseProxySubmission,
+} from '../services/delegated-submission';
+import { ErrorCode } from '../utils/error-codes';
+import { createSilentLogger } from './test-logger';
+
+const REAL = '15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5';
+const ALICE = '5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY';
+const BOB = '5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty';
+const CHARLIE = '5FLSigC9HGRKVhB9FiEo4Y3koPsNmBmLJbpXg2mp1hXcS59Y';
+
+const NEW_MULTISIG_EVENTS = [{ type: 'Multisig', value: { type: 'NewMultisig', value: {} } }];
+
+describe('parseProxySubmission', () => {
+  it('defaults to an Any proxy without delay', () => {
+    expect(parseProxySubmission(REAL)).toEqual({
+      kind: 'proxy',
+      real: REAL,
+      proxyType: 'Any',
+      delay: 0,
+    });
+    expect(parseProxySubmission(undefined)).toBeUndefined();
+  });
+
+  it('reads the proxy type and announcement delay', () => {
+    expect(parseProxySubmission(`${REAL}:Governance:100`)).toEqual({
+      kind: 'proxy',
+      real: REAL,
+      proxyType: 'Governance',
+      delay: 100,
+    });
+  });
+
+  it('rejects invalid addresses, types and delays', () => {
+    for (const value of ['alice', `${REAL}:Gov-ernance`, `${REAL}:Any:soon`, `${REAL}:Any:1:2`]) {
+      expect(() => parseProxySubmission(value)).toThrow(
+        expect.objectContaining({ code: ErrorCode.InvalidProxy })
+      );
+    }
+  });
+});
+
+describe('parseMultisigSubmission', () => {
+  it('reads the threshold and signatories', () => {
+    expect(parseMultisigSubmission(`2:${ALICE}, ${BOB},${CHARLIE}`)).toEqual({
+      kind: 'multisig',
+      threshold: 2,
+      signatories: [ALICE, BOB, CHARLIE],
+    });
+  });
+
+  it('rejects bad thresholds, addresses and duplicate signatories', () => {
+    for (const value of [
+      `${ALICE},${BOB}`,
+      `0:${ALICE},${BOB}`,
+      `3:${ALICE},${BOB}`,
+      `1:${ALICE},bob`,
+      `1:${ALICE}`,
+      `2:${ALICE},${REAL}`,
+    ]) {
+      expect(() => parseMultisigSubmission(value)).toThrow(
+        expect.objectContaining({ code: ErrorCode.InvalidMultisig })
+      );
+    }
+  });
+});
+
+describe('parseDelegatedSubmission', () => {
+  it('parses whichever delegated submission flag is given', () => {
+    expect(parseDelegatedSubmission({ submitViaProxy: REAL })?.kind).toBe('proxy');
+    expect(parseDelegatedSubmission({ submitViaMultisig: `2:${ALICE},${BOB}` })?.kind).toBe(
+      'multisig'
+    );
+    expect(parseDelegatedSubmission({})).toBeUndefined();
+  });
+});
+
+describe('multisigAccountId', () => {
+  it('derives the pallet account independent of signatory order', () => {
+    const account = '5DjYJStmdZ2rcqXbXGX7TW85JsrW6uG4y9MUcLq2BoPMpRA7';
+    expect(multisigAccountId([ALICE, BOB, CHARLIE], 2)).toBe(account);
+    expect(multisigAccountId([CHARLIE, ALICE, BOB], 2)).toBe(account);
+    expect(multisigAccountId([ALICE, BOB, CHARLIE], 3)).not.toBe(account);
+  });
+});
+
+describe('delegatedSteps', () => {
+  const decoded = {
+    decodedCall: { type: 'Referenda', value: { type: 'submit' } },
+    getEncodedData: () => Binary.fromHex('0x1500'),
+  } as any;
+  const encoded = (hex: string) => ({
+    getEncodedData: vi.fn().mockResolvedValue(Binary.fromHex(hex)),
+  });
+
+  it('wraps the call in Proxy.proxy', async () => {
+    const proxy = vi.fn(() => encoded('0x2a00'));
+    const api = { tx: { Proxy: { proxy } } } as any;
+
+    const steps = await delegatedSteps(api, decoded, parseProxySubmission(REAL)!);
+
+    expect(steps).toEqual([{ name: 'Proxy.proxy', signer: 'the proxy', hex: '0x2a00' }]);
+    expect(proxy).toHaveBeenCalledWith(expect.objectContaining({ call: decoded.decodedCall }));
+  });
+
+  it('only encodes the first multisig approval, as later calls need its timepoint', async () => {
+    const api = {
+      tx: { Multisig: { approve_as_multi: vi.fn(() => encoded('0x1f01')) } },
+    } as any;
+
+    const multisig = parseMultisigSubmission(`3:${ALICE},${BOB},${CHARLIE}`)!;
+
+    const steps = await delegatedSteps(api, decoded, multisig);
+
+    expect(steps.map((step) => [step.name, step.signer, step.hex])).toEqual([
+      ['Multisig.approve_as_multi', ALICE, '0x1f01'],
+      ['Multisig.approve_as_multi', BOB, undefined],
+      ['Multisig.as_multi', CHARLIE, undefined],
+    ]);
+  });
+});
+
+describe('DelegatedSubmitter', () => {
+  it('approves the call hash before the last signatory executes it', async () => {
+    const approval = { sign: vi.fn().mockResolvedValue('0xapproval') };
+    const execution = { sign: vi.fn().mockResolvedValue('0xexecution') };
+    const multisigTx = {
+      approve_as_multi: vi.fn(() => approval),
+      as_multi: vi.fn(() => execution),
+    };
+    const api = {
+      query: {
+        System: { Events: { getValue: vi.fn().mockResolvedValue(NEW_MULTISIG_EVENTS) } },
+        Multisig: {
+          Multisigs: {
+            getValue: vi.fn().mockResolvedValue({ when: { height: 10, index: 1 } }),
+          },
+        },
+      },
+      tx: { Multisig: multisigTx },
+    } as any;
+    const chopsticks = {
+      setStorageBatch: vi.fn().mockResolvedValue(undefined),
+      newBlock: vi.fn().mockResolvedValue(undefined),
+    } as any;
+    const decoded = {
+      decodedCall: { type: 'Referenda', value: { type: 'submit' } },
+      getEncodedData: () => Binary.fromHex('0x1500'),
+    } as any;
+
+    const submitter = new DelegatedSubmitter(createSilentLogger(), chopsticks);
+    const signed = await submitter.sign(api, {} as any, decoded, {
+      kind: 'multisig',
+      threshold: 2,
+      signatories: [ALICE, BOB, CHARLIE],
+    });
+
+    expect(signed).toBe('0xexecution');
+    expect(multisigTx.approve_as_multi).toHaveBeenCalledWith(
+      expect.objectContaining({ other_signatories: [BOB, CHARLIE], maybe_timepoint: undefined })
+    );
+    expect(chopsticks.newBlock).toHaveBeenCalledWith({ transactions: ['0xapproval'] });
+    expect(multisigTx.as_multi).toHaveBeenCalledWith(
+      expect.objectContaining({
+        other_signatories: [CHARLIE, ALICE],
+        maybe_timepoint: { height: 10, index: 1 },
+        call: decoded.decodedCall,
+      })
+    );
+  });
+});
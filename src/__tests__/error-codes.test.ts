@@ -0,0 +1,70 @@
+import { describe, expect, it } from 'vitest';
+import {
+  CodedError,
+  ErrorCode,
+  errorCodeOf,
+  exitCodeOf,
+  formatCodedError,
+} from '../utils/error-codes';
+import { validateFlagRules } from '../utils/flag-rules';
+
+describe('errorCodeOf', () => {
+  it('returns the code of a CodedError', () => {
+    expect(errorCodeOf(new CodedError(ErrorCode.InvalidHex, 'bad hex'))).toBe('E-INVALID-HEX');
+  });
+
+  it('follows the cause chain of wrapped errors', () => {
+    const root = new CodedError(ErrorCode.ReferendumNotOngoing, 'not ongoing');
+    const wrapped = new Error('Failed to force referendum execution', { cause: root });
+    expect(errorCodeOf(wrapped)).toBe(ErrorCode.ReferendumNotOngoing);
+  });
+
+  it('falls back to E-UNKNOWN', () => {
+    expect(errorCodeOf(new Error('boom'))).toBe(ErrorCode.Unknown);
+    expect(errorCodeOf('boom')).toBe(ErrorCode.Unknown);
+  });
+});
+
+describe('formatCodedError', () => {
+  it('prefixes the message with the code', () => {
+    const error = new CodedError(ErrorCode.PreimageMissing, 'Governance execution failed');
+    expect(formatCodedError(error)).toBe('E-PREIMAGE-MISSING: Governance execution failed');
+  });
+
+  it('uses the outer message with the root code', () => {
+    const wrapped = new Error('outer', { cause: new CodedError(ErrorCode.InvalidHex, 'inner') });
+    expect(formatCodedError(wrapped)).toBe('E-INVALID-HEX: outer');
+  });
+});
+
+describe('exitCodeOf', () => {
+  it('exits with 2 when the scheduler never dispatched the proposal', () => {
+    expect(exitCodeOf(new CodedError(ErrorCode.PreimageMissing, 'missing'))).toBe(2);
+    expect(exitCodeOf(new CodedError(ErrorCode.PermanentlyOverweight, 'too heavy'))).toBe(2);
+    expect(exitCodeOf(new CodedError(ErrorCode.EnactmentPostponed, 'postponed'))).toBe(2);
+  });
+
+  it('exits with 130 when the run was interrupted', () => {
+    expect(exitCodeOf(new CodedError(ErrorCode.Interrupted, 'Interrupted by SIGINT'))).toBe(130);
+  });
+
+  it('exits with 3 when only one referendum of a pair passed', () => {
+    expect(exitCodeOf(new CodedError(ErrorCode.PartialSuccess, 'partial'))).toBe(3);
+  });
+
+  it('exits with 1 for any other failure', () => {
+    expect(exitCodeOf(new CodedError(ErrorCode.ExecutionFailed, 'failed'))).toBe(1);
+    expect(exitCodeOf(new Error('boom'))).toBe(1);
+  });
+});
+
+describe('flag rule codes', () => {
+  it('codes flag violations from the rule table', () => {
+    expect(() => validateFlagRules({ governanceChainUrl: 'wss://example.com' })).toThrow(
+      expect.objectContaining({ code: ErrorCode.NoReferendum })
+    );
+    expect(() => validateFlagRules({ referendum: '1' })).toThrow(
+      expect.objectContaining({ code: ErrorCode.MissingChainUrl })
+    );
+  });
+});
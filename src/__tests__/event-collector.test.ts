@@ -0,0 +1,101 @@
+import { describe, expect, it, vi } from 'vitest';
+import type { ChopsticksManager } from '../services/chopsticks-manager';
+import {
+  EventCollector,
+  parseBlocksAfterDispatch,
+  parseSettleBlocks,
+} from '../services/event-collector';
+import type { Logger } from '../utils/logger';
+
+vi.mock('../services/chain-registry', () => ({
+  createPolkadotClient: () => ({ destroy: vi.fn() }),
+  createApiForChain: () => ({
+    query: {
+      System: {
+        Number: { getValue: vi.fn().mockResolvedValue(10) },
+        Events: { getValue: vi.fn().mockResolvedValue([]) },
+      },
+    },
+  }),
+}));
+
+function createMockLogger(): Logger {
+  return {
+    info: vi.fn(),
+    debug: vi.fn(),
+    error: vi.fn(),
+    section: vi.fn(),
+    isVerbose: () => false,
+  } as unknown as Logger;
+}
+
+function mockManager(order: string[], label: string): ChopsticksManager {
+  return {
+    newBlock: vi.fn(async () => {
+      order.push(label);
+    }),
+    getContext: () => ({ ws: { endpoint: `ws://${label}` } }),
+  } as unknown as ChopsticksManager;
+}
+
+describe('parseSettleBlocks', () => {
+  it('defaults to one block', () => {
+    expect(parseSettleBlocks(undefined)).toBe(1);
+  });
+
+  it('accepts positive integers', () => {
+    expect(parseSettleBlocks('5')).toBe(5);
+  });
+
+  it.each(['0', '-1', 'abc', '2.5'])('rejects %s with E-INVALID-SETTLE-BLOCKS', (value) => {
+    expect(() => parseSettleBlocks(value)).toThrow(
+      expect.objectContaining({ code: 'E-INVALID-SETTLE-BLOCKS' })
+    );
+  });
+});
+
+describe('parseBlocksAfterDispatch', () => {
+  it('defaults to no extra blocks and accepts zero', () => {
+    expect(parseBlocksAfterDispatch(undefined)).toBe(0);
+    expect(parseBlocksAfterDispatch('0')).toBe(0);
+    expect(parseBlocksAfterDispatch('3')).toBe(3);
+  });
+
+  it.each(['-1', 'abc', '2.5'])('rejects %s with E-INVALID-BLOCKS-AFTER-DISPATCH', (value) => {
+    expect(() => parseBlocksAfterDispatch(value)).toThrow(
+      expect.objectContaining({ code: 'E-INVALID-BLOCKS-AFTER-DISPATCH' })
+    );
+  });
+});
+
+describe('EventCollector.collectAdditionalChainEvents', () => {
+  it('advances every additional chain round-robin for each settle block', async () => {
+    const order: string[] = [];
+    const logger = createMockLogger();
+    const collector = new EventCollector(logger, 3);
+
+    await collector.collectAdditionalChainEvents(
+      new Map([
+        ['Relay', mockManager(order, 'Relay')],
+        ['People', mockManager(order, 'People')],
+      ])
+    );
+
+    expect(order).toEqual(['Relay', 'People', 'Relay', 'People', 'Relay', 'People']);
+    expect(logger.info).toHaveBeenCalledWith(
+      expect.stringContaining('Relay [settle block 3/3] (Block #10)')
+    );
+  });
+
+  it('stops advancing a chain after it fails', async () => {
+    const logger = createMockLogger();
+    const broken = {
+      newBlock: vi.fn().mockRejectedValue(new Error('ws closed')),
+    } as unknown as ChopsticksManager;
+
+    await new EventCollector(logger, 3).collectAdditionalChainEvents(new Map([['Relay', broken]]));
+
+    expect(broken.newBlock).toHaveBeenCalledTimes(1);
+    expect(logger.error).toHaveBeenCalledWith(expect.stringContaining('ws closed'));
+  });
+});
@@ -1,5 +1,7 @@
-import { describe, expect, it } from 'vitest';
-import { parseBlockEvent, serializeEventData } from '../utils/event-serializer';
+import { describe, expect, it, vi } from 'vitest';
+import { ErrorCode } from '../utils/error-codes';
+import { expectEvent, parseBlockEvent, serializeEventData } from '../utils/event-serializer';
+import { createSilentLogger } from './test-logger';
 
 describe('serializeEventData', () => {
   it('returns null/undefined as-is', () => {
@@ -167,3 +169,51 @@ describe('parseBlockEvent', () => {
     expect(parsed.data).toBe(42);
   });
 });
+
+describe('expectEvent', () => {
+  const event = (section: string, method: string, value: unknown = {}) => ({
+    type: section,
+    value: { type: method, value },
+  });
+  const run = (events: unknown[]) => {
+    const chain = { newBlock: vi.fn(async () => {}) };
+    const logger = createSilentLogger();
+    const expected = {
+      section: 'Preimage',
+      method: 'Noted',
+      step: 'Noting metadata',
+      code: ErrorCode.InvalidMetadata,
+    };
+    const result = expectEvent(chain, { getValue: async () => events }, '0x01', expected, logger);
+    return { chain, logger, result };
+  };
+
+  it('builds a block with the extrinsic and resolves on the expected event', async () => {
+    const { chain, logger, result } = run([event('Preimage', 'Noted')]);
+    await expect(result).resolves.toBeUndefined();
+    expect(chain.newBlock).toHaveBeenCalledWith({ transactions: ['0x01'] });
+    expect(logger.failSpinner).not.toHaveBeenCalled();
+  });
+
+  it('fails with the given code and the dispatch error', async () => {
+    const { logger, result } = run([event('System', 'ExtrinsicFailed', { type: 'BadOrigin' })]);
+    await expect(result).rejects.toMatchObject({
+      code: ErrorCode.InvalidMetadata,
+      message: 'Noting metadata was not accepted: BadOrigin',
+    });
+    expect(logger.failSpinner).toHaveBeenCalledWith('Noting metadata failed');
+  });
+
+  it('reports the error of a call wrapped in a proxy', async () => {
+    const executed = event('Proxy', 'ProxyExecuted', {
+      result: { success: false, value: { type: 'Module', value: 'NotProxy' } },
+    });
+    await expect(run([executed]).result).rejects.toThrow(
+      'Noting metadata was not accepted: Module'
+    );
+  });
+
+  it('fails without a reason when the block has no matching event', async () => {
+    await expect(run([]).result).rejects.toThrow(/^Noting metadata was not accepted$/);
+  });
+});
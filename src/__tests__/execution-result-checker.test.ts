@@ -1,26 +1,7 @@
-import { describe, expect, it, vi } from 'vitest';
-import { ExecutionResultChecker } from '../services/execution-result-checker';
+import { describe, expect, it } from 'vitest';
+import { ExecutionResultChecker, findScheduledTasks } from '../services/execution-result-checker';
 import type { ParsedEvent } from '../utils/event-serializer';
-import type { Logger } from '../utils/logger';
-
-/** Minimal logger stub that satisfies the Logger interface without producing output. */
-function createSilentLogger(): Logger {
-  return {
-    info: vi.fn(),
-    debug: vi.fn(),
-    warn: vi.fn(),
-    error: vi.fn(),
-    success: vi.fn(),
-    isVerbose: () => false,
-    startSpinner: vi.fn(),
-    succeedSpinner: vi.fn(),
-    failSpinner: vi.fn(),
-    updateSpinner: vi.fn(),
-    stopSpinner: vi.fn(),
-    section: vi.fn(),
-    table: vi.fn(),
-  } as unknown as Logger;
-}
+import { createSilentLogger } from './test-logger';
 
 function makeDispatchedEvent(
   taskBlock: number,
@@ -39,6 +20,14 @@ function makeDispatchedEvent(
   };
 }
 
+function makeSchedulerEvent(method: string, taskBlock: number, taskIndex: number): ParsedEvent {
+  return {
+    section: 'Scheduler',
+    method,
+    data: { value: { task: [taskBlock, taskIndex], id: undefined } },
+  };
+}
+
 function makeDispatchedEventWithId(
   taskBlock: number,
   taskIndex: number,
@@ -302,4 +291,48 @@ describe('ExecutionResultChecker', () => {
     const result = checker.checkExecutionResults(events, 200, 0, expectedId);
     expect(result.executionSucceeded).toBe(true);
   });
+
+  it('explains a missing preimage reported as CallUnavailable', () => {
+    const checker = new ExecutionResultChecker(createSilentLogger());
+    const events = [makeSchedulerEvent('CallUnavailable', 100, 0)];
+    const result = checker.checkExecutionResults(events, 100, 0);
+    expect(result.executionSucceeded).toBe(false);
+    expect(result.notDispatched).toBe('call-unavailable');
+    expect(result.errors?.[0]).toContain(
+      "Scheduler.CallUnavailable for task [100, 0]: the proposal's preimage was not available"
+    );
+  });
+
+  it('explains a call too heavy to ever dispatch as PermanentlyOverweight', () => {
+    const checker = new ExecutionResultChecker(createSilentLogger());
+    const events = [makeSchedulerEvent('PermanentlyOverweight', 100, 0)];
+    const result = checker.checkExecutionResults(events, 100, 0);
+    expect(result.notDispatched).toBe('permanently-overweight');
+    expect(result.errors?.[0]).toContain('it is not postponed to the next block');
+  });
+
+  it('ignores an undispatched task other than the expected one', () => {
+    const checker = new ExecutionResultChecker(createSilentLogger());
+    const events = [
+      makeSchedulerEvent('CallUnavailable', 100, 1),
+      makeDispatchedEvent(100, 0, 'Ok'),
+    ];
+    const result = checker.checkExecutionResults(events, 100, 0);
+    expect(result).toEqual({ executionSucceeded: true });
+  });
+});
+
+describe('findScheduledTasks', () => {
+  it('lists the agenda slots of Scheduler.Scheduled events', () => {
+    const events: ParsedEvent[] = [
+      { section: 'Scheduler', method: 'Scheduled', data: { value: { when: 5000, index: 1 } } },
+      makeDispatchedEvent(100, 0, 'Ok'),
+      { section: 'Scheduler', method: 'Scheduled', data: { when: 6000, index: 0 } },
+    ];
+
+    expect(findScheduledTasks(events)).toEqual([
+      { when: 5000, index: 1 },
+      { when: 6000, index: 0 },
+    ]);
+  });
 });
@@ -0,0 +1,111 @@
+import { describe, expect, it } from 'vitest';
+import { ErrorCode } from '../utils/error-codes';
+import { FLAG_RULES, validateFlagRules } from '../utils/flag-rules';
+
+const GOV_URL = 'wss://example.com';
+const PROXY = FLAG_RULES.flags['--submit-via-proxy'].sample;
+const MULTISIG = FLAG_RULES.flags['--submit-via-multisig'].sample;
+const HASH = FLAG_RULES.flags['--metadata-for-fellowship-referendum'].sample;
+
+describe('validateFlagRules', () => {
+  it('accepts a governance referendum with its chain URL', () => {
+    expect(() => validateFlagRules({ referendum: '1', governanceChainUrl: GOV_URL })).not.toThrow();
+  });
+
+  it('rejects mutually exclusive governance flags', () => {
+    expect(() =>
+      validateFlagRules({
+        referendum: '1',
+        callToCreateGovernanceReferendum: '0x00',
+        governanceChainUrl: GOV_URL,
+      })
+    ).toThrow('Cannot specify both --referendum');
+  });
+
+  it('requires at least one referendum', () => {
+    expect(() => validateFlagRules({ governanceChainUrl: GOV_URL })).toThrow(
+      'At least one referendum must be specified'
+    );
+  });
+
+  it('requires the governance chain URL', () => {
+    expect(() => validateFlagRules({ referendum: '1' })).toThrow(
+      '--governance-chain-url is required'
+    );
+  });
+
+  it('requires the fellowship chain URL for created fellowship referenda', () => {
+    expect(() => validateFlagRules({ callToCreateFellowshipReferendum: '0x00' })).toThrow(
+      '--fellowship-chain-url is required'
+    );
+  });
+
+  it('rejects proxy and multisig submission together', () => {
+    expect(() =>
+      validateFlagRules({
+        callToCreateGovernanceReferendum: '0x00',
+        governanceChainUrl: GOV_URL,
+        submitViaProxy: PROXY,
+        submitViaMultisig: MULTISIG,
+      })
+    ).toThrow(expect.objectContaining({ code: ErrorCode.FlagConflict }));
+  });
+
+  it('requires a governance referendum to create for delegated submission', () => {
+    const existing = { referendum: '1', governanceChainUrl: GOV_URL };
+    expect(() => validateFlagRules({ ...existing, submitViaProxy: PROXY })).toThrow(
+      expect.objectContaining({ code: ErrorCode.InvalidProxy })
+    );
+    expect(() => validateFlagRules({ ...existing, submitViaMultisig: MULTISIG })).toThrow(
+      expect.objectContaining({ code: ErrorCode.InvalidMultisig })
+    );
+  });
+
+  it('requires a referendum to create for its metadata', () => {
+    expect(() =>
+      validateFlagRules({
+        referendum: '1',
+        governanceChainUrl: GOV_URL,
+        metadataForFellowshipReferendum: HASH,
+      })
+    ).toThrow(expect.objectContaining({ code: ErrorCode.InvalidMetadata }));
+  });
+
+  it('rejects non-numeric referendum IDs with the value in the message', () => {
+    expect(() => validateFlagRules({ referendum: 'abc', governanceChainUrl: GOV_URL })).toThrow(
+      'Invalid referendum ID: abc'
+    );
+  });
+
+  it('accepts a referenda pallet prefix on --referendum', () => {
+    expect(() =>
+      validateFlagRules({ referendum: 'AmbassadorReferenda:12', governanceChainUrl: GOV_URL })
+    ).not.toThrow();
+    expect(() =>
+      validateFlagRules({ referendum: 'AmbassadorReferenda:x', governanceChainUrl: GOV_URL })
+    ).toThrow('Invalid referendum ID: AmbassadorReferenda:x');
+  });
+
+  it('only references flags defined in the table', () => {
+    const referenced = [
+      ...FLAG_RULES.mutuallyExclusive.flatMap((rule) => rule.flags),
+      ...FLAG_RULES.atLeastOneOf.flatMap((rule) => rule.flags),
+      ...FLAG_RULES.requires.flatMap((rule) => [...rule.whenAny, rule.requires]),
+    ];
+    for (const flag of referenced) {
+      expect(FLAG_RULES.flags[flag], flag).toBeDefined();
+    }
+  });
+
+  it('gives every rule a unique name', () => {
+    const names = [
+      ...FLAG_RULES.mutuallyExclusive.map((rule) => rule.name),
+      ...FLAG_RULES.atLeastOneOf.map((rule) => rule.name),
+      ...FLAG_RULES.requires.map((rule) => rule.name),
+      ...Object.values(FLAG_RULES.flags).flatMap((flag) =>
+        flag.integer ? [flag.integer.name] : []
+      ),
+    ];
+    expect(new Set(names).size).toBe(names.length);
+  });
+});
@@ -0,0 +1,93 @@
+import { describe, expect, it, vi } from 'vitest';
+import {
+  describeGovernanceV1Event,
+  findGovernanceV1Pallets,
+  type GovernanceV1Metadata,
+  governanceV1PalletNames,
+} from '../services/governance-v1';
+import { displayChainEvents } from '../utils/event-serializer';
+import type { Logger } from '../utils/logger';
+
+const storage = (...names: string[]) => ({ items: names.map((name) => ({ name })) });
+
+const METADATA: GovernanceV1Metadata = {
+  pallets: [
+    { name: 'System', storage: storage('Account', 'Number') },
+    { name: 'Democracy', storage: storage('PublicProps', 'ReferendumInfoOf', 'VotingOf') },
+    { name: 'Council', storage: storage('Proposals', 'ProposalOf', 'Voting', 'Members', 'Prime') },
+    { name: 'TechCommittee', storage: storage('ProposalOf', 'Voting', 'Members', 'Prime') },
+    { name: 'TechnicalMembership', storage: storage('Members', 'Prime') },
+    { name: 'Referenda', storage: storage('ReferendumCount', 'ReferendumInfoFor') },
+  ],
+};
+
+const PALLETS = { democracy: 'Democracy', collectives: ['Council', 'TechCommittee'] };
+const HASH = `0x${'ab'.repeat(32)}`;
+
+describe('findGovernanceV1Pallets', () => {
+  it('finds Democracy and collective instances by their storage', () => {
+    expect(findGovernanceV1Pallets(METADATA)).toEqual(PALLETS);
+    expect(governanceV1PalletNames(PALLETS)).toEqual(['Democracy', 'Council', 'TechCommittee']);
+  });
+
+  it('finds nothing on an OpenGov chain', () => {
+    const openGov = { pallets: METADATA.pallets.filter((p) => p.name === 'Referenda') };
+    expect(governanceV1PalletNames(findGovernanceV1Pallets(openGov))).toEqual([]);
+  });
+});
+
+describe('describeGovernanceV1Event', () => {
+  const describeEvent = (section: string, method: string, data: unknown) =>
+    describeGovernanceV1Event(PALLETS, { section, method, data });
+
+  it('describes the Democracy referendum lifecycle', () => {
+    expect(
+      describeEvent('Democracy', 'Started', { ref_index: 4, threshold: { type: 'SimpleMajority' } })
+    ).toBe('referendum #4 started (SimpleMajority)');
+    expect(
+      describeEvent('Democracy', 'Voted', {
+        voter: 'alice',
+        ref_index: 4,
+        vote: { type: 'Standard', value: { vote: 0x81, balance: 10n } },
+      })
+    ).toBe('alice voted aye on referendum #4');
+    expect(describeEvent('Democracy', 'Passed', { ref_index: 4 })).toBe('referendum #4 passed');
+  });
+
+  it('describes motions of every collective instance', () => {
+    expect(
+      describeEvent('TechCommittee', 'Proposed', {
+        account: 'bob',
+        proposal_index: 2,
+        proposal_hash: HASH,
+        threshold: 3,
+      })
+    ).toBe('motion #2 0xabababab… proposed by bob (threshold 3)');
+    expect(
+      describeEvent('Council', 'Executed', {
+        proposal_hash: HASH,
+        result: { success: false, value: { type: 'BadOrigin' } },
+      })
+    ).toBe('motion 0xabababab… executed: failed (BadOrigin)');
+  });
+
+  it('leaves other events alone', () => {
+    expect(describeEvent('Balances', 'Transfer', {})).toBeUndefined();
+    expect(describeEvent('Democracy', 'SomethingNew', {})).toBeUndefined();
+  });
+});
+
+describe('displayChainEvents', () => {
+  it('appends the description of an event', () => {
+    const logger = { info: vi.fn(), isVerbose: () => false } as unknown as Logger;
+    const event = { type: 'Council', value: { type: 'Approved', value: { proposal_hash: HASH } } };
+
+    displayChainEvents('Kusama Para', 7, [event], logger, (parsed) =>
+      describeGovernanceV1Event(PALLETS, parsed)
+    );
+
+    expect(logger.info).toHaveBeenCalledWith(
+      '  • Council.Approved — motion 0xabababab… approved'
+    );
+  });
+});
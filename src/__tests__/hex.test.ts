@@ -0,0 +1,54 @@
+import { describe, expect, it } from 'vitest';
+import type { TestOptions } from '../types';
+import { normalizeCallData, normalizeCallDataOptions, toHexString } from '../utils/hex';
+
+describe('toHexString', () => {
+  it('prefixes strings and encodes bytes', () => {
+    expect(toHexString('abcd')).toBe('0xabcd');
+    expect(toHexString(new Uint8Array([0xab, 0xcd]))).toBe('0xabcd');
+    expect(toHexString(undefined)).toBeUndefined();
+  });
+});
+
+describe('normalizeCallData', () => {
+  it('keeps canonical call data', () => {
+    expect(normalizeCallData('0x0001', 'call')).toBe('0x0001');
+  });
+
+  it('drops surrounding and embedded whitespace', () => {
+    expect(normalizeCallData('  0x0001\n', 'call')).toBe('0x0001');
+    expect(normalizeCallData('0x00\r\n01 \t02', 'call')).toBe('0x000102');
+  });
+
+  it('adds a missing 0x and lower-cases', () => {
+    expect(normalizeCallData('3E00AB', 'call')).toBe('0x3e00ab');
+    expect(normalizeCallData('0X3E00', 'call')).toBe('0x3e00');
+  });
+
+  it('rejects non-hex input', () => {
+    expect(() => normalizeCallData('0xzz', '--pre-call')).toThrow(
+      expect.objectContaining({
+        code: 'E-INVALID-HEX',
+        message: 'Invalid hex string for --pre-call: 0xzz',
+      })
+    );
+  });
+});
+
+describe('normalizeCallDataOptions', () => {
+  it('normalizes every call-data option and names the flag on failure', () => {
+    const options = {
+      callToCreateGovernanceReferendum: ' 3E00\n',
+      callToNotePreimageForGovernanceReferendum: '0X0500',
+    } as TestOptions;
+
+    normalizeCallDataOptions(options);
+
+    expect(options.callToCreateGovernanceReferendum).toBe('0x3e00');
+    expect(options.callToNotePreimageForGovernanceReferendum).toBe('0x0500');
+    expect(options.callToCreateFellowshipReferendum).toBeUndefined();
+    expect(() =>
+      normalizeCallDataOptions({ callToCreateFellowshipReferendum: 'nope' } as TestOptions)
+    ).toThrow(/--call-to-create-fellowship-referendum/);
+  });
+});
@@ -0,0 +1,76 @@
+import * as fs from 'fs';
+import * as os from 'os';
+import * as path from 'path';
+import { afterAll, describe, expect, it } from 'vitest';
+import { HookRunner } from '../services/hook-runner';
+import { createSilentLogger } from './test-logger';
+
+const dir = fs.mkdtempSync(path.join(os.tmpdir(), 'hooks-'));
+
+function writeHook(name: string, source: string): string {
+  const file = path.join(dir, name);
+  fs.writeFileSync(file, source);
+  return file;
+}
+
+const context = {
+  endpoint: 'ws://127.0.0.1:9000',
+  referendumId: 7,
+  isFellowship: false,
+  block: 123,
+};
+
+describe('HookRunner', () => {
+  afterAll(() => {
+    fs.rmSync(dir, { recursive: true, force: true });
+  });
+
+  it('rejects missing scripts and unsupported extensions up front', () => {
+    expect(() => new HookRunner(createSilentLogger(), { 'after-dispatch': 'missing.js' })).toThrow(
+      expect.objectContaining({ code: 'E-INVALID-HOOK' })
+    );
+    const shell = writeHook('hook.sh', 'exit 0');
+    expect(() => new HookRunner(createSilentLogger(), { 'after-dispatch': shell })).toThrow(
+      expect.objectContaining({ code: 'E-INVALID-HOOK' })
+    );
+  });
+
+  it('passes the fork endpoint and simulation state as HOOK_* env vars', async () => {
+    const out = path.join(dir, 'env.json');
+    const hook = writeHook(
+      'env.js',
+      `require('fs').writeFileSync(${JSON.stringify(out)}, JSON.stringify({
+        phase: process.env.HOOK_PHASE,
+        endpoint: process.env.HOOK_RPC_ENDPOINT,
+        referendum: process.env.HOOK_REFERENDUM_ID,
+        block: process.env.HOOK_BLOCK,
+      }));`
+    );
+    const hooks = new HookRunner(createSilentLogger(), { 'before-dispatch': hook });
+
+    await hooks.run('before-dispatch', context);
+
+    expect(JSON.parse(fs.readFileSync(out, 'utf8'))).toEqual({
+      phase: 'before-dispatch',
+      endpoint: 'ws://127.0.0.1:9000',
+      referendum: '7',
+      block: '123',
+    });
+  });
+
+  it('fails with E-HOOK-FAILED when the hook exits non-zero', async () => {
+    const hook = writeHook('fail.js', 'process.exit(3);');
+    const hooks = new HookRunner(createSilentLogger(), { 'after-dispatch': hook });
+
+    await expect(hooks.run('after-dispatch', context)).rejects.toMatchObject({
+      code: 'E-HOOK-FAILED',
+      message: 'after-dispatch hook fail.js exited with code 3',
+    });
+  });
+
+  it('skips phases without a hook', async () => {
+    const hooks = new HookRunner(createSilentLogger(), {});
+    expect(hooks.hasHooks()).toBe(false);
+    await expect(hooks.run('after-passing', context)).resolves.toBeUndefined();
+  });
+});
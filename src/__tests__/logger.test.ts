@@ -0,0 +1,107 @@
+import chalk from 'chalk';
+import * as fs from 'fs';
+import * as os from 'os';
+import * as path from 'path';
+import { afterEach, describe, expect, it, vi } from 'vitest';
+import { BLOCK_LINE_PREFIX, HEADS_LINE_PREFIX, Logger } from '../utils/logger';
+
+describe('Logger.block', () => {
+  const logSpy = vi.spyOn(console, 'log').mockImplementation(() => {});
+
+  afterEach(() => {
+    logSpy.mockClear();
+  });
+
+  it('prints a machine-readable line with --emit-blocks', () => {
+    new Logger(false, true).block({ endpoint: 'ws://127.0.0.1:9000', number: 7, hash: '0xab' });
+    expect(logSpy).toHaveBeenCalledWith(
+      `${BLOCK_LINE_PREFIX}{"endpoint":"ws://127.0.0.1:9000","number":7,"hash":"0xab"}`
+    );
+  });
+
+  it('prints nothing by default', () => {
+    new Logger(true).block({ endpoint: 'ws://127.0.0.1:9000', number: 7 });
+    expect(logSpy).not.toHaveBeenCalled();
+  });
+});
+
+describe('Logger.heads', () => {
+  const logSpy = vi.spyOn(console, 'log').mockImplementation(() => {});
+
+  afterEach(() => {
+    logSpy.mockClear();
+  });
+
+  it('prints every head on one machine-readable line with --emit-blocks', () => {
+    const head = { chain: 'AH', endpoint: 'ws://x', fork: { number: 7 }, final: { number: 9 } };
+    new Logger(false, true).heads([head]);
+    expect(logSpy).toHaveBeenCalledWith(
+      `${HEADS_LINE_PREFIX}[{"chain":"AH","endpoint":"ws://x","fork":{"number":7},"final":{"number":9}}]`
+    );
+  });
+
+  it('prints nothing by default', () => {
+    new Logger(true).heads([]);
+    expect(logSpy).not.toHaveBeenCalled();
+  });
+});
+
+describe('Logger run id', () => {
+  const logSpy = vi.spyOn(console, 'log').mockImplementation(() => {});
+
+  afterEach(() => {
+    logSpy.mockClear();
+  });
+
+  it('prefixes every human-readable line with the run id', () => {
+    const logger = new Logger(true, false, 'gov_create-4242-1');
+    logger.info('Forked');
+    logger.debug('Chopsticks config');
+    logger.table({ Referendum: 7 });
+
+    const tag = chalk.gray('[gov_create-4242-1]');
+    expect(logSpy).toHaveBeenCalledWith(tag, chalk.blue('ℹ'), 'Forked');
+    expect(logSpy).toHaveBeenCalledWith(tag, chalk.gray('▸'), chalk.gray('Chopsticks config'));
+    expect(logSpy).toHaveBeenCalledWith(tag, `  ${chalk.gray('Referendum'.padEnd(20))}: 7`);
+  });
+
+  it('leaves machine-readable lines alone', () => {
+    new Logger(false, true, 'gov_create-4242-1').block({ endpoint: 'ws://x', number: 7 });
+    expect(logSpy).toHaveBeenCalledWith(`${BLOCK_LINE_PREFIX}{"endpoint":"ws://x","number":7}`);
+  });
+
+  it('prints lines unprefixed without a run id', () => {
+    new Logger().info('Forked');
+    expect(logSpy).toHaveBeenCalledWith(chalk.blue('ℹ'), 'Forked');
+  });
+});
+
+describe('Logger.writeTo', () => {
+  const logSpy = vi.spyOn(console, 'log').mockImplementation(() => {});
+
+  afterEach(() => {
+    logSpy.mockClear();
+  });
+
+  it('writes every line to the log file without colors, debug lines included', () => {
+    const dir = fs.mkdtempSync(path.join(os.tmpdir(), 'logger-test-'));
+    try {
+      const logger = new Logger(false, false, 'run-1');
+      logger.writeTo(path.join(dir, 'tool.log'));
+      logger.info('Forked');
+      logger.debug('Chopsticks config');
+      expect(fs.readFileSync(path.join(dir, 'tool.log'), 'utf8')).toBe(
+        'ℹ Forked\n▸ Chopsticks config\n'
+      );
+      expect(logSpy).toHaveBeenCalledTimes(1);
+    } finally {
+      fs.rmSync(dir, { recursive: true, force: true });
+    }
+  });
+
+  it('stops writing once the log file is gone', () => {
+    const logger = new Logger();
+    logger.writeTo(path.join(os.tmpdir(), 'no-such-dir-for-logger-test', 'tool.log'));
+    expect(() => logger.info('Forked')).not.toThrow();
+  });
+});
@@ -40,6 +40,8 @@ describe('NetworkCoordinator routing logic', () => {
       getGovernanceEndpoint: vi.fn(),
       getFellowshipBlock: vi.fn().mockReturnValue(undefined),
       getGovernanceBlock: vi.fn().mockReturnValue(undefined),
+      getFellowshipLabel: vi.fn().mockReturnValue(undefined),
+      getGovernanceLabel: vi.fn().mockReturnValue(undefined),
       hasAdditionalChains: vi.fn().mockReturnValue(false),
       detectChainTypes: vi.fn().mockResolvedValue(undefined),
       governanceChain: undefined,
@@ -63,6 +65,28 @@ describe('NetworkCoordinator routing logic', () => {
       expect(spies.testMultiChain).not.toHaveBeenCalled();
     });
 
+    it('passes a --referendum pallet to runSingleChainTest', async () => {
+      (mockTopology.getGovernanceEndpoint as ReturnType<typeof vi.fn>).mockReturnValue(
+        'wss://gov.example.com'
+      );
+      const spies = spyOnRouting();
+
+      await coordinator.testWithFellowship(12, undefined, true, undefined, 'AmbassadorReferenda');
+
+      expect(spies.runSingleChainTest).toHaveBeenCalledWith(
+        expect.objectContaining({ referendumId: 12, referendaPallet: 'AmbassadorReferenda' })
+      );
+    });
+
+    it('rejects a --referendum pallet combined with a fellowship referendum', async () => {
+      const spies = spyOnRouting();
+
+      await expect(
+        coordinator.testWithFellowship(12, 5, true, undefined, 'AmbassadorReferenda')
+      ).rejects.toThrow('cannot be combined with a fellowship referendum');
+      expect(spies.testMultiChain).not.toHaveBeenCalled();
+    });
+
     it('routes governance-only with additional chains to runSingleChainWithAdditionalChains', async () => {
       (mockTopology.getGovernanceEndpoint as ReturnType<typeof vi.fn>).mockReturnValue(
         'wss://gov.example.com'
@@ -111,8 +135,8 @@ describe('NetworkCoordinator routing logic', () => {
         'wss://same.example.com'
       );
       (mockTopology.detectChainTypes as ReturnType<typeof vi.fn>).mockImplementation(() => {
-        mockTopology.governanceChain = { label: 'collectives-polkadot' };
-        mockTopology.fellowshipChain = { label: 'collectives-polkadot' };
+        mockTopology.governanceChain = { id: 'collectives-polkadot' };
+        mockTopology.fellowshipChain = { id: 'collectives-polkadot' };
         return Promise.resolve();
       });
       const spies = spyOnRouting();
@@ -131,8 +155,8 @@ describe('NetworkCoordinator routing logic', () => {
         'wss://fell.example.com'
       );
       (mockTopology.detectChainTypes as ReturnType<typeof vi.fn>).mockImplementation(() => {
-        mockTopology.governanceChain = { label: 'asset-hub-polkadot' };
-        mockTopology.fellowshipChain = { label: 'collectives-polkadot' };
+        mockTopology.governanceChain = { id: 'asset-hub-polkadot' };
+        mockTopology.fellowshipChain = { id: 'collectives-polkadot' };
         return Promise.resolve();
       });
       const spies = spyOnRouting();
@@ -157,6 +181,7 @@ describe('NetworkCoordinator routing logic', () => {
       expect(spies.runSingleChainTest).toHaveBeenCalledWith({
         endpoint: 'wss://gov.example.com',
         block: 100,
+        label: undefined,
         referendumId: 42,
         isFellowship: false,
         storageInjection: undefined,
@@ -172,6 +197,7 @@ describe('NetworkCoordinator routing logic', () => {
         'wss://fell.example.com'
       );
       (mockTopology.getFellowshipBlock as ReturnType<typeof vi.fn>).mockReturnValue(200);
+      (mockTopology.getFellowshipLabel as ReturnType<typeof vi.fn>).mockReturnValue('Collectives');
       const spies = spyOnRouting();
       const options = { callToCreateFellowshipReferendum: '0xdeadbeef' } as any;
 
@@ -180,6 +206,7 @@ describe('NetworkCoordinator routing logic', () => {
       expect(spies.runSingleChainTest).toHaveBeenCalledWith({
         endpoint: 'wss://fell.example.com',
         block: 200,
+        label: 'Collectives',
         referendumId: undefined,
         isFellowship: true,
         storageInjection: 'fellowship',
@@ -0,0 +1,141 @@
+import { Binary } from 'polkadot-api';
+import { describe, expect, it, vi } from 'vitest';
+import {
+  findOrigins,
+  formatOriginTable,
+  type OriginMetadata,
+  OriginSelfTest,
+} from '../services/origin-selftest';
+import { createSilentLogger } from './test-logger';
+
+// OriginCaller (1) -> system RawOrigin (2), Origins (3), Void (4)
+const METADATA: OriginMetadata = {
+  lookup: [
+    {
+      id: 1,
+      path: ['asset_hub_polkadot_runtime', 'OriginCaller'],
+      def: {
+        tag: 'variant',
+        value: [
+          { name: 'system', fields: [{ type: 2 }] },
+          { name: 'Origins', fields: [{ type: 3 }] },
+          { name: 'Void', fields: [{ type: 4 }] },
+        ],
+      },
+    },
+    {
+      id: 2,
+      path: ['frame_support', 'dispatch', 'RawOrigin'],
+      def: {
+        tag: 'variant',
+        value: [
+          { name: 'Root', fields: [] },
+          { name: 'Signed', fields: [{ type: 0 }] },
+          { name: 'None', fields: [] },
+        ],
+      },
+    },
+    {
+      id: 3,
+      path: ['asset_hub_polkadot_runtime', 'governance', 'pallet_custom_origins', 'Origin'],
+      def: { tag: 'variant', value: [{ name: 'WhitelistedCaller', fields: [] }] },
+    },
+    { id: 4, path: ['sp_core', 'Void'], def: { tag: 'variant', value: [] } },
+  ],
+  pallets: [{ name: 'System' }, { name: 'Referenda' }],
+};
+
+function dispatched(block: number, result: unknown) {
+  return {
+    type: 'Scheduler',
+    value: { type: 'Dispatched', value: { task: [block, 0], id: undefined, result } },
+  };
+}
+
+function createMocks(events: unknown[][]) {
+  const chopsticks = {
+    newBlock: vi.fn().mockResolvedValue(undefined),
+    setStorageBatch: vi.fn().mockResolvedValue(undefined),
+  } as any;
+  const Events = { getValue: vi.fn() };
+  for (const blockEvents of events) Events.getValue.mockResolvedValueOnce(blockEvents);
+  const api = {
+    query: {
+      System: { Number: { getValue: vi.fn().mockResolvedValue(100) }, Events },
+    },
+    tx: {
+      System: {
+        remark: vi.fn(() => ({
+          getEncodedData: vi.fn().mockResolvedValue(Binary.fromHex('0x0000')),
+        })),
+      },
+    },
+  } as any;
+  return { chopsticks, api };
+}
+
+describe('findOrigins', () => {
+  it('lists every variant of every pallet origin in OriginCaller', () => {
+    expect(findOrigins(METADATA)).toEqual([
+      { name: 'system.Root', fields: 0 },
+      { name: 'system.Signed', fields: 1 },
+      { name: 'system.None', fields: 0 },
+      { name: 'Origins.WhitelistedCaller', fields: 0 },
+    ]);
+  });
+
+  it('rejects metadata without an OriginCaller', () => {
+    expect(() => findOrigins({ lookup: [], pallets: [] })).toThrow('no OriginCaller type');
+  });
+});
+
+describe('OriginSelfTest', () => {
+  it('dispatches a remark from each origin without fields', async () => {
+    const { chopsticks, api } = createMocks([
+      [dispatched(101, { success: true })],
+      [dispatched(101, { success: false, value: { type: 'BadOrigin' } })],
+    ]);
+
+    const checks = await new OriginSelfTest(createSilentLogger(), chopsticks, api, false).run([
+      { name: 'Origins.WhitelistedCaller', fields: 0 },
+      { name: 'system.Signed', fields: 1 },
+      { name: 'Origins.Renamed', fields: 0 },
+    ]);
+
+    expect(checks.map((check) => check.outcome)).toEqual(['ok', 'unsupported', 'failed']);
+    expect(chopsticks.newBlock).toHaveBeenCalledTimes(2);
+    expect(chopsticks.setStorageBatch.mock.calls[0][0].Scheduler.agenda).toEqual([
+      [[101], [{ call: { Inline: '0x0000' }, origin: { Origins: 'WhitelistedCaller' } }]],
+    ]);
+  });
+
+  it('fails an origin the fork cannot encode', async () => {
+    const { chopsticks, api } = createMocks([]);
+    chopsticks.setStorageBatch.mockRejectedValue(new Error('Unable to create Enum'));
+
+    const [check] = await new OriginSelfTest(createSilentLogger(), chopsticks, api, false).run([
+      { name: 'Origins.Gone', fields: 0 },
+    ]);
+
+    expect(check).toMatchObject({
+      outcome: 'failed',
+      reason: 'does not encode: Unable to create Enum',
+    });
+    expect(chopsticks.newBlock).not.toHaveBeenCalled();
+  });
+});
+
+describe('formatOriginTable', () => {
+  it('aligns the columns', () => {
+    expect(
+      formatOriginTable([
+        { name: 'system.Root', fields: 0, outcome: 'ok' },
+        { name: 'system.Signed', fields: 1, outcome: 'unsupported', reason: 'carries 1 field(s)' },
+      ])
+    ).toEqual([
+      'Origin         Result  Reason',
+      'system.Root    OK',
+      'system.Signed  SKIP    carries 1 field(s)',
+    ]);
+  });
+});
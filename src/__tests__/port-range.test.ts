@@ -0,0 +1,53 @@
+import * as net from 'net';
+import { afterEach, describe, expect, it } from 'vitest';
+import { assertPortRangeFree, parsePortRange, PortAllocator } from '../utils/port-range';
+
+describe('parsePortRange', () => {
+  it('parses start:len', () => {
+    expect(parsePortRange('9000:10')).toEqual({ start: 9000, len: 10 });
+  });
+
+  it.each(['9000', '9000-9010', 'a:b', '0:10', '9000:0', '65530:10'])('rejects %s', (value) => {
+    expect(() => parsePortRange(value)).toThrow(
+      expect.objectContaining({ code: 'E-INVALID-PORT-RANGE' })
+    );
+  });
+});
+
+describe('PortAllocator', () => {
+  it('hands out the range in order and fails once exhausted', () => {
+    const ports = new PortAllocator({ start: 9000, len: 2 });
+    expect(ports.take()).toBe(9000);
+    expect(ports.take()).toBe(9001);
+    expect(() => ports.take()).toThrow(expect.objectContaining({ code: 'E-PORT-UNAVAILABLE' }));
+  });
+});
+
+describe('assertPortRangeFree', () => {
+  let server: net.Server | undefined;
+
+  afterEach(async () => {
+    await new Promise<void>((resolve) => (server ? server.close(() => resolve()) : resolve()));
+    server = undefined;
+  });
+
+  async function listen(): Promise<number> {
+    server = net.createServer();
+    await new Promise<void>((resolve) => server!.listen(0, resolve));
+    return (server!.address() as net.AddressInfo).port;
+  }
+
+  it('fails fast when a port in the range is taken', async () => {
+    const taken = await listen();
+    await expect(assertPortRangeFree({ start: taken, len: 1 })).rejects.toMatchObject({
+      code: 'E-PORT-UNAVAILABLE',
+    });
+  });
+
+  it('passes once the port is released', async () => {
+    const port = await listen();
+    await new Promise<void>((resolve) => server!.close(() => resolve()));
+    server = undefined;
+    await expect(assertPortRangeFree({ start: port, len: 1 })).resolves.toBeUndefined();
+  });
+});
@@ -0,0 +1,68 @@
+import { describe, expect, it, vi } from 'vitest';
+import {
+  boundWarnings,
+  displayProposalFootprint,
+  INLINE_MAX_LEN,
+  PREIMAGE_MAX_SIZE,
+  proposalBound,
+  proposalFootprint,
+} from '../services/proposal-footprint';
+import type { Logger } from '../utils/logger';
+
+describe('proposalBound', () => {
+  it('bounds Inline and Lookup proposals', () => {
+    expect(proposalBound('Inline')).toBe(INLINE_MAX_LEN);
+    expect(proposalBound('Lookup')).toBe(PREIMAGE_MAX_SIZE);
+    expect(proposalBound('Legacy')).toBeUndefined();
+  });
+});
+
+describe('boundWarnings', () => {
+  it('is silent well below the bound', () => {
+    expect(boundWarnings('Inline', 64)).toEqual([]);
+    expect(boundWarnings('Lookup', 1024)).toEqual([]);
+    expect(boundWarnings('Lookup', undefined)).toEqual([]);
+  });
+
+  it('warns close to the bound', () => {
+    expect(boundWarnings('Lookup', 4_000_000)).toEqual([
+      `Lookup proposal of 4000000 bytes is at 95% of the ${PREIMAGE_MAX_SIZE}-byte bound`,
+    ]);
+  });
+
+  it('warns over the bound', () => {
+    expect(boundWarnings('Inline', 129)).toEqual([
+      'Inline proposal of 129 bytes exceeds the 128-byte bound',
+    ]);
+  });
+});
+
+describe('proposalFootprint', () => {
+  it('falls back to the preimage length for the bound check', () => {
+    const footprint = proposalFootprint({ type: 'Lookup' }, { len: PREIMAGE_MAX_SIZE + 1 });
+
+    expect(footprint).toMatchObject({ type: 'Lookup', preimageLen: PREIMAGE_MAX_SIZE + 1 });
+    expect(footprint.warnings).toHaveLength(1);
+  });
+});
+
+describe('displayProposalFootprint', () => {
+  it('prints sizes, the bound and the deposit', () => {
+    const logger = { info: vi.fn(), table: vi.fn(), warn: vi.fn() } as unknown as Logger;
+
+    displayProposalFootprint(
+      logger,
+      'Governance',
+      proposalFootprint({ type: 'Lookup', len: 40 }, { len: 40, deposit: 4_000n }),
+      { symbol: 'DOT', decimals: 3 }
+    );
+
+    expect(logger.table).toHaveBeenCalledWith({
+      Proposal: 'Lookup (40 bytes)',
+      Bound: `${PREIMAGE_MAX_SIZE} bytes`,
+      Preimage: '40 bytes',
+      'Preimage deposit': '4 DOT',
+    });
+    expect(logger.warn).not.toHaveBeenCalled();
+  });
+});
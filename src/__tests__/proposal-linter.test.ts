@@ -0,0 +1,156 @@
+import { Binary } from 'polkadot-api';
+import { describe, expect, it, vi } from 'vitest';
+import {
+  collectCalls,
+  type DecodedCall,
+  parseLintLevels,
+  ProposalLinter,
+} from '../services/proposal-linter';
+import type { ReferendumInfo } from '../types';
+import { createSilentLogger } from './test-logger';
+
+function call(pallet: string, method: string, args: Record<string, unknown> = {}): DecodedCall {
+  return { type: pallet, value: { type: method, value: args } };
+}
+
+const ROOT = { type: 'system', value: { type: 'Root' } };
+const TREASURER = { type: 'Origins', value: { type: 'Treasurer' } };
+
+function makeReferendum(origin: unknown): ReferendumInfo {
+  return {
+    id: 7,
+    track: 'treasurer',
+    origin,
+    proposal: { hash: '0x00', call: Binary.fromHex('0x00'), type: 'Inline' },
+    status: 'ongoing',
+    submittedAt: 0,
+  };
+}
+
+function mockApi(decoded: DecodedCall, proxies: unknown[] = []) {
+  return {
+    txFromCallData: vi.fn().mockResolvedValue({ decodedCall: decoded }),
+    query: { Proxy: { Proxies: { getValue: vi.fn().mockResolvedValue([proxies, 0n]) } } },
+  } as any;
+}
+
+describe('parseLintLevels', () => {
+  it('uses rule defaults and applies overrides', () => {
+    const levels = parseLintLevels('set-code-without-authorize-upgrade=deny');
+    expect(levels['set-code-without-authorize-upgrade']).toBe('deny');
+    expect(levels['sudo-like-call-on-non-root-track']).toBe('warn');
+  });
+
+  it('promotes warnings with --deny warnings but keeps allowed rules silent', () => {
+    const levels = parseLintLevels('treasury-spend-beneficiary-proxy=allow', 'warnings');
+    expect(levels['treasury-spend-beneficiary-proxy']).toBe('allow');
+    expect(levels['sudo-like-call-on-non-root-track']).toBe('deny');
+  });
+
+  it.each([
+    ['no-such-rule=warn', undefined],
+    ['set-code-without-authorize-upgrade=loud', undefined],
+    [undefined, 'errors'],
+  ])('rejects --lint %s / --deny %s', (lint, deny) => {
+    expect(() => parseLintLevels(lint, deny)).toThrow(
+      expect.objectContaining({ code: 'E-INVALID-LINT-CONFIG' })
+    );
+  });
+});
+
+describe('collectCalls', () => {
+  it('treats whitelisted calls as Root and batches as inheriting the origin', () => {
+    const tree = call('Utility', 'batch_all', {
+      calls: [
+        call('System', 'remark', { remark: '0x' }),
+        call('Whitelist', 'dispatch_whitelisted_call_with_preimage', {
+          call: call('System', 'set_storage'),
+        }),
+      ],
+    });
+    const targets = collectCalls(tree, false).map((t) => [
+      `${t.call.type}.${t.call.value.type}`,
+      t.root,
+    ]);
+    expect(targets).toEqual([
+      ['Utility.batch_all', false],
+      ['System.remark', false],
+      ['Whitelist.dispatch_whitelisted_call_with_preimage', false],
+      ['System.set_storage', true],
+    ]);
+  });
+});
+
+describe('ProposalLinter', () => {
+  const linter = new ProposalLinter(createSilentLogger(), parseLintLevels());
+
+  it('flags set_code even on the Root track', async () => {
+    const api = mockApi(call('System', 'set_code', { code: '0x' }));
+    const findings = await linter.lint(api, makeReferendum(ROOT));
+    expect(findings.map((f) => f.rule)).toEqual(['set-code-without-authorize-upgrade']);
+  });
+
+  it('flags Root-only calls on a non-Root track', async () => {
+    const api = mockApi(call('System', 'set_storage', { items: [] }));
+    const findings = await linter.lint(api, makeReferendum(TREASURER));
+    expect(findings).toEqual([
+      expect.objectContaining({
+        rule: 'sudo-like-call-on-non-root-track',
+        level: 'warn',
+        call: 'System.set_storage',
+      }),
+    ]);
+  });
+
+  it('flags treasury spends to a proxied beneficiary', async () => {
+    const beneficiary = { type: 'Id', value: '5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY' };
+    const api = mockApi(call('Treasury', 'spend_local', { amount: 1n, beneficiary }), [
+      { delegate: '5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty', proxy_type: { type: 'Any' } },
+    ]);
+    const findings = await linter.lint(api, makeReferendum(TREASURER));
+    expect(findings).toHaveLength(1);
+    expect(findings[0].rule).toBe('treasury-spend-beneficiary-proxy');
+    expect(findings[0].message).toContain('5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty (Any)');
+  });
+
+  it('flags XCM the estimator finds under-funded', async () => {
+    const send = call('XcmPallet', 'send', { dest: {}, message: {} });
+    const xcm = {
+      estimate: vi.fn(async (target: DecodedCall) =>
+        target === send ? { destination: 'Asset Hub', problems: ['dry run failed'] } : undefined
+      ),
+    } as any;
+    const findings = await linter.lint(mockApi(send), makeReferendum(ROOT), xcm);
+    expect(findings).toEqual([
+      expect.objectContaining({
+        rule: 'xcm-underfunded',
+        call: 'XcmPallet.send',
+        message: 'XCM to Asset Hub: dry run failed',
+      }),
+    ]);
+  });
+
+  it('flags a sovereign account that cannot fund a Transact', async () => {
+    const send = call('PolkadotXcm', 'send', { dest: {}, message: {} });
+    const sovereignAccount = { account: '5Dt6', free: 0n, withdrawn: 10n, warnings: ['too poor'] };
+    const xcm = {
+      estimate: vi.fn().mockResolvedValue({ destination: 'Relay', problems: [], sovereignAccount }),
+    } as any;
+    const findings = await linter.lint(mockApi(send), makeReferendum(ROOT), xcm);
+    expect(findings).toEqual([
+      expect.objectContaining({
+        rule: 'xcm-sovereign-account-underfunded',
+        message: 'XCM to Relay: too poor',
+      }),
+    ]);
+  });
+
+  it('skips rules set to allow', async () => {
+    const quiet = new ProposalLinter(
+      createSilentLogger(),
+      parseLintLevels('set-code-without-authorize-upgrade=allow')
+    );
+    const api = mockApi(call('System', 'set_code', { code: '0x' }));
+    expect(await quiet.lint(api, makeReferendum(ROOT))).toEqual([]);
+  });
+});
@@ -0,0 +1,121 @@
+import { describe, expect, it, vi } from 'vitest';
+import { collectivePalletName, RankedApprover } from '../services/ranked-approval';
+import { ALICE_ADDRESS } from '../utils/storage-constants';
+import { createSilentLogger } from './test-logger';
+
+vi.mock('../services/delegated-submission', () => ({
+  mockSigner: vi.fn(() => 'alice-signer'),
+}));
+
+function createMockChopsticks() {
+  return {
+    newBlock: vi.fn().mockResolvedValue(undefined),
+    setStorageBatch: vi.fn().mockResolvedValue(undefined),
+  } as any;
+}
+
+function createMockApi(events: unknown[]) {
+  const sign = vi.fn().mockResolvedValue('0xsigned-vote');
+  const vote = vi.fn(() => ({ sign }));
+  const api = {
+    query: { System: { Events: { getValue: vi.fn().mockResolvedValue(events) } } },
+    tx: { FellowshipCollective: { vote } },
+  } as any;
+  return { api, vote, sign };
+}
+
+const votedEvent = (votes: number) => ({
+  type: 'FellowshipCollective',
+  value: {
+    type: 'Voted',
+    value: {
+      who: ALICE_ADDRESS,
+      poll: 7,
+      vote: { type: 'Aye', value: votes },
+      tally: { bare_ayes: 1, ayes: votes, nays: 0 },
+    },
+  },
+});
+
+describe('collectivePalletName', () => {
+  it('pairs each referenda instance with its collective', () => {
+    expect(collectivePalletName('FellowshipReferenda')).toBe('FellowshipCollective');
+    expect(collectivePalletName('AmbassadorReferenda')).toBe('AmbassadorCollective');
+  });
+});
+
+describe('RankedApprover', () => {
+  it('replaces the members and clears earlier votes', async () => {
+    const chopsticks = createMockChopsticks();
+    const { api } = createMockApi([]);
+    const approver = new RankedApprover(
+      createSilentLogger(),
+      chopsticks,
+      api,
+      'FellowshipReferenda'
+    );
+
+    await approver.injectMembers();
+
+    const storage = chopsticks.setStorageBatch.mock.calls[0][0];
+    expect(storage.FellowshipCollective.$removePrefix).toContain('Voting');
+    expect(storage.FellowshipCollective.Members).toEqual([[[ALICE_ADDRESS], { rank: 9 }]]);
+    expect(storage.FellowshipCollective.MemberCount).toHaveLength(10);
+  });
+
+  it('votes aye and reports the rank-scaled votes', async () => {
+    const chopsticks = createMockChopsticks();
+    const { api, vote } = createMockApi([votedEvent(28)]);
+    const approver = new RankedApprover(
+      createSilentLogger(),
+      chopsticks,
+      api,
+      'FellowshipReferenda'
+    );
+
+    const votes = await approver.approve(7);
+
+    expect(vote).toHaveBeenCalledWith({ poll: 7, aye: true });
+    expect(chopsticks.newBlock).toHaveBeenCalledWith({ transactions: ['0xsigned-vote'] });
+    expect(votes).toEqual([{ who: ALICE_ADDRESS, rank: 9, votes: 28 }]);
+  });
+
+  it('fails when the vote is rejected', async () => {
+    const { api } = createMockApi([
+      {
+        type: 'System',
+        value: {
+          type: 'ExtrinsicFailed',
+          value: {
+            dispatch_error: {
+              type: 'Module',
+              value: { type: 'FellowshipCollective', value: { type: 'RankTooLow' } },
+            },
+          },
+        },
+      },
+    ]);
+    const approver = new RankedApprover(
+      createSilentLogger(),
+      createMockChopsticks(),
+      api,
+      'FellowshipReferenda'
+    );
+
+    await expect(approver.approve(7)).rejects.toThrow(
+      'FellowshipCollective.vote on referendum #7 was not accepted'
+    );
+  });
+
+  it('fails when the chain has no matching collective', async () => {
+    const { api } = createMockApi([]);
+    const approver = new RankedApprover(
+      createSilentLogger(),
+      createMockChopsticks(),
+      api,
+      'AmbassadorReferenda'
+    );
+
+    await expect(approver.approve(3)).rejects.toThrow('chain has no AmbassadorCollective.vote');
+  });
+});
@@ -0,0 +1,155 @@
+import { describe, expect, it, vi } from 'vitest';
+import {
+  discoverReferendaPallets,
+  discoverSubmittedReferendaPallet,
+  findReferendaPallets,
+  findSubmittedReferendaPallet,
+  parseReferendumTarget,
+  type ReferendaMetadata,
+  selectReferendaPallet,
+} from '../services/referenda-pallets';
+
+vi.mock('@polkadot-api/substrate-bindings', () => ({
+  decAnyMetadata: (hex: string) => (hex === '0xmeta' ? METADATA : undefined),
+  unifyMetadata: (metadata: unknown) => metadata,
+}));
+
+const referendaStorage = (info: number) => ({
+  items: [
+    { name: 'ReferendumCount', type: { tag: 'plain' as const, value: 0 } },
+    { name: 'ReferendumInfoFor', type: { tag: 'map' as const, value: { value: info } } },
+  ],
+});
+
+// ReferendumInfo (10/20) -> Ongoing status (11/21) -> tally (12/22)
+const METADATA: ReferendaMetadata = {
+  lookup: [
+    { id: 10, def: { tag: 'variant', value: [{ name: 'Ongoing', fields: [{ type: 11 }] }] } },
+    { id: 11, def: { tag: 'composite', value: [{ name: 'tally', type: 12 }] } },
+    {
+      id: 12,
+      def: {
+        tag: 'composite',
+        value: [
+          { name: 'ayes', type: 0 },
+          { name: 'nays', type: 0 },
+          { name: 'support', type: 0 },
+        ],
+      },
+    },
+    { id: 20, def: { tag: 'variant', value: [{ name: 'Ongoing', fields: [{ type: 21 }] }] } },
+    { id: 21, def: { tag: 'composite', value: [{ name: 'tally', type: 22 }] } },
+    {
+      id: 22,
+      def: {
+        tag: 'composite',
+        value: [
+          { name: 'bare_ayes', type: 0 },
+          { name: 'ayes', type: 0 },
+          { name: 'nays', type: 0 },
+        ],
+      },
+    },
+  ],
+  pallets: [
+    { name: 'System', index: 0, storage: { items: [] } },
+    { name: 'Referenda', index: 21, storage: referendaStorage(10) },
+    { name: 'Utility', index: 40 },
+    { name: 'FellowshipReferenda', index: 61, storage: referendaStorage(20) },
+    { name: 'AmbassadorReferenda', index: 71, storage: referendaStorage(20) },
+  ],
+};
+
+describe('parseReferendumTarget', () => {
+  it('parses a bare id', () => {
+    expect(parseReferendumTarget('1777')).toEqual({ pallet: undefined, id: 1777 });
+  });
+
+  it('parses a pallet-prefixed id', () => {
+    expect(parseReferendumTarget('AmbassadorReferenda:12')).toEqual({
+      pallet: 'AmbassadorReferenda',
+      id: 12,
+    });
+  });
+
+  it.each(['abc', 'Referenda:', ':12', 'Referenda:12:3', '1.5'])('rejects %s', (value) => {
+    expect(() => parseReferendumTarget(value)).toThrow(`Invalid referendum ID: ${value}`);
+  });
+});
+
+describe('findReferendaPallets', () => {
+  it('finds every referenda instance and tells ranked tallies apart', () => {
+    expect(findReferendaPallets(METADATA)).toEqual([
+      { name: 'Referenda', ranked: false },
+      { name: 'FellowshipReferenda', ranked: true },
+      { name: 'AmbassadorReferenda', ranked: true },
+    ]);
+  });
+
+  it('ignores pallets with only part of the referenda storage', () => {
+    const metadata: ReferendaMetadata = {
+      lookup: [],
+      pallets: [
+        {
+          name: 'Lookalike',
+          index: 0,
+          storage: {
+            items: [{ name: 'ReferendumInfoFor', type: { tag: 'map', value: { value: 1 } } }],
+          },
+        },
+      ],
+    };
+    expect(findReferendaPallets(metadata)).toEqual([]);
+  });
+});
+
+describe('discoverReferendaPallets', () => {
+  it('decodes the fork metadata', async () => {
+    const request = vi.fn().mockResolvedValue('0xmeta');
+    const instances = await discoverReferendaPallets(request);
+    expect(request).toHaveBeenCalledWith('state_getMetadata', []);
+    expect(instances.map((i) => i.name)).toEqual([
+      'Referenda',
+      'FellowshipReferenda',
+      'AmbassadorReferenda',
+    ]);
+  });
+});
+
+describe('findSubmittedReferendaPallet', () => {
+  it('finds the instance by the pallet index of the call', () => {
+    // AmbassadorReferenda (71 = 0x47).submit
+    expect(findSubmittedReferendaPallet(METADATA, '0x4700')).toEqual({
+      name: 'AmbassadorReferenda',
+      ranked: true,
+    });
+    expect(findSubmittedReferendaPallet(METADATA, '1500')?.name).toBe('Referenda');
+  });
+
+  it('returns undefined for a call to another pallet', () => {
+    expect(findSubmittedReferendaPallet(METADATA, '0x2800')).toBeUndefined();
+  });
+
+  it('decodes the fork metadata', async () => {
+    const request = vi.fn().mockResolvedValue('0xmeta');
+    const instance = await discoverSubmittedReferendaPallet(request, '0x3d00');
+    expect(instance?.name).toBe('FellowshipReferenda');
+  });
+});
+
+describe('selectReferendaPallet', () => {
+  const instances = findReferendaPallets(METADATA);
+
+  it('selects the named instance', () => {
+    expect(selectReferendaPallet(instances, 'AmbassadorReferenda')).toEqual({
+      name: 'AmbassadorReferenda',
+      ranked: true,
+    });
+  });
+
+  it('lists the available instances for an unknown pallet', () => {
+    expect(() => selectReferendaPallet(instances, 'SecretReferenda')).toThrow(
+      'No referenda pallet named SecretReferenda on this chain (found: Referenda, FellowshipReferenda, AmbassadorReferenda)'
+    );
+  });
+});
@@ -0,0 +1,80 @@
+import { describe, expect, it } from 'vitest';
+import {
+  findOngoingReferenda,
+  formatSweepTable,
+  mapWithConcurrency,
+  parseConcurrency,
+} from '../services/referenda-sweeper';
+import { ErrorCode } from '../utils/error-codes';
+
+describe('parseConcurrency', () => {
+  it('defaults to 2 and accepts positive integers', () => {
+    expect(parseConcurrency(undefined)).toBe(2);
+    expect(parseConcurrency('8')).toBe(8);
+  });
+
+  it.each(['0', '-1', 'two', '1.5'])('rejects %s', (value) => {
+    expect(() => parseConcurrency(value)).toThrow(
+      expect.objectContaining({ code: ErrorCode.InvalidConcurrency })
+    );
+  });
+});
+
+describe('mapWithConcurrency', () => {
+  it('never runs more than the limit at once and keeps the input order', async () => {
+    let running = 0;
+    let peak = 0;
+    const results = await mapWithConcurrency([30, 10, 20, 5, 15], 2, async (delay) => {
+      running++;
+      peak = Math.max(peak, running);
+      await new Promise((resolve) => setTimeout(resolve, delay));
+      running--;
+      return delay * 2;
+    });
+    expect(results).toEqual([60, 20, 40, 10, 30]);
+    expect(peak).toBe(2);
+  });
+});
+
+describe('findOngoingReferenda', () => {
+  it('keeps only ongoing referenda', async () => {
+    const infos: Record<number, unknown> = {
+      0: { type: 'Approved', value: [1, undefined, undefined] },
+      2: { type: 'Ongoing', value: { track: 33 } },
+      3: { type: 'Ongoing', value: { track: 0 } },
+    };
+    const api = {
+      query: {
+        Referenda: {
+          ReferendumCount: { getValue: async () => 4 },
+          ReferendumInfoFor: { getValue: async (id: number) => infos[id] },
+        },
+      },
+    } as any;
+    expect(await findOngoingReferenda(api, false)).toEqual([
+      { id: 2, track: 33 },
+      { id: 3, track: 0 },
+    ]);
+  });
+});
+
+describe('formatSweepTable', () => {
+  it('aligns columns and shows the failure code', () => {
+    expect(
+      formatSweepTable([
+        { id: 7, track: 0, outcome: 'passed' },
+        {
+          id: 1234,
+          track: 33,
+          outcome: 'failed',
+          code: ErrorCode.PreimageMissing,
+          message: 'execution failed',
+        },
+      ])
+    ).toEqual([
+      'Referendum  Track  Result  Reason',
+      '#7          0      PASS',
+      '#1234       33     FAIL    E-PREIMAGE-MISSING: execution failed',
+    ]);
+  });
+});
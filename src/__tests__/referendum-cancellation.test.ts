@@ -0,0 +1,185 @@
+import { Binary } from 'polkadot-api';
+import { describe, expect, it, vi } from 'vitest';
+import {
+  cancelOrigin,
+  parseCancelPhase,
+  ReferendumCancellation,
+} from '../services/referendum-cancellation';
+import { ErrorCode } from '../utils/error-codes';
+import { createSilentLogger } from './test-logger';
+
+const DEPOSITOR = '5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY';
+const SUBMISSION = { who: DEPOSITOR, amount: 10n };
+const DECISION = { who: DEPOSITOR, amount: 1000n };
+
+const ONGOING = {
+  type: 'Ongoing',
+  value: { deciding: { since: 90, confirming: 120 }, decision_deposit: DECISION },
+};
+const CANCELLED = { type: 'Cancelled', value: [101, SUBMISSION, DECISION] };
+
+const dispatched = (success = true) => ({
+  type: 'Scheduler',
+  value: { type: 'Dispatched', value: { result: { success } } },
+});
+const cancelledEvent = (index: number) => ({
+  type: 'Referenda',
+  value: { type: 'Cancelled', value: { index, tally: {} } },
+});
+const refunded = (method: string) => ({
+  type: 'Referenda',
+  value: { type: method, value: { index: 42, who: DEPOSITOR, amount: 0n } },
+});
+
+/**
+ * A chain where `Referenda.cancel` and both refunds are dispatched in turn,
+ * each block emitting the given events. Free balances are read before and
+ * after each refund.
+ */
+function mockChain(options: {
+  info?: unknown;
+  cancelEvents?: unknown[];
+  refundEvents?: unknown[][];
+  balances?: bigint[];
+}) {
+  const encoded = (hex: string) =>
+    vi.fn(() => ({ getEncodedData: async () => Binary.fromHex(hex) }));
+  const events = { getValue: vi.fn() };
+  events.getValue.mockResolvedValueOnce(options.cancelEvents ?? [dispatched(), cancelledEvent(42)]);
+  for (const refundEvents of options.refundEvents ?? [
+    [dispatched(), refunded('SubmissionDepositRefunded')],
+    [dispatched(), refunded('DecisionDepositRefunded')],
+  ]) {
+    events.getValue.mockResolvedValueOnce(refundEvents);
+  }
+  const account = { getValue: vi.fn() };
+  for (const free of options.balances ?? [0n, 10n, 10n, 1010n]) {
+    account.getValue.mockResolvedValueOnce({ data: { free, reserved: 0n } });
+  }
+  const api = {
+    query: {
+      System: {
+        Number: { getValue: vi.fn().mockResolvedValue(101) },
+        Events: events,
+        Account: account,
+      },
+      Referenda: {
+        ReferendumInfoFor: {
+          getValue: vi
+            .fn()
+            .mockResolvedValueOnce(options.info ?? ONGOING)
+            .mockResolvedValueOnce(CANCELLED),
+        },
+      },
+    },
+    tx: {
+      Referenda: {
+        cancel: encoded('0x1503'),
+        refund_submission_deposit: encoded('0x1505'),
+        refund_decision_deposit: encoded('0x1501'),
+      },
+    },
+  } as any;
+  const chopsticks = { newBlock: vi.fn().mockResolvedValue(undefined) } as any;
+  const scheduler = { scheduleCallAtNextBlock: vi.fn().mockResolvedValue(101) } as any;
+  const cancellation = new ReferendumCancellation(
+    createSilentLogger(),
+    chopsticks,
+    api,
+    scheduler,
+    'Referenda',
+    false
+  );
+  return { api, scheduler, cancellation };
+}
+
+describe('parseCancelPhase', () => {
+  it('defaults to no cancellation', () => {
+    expect(parseCancelPhase(undefined)).toBeUndefined();
+  });
+
+  it('parses the phases', () => {
+    expect(parseCancelPhase(' confirming ')).toBe('confirming');
+    expect(parseCancelPhase('ongoing')).toBe('ongoing');
+  });
+
+  it('rejects unknown phases', () => {
+    expect(() => parseCancelPhase('enactment')).toThrow(
+      expect.objectContaining({
+        code: ErrorCode.InvalidCancelPhase,
+        message: 'Invalid --cancel-at value: enactment (expected one of: ongoing, confirming)',
+      })
+    );
+  });
+});
+
+describe('cancelOrigin', () => {
+  it('uses ReferendumCanceller, or Root for ranked collectives', () => {
+    expect(cancelOrigin(false)).toEqual({ Origins: 'ReferendumCanceller' });
+    expect(cancelOrigin(true)).toEqual({ System: 'Root' });
+  });
+});
+
+describe('ReferendumCancellation', () => {
+  it('cancels while confirming and refunds both deposits', async () => {
+    const { api, scheduler, cancellation } = mockChain({});
+
+    const result = await cancellation.cancel(42, 'confirming');
+
+    expect(result).toEqual({
+      phase: 'confirming',
+      block: 101,
+      deposits: [
+        { kind: 'submission', who: DEPOSITOR, amount: 10n, refunded: true },
+        { kind: 'decision', who: DEPOSITOR, amount: 1000n, refunded: true },
+      ],
+    });
+    expect(api.tx.Referenda.cancel).toHaveBeenCalledWith({ index: 42 });
+    expect(scheduler.scheduleCallAtNextBlock).toHaveBeenNthCalledWith(1, '0x1503', {
+      Origins: 'ReferendumCanceller',
+    });
+    expect(scheduler.scheduleCallAtNextBlock).toHaveBeenNthCalledWith(2, '0x1505', {
+      System: { Signed: expect.any(String) },
+    });
+  });
+
+  it('refuses to cancel at confirming when the referendum is not confirming', async () => {
+    const { cancellation } = mockChain({
+      info: { type: 'Ongoing', value: { deciding: { since: 90, confirming: undefined } } },
+    });
+
+    await expect(cancellation.cancel(42, 'confirming')).rejects.toMatchObject({
+      code: ErrorCode.CancellationFailed,
+    });
+  });
+
+  it('fails when no Cancelled event is emitted for the referendum', async () => {
+    const { cancellation } = mockChain({ cancelEvents: [dispatched(), cancelledEvent(7)] });
+
+    await expect(cancellation.cancel(42, 'ongoing')).rejects.toThrow(
+      'Referenda.cancel(42) dispatched without a Referenda.Cancelled event'
+    );
+  });
+
+  it('fails when the cancel call does not dispatch', async () => {
+    const { cancellation } = mockChain({ cancelEvents: [dispatched(false)] });
+
+    await expect(cancellation.cancel(42, 'ongoing')).rejects.toMatchObject({
+      code: ErrorCode.CancellationFailed,
+    });
+  });
+
+  it('reports a deposit that was not refunded in full', async () => {
+    const { cancellation } = mockChain({ balances: [0n, 10n, 10n, 510n] });
+
+    const { deposits } = await cancellation.cancel(42, 'ongoing');
+
+    expect(deposits[1]).toEqual({
+      kind: 'decision',
+      who: DEPOSITOR,
+      amount: 1000n,
+      refunded: false,
+      error: `${DEPOSITOR} received 500, expected 1000`,
+    });
+  });
+});
@@ -0,0 +1,51 @@
+import * as fs from 'fs';
+import * as os from 'os';
+import * as path from 'path';
+import { Binary, Blake2256 } from '@polkadot-api/substrate-bindings';
+import { describe, expect, it } from 'vitest';
+import { parseReferendumMetadata } from '../services/referendum-metadata';
+
+const dir = fs.mkdtempSync(path.join(os.tmpdir(), 'metadata-'));
+
+function writeFile(name: string, contents: string): string {
+  const file = path.join(dir, name);
+  fs.writeFileSync(file, contents);
+  return file;
+}
+
+const HASH = `0x${'ab'.repeat(32)}`;
+
+describe('parseReferendumMetadata', () => {
+  it('is unset without the flag', () => {
+    expect(parseReferendumMetadata({}, false)).toBeUndefined();
+  });
+
+  it('reads a JSON file and hashes its bytes', () => {
+    const json = '{"title":"Increase the treasury spend period"}';
+    const file = writeFile('metadata.json', json);
+    const metadata = parseReferendumMetadata({ metadataForGovernanceReferendum: file }, false);
+    expect(metadata).toMatchObject({ kind: 'file', path: file });
+    expect(metadata?.hash.asHex()).toBe(
+      Binary.fromBytes(Blake2256(new TextEncoder().encode(json))).asHex()
+    );
+  });
+
+  it('takes a preimage hash as is', () => {
+    const metadata = parseReferendumMetadata({ metadataForFellowshipReferendum: HASH }, true);
+    expect(metadata?.kind).toBe('hash');
+    expect(metadata?.hash.asHex()).toBe(HASH);
+  });
+
+  it('rejects a file that is not JSON', () => {
+    const file = writeFile('metadata.md', '# Not JSON');
+    expect(() => parseReferendumMetadata({ metadataForGovernanceReferendum: file }, false)).toThrow(
+      expect.objectContaining({ code: 'E-INVALID-METADATA' })
+    );
+  });
+
+  it.each(['0x1234', './missing.json'])('rejects %s', (value) => {
+    expect(() =>
+      parseReferendumMetadata({ metadataForGovernanceReferendum: value }, false)
+    ).toThrow(`Invalid --metadata-for-governance-referendum value: ${value}`);
+  });
+});
@@ -1,25 +1,7 @@
 import { describe, expect, it, vi } from 'vitest';
 import { ReferendumSimulator } from '../services/referendum-simulator';
 import type { ReferendumInfo } from '../types';
-import type { Logger } from '../utils/logger';
-
-function createSilentLogger(): Logger {
-  return {
-    info: vi.fn(),
-    debug: vi.fn(),
-    warn: vi.fn(),
-    error: vi.fn(),
-    success: vi.fn(),
-    isVerbose: () => false,
-    startSpinner: vi.fn(),
-    succeedSpinner: vi.fn(),
-    failSpinner: vi.fn(),
-    updateSpinner: vi.fn(),
-    stopSpinner: vi.fn(),
-    section: vi.fn(),
-    table: vi.fn(),
-  } as unknown as Logger;
-}
+import { createSilentLogger } from './test-logger';
 
 function createMockChopsticks() {
   return {
@@ -29,6 +11,7 @@ function createMockChopsticks() {
     pause: vi.fn().mockResolvedValue(undefined),
     waitForChainReady: vi.fn().mockResolvedValue(undefined),
     getContext: vi.fn().mockReturnValue({ ws: { endpoint: 'ws://localhost:8000' } }),
+    getForkHead: vi.fn().mockReturnValue(undefined),
   } as any;
 }
 
@@ -96,6 +79,22 @@ describe('ReferendumSimulator', () => {
       expect(chopsticks.setStorageBatch).not.toHaveBeenCalled();
     });
 
+    it('warns when the fork starts on a session boundary', async () => {
+      const logger = createSilentLogger();
+      const chopsticks = createMockChopsticks();
+      chopsticks.getForkHead.mockReturnValue({ number: 2400 });
+      const api = createMockApi({
+        constants: { Babe: { EpochDuration: vi.fn().mockResolvedValue(600n) } },
+      });
+
+      const simulator = new ReferendumSimulator(logger, chopsticks, api, false);
+      await simulator.simulate(makeReferendum({ status: 'approved' }));
+
+      expect(logger.warn).toHaveBeenCalledWith(
+        expect.stringContaining('Forked at session boundary #2400 (epoch 600 blocks)')
+      );
+    });
+
     it('catches thrown errors and returns them in result', async () => {
       const logger = createSilentLogger();
       const chopsticks = createMockChopsticks();
@@ -268,12 +267,12 @@ describe('ReferendumSimulator', () => {
       expect(tally.bare_ayes).toBeUndefined();
     });
 
-    it('builds fellowship tally with fixed values', () => {
+    it('builds an empty fellowship tally for the synthetic votes to fill', () => {
       const result = callBuild(true, 1000000n, 100);
       const tally = result.ongoing.tally;
 
-      expect(tally.bare_ayes).toBe(100);
-      expect(tally.ayes).toBe(1000);
+      expect(tally.bare_ayes).toBe(0);
+      expect(tally.ayes).toBe(0);
       expect(tally.nays).toBe(0);
       // Should NOT have governance fields
       expect(tally.support).toBeUndefined();
@@ -361,4 +360,235 @@ describe('ReferendumSimulator', () => {
       );
     });
   });
+
+  // ═══════════════════════════════════════════════════════════════════════
+  // buildBlocksAfterDispatch() - --blocks-after-dispatch
+  // ═══════════════════════════════════════════════════════════════════════
+
+  describe('buildBlocksAfterDispatch()', () => {
+    it('builds each block and keeps its events', async () => {
+      const logger = createSilentLogger();
+      const chopsticks = createMockChopsticks();
+      const api = createMockApi();
+      api.query.System.Number.getValue.mockResolvedValueOnce(101).mockResolvedValueOnce(102);
+      api.query.System.Events.getValue
+        .mockResolvedValueOnce([{ type: 'MessageQueue', value: { type: 'Processed', value: {} } }])
+        .mockResolvedValueOnce([]);
+
+      const simulator = new ReferendumSimulator(logger, chopsticks, api, false, {
+        dispatchOptions: { blocksAfterDispatch: 2 },
+      });
+      const blocks = await (simulator as any).buildBlocksAfterDispatch(2);
+
+      expect(chopsticks.newBlock).toHaveBeenCalledTimes(2);
+      expect(blocks).toEqual([
+        { block: 101, events: [{ section: 'MessageQueue', method: 'Processed', data: {} }] },
+        { block: 102, events: [] },
+      ]);
+      expect(logger.info).toHaveBeenCalledWith(
+        '\u{1F4E1} Governance [after dispatch 1/2] (Block #101)'
+      );
+    });
+  });
+
+  // ═══════════════════════════════════════════════════════════════════════
+  // followScheduledTasks() - --follow-scheduled
+  // ═══════════════════════════════════════════════════════════════════════
+
+  describe('followScheduledTasks()', () => {
+    function scheduled(when: number, index: number) {
+      return { type: 'Scheduler', value: { type: 'Scheduled', value: { when, index } } };
+    }
+
+    function dispatched(block: number, index: number, result: unknown) {
+      return {
+        type: 'Scheduler',
+        value: { type: 'Dispatched', value: { task: [block, index], id: undefined, result } },
+      };
+    }
+
+    it('dispatches each scheduled task and the tasks it schedules in turn', async () => {
+      const logger = createSilentLogger();
+      const chopsticks = createMockChopsticks();
+      const api = createMockApi();
+      api.query.System.Number.getValue.mockResolvedValueOnce(101).mockResolvedValueOnce(102);
+      api.query.System.Events.getValue
+        .mockResolvedValueOnce([dispatched(101, 0, { success: true }), scheduled(9000, 0)])
+        .mockResolvedValueOnce([
+          dispatched(102, 0, { success: false, value: { type: 'BadOrigin' } }),
+        ]);
+
+      const simulator = new ReferendumSimulator(logger, chopsticks, api, false);
+      const moveTask = vi
+        .spyOn((simulator as any).scheduler, 'moveTaskToNextBlock')
+        .mockResolvedValueOnce({ block: 101, taskIndex: 0, taskId: undefined })
+        .mockResolvedValueOnce({ block: 102, taskIndex: 0, taskId: undefined });
+
+      const followed = await (simulator as any).followScheduledTasks([{ when: 5000, index: 0 }]);
+
+      expect(moveTask).toHaveBeenNthCalledWith(1, 5000, 0);
+      expect(moveTask).toHaveBeenNthCalledWith(2, 9000, 0);
+      expect(followed).toMatchObject([
+        { scheduledAt: 5000, index: 0, executedAt: 101, succeeded: true },
+        { scheduledAt: 9000, index: 0, executedAt: 102, succeeded: false },
+      ]);
+      expect(logger.section).toHaveBeenCalledWith('Scheduled Tasks');
+    });
+  });
+
+  // ═══════════════════════════════════════════════════════════════════════
+  // retryEnactment() - scheduler retries of a failed enactment
+  // ═══════════════════════════════════════════════════════════════════════
+
+  describe('retryEnactment()', () => {
+    const config = { totalRetries: 2, remaining: 2, period: 10 };
+
+    function scheduled(when: number, index: number) {
+      return { type: 'Scheduler', value: { type: 'Scheduled', value: { when, index } } };
+    }
+
+    function dispatched(block: number, index: number, result: unknown) {
+      return {
+        type: 'Scheduler',
+        value: { type: 'Dispatched', value: { task: [block, index], id: undefined, result } },
+      };
+    }
+
+    const failure = { success: false, value: { type: 'BadOrigin' } };
+
+    it('dispatches retries until one succeeds', async () => {
+      const logger = createSilentLogger();
+      const chopsticks = createMockChopsticks();
+      const api = createMockApi();
+      api.query.System.Number.getValue
+        .mockResolvedValueOnce(100)
+        .mockResolvedValueOnce(101)
+        .mockResolvedValueOnce(102);
+      api.query.System.Events.getValue
+        .mockResolvedValueOnce([dispatched(101, 0, failure), scheduled(111, 0)])
+        .mockResolvedValueOnce([dispatched(102, 0, { success: true })]);
+
+      const simulator = new ReferendumSimulator(logger, chopsticks, api, false);
+      const scheduler = (simulator as any).scheduler;
+      vi.spyOn(scheduler, 'getRetryConfig').mockResolvedValue({ ...config, remaining: 1 });
+      const moveTask = vi
+        .spyOn(scheduler, 'moveTaskToNextBlock')
+        .mockResolvedValueOnce({ block: 101, taskIndex: 0, taskId: undefined })
+        .mockResolvedValueOnce({ block: 102, taskIndex: 0, taskId: undefined });
+
+      const result = await (simulator as any).retryEnactment(
+        config,
+        [{ section: 'Scheduler', method: 'Scheduled', data: { when: 110, index: 0 } }],
+        ['BadOrigin']
+      );
+
+      expect(moveTask).toHaveBeenNthCalledWith(1, 110, 0);
+      expect(moveTask).toHaveBeenNthCalledWith(2, 111, 0);
+      expect(result).toMatchObject({
+        executionSucceeded: true,
+        blockExecuted: 102,
+        retries: {
+          config,
+          firstErrors: ['BadOrigin'],
+          attempts: [
+            { scheduledAt: 110, index: 0, executedAt: 101, succeeded: false },
+            { scheduledAt: 111, index: 0, executedAt: 102, succeeded: true },
+          ],
+        },
+      });
+      expect(result.errors).toBeUndefined();
+      expect(logger.section).toHaveBeenCalledWith('Enactment Retries');
+    });
+
+    it('fails with every error when no retry succeeds', async () => {
+      const logger = createSilentLogger();
+      const chopsticks = createMockChopsticks();
+      const api = createMockApi();
+      api.query.System.Number.getValue.mockResolvedValueOnce(100).mockResolvedValueOnce(101);
+      api.query.System.Events.getValue.mockResolvedValueOnce([dispatched(101, 0, failure)]);
+
+      const simulator = new ReferendumSimulator(logger, chopsticks, api, false);
+      const scheduler = (simulator as any).scheduler;
+      vi.spyOn(scheduler, 'getRetryConfig').mockResolvedValue(config);
+      vi.spyOn(scheduler, 'moveTaskToNextBlock').mockResolvedValue({
+        block: 101,
+        taskIndex: 0,
+        taskId: undefined,
+      });
+
+      const result = await (simulator as any).retryEnactment(
+        config,
+        [{ section: 'Scheduler', method: 'Scheduled', data: { when: 110, index: 0 } }],
+        ['BadOrigin']
+      );
+
+      expect(result.executionSucceeded).toBe(false);
+      expect(result.retries.attempts).toHaveLength(1);
+      expect(result.errors[0]).toBe('BadOrigin');
+      expect(result.errors[1]).toMatch(/^Retry 1 at block 101: /);
+    });
+
+    it('reports a failed enactment the scheduler scheduled no retry for', async () => {
+      const logger = createSilentLogger();
+      const simulator = new ReferendumSimulator(
+        logger,
+        createMockChopsticks(),
+        createMockApi(),
+        false
+      );
+
+      const result = await (simulator as any).retryEnactment(
+        { ...config, remaining: 0 },
+        [],
+        ['BadOrigin']
+      );
+
+      expect(result).toMatchObject({ executionSucceeded: false, errors: ['BadOrigin'] });
+      expect(result.retries.attempts).toEqual([]);
+      expect(logger.warn).toHaveBeenCalledWith(
+        'The scheduler scheduled no retry of the enactment task'
+      );
+    });
+  });
+
+  describe('enactmentRetryConfig()', () => {
+    it('gives a task without one the --enactment-retries configuration', async () => {
+      const simulator = new ReferendumSimulator(
+        createSilentLogger(),
+        createMockChopsticks(),
+        createMockApi(),
+        false,
+        { dispatchOptions: { enactmentRetries: 3 } }
+      );
+      const scheduler = (simulator as any).scheduler;
+      const setRetryConfig = vi.spyOn(scheduler, 'setRetryConfig').mockResolvedValue(undefined);
+
+      const config = await (simulator as any).enactmentRetryConfig(101, 0);
+
+      expect(config).toEqual({ totalRetries: 3, remaining: 3, period: 1 });
+      expect(setRetryConfig).toHaveBeenCalledWith(101, 0, config);
+    });
+
+    it("keeps the task's own configuration", async () => {
+      const api = createMockApi();
+      api.query.Scheduler.Retries = {
+        getValue: vi.fn().mockResolvedValue({ total_retries: 5, remaining: 4, period: 20 }),
+      };
+      const simulator = new ReferendumSimulator(
+        createSilentLogger(),
+        createMockChopsticks(),
+        api,
+        false,
+        { dispatchOptions: { enactmentRetries: 3 } }
+      );
+      const setRetryConfig = vi.spyOn((simulator as any).scheduler, 'setRetryConfig');
+
+      expect(await (simulator as any).enactmentRetryConfig(101, 0)).toEqual({
+        totalRetries: 5,
+        remaining: 4,
+        period: 20,
+      });
+      expect(setRetryConfig).not.toHaveBeenCalled();
+    });
+  });
 });
@@ -0,0 +1,57 @@
+import { describe, expect, it, vi } from 'vitest';
+import {
+  displayResourceUsage,
+  formatMebibytes,
+  measureResourceUsage,
+} from '../services/resource-usage';
+import type { Logger } from '../utils/logger';
+
+describe('measureResourceUsage', () => {
+  it('converts process.resourceUsage() units to ms and bytes', () => {
+    const usage = {
+      userCPUTime: 4_200_000,
+      systemCPUTime: 800_000,
+      maxRSS: 512 * 1024,
+    } as NodeJS.ResourceUsage;
+
+    expect(measureResourceUsage(usage, 12.3456)).toEqual({
+      wallMs: 12346,
+      cpuMs: 5000,
+      peakRssBytes: 512 * 1024 * 1024,
+    });
+  });
+
+  it('measures the current process by default', () => {
+    const usage = measureResourceUsage();
+
+    expect(usage.wallMs).toBeGreaterThan(0);
+    expect(usage.cpuMs).toBeGreaterThan(0);
+    expect(usage.peakRssBytes).toBeGreaterThan(0);
+  });
+});
+
+describe('formatMebibytes', () => {
+  it('shows one decimal', () => {
+    expect(formatMebibytes(812.44 * 1024 * 1024)).toBe('812.4 MiB');
+    expect(formatMebibytes(0)).toBe('0.0 MiB');
+  });
+});
+
+describe('displayResourceUsage', () => {
+  it('prints wall time, CPU time and peak RSS in their own section', () => {
+    const logger = { section: vi.fn(), info: vi.fn() } as unknown as Logger;
+
+    displayResourceUsage(logger, {
+      wallMs: 95_200,
+      cpuMs: 61_000,
+      peakRssBytes: 1536 * 1024 * 1024,
+    });
+
+    expect(logger.section).toHaveBeenCalledWith('Resource Usage');
+    expect(vi.mocked(logger.info).mock.calls.map(([line]) => line)).toEqual([
+      'Wall time: 95.2s',
+      'CPU time: 61.0s',
+      'Peak RSS: 1536.0 MiB',
+    ]);
+  });
+});
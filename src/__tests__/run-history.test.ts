@@ -0,0 +1,112 @@
+import * as fs from 'fs';
+import * as os from 'os';
+import * as path from 'path';
+import { afterEach, describe, expect, it, vi } from 'vitest';
+import { diffRuns, formatRunDiff, RunHistory, toStoredResult } from '../services/run-history';
+import type { SimulationResult } from '../types';
+import { createSilentLogger } from './test-logger';
+
+function extrinsicSuccess(refTime: bigint) {
+  return {
+    section: 'System',
+    method: 'ExtrinsicSuccess',
+    data: { dispatch_info: { weight: { ref_time: refTime, proof_size: 1000n }, class: 'Normal' } },
+  };
+}
+
+const PASSING: SimulationResult = {
+  referendumId: 7,
+  executionSucceeded: true,
+  events: [
+    extrinsicSuccess(100n),
+    { section: 'Scheduler', method: 'Dispatched', data: { result: { success: true } } },
+  ],
+  balanceChanges: [{ account: 'alice', asset: 'native', before: 10n, after: 5n, delta: -5n }],
+};
+
+describe('diffRuns', () => {
+  it('reports nothing for identical runs', () => {
+    const diff = diffRuns(toStoredResult(PASSING), toStoredResult(PASSING));
+    expect(formatRunDiff(diff)).toEqual([]);
+  });
+
+  it('reports a new failure, changed weights and changed balance deltas', () => {
+    const current: SimulationResult = {
+      referendumId: 7,
+      executionSucceeded: false,
+      events: [
+        extrinsicSuccess(250n),
+        {
+          section: 'Scheduler',
+          method: 'Dispatched',
+          data: { result: { success: false, value: { type: 'BadOrigin' } } },
+        },
+      ],
+      balanceChanges: [{ account: 'alice', asset: 'native', before: 10n, after: 10n, delta: 0n }],
+    };
+    const diff = diffRuns(toStoredResult(PASSING), toStoredResult(current));
+
+    expect(diff.executionChanged).toEqual({ before: true, after: false });
+    expect(diff.newFailingEvents).toHaveLength(1);
+    expect(diff.newFailingEvents[0]).toMatch(/^Scheduler\.Dispatched: .*BadOrigin/);
+    expect(diff.changedWeights).toEqual([
+      {
+        event: 'System.ExtrinsicSuccess#0.dispatch_info.weight',
+        before: { ref_time: '100', proof_size: '1000' },
+        after: { ref_time: '250', proof_size: '1000' },
+      },
+    ]);
+    expect(diff.changedBalances).toEqual([
+      { account: 'alice', asset: 'native', before: '-5', after: '0' },
+    ]);
+  });
+});
+
+describe('RunHistory', () => {
+  let dir: string | undefined;
+
+  afterEach(() => {
+    if (dir) fs.rmSync(dir, { recursive: true, force: true });
+  });
+
+  it('diffs each run with the latest stored run of the same referendum', async () => {
+    dir = fs.mkdtempSync(path.join(os.tmpdir(), 'referenda-history-'));
+    const api = {
+      constants: { System: { Version: vi.fn().mockResolvedValue({ spec_name: 'polkadot' }) } },
+    } as any;
+    const history = new RunHistory(createSilentLogger(), { dir, diffWithLast: true });
+
+    expect(await history.record(api, false, PASSING)).toBeUndefined();
+    const diff = await history.record(api, false, { ...PASSING, executionSucceeded: false });
+
+    expect(diff?.executionChanged).toEqual({ before: true, after: false });
+    expect(fs.readdirSync(path.join(dir, 'polkadot-Referenda-7'))).toHaveLength(2);
+    expect(history.latest('polkadot-Referenda-7')?.result.executionSucceeded).toBe(false);
+  });
+
+  it('stores the run id with each record', () => {
+    dir = fs.mkdtempSync(path.join(os.tmpdir(), 'referenda-history-'));
+    const history = new RunHistory(createSilentLogger(), { dir, runId: 'gov_create-4242-1' });
+
+    history.save('polkadot-Referenda-7', PASSING);
+
+    expect(history.latest('polkadot-Referenda-7')?.runId).toBe('gov_create-4242-1');
+    expect(fs.readdirSync(path.join(dir, 'polkadot-Referenda-7'))).toHaveLength(1);
+  });
+
+  it('never overwrites the record of a run that took the same run number', () => {
+    dir = fs.mkdtempSync(path.join(os.tmpdir(), 'referenda-history-'));
+    const runDir = path.join(dir, 'polkadot-Referenda-7');
+    fs.mkdirSync(runDir);
+    // Another run stored 0002 while 0001 is still missing, so this run counts one record
+    fs.writeFileSync(path.join(runDir, '0002.json'), '{"runId":"other"}');
+
+    new RunHistory(createSilentLogger(), { dir, runId: 'this' }).save(
+      'polkadot-Referenda-7',
+      PASSING
+    );
+
+    expect(fs.readdirSync(runDir).sort()).toEqual(['0002.json', '0003.json']);
+    expect(fs.readFileSync(path.join(runDir, '0002.json'), 'utf8')).toBe('{"runId":"other"}');
+  });
+});
@@ -0,0 +1,102 @@
+import { afterEach, describe, expect, it, vi } from 'vitest';
+import {
+  buildRunReport,
+  emitRunReport,
+  parseOutputFormat,
+  RESULT_LINE_PREFIX,
+  reportEndpoints,
+} from '../services/run-report';
+import { CodedError, ErrorCode } from '../utils/error-codes';
+
+const USAGE = { wallMs: 1000, cpuMs: 800, peakRssBytes: 1024 };
+
+describe('parseOutputFormat', () => {
+  it('defaults to text', () => {
+    expect(parseOutputFormat(undefined)).toBe('text');
+    expect(parseOutputFormat(' json ')).toBe('json');
+  });
+
+  it('rejects unknown formats', () => {
+    expect(() => parseOutputFormat('yaml')).toThrow(
+      expect.objectContaining({ code: 'E-INVALID-OUTPUT-FORMAT' })
+    );
+  });
+});
+
+describe('buildRunReport', () => {
+  it('reports a passed run', () => {
+    expect(buildRunReport('run-1', undefined, USAGE)).toEqual({
+      runId: 'run-1',
+      exitCode: 0,
+      error: null,
+      endpoints: null,
+      heads: [],
+      resourceUsage: USAGE,
+    });
+  });
+
+  it('reports where each fork started and ended', () => {
+    const heads = [
+      {
+        chain: 'AssetHub',
+        endpoint: 'ws://localhost:8000',
+        fork: { number: 100, hash: '0x01' },
+        final: { number: 104, hash: '0x05' },
+      },
+    ];
+
+    expect(buildRunReport('run-1', undefined, USAGE, undefined, heads).heads).toEqual(heads);
+  });
+
+  it('reports the parsed chain URLs', () => {
+    const endpoints = reportEndpoints({
+      governance: { url: 'wss://ah', block: 255 },
+      additionalChains: [{ url: 'wss://relay', label: 'Relay' }],
+    });
+
+    expect(buildRunReport('run-1', undefined, USAGE, endpoints).endpoints).toEqual({
+      governance: { url: 'wss://ah', block: 255, label: null },
+      fellowship: null,
+      additionalChains: [{ url: 'wss://relay', block: null, label: 'Relay' }],
+    });
+  });
+
+  it('reports the code, message and exit code of a failure', () => {
+    const error = new CodedError(ErrorCode.PreimageMissing, 'Preimage 0x01 is not noted');
+
+    expect(buildRunReport('run-1', error, USAGE)).toMatchObject({
+      exitCode: 2,
+      error: { code: 'E-PREIMAGE-MISSING', message: 'Preimage 0x01 is not noted' },
+    });
+  });
+
+  it('reports uncoded errors as E-UNKNOWN', () => {
+    expect(buildRunReport('run-1', new Error('boom'), USAGE).error).toEqual({
+      code: 'E-UNKNOWN',
+      message: 'boom',
+    });
+  });
+});
+
+describe('emitRunReport', () => {
+  afterEach(() => {
+    vi.restoreAllMocks();
+  });
+
+  it('prints one @@result line in json format only', () => {
+    const log = vi.spyOn(console, 'log').mockImplementation(() => {});
+
+    emitRunReport('text', 'run-1');
+    expect(log).not.toHaveBeenCalled();
+
+    emitRunReport('json', 'run-1');
+    expect(log).toHaveBeenCalledTimes(1);
+    const line = log.mock.calls[0][0] as string;
+    expect(line.startsWith(RESULT_LINE_PREFIX)).toBe(true);
+    expect(JSON.parse(line.slice(RESULT_LINE_PREFIX.length))).toMatchObject({
+      runId: 'run-1',
+      exitCode: 0,
+      error: null,
+    });
+  });
+});
@@ -0,0 +1,138 @@
+import { describe, expect, it, vi } from 'vitest';
+import {
+  type CapabilityMetadata,
+  checkRuntimeCapabilities,
+  formatCapabilities,
+  missingCapabilities,
+  probeCapabilities,
+} from '../services/runtime-capabilities';
+import { createSilentLogger } from './test-logger';
+
+const RELAY_APIS = [{ name: 'Core' }, { name: 'BlockBuilder' }, { name: 'Metadata' }];
+
+function chain(...pallets: string[]): CapabilityMetadata {
+  return { pallets: pallets.map((name) => ({ name })), apis: RELAY_APIS };
+}
+
+function request(metadata: string) {
+  return vi.fn().mockResolvedValue(metadata);
+}
+
+// Metadata "hex" is looked up by name.
+const METADATA: Record<string, CapabilityMetadata> = {
+  assetHub: chain('Scheduler', 'Preimage', 'ConvictionVoting', 'Whitelist', 'AhMigrator'),
+  parachain: chain('System', 'Scheduler', 'Preimage'),
+  noScheduler: chain('System'),
+};
+
+vi.mock('@polkadot-api/substrate-bindings', () => ({
+  decAnyMetadata: (hex: string) => METADATA[hex],
+  unifyMetadata: (metadata: unknown) => metadata,
+}));
+
+describe('probeCapabilities', () => {
+  it('probes the capabilities of the roles the chain hosts', () => {
+    const probed = probeCapabilities(METADATA.assetHub, ['governance'], {});
+
+    expect(probed.map(({ name, status }) => [name, status])).toEqual([
+      ['Scheduler', 'present'],
+      ['Preimage', 'present'],
+      ['ConvictionVoting', 'present'],
+      ['Whitelist', 'present'],
+      ['Treasury', 'absent'],
+      ['AhMigrator', 'present'],
+      ['Core', 'present'],
+      ['BlockBuilder', 'present'],
+      ['Metadata', 'present'],
+    ]);
+    const fellowship = probeCapabilities(METADATA.assetHub, ['fellowship'], {});
+    expect(fellowship.map((c) => c.name)).toContain('CoreFellowship');
+  });
+
+  it('marks runtime APIs unknown when the metadata does not list them', () => {
+    const probed = probeCapabilities({ pallets: [] }, ['governance'], {});
+
+    expect(probed.find((c) => c.name === 'Core')?.status).toBe('unknown');
+    expect(missingCapabilities('Chain', probed)).toEqual([
+      'Chain has no Scheduler pallet, needed by every run',
+    ]);
+  });
+
+  it('needs a pallet only when a flag of the role uses it', () => {
+    const options = { voteConviction: 'Locked1x' };
+    const governance = probeCapabilities(METADATA.parachain, ['governance'], options);
+    const fellowship = probeCapabilities(METADATA.parachain, ['fellowship'], options);
+
+    expect(missingCapabilities('Para', governance)).toEqual([
+      'Para has no ConvictionVoting pallet, needed by --vote-conviction',
+    ]);
+    expect(missingCapabilities('Para', fellowship)).toEqual([]);
+  });
+});
+
+describe('formatCapabilities', () => {
+  it('lines up the matrix and marks skipped features', () => {
+    expect(
+      formatCapabilities([
+        { name: 'Scheduler', kind: 'pallet', usedFor: 'enacting the proposal', status: 'present' },
+        { name: 'Whitelist', kind: 'pallet', usedFor: 'whitelisted calls', status: 'absent' },
+        { name: 'Core', kind: 'api', usedFor: 'executing blocks', status: 'unknown' },
+      ])
+    ).toEqual([
+      'Capability  Status   Used for',
+      'Scheduler   present  enacting the proposal',
+      'Whitelist   absent   whitelisted calls (skipped)',
+      'Core API    unknown  executing blocks',
+    ]);
+  });
+});
+
+describe('checkRuntimeCapabilities', () => {
+  it('prints a matrix per chain and lets a run without optional features go on', async () => {
+    const logger = createSilentLogger();
+
+    await checkRuntimeCapabilities(
+      logger,
+      [{ label: 'Para', roles: ['governance'], request: request('parachain') }],
+      {}
+    );
+
+    expect(logger.section).toHaveBeenCalledWith('Runtime Capabilities');
+    expect(logger.info).toHaveBeenCalledWith('Para (governance):');
+    expect(logger.info).toHaveBeenCalledWith(expect.stringMatching(/^ {2}Whitelist +absent/));
+  });
+
+  it('fails with E-MISSING-CAPABILITY when the run needs an absent pallet', async () => {
+    await expect(
+      checkRuntimeCapabilities(
+        createSilentLogger(),
+        [
+          { label: 'AssetHub', roles: ['governance'], request: request('assetHub') },
+          { label: 'Bare', roles: ['fellowship'], request: request('noScheduler') },
+        ],
+        {}
+      )
+    ).rejects.toMatchObject({
+      code: 'E-MISSING-CAPABILITY',
+      message: 'Bare has no Scheduler pallet, needed by every run',
+    });
+  });
+
+  it('skips a chain whose metadata cannot be read', async () => {
+    const logger = createSilentLogger();
+
+    await checkRuntimeCapabilities(
+      logger,
+      [
+        {
+          label: 'Down',
+          roles: ['governance'],
+          request: vi.fn().mockRejectedValue(new Error('gone')),
+        },
+      ],
+      {}
+    );
+
+    expect(logger.warn).toHaveBeenCalledWith('Could not probe Down: gone');
+  });
+});
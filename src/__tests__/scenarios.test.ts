@@ -0,0 +1,22 @@
+import { describe, expect, it } from 'vitest';
+import { parseScenario } from '../services/scenarios';
+import { ErrorCode } from '../utils/error-codes';
+
+describe('parseScenario', () => {
+  it('defaults to no scenario', () => {
+    expect(parseScenario(undefined)).toBeUndefined();
+  });
+
+  it('parses bad-origin', () => {
+    expect(parseScenario(' bad-origin ')).toBe('bad-origin');
+  });
+
+  it('rejects unknown scenarios', () => {
+    expect(() => parseScenario('bad-weight')).toThrow(
+      expect.objectContaining({
+        code: ErrorCode.InvalidScenario,
+        message: 'Invalid --scenario value: bad-weight (expected one of: bad-origin)',
+      })
+    );
+  });
+});
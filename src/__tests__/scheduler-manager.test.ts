@@ -1,24 +1,6 @@
 import { describe, expect, it, vi } from 'vitest';
-import { SchedulerManager } from '../services/scheduler-manager';
-import type { Logger } from '../utils/logger';
-
-function createSilentLogger(): Logger {
-  return {
-    info: vi.fn(),
-    debug: vi.fn(),
-    warn: vi.fn(),
-    error: vi.fn(),
-    success: vi.fn(),
-    isVerbose: () => false,
-    startSpinner: vi.fn(),
-    succeedSpinner: vi.fn(),
-    failSpinner: vi.fn(),
-    updateSpinner: vi.fn(),
-    stopSpinner: vi.fn(),
-    section: vi.fn(),
-    table: vi.fn(),
-  } as unknown as Logger;
-}
+import { parseEnactmentRetries, SchedulerManager } from '../services/scheduler-manager';
+import { createSilentLogger } from './test-logger';
 
 function createMockChopsticks() {
   return {
@@ -35,7 +17,7 @@ function createMockApi(overrides: Record<string, unknown> = {}) {
       },
       ParachainSystem: undefined as any,
       Scheduler: {
-        Agenda: { getEntries: vi.fn().mockResolvedValue([]) },
+        Agenda: { getEntries: vi.fn().mockResolvedValue([]), getValue: vi.fn() },
         Lookup: { getValue: vi.fn().mockResolvedValue(null) },
       },
     },
@@ -267,6 +249,29 @@ describe('SchedulerManager', () => {
       expect(result.taskIndex).toBe(0);
     });
 
+    it('dispatches a moved execute call from an overridden origin', async () => {
+      const api = createMockApi();
+      const chopsticks = createMockChopsticks();
+
+      api.query.Scheduler.Agenda.getEntries.mockResolvedValue([
+        {
+          keyArgs: [600],
+          value: [
+            {
+              call: { type: 'Lookup', value: { hash: new Uint8Array([0xaa]) } },
+              origin: { type: 'system', value: { type: 'Root' } },
+            },
+          ],
+        },
+      ]);
+
+      const manager = new SchedulerManager(createSilentLogger(), chopsticks, api, false);
+      await manager.moveScheduledCallToNextBlock(42, 'execute', '0xaa', { system: 'None' });
+
+      const [, [, moved]] = chopsticks.setStorageBatch.mock.calls[0][0].Scheduler.Agenda;
+      expect(moved[0].origin).toEqual({ system: 'None' });
+    });
+
     it('updates Lookup when scheduledEntry has maybeId', async () => {
       const api = createMockApi();
       const chopsticks = createMockChopsticks();
@@ -363,6 +368,70 @@ describe('SchedulerManager', () => {
     });
   });
 
+  // ═══════════════════════════════════════════════════════════════════════
+  // moveTaskToNextBlock() - --follow-scheduled
+  // ═══════════════════════════════════════════════════════════════════════
+
+  describe('moveTaskToNextBlock()', () => {
+    it('moves the agenda holding the task and its lookup to the next block', async () => {
+      const api = createMockApi();
+      const chopsticks = createMockChopsticks();
+      const taskId = new Uint8Array([1, 2, 3, 4]);
+      api.query.Scheduler.Agenda.getValue.mockResolvedValue([
+        { call: { type: 'Inline', value: new Uint8Array([0x00]) } },
+        { call: { type: 'Inline', value: new Uint8Array([0x01]) }, maybeId: taskId },
+      ]);
+      api.query.Scheduler.Lookup.getValue.mockResolvedValue([5000, 1]);
+
+      const manager = new SchedulerManager(createSilentLogger(), chopsticks, api, false);
+      const moved = await manager.moveTaskToNextBlock(5000, 1);
+
+      expect(api.query.Scheduler.Agenda.getValue).toHaveBeenCalledWith(5000);
+      expect(moved).toEqual({ block: 101, taskIndex: 1, taskId });
+      const agenda = chopsticks.setStorageBatch.mock.calls[0][0].Scheduler.Agenda;
+      expect(agenda[0]).toEqual([[5000], null]);
+      expect(agenda[1][0]).toEqual([101]);
+      expect(agenda[1][1]).toHaveLength(2);
+      expect(chopsticks.setStorageBatch.mock.calls[1][0].Scheduler.Lookup).toEqual([
+        [[taskId], [101, 1]],
+      ]);
+    });
+
+    it('moves the retry configurations of the agenda with it', async () => {
+      const api = createMockApi();
+      const chopsticks = createMockChopsticks();
+      api.query.Scheduler.Agenda.getValue.mockResolvedValue([
+        { call: { type: 'Inline', value: new Uint8Array([0x00]) } },
+        { call: { type: 'Inline', value: new Uint8Array([0x01]) } },
+      ]);
+      api.query.Scheduler.Retries = {
+        getValue: vi.fn(async ([, index]: [number, number]) =>
+          index === 1 ? { total_retries: 3, remaining: 2, period: 10 } : undefined
+        ),
+      };
+
+      const manager = new SchedulerManager(createSilentLogger(), chopsticks, api, false);
+      await manager.moveTaskToNextBlock(5000, 1);
+
+      expect(chopsticks.setStorageBatch.mock.calls[0][0].Scheduler.Retries).toEqual([
+        [[[5000, 1]], null],
+        [[[101, 1]], { total_retries: 3, remaining: 2, period: 10 }],
+      ]);
+    });
+
+    it('throws when the task is not in the agenda', async () => {
+      const api = createMockApi();
+      const chopsticks = createMockChopsticks();
+      api.query.Scheduler.Agenda.getValue.mockResolvedValue([]);
+
+      const manager = new SchedulerManager(createSilentLogger(), chopsticks, api, false);
+
+      await expect(manager.moveTaskToNextBlock(5000, 0)).rejects.toThrow(
+        'Scheduled task [5000, 0] not found'
+      );
+    });
+  });
+
   // ═══════════════════════════════════════════════════════════════════════
   // isNudgeReferendumCall() - nudge detection strategies
   // ═══════════════════════════════════════════════════════════════════════
@@ -635,4 +704,82 @@ describe('SchedulerManager', () => {
       expect(result).toBe(true);
     });
   });
+
+  describe('isTaskPending()', () => {
+    it('reports a task still in its agenda slot', async () => {
+      const api = createMockApi();
+      api.query.Scheduler.Agenda.getValue = vi
+        .fn()
+        .mockResolvedValue([undefined, { call: { type: 'Inline' } }]);
+      const manager = new SchedulerManager(
+        createSilentLogger(),
+        createMockChopsticks(),
+        api,
+        false
+      );
+
+      expect(await manager.isTaskPending(101, 1)).toBe(true);
+      expect(await manager.isTaskPending(101, 0)).toBe(false);
+      expect(api.query.Scheduler.Agenda.getValue).toHaveBeenCalledWith(101);
+    });
+
+    it('reports a serviced agenda as not pending', async () => {
+      const api = createMockApi();
+      api.query.Scheduler.Agenda.getValue = vi.fn().mockResolvedValue(undefined);
+      const manager = new SchedulerManager(
+        createSilentLogger(),
+        createMockChopsticks(),
+        api,
+        false
+      );
+
+      expect(await manager.isTaskPending(101, 0)).toBe(false);
+    });
+  });
+
+  describe('getRetryConfig()', () => {
+    it('decodes the retry configuration of a task', async () => {
+      const api = createMockApi();
+      api.query.Scheduler.Retries = {
+        getValue: vi.fn().mockResolvedValue({ total_retries: 3, remaining: 1, period: 10 }),
+      };
+      const manager = new SchedulerManager(
+        createSilentLogger(),
+        createMockChopsticks(),
+        api,
+        false
+      );
+
+      expect(await manager.getRetryConfig(101, 0)).toEqual({
+        totalRetries: 3,
+        remaining: 1,
+        period: 10,
+      });
+      expect(api.query.Scheduler.Retries.getValue).toHaveBeenCalledWith([101, 0]);
+    });
+
+    it('has none on runtimes without scheduler retries', async () => {
+      const manager = new SchedulerManager(
+        createSilentLogger(),
+        createMockChopsticks(),
+        createMockApi(),
+        false
+      );
+
+      expect(await manager.getRetryConfig(101, 0)).toBeUndefined();
+    });
+  });
+});
+
+describe('parseEnactmentRetries', () => {
+  it('parses a number of retries', () => {
+    expect(parseEnactmentRetries('3')).toBe(3);
+    expect(parseEnactmentRetries(undefined)).toBeUndefined();
+  });
+
+  it.each(['0', '256', '-1', 'abc', '1.5'])('rejects %s', (value) => {
+    expect(() => parseEnactmentRetries(value)).toThrow(
+      expect.objectContaining({ code: 'E-INVALID-ENACTMENT-RETRIES' })
+    );
+  });
 });
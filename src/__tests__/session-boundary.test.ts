@@ -0,0 +1,47 @@
+import { describe, expect, it } from 'vitest';
+import { isSessionBoundary, readEpochLength } from '../services/session-boundary';
+import type { SubstrateApi } from '../types/substrate-api';
+
+function mockApi(constants: Record<string, unknown>): SubstrateApi {
+  return { constants } as unknown as SubstrateApi;
+}
+
+describe('isSessionBoundary', () => {
+  it('is true for multiples of the epoch length', () => {
+    expect(isSessionBoundary(2400, 600)).toBe(true);
+    expect(isSessionBoundary(600, 600)).toBe(true);
+  });
+
+  it('is false inside an epoch', () => {
+    expect(isSessionBoundary(2399, 600)).toBe(false);
+    expect(isSessionBoundary(2401, 600)).toBe(false);
+  });
+
+  it('is false for genesis and unknown epoch lengths', () => {
+    expect(isSessionBoundary(0, 600)).toBe(false);
+    expect(isSessionBoundary(2400, undefined)).toBe(false);
+    expect(isSessionBoundary(2400, 0)).toBe(false);
+  });
+});
+
+describe('readEpochLength', () => {
+  it('reads Babe.EpochDuration', async () => {
+    const api = mockApi({ Babe: { EpochDuration: async () => 600n } });
+    expect(await readEpochLength(api)).toBe(600);
+  });
+
+  it('is undefined for chains without BABE', async () => {
+    expect(await readEpochLength(mockApi({}))).toBeUndefined();
+  });
+
+  it('is undefined when the constant cannot be read', async () => {
+    const api = mockApi({
+      Babe: {
+        EpochDuration: async () => {
+          throw new Error('Runtime entry Babe.EpochDuration not found');
+        },
+      },
+    });
+    expect(await readEpochLength(api)).toBeUndefined();
+  });
+});
@@ -0,0 +1,119 @@
+import { afterEach, describe, expect, it, vi } from 'vitest';
+import { Shutdown, STOP_METHOD } from '../services/shutdown';
+import type { Logger } from '../utils/logger';
+
+function mockLogger(): Logger {
+  return { warn: vi.fn() } as unknown as Logger;
+}
+
+describe('Shutdown', () => {
+  const errorSpy = vi.spyOn(console, 'error').mockImplementation(() => {});
+
+  afterEach(() => {
+    errorSpy.mockClear();
+  });
+
+  it('tears down registered forks, then exits with 130 and E-INTERRUPTED', async () => {
+    const exit = vi.fn();
+    const shutdown = new Shutdown(exit);
+    const order: string[] = [];
+    shutdown.register('governance', async () => {
+      order.push('governance');
+    });
+    shutdown.register('fellowship', async () => {
+      order.push('fellowship');
+    });
+
+    await shutdown.stop('Interrupted by SIGINT');
+
+    expect(order).toEqual(['governance', 'fellowship']);
+    expect(errorSpy).toHaveBeenCalledWith('E-INTERRUPTED: Interrupted by SIGINT');
+    expect(exit).toHaveBeenCalledWith(130);
+    expect(shutdown.stopping).toBe(true);
+  });
+
+  it('reports the E-INTERRUPTED error before exiting', async () => {
+    const exit = vi.fn();
+    const shutdown = new Shutdown(exit);
+    const report = vi.fn(() => expect(exit).not.toHaveBeenCalled());
+    const removeSignalHandlers = shutdown.install(mockLogger(), report);
+
+    await shutdown.stop('Interrupted by SIGTERM');
+    removeSignalHandlers();
+
+    expect(report).toHaveBeenCalledWith(
+      expect.objectContaining({ code: 'E-INTERRUPTED', message: 'Interrupted by SIGTERM' })
+    );
+    expect(exit).toHaveBeenCalledWith(130);
+  });
+
+  it('skips forks that were already cleaned up', async () => {
+    const shutdown = new Shutdown(vi.fn());
+    const teardown = vi.fn().mockResolvedValue(undefined);
+    const untrack = shutdown.register('governance', teardown);
+    untrack();
+
+    await shutdown.stop('Interrupted by SIGTERM');
+
+    expect(teardown).not.toHaveBeenCalled();
+  });
+
+  it('still exits when a teardown fails', async () => {
+    const exit = vi.fn();
+    const logger = mockLogger();
+    const shutdown = new Shutdown(exit);
+    const removeSignalHandlers = shutdown.install(logger);
+    shutdown.register('governance', async () => {
+      throw new Error('socket closed');
+    });
+
+    await shutdown.stop('Interrupted by SIGINT');
+    removeSignalHandlers();
+
+    expect(logger.warn).toHaveBeenCalledWith('Failed to tear down governance: socket closed');
+    expect(exit).toHaveBeenCalledWith(130);
+  });
+
+  it('exits at once on a second stop', async () => {
+    const exit = vi.fn();
+    const shutdown = new Shutdown(exit);
+    shutdown.register('governance', () => new Promise(() => {}));
+
+    void shutdown.stop('Interrupted by SIGINT');
+    await shutdown.stop('Interrupted by SIGINT');
+
+    expect(exit).toHaveBeenCalledOnce();
+    expect(exit).toHaveBeenCalledWith(130);
+  });
+
+  it('stops on a dev_stop request to the control endpoint', async () => {
+    const exit = vi.fn();
+    const shutdown = new Shutdown(exit);
+    const removeSignalHandlers = shutdown.install(mockLogger());
+    const teardown = vi.fn().mockResolvedValue(undefined);
+    shutdown.register('governance', teardown);
+
+    try {
+      const url = await shutdown.serveControl();
+      expect(await shutdown.serveControl()).toBe(url);
+
+      const unknown = await fetch(url, {
+        method: 'POST',
+        body: JSON.stringify({ jsonrpc: '2.0', id: 1, method: 'dev_newBlock' }),
+      });
+      expect((await unknown.json()).error.code).toBe(-32601);
+      expect(teardown).not.toHaveBeenCalled();
+
+      const stop = await fetch(url, {
+        method: 'POST',
+        body: JSON.stringify({ jsonrpc: '2.0', id: 2, method: STOP_METHOD }),
+      });
+      expect(await stop.json()).toEqual({ jsonrpc: '2.0', id: 2, result: true });
+      await vi.waitFor(() => expect(exit).toHaveBeenCalledWith(130));
+      expect(teardown).toHaveBeenCalledOnce();
+      expect(errorSpy).toHaveBeenCalledWith('E-INTERRUPTED: Stopped by dev_stop');
+    } finally {
+      removeSignalHandlers();
+    }
+  });
+});
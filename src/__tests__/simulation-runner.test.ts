@@ -1,28 +1,253 @@
-import { describe, expect, it, vi } from 'vitest';
-import { SimulationRunner } from '../services/simulation-runner';
+import { describe, expect, it } from 'vitest';
+import {
+  describeOutcome,
+  formatOutcome,
+  settleOutcomes,
+  SimulationRunner,
+} from '../services/simulation-runner';
 import type { SimulationResult } from '../types';
-import type { Logger } from '../utils/logger';
-
-function createSilentLogger(): Logger {
-  return {
-    info: vi.fn(),
-    debug: vi.fn(),
-    warn: vi.fn(),
-    error: vi.fn(),
-    success: vi.fn(),
-    isVerbose: () => false,
-    startSpinner: vi.fn(),
-    succeedSpinner: vi.fn(),
-    failSpinner: vi.fn(),
-    updateSpinner: vi.fn(),
-    stopSpinner: vi.fn(),
-    section: vi.fn(),
-    table: vi.fn(),
-  } as unknown as Logger;
-}
+import { CodedError, ErrorCode } from '../utils/error-codes';
+import { createSilentLogger } from './test-logger';
+
+describe('describeOutcome', () => {
+  it('names the block that dispatched the proposal', () => {
+    const result: SimulationResult = {
+      referendumId: 1,
+      executionSucceeded: true,
+      events: [],
+      blockExecuted: 105,
+    };
+    expect(describeOutcome(result)).toBe('executed successfully at block 105!');
+  });
+
+  it('names the retry that dispatched it', () => {
+    const result: SimulationResult = {
+      referendumId: 1,
+      executionSucceeded: true,
+      events: [],
+      blockExecuted: 107,
+      retries: {
+        config: { totalRetries: 3, remaining: 3, period: 1 },
+        firstErrors: ['Module error: Treasury.InsufficientFunds'],
+        attempts: [
+          { scheduledAt: 106, index: 0, executedAt: 106, succeeded: false },
+          { scheduledAt: 107, index: 0, executedAt: 107, succeeded: true },
+        ],
+      },
+    };
+    expect(describeOutcome(result)).toBe('executed successfully at block 107 on retry 2!');
+  });
+
+  it('reports a cancellation instead', () => {
+    const result: SimulationResult = {
+      referendumId: 1,
+      executionSucceeded: false,
+      events: [],
+      cancellation: { phase: 'ongoing', block: 101, deposits: [] },
+    };
+    expect(describeOutcome(result)).toBe('cancelled and its deposits refunded');
+  });
+});
+
+describe('settleOutcomes', () => {
+  const failure = new CodedError(ErrorCode.ExecutionFailed, 'Main referendum #5 execution failed');
+
+  it('lists each referendum and passes when both passed', () => {
+    const logger = createSilentLogger();
+    settleOutcomes(logger, [
+      { role: 'fellowship', referendumId: 3 },
+      { role: 'governance', referendumId: 5 },
+    ]);
+
+    expect(logger.section).toHaveBeenCalledWith('Referendum Results');
+    expect(logger.success).toHaveBeenCalledWith('fellowship #3: passed');
+    expect(logger.success).toHaveBeenCalledWith('governance #5: passed');
+  });
+
+  it('fails with E-PARTIAL-SUCCESS when only one passed', () => {
+    const logger = createSilentLogger();
+    expect(() =>
+      settleOutcomes(logger, [
+        { role: 'fellowship', referendumId: 3 },
+        { role: 'governance', referendumId: 5, error: failure },
+      ])
+    ).toThrow(
+      expect.objectContaining({
+        code: ErrorCode.PartialSuccess,
+        message:
+          'fellowship #3 passed, but governance #5 failed with ' +
+          'E-EXECUTION-FAILED: Main referendum #5 execution failed',
+      })
+    );
+    expect(logger.error).toHaveBeenCalledWith(
+      'governance #5: failed, E-EXECUTION-FAILED: Main referendum #5 execution failed'
+    );
+  });
+
+  it('fails with the first failure when none passed', () => {
+    const first = new CodedError(ErrorCode.PreimageMissing, 'Fellowship referendum #3 failed');
+    expect(() =>
+      settleOutcomes(createSilentLogger(), [
+        { role: 'fellowship', referendumId: 3, error: first },
+        { role: 'governance', referendumId: 5, error: failure },
+      ])
+    ).toThrow(first);
+  });
+
+  it('formats errors without a code as E-UNKNOWN', () => {
+    expect(
+      formatOutcome({ role: 'fellowship', referendumId: 3, error: new Error('socket closed') })
+    ).toBe('fellowship #3: failed, E-UNKNOWN: socket closed');
+  });
+});
 
 describe('SimulationRunner', () => {
+  describe('attempt()', () => {
+    it('returns a failure of the referendum so the other one still runs', async () => {
+      const runner = new SimulationRunner(createSilentLogger()) as any;
+      const error = new CodedError(ErrorCode.ExecutionFailed, 'Fellowship referendum #3 failed');
+
+      const outcome = await runner.attempt('fellowship', 3, async () => {
+        throw error;
+      });
+
+      expect(outcome).toEqual({ role: 'fellowship', referendumId: 3, error });
+    });
+
+    it.each([
+      new CodedError(ErrorCode.Interrupted, 'Interrupted by SIGINT'),
+      new CodedError(ErrorCode.ChopsticksFailed, 'Failed to start Chopsticks'),
+      new Error('socket closed'),
+    ])('rethrows %s, which leaves no fork to go on with', async (error) => {
+      const runner = new SimulationRunner(createSilentLogger()) as any;
+
+      await expect(
+        runner.attempt('fellowship', 3, async () => {
+          throw error;
+        })
+      ).rejects.toBe(error);
+    });
+  });
+
   describe('throwIfFailed()', () => {
+    it('fails with E-LINT-DENIED when a denied lint fired on a successful execution', () => {
+      const runner = new SimulationRunner(createSilentLogger());
+      const result: SimulationResult = {
+        referendumId: 1,
+        executionSucceeded: true,
+        events: [],
+        lints: [
+          { rule: 'sudo-like-call-on-non-root-track', level: 'warn', message: 'm', call: 'A.b' },
+          { rule: 'set-code-without-authorize-upgrade', level: 'deny', message: 'm', call: 'A.c' },
+        ],
+      };
+      expect(() => runner.throwIfFailed(result, 'Test')).toThrow(
+        expect.objectContaining({ code: ErrorCode.LintDenied })
+      );
+    });
+
+    it('fails with E-TREASURY-PAYOUT-FAILED when a spend payout failed', () => {
+      const runner = new SimulationRunner(createSilentLogger());
+      const result: SimulationResult = {
+        referendumId: 1,
+        executionSucceeded: true,
+        events: [],
+        treasurySpends: [
+          { index: 3, kind: 'spend', asset: 'native', amount: 10n, status: 'paid' },
+          {
+            index: 4,
+            kind: 'spend',
+            asset: 'Assets #1984 (USDT)',
+            amount: 10n,
+            status: 'failed',
+            error: 'EarlyPayout',
+          },
+        ],
+      };
+      expect(() => runner.throwIfFailed(result, 'Test')).toThrow(
+        expect.objectContaining({
+          code: ErrorCode.TreasuryPayoutFailed,
+          message: 'Test executed, but treasury payout failed: spend #4 (EarlyPayout)',
+        })
+      );
+    });
+
+    it('fails with E-BALANCE-CHECK-FAILED when a tracked balance moved unexpectedly', () => {
+      const runner = new SimulationRunner(createSilentLogger());
+      const change = { account: 'alice', asset: 'Assets #1984', before: 0n, after: 5n, delta: 5n };
+      const result: SimulationResult = {
+        referendumId: 1,
+        executionSucceeded: true,
+        events: [],
+        balanceChanges: [
+          { ...change, account: 'bob' },
+          { ...change, expectedDelta: 10n },
+        ],
+      };
+      expect(() => runner.throwIfFailed(result, 'Test')).toThrow(
+        expect.objectContaining({
+          code: ErrorCode.BalanceCheckFailed,
+          message:
+            'Test executed, but tracked balances changed unexpectedly: ' +
+            'alice Assets #1984 changed by 5, expected 10',
+        })
+      );
+    });
+
+    it('fails with E-CONVICTION-LOCK-FAILED when the voter was left with the wrong lock', () => {
+      const runner = new SimulationRunner(createSilentLogger());
+      const result: SimulationResult = {
+        referendumId: 1,
+        executionSucceeded: true,
+        events: [],
+        convictionLock: {
+          who: 'alice',
+          track: 3,
+          conviction: 'Locked1x',
+          balance: 10n,
+          classLock: 10n,
+          unlockAt: 0,
+          expectedUnlockAt: 1100,
+          lockedAfterUnlock: 0n,
+          errors: ['vote unlocks at block #0, expected #1100'],
+        },
+      };
+      expect(() => runner.throwIfFailed(result, 'Test')).toThrow(
+        expect.objectContaining({
+          code: ErrorCode.ConvictionLockFailed,
+          message:
+            "Test executed, but the voter's conviction lock is wrong: " +
+            'vote unlocks at block #0, expected #1100',
+        })
+      );
+    });
+
+    it('fails with E-CANCELLATION-FAILED when a deposit of a cancelled referendum was kept', () => {
+      const runner = new SimulationRunner(createSilentLogger());
+      const deposit = { who: 'alice', amount: 10n, refunded: true };
+      const result: SimulationResult = {
+        referendumId: 1,
+        executionSucceeded: false,
+        events: [],
+        cancellation: {
+          phase: 'confirming',
+          block: 101,
+          deposits: [
+            { ...deposit, kind: 'submission' },
+            { ...deposit, kind: 'decision', refunded: false, error: 'alice received 0' },
+          ],
+        },
+      };
+      expect(() => runner.throwIfFailed(result, 'Test')).toThrow(
+        expect.objectContaining({
+          code: ErrorCode.CancellationFailed,
+          message:
+            'Test was cancelled, but its deposits were not refunded: ' +
+            'decision deposit of 10 to alice (alice received 0)',
+        })
+      );
+    });
+
     it('does not throw when execution succeeded', () => {
       const runner = new SimulationRunner(createSilentLogger());
       const result: SimulationResult = {
@@ -62,6 +287,79 @@ describe('SimulationRunner', () => {
       expect(logger.error).toHaveBeenCalledWith('  Module error: foo');
       expect(logger.error).toHaveBeenCalledWith('  Dispatch failed');
     });
+
+    it('codes a plain execution failure as E-EXECUTION-FAILED', () => {
+      const runner = new SimulationRunner(createSilentLogger());
+      const result: SimulationResult = {
+        referendumId: 1,
+        executionSucceeded: false,
+        events: [],
+      };
+      expect(() => runner.throwIfFailed(result, 'Test')).toThrow(
+        expect.objectContaining({ code: ErrorCode.ExecutionFailed })
+      );
+    });
+
+    it('codes a BadOrigin dispatch as E-BAD-ORIGIN', () => {
+      const runner = new SimulationRunner(createSilentLogger());
+      const result: SimulationResult = {
+        referendumId: 1,
+        executionSucceeded: false,
+        events: [],
+        errors: ['BadOrigin'],
+      };
+      expect(() => runner.throwIfFailed(result, 'Test')).toThrow(
+        expect.objectContaining({
+          code: ErrorCode.BadOrigin,
+          message: expect.stringContaining('Test execution failed'),
+        })
+      );
+    });
+
+    it('codes Scheduler.CallUnavailable as E-PREIMAGE-MISSING', () => {
+      const runner = new SimulationRunner(createSilentLogger());
+      const result: SimulationResult = {
+        referendumId: 1,
+        executionSucceeded: false,
+        events: [{ section: 'Scheduler', method: 'CallUnavailable', data: {} }],
+      };
+      expect(() => runner.throwIfFailed(result, 'Test')).toThrow(
+        expect.objectContaining({ code: ErrorCode.PreimageMissing })
+      );
+    });
+
+    it('codes a permanently overweight enactment as E-PERMANENTLY-OVERWEIGHT', () => {
+      const runner = new SimulationRunner(createSilentLogger());
+      const result: SimulationResult = {
+        referendumId: 1,
+        executionSucceeded: false,
+        events: [],
+        notDispatched: 'permanently-overweight',
+      };
+      expect(() => runner.throwIfFailed(result, 'Test')).toThrow(
+        expect.objectContaining({
+          code: ErrorCode.PermanentlyOverweight,
+          message: expect.stringContaining('weight is too high'),
+        })
+      );
+    });
+
+    it('codes an enactment the scheduler kept postponing as E-ENACTMENT-POSTPONED', () => {
+      const runner = new SimulationRunner(createSilentLogger());
+      const result: SimulationResult = {
+        referendumId: 1,
+        executionSucceeded: false,
+        events: [],
+        notDispatched: 'postponed',
+        postponedBlocks: 3,
+      };
+      expect(() => runner.throwIfFailed(result, 'Test')).toThrow(
+        expect.objectContaining({
+          code: ErrorCode.EnactmentPostponed,
+          message: expect.stringContaining('blocks ran out of weight'),
+        })
+      );
+    });
   });
 
   describe('createReferendumIfNeeded()', () => {
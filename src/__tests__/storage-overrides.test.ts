@@ -0,0 +1,375 @@
+import { describe, expect, it, vi } from 'vitest';
+import type { ChopsticksManager } from '../services/chopsticks-manager';
+import {
+  applyStorageOverrides,
+  CALL_FILTER_UNLOCKS,
+  mergeFundOverrides,
+  parseFundOverride,
+  parseParameterOverride,
+  parseStorageOverride,
+  parseStorageOverrides,
+  resolveStorageOverrides,
+  type StorageMetadata,
+  storageOverridesFor,
+  storageOverrideUpdates,
+} from '../services/storage-overrides';
+import type { Logger } from '../utils/logger';
+
+vi.mock('@polkadot-api/substrate-bindings', async (importOriginal) => ({
+  ...(await importOriginal<object>()),
+  decAnyMetadata: (hex: string) => (hex === '0xmeta' ? METADATA : undefined),
+  unifyMetadata: (metadata: unknown) => metadata,
+}));
+
+const map = (hashers: number) => ({
+  tag: 'map' as const,
+  value: { hashers: Array(hashers).fill({ tag: 'Twox64Concat' }), key: 0, value: 0 },
+});
+
+const METADATA: StorageMetadata = {
+  lookup: [
+    // RuntimeParametersKey
+    { id: 10, def: { tag: 'variant', value: [{ name: 'Treasury', fields: [{ type: 11 }] }] } },
+    // TreasuryKey
+    { id: 11, def: { tag: 'variant', value: [{ name: 'BurnPortion', fields: [{ type: 12 }] }] } },
+    { id: 12, def: { tag: 'composite', value: [] } },
+  ],
+  pallets: [
+    {
+      name: 'System',
+      storage: { items: [{ name: 'Account', type: map(1) }] },
+    },
+    {
+      name: 'AhMigrator',
+      storage: { items: [{ name: 'AhMigrationStage', type: { tag: 'plain', value: 0 } }] },
+    },
+    {
+      name: 'FellowshipCollective',
+      storage: { items: [{ name: 'IdToIndex', type: map(2) }] },
+    },
+    { name: 'Utility' },
+    {
+      name: 'Parameters',
+      storage: {
+        items: [
+          {
+            name: 'Parameters',
+            type: {
+              tag: 'map',
+              value: { hashers: [{ tag: 'Blake2128Concat' }], key: 10, value: 0 },
+            },
+          },
+        ],
+      },
+    },
+  ],
+};
+
+const ALICE = '5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY';
+
+describe('parseStorageOverride', () => {
+  it('parses a plain value', () => {
+    expect(parseStorageOverride('AhMigrator.AhMigrationStage=MigrationDone')).toEqual({
+      pallet: 'AhMigrator',
+      item: 'AhMigrationStage',
+      keys: [],
+      value: 'MigrationDone',
+    });
+  });
+
+  it('parses map keys and a JSON value', () => {
+    expect(
+      parseStorageOverride(`System.Account[${ALICE}]={"providers":1,"data":{"free":"1000"}}`)
+    ).toEqual({
+      pallet: 'System',
+      item: 'Account',
+      keys: [ALICE],
+      value: { providers: 1, data: { free: '1000' } },
+    });
+  });
+
+  it('takes one bracket per key, with brackets inside JSON', () => {
+    expect(parseStorageOverride('FellowshipCollective.IdToIndex[3][[1,"]"]] = 0').keys).toEqual([
+      3,
+      [1, ']'],
+    ]);
+  });
+
+  it('keeps integers beyond 2^53 exact', () => {
+    expect(parseStorageOverride('Balances.TotalIssuance=100000000000000000000').value).toBe(
+      '100000000000000000000'
+    );
+  });
+
+  it.each(['AhMigrationStage=1', 'System.Account[1=2', 'System.Number', 'System.Number='])(
+    'rejects %s',
+    (value) => {
+      expect(() => parseStorageOverride(value)).toThrow(
+        expect.objectContaining({ code: 'E-INVALID-STORAGE-OVERRIDE' })
+      );
+    }
+  );
+
+  it('parses every flag in order', () => {
+    expect(parseStorageOverrides(['A.B=1', 'C.D=2']).map((o) => o.value)).toEqual([1, 2]);
+    expect(parseStorageOverrides(undefined)).toEqual([]);
+  });
+});
+
+describe('parseFundOverride', () => {
+  it('sets the free balance of the account', () => {
+    expect(parseFundOverride(`${ALICE}=1000000000000000000000`)).toEqual({
+      pallet: 'System',
+      item: 'Account',
+      keys: [ALICE],
+      value: { providers: 1, data: { free: '1000000000000000000000' } },
+      fund: '1000000000000000000000',
+    });
+  });
+
+  it.each([ALICE, `${ALICE}=-1`, `${ALICE}=1.5`, 'alice=100', `${ALICE}=1=2`])(
+    'rejects %s',
+    (value) => {
+      expect(() => parseFundOverride(value)).toThrow(`Invalid --fund value: ${value}`);
+    }
+  );
+});
+
+describe('mergeFundOverrides', () => {
+  const accountApi = (info: unknown) =>
+    ({ query: { System: { Account: { getValue: vi.fn().mockResolvedValue(info) } } } }) as any;
+
+  it('only replaces the free balance of an existing account', async () => {
+    const api = accountApi({
+      nonce: 7,
+      consumers: 2,
+      providers: 1,
+      sufficients: 0,
+      data: { free: 5n, reserved: 100n, frozen: 50n, flags: 1n << 127n },
+    });
+    const [merged] = await mergeFundOverrides(api, [parseFundOverride(`${ALICE}=1000`)]);
+    expect(api.query.System.Account.getValue).toHaveBeenCalledWith(ALICE);
+    expect(merged.value).toEqual({
+      nonce: 7,
+      consumers: 2,
+      providers: 1,
+      sufficients: 0,
+      data: { free: '1000', reserved: '100', frozen: '50', flags: (1n << 127n).toString() },
+    });
+  });
+
+  it('creates an account that does not exist', async () => {
+    const api = accountApi({
+      nonce: 0,
+      consumers: 0,
+      providers: 0,
+      sufficients: 0,
+      data: { free: 0n, reserved: 0n, frozen: 0n, flags: 0n },
+    });
+    const [merged] = await mergeFundOverrides(api, [parseFundOverride(`${ALICE}=1000`)]);
+    expect(merged.value).toEqual({ providers: 1, data: { free: '1000' } });
+  });
+
+  it('leaves other overrides alone', async () => {
+    const api = accountApi(undefined);
+    const overrides = parseStorageOverrides(['A.B=1']);
+    expect(await mergeFundOverrides(api, overrides)).toEqual(overrides);
+    expect(api.query.System.Account.getValue).not.toHaveBeenCalled();
+  });
+});
+
+describe('parseParameterOverride', () => {
+  it('parses Namespace.Name=value', () => {
+    expect(parseParameterOverride('Treasury.BurnPortion = 100000000000000000000')).toEqual({
+      pallet: 'Treasury',
+      item: 'BurnPortion',
+      keys: [],
+      value: '100000000000000000000',
+      parameter: 'Treasury.BurnPortion',
+    });
+  });
+
+  it.each(['BurnPortion=1', 'Treasury.BurnPortion', 'Treasury.BurnPortion=', 'A.B[1]=2'])(
+    'rejects %s',
+    (value) => {
+      expect(() => parseParameterOverride(value)).toThrow(
+        `Invalid --override-parameter value: ${value}`
+      );
+    }
+  );
+});
+
+describe('storageOverridesFor', () => {
+  it('orders the unlock, funds and parameters before --set-storage', () => {
+    const overrides = storageOverridesFor({
+      setStorage: ['AhMigrator.AhMigrationStage=Pending'],
+      overrideParameter: ['Treasury.BurnPortion=0'],
+      fund: [`${ALICE}=1`],
+      unlockCallFilter: true,
+    });
+    expect(overrides.map((o) => `${o.pallet}.${o.item}`)).toEqual([
+      'AhMigrator.AhMigrationStage',
+      'RcMigrator.RcMigrationStage',
+      'System.Account',
+      'Treasury.BurnPortion',
+      'AhMigrator.AhMigrationStage',
+    ]);
+  });
+
+  it('is empty without the flags', () => {
+    expect(storageOverridesFor({})).toEqual([]);
+  });
+});
+
+describe('resolveStorageOverrides', () => {
+  it('drops call filter unlocks the chain has no storage for', () => {
+    expect(resolveStorageOverrides(METADATA, CALL_FILTER_UNLOCKS)).toEqual([
+      CALL_FILTER_UNLOCKS[0],
+    ]);
+  });
+
+  it("uses the runtime's spelling of pallet and item", () => {
+    const [override] = resolveStorageOverrides(METADATA, [
+      parseStorageOverride('ahmigrator.ahmigrationstage=MigrationDone'),
+    ]);
+    expect(override).toMatchObject({ pallet: 'AhMigrator', item: 'AhMigrationStage' });
+  });
+
+  it('rejects storage the chain does not have', () => {
+    expect(() =>
+      resolveStorageOverrides(METADATA, [parseStorageOverride('Utility.Batches=1')])
+    ).toThrow('--set-storage Utility.Batches: no such storage item on this chain');
+  });
+
+  it('checks the number of keys against the hashers', () => {
+    const override = parseStorageOverride('FellowshipCollective.IdToIndex[3]=0');
+    expect(() => resolveStorageOverrides(METADATA, [override])).toThrow(
+      '--set-storage FellowshipCollective.IdToIndex takes 2 key(s), got 1'
+    );
+  });
+});
+
+describe('resolveStorageOverrides with parameters', () => {
+  it('writes a parameter of the runtime to Parameters.Parameters', () => {
+    const [override] = resolveStorageOverrides(METADATA, [
+      parseParameterOverride('treasury.burnportion=0'),
+    ]);
+    expect(override).toEqual({
+      pallet: 'Parameters',
+      item: 'Parameters',
+      keys: [{ Treasury: { BurnPortion: null } }],
+      value: { Treasury: { BurnPortion: 0 } },
+      parameter: 'Treasury.BurnPortion',
+    });
+  });
+
+  it('falls back to a storage item of the same name', () => {
+    const [override] = resolveStorageOverrides(METADATA, [
+      parseParameterOverride('AhMigrator.AhMigrationStage=MigrationDone'),
+    ]);
+    expect(override).toMatchObject({ pallet: 'AhMigrator', item: 'AhMigrationStage', keys: [] });
+  });
+
+  it('looks for a storage item on chains without the Parameters pallet', () => {
+    const metadata = { pallets: METADATA.pallets.filter((p) => p.name !== 'Parameters') };
+    expect(() =>
+      resolveStorageOverrides(metadata, [parseParameterOverride('Treasury.BurnPortion=0')])
+    ).toThrow('--override-parameter Treasury.BurnPortion: no such parameter or storage item');
+  });
+
+  it('rejects parameters the runtime does not have', () => {
+    expect(() =>
+      resolveStorageOverrides(METADATA, [parseParameterOverride('Treasury.SpendPeriod=1')])
+    ).toThrow('--override-parameter Treasury.SpendPeriod: no such parameter or storage item');
+  });
+});
+
+describe('storageOverrideUpdates', () => {
+  it('merges entries of the same map', () => {
+    const overrides = parseStorageOverrides([
+      'System.Account[1]={"providers":1}',
+      'System.Account[2]={"providers":2}',
+      'AhMigrator.AhMigrationStage=MigrationDone',
+    ]);
+    expect(storageOverrideUpdates(overrides)).toEqual({
+      System: {
+        Account: [
+          [[1], { providers: 1 }],
+          [[2], { providers: 2 }],
+        ],
+      },
+      AhMigrator: { AhMigrationStage: 'MigrationDone' },
+    });
+  });
+});
+
+describe('applyStorageOverrides', () => {
+  const logger = { info: vi.fn() } as unknown as Logger;
+
+  it('resolves against the fork metadata and writes one update', async () => {
+    const request = vi.fn().mockResolvedValue('0xmeta');
+    const chopsticks = { setStorageBatch: vi.fn().mockResolvedValue(undefined) };
+    await applyStorageOverrides(
+      logger,
+      request,
+      chopsticks as unknown as ChopsticksManager,
+      parseStorageOverrides(['ahmigrator.ahmigrationstage=MigrationDone'])
+    );
+    expect(request).toHaveBeenCalledWith('state_getMetadata', []);
+    expect(chopsticks.setStorageBatch).toHaveBeenCalledWith({
+      AhMigrator: { AhMigrationStage: 'MigrationDone' },
+    });
+  });
+
+  it('logs parameter overrides by name', async () => {
+    const info = vi.fn();
+    const chopsticks = { setStorageBatch: vi.fn().mockResolvedValue(undefined) };
+    await applyStorageOverrides(
+      { info } as unknown as Logger,
+      vi.fn().mockResolvedValue('0xmeta'),
+      chopsticks as unknown as ChopsticksManager,
+      [parseParameterOverride('Treasury.BurnPortion=0')]
+    );
+    expect(chopsticks.setStorageBatch).toHaveBeenCalledWith({
+      Parameters: {
+        Parameters: [[[{ Treasury: { BurnPortion: null } }], { Treasury: { BurnPortion: 0 } }]],
+      },
+    });
+    expect(info).toHaveBeenCalledWith('Storage override: parameter Treasury.BurnPortion = 0');
+  });
+
+  it('warns when there is no call filter to unlock', async () => {
+    const warn = vi.fn();
+    const chopsticks = { setStorageBatch: vi.fn() };
+    await applyStorageOverrides(
+      { info: vi.fn(), warn } as unknown as Logger,
+      vi.fn().mockResolvedValue('0xmeta'),
+      chopsticks as unknown as ChopsticksManager,
+      [CALL_FILTER_UNLOCKS[1]]
+    );
+    expect(warn).toHaveBeenCalledWith(
+      '--unlock-call-filter: this chain has no known call filter to unlock'
+    );
+    expect(chopsticks.setStorageBatch).not.toHaveBeenCalled();
+  });
+
+  it('does nothing without overrides', async () => {
+    const request = vi.fn();
+    const chopsticks = { setStorageBatch: vi.fn() };
+    await applyStorageOverrides(logger, request, chopsticks as unknown as ChopsticksManager, []);
+    expect(request).not.toHaveBeenCalled();
+    expect(chopsticks.setStorageBatch).not.toHaveBeenCalled();
+  });
+
+  it('reports values Chopsticks cannot encode', async () => {
+    const chopsticks = { setStorageBatch: vi.fn().mockRejectedValue(new Error('bad enum')) };
+    await expect(
+      applyStorageOverrides(
+        logger,
+        vi.fn().mockResolvedValue('0xmeta'),
+        chopsticks as unknown as ChopsticksManager,
+        parseStorageOverrides(['AhMigrator.AhMigrationStage=Nope'])
+      )
+    ).rejects.toMatchObject({ code: 'E-INVALID-STORAGE-OVERRIDE' });
+  });
+});
@@ -0,0 +1,131 @@
+import { describe, expect, it, vi } from 'vitest';
+import type { CreationPreview, CreationTarget } from '../services/creation-preview';
+import {
+  displaySubmissionPayloads,
+  polkadotJsSnippet,
+  submissionPayloads,
+  subxtSnippet,
+} from '../services/submission-payloads';
+import type { Logger } from '../utils/logger';
+
+const PREVIEW: CreationPreview = {
+  submit: { name: 'Referenda.submit', args: {} },
+  origin: 'Origins.WhitelistedCaller',
+  track: { id: 1, name: 'whitelisted_caller', decisionDeposit: 100_000_000_000n },
+  proposal: { type: 'Lookup', hash: '0xab', len: 2 },
+  enactment: 'After 10 blocks',
+  preimage: { name: 'Preimage.note_preimage', args: {}, hash: '0xab', len: 2 },
+  submissionDeposit: 10_000_000_000n,
+  warnings: [],
+};
+
+const TOKEN = { symbol: 'DOT', decimals: 10 };
+
+function target(preimage?: string): CreationTarget {
+  return {
+    label: 'Governance',
+    isFellowship: false,
+    endpoint: { url: 'wss://example.com' },
+    submit: '0x1500',
+    preimage,
+  };
+}
+
+function lines(logger: Logger): string[] {
+  return vi.mocked(logger.info).mock.calls.map(([line]) => line);
+}
+
+describe('polkadotJsSnippet', () => {
+  it('submits a single call directly', () => {
+    expect(polkadotJsSnippet(['0x1500'])).toBe(
+      "await api.tx(api.createType('Call', '0x1500')).signAndSend(account);"
+    );
+  });
+
+  it('batches several calls with batch_all', () => {
+    const snippet = polkadotJsSnippet(['0x0500', '0x1500']);
+
+    expect(snippet).toMatch(
+      /^await api\.tx\.utility\.batchAll\(\[.*\]\)\.signAndSend\(account\);$/
+    );
+    expect(snippet).toContain(
+      "[api.createType('Call', '0x0500'), api.createType('Call', '0x1500')]"
+    );
+  });
+});
+
+describe('subxtSnippet', () => {
+  it('submits each call as raw call data', () => {
+    expect(subxtSnippet(['0x0500', '0x1500'])).toBe(
+      'api.tx().sign_and_submit_then_watch_default(&RawCall(hex::decode("0500")?), &signer).await?; ' +
+        'api.tx().sign_and_submit_then_watch_default(&RawCall(hex::decode("1500")?), &signer).await?;'
+    );
+  });
+});
+
+describe('displaySubmissionPayloads', () => {
+  it('lists the preimage and submit calls with their deposits', () => {
+    const logger = { section: vi.fn(), info: vi.fn() } as unknown as Logger;
+
+    displaySubmissionPayloads(logger, target('0x0500'), PREVIEW, TOKEN);
+
+    expect(logger.section).toHaveBeenCalledWith('Governance Submission Payloads');
+    const printed = lines(logger);
+    expect(printed.slice(0, 4)).toEqual([
+      '1. Preimage.note_preimage (0xab, 2 bytes):',
+      '0x0500',
+      '2. Referenda.submit on track #1 whitelisted_caller:',
+      '0x1500',
+    ]);
+    expect(printed[4]).toBe(
+      'Deposits: 1 DOT submission deposit, 10 DOT decision deposit (place_decision_deposit), plus the preimage deposit'
+    );
+    expect(printed[5]).toContain('api.tx.utility.batchAll(');
+  });
+
+  it('submits the call alone without a preimage', () => {
+    const logger = { section: vi.fn(), info: vi.fn() } as unknown as Logger;
+
+    displaySubmissionPayloads(logger, target(), { ...PREVIEW, track: undefined });
+
+    const printed = lines(logger);
+    expect(printed[0]).toBe('1. Referenda.submit:');
+    expect(printed[2]).toContain('unknown decision deposit');
+    expect(printed[2]).not.toContain('preimage deposit');
+    expect(printed[3]).toBe(
+      "polkadot-js: await api.tx(api.createType('Call', '0x1500')).signAndSend(account);"
+    );
+    expect(printed[4]).toMatch(/^subxt: api\.tx\(\)\.sign_and_submit_then_watch_default/);
+  });
+
+  it('submits a delegated submit through the steps wrapping it', () => {
+    const logger = { section: vi.fn(), info: vi.fn() } as unknown as Logger;
+    const payloads = submissionPayloads(target('0x0500'), PREVIEW, [
+      { name: 'Multisig.approve_as_multi', signer: 'Alice', hex: '0x1f01' },
+      {
+        name: 'Multisig.as_multi',
+        signer: 'Bob',
+        note: 'with the timepoint of the first approval',
+      },
+    ]);
+
+    displaySubmissionPayloads(logger, target('0x0500'), PREVIEW, TOKEN, payloads);
+
+    const printed = lines(logger);
+    expect(printed).toContain(
+      '2. Referenda.submit on track #1 whitelisted_caller, wrapped by the calls below:'
+    );
+    expect(printed).toContain('3. Multisig.approve_as_multi, signed by Alice:');
+    expect(printed).toContain(
+      '4. Multisig.as_multi, signed by Bob (with the timepoint of the first approval)'
+    );
+    // The wrapped submit is not sent on its own
+    expect(printed).toContain(
+      "polkadot-js: await api.tx(api.createType('Call', '0x0500')).signAndSend(account);"
+    );
+    expect(printed).toContain(
+      "polkadot-js (Alice): await api.tx(api.createType('Call', '0x1f01')).signAndSend(account);"
+    );
+    expect(printed.filter((line) => line.startsWith('polkadot-js'))).toHaveLength(2);
+  });
+});
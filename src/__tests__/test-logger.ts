@@ -0,0 +1,24 @@
+import { vi } from 'vitest';
+import type { Logger } from '../utils/logger';
+
+/** A Logger whose every method is a mock and which prints nothing. */
+export function createSilentLogger(): Logger {
+  return {
+    info: vi.fn(),
+    debug: vi.fn(),
+    warn: vi.fn(),
+    error: vi.fn(),
+    success: vi.fn(),
+    block: vi.fn(),
+    heads: vi.fn(),
+    isVerbose: () => false,
+    startSpinner: vi.fn(),
+    succeedSpinner: vi.fn(),
+    failSpinner: vi.fn(),
+    updateSpinner: vi.fn(),
+    stopSpinner: vi.fn(),
+    section: vi.fn(),
+    table: vi.fn(),
+    writeTo: vi.fn(),
+  } as unknown as Logger;
+}
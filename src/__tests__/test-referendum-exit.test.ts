@@ -1,13 +1,20 @@
-import { afterEach, describe, expect, it, vi } from 'vitest';
+import * as fs from 'fs';
+import * as os from 'os';
+import * as path from 'path';
+import { afterAll, afterEach, describe, expect, it, vi } from 'vitest';
 
 const mockTestWithFellowship = vi.fn();
 
 vi.mock('../services/network-coordinator', () => ({
   NetworkCoordinator: vi.fn(function () {
-    return { testWithFellowship: mockTestWithFellowship };
+    return { testWithFellowship: mockTestWithFellowship, getHeads: vi.fn(() => []) };
   }),
 }));
 
+vi.mock('../services/submission-payloads', () => ({
+  printSubmissionPayloads: vi.fn(),
+}));
+
 vi.mock('../utils/chain-endpoint-parser', () => ({
   parseEndpoint: vi.fn((url: string) => ({ url, block: undefined })),
   parseMultipleEndpoints: vi.fn((urls: string) =>
@@ -30,12 +37,24 @@ vi.mock('../utils/logger', () => {
     this.updateSpinner = vi.fn();
     this.stopSpinner = vi.fn();
     this.table = vi.fn();
+    this.writeTo = vi.fn();
   });
   return { Logger: MockLogger };
 });
 
 import { testReferendum } from '../commands/test-referendum';
+import { NetworkCoordinator } from '../services/network-coordinator';
+import { shutdown } from '../services/shutdown';
+import { printSubmissionPayloads } from '../services/submission-payloads';
 import type { TestOptions } from '../types';
+import { CodedError, ErrorCode } from '../utils/error-codes';
+
+// Failed runs keep their work dir; keep them out of the system temp dir
+const testWorkRoot = fs.mkdtempSync(path.join(os.tmpdir(), 'exit-test-'));
+
+afterAll(() => {
+  fs.rmSync(testWorkRoot, { recursive: true, force: true });
+});
 
 function makeOptions(overrides: Partial<TestOptions> = {}): TestOptions {
   return {
@@ -44,6 +63,7 @@ function makeOptions(overrides: Partial<TestOptions> = {}): TestOptions {
     port: '8000',
     verbose: false,
     cleanup: true,
+    workDir: testWorkRoot,
     ...overrides,
   };
 }
@@ -54,6 +74,7 @@ describe('testReferendum process exit', () => {
   afterEach(() => {
     exitSpy.mockClear();
     mockTestWithFellowship.mockReset();
+    vi.mocked(printSubmissionPayloads).mockClear();
   });
 
   it('calls process.exit(0) after successful workflow with cleanup enabled', async () => {
@@ -73,6 +94,16 @@ describe('testReferendum process exit', () => {
     expect(exitSpy).toHaveBeenCalledWith(1);
   });
 
+  it('calls process.exit(2) when the scheduler never dispatched the proposal', async () => {
+    mockTestWithFellowship.mockRejectedValue(
+      new CodedError(ErrorCode.PreimageMissing, 'Governance execution failed')
+    );
+
+    await testReferendum(makeOptions({ cleanup: true }));
+
+    expect(exitSpy).toHaveBeenCalledWith(2);
+  });
+
   it('does not call process.exit when cleanup is disabled (no-cleanup mode)', async () => {
     mockTestWithFellowship.mockResolvedValue(undefined);
 
@@ -82,6 +113,73 @@ describe('testReferendum process exit', () => {
     expect(exitSpy).not.toHaveBeenCalled();
   });
 
+  it('removes the work dir after a successful run and keeps it after a failed one', async () => {
+    const workRoot = fs.mkdtempSync(path.join(testWorkRoot, 'run-'));
+    try {
+      mockTestWithFellowship.mockResolvedValue(undefined);
+      await testReferendum(makeOptions({ workDir: workRoot }));
+      expect(fs.readdirSync(workRoot)).toEqual([]);
+
+      mockTestWithFellowship.mockRejectedValue(new Error('boom'));
+      await testReferendum(makeOptions({ workDir: workRoot }));
+      expect(fs.readdirSync(workRoot)).toHaveLength(1);
+    } finally {
+      fs.rmSync(workRoot, { recursive: true, force: true });
+    }
+  });
+
+  it('keeps the persistent storage cache and hands the work dir to the coordinator', async () => {
+    mockTestWithFellowship.mockResolvedValue(undefined);
+
+    await testReferendum(makeOptions());
+
+    const topology = vi.mocked(NetworkCoordinator).mock.lastCall?.[1];
+    expect(topology?.db).toBeUndefined();
+    expect(path.dirname(topology?.workDir ?? '')).toBe(testWorkRoot);
+  });
+
+  it('keeps a run history only with --history-dir or --diff-with-last', async () => {
+    mockTestWithFellowship.mockResolvedValue(undefined);
+
+    await testReferendum(makeOptions());
+    expect(vi.mocked(NetworkCoordinator).mock.lastCall?.[2]?.history).toBeUndefined();
+
+    await testReferendum(makeOptions({ diffWithLast: true }));
+    expect(vi.mocked(NetworkCoordinator).mock.lastCall?.[2]?.history).toBeDefined();
+  });
+
+  it('removes the work dir of a --no-cleanup run when it is stopped', async () => {
+    const workRoot = fs.mkdtempSync(path.join(testWorkRoot, 'run-'));
+    const registerSpy = vi.spyOn(shutdown, 'register');
+    try {
+      mockTestWithFellowship.mockResolvedValue(undefined);
+      await testReferendum(makeOptions({ cleanup: false, workDir: workRoot }));
+      expect(fs.readdirSync(workRoot)).toHaveLength(1);
+
+      const teardown = registerSpy.mock.calls.find(([label]) => label === 'work dir')?.[1];
+      expect(teardown).toBeDefined();
+      await teardown?.();
+      expect(fs.readdirSync(workRoot)).toEqual([]);
+    } finally {
+      registerSpy.mockRestore();
+      fs.rmSync(workRoot, { recursive: true, force: true });
+    }
+  });
+
+  it('monitors the chains of every --additional-chains value', async () => {
+    mockTestWithFellowship.mockResolvedValue(undefined);
+
+    await testReferendum(
+      makeOptions({ additionalChains: ['wss://relay.io', 'wss://coretime.io,wss://people.io'] })
+    );
+
+    expect(vi.mocked(NetworkCoordinator).mock.lastCall?.[1].additionalChains).toEqual([
+      { url: 'wss://relay.io', block: undefined },
+      { url: 'wss://coretime.io', block: undefined },
+      { url: 'wss://people.io', block: undefined },
+    ]);
+  });
+
   it('calls process.exit(1) on validation error (no referendum specified)', async () => {
     await testReferendum(
       makeOptions({
@@ -92,4 +190,28 @@ describe('testReferendum process exit', () => {
 
     expect(exitSpy).toHaveBeenCalledWith(1);
   });
+
+  it('calls process.exit(1) when --submit-via-proxy has no referendum to create', async () => {
+    await testReferendum(
+      makeOptions({ submitViaProxy: '15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5' })
+    );
+
+    expect(mockTestWithFellowship).not.toHaveBeenCalled();
+    expect(exitSpy).toHaveBeenCalledWith(1);
+  });
+
+  it('prints the submission payloads after a passing create run', async () => {
+    mockTestWithFellowship.mockResolvedValue(undefined);
+    const create = { referendum: undefined, callToCreateGovernanceReferendum: '0x1500' };
+
+    await testReferendum(makeOptions(create));
+    expect(printSubmissionPayloads).toHaveBeenCalledOnce();
+
+    await testReferendum(makeOptions({ ...create, scenario: 'bad-origin' }));
+    expect(printSubmissionPayloads).toHaveBeenCalledOnce();
+
+    mockTestWithFellowship.mockRejectedValue(new Error('boom'));
+    await testReferendum(makeOptions(create));
+    expect(printSubmissionPayloads).toHaveBeenCalledOnce();
+  });
 });
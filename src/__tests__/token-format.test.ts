@@ -0,0 +1,25 @@
+import { describe, expect, it } from 'vitest';
+import { formatDelta, formatTokenAmount } from '../utils/token-format';
+
+const DOT = { symbol: 'DOT', decimals: 10 };
+
+describe('formatTokenAmount', () => {
+  it('formats whole and fractional amounts', () => {
+    expect(formatTokenAmount(15_000_000_000n, DOT)).toBe('1.5 DOT');
+    expect(formatTokenAmount(20_000_000_000n, DOT)).toBe('2 DOT');
+    expect(formatTokenAmount(1n, DOT)).toBe('0.0000000001 DOT');
+    expect(formatTokenAmount(-5_000_000_000n, DOT)).toBe('-0.5 DOT');
+  });
+
+  it('falls back to plancks without a known token', () => {
+    expect(formatTokenAmount(15_000_000_000n)).toBe('15000000000');
+  });
+});
+
+describe('formatDelta', () => {
+  it('signs gains and losses', () => {
+    expect(formatDelta(15_000_000_000n, DOT)).toBe('+1.5 DOT');
+    expect(formatDelta(-30n)).toBe('-30');
+    expect(formatDelta(0n, DOT)).toBe('+0 DOT');
+  });
+});
@@ -0,0 +1,172 @@
+import { describe, expect, it, vi } from 'vitest';
+import {
+  diffTracks,
+  formatTrackChanges,
+  TrackChanges,
+  type TrackTables,
+} from '../services/track-changes';
+import { createSilentLogger } from './test-logger';
+
+const CURVE = { type: 'LinearDecreasing', value: { length: 1_000_000_000, floor: 0, ceil: 100 } };
+
+function track(id: number, name: string, params: Record<string, unknown>) {
+  return [id, { name, max_deciding: 10, decision_deposit: 1000n, min_support: CURVE, ...params }];
+}
+
+// Metadata "hex" is looked up by name; each has a `Tracks` constant whose
+// "encoded" value is the decoded track list itself.
+const METADATA: Record<string, unknown> = {
+  before: {
+    pallets: [
+      { name: 'System', constants: [] },
+      {
+        name: 'Referenda',
+        constants: [
+          { name: 'Tracks', type: 1, value: [track(0, 'root', {}), track(1, 'spender', {})] },
+        ],
+      },
+    ],
+  },
+  after: {
+    pallets: [
+      {
+        name: 'Referenda',
+        constants: [
+          {
+            name: 'Tracks',
+            type: 1,
+            value: [track(0, 'root', { decision_deposit: 2000n }), track(2, 'new_track', {})],
+          },
+        ],
+      },
+    ],
+  },
+};
+
+vi.mock('@polkadot-api/substrate-bindings', () => ({
+  decAnyMetadata: (hex: string) => METADATA[hex],
+  unifyMetadata: (metadata: unknown) => metadata,
+}));
+
+vi.mock('@polkadot-api/metadata-builders', () => ({
+  getLookupFn: () => undefined,
+  getDynamicBuilder: () => ({ buildDefinition: () => ({ dec: (value: unknown) => value }) }),
+}));
+
+describe('diffTracks', () => {
+  const before: TrackTables = {
+    Referenda: [
+      { id: 0, name: 'root', params: { max_deciding: '1', confirm_period: '14400' } },
+      { id: 1, name: 'spender', params: { max_deciding: '10' } },
+    ],
+  };
+
+  it('reports nothing for identical tables', () => {
+    expect(diffTracks(before, before)).toEqual([]);
+  });
+
+  it('reports changed parameters, and added and removed tracks', () => {
+    const after: TrackTables = {
+      Referenda: [
+        { id: 0, name: 'root', params: { max_deciding: '1', confirm_period: '7200' } },
+        { id: 2, name: 'treasurer', params: { max_deciding: '5' } },
+      ],
+    };
+
+    expect(diffTracks(before, after)).toEqual([
+      {
+        pallet: 'Referenda',
+        track: 0,
+        name: 'root',
+        kind: 'changed',
+        params: [{ param: 'confirm_period', before: '14400', after: '7200' }],
+      },
+      {
+        pallet: 'Referenda',
+        track: 1,
+        name: 'spender',
+        kind: 'removed',
+        params: [{ param: 'max_deciding', before: '10', after: undefined }],
+      },
+      {
+        pallet: 'Referenda',
+        track: 2,
+        name: 'treasurer',
+        kind: 'added',
+        params: [{ param: 'max_deciding', before: undefined, after: '5' }],
+      },
+    ]);
+  });
+
+  it('reports a renamed track', () => {
+    const after: TrackTables = {
+      Referenda: [before.Referenda[0], { ...before.Referenda[1], name: 'small_spender' }],
+    };
+
+    expect(diffTracks(before, after)[0].params).toEqual([
+      { param: 'name', before: 'spender', after: 'small_spender' },
+    ]);
+  });
+});
+
+describe('formatTrackChanges', () => {
+  it('lines up before and after values', () => {
+    expect(
+      formatTrackChanges([
+        {
+          pallet: 'Referenda',
+          track: 0,
+          name: 'root',
+          kind: 'changed',
+          params: [
+            { param: 'confirm_period', before: '14400', after: '7200' },
+            { param: 'max_deciding', before: '1', after: '2' },
+          ],
+        },
+        {
+          pallet: 'Referenda',
+          track: 2,
+          name: 'treasurer',
+          kind: 'added',
+          params: [{ param: 'max_deciding', after: '5' }],
+        },
+      ])
+    ).toEqual([
+      'Track                           Parameter       Before  After',
+      'Referenda #0 root (changed)     confirm_period  14400   7200',
+      '                                max_deciding    1       2',
+      'Referenda #2 treasurer (added)  max_deciding    -       5',
+    ]);
+  });
+});
+
+describe('TrackChanges', () => {
+  it('compares the Tracks constants of the metadata before and after dispatch', async () => {
+    const request = vi.fn().mockResolvedValueOnce('before').mockResolvedValueOnce('after');
+    const logger = createSilentLogger();
+
+    const snapshot = await TrackChanges.snapshot(request);
+    const changes = await snapshot.report(logger, request);
+
+    expect(request).toHaveBeenCalledWith('state_getMetadata', []);
+    expect(changes.map(({ track, kind }) => [track, kind])).toEqual([
+      [0, 'changed'],
+      [1, 'removed'],
+      [2, 'added'],
+    ]);
+    expect(changes[0].params).toEqual([
+      { param: 'decision_deposit', before: '1000', after: '2000' },
+    ]);
+    expect(logger.section).toHaveBeenCalledWith('Track Changes');
+  });
+
+  it('stays quiet when the tracks did not change', async () => {
+    const request = vi.fn().mockResolvedValue('before');
+    const logger = createSilentLogger();
+
+    const snapshot = await TrackChanges.snapshot(request);
+
+    expect(await snapshot.report(logger, request)).toEqual([]);
+    expect(logger.section).not.toHaveBeenCalled();
+  });
+});
@@ -0,0 +1,183 @@
+import { AccountId, Binary } from 'polkadot-api';
+import { describe, expect, it, vi } from 'vitest';
+import { localAsset, parseApprovedSpends, TreasuryPayouts } from '../services/treasury-payouts';
+import type { ParsedEvent } from '../utils/event-serializer';
+import { createSilentLogger } from './test-logger';
+
+const BENEFICIARY_BYTES = new Uint8Array(32).fill(7);
+const BENEFICIARY = AccountId().dec(BENEFICIARY_BYTES);
+const HERE = { parents: 0, interior: { type: 'Here' } };
+const USDT = {
+  parents: 0,
+  interior: {
+    type: 'X2',
+    value: [
+      { type: 'PalletInstance', value: 50 },
+      { type: 'GeneralIndex', value: 1984n },
+    ],
+  },
+};
+const ASSET_HUB = {
+  parents: 0,
+  interior: { type: 'X1', value: { type: 'Parachain', value: 1000 } },
+};
+
+function assetSpendApproved(location: unknown, assetId: unknown, amount = 1_000_000n): ParsedEvent {
+  return {
+    section: 'Treasury',
+    method: 'AssetSpendApproved',
+    data: {
+      index: 5,
+      asset_kind: { type: 'V4', value: { location, asset_id: assetId } },
+      amount,
+      beneficiary: {
+        type: 'V4',
+        value: {
+          parents: 0,
+          interior: {
+            type: 'X1',
+            value: { type: 'AccountId32', value: { id: Binary.fromBytes(BENEFICIARY_BYTES) } },
+          },
+        },
+      },
+      valid_from: 100,
+      expire_at: 200,
+    },
+  };
+}
+
+function mockChain(balances: bigint[], payoutEvents: unknown[]) {
+  const accountQuery = { getValue: vi.fn() };
+  for (const balance of balances) {
+    accountQuery.getValue.mockResolvedValueOnce({ balance });
+  }
+  const api = {
+    query: {
+      System: { Events: { getValue: vi.fn().mockResolvedValue(payoutEvents) } },
+      Assets: {
+        Account: accountQuery,
+        Metadata: {
+          getValue: vi.fn().mockResolvedValue({ symbol: Binary.fromText('USDT'), decimals: 6 }),
+        },
+      },
+    },
+    tx: {
+      Treasury: {
+        payout: vi.fn(() => ({ getEncodedData: async () => Binary.fromHex('0x3c0705000000') })),
+      },
+    },
+  } as any;
+  const chopsticks = { newBlock: vi.fn().mockResolvedValue(undefined) } as any;
+  const scheduler = { scheduleCallAtNextBlock: vi.fn().mockResolvedValue(101) } as any;
+  return { api, chopsticks, scheduler, accountQuery };
+}
+
+const PAID_EVENTS = [
+  { type: 'Scheduler', value: { type: 'Dispatched', value: { result: { success: true } } } },
+  { type: 'Treasury', value: { type: 'Paid', value: { index: 5, payment_id: 0n } } },
+];
+
+describe('parseApprovedSpends', () => {
+  it('decodes asset kind spends and native spend_local', () => {
+    const spends = parseApprovedSpends([
+      assetSpendApproved(HERE, USDT),
+      {
+        section: 'Treasury',
+        method: 'SpendApproved',
+        data: { proposal_index: 9, amount: 42n, beneficiary: BENEFICIARY },
+      },
+    ]);
+
+    expect(spends).toEqual([
+      {
+        index: 5,
+        kind: 'spend',
+        assetKind: { location: HERE, assetId: USDT },
+        amount: 1_000_000n,
+        beneficiary: BENEFICIARY,
+        validFrom: 100,
+      },
+      { index: 9, kind: 'spend_local', amount: 42n, beneficiary: BENEFICIARY },
+    ]);
+  });
+});
+
+describe('localAsset', () => {
+  it('maps the relay token, Assets pallet ids and foreign locations', () => {
+    expect(localAsset({ parents: 1, interior: { type: 'Here' } })).toEqual({ type: 'native' });
+    expect(localAsset(USDT)).toEqual({ type: 'assets', id: 1984 });
+    expect(localAsset({ ...ASSET_HUB, parents: 1 })).toEqual({
+      type: 'foreign',
+      location: { ...ASSET_HUB, parents: 1 },
+    });
+    expect(localAsset(ASSET_HUB)).toBeUndefined();
+  });
+});
+
+describe('TreasuryPayouts', () => {
+  it('pays out an Assets spend and checks the beneficiary delta', async () => {
+    const { api, chopsticks, scheduler, accountQuery } = mockChain([0n, 1_000_000n], PAID_EVENTS);
+    const payouts = new TreasuryPayouts(createSilentLogger(), chopsticks, api, scheduler);
+
+    const [result] = await payouts.payoutAll([assetSpendApproved(HERE, USDT)]);
+
+    expect(result).toMatchObject({ index: 5, asset: 'Assets #1984 (USDT)', status: 'paid' });
+    expect(api.tx.Treasury.payout).toHaveBeenCalledWith({ index: 5 });
+    expect(scheduler.scheduleCallAtNextBlock).toHaveBeenCalledWith('0x3c0705000000', {
+      System: { Signed: expect.any(String) },
+    });
+    expect(accountQuery.getValue).toHaveBeenCalledWith([1984, BENEFICIARY]);
+  });
+
+  it('fails when the beneficiary received a different amount', async () => {
+    const { api, chopsticks, scheduler } = mockChain([0n, 999n], PAID_EVENTS);
+    const payouts = new TreasuryPayouts(createSilentLogger(), chopsticks, api, scheduler);
+
+    const [result] = await payouts.payoutAll([assetSpendApproved(HERE, USDT)]);
+
+    expect(result.status).toBe('failed');
+    expect(result.error).toContain('received 999');
+  });
+
+  it('fails when the payout dispatch fails', async () => {
+    const events = [
+      {
+        type: 'Scheduler',
+        value: { type: 'Dispatched', value: { result: { success: false, value: 'SpendExpired' } } },
+      },
+    ];
+    const { api, chopsticks, scheduler } = mockChain([0n], events);
+    const payouts = new TreasuryPayouts(createSilentLogger(), chopsticks, api, scheduler);
+
+    const [result] = await payouts.payoutAll([assetSpendApproved(HERE, USDT)]);
+
+    expect(result.status).toBe('failed');
+    expect(result.error).toContain('SpendExpired');
+  });
+
+  it('reports a spend that is not valid yet as pending', async () => {
+    const events = [
+      {
+        type: 'Scheduler',
+        value: { type: 'Dispatched', value: { result: { success: false, value: 'EarlyPayout' } } },
+      },
+    ];
+    const { api, chopsticks, scheduler } = mockChain([0n], events);
+    const payouts = new TreasuryPayouts(createSilentLogger(), chopsticks, api, scheduler);
+
+    const [result] = await payouts.payoutAll([assetSpendApproved(HERE, USDT)]);
+
+    expect(result).toMatchObject({ index: 5, status: 'pending', validFrom: 100 });
+    expect(result.error).toBeUndefined();
+  });
+
+  it('only reports spends paid out on another chain', async () => {
+    const { api, chopsticks, scheduler } = mockChain([], []);
+    const payouts = new TreasuryPayouts(createSilentLogger(), chopsticks, api, scheduler);
+
+    const [result] = await payouts.payoutAll([assetSpendApproved(ASSET_HUB, USDT)]);
+
+    expect(result.status).toBe('unchecked');
+    expect(scheduler.scheduleCallAtNextBlock).not.toHaveBeenCalled();
+  });
+});
@@ -0,0 +1,64 @@
+import { Binary } from 'polkadot-api';
+import { describe, expect, it, vi } from 'vitest';
+import { palletAccount, TreasuryPot } from '../services/treasury-pot';
+import type { Logger } from '../utils/logger';
+
+const TREASURY = '5EYCAe5ijiYfyeZ2JJCGq56LmPyNRAKzpG4QkoQkkQNB5e6Z';
+const PALLETS = ['System', 'Treasury', 'Assets'];
+
+function logger(): Logger {
+  return { section: vi.fn(), info: vi.fn(), debug: vi.fn() } as unknown as Logger;
+}
+
+function chain(native: bigint[], usdt: bigint[]) {
+  const System = { Account: { getValue: vi.fn() } };
+  for (const free of native) System.Account.getValue.mockResolvedValueOnce({ data: { free } });
+  const Assets = {
+    Asset: { getValue: vi.fn(async (id: number) => (id === 1984 ? {} : undefined)) },
+    Account: { getValue: vi.fn() },
+    Metadata: { getValue: vi.fn().mockResolvedValue({ symbol: Binary.fromText('USDt') }) },
+  };
+  for (const balance of usdt) Assets.Account.getValue.mockResolvedValueOnce({ balance });
+  return {
+    constants: { Treasury: { PalletId: vi.fn().mockResolvedValue(Binary.fromText('py/trsry')) } },
+    query: { System, Assets },
+  } as any;
+}
+
+describe('palletAccount', () => {
+  it('derives the treasury account from its pallet id', () => {
+    expect(palletAccount(new TextEncoder().encode('py/trsry'))).toBe(TREASURY);
+  });
+});
+
+describe('TreasuryPot', () => {
+  it('is skipped on chains without a Treasury pallet', async () => {
+    expect(await TreasuryPot.snapshot(chain([], []), ['System', 'Assets'])).toBeUndefined();
+  });
+
+  it('reports native and stablecoin deltas of the pot', async () => {
+    const api = chain([1_000n, 1_000n], [500n, 200n]);
+    const pot = await TreasuryPot.snapshot(api, PALLETS);
+    const log = logger();
+
+    expect(pot?.account).toBe(TREASURY);
+    expect(api.query.Assets.Account.getValue).toHaveBeenCalledWith([1984, TREASURY]);
+    const changes = await pot?.report(log);
+
+    expect(changes).toEqual([
+      { account: TREASURY, asset: 'native', before: 1_000n, after: 1_000n, delta: 0n },
+      { account: TREASURY, asset: 'Assets #1984 (USDt)', before: 500n, after: 200n, delta: -300n },
+    ]);
+    expect(log.section).toHaveBeenCalledWith('Treasury Pot');
+    expect(log.info).toHaveBeenCalledWith('  Assets #1984 (USDt): 500 -> 200 (-300)');
+  });
+
+  it('stays quiet when the pot did not move', async () => {
+    const pot = await TreasuryPot.snapshot(chain([7n, 7n], [3n, 3n]), PALLETS);
+    const log = logger();
+
+    await pot?.report(log);
+
+    expect(log.section).not.toHaveBeenCalled();
+  });
+});
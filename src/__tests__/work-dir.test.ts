@@ -0,0 +1,69 @@
+import * as fs from 'fs';
+import * as os from 'os';
+import * as path from 'path';
+import { afterEach, describe, expect, it } from 'vitest';
+import { ErrorCode } from '../utils/error-codes';
+import { DEFAULT_WORK_ROOT, newRunId, parseRunId, WorkDir } from '../utils/work-dir';
+
+describe('newRunId', () => {
+  it('is the start time with a random suffix', () => {
+    const id = newRunId(new Date('2026-10-17T10:15:00.123Z'));
+    expect(id).toMatch(/^20261017T101500Z-[0-9a-f]{6}$/);
+  });
+
+  it('differs between runs started at the same time', () => {
+    const now = new Date();
+    expect(newRunId(now)).not.toBe(newRunId(now));
+  });
+});
+
+describe('parseRunId', () => {
+  it('accepts ids usable as a folder name', () => {
+    expect(parseRunId('gov_create-4242-1')).toBe('gov_create-4242-1');
+    expect(parseRunId('20261017T101500Z-3fa9c1')).toBe('20261017T101500Z-3fa9c1');
+  });
+
+  it('rejects ids with other characters', () => {
+    for (const id of ['', '..', 'a/b', 'run 1', 'run:1']) {
+      expect(() => parseRunId(id)).toThrow(
+        expect.objectContaining({ code: ErrorCode.InvalidRunId })
+      );
+    }
+  });
+});
+
+describe('WorkDir', () => {
+  const root = path.join(os.tmpdir(), `work-dir-test-${process.pid}`);
+
+  afterEach(() => {
+    fs.rmSync(root, { recursive: true, force: true });
+  });
+
+  it('creates a run id subfolder of the given root', () => {
+    const workDir = new WorkDir(root, 'run-1');
+
+    expect(workDir.path).toBe(path.join(root, 'run-1'));
+    expect(fs.statSync(workDir.path).isDirectory()).toBe(true);
+    expect(workDir.file('chopsticks.db')).toBe(path.join(root, 'run-1', 'chopsticks.db'));
+  });
+
+  it('defaults to the system temp dir', () => {
+    const workDir = new WorkDir(undefined);
+    try {
+      expect(path.dirname(workDir.path)).toBe(DEFAULT_WORK_ROOT);
+      expect(path.basename(workDir.path)).toBe(workDir.runId);
+    } finally {
+      workDir.remove();
+    }
+  });
+
+  it('removes the folder with everything in it', () => {
+    const workDir = new WorkDir(root, 'run-1');
+    fs.writeFileSync(workDir.file('chopsticks.db'), 'state');
+
+    workDir.remove();
+
+    expect(fs.existsSync(workDir.path)).toBe(false);
+    expect(fs.existsSync(root)).toBe(true);
+  });
+});
@@ -0,0 +1,316 @@
+import { AccountId, Binary } from 'polkadot-api';
+import { describe, expect, it, vi } from 'vitest';
+import type { DecodedCall } from '../services/proposal-linter';
+import {
+  checkSovereignFunding,
+  checkXcmLimits,
+  relativeLocation,
+  sovereignAccount,
+  withdrawnNative,
+  XcmFeeEstimator,
+  xcmSend,
+} from '../services/xcm-fee-estimator';
+import { formatLocation } from '../utils/xcm-location';
+import { createSilentLogger } from './test-logger';
+
+const weight = (ref_time: bigint, proof_size: bigint) => ({ ref_time, proof_size });
+
+const ASSET_HUB = {
+  parents: 0,
+  interior: { type: 'X1', value: [{ type: 'Parachain', value: 1000 }] },
+};
+const DOT = { parents: 1, interior: { type: 'Here' } };
+const REMARK = Binary.fromHex('0x0000');
+
+function buyExecution(amount: bigint, limit?: ReturnType<typeof weight>) {
+  return {
+    type: 'BuyExecution',
+    value: {
+      fees: { id: DOT, fun: { type: 'Fungible', value: amount } },
+      weight_limit: limit ? { type: 'Limited', value: limit } : { type: 'Unlimited' },
+    },
+  };
+}
+
+function transact(declared: ReturnType<typeof weight>, originKind = 'Superuser') {
+  return {
+    type: 'Transact',
+    value: { origin_kind: { type: originKind }, require_weight_at_most: declared, call: REMARK },
+  };
+}
+
+function withdrawAsset(id: unknown, amount: bigint) {
+  return { type: 'WithdrawAsset', value: [{ id, fun: { type: 'Fungible', value: amount } }] };
+}
+
+function send(instructions: unknown[], dest: unknown = ASSET_HUB): DecodedCall {
+  return {
+    type: 'XcmPallet',
+    value: {
+      type: 'send',
+      value: { dest: { type: 'V4', value: dest }, message: { type: 'V4', value: instructions } },
+    },
+  };
+}
+
+function mockChain(paraId: number | undefined, overrides: Record<string, unknown> = {}) {
+  return {
+    query: {
+      System: {
+        Account: {
+          getValue: vi.fn().mockResolvedValue({ data: { free: 1_000n, reserved: 0n } }),
+        },
+      },
+      ParachainInfo: {
+        ParachainId: {
+          getValue: vi.fn(() =>
+            paraId === undefined ? Promise.reject(new Error('no storage')) : Promise.resolve(paraId)
+          ),
+        },
+      },
+    },
+    apis: {
+      XcmPaymentApi: {
+        query_xcm_weight: vi
+          .fn()
+          .mockResolvedValue({ success: true, value: weight(1_000_000n, 10_000n) }),
+        query_weight_to_asset_fee: vi.fn().mockResolvedValue({ success: true, value: 500n }),
+      },
+      DryRunApi: {
+        dry_run_xcm: vi.fn().mockResolvedValue({
+          success: true,
+          value: { execution_result: { type: 'Complete', value: { used: weight(1n, 1n) } } },
+        }),
+      },
+      TransactionPaymentCallApi: {
+        query_call_info: vi.fn().mockResolvedValue({ weight: weight(200n, 20n) }),
+      },
+    },
+    constants: { Balances: { ExistentialDeposit: vi.fn().mockResolvedValue(100n) } },
+    txFromCallData: vi.fn().mockResolvedValue({ decodedCall: { type: 'System' } }),
+    ...overrides,
+  } as any;
+}
+
+describe('xcmSend', () => {
+  it('unwraps the destination and message of a send', () => {
+    const found = xcmSend(send([buyExecution(1n)]));
+    expect(found?.version).toBe('V4');
+    expect(formatLocation(found!.dest)).toBe('Parachain(1000)');
+    expect(found?.instructions).toHaveLength(1);
+  });
+
+  it('ignores other calls', () => {
+    expect(xcmSend({ type: 'XcmPallet', value: { type: 'execute', value: {} } })).toBeUndefined();
+    expect(xcmSend({ type: 'System', value: { type: 'remark', value: {} } })).toBeUndefined();
+  });
+});
+
+describe('relativeLocation', () => {
+  it.each([
+    [undefined, 1000, { parents: 0, para: 1000 }],
+    [1000, undefined, { parents: 1, para: undefined }],
+    [1001, 1000, { parents: 1, para: 1000 }],
+  ])('locates %s -> %s', (from, to, expected) => {
+    const location = relativeLocation(from, to);
+    expect(location.parents).toBe(expected.parents);
+    expect(location.interior).toEqual(
+      expected.para === undefined
+        ? { type: 'Here' }
+        : { type: 'X1', value: [{ type: 'Parachain', value: expected.para }] }
+    );
+  });
+});
+
+describe('sovereignAccount', () => {
+  const bytes = (account: string) => AccountId().enc(account);
+
+  it('derives para and sibl accounts from the para id', () => {
+    const onRelay = bytes(sovereignAccount(1000, undefined));
+    expect(new TextDecoder().decode(onRelay.slice(0, 4))).toBe('para');
+    expect(new DataView(onRelay.buffer, onRelay.byteOffset).getUint32(4, true)).toBe(1000);
+    expect(onRelay.slice(8).every((byte) => byte === 0)).toBe(true);
+
+    const onSibling = bytes(sovereignAccount(1001, 1000));
+    expect(new TextDecoder().decode(onSibling.slice(0, 4))).toBe('sibl');
+    expect(new DataView(onSibling.buffer, onSibling.byteOffset).getUint32(4, true)).toBe(1001);
+  });
+
+  it('uses the Parent account for the relay chain', () => {
+    const parent = bytes(sovereignAccount(undefined, 1000));
+    expect(new TextDecoder().decode(parent.slice(0, 6))).toBe('Parent');
+    expect(parent.slice(6).every((byte) => byte === 0)).toBe(true);
+  });
+});
+
+describe('withdrawnNative', () => {
+  it('sums the relay token withdrawn on a parachain and ignores other assets', () => {
+    const usdt = {
+      parents: 0,
+      interior: {
+        type: 'X2',
+        value: [
+          { type: 'PalletInstance', value: 50 },
+          { type: 'GeneralIndex', value: 1984n },
+        ],
+      },
+    };
+    const instructions = [withdrawAsset(DOT, 300n), withdrawAsset(usdt, 5n), buyExecution(1n)];
+    expect(withdrawnNative(instructions, 1000)).toBe(300n);
+    expect(withdrawnNative(instructions, undefined)).toBe(0n);
+  });
+});
+
+describe('checkSovereignFunding', () => {
+  const account = sovereignAccount(undefined, 1000);
+
+  it('flags an account holding less than the message withdraws', () => {
+    const problems = checkSovereignFunding([], { account, free: 10n, withdrawn: 20n }, 1n);
+    expect(problems).toEqual([
+      `sovereign account ${account} holds 10, less than the 20 the message withdraws`,
+    ]);
+  });
+
+  it('flags a signed Transact left below the existential deposit', () => {
+    const instructions = [transact(weight(1n, 1n), 'SovereignAccount')];
+    const funding = { account, free: 150n, withdrawn: 100n };
+    expect(checkSovereignFunding(instructions, funding, 100n)[0]).toContain(
+      'keeps 50 after the withdrawal, below the existential deposit (100)'
+    );
+    expect(checkSovereignFunding(instructions, funding, 50n)).toEqual([]);
+  });
+
+  it('does not need a balance for a Superuser Transact', () => {
+    const instructions = [transact(weight(1n, 1n))];
+    expect(checkSovereignFunding(instructions, { account, free: 0n, withdrawn: 0n }, 1n)).toEqual(
+      []
+    );
+  });
+});
+
+describe('checkXcmLimits', () => {
+  const estimate = { weight: weight(1_000n, 100n), fee: 50n, callWeights: [weight(10n, 10n)] };
+
+  it('accepts limits that cover the estimate', () => {
+    const instructions = [buyExecution(50n, weight(1_000n, 100n)), transact(weight(10n, 10n))];
+    expect(checkXcmLimits(instructions, estimate)).toEqual([]);
+  });
+
+  it('flags a low weight limit, low fees and a low Transact weight', () => {
+    const instructions = [buyExecution(49n, weight(1_000n, 99n)), transact(weight(9n, 10n))];
+    const problems = checkXcmLimits(instructions, estimate);
+    expect(problems).toHaveLength(3);
+    expect(problems[0]).toContain('BuyExecution weight limit');
+    expect(problems[1]).toContain('BuyExecution fees (49) are below the estimated fee (50)');
+    expect(problems[2]).toContain('Transact declares ref_time 9');
+  });
+
+  it('skips what the destination could not estimate', () => {
+    const instructions = [buyExecution(1n, weight(1n, 1n)), transact(weight(1n, 1n))];
+    expect(checkXcmLimits(instructions, { callWeights: [undefined] })).toEqual([]);
+  });
+});
+
+describe('XcmFeeEstimator', () => {
+  it('estimates a message on the chain it is sent to', async () => {
+    const relay = mockChain(undefined);
+    const assetHub = mockChain(1000);
+    const estimator = new XcmFeeEstimator(createSilentLogger(), relay, [
+      { label: 'Asset Hub', api: assetHub },
+    ]);
+
+    const estimate = await estimator.estimate(
+      send([buyExecution(400n), transact(weight(100n, 20n))])
+    );
+
+    expect(estimate).toEqual({
+      destination: 'Asset Hub',
+      weight: weight(1_000_000n, 10_000n),
+      fee: 500n,
+      dryRun: 'Complete',
+      problems: [
+        'BuyExecution fees (400) are below the estimated fee (500)',
+        "Transact declares ref_time 100, proof_size 20, below its call's weight (ref_time 200, proof_size 20)",
+      ],
+      sovereignAccount: {
+        account: sovereignAccount(undefined, 1000),
+        free: 1_000n,
+        withdrawn: 0n,
+        warnings: [],
+      },
+    });
+    expect(assetHub.query.System.Account.getValue).toHaveBeenCalledWith(
+      sovereignAccount(undefined, 1000)
+    );
+    expect(assetHub.apis.DryRunApi.dry_run_xcm).toHaveBeenCalledWith(
+      { type: 'V4', value: { parents: 1, interior: { type: 'Here' } } },
+      expect.objectContaining({ type: 'V4' })
+    );
+  });
+
+  it('reports a failed dry run and skips APIs the destination lacks', async () => {
+    const assetHub = mockChain(1000, {
+      apis: {
+        XcmPaymentApi: {
+          query_xcm_weight: vi.fn().mockRejectedValue(new Error('no such API')),
+        },
+        DryRunApi: {
+          dry_run_xcm: vi.fn().mockResolvedValue({
+            success: true,
+            value: {
+              execution_result: {
+                type: 'Incomplete',
+                value: { used: weight(1n, 1n), error: { type: 'Barrier' } },
+              },
+            },
+          }),
+        },
+        TransactionPaymentCallApi: {
+          query_call_info: vi.fn().mockRejectedValue(new Error('no such API')),
+        },
+      },
+    });
+    const estimator = new XcmFeeEstimator(createSilentLogger(), mockChain(undefined), [
+      { label: 'Asset Hub', api: assetHub },
+    ]);
+
+    const estimate = await estimator.estimate(send([transact(weight(1n, 1n))]));
+
+    expect(estimate?.weight).toBeUndefined();
+    expect(estimate?.problems).toEqual(['dry run on Asset Hub: Incomplete: {"type":"Barrier"}']);
+  });
+
+  it('warns when the sovereign account cannot cover the withdrawal', async () => {
+    const assetHub = mockChain(1000);
+    const estimator = new XcmFeeEstimator(createSilentLogger(), mockChain(undefined), [
+      { label: 'Asset Hub', api: assetHub },
+    ]);
+
+    const estimate = await estimator.estimate(
+      send([withdrawAsset(DOT, 5_000n), buyExecution(5_000n), transact(weight(200n, 20n))])
+    );
+
+    expect(estimate?.sovereignAccount?.withdrawn).toBe(5_000n);
+    expect(estimate?.sovereignAccount?.warnings).toEqual([
+      `sovereign account ${sovereignAccount(undefined, 1000)} holds 1000, less than the 5000 the message withdraws`,
+    ]);
+  });
+
+  it('estimates each call once', async () => {
+    const assetHub = mockChain(1000);
+    const estimator = new XcmFeeEstimator(createSilentLogger(), mockChain(undefined), [
+      { label: 'Asset Hub', api: assetHub },
+    ]);
+    const call = send([buyExecution(1n)]);
+    await estimator.estimate(call);
+    await estimator.estimate(call);
+    expect(assetHub.apis.XcmPaymentApi.query_xcm_weight).toHaveBeenCalledTimes(1);
+  });
+
+  it('skips messages to chains that are not forked', async () => {
+    const estimator = new XcmFeeEstimator(createSilentLogger(), mockChain(undefined), [
+      { label: 'People', api: mockChain(1004) },
+    ]);
+    expect(await estimator.estimate(send([buyExecution(1n)]))).toBeUndefined();
+  });
+});
@@ -3,6 +3,8 @@
 import { Command } from 'commander';
 import { version } from '../package.json';
 import { listReferendums } from './commands/list-referendums';
+import { selftestOrigins } from './commands/selftest-origins';
+import { sweepReferendums } from './commands/sweep-referendums';
 import { testReferendum } from './commands/test-referendum';
 
 const program = new Command();
@@ -18,15 +20,22 @@ program
   .description('Test a referendum by simulating its execution')
   .option(
     '--governance-chain-url <url>',
-    'Governance chain RPC endpoint URL. Format: url or url,block (e.g., wss://polkadot.io or wss://polkadot.io,12345)'
+    'Governance chain RPC endpoint URL. Format: url, url,block or name=label;url=url;block=block (e.g., wss://polkadot.io,12345 or name=AssetHub;url=wss://polkadot.io)'
   )
   .option(
     '--fellowship-chain-url <url>',
-    'Fellowship chain RPC endpoint URL. Format: url or url,block (only required when using --fellowship)'
+    'Fellowship chain RPC endpoint URL. Format: url, url,block or name=label;url=url;block=block (only required when using --fellowship)'
+  )
+  .option(
+    '-r, --referendum <[pallet:]id>',
+    'Main governance referendum ID to test, optionally in another referenda pallet (e.g. AmbassadorReferenda:12)'
   )
-  .option('-r, --referendum <id>', 'Main governance referendum ID to test')
   .option('-f, --fellowship <id>', 'Fellowship referendum ID (for whitelisting scenarios)')
   .option('-p, --port <port>', 'Local Chopsticks starting port', '8000')
+  .option(
+    '--port-range <start:len>',
+    'Reserve ports start..start+len-1 for Chopsticks; fails if any of them is already in use'
+  )
   .option(
     '--pre-call <hex>',
     'Hex string of call to execute before the main referendum (via Scheduler.Inline)'
@@ -35,11 +44,105 @@ program
     '--pre-origin <origin>',
     'Origin for pre-execution call (e.g., "Root", "WhitelistedCaller", "Origins.Treasurer")'
   )
+  .option(
+    '--scenario <name>',
+    'Inject a fault to check its reporting: bad-origin (dispatch the proposal from a mismatched origin)'
+  )
+  .option(
+    '--cancel-at <phase>',
+    'Cancel the referendum via ReferendumCanceller instead of dispatching it, then check its deposits are refunded: ongoing (as forked) or confirming (in its confirm period)'
+  )
+  .option(
+    '--vote-conviction <conviction>',
+    "Approve conviction-voting referenda by a funded voter's aye at this conviction (None, Locked1x..Locked6x) instead of writing the tally, then check the class lock it leaves"
+  )
+  .option(
+    '--set-storage <override>',
+    "Write Pallet.Item[key]=value to the forked chain before simulating (repeatable), e.g. 'AhMigrator.AhMigrationStage=MigrationDone'",
+    (value: string, previous: string[] = []) => [...previous, value]
+  )
+  .option(
+    '--override-parameter <override>',
+    "Simulate under a different runtime parameter: Namespace.Name=value, written to Parameters.Parameters on the forked chain (repeatable), e.g. 'Treasury.BurnPortion=0'",
+    (value: string, previous: string[] = []) => [...previous, value]
+  )
+  .option(
+    '--fund <ss58=amount>',
+    'Give an account a free balance of <amount> plancks on the forked chain before simulating (repeatable)',
+    (value: string, previous: string[] = []) => [...previous, value]
+  )
+  .option(
+    '--unlock-call-filter',
+    'Lift the Asset Hub migration call filter (AhMigrator/RcMigrator stage MigrationDone) on the forked chain'
+  )
   .option('--no-cleanup', 'Keep Chopsticks instance running after test')
   .option('-v, --verbose', 'Enable verbose logging')
+  .option('--emit-blocks', 'Print a machine-readable "@@block {json}" line for every block built')
+  .option(
+    '--output-format <format>',
+    'text (default) or json: json also prints the outcome as one machine-readable "@@result {json}" line'
+  )
+  .option(
+    '--hook-after-passing <script>',
+    'Run a .js or .wasm hook once the referendum is forced into a passing state'
+  )
+  .option(
+    '--hook-before-dispatch <script>',
+    'Run a .js or .wasm hook right before the block that dispatches the proposal'
+  )
+  .option(
+    '--hook-after-dispatch <script>',
+    'Run a .js or .wasm hook after the proposal was dispatched, before results are checked'
+  )
+  .option(
+    '--lint <rules>',
+    'Comma-separated lint levels, e.g. set-code-without-authorize-upgrade=deny,treasury-spend-beneficiary-proxy=allow'
+  )
+  .option('--deny <kind>', 'Fail the run on lint findings of this kind (only "warnings")')
+  .option(
+    '--db <path>',
+    'Chopsticks storage cache (SQLite file). Forks of an already-cached block skip re-fetching state (default: .chopsticks-db)'
+  )
+  .option(
+    '--run-id <id>',
+    'Id of this run, prefixed to every log line and used in its work dir and history record (default: start time and a random suffix)'
+  )
+  .option(
+    '--work-dir <path>',
+    'Directory for temporary state (log, fork runtimes); each run uses a <run id> subfolder, removed unless the run fails (default: polkadot-referenda-tester in the system temp dir)'
+  )
+  .option(
+    '--history-dir <path>',
+    "Keep each run's JSON result in this directory, per chain and referendum (off unless this or --diff-with-last is given; default with --diff-with-last: .referenda-history)"
+  )
+  .option(
+    '--diff-with-last',
+    'Report new failing events, changed weights and changed balances since the previous run of the same referendum'
+  )
   .option(
     '--additional-chains <urls>',
-    'Comma-separated list of additional chain URLs to monitor for XCM events. Format: url or url,block (e.g., wss://chain1.io,11111,wss://chain2.io)'
+    'Additional chain URLs to monitor for XCM events (repeatable; each value may also be a comma-separated list). Format: url, url,block or name=label;url=url;block=block (e.g., wss://chain1.io,11111,name=People;url=wss://chain2.io)',
+    (value: string, previous: string[] = []) => [...previous, value]
+  )
+  .option(
+    '--settle-blocks <n>',
+    'Blocks to build on each additional chain after dispatch, so late XCM effects are seen (default: 1)'
+  )
+  .option(
+    '--blocks-after-dispatch <n>',
+    'Blocks to build on the governance chain after the dispatch block, showing their events to catch deferred effects such as on_idle work or message queue servicing (default: 0)'
+  )
+  .option(
+    '--follow-scheduled',
+    'When the governance proposal schedules tasks for later blocks, dispatch them right away and report their results instead of stopping at enactment'
+  )
+  .option(
+    '--enactment-retries <n>',
+    'Give the enactment task a scheduler retry configuration of <n> retries (1-255) if it has none, so a failed dispatch is retried and the retries are reported'
+  )
+  .option(
+    '--track-balance <checks>',
+    'Comma-separated account@asset[=delta] balances to report across dispatch, where asset is native, assets:<id> or foreign:<location> (e.g. 15oF...@assets:1984=+1000000)'
   )
   .option(
     '--call-to-create-governance-referendum <hex>',
@@ -49,6 +152,18 @@ program
     '--call-to-note-preimage-for-governance-referendum <hex>',
     'Call data to note preimage for governance referendum (hex, optional)'
   )
+  .option(
+    '--metadata-for-governance-referendum <file|hash>',
+    "Set the created governance referendum's metadata (set_metadata): a JSON file, noted as a preimage first, or the 0x hash of a noted preimage"
+  )
+  .option(
+    '--submit-via-proxy <real[:type[:delay]]>',
+    'Create the governance referendum as <real> with Alice as its proxy (Proxy.proxy, or announce + Proxy.proxy_announced with a delay). Proxy type defaults to Any'
+  )
+  .option(
+    '--submit-via-multisig <threshold:signatories>',
+    'Create the governance referendum from the <threshold>-of-n multisig of the comma-separated <signatories>: the first <threshold> - 1 approve the call hash (Multisig.approve_as_multi), the next one executes it (Multisig.as_multi, or as_multi_threshold_1 for a threshold of 1)'
+  )
   .option(
     '--call-to-create-fellowship-referendum <hex>',
     'Call data to create a fellowship referendum (hex). Mutually exclusive with --fellowship'
@@ -57,6 +172,14 @@ program
     '--call-to-note-preimage-for-fellowship-referendum <hex>',
     'Call data to note preimage for fellowship referendum (hex, optional)'
   )
+  .option(
+    '--metadata-for-fellowship-referendum <file|hash>',
+    "Set the created fellowship referendum's metadata (set_metadata): a JSON file, noted as a preimage first, or the 0x hash of a noted preimage"
+  )
+  .option(
+    '--preview',
+    'Decode the --call-to-create-* and preimage calls against the live chain, show origin, track, proposal hash and length and deposits, then exit without forking'
+  )
   .action(testReferendum);
 
 // List all referendums
@@ -78,4 +201,45 @@ program
   .option('-v, --verbose', 'Enable verbose logging')
   .action(listReferendums);
 
+// Simulate every ongoing referendum
+program
+  .command('sweep')
+  .description('Simulate every ongoing referendum and summarize which would execute today')
+  .option(
+    '--governance-chain-url <url>',
+    'Governance chain RPC endpoint URL. Format: url or url,block (e.g., wss://polkadot.io or wss://polkadot.io,12345)'
+  )
+  .option(
+    '--fellowship-chain-url <url>',
+    'Fellowship chain RPC endpoint URL. Format: url or url,block'
+  )
+  .option(
+    '--concurrency <n>',
+    'Referenda simulated at the same time, each on its own fork (default: 2)'
+  )
+  .option(
+    '--db <path>',
+    'Chopsticks storage cache (SQLite file) shared by all forks of the sweep (default: .chopsticks-db)'
+  )
+  .option('-v, --verbose', 'Enable verbose logging')
+  .action(sweepReferendums);
+
+// Check the tool against the chain's runtime
+const selftest = program
+  .command('selftest')
+  .description('Check that the tool still works against a chain after a runtime upgrade');
+
+selftest
+  .command('origins')
+  .description(
+    "Dispatch a remark from every origin of the chain's OriginCaller and report which the tool can construct"
+  )
+  .option(
+    '--chain-url <url>',
+    'Chain RPC endpoint URL. Format: url or url,block (e.g., wss://polkadot.io or wss://polkadot.io,12345)'
+  )
+  .option('--db <path>', 'Chopsticks storage cache (SQLite file) (default: .chopsticks-db)')
+  .option('-v, --verbose', 'Enable verbose logging')
+  .action(selftestOrigins);
+
 program.parse();
@@ -0,0 +1,85 @@
+import type { PolkadotClient } from 'polkadot-api';
+import { createApiForChain, createPolkadotClient } from '../services/chain-registry';
+import { ChainTopologyBuilder } from '../services/chain-topology-builder';
+import { ChopsticksManager } from '../services/chopsticks-manager';
+import {
+  findOrigins,
+  formatOriginTable,
+  OriginSelfTest,
+  readOriginMetadata,
+} from '../services/origin-selftest';
+import type { ChopsticksConfig } from '../types';
+import { parseEndpoint } from '../utils/chain-endpoint-parser';
+import { CodedError, ErrorCode, formatCodedError } from '../utils/error-codes';
+import { Logger } from '../utils/logger';
+
+interface SelftestOriginsOptions {
+  chainUrl?: string;
+  db?: string;
+  verbose?: boolean;
+}
+
+export async function selftestOrigins(options: SelftestOriginsOptions): Promise<void> {
+  const logger = new Logger(options.verbose);
+  const chopsticks = new ChopsticksManager(logger);
+  let client: PolkadotClient | undefined;
+
+  try {
+    if (!options.chainUrl) {
+      throw new CodedError(ErrorCode.MissingChainUrl, '--chain-url is required');
+    }
+    const endpoint = parseEndpoint(options.chainUrl);
+
+    logger.section('Origin Self-Test');
+    const topology = new ChainTopologyBuilder(logger, { db: options.db });
+    const networkKey = await topology.detectRelayNetworkKey(endpoint.url);
+    const config = topology.buildConfig(endpoint.url, endpoint.block);
+    const context = await chopsticks.setup(config as unknown as ChopsticksConfig, networkKey);
+    const forkClient = createPolkadotClient(context.ws.endpoint);
+    client = forkClient;
+    const api = createApiForChain(forkClient);
+    await chopsticks.waitForChainReady(api);
+
+    const metadata = await readOriginMetadata((method, params) =>
+      forkClient._request(method, params)
+    );
+    const origins = findOrigins(metadata);
+    logger.info(`Found ${origins.length} origin(s) in OriginCaller`);
+
+    // Chains without `Referenda` (Collectives) schedule by their own block
+    // numbers, as fellowship referenda do.
+    const isFellowship = !metadata.pallets.some((pallet) => pallet.name === 'Referenda');
+    const checks = await new OriginSelfTest(logger, chopsticks, api, isFellowship).run(origins);
+
+    logger.section('Origin Summary');
+    for (const line of formatOriginTable(checks)) {
+      console.log(line);
+    }
+    const count = (outcome: string) => checks.filter((check) => check.outcome === outcome).length;
+    logger.info(
+      `${count('ok')} of ${checks.length} origins dispatched, ${count('failed')} failed, ${count('unsupported')} carry fields`
+    );
+    if (count('failed') > 0) {
+      throw new CodedError(
+        ErrorCode.OriginSelftestFailed,
+        `${count('failed')} origin(s) could not be constructed`
+      );
+    }
+    await cleanup(logger, chopsticks, client);
+    process.exit(0);
+  } catch (error) {
+    logger.error('Origin self-test failed', error as Error);
+    console.error(formatCodedError(error));
+    await cleanup(logger, chopsticks, client);
+    process.exit(1);
+  }
+}
+
+async function cleanup(
+  logger: Logger,
+  chopsticks: ChopsticksManager,
+  client?: PolkadotClient
+): Promise<void> {
+  client?.destroy();
+  await chopsticks.cleanup().catch((error) => logger.debug(`Cleanup failed: ${error}`));
+}
@@ -0,0 +1,57 @@
+import { ChainTopologyBuilder } from '../services/chain-topology-builder';
+import {
+  formatSweepTable,
+  parseConcurrency,
+  ReferendaSweeper,
+} from '../services/referenda-sweeper';
+import { parseEndpoint } from '../utils/chain-endpoint-parser';
+import { CodedError, ErrorCode, formatCodedError } from '../utils/error-codes';
+import { Logger } from '../utils/logger';
+
+interface SweepOptions {
+  governanceChainUrl?: string;
+  fellowshipChainUrl?: string;
+  concurrency?: string;
+  db?: string;
+  verbose?: boolean;
+}
+
+export async function sweepReferendums(options: SweepOptions): Promise<void> {
+  const logger = new Logger(options.verbose);
+
+  try {
+    if (options.governanceChainUrl && options.fellowshipChainUrl) {
+      throw new CodedError(
+        ErrorCode.FlagConflict,
+        'Sweep one chain at a time: --governance-chain-url or --fellowship-chain-url'
+      );
+    }
+    const chainUrl = options.governanceChainUrl ?? options.fellowshipChainUrl;
+    if (!chainUrl) {
+      throw new CodedError(
+        ErrorCode.MissingChainUrl,
+        'Either --governance-chain-url or --fellowship-chain-url is required'
+      );
+    }
+    const isFellowship = !options.governanceChainUrl;
+    const endpoint = parseEndpoint(chainUrl);
+    const concurrency = parseConcurrency(options.concurrency);
+
+    logger.section(`Sweeping ${isFellowship ? 'Fellowship' : 'Governance'} Referenda`);
+    const topology = new ChainTopologyBuilder(logger, { db: options.db });
+    const sweeper = new ReferendaSweeper(logger, topology, isFellowship, concurrency);
+    const entries = await sweeper.sweep(endpoint.url, endpoint.block);
+
+    logger.section('Sweep Summary');
+    for (const line of formatSweepTable(entries)) {
+      console.log(line);
+    }
+    const passed = entries.filter((entry) => entry.outcome === 'passed').length;
+    logger.info(`${passed} of ${entries.length} ongoing referenda would execute successfully`);
+    process.exit(0);
+  } catch (error) {
+    logger.error('Sweep failed', error as Error);
+    console.error(formatCodedError(error));
+    process.exit(1);
+  }
+}
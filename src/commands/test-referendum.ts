@@ -1,49 +1,59 @@
+import { parseBalanceChecks } from '../services/balance-tracker';
+import { tagChopsticksLogs } from '../services/chopsticks-manager';
+import { parseConviction } from '../services/conviction-approval';
+import { previewCreation } from '../services/creation-preview';
+import { parseDelegatedSubmission } from '../services/delegated-submission';
+import { parseBlocksAfterDispatch, parseSettleBlocks } from '../services/event-collector';
+import { HookRunner } from '../services/hook-runner';
 import { NetworkCoordinator } from '../services/network-coordinator';
+import { parseLintLevels, ProposalLinter } from '../services/proposal-linter';
+import { parseReferendumTarget } from '../services/referenda-pallets';
+import { parseCancelPhase } from '../services/referendum-cancellation';
+import { parseReferendumMetadata } from '../services/referendum-metadata';
+import { RunHistory } from '../services/run-history';
+import {
+  emitRunReport,
+  type OutputFormat,
+  parseOutputFormat,
+  type RunEndpoints,
+  reportEndpoints,
+} from '../services/run-report';
+import { parseScenario } from '../services/scenarios';
+import { parseEnactmentRetries } from '../services/scheduler-manager';
+import { shutdown } from '../services/shutdown';
+import { storageOverridesFor } from '../services/storage-overrides';
+import { printSubmissionPayloads } from '../services/submission-payloads';
 import type { TestOptions } from '../types';
 import { parseEndpoint, parseMultipleEndpoints } from '../utils/chain-endpoint-parser';
+import { exitCodeOf, formatCodedError } from '../utils/error-codes';
+import { validateFlagRules } from '../utils/flag-rules';
+import { normalizeCallDataOptions } from '../utils/hex';
 import { Logger } from '../utils/logger';
-
-function validateOptions(options: TestOptions): void {
-  if (options.referendum && options.callToCreateGovernanceReferendum) {
-    throw new Error(
-      'Cannot specify both --referendum (existing ID) and --call-to-create-governance-referendum (create new). Use one or the other.'
-    );
-  }
-
-  if (options.fellowship && options.callToCreateFellowshipReferendum) {
-    throw new Error(
-      'Cannot specify both --fellowship (existing ID) and --call-to-create-fellowship-referendum (create new). Use one or the other.'
-    );
-  }
-
-  const hasGovernanceRef = !!(options.referendum || options.callToCreateGovernanceReferendum);
-  const hasFellowshipRef = !!(options.fellowship || options.callToCreateFellowshipReferendum);
-
-  if (!hasGovernanceRef && !hasFellowshipRef) {
-    throw new Error(
-      'At least one referendum must be specified (--referendum, --fellowship) or created (--call-to-create-governance-referendum, --call-to-create-fellowship-referendum)'
-    );
-  }
-}
+import { assertPortRangeFree, parsePortRange } from '../utils/port-range';
+import { newRunId, parseRunId, WorkDir } from '../utils/work-dir';
 
 export async function testReferendum(options: TestOptions): Promise<void> {
-  const logger = new Logger(options.verbose);
+  const runId = options.runId ?? newRunId();
+  const logger = new Logger(options.verbose, options.emitBlocks, runId);
   const cleanupEnabled = options.cleanup !== false;
+  let workDir: WorkDir | undefined;
+  let untrackWorkDir: (() => void) | undefined;
+  let outputFormat: OutputFormat = 'text';
+  let endpoints: RunEndpoints | undefined;
+  let coordinator: NetworkCoordinator | undefined;
+  const removeSignalHandlers = shutdown.install(logger, (error) =>
+    emitRunReport(outputFormat, runId, error, endpoints, coordinator?.getHeads())
+  );
 
   try {
-    validateOptions(options);
+    normalizeCallDataOptions(options);
+    validateFlagRules(options);
+    parseRunId(runId);
+    tagChopsticksLogs(runId);
+    outputFormat = parseOutputFormat(options.outputFormat);
 
-    const hasGovernanceRef = !!(options.referendum || options.callToCreateGovernanceReferendum);
     const hasFellowshipRef = !!(options.fellowship || options.callToCreateFellowshipReferendum);
 
-    if (hasGovernanceRef && !options.governanceChainUrl) {
-      throw new Error('--governance-chain-url is required when testing a governance referendum');
-    }
-
-    if (hasFellowshipRef && !options.fellowshipChainUrl) {
-      throw new Error('--fellowship-chain-url is required when testing a fellowship referendum');
-    }
-
     const governanceParsed = options.governanceChainUrl
       ? parseEndpoint(options.governanceChainUrl)
       : undefined;
@@ -52,19 +62,66 @@ export async function testReferendum(options: TestOptions): Promise<void> {
       ? parseEndpoint(options.fellowshipChainUrl)
       : undefined;
 
-    const additionalChainsParsed = options.additionalChains
-      ? parseMultipleEndpoints(options.additionalChains)
-      : [];
+    const additionalChainsParsed = (options.additionalChains ?? []).flatMap(
+      parseMultipleEndpoints
+    );
+    endpoints = reportEndpoints({
+      governance: governanceParsed,
+      fellowship: fellowshipParsed,
+      additionalChains: additionalChainsParsed,
+    });
+
+    const portRange = options.portRange ? parsePortRange(options.portRange) : undefined;
+    if (portRange) {
+      await assertPortRangeFree(portRange);
+    }
+
+    const hooks = new HookRunner(logger, {
+      'after-passing': options.hookAfterPassing,
+      'before-dispatch': options.hookBeforeDispatch,
+      'after-dispatch': options.hookAfterDispatch,
+    });
 
-    const mainRefId = options.referendum ? parseInt(options.referendum, 10) : undefined;
-    if (mainRefId !== undefined && Number.isNaN(mainRefId)) {
-      throw new Error(`Invalid referendum ID: ${options.referendum}`);
+    const linter = new ProposalLinter(logger, parseLintLevels(options.lint, options.deny));
+    const settleBlocks = parseSettleBlocks(options.settleBlocks);
+    const blocksAfterDispatch = parseBlocksAfterDispatch(options.blocksAfterDispatch);
+    const enactmentRetries = parseEnactmentRetries(options.enactmentRetries);
+    const balanceChecks = parseBalanceChecks(options.trackBalance);
+    const governanceSubmission = parseDelegatedSubmission(options);
+    const history =
+      options.historyDir || options.diffWithLast
+        ? new RunHistory(logger, {
+            dir: options.historyDir,
+            diffWithLast: options.diffWithLast,
+            runId,
+          })
+        : undefined;
+    // Fail on bad --scenario, --cancel-at, metadata, --set-storage, --fund or
+    // --override-parameter values before forking; the coordinator applies them
+    parseScenario(options.scenario);
+    parseCancelPhase(options.cancelAt);
+    parseConviction(options.voteConviction);
+    storageOverridesFor(options);
+    parseReferendumMetadata(options, false);
+    parseReferendumMetadata(options, true);
+
+    if (options.preview) {
+      await previewCreation(logger, options, {
+        governance: governanceParsed,
+        fellowship: fellowshipParsed,
+      });
+      emitRunReport(outputFormat, runId, undefined, endpoints);
+      process.exit(0);
     }
 
+    workDir = new WorkDir(options.workDir, runId);
+    logger.writeTo(workDir.file('tool.log'));
+    logger.debug(`Work dir: ${workDir.path}`);
+    // An interrupted run, or a --no-cleanup one once stopped, leaves nothing to inspect
+    untrackWorkDir = shutdown.register('work dir', async () => workDir?.remove());
+
+    const mainTarget = options.referendum ? parseReferendumTarget(options.referendum) : undefined;
     const fellowshipRefId = options.fellowship ? parseInt(options.fellowship, 10) : undefined;
-    if (fellowshipRefId !== undefined && Number.isNaN(fellowshipRefId)) {
-      throw new Error('Invalid fellowship referendum ID');
-    }
 
     if (hasFellowshipRef) {
       logger.section('Polkadot Referenda Tester (Fellowship Mode)');
@@ -72,22 +129,70 @@ export async function testReferendum(options: TestOptions): Promise<void> {
       logger.section('Polkadot Referenda Tester');
     }
 
-    const coordinator = new NetworkCoordinator(logger, {
-      governance: governanceParsed?.url,
-      governanceBlock: governanceParsed?.block,
-      fellowship: fellowshipParsed?.url,
-      fellowshipBlock: fellowshipParsed?.block,
-      additionalChains: additionalChainsParsed,
-    });
+    coordinator = new NetworkCoordinator(
+      logger,
+      {
+        governance: governanceParsed?.url,
+        governanceBlock: governanceParsed?.block,
+        governanceLabel: governanceParsed?.label,
+        fellowship: fellowshipParsed?.url,
+        fellowshipBlock: fellowshipParsed?.block,
+        fellowshipLabel: fellowshipParsed?.label,
+        additionalChains: additionalChainsParsed,
+        db: options.db,
+        workDir: workDir.path,
+        portRange,
+      },
+      {
+        hooks: hooks.hasHooks() ? hooks : undefined,
+        linter,
+        settleBlocks,
+        balanceChecks,
+        governanceSubmission,
+        history,
+        dispatchOptions: {
+          blocksAfterDispatch,
+          followScheduled: options.followScheduled,
+          enactmentRetries,
+        },
+      }
+    );
 
-    await coordinator.testWithFellowship(mainRefId, fellowshipRefId, cleanupEnabled, options);
+    await coordinator.testWithFellowship(
+      mainTarget?.id,
+      fellowshipRefId,
+      cleanupEnabled,
+      options,
+      mainTarget?.pallet
+    );
+
+    // A fault or cancellation run says nothing about submitting the proposal as is
+    if (cleanupEnabled && !shutdown.stopping && !options.scenario && !options.cancelAt) {
+      await printSubmissionPayloads(logger, options, {
+        governance: governanceParsed,
+        fellowship: fellowshipParsed,
+      });
+    }
 
-    if (cleanupEnabled) {
+    if (cleanupEnabled && !shutdown.stopping) {
+      untrackWorkDir();
+      workDir.remove();
       logger.success('\n\u2713 Workflow completed');
+      emitRunReport(outputFormat, runId, undefined, endpoints, coordinator.getHeads());
       process.exit(0);
     }
   } catch (error) {
+    // Tearing down the forks on a signal fails the run; the stop reports and exits
+    if (shutdown.stopping) return;
     logger.error('Test execution failed', error as Error);
-    process.exit(1);
+    if (workDir) {
+      untrackWorkDir?.();
+      logger.info(`Work dir kept for inspection: ${workDir.path}`);
+    }
+    console.error(formatCodedError(error));
+    emitRunReport(outputFormat, runId, error, endpoints, coordinator?.getHeads());
+    process.exit(exitCodeOf(error));
+  } finally {
+    if (!shutdown.stopping) removeSignalHandlers();
   }
 }
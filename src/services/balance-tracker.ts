@@ -0,0 +1,156 @@
+import { AccountId, type SS58String } from 'polkadot-api';
+import type { SubstrateApi } from '../types/substrate-api';
+import { CodedError, ErrorCode } from '../utils/error-codes';
+import { formatLocation, parseLocation, type XcmLocation } from '../utils/xcm-location';
+
+/**
+ * A fungible asset on the forked chain: its native token, an `Assets` pallet
+ * asset (by id) or a `ForeignAssets` pallet asset (by location).
+ */
+export type AssetRef =
+  | { type: 'native' }
+  | { type: 'assets'; id: number }
+  | { type: 'foreign'; location: XcmLocation };
+
+export interface BalanceDelta {
+  asset: AssetRef;
+  account: SS58String;
+  before: bigint;
+  after: bigint;
+  delta: bigint;
+}
+
+/** A balance to track across dispatch, and optionally the change it must show. */
+export interface BalanceCheck {
+  account: SS58String;
+  asset: AssetRef;
+  expectedDelta?: bigint;
+}
+
+export function describeAsset(asset: AssetRef): string {
+  switch (asset.type) {
+    case 'native':
+      return 'native';
+    case 'assets':
+      return `Assets #${asset.id}`;
+    case 'foreign':
+      return `ForeignAssets ${formatLocation(asset.location)}`;
+  }
+}
+
+/** `describeAsset` plus the asset's symbol from its pallet metadata, when it has one. */
+export async function assetLabel(api: SubstrateApi, asset: AssetRef): Promise<string> {
+  let metadata: { symbol: { asText(): string } } | undefined;
+  if (asset.type === 'assets') {
+    metadata = await api.query.Assets?.Metadata.getValue(asset.id).catch(() => undefined);
+  } else if (asset.type === 'foreign') {
+    const query = api.query.ForeignAssets?.Metadata;
+    metadata = await query?.getValue(asset.location).catch(() => undefined);
+  }
+  const symbol = metadata?.symbol.asText();
+  return symbol ? `${describeAsset(asset)} (${symbol})` : describeAsset(asset);
+}
+
+/** Free balance of `account` in `asset` (0 for an account that holds none). */
+export async function readBalance(
+  api: SubstrateApi,
+  asset: AssetRef,
+  account: SS58String
+): Promise<bigint> {
+  switch (asset.type) {
+    case 'native': {
+      const info = await api.query.System.Account.getValue(account);
+      return BigInt(info?.data.free ?? 0n);
+    }
+    case 'assets': {
+      if (!api.query.Assets) {
+        throw new Error(`Chain has no Assets pallet to read ${describeAsset(asset)}`);
+      }
+      const holding = await api.query.Assets.Account.getValue([asset.id, account]);
+      return BigInt(holding?.balance ?? 0n);
+    }
+    case 'foreign': {
+      if (!api.query.ForeignAssets) {
+        throw new Error(`Chain has no ForeignAssets pallet to read ${describeAsset(asset)}`);
+      }
+      const holding = await api.query.ForeignAssets.Account.getValue([asset.location, account]);
+      return BigInt(holding?.balance ?? 0n);
+    }
+  }
+}
+
+function parseAsset(value: string): AssetRef {
+  if (value === 'native') return { type: 'native' };
+  const assetId = value.match(/^assets:(\d+)$/);
+  if (assetId) return { type: 'assets', id: Number(assetId[1]) };
+  if (value.startsWith('foreign:')) {
+    return { type: 'foreign', location: parseLocation(value.slice('foreign:'.length)) };
+  }
+  throw new Error(`unknown asset "${value}" (expected native, assets:<id> or foreign:<location>)`);
+}
+
+/**
+ * Parse `--track-balance`: comma-separated `account@asset[=delta]` entries, e.g.
+ * `15oF...@assets:1984=+1000000,15oF...@foreign:../Parachain(2030)/GeneralIndex(0)`.
+ */
+export function parseBalanceChecks(value?: string): BalanceCheck[] {
+  if (!value) return [];
+  return value.split(',').map((entry) => {
+    const match = entry.trim().match(/^([^@]+)@([^=]+)(?:=([+-]?\d+))?$/);
+    if (!match) {
+      throw new CodedError(
+        ErrorCode.InvalidBalanceCheck,
+        `Invalid --track-balance entry "${entry}": expected account@asset[=delta]`
+      );
+    }
+    const [, account, asset, delta] = match;
+    try {
+      AccountId().enc(account);
+    } catch {
+      throw new CodedError(
+        ErrorCode.InvalidBalanceCheck,
+        `Invalid --track-balance entry "${entry}": "${account}" is not an SS58 address`
+      );
+    }
+    try {
+      return {
+        account,
+        asset: parseAsset(asset),
+        expectedDelta: delta === undefined ? undefined : BigInt(delta),
+      };
+    } catch (error) {
+      throw new CodedError(
+        ErrorCode.InvalidBalanceCheck,
+        `Invalid --track-balance entry "${entry}": ${(error as Error).message}`
+      );
+    }
+  });
+}
+
+/**
+ * Records balances before a state change and reports the deltas after it.
+ * Each (asset, account) pair is snapshotted once; later snapshots are ignored.
+ */
+export class BalanceTracker {
+  private api: SubstrateApi;
+  private before = new Map<string, { asset: AssetRef; account: SS58String; balance: bigint }>();
+
+  constructor(api: SubstrateApi) {
+    this.api = api;
+  }
+
+  async snapshot(asset: AssetRef, account: SS58String): Promise<void> {
+    const key = `${describeAsset(asset)}/${account}`;
+    if (this.before.has(key)) return;
+    this.before.set(key, { asset, account, balance: await readBalance(this.api, asset, account) });
+  }
+
+  async deltas(): Promise<BalanceDelta[]> {
+    const deltas: BalanceDelta[] = [];
+    for (const { asset, account, balance: before } of this.before.values()) {
+      const after = await readBalance(this.api, asset, account);
+      deltas.push({ asset, account, before, after, delta: after - before });
+    }
+    return deltas;
+  }
+}
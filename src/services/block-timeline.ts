@@ -0,0 +1,127 @@
+import type { SubstrateApi } from '../types/substrate-api';
+import { type ParsedEvent, parseBlockEvent } from '../utils/event-serializer';
+import type { Logger } from '../utils/logger';
+import { createApiForChain, createPolkadotClient } from './chain-registry';
+import type { ChopsticksManager } from './chopsticks-manager';
+
+/** A block built on one fork during the run. */
+export interface TimelineBlock {
+  chain: string;
+  number: number;
+  /** Relay parent of a parachain block; unset for relay chain blocks */
+  relayParent?: number;
+  /** XCM send/receive events in the block, as `Section.Method` */
+  messages: string[];
+}
+
+/** Blocks sharing one relay chain height: the relay block and its children. */
+export interface TimelineRow {
+  relayNumber: number;
+  blocks: TimelineBlock[];
+}
+
+const XCM_SECTIONS = new Set(['XcmPallet', 'PolkadotXcm', 'XcmpQueue', 'DmpQueue', 'MessageQueue']);
+const XCM_PARACHAIN_SYSTEM_EVENTS = new Set([
+  'DownwardMessagesReceived',
+  'DownwardMessagesProcessed',
+  'UpwardMessageSent',
+]);
+
+function isXcmEvent({ section, method }: ParsedEvent): boolean {
+  return (
+    XCM_SECTIONS.has(section) ||
+    (section === 'ParachainSystem' && XCM_PARACHAIN_SYSTEM_EVENTS.has(method))
+  );
+}
+
+/**
+ * Group blocks by relay height: a relay block by its own number, a parachain
+ * block by its relay parent. Within a row the relay block comes first.
+ */
+export function alignTimeline(blocks: TimelineBlock[]): TimelineRow[] {
+  const rows = new Map<number, TimelineBlock[]>();
+  for (const block of blocks) {
+    const relayNumber = block.relayParent ?? block.number;
+    rows.set(relayNumber, [...(rows.get(relayNumber) ?? []), block]);
+  }
+  return Array.from(rows, ([relayNumber, rowBlocks]) => ({
+    relayNumber,
+    blocks: rowBlocks.sort(
+      (a, b) =>
+        Number(a.relayParent !== undefined) - Number(b.relayParent !== undefined) ||
+        a.chain.localeCompare(b.chain) ||
+        a.number - b.number
+    ),
+  })).sort((a, b) => a.relayNumber - b.relayNumber);
+}
+
+/**
+ * Aligns the blocks built on several forks into one timeline keyed by relay
+ * chain height, so it is visible which relay block carried which message to
+ * which parachain block.
+ */
+export class BlockTimeline {
+  private logger: Logger;
+
+  constructor(logger: Logger) {
+    this.logger = logger;
+  }
+
+  /** Read relay parents and XCM events of every block the forks built. */
+  async collect(
+    chains: Array<{ label: string; manager: ChopsticksManager }>
+  ): Promise<TimelineBlock[]> {
+    const blocks: TimelineBlock[] = [];
+    for (const { label, manager } of chains) {
+      const client = createPolkadotClient(manager.getContext().ws.endpoint);
+      try {
+        const api = createApiForChain(client);
+        for (const number of manager.getBuiltBlocks()) {
+          const hash: string = await client._request('chain_getBlockHash', [number]);
+          const events = await api.query.System.Events.getValue({ at: hash });
+          blocks.push({
+            chain: label,
+            number,
+            relayParent: await this.relayParentAt(api, hash),
+            messages: (events ?? [])
+              .map(parseBlockEvent)
+              .filter(isXcmEvent)
+              .map((e) => `${e.section}.${e.method}`),
+          });
+        }
+      } catch (error) {
+        this.logger.warn(`Could not read block timeline of ${label}: ${(error as Error).message}`);
+      } finally {
+        client.destroy();
+      }
+    }
+    return blocks;
+  }
+
+  display(rows: TimelineRow[]): void {
+    this.logger.section('Block Timeline');
+    if (rows.length === 0) {
+      this.logger.info('No blocks were built');
+      return;
+    }
+    for (const row of rows) {
+      this.logger.info(`Relay #${row.relayNumber}`);
+      for (const block of row.blocks) {
+        const messages = block.messages.length > 0 ? `: ${block.messages.join(', ')}` : '';
+        this.logger.info(`  ${block.chain} #${block.number}${messages}`);
+      }
+    }
+  }
+
+  private async relayParentAt(api: SubstrateApi, hash: string): Promise<number | undefined> {
+    try {
+      const relayParent = await api.query.ParachainSystem?.LastRelayChainBlockNumber.getValue({
+        at: hash,
+      });
+      return relayParent === undefined ? undefined : Number(relayParent);
+    } catch {
+      // Relay chains have no ParachainSystem pallet
+      return undefined;
+    }
+  }
+}
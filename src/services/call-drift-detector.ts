@@ -0,0 +1,163 @@
+import { decAnyMetadata, unifyMetadata } from '@polkadot-api/substrate-bindings';
+import type { Logger } from '../utils/logger';
+
+/** JSON-RPC request against the forked chain (`PolkadotClient._request`). */
+export type RpcRequest = (method: string, params: unknown[]) => Promise<any>;
+
+/** The subset of (unified) metadata needed to name a call from its indices. */
+export interface CallMetadata {
+  lookup: { id: number; def: { tag: string; value: unknown } }[];
+  pallets: { name: string; index: number; calls?: number | { type: number } }[];
+}
+
+/** What the call's leading `[pallet, call]` bytes mean under one runtime. */
+export interface RuntimeCallView {
+  specVersion: number;
+  /** Last block of that runtime; unset for the current one */
+  block?: number;
+  /** `Pallet.call`, or unset if the runtime has no call at these indices */
+  call?: string;
+}
+
+export interface CallDriftReport {
+  /** The two index bytes, e.g. `0x2800` */
+  indices: string;
+  current: RuntimeCallView;
+  previous?: RuntimeCallView;
+}
+
+/** How far back to look for the previous runtime. */
+const PREVIOUS_RUNTIME_WINDOW = 1_000_000;
+
+/** Name the call at `[palletIndex, callIndex]`, or `undefined` if there is none. */
+export function callNameAt(
+  metadata: CallMetadata,
+  palletIndex: number,
+  callIndex: number
+): string | undefined {
+  const pallet = metadata.pallets.find((p) => p.index === palletIndex);
+  const callsType = typeof pallet?.calls === 'number' ? pallet.calls : pallet?.calls?.type;
+  if (!pallet || callsType === undefined) return undefined;
+  const def = metadata.lookup.find((t) => t.id === callsType)?.def;
+  if (def?.tag !== 'variant') return undefined;
+  const variant = (def.value as { name: string; index: number }[]).find(
+    (v) => v.index === callIndex
+  );
+  return variant ? `${pallet.name}.${variant.name}` : undefined;
+}
+
+function decodeMetadata(hex: string): CallMetadata {
+  return unifyMetadata(decAnyMetadata(hex)) as unknown as CallMetadata;
+}
+
+/** Render a report as the warning shown to the user. */
+export function formatCallDrift(report: CallDriftReport): string {
+  const name = (view: RuntimeCallView) => view.call ?? '<no such call>';
+  const lines = [
+    'The call data may have been encoded against an older runtime: pallet/call indices may have shifted.',
+    `Index bytes ${report.indices} decode as:`,
+    `  current runtime (spec ${report.current.specVersion}): ${name(report.current)}`,
+  ];
+  if (report.previous) {
+    lines.push(
+      `  previous runtime (spec ${report.previous.specVersion}, until block #${report.previous.block}): ${name(report.previous)}`
+    );
+  } else {
+    lines.push(`  previous runtime: none within the last ${PREVIOUS_RUNTIME_WINDOW} blocks`);
+  }
+  return lines.join('\n');
+}
+
+/**
+ * Explains call data that does not fit the fork's runtime by decoding its
+ * pallet/call index bytes under both the current metadata and the metadata of
+ * the runtime before the last upgrade. Historical blocks are served by
+ * Chopsticks from the upstream chain, so only the fork's RPC is needed.
+ */
+export class CallDriftDetector {
+  private logger: Logger;
+  private request: RpcRequest;
+
+  constructor(logger: Logger, request: RpcRequest) {
+    this.logger = logger;
+    this.request = request;
+  }
+
+  /**
+   * Report drift if the index bytes name a different call (or none) under the
+   * current runtime than under the previous one. `undefined` when they agree,
+   * when no upgrade is in range and the current runtime knows the call, or when
+   * the RPCs fail.
+   */
+  async explain(callHex: string): Promise<CallDriftReport | undefined> {
+    const bytes = callHex.replace(/^0x/, '');
+    if (bytes.length < 4) return undefined;
+    const palletIndex = parseInt(bytes.slice(0, 2), 16);
+    const callIndex = parseInt(bytes.slice(2, 4), 16);
+
+    try {
+      const header = await this.request('chain_getHeader', []);
+      const head = parseInt(header.number, 16);
+      const currentSpec = await this.specVersionAt(await this.blockHash(head));
+      const current: RuntimeCallView = {
+        specVersion: currentSpec,
+        call: callNameAt(
+          decodeMetadata(await this.request('state_getMetadata', [])),
+          palletIndex,
+          callIndex
+        ),
+      };
+
+      const previousBlock = await this.findPreviousRuntimeBlock(head, currentSpec);
+      let previous: RuntimeCallView | undefined;
+      if (previousBlock !== undefined) {
+        const hash = await this.blockHash(previousBlock);
+        previous = {
+          specVersion: await this.specVersionAt(hash),
+          block: previousBlock,
+          call: callNameAt(
+            decodeMetadata(await this.request('state_getMetadata', [hash])),
+            palletIndex,
+            callIndex
+          ),
+        };
+      }
+
+      if (previous && previous.call === current.call) return undefined;
+      if (!previous && current.call) return undefined;
+      return { indices: `0x${bytes.slice(0, 4)}`, current, previous };
+    } catch (error) {
+      this.logger.debug(`Call drift detection failed: ${(error as Error).message}`);
+      return undefined;
+    }
+  }
+
+  /**
+   * Binary-search the last block whose spec version is below `currentSpec`,
+   * within `PREVIOUS_RUNTIME_WINDOW` blocks of `head`.
+   */
+  async findPreviousRuntimeBlock(head: number, currentSpec: number): Promise<number | undefined> {
+    let lo = Math.max(0, head - PREVIOUS_RUNTIME_WINDOW);
+    let hi = head;
+    if ((await this.specVersionAt(await this.blockHash(lo))) >= currentSpec) return undefined;
+
+    while (hi - lo > 1) {
+      const mid = Math.floor((lo + hi) / 2);
+      if ((await this.specVersionAt(await this.blockHash(mid))) < currentSpec) {
+        lo = mid;
+      } else {
+        hi = mid;
+      }
+    }
+    return lo;
+  }
+
+  private blockHash(number: number): Promise<string> {
+    return this.request('chain_getBlockHash', [number]);
+  }
+
+  private async specVersionAt(hash: string): Promise<number> {
+    const version = await this.request('state_getRuntimeVersion', [hash]);
+    return Number(version.specVersion);
+  }
+}
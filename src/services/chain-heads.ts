@@ -0,0 +1,42 @@
+import type { ChainHeadReport, HeadRef, Logger } from '../utils/logger';
+import type { ChopsticksManager } from './chopsticks-manager';
+
+/**
+ * Where each fork started and where the run left it. Chains whose fork never
+ * came up are left out.
+ */
+export function collectChainHeads(
+  chains: Array<{ label: string; manager: ChopsticksManager }>
+): ChainHeadReport[] {
+  const heads: ChainHeadReport[] = [];
+  for (const { label, manager } of chains) {
+    const fork = manager.getForkHead();
+    const final = manager.getHead();
+    if (!fork || !final) continue;
+    heads.push({ chain: label, endpoint: manager.getContext().ws.endpoint, fork, final });
+  }
+  return heads;
+}
+
+function formatHead(head: HeadRef): string {
+  return head.hash ? `#${head.number} (${head.hash})` : `#${head.number}`;
+}
+
+/**
+ * Show every fork's start and final head, so the forks left running (or a
+ * post-run inspection) can be queried at exactly the block the run ended on.
+ * Also emitted as an `@@heads` line with `--emit-blocks`.
+ */
+export function displayChainHeads(logger: Logger, heads: ChainHeadReport[]): void {
+  if (heads.length === 0) return;
+
+  logger.section('Final Heads');
+  const width = Math.max(...heads.map((head) => head.chain.length));
+  for (const head of heads) {
+    const built = head.final.number - head.fork.number;
+    logger.info(
+      `${head.chain.padEnd(width)}  forked at ${formatHead(head.fork)}, ended at ${formatHead(head.final)} (+${built} block(s))`
+    );
+  }
+  logger.heads(heads);
+}
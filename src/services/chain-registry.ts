@@ -3,6 +3,7 @@ import { createClient } from 'polkadot-api';
 import { withPolkadotSdkCompat } from 'polkadot-api/polkadot-sdk-compat';
 import { getWsProvider } from 'polkadot-api/ws-provider/node';
 import type { ReferendaPallet, SubstrateApi } from '../types/substrate-api';
+import type { NativeToken } from '../utils/token-format';
 
 export type ChainNetwork = 'polkadot' | 'kusama' | 'paseo' | 'westend' | 'rococo' | 'unknown';
 export type ChainKind = 'relay' | 'parachain';
@@ -14,23 +15,72 @@ export interface ChainInfo {
   network: ChainNetwork;
   kind: ChainKind;
   specName: string;
+  /** Native token, from the node's `system_properties` */
+  token?: NativeToken;
+}
+
+/** What the node reports about itself through `system_chain` and `system_properties`. */
+export interface ChainProperties {
+  name?: string;
+  token?: NativeToken;
+}
+
+type RpcRequest = (method: string, params: unknown[]) => Promise<unknown>;
+
+/**
+ * Read the chain name and native token from the node. Chains with several
+ * tokens list the native one first. Missing or failing RPCs leave fields unset.
+ */
+export async function getChainProperties(request: RpcRequest): Promise<ChainProperties> {
+  const [name, properties] = await Promise.all([
+    request('system_chain', []).catch(() => undefined),
+    request('system_properties', []).catch(() => undefined),
+  ]);
+  const { tokenSymbol, tokenDecimals } = (properties ?? {}) as Record<string, unknown>;
+  const symbol = Array.isArray(tokenSymbol) ? tokenSymbol[0] : tokenSymbol;
+  const decimals = Array.isArray(tokenDecimals) ? tokenDecimals[0] : tokenDecimals;
+
+  return {
+    name: typeof name === 'string' && name.length > 0 ? name : undefined,
+    token:
+      typeof symbol === 'string' && typeof decimals === 'number'
+        ? { symbol, decimals }
+        : undefined,
+  };
 }
 
 /**
- * Get chain information from runtime metadata.
- * Uses system.version.specName to accurately identify the chain.
+ * Get chain information from runtime metadata and the node's properties.
+ * Uses system.version.specName to accurately identify the chain, and labels
+ * it with the node's chain name. A user-given `label` (`name=` of the
+ * endpoint) replaces both.
  */
-export async function getChainInfo(api: SubstrateApi, endpoint: string): Promise<ChainInfo> {
-  const systemVersion = await api.constants.System.Version();
+export async function getChainInfo(
+  client: PolkadotClient,
+  endpoint: string,
+  label?: string
+): Promise<ChainInfo> {
+  const api = createApiForChain(client);
+  const [systemVersion, properties] = await Promise.all([
+    api.constants.System.Version(),
+    getChainProperties((method, params) => client._request(method, params)),
+  ]);
   const specName: string = systemVersion.spec_name || systemVersion.specName || 'unknown';
 
-  return buildChainInfoFromSpecName(specName, endpoint);
+  return {
+    ...buildChainInfoFromSpecName(specName, endpoint, label ?? properties.name),
+    token: properties.token,
+  };
 }
 
 /**
  * Build ChainInfo from specName
  */
-export function buildChainInfoFromSpecName(specName: string, endpoint: string): ChainInfo {
+export function buildChainInfoFromSpecName(
+  specName: string,
+  endpoint: string,
+  label?: string
+): ChainInfo {
   const lower = specName.toLowerCase();
 
   // Determine network from specName
@@ -54,11 +104,11 @@ export function buildChainInfoFromSpecName(specName: string, endpoint: string):
   const kind: ChainKind = isRelay ? 'relay' : 'parachain';
 
   // Create a clean label from specName
-  const label = specName.toLowerCase().replace(/_/g, '-');
+  const id = specName.toLowerCase().replace(/_/g, '-');
 
   return {
-    id: label,
-    label,
+    id,
+    label: label ?? id,
     endpoint,
     network,
     kind,
@@ -86,15 +136,21 @@ export function createApiForChain(client: PolkadotClient): SubstrateApi {
 }
 
 /**
- * Returns the referenda pallet name based on fellowship flag.
+ * Returns the referenda pallet name: `pallet` when a specific instance was
+ * requested (`--referendum <pallet>:<id>`), otherwise based on fellowship flag.
  */
-export function getReferendaPalletName(isFellowship: boolean): string {
-  return isFellowship ? 'FellowshipReferenda' : 'Referenda';
+export function getReferendaPalletName(isFellowship: boolean, pallet?: string): string {
+  return pallet ?? (isFellowship ? 'FellowshipReferenda' : 'Referenda');
 }
 
 /**
  * Get the referenda pallet query accessor from the API.
  */
-export function getReferendaPallet(api: SubstrateApi, isFellowship: boolean): ReferendaPallet {
-  return isFellowship ? api.query.FellowshipReferenda : api.query.Referenda;
+export function getReferendaPallet(
+  api: SubstrateApi,
+  isFellowship: boolean,
+  pallet?: string
+): ReferendaPallet {
+  const query = api.query as unknown as Record<string, ReferendaPallet>;
+  return query[getReferendaPalletName(isFellowship, pallet)];
 }
@@ -4,11 +4,11 @@ import type { PolkadotClient } from 'polkadot-api';
 import type { TestOptions } from '../types';
 import type { ParsedEndpoint } from '../utils/chain-endpoint-parser';
 import type { Logger } from '../utils/logger';
+import { PortAllocator, type PortRange } from '../utils/port-range';
 import { ALICE_ACCOUNT_INJECTION, FELLOWSHIP_STORAGE_INJECTION } from '../utils/storage-constants';
 import {
   type ChainInfo,
   type ChainNetwork,
-  createApiForChain,
   createPolkadotClient,
   getChainInfo,
 } from './chain-registry';
@@ -16,18 +16,33 @@ import {
 export interface TopologyConfig {
   governance?: string;
   governanceBlock?: number;
+  /** `name=` of the governance endpoint, shown instead of its spec name */
+  governanceLabel?: string;
   fellowship?: string;
   fellowshipBlock?: number;
+  /** `name=` of the fellowship endpoint, shown instead of its spec name */
+  fellowshipLabel?: string;
   additionalChains?: ParsedEndpoint[];
+  /** Chopsticks storage cache (SQLite file); defaults to `.chopsticks-db` in the cwd */
+  db?: string;
+  /** The run's work dir, where each fork's runtime is kept (see `WorkDir`) */
+  workDir?: string;
+  /** Ports to assign to Chopsticks instances (`--port-range`); Chopsticks picks them otherwise */
+  portRange?: PortRange;
 }
 
 export class ChainTopologyBuilder {
   private logger: Logger;
   private governanceEndpoint?: string;
   private governanceBlock?: number;
+  private governanceLabel?: string;
   private fellowshipEndpoint?: string;
   private fellowshipBlock?: number;
+  private fellowshipLabel?: string;
   private additionalChainEndpoints: ParsedEndpoint[];
+  private db: string;
+  private workDir?: string;
+  private ports?: PortAllocator;
 
   private _governanceChain?: ChainInfo;
   private _fellowshipChain?: ChainInfo;
@@ -37,9 +52,14 @@ export class ChainTopologyBuilder {
     this.logger = logger;
     this.governanceEndpoint = config.governance;
     this.governanceBlock = config.governanceBlock;
+    this.governanceLabel = config.governanceLabel;
     this.fellowshipEndpoint = config.fellowship;
     this.fellowshipBlock = config.fellowshipBlock;
+    this.fellowshipLabel = config.fellowshipLabel;
     this.additionalChainEndpoints = config.additionalChains || [];
+    this.db = config.db ? path.resolve(config.db) : path.join(process.cwd(), '.chopsticks-db');
+    this.workDir = config.workDir;
+    this.ports = config.portRange ? new PortAllocator(config.portRange) : undefined;
   }
 
   get governanceChain(): ChainInfo | undefined {
@@ -78,6 +98,32 @@ export class ChainTopologyBuilder {
     return this.fellowshipBlock;
   }
 
+  getGovernanceLabel(): string | undefined {
+    return this.governanceLabel;
+  }
+
+  getFellowshipLabel(): string | undefined {
+    return this.fellowshipLabel;
+  }
+
+  /**
+   * Chopsticks storage cache shared by every fork of this run. Remote storage is
+   * cached per block hash, so later forks of the same block read it locally.
+   */
+  getDbPath(): string {
+    return this.db;
+  }
+
+  /** The run's work dir, when it has one. */
+  getWorkDir(): string | undefined {
+    return this.workDir;
+  }
+
+  /** Next port of `--port-range`, or undefined to let Chopsticks choose. */
+  nextPort(): number | undefined {
+    return this.ports?.take();
+  }
+
   hasAdditionalChains(): boolean {
     return this.additionalChainEndpoints.length > 0;
   }
@@ -91,25 +137,31 @@ export class ChainTopologyBuilder {
 
       if (this.governanceEndpoint) {
         detectionTasks.push(
-          this.detectChainInfo(this.governanceEndpoint, clients).then((info) => {
-            this._governanceChain = info;
-          })
+          this.detectChainInfo(this.governanceEndpoint, clients, this.governanceLabel).then(
+            (info) => {
+              this._governanceChain = info;
+            }
+          )
         );
       }
 
       if (this.fellowshipEndpoint) {
         detectionTasks.push(
-          this.detectChainInfo(this.fellowshipEndpoint, clients).then((info) => {
-            this._fellowshipChain = info;
-          })
+          this.detectChainInfo(this.fellowshipEndpoint, clients, this.fellowshipLabel).then(
+            (info) => {
+              this._fellowshipChain = info;
+            }
+          )
         );
       }
 
       for (const additionalEndpoint of this.additionalChainEndpoints) {
         detectionTasks.push(
-          this.detectChainInfo(additionalEndpoint.url, clients).then((info) => {
-            this._additionalChains.push(info);
-          })
+          this.detectChainInfo(additionalEndpoint.url, clients, additionalEndpoint.label).then(
+            (info) => {
+              this._additionalChains.push(info);
+            }
+          )
         );
       }
 
@@ -133,19 +185,21 @@ export class ChainTopologyBuilder {
     }
   }
 
-  private async detectChainInfo(endpoint: string, clients: PolkadotClient[]): Promise<ChainInfo> {
+  private async detectChainInfo(
+    endpoint: string,
+    clients: PolkadotClient[],
+    label?: string
+  ): Promise<ChainInfo> {
     const client = createPolkadotClient(endpoint);
     clients.push(client);
-    const api = createApiForChain(client);
-    return getChainInfo(api, endpoint);
+    return getChainInfo(client, endpoint, label);
   }
 
   async detectRelayNetworkKey(endpoint: string): Promise<string | undefined> {
     let tempClient: PolkadotClient | undefined;
     try {
       tempClient = createPolkadotClient(endpoint);
-      const tempApi = createApiForChain(tempClient);
-      const chainInfo = await getChainInfo(tempApi, endpoint);
+      const chainInfo = await getChainInfo(tempClient, endpoint);
       if (chainInfo.kind === 'relay') {
         this.logger.debug(`Chain is a relay chain, using network key: ${chainInfo.network}`);
         return chainInfo.network;
@@ -277,7 +331,7 @@ export class ChainTopologyBuilder {
   ): Record<string, unknown> {
     const config: Record<string, unknown> = {
       endpoint,
-      db: path.join(process.cwd(), '.chopsticks-db'),
+      db: this.db,
       'build-block-mode': BuildBlockMode.Manual,
       'mock-signature-host': true,
       'allow-unresolved-imports': true,
@@ -288,6 +342,11 @@ export class ChainTopologyBuilder {
       config.block = block;
     }
 
+    const port = this.nextPort();
+    if (port !== undefined) {
+      config.port = port;
+    }
+
     if (storageInjection === 'fellowship') {
       config['import-storage'] = FELLOWSHIP_STORAGE_INJECTION;
       this.logger.debug('Injecting fellowship storage for Alice account');
@@ -1,15 +1,32 @@
 import type { Config } from '@acala-network/chopsticks/dist/esm/schema/index.js';
-import { BuildBlockMode } from '@acala-network/chopsticks-core';
+import { BuildBlockMode, pinoLogger } from '@acala-network/chopsticks-core';
 import { setupNetworks } from '@acala-network/chopsticks-testing';
+import * as fs from 'fs';
 import * as path from 'path';
 import type { ChopsticksConfig } from '../types';
 import type { SubstrateApi } from '../types/substrate-api';
+import { CodedError, ErrorCode } from '../utils/error-codes';
 import { stringify } from '../utils/json';
-import type { Logger } from '../utils/logger';
+import type { HeadRef, Logger } from '../utils/logger';
+import { shutdown } from './shutdown';
 
 const CHAIN_READY_MAX_ATTEMPTS = 10;
 const CHAIN_READY_DELAY_MS = 500;
 
+/**
+ * Start every Chopsticks log line with `[runId]`, as the tool's own lines do.
+ * Chopsticks logs through pino children of `pinoLogger`, which inherit its
+ * message prefix, so setting it on the root tags the forks' output too.
+ */
+export function tagChopsticksLogs(runId: string): void {
+  const msgPrefix = Object.getOwnPropertySymbols(pinoLogger).find(
+    (symbol) => symbol.description === 'pino.msgPrefix'
+  );
+  if (msgPrefix) {
+    (pinoLogger as unknown as Record<symbol, string>)[msgPrefix] = `[${runId}] `;
+  }
+}
+
 /** Minimal interface for the Chopsticks network context returned by setupNetworks */
 export interface ChopsticksContext {
   ws: { endpoint: string };
@@ -19,7 +36,10 @@ export interface ChopsticksContext {
     timeTravel(timestamp: string | number): Promise<unknown>;
     setHead?(hashOrNumber: string | number): Promise<unknown>;
   };
-  chain?: { port?: number; head?: { number: number } };
+  chain?: {
+    port?: number;
+    head?: { number: number; hash?: string; wasm?: Promise<string> };
+  };
   head?: { number: number };
   pause?(): Promise<unknown>;
   teardown?(): Promise<void>;
@@ -29,6 +49,9 @@ export interface ChopsticksContext {
 export class ChopsticksManager {
   private logger: Logger;
   private context: ChopsticksContext | null = null;
+  private builtBlocks: number[] = [];
+  private forkHead?: HeadRef;
+  private untrack?: () => void;
 
   constructor(logger: Logger) {
     this.logger = logger;
@@ -60,7 +83,7 @@ export class ChopsticksManager {
         'allow-unresolved-imports': config['allow-unresolved-imports'] !== false,
         'runtime-log-level': config['runtime-log-level'] ?? 0,
         ...(config.port && { port: config.port }),
-        ...(config.block && { block: config.block }),
+        ...(config.block !== undefined && { block: config.block }),
         ...(config['import-storage'] && { 'import-storage': config['import-storage'] }),
       } as Config;
 
@@ -75,19 +98,30 @@ export class ChopsticksManager {
       });
 
       this.context = networks[key] as unknown as ChopsticksContext;
+      this.forkHead = this.getHead();
+      this.trackForShutdown();
 
       const endpoint = this.context.ws.endpoint;
       this.logger.succeedSpinner(`Chopsticks started at ${endpoint}`);
       this.logger.success(`Connected to: ${config.endpoint}`);
 
-      if (config.block) {
+      // Everything the simulation does derives from this block, so name it
+      // even when the latest one was picked: `url,<block>` reproduces the run.
+      const forkHead = this.context.chain?.head;
+      if (config.block !== undefined) {
         this.logger.info(`Forked from block: ${config.block}`);
+      } else if (forkHead) {
+        this.logger.info(
+          `Forked from latest block: ${forkHead.number} (pass ${config.endpoint},${forkHead.number} to reproduce)`
+        );
       }
 
       return this.context;
     } catch (error) {
       this.logger.failSpinner('Failed to start Chopsticks');
-      throw new Error('Failed to start Chopsticks', { cause: error });
+      throw new CodedError(ErrorCode.ChopsticksFailed, 'Failed to start Chopsticks', {
+        cause: error,
+      });
     }
   }
 
@@ -124,6 +158,16 @@ export class ChopsticksManager {
         await new Promise((r) => setTimeout(r, pollInterval));
       }
     }
+
+    const head = this.context.chain?.head;
+    if (head) {
+      this.builtBlocks.push(head.number);
+      this.logger.block({
+        endpoint: this.context.ws.endpoint,
+        number: head.number,
+        hash: head.hash,
+      });
+    }
   }
 
   async setStorage(module: string, item: string, key: unknown, value: unknown): Promise<void> {
@@ -174,7 +218,10 @@ export class ChopsticksManager {
         return;
       } catch (error) {
         if (attempt === maxAttempts) {
-          throw new Error(`Chain not ready after ${maxAttempts} attempts: ${error}`);
+          throw new CodedError(
+            ErrorCode.ChopsticksFailed,
+            `Chain not ready after ${maxAttempts} attempts: ${error}`
+          );
         }
 
         this.logger.debug(`Chain not ready yet (attempt ${attempt}/${maxAttempts}), retrying...`);
@@ -183,6 +230,33 @@ export class ChopsticksManager {
     }
   }
 
+  /** Numbers of the blocks built through {@link newBlock}, in order. */
+  getBuiltBlocks(): number[] {
+    return [...this.builtBlocks];
+  }
+
+  /** The block the fork started from, once it is set up. */
+  getForkHead(): HeadRef | undefined {
+    return this.forkHead;
+  }
+
+  /** The fork's current head, once it is set up. */
+  getHead(): HeadRef | undefined {
+    const head = this.context?.chain?.head;
+    return head ? { number: head.number, hash: head.hash } : undefined;
+  }
+
+  /**
+   * Write the runtime of the fork's head to `file`, as Chopsticks fetched it
+   * (or as the run upgraded it). Returns whether the fork had one to write.
+   */
+  async saveRuntime(file: string): Promise<boolean> {
+    const wasm = await this.context?.chain?.head?.wasm;
+    if (!wasm) return false;
+    fs.writeFileSync(file, Buffer.from(wasm.replace(/^0x/, ''), 'hex'));
+    return true;
+  }
+
   getContext(): ChopsticksContext {
     if (!this.context) {
       throw new Error('Chopsticks context not initialized');
@@ -192,6 +266,16 @@ export class ChopsticksManager {
 
   setContext(context: ChopsticksContext): void {
     this.context = context;
+    this.forkHead = this.getHead();
+    this.trackForShutdown();
+  }
+
+  /** Tear the fork down when the run is interrupted, also while it is paused. */
+  private trackForShutdown(): void {
+    this.untrack?.();
+    this.untrack = shutdown.register(this.context?.ws?.endpoint ?? 'Chopsticks', () =>
+      this.cleanup()
+    );
   }
 
   async pause(): Promise<void> {
@@ -209,6 +293,8 @@ export class ChopsticksManager {
   }
 
   async cleanup(): Promise<void> {
+    this.untrack?.();
+    this.untrack = undefined;
     if (this.context) {
       this.logger.debug('Cleaning up Chopsticks context...');
       if (this.context.teardown) {
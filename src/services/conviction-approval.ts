@@ -0,0 +1,221 @@
+import { Enum, type SS58String } from 'polkadot-api';
+import type { ConvictionLock, SyntheticVote } from '../types';
+import type { ReferendaPallet, SubstrateApi, UnsafeTransaction } from '../types/substrate-api';
+import { formatDispatchError } from '../utils/dispatch-result';
+import { CodedError, ErrorCode } from '../utils/error-codes';
+import { getBlockEvents } from '../utils/event-serializer';
+import type { Logger } from '../utils/logger';
+import { ALICE_ADDRESS } from '../utils/storage-constants';
+import type { ChopsticksManager } from './chopsticks-manager';
+import { mockSigner } from './delegated-submission';
+
+/**
+ * `--vote-conviction`: the conviction of the synthetic aye. Each level doubles
+ * the lock period of the one before (`Locked1x` locks for one
+ * `VoteLockingPeriod`); `None` counts a tenth of the balance and never locks
+ * past the poll.
+ */
+export const CONVICTIONS = [
+  'None',
+  'Locked1x',
+  'Locked2x',
+  'Locked3x',
+  'Locked4x',
+  'Locked5x',
+  'Locked6x',
+] as const;
+
+export type Conviction = (typeof CONVICTIONS)[number];
+
+export function parseConviction(value?: string): Conviction | undefined {
+  if (value === undefined) return undefined;
+  const conviction = CONVICTIONS.find((name) => name.toLowerCase() === value.trim().toLowerCase());
+  if (!conviction) {
+    throw new CodedError(
+      ErrorCode.InvalidConviction,
+      `Invalid --vote-conviction value: ${value} (expected one of: ${CONVICTIONS.join(', ')})`
+    );
+  }
+  return conviction;
+}
+
+/** `VoteLockingPeriod`s a winning vote of `conviction` stays locked for. */
+export function lockPeriods(conviction: Conviction): number {
+  const level = CONVICTIONS.indexOf(conviction);
+  return level === 0 ? 0 : 2 ** (level - 1);
+}
+
+/** Votes a balance counts for: a tenth without conviction, else balance times the level. */
+export function convictionVotes(conviction: Conviction, balance: bigint): bigint {
+  const level = CONVICTIONS.indexOf(conviction);
+  return level === 0 ? balance / 10n : balance * BigInt(level);
+}
+
+/**
+ * Approves a conviction-voting referendum the way a voter would: Alice is
+ * funded with the whole issuance and casts an aye of it with
+ * `ConvictionVoting.vote`, so the runtime computes the tally (and its
+ * support) instead of it being written by hand. After dispatch, her vote is
+ * removed and her class unlocked, to check the lock a real voter is left with
+ * runs until the referendum's end plus the conviction's lock periods.
+ */
+export class ConvictionApprover {
+  private logger: Logger;
+  private chopsticks: ChopsticksManager;
+  private api: SubstrateApi;
+  private referenda: ReferendaPallet;
+  private conviction: Conviction;
+  private vote?: { referendumId: number; track: number; balance: bigint };
+
+  constructor(
+    logger: Logger,
+    chopsticks: ChopsticksManager,
+    api: SubstrateApi,
+    referenda: ReferendaPallet,
+    conviction: Conviction
+  ) {
+    this.logger = logger;
+    this.chopsticks = chopsticks;
+    this.api = api;
+    this.referenda = referenda;
+    this.conviction = conviction;
+  }
+
+  /** Fund Alice with `balance` and forget her earlier votes on `track`. */
+  async injectVoter(track: number, balance: bigint): Promise<void> {
+    await this.chopsticks.setStorageBatch({
+      System: {
+        Account: [[[ALICE_ADDRESS], { providers: 1, data: { free: balance.toString() } }]],
+      },
+      ConvictionVoting: {
+        VotingFor: [[[ALICE_ADDRESS, track], null]],
+        ClassLocksFor: [[[ALICE_ADDRESS], []]],
+      },
+    });
+  }
+
+  /** Vote aye on `referendumId` with `balance` at the conviction and report the vote. */
+  async approve(referendumId: number, track: number, balance: bigint): Promise<SyntheticVote[]> {
+    const tx = this.api.tx.ConvictionVoting;
+    if (!tx) {
+      throw new CodedError(
+        ErrorCode.ReferendumNotApproved,
+        `Cannot vote on referendum #${referendumId}: chain has no ConvictionVoting.vote`
+      );
+    }
+
+    this.logger.startSpinner(`Voting aye with conviction ${this.conviction}...`);
+    const aye = 0x80 | CONVICTIONS.indexOf(this.conviction);
+    await this.submit(
+      tx.vote({ poll_index: referendumId, vote: Enum('Standard', { vote: aye, balance }) }),
+      `ConvictionVoting.vote on referendum #${referendumId}`,
+      ErrorCode.ReferendumNotApproved
+    );
+    this.vote = { referendumId, track, balance };
+
+    const votes = convictionVotes(this.conviction, balance);
+    this.logger.succeedSpinner('Synthetic vote cast');
+    this.logger.info(
+      `\u2713 ${ALICE_ADDRESS} voted aye with ${balance} at ${this.conviction} (${votes} vote(s))`
+    );
+    return [{ who: ALICE_ADDRESS, conviction: this.conviction, balance, votes }];
+  }
+
+  /**
+   * After dispatch: read the class lock of the vote, remove the vote and
+   * unlock the class, and check when the lock can be lifted. `undefined` if
+   * no vote was cast.
+   */
+  async checkLock(): Promise<ConvictionLock | undefined> {
+    const tx = this.api.tx.ConvictionVoting;
+    const query = this.api.query.ConvictionVoting;
+    if (!this.vote || !tx || !query) return undefined;
+    const { referendumId, track, balance } = this.vote;
+
+    this.logger.section('Conviction Lock');
+    const classLock = await this.classLock(ALICE_ADDRESS, track);
+    const info = await this.referenda.ReferendumInfoFor.getValue(referendumId);
+    const endedAt = info?.type === 'Approved' ? info.value[0] : undefined;
+    const lockingPeriod = (await this.api.constants.ConvictionVoting?.VoteLockingPeriod()) ?? 0;
+    const periods = lockPeriods(this.conviction);
+
+    await this.submit(
+      tx.remove_vote({ class: track, index: referendumId }),
+      `ConvictionVoting.remove_vote on referendum #${referendumId}`,
+      ErrorCode.ConvictionLockFailed
+    );
+    const voting = await query.VotingFor.getValue([ALICE_ADDRESS, track]);
+    const unlockAt = voting?.type === 'Casting' ? voting.value.prior[0] : 0;
+    await this.submit(
+      tx.unlock({ class: track, target: Enum('Id', ALICE_ADDRESS) }),
+      `ConvictionVoting.unlock of track ${track}`,
+      ErrorCode.ConvictionLockFailed
+    );
+    const lockedAfterUnlock = await this.classLock(ALICE_ADDRESS, track);
+
+    const lock: ConvictionLock = {
+      who: ALICE_ADDRESS,
+      track,
+      conviction: this.conviction,
+      balance,
+      classLock,
+      unlockAt,
+      expectedUnlockAt:
+        periods > 0 && endedAt !== undefined ? endedAt + periods * lockingPeriod : 0,
+      lockedAfterUnlock,
+    };
+    const errors = convictionLockErrors(lock);
+    if (errors.length > 0) lock.errors = errors;
+
+    this.logger.info(`Voter: ${lock.who} (track ${track}, ${this.conviction})`);
+    this.logger.info(`  class lock after dispatch: ${classLock} (voted ${balance})`);
+    this.logger.info(
+      lock.unlockAt > 0
+        ? `  unlocks at block #${lock.unlockAt}: referendum ended at #${endedAt}, plus ${periods} x ${lockingPeriod} blocks`
+        : '  unlocks as soon as the vote is removed'
+    );
+    this.logger.info(`  class lock after unlock now: ${lockedAfterUnlock}`);
+    for (const error of errors) {
+      this.logger.error(`  \u2717 ${error}`);
+    }
+    if (errors.length === 0) {
+      this.logger.success('  \u2713 Conviction lock matches the vote');
+    }
+    return lock;
+  }
+
+  private async classLock(who: SS58String, track: number): Promise<bigint> {
+    const locks = (await this.api.query.ConvictionVoting?.ClassLocksFor.getValue(who)) ?? [];
+    return locks.find(([id]) => id === track)?.[1] ?? 0n;
+  }
+
+  /** Sign `call` as Alice, include it in a new block and throw `code` if it failed. */
+  private async submit(call: UnsafeTransaction, label: string, code: ErrorCode): Promise<void> {
+    const signed = await call.sign(mockSigner(ALICE_ADDRESS));
+    await this.chopsticks.newBlock({ transactions: [signed] });
+    const events = await getBlockEvents(this.api.query.System.Events, this.logger);
+    const failed = events.find((e) => e.section === 'System' && e.method === 'ExtrinsicFailed');
+    if (failed) {
+      this.logger.failSpinner(`${label} failed`);
+      throw new CodedError(code, `${label} failed: ${formatDispatchError(failed.data)}`);
+    }
+  }
+}
+
+/** Where the lock differs from what the vote and its conviction should leave. */
+export function convictionLockErrors(lock: ConvictionLock): string[] {
+  const errors: string[] = [];
+  if (lock.classLock !== lock.balance) {
+    errors.push(`class lock is ${lock.classLock} after dispatch, expected ${lock.balance}`);
+  }
+  if (lock.unlockAt !== lock.expectedUnlockAt) {
+    errors.push(`vote unlocks at block #${lock.unlockAt}, expected #${lock.expectedUnlockAt}`);
+  }
+  const stillLocked = lock.expectedUnlockAt > 0 ? lock.balance : 0n;
+  if (lock.lockedAfterUnlock !== stillLocked) {
+    errors.push(
+      `class lock is ${lock.lockedAfterUnlock} after unlocking right away, expected ${stillLocked}`
+    );
+  }
+  return errors;
+}
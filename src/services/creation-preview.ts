@@ -0,0 +1,418 @@
+import { getDynamicBuilder, getLookupFn } from '@polkadot-api/metadata-builders';
+import {
+  Binary,
+  Blake2256,
+  decAnyMetadata,
+  unifyMetadata,
+} from '@polkadot-api/substrate-bindings';
+import type { TestOptions } from '../types';
+import type { SubstrateApi, TrackInfo } from '../types/substrate-api';
+import type { ParsedEndpoint } from '../utils/chain-endpoint-parser';
+import { CodedError, ErrorCode } from '../utils/error-codes';
+import { serializeEventData } from '../utils/event-serializer';
+import { stringify } from '../utils/json';
+import type { Logger } from '../utils/logger';
+import { formatTokenAmount, type NativeToken } from '../utils/token-format';
+import type { RpcRequest } from './call-drift-detector';
+import {
+  createApiForChain,
+  createPolkadotClient,
+  getChainProperties,
+  getReferendaPalletName,
+} from './chain-registry';
+import { boundWarnings } from './proposal-footprint';
+import { ReferendumCreator } from './referendum-creator';
+import { trackName } from './track-changes';
+
+/** A call decoded against the chain's runtime. */
+export interface DecodedCall {
+  /** `Pallet.call`, e.g. `Referenda.submit` */
+  name: string;
+  args: unknown;
+}
+
+/**
+ * `--preview`: what `--call-to-create-*-referendum` would submit, decoded
+ * without forking or building a block.
+ */
+export interface CreationPreview {
+  submit: DecodedCall;
+  /** `Pallet.Variant` of `proposal_origin`, e.g. `Origins.WhitelistedCaller` */
+  origin: string;
+  /** Track the runtime assigns the origin to, when it has one */
+  track?: { id: number; name: string; decisionDeposit?: bigint };
+  proposal: { type: string; hash?: string; len?: number };
+  /** The proposal call itself, when inline or given as the preimage */
+  proposalCall?: DecodedCall;
+  /** `After <n> blocks` or `At block <n>` */
+  enactment: string;
+  preimage?: DecodedCall & { hash: string; len: number };
+  submissionDeposit?: bigint;
+  /** Mismatches between the submit and preimage calls */
+  warnings: string[];
+}
+
+type Variant = { type: string; value?: unknown };
+
+/** `Origins.WhitelistedCaller`, `system.Root` */
+export function originName(origin: unknown): string {
+  const outer = origin as Variant;
+  const inner = outer?.value as Variant | undefined;
+  return inner?.type ? `${outer.type}.${inner.type}` : String(outer?.type ?? 'unknown');
+}
+
+/**
+ * Track id of each origin, per referenda pallet, as the Polkadot and Kusama
+ * runtimes' `TracksInfo::track_for` assigns them. The mapping lives in runtime
+ * code, not in metadata, so it is repeated here.
+ */
+export const ORIGIN_TRACKS: Record<string, Record<string, number>> = {
+  Referenda: {
+    'system.Root': 0,
+    'Origins.WhitelistedCaller': 1,
+    'Origins.WishForChange': 2,
+    'Origins.StakingAdmin': 10,
+    'Origins.Treasurer': 11,
+    'Origins.LeaseAdmin': 12,
+    'Origins.FellowshipAdmin': 13,
+    'Origins.GeneralAdmin': 14,
+    'Origins.AuctionAdmin': 15,
+    'Origins.ReferendumCanceller': 20,
+    'Origins.ReferendumKiller': 21,
+    'Origins.SmallTipper': 30,
+    'Origins.BigTipper': 31,
+    'Origins.SmallSpender': 32,
+    'Origins.MediumSpender': 33,
+    'Origins.BigSpender': 34,
+  },
+  FellowshipReferenda: {
+    'FellowshipOrigins.Members': 1,
+    'FellowshipOrigins.Fellowship1Dan': 1,
+    'FellowshipOrigins.Fellowship2Dan': 2,
+    'FellowshipOrigins.Fellows': 3,
+    'FellowshipOrigins.Fellowship3Dan': 3,
+    'FellowshipOrigins.Architects': 4,
+    'FellowshipOrigins.Fellowship4Dan': 4,
+    'FellowshipOrigins.Fellowship5Dan': 5,
+    'FellowshipOrigins.Fellowship6Dan': 6,
+    'FellowshipOrigins.Masters': 7,
+    'FellowshipOrigins.Fellowship7Dan': 7,
+    'FellowshipOrigins.Fellowship8Dan': 8,
+    'FellowshipOrigins.Fellowship9Dan': 9,
+    'FellowshipOrigins.RetainAt1Dan': 11,
+    'FellowshipOrigins.RetainAt2Dan': 12,
+    'FellowshipOrigins.RetainAt3Dan': 13,
+    'FellowshipOrigins.RetainAt4Dan': 14,
+    'FellowshipOrigins.RetainAt5Dan': 15,
+    'FellowshipOrigins.RetainAt6Dan': 16,
+    'FellowshipOrigins.PromoteTo1Dan': 21,
+    'FellowshipOrigins.PromoteTo2Dan': 22,
+    'FellowshipOrigins.PromoteTo3Dan': 23,
+    'FellowshipOrigins.PromoteTo4Dan': 24,
+    'FellowshipOrigins.PromoteTo5Dan': 25,
+    'FellowshipOrigins.PromoteTo6Dan': 26,
+    'FellowshipOrigins.FastPromoteTo1Dan': 31,
+    'FellowshipOrigins.FastPromoteTo2Dan': 32,
+    'FellowshipOrigins.FastPromoteTo3Dan': 33,
+  },
+};
+
+/**
+ * The track `pallet` assigns `origin` to: its id from `ORIGIN_TRACKS`, its name
+ * and decision deposit from the `Tracks` constant. `undefined` when the origin
+ * is not mapped or the runtime has no track with that id.
+ */
+export function trackForOrigin(
+  tracks: TrackInfo[],
+  origin: unknown,
+  pallet: string
+): { id: number; name: string; decisionDeposit?: bigint } | undefined {
+  const id = ORIGIN_TRACKS[pallet]?.[originName(origin)];
+  const track = tracks.find(([trackId]) => trackId === id);
+  if (!track) return undefined;
+  const deposit = track[1].decision_deposit;
+  return {
+    id: track[0],
+    name: trackName(track[1].name, track[0]),
+    decisionDeposit: typeof deposit === 'bigint' ? deposit : undefined,
+  };
+}
+
+/** What the preview reads from a runtime: the live API, or `runtimeAtBlock`. */
+export type PreviewRuntime = Pick<SubstrateApi, 'txFromCallData' | 'constants'>;
+
+/**
+ * Decode calls and constants with the metadata of block `number`, for a fork
+ * block whose runtime may differ from the live one.
+ */
+export async function runtimeAtBlock(
+  request: RpcRequest,
+  number: number
+): Promise<PreviewRuntime> {
+  const hash: string | null = await request('chain_getBlockHash', [number]);
+  if (!hash) {
+    throw new CodedError(ErrorCode.InvalidEndpoint, `Block #${number} is not on the chain`);
+  }
+  const metadata = unifyMetadata(decAnyMetadata(await request('state_getMetadata', [hash])));
+  const builder = getDynamicBuilder(getLookupFn(metadata));
+  const call = builder.buildDefinition(metadata.extrinsic.call);
+  const constants = Object.fromEntries(
+    metadata.pallets.map((pallet) => [
+      pallet.name,
+      Object.fromEntries(
+        pallet.constants.map((constant) => [
+          constant.name,
+          async () => builder.buildDefinition(constant.type).dec(constant.value),
+        ])
+      ),
+    ])
+  );
+  return {
+    txFromCallData: async (data: Binary) => ({ decodedCall: call.dec(data.asBytes()) }),
+    constants,
+  } as unknown as PreviewRuntime;
+}
+
+export function describeEnactment(moment: unknown): string {
+  const { type, value } = (moment ?? {}) as Variant;
+  if (type === 'After') return `After ${value} blocks`;
+  if (type === 'At') return `At block ${value}`;
+  return String(type ?? 'unknown');
+}
+
+async function decode(api: PreviewRuntime, hex: string, label: string): Promise<DecodedCall> {
+  try {
+    const { decodedCall } = await api.txFromCallData(Binary.fromHex(hex));
+    const call = decodedCall.value as Variant;
+    return {
+      name: `${decodedCall.type}.${call.type}`,
+      args: serializeEventData(call.value),
+    };
+  } catch (error) {
+    throw new CodedError(
+      ErrorCode.CallDecodeFailed,
+      `Invalid ${label} call data for this chain's runtime: ${(error as Error).message}`
+    );
+  }
+}
+
+/**
+ * Decode `submitHex` (a referenda `submit`) and `preimageHex` (a
+ * `Preimage.note_preimage`), and look up the track and deposits they imply.
+ */
+export async function buildCreationPreview(
+  api: PreviewRuntime,
+  submitHex: string,
+  preimageHex: string | undefined,
+  isFellowship: boolean
+): Promise<CreationPreview> {
+  const pallet = getReferendaPalletName(isFellowship);
+  const warnings: string[] = [];
+
+  const submit = await decode(
+    api,
+    ReferendumCreator.validateHex(submitHex, 'submitCall'),
+    `${pallet}.submit`
+  );
+  if (submit.name !== `${pallet}.submit`) {
+    warnings.push(`The creation call is ${submit.name}, not ${pallet}.submit`);
+  }
+  const { proposal_origin, proposal, enactment_moment } = (submit.args ?? {}) as Record<
+    string,
+    unknown
+  >;
+
+  // Args are serialized: bytes and hashes are hex strings
+  const { type: proposalType, value } = (proposal ?? {}) as Variant;
+  let proposalCall: DecodedCall | undefined;
+  let proposalInfo: CreationPreview['proposal'];
+  if (proposalType === 'Inline' && typeof value === 'string') {
+    proposalInfo = { type: 'Inline', len: (value.length - 2) / 2 };
+    proposalCall = await decode(api, value, 'inline proposal');
+  } else {
+    const lookup = value as { hash?: string; len?: number } | undefined;
+    proposalInfo = { type: String(proposalType), hash: lookup?.hash, len: lookup?.len };
+  }
+
+  let preimage: CreationPreview['preimage'];
+  if (preimageHex) {
+    const preimageCall = await decode(
+      api,
+      ReferendumCreator.validateHex(preimageHex, 'preimageCall'),
+      'preimage'
+    );
+    const bytesHex = (preimageCall.args as { bytes?: string } | undefined)?.bytes;
+    if (preimageCall.name !== 'Preimage.note_preimage' || typeof bytesHex !== 'string') {
+      warnings.push(`The preimage call is ${preimageCall.name}, not Preimage.note_preimage`);
+    } else {
+      const bytes = Binary.fromHex(bytesHex).asBytes();
+      preimage = {
+        ...preimageCall,
+        hash: Binary.fromBytes(Blake2256(bytes)).asHex(),
+        len: bytes.length,
+      };
+      proposalCall = await decode(api, bytesHex, 'proposal');
+      if (proposalInfo.type === 'Lookup' && proposalInfo.hash !== preimage.hash) {
+        warnings.push(
+          `The proposal's hash ${proposalInfo.hash} is not the preimage's ${preimage.hash}`
+        );
+      }
+      if (proposalInfo.type === 'Lookup' && proposalInfo.len !== preimage.len) {
+        warnings.push(
+          `The proposal's length ${proposalInfo.len} is not the preimage's ${preimage.len}`
+        );
+      }
+    }
+  } else if (proposalInfo.type === 'Lookup') {
+    warnings.push('No preimage call: the proposal must already be noted on chain');
+  }
+  warnings.push(...boundWarnings(String(proposalInfo.type), proposalInfo.len ?? preimage?.len));
+
+  const constants = api.constants as unknown as Record<
+    string,
+    { Tracks(): Promise<TrackInfo[]>; SubmissionDeposit?(): Promise<bigint> } | undefined
+  >;
+  const tracks = (await constants[pallet]?.Tracks().catch(() => undefined)) ?? [];
+  const track = trackForOrigin(tracks, proposal_origin, pallet);
+  if (!track) {
+    warnings.push(`Origin ${originName(proposal_origin)} has no ${pallet} track on this runtime`);
+  }
+
+  return {
+    submit,
+    origin: originName(proposal_origin),
+    track,
+    proposal: proposalInfo,
+    proposalCall,
+    enactment: describeEnactment(enactment_moment),
+    preimage,
+    submissionDeposit: await constants[pallet]?.SubmissionDeposit?.().catch(() => undefined),
+    warnings,
+  };
+}
+
+export function displayCreationPreview(
+  logger: Logger,
+  label: string,
+  preview: CreationPreview,
+  token?: NativeToken
+): void {
+  logger.section(`${label} Referendum Preview`);
+  const amount = (value?: bigint) =>
+    value === undefined ? 'unknown' : formatTokenAmount(value, token);
+  const { proposal, track } = preview;
+  logger.table({
+    'Submit call': preview.submit.name,
+    Origin: preview.origin,
+    Track: track ? `#${track.id} ${track.name}` : 'unknown',
+    Proposal:
+      proposal.type === 'Lookup'
+        ? `Lookup ${proposal.hash} (${proposal.len} bytes)`
+        : `${proposal.type} (${proposal.len ?? '?'} bytes)`,
+    Enactment: preview.enactment,
+    Preimage: preview.preimage
+      ? `${preview.preimage.hash} (${preview.preimage.len} bytes)`
+      : 'none',
+    'Submission deposit': amount(preview.submissionDeposit),
+    'Decision deposit': amount(track?.decisionDeposit),
+  });
+  logger.info(`${preview.submit.name}: ${stringify(preview.submit.args, 2)}`);
+  if (preview.proposalCall) {
+    logger.info(
+      `Proposal ${preview.proposalCall.name}: ${stringify(preview.proposalCall.args, 2)}`
+    );
+  }
+  for (const warning of preview.warnings) {
+    logger.warn(warning);
+  }
+}
+
+/** A `--call-to-create-*-referendum` of the run and the chain it targets. */
+export interface CreationTarget {
+  label: 'Fellowship' | 'Governance';
+  isFellowship: boolean;
+  endpoint: ParsedEndpoint;
+  submit: string;
+  preimage?: string;
+}
+
+export function creationTargets(
+  options: TestOptions,
+  endpoints: { governance?: ParsedEndpoint; fellowship?: ParsedEndpoint }
+): CreationTarget[] {
+  const targets = [
+    {
+      label: 'Fellowship' as const,
+      isFellowship: true,
+      endpoint: endpoints.fellowship,
+      submit: options.callToCreateFellowshipReferendum,
+      preimage: options.callToNotePreimageForFellowshipReferendum,
+    },
+    {
+      label: 'Governance' as const,
+      isFellowship: false,
+      endpoint: endpoints.governance,
+      submit: options.callToCreateGovernanceReferendum,
+      preimage: options.callToNotePreimageForGovernanceReferendum,
+    },
+  ];
+  return targets.filter((target): target is CreationTarget => !!target.submit && !!target.endpoint);
+}
+
+/**
+ * Run `use` against the target's live chain, with the chain's native token and
+ * its RPC for reads at older blocks.
+ */
+export async function withTargetApi<T>(
+  logger: Logger,
+  target: CreationTarget,
+  use: (api: SubstrateApi, request: RpcRequest) => Promise<T>
+): Promise<{ value: T; token?: NativeToken }> {
+  logger.startSpinner(`Connecting to ${target.endpoint.url}...`);
+  const client = createPolkadotClient(target.endpoint.url);
+  try {
+    const api = createApiForChain(client);
+    const request: RpcRequest = (method, params) => client._request(method, params);
+    const { token } = await getChainProperties(request);
+    logger.succeedSpinner(`Connected to ${target.endpoint.url}`);
+    return { value: await use(api, request), token };
+  } finally {
+    logger.stopSpinner();
+    client.destroy();
+  }
+}
+
+/**
+ * Decode a target's calls against the runtime of its fork block: the `,block`
+ * of the URL, or the live chain's current runtime without one.
+ */
+export async function previewTarget(
+  logger: Logger,
+  target: CreationTarget
+): Promise<{ preview: CreationPreview; token?: NativeToken }> {
+  const { block } = target.endpoint;
+  const { value, token } = await withTargetApi(logger, target, async (api, request) =>
+    buildCreationPreview(
+      block === undefined ? api : await runtimeAtBlock(request, block),
+      target.submit,
+      target.preimage,
+      target.isFellowship
+    )
+  );
+  return { preview: value, token };
+}
+
+/**
+ * Decode every `--call-to-create-*-referendum` against its chain's runtime at
+ * the fork block and show what it would create. No fork is started.
+ */
+export async function previewCreation(
+  logger: Logger,
+  options: TestOptions,
+  endpoints: { governance?: ParsedEndpoint; fellowship?: ParsedEndpoint }
+): Promise<void> {
+  for (const target of creationTargets(options, endpoints)) {
+    const { preview, token } = await previewTarget(logger, target);
+    displayCreationPreview(logger, target.label, preview, token);
+  }
+}
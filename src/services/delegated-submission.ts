@@ -0,0 +1,519 @@
+import { Binary, Blake2256, compact, u16 } from '@polkadot-api/substrate-bindings';
+import { AccountId, Enum, type PolkadotSigner, type SS58String } from 'polkadot-api';
+import { getPolkadotSigner } from 'polkadot-api/signer';
+import type {
+  DecodedTransaction,
+  MultisigTimepoint,
+  SubstrateApi,
+  UnsafeTransaction,
+} from '../types/substrate-api';
+import { isAddress } from '../utils/address';
+import { CodedError, ErrorCode } from '../utils/error-codes';
+import { expectEvent } from '../utils/event-serializer';
+import type { Logger } from '../utils/logger';
+import { ALICE_ADDRESS } from '../utils/storage-constants';
+import type { ChopsticksManager } from './chopsticks-manager';
+
+/**
+ * Submit the referendum as `real`, with Alice acting as its proxy. With a
+ * `delay`, Alice announces the call first and executes it once the delay has
+ * passed, as a time-delayed proxy has to.
+ */
+export interface ProxySubmission {
+  kind: 'proxy';
+  real: SS58String;
+  proxyType: string;
+  delay: number;
+}
+
+/**
+ * Submit the referendum from the multisig account of `signatories`: the first
+ * `threshold - 1` signatories approve the call hash, the last one executes it.
+ */
+export interface MultisigSubmission {
+  kind: 'multisig';
+  threshold: number;
+  signatories: SS58String[];
+}
+
+/** How a created referendum is submitted when it is not signed by Alice directly. */
+export type DelegatedSubmission = ProxySubmission | MultisigSubmission;
+
+/** Upper bound for the weight of the multisig call (a `Referenda.submit`). */
+const MULTISIG_MAX_WEIGHT = { ref_time: 10_000_000_000n, proof_size: 1_000_000n };
+
+/** Chopsticks' `mock-signature-host` accepts this signature for any account. */
+const MOCK_SIGNATURE = new Uint8Array(64).fill(0xcd);
+MOCK_SIGNATURE.set([0xde, 0xad, 0xbe, 0xef]);
+
+/** Balance given to accounts that pay deposits during a delegated submission. */
+const FUNDED_BALANCE = '10000000000000000000';
+
+/** Parse `--submit-via-proxy`: `real[:proxy type[:delay]]`, e.g. `15oF...:Governance:100`. */
+export function parseProxySubmission(value?: string): ProxySubmission | undefined {
+  if (!value) return undefined;
+  const [real, proxyType = 'Any', delay = '0', ...rest] = value.split(':');
+  if (!isAddress(real)) {
+    throw new CodedError(
+      ErrorCode.InvalidProxy,
+      `Invalid --submit-via-proxy "${value}": "${real}" is not an SS58 address`
+    );
+  }
+  if (rest.length > 0 || !/^[A-Za-z]+$/.test(proxyType) || !/^\d+$/.test(delay)) {
+    throw new CodedError(
+      ErrorCode.InvalidProxy,
+      `Invalid --submit-via-proxy "${value}": expected real[:proxy type[:delay]]`
+    );
+  }
+  return { kind: 'proxy', real, proxyType, delay: Number(delay) };
+}
+
+/** Parse `--submit-via-multisig`: `threshold:signatory,signatory[,...]`. */
+export function parseMultisigSubmission(value?: string): MultisigSubmission | undefined {
+  if (!value) return undefined;
+  const match = value.match(/^(\d+):(.+)$/);
+  if (!match) {
+    throw new CodedError(
+      ErrorCode.InvalidMultisig,
+      `Invalid --submit-via-multisig "${value}": expected threshold:signatory,signatory[,...]`
+    );
+  }
+  const threshold = Number(match[1]);
+  const signatories = match[2].split(',').map((signatory) => signatory.trim());
+  const invalid = signatories.find((signatory) => !isAddress(signatory));
+  if (invalid !== undefined) {
+    throw new CodedError(
+      ErrorCode.InvalidMultisig,
+      `Invalid --submit-via-multisig "${value}": "${invalid}" is not an SS58 address`
+    );
+  }
+  const unique = new Set(
+    signatories.map((signatory) => Binary.fromBytes(AccountId().enc(signatory)).asHex())
+  );
+  if (signatories.length < 2 || unique.size !== signatories.length) {
+    throw new CodedError(
+      ErrorCode.InvalidMultisig,
+      `Invalid --submit-via-multisig "${value}": needs at least two distinct signatories`
+    );
+  }
+  if (threshold < 1 || threshold > signatories.length) {
+    throw new CodedError(
+      ErrorCode.InvalidMultisig,
+      `Invalid --submit-via-multisig "${value}": threshold must be between 1 and ${signatories.length}`
+    );
+  }
+  return { kind: 'multisig', threshold, signatories };
+}
+
+/**
+ * Parse the delegated submission flags of `test`. That at most one of them is
+ * given, and only with a referendum to create, is checked by the flag rules.
+ */
+export function parseDelegatedSubmission(options: {
+  submitViaProxy?: string;
+  submitViaMultisig?: string;
+}): DelegatedSubmission | undefined {
+  return (
+    parseProxySubmission(options.submitViaProxy) ??
+    parseMultisigSubmission(options.submitViaMultisig)
+  );
+}
+
+function sortedAccountIds(signatories: SS58String[]): Uint8Array[] {
+  return signatories
+    .map((signatory) => AccountId().enc(signatory))
+    .sort((a, b) => Binary.fromBytes(a).asHex().localeCompare(Binary.fromBytes(b).asHex()));
+}
+
+/** The `Multisig` pallet account of `signatories` with `threshold`. */
+export function multisigAccountId(signatories: SS58String[], threshold: number): SS58String {
+  const ids = sortedAccountIds(signatories);
+  const entropy = new Uint8Array([
+    ...new TextEncoder().encode('modlpy/utilisuba'),
+    ...compact.enc(ids.length),
+    ...ids.flatMap((id) => [...id]),
+    ...u16.enc(threshold),
+  ]);
+  return AccountId().dec(Blake2256(entropy));
+}
+
+/** The signatories other than `signatory`, sorted as the `Multisig` pallet expects. */
+function otherSignatories(signatories: SS58String[], signatory: SS58String): SS58String[] {
+  const own = Binary.fromBytes(AccountId().enc(signatory)).asHex();
+  return sortedAccountIds(signatories)
+    .filter((id) => Binary.fromBytes(id).asHex() !== own)
+    .map((id) => AccountId().dec(id));
+}
+
+function callHashOf(call: DecodedTransaction): Binary {
+  return Binary.fromBytes(Blake2256(call.getEncodedData().asBytes()));
+}
+
+function proxyPallet(api: SubstrateApi): NonNullable<SubstrateApi['tx']['Proxy']> {
+  if (!api.tx.Proxy) {
+    throw new CodedError(ErrorCode.InvalidProxy, 'Chain has no Proxy pallet to submit through');
+  }
+  return api.tx.Proxy;
+}
+
+function multisigPallet(api: SubstrateApi): NonNullable<SubstrateApi['tx']['Multisig']> {
+  if (!api.tx.Multisig) {
+    throw new CodedError(
+      ErrorCode.InvalidMultisig,
+      'Chain has no Multisig pallet to submit through'
+    );
+  }
+  return api.tx.Multisig;
+}
+
+/**
+ * `Proxy.proxy` of `call` as `proxy.real`, or, once `delegate` has announced
+ * it, `Proxy.proxy_announced`.
+ */
+export function proxiedCall(
+  api: SubstrateApi,
+  call: DecodedTransaction,
+  proxy: ProxySubmission,
+  delegate?: SS58String
+): UnsafeTransaction {
+  const real = Enum('Id', proxy.real);
+  if (delegate === undefined) {
+    return proxyPallet(api).proxy({ real, force_proxy_type: undefined, call: call.decodedCall });
+  }
+  return proxyPallet(api).proxy_announced({
+    delegate: Enum('Id', delegate),
+    real,
+    force_proxy_type: undefined,
+    call: call.decodedCall,
+  });
+}
+
+/** `Proxy.announce` of `call`, which a time-delayed proxy sends first. */
+export function proxyAnnouncement(
+  api: SubstrateApi,
+  call: DecodedTransaction,
+  proxy: ProxySubmission
+): UnsafeTransaction {
+  return proxyPallet(api).announce({ real: Enum('Id', proxy.real), call_hash: callHashOf(call) });
+}
+
+/** `Multisig.approve_as_multi` of `call`'s hash by `approver`. */
+export function multisigApproval(
+  api: SubstrateApi,
+  call: DecodedTransaction,
+  multisig: MultisigSubmission,
+  approver: SS58String,
+  timepoint?: MultisigTimepoint
+): UnsafeTransaction {
+  return multisigPallet(api).approve_as_multi({
+    threshold: multisig.threshold,
+    other_signatories: otherSignatories(multisig.signatories, approver),
+    maybe_timepoint: timepoint,
+    call_hash: callHashOf(call),
+    max_weight: MULTISIG_MAX_WEIGHT,
+  });
+}
+
+/**
+ * The call executing `call` from the multisig, signed by the signatory at
+ * position `threshold`: `Multisig.as_multi`, or `as_multi_threshold_1`.
+ */
+export function multisigExecution(
+  api: SubstrateApi,
+  call: DecodedTransaction,
+  multisig: MultisigSubmission,
+  timepoint?: MultisigTimepoint
+): UnsafeTransaction {
+  const { threshold, signatories } = multisig;
+  const executor = signatories[threshold - 1];
+  const other_signatories = otherSignatories(signatories, executor);
+  if (threshold === 1) {
+    return multisigPallet(api).as_multi_threshold_1({ other_signatories, call: call.decodedCall });
+  }
+  return multisigPallet(api).as_multi({
+    threshold,
+    other_signatories,
+    maybe_timepoint: timepoint,
+    call: call.decodedCall,
+    max_weight: MULTISIG_MAX_WEIGHT,
+  });
+}
+
+/** One extrinsic of a delegated submission on the live chain. */
+export interface DelegatedStep {
+  /** `Pallet.call` */
+  name: string;
+  /** The account signing it, or `the proxy` */
+  signer: string;
+  /** Call data; unset when it depends on a timepoint only the live chain knows */
+  hex?: string;
+  note?: string;
+}
+
+/**
+ * The extrinsics that submit `call` through `submission` on the live chain,
+ * built as {@link DelegatedSubmitter} builds them on the fork.
+ */
+export async function delegatedSteps(
+  api: SubstrateApi,
+  call: DecodedTransaction,
+  submission: DelegatedSubmission
+): Promise<DelegatedStep[]> {
+  const hexOf = async (tx: UnsafeTransaction) => (await tx.getEncodedData()).asHex();
+  if (submission.kind === 'proxy') {
+    if (submission.delay === 0) {
+      const proxied = await hexOf(proxiedCall(api, call, submission));
+      return [{ name: 'Proxy.proxy', signer: 'the proxy', hex: proxied }];
+    }
+    return [
+      {
+        name: 'Proxy.announce',
+        signer: 'the proxy',
+        hex: await hexOf(proxyAnnouncement(api, call, submission)),
+      },
+      {
+        name: 'Proxy.proxy_announced',
+        signer: 'the proxy',
+        note: `${submission.delay} blocks later, with the proxy as delegate`,
+      },
+    ];
+  }
+
+  const { threshold, signatories } = submission;
+  const executor = signatories[threshold - 1];
+  if (threshold === 1) {
+    return [
+      {
+        name: 'Multisig.as_multi_threshold_1',
+        signer: executor,
+        hex: await hexOf(multisigExecution(api, call, submission)),
+      },
+    ];
+  }
+  const timepoint = 'with the timepoint of the first approval';
+  const approvals = await Promise.all(
+    signatories.slice(0, threshold - 1).map(async (approver, i) => ({
+      name: 'Multisig.approve_as_multi',
+      signer: approver,
+      ...(i === 0
+        ? { hex: await hexOf(multisigApproval(api, call, submission, approver)) }
+        : { note: timepoint }),
+    }))
+  );
+  return [...approvals, { name: 'Multisig.as_multi', signer: executor, note: timepoint }];
+}
+
+/** A signer producing Chopsticks' mock signature, so any account can sign on the fork. */
+export function mockSigner(address: SS58String): PolkadotSigner {
+  return getPolkadotSigner(AccountId().enc(address), 'Sr25519', () => MOCK_SIGNATURE);
+}
+
+export function describeSubmission(submission: DelegatedSubmission): string {
+  if (submission.kind === 'proxy') {
+    return `as ${submission.real} through Alice as its ${submission.proxyType} proxy (delay ${submission.delay})`;
+  }
+  const account = multisigAccountId(submission.signatories, submission.threshold);
+  return `as ${submission.threshold}-of-${submission.signatories.length} multisig ${account}`;
+}
+
+/**
+ * Prepares the fork for a proxy or multisig submission and signs the
+ * extrinsic that dispatches the call through it.
+ */
+export class DelegatedSubmitter {
+  private logger: Logger;
+  private chopsticks: ChopsticksManager;
+
+  constructor(logger: Logger, chopsticks: ChopsticksManager) {
+    this.logger = logger;
+    this.chopsticks = chopsticks;
+  }
+
+  /**
+   * Run the steps before the final extrinsic (proxy registration and
+   * announcement, or multisig approvals) and return it signed.
+   */
+  async sign(
+    api: SubstrateApi,
+    signer: PolkadotSigner,
+    decoded: DecodedTransaction,
+    submission: DelegatedSubmission
+  ): Promise<string> {
+    return submission.kind === 'proxy'
+      ? this.signProxied(api, signer, decoded, submission)
+      : this.signMultisig(api, decoded, submission);
+  }
+
+  private async fund(accounts: SS58String[]): Promise<void> {
+    await this.chopsticks.setStorageBatch({
+      System: {
+        Account: accounts.map((account) => [
+          [account],
+          { providers: 1, data: { free: FUNDED_BALANCE } },
+        ]),
+      },
+    });
+  }
+
+  /**
+   * Make Alice a `proxyType` proxy of `real` with `delay` (keeping its other
+   * proxies) and fund `real` for the submission deposit.
+   */
+  private async grantProxy(api: SubstrateApi, proxy: ProxySubmission): Promise<void> {
+    if (!api.query.Proxy || !api.tx.Proxy) {
+      throw new CodedError(ErrorCode.InvalidProxy, 'Chain has no Proxy pallet to submit through');
+    }
+    const [definitions, deposit] = (await api.query.Proxy.Proxies.getValue(proxy.real)) ?? [
+      [],
+      0n,
+    ];
+    const others = definitions
+      .filter((definition) => definition.delegate !== ALICE_ADDRESS)
+      .map((definition) => ({
+        delegate: definition.delegate,
+        proxy_type: definition.proxy_type.type,
+        delay: definition.delay,
+      }));
+
+    await this.fund([proxy.real]);
+    await this.chopsticks.setStorageBatch({
+      Proxy: {
+        Proxies: [
+          [
+            [proxy.real],
+            [
+              [
+                ...others,
+                { delegate: ALICE_ADDRESS, proxy_type: proxy.proxyType, delay: proxy.delay },
+              ],
+              deposit.toString(),
+            ],
+          ],
+        ],
+      },
+    });
+    this.logger.debug(`Alice registered as ${proxy.proxyType} proxy of ${proxy.real}`);
+  }
+
+  /** Wrap the call in `Proxy.proxy` (or announce it and use `Proxy.proxy_announced`). */
+  private async signProxied(
+    api: SubstrateApi,
+    signer: PolkadotSigner,
+    decoded: DecodedTransaction,
+    proxy: ProxySubmission
+  ): Promise<string> {
+    await this.grantProxy(api, proxy);
+    if (proxy.delay === 0) {
+      return proxiedCall(api, decoded, proxy).sign(signer);
+    }
+
+    await this.announce(api, signer, decoded, proxy);
+    return proxiedCall(api, decoded, proxy, ALICE_ADDRESS).sign(signer);
+  }
+
+  /**
+   * Announce the call as Alice, then age the announcement by `delay` blocks
+   * so it can be executed right away instead of building the delay out.
+   */
+  private async announce(
+    api: SubstrateApi,
+    signer: PolkadotSigner,
+    decoded: DecodedTransaction,
+    proxy: ProxySubmission
+  ): Promise<void> {
+    // grantProxy guarantees the pallet exists
+    const proxyQuery = api.query.Proxy!;
+    const callHash = callHashOf(decoded);
+    const signedAnnounce = await proxyAnnouncement(api, decoded, proxy).sign(signer);
+    await expectEvent(
+      this.chopsticks,
+      api.query.System.Events,
+      signedAnnounce,
+      {
+        section: 'Proxy',
+        method: 'Announced',
+        step: `Proxy.announce of the referendum submit (call hash ${callHash.asHex()})`,
+        code: ErrorCode.ReferendumNotCreated,
+      },
+      this.logger
+    );
+
+    const [announcements, deposit] = (await proxyQuery.Announcements.getValue(ALICE_ADDRESS)) ?? [
+      [],
+      0n,
+    ];
+    await this.chopsticks.setStorageBatch({
+      Proxy: {
+        Announcements: [
+          [
+            [ALICE_ADDRESS],
+            [
+              announcements.map((announcement) => ({
+                real: announcement.real,
+                call_hash: announcement.call_hash.asHex(),
+                height: Math.max(0, announcement.height - proxy.delay),
+              })),
+              deposit.toString(),
+            ],
+          ],
+        ],
+      },
+    });
+    this.logger.info(`Announced referendum submit; aged the announcement by ${proxy.delay} blocks`);
+  }
+
+  /**
+   * Walk the multisig ceremony: every signatory but the last approves the call
+   * hash (`approve_as_multi`), the last one executes the call (`as_multi`).
+   * Signatures are mocked, so any signatory can sign on the fork.
+   */
+  private async signMultisig(
+    api: SubstrateApi,
+    decoded: DecodedTransaction,
+    multisig: MultisigSubmission
+  ): Promise<string> {
+    if (!api.query.Multisig || !api.tx.Multisig) {
+      throw new CodedError(
+        ErrorCode.InvalidMultisig,
+        'Chain has no Multisig pallet to submit through'
+      );
+    }
+    const { threshold, signatories } = multisig;
+    const account = multisigAccountId(signatories, threshold);
+    await this.fund([...signatories, account]);
+
+    const approvers = signatories.slice(0, threshold - 1);
+    const executor = signatories[threshold - 1];
+
+    if (threshold === 1) {
+      this.logger.info(`Multisig ${account}: ${executor} executes the submit (threshold 1)`);
+      return multisigExecution(api, decoded, multisig).sign(mockSigner(executor));
+    }
+
+    const callHash = callHashOf(decoded);
+    let timepoint: MultisigTimepoint | undefined;
+    for (const [i, approver] of approvers.entries()) {
+      const approval = multisigApproval(api, decoded, multisig, approver, timepoint);
+      const signedApproval = await approval.sign(mockSigner(approver));
+      await expectEvent(
+        this.chopsticks,
+        api.query.System.Events,
+        signedApproval,
+        {
+          section: 'Multisig',
+          method: i === 0 ? 'NewMultisig' : 'MultisigApproval',
+          step: `Multisig approval ${i + 1}/${threshold} by ${approver}`,
+          code: ErrorCode.ReferendumNotCreated,
+        },
+        this.logger
+      );
+      this.logger.info(`Multisig ${account}: approval ${i + 1}/${threshold} by ${approver}`);
+      timepoint ??= (await api.query.Multisig.Multisigs.getValue([account, callHash]))?.when;
+    }
+
+    this.logger.info(
+      `Multisig ${account}: ${executor} executes the submit (approval ${threshold}/${threshold})`
+    );
+    return multisigExecution(api, decoded, multisig, timepoint).sign(mockSigner(executor));
+  }
+}
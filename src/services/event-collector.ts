@@ -1,8 +1,44 @@
 import type { SubstrateApi } from '../types/substrate-api';
+import { CodedError, ErrorCode } from '../utils/error-codes';
 import { displayChainEvents } from '../utils/event-serializer';
 import type { Logger } from '../utils/logger';
+import type { RpcRequest } from './call-drift-detector';
 import { createApiForChain, createPolkadotClient } from './chain-registry';
 import type { ChopsticksManager } from './chopsticks-manager';
+import {
+  describeGovernanceV1Event,
+  discoverGovernanceV1Pallets,
+  type GovernanceV1Pallets,
+  governanceV1PalletNames,
+  NO_GOVERNANCE_V1,
+} from './governance-v1';
+
+/** Blocks built on each additional chain after dispatch (`--settle-blocks`). */
+export const DEFAULT_SETTLE_BLOCKS = 1;
+
+/** Parse `--settle-blocks`; it must be a positive integer. */
+export function parseSettleBlocks(value?: string): number {
+  if (value === undefined) return DEFAULT_SETTLE_BLOCKS;
+  if (!/^\d+$/.test(value.trim()) || parseInt(value, 10) < 1) {
+    throw new CodedError(
+      ErrorCode.InvalidSettleBlocks,
+      `Invalid --settle-blocks value: ${value} (expected a positive integer)`
+    );
+  }
+  return parseInt(value, 10);
+}
+
+/** Parse `--blocks-after-dispatch`; it must be a non-negative integer (default 0). */
+export function parseBlocksAfterDispatch(value?: string): number {
+  if (value === undefined) return 0;
+  if (!/^\d+$/.test(value.trim())) {
+    throw new CodedError(
+      ErrorCode.InvalidBlocksAfterDispatch,
+      `Invalid --blocks-after-dispatch value: ${value} (expected a non-negative integer)`
+    );
+  }
+  return parseInt(value, 10);
+}
 
 /**
  * Collects and displays post-execution events from chain instances.
@@ -17,18 +53,24 @@ import type { ChopsticksManager } from './chopsticks-manager';
  * │    └─ collectAdditionalChainEvents()              │
  * │                                                   │
  * │  collectAdditionalChainEvents()                   │
- * │    ├─ for each additional manager:                │
- * │    │   ├─ newBlock() to process XCM               │
- * │    │   ├─ create temp client + api                │
- * │    │   └─ displayChainEvents()                    │
+ * │    ├─ repeat settleBlocks times:                  │
+ * │    │   └─ for each additional manager:            │
+ * │    │       ├─ newBlock() to process XCM           │
+ * │    │       ├─ create temp client + api            │
+ * │    │       ├─ detect governance v1 pallets (once) │
+ * │    │       └─ displayChainEvents()                │
  * │    └─ destroy temp clients                        │
  * └──────────────────────────────────────────────────┘
  */
 export class EventCollector {
   private logger: Logger;
+  private settleBlocks: number;
+  /** Governance v1 pallets of each additional chain, detected on its first block */
+  private governanceV1 = new Map<string, GovernanceV1Pallets>();
 
-  constructor(logger: Logger) {
+  constructor(logger: Logger, settleBlocks: number = DEFAULT_SETTLE_BLOCKS) {
     this.logger = logger;
+    this.settleBlocks = settleBlocks;
   }
 
   async displayPostExecutionEvents(context: {
@@ -82,34 +124,74 @@ export class EventCollector {
 
     this.logger.section('Additional Chain Events');
     this.logger.info(
-      `Advancing blocks on ${additionalManagers.size} additional chains to process XCM messages...\n`
+      `Advancing ${this.settleBlocks} block(s) on ${additionalManagers.size} additional chains to process XCM messages...\n`
     );
 
-    for (const [chainLabel, manager] of additionalManagers) {
-      this.logger.debug(`Processing events for chain: ${chainLabel}`);
-      try {
-        await manager.newBlock();
-
-        const endpoint = manager.getContext().ws.endpoint;
-        const client = createPolkadotClient(endpoint);
-
+    // Build round-robin so a message relayed across several chains (e.g. UMP
+    // then DMP) can land within the same number of settle blocks.
+    const failed = new Set<string>();
+    for (let round = 1; round <= this.settleBlocks; round++) {
+      for (const [chainLabel, manager] of additionalManagers) {
+        if (failed.has(chainLabel)) continue;
+        this.logger.debug(`Processing events for chain: ${chainLabel}`);
         try {
-          const api = createApiForChain(client);
-
-          const blockNumber = await api.query.System.Number.getValue();
-          const events = await api.query.System.Events.getValue();
-
-          displayChainEvents(chainLabel, blockNumber, events, this.logger);
-        } finally {
-          client.destroy();
+          await manager.newBlock();
+
+          const endpoint = manager.getContext().ws.endpoint;
+          const client = createPolkadotClient(endpoint);
+
+          try {
+            const api = createApiForChain(client);
+
+            const blockNumber = await api.query.System.Number.getValue();
+            const events = await api.query.System.Events.getValue();
+            const governanceV1 = await this.detectGovernanceV1(chainLabel, (method, params) =>
+              client._request(method, params)
+            );
+
+            const label =
+              this.settleBlocks > 1
+                ? `${chainLabel} [settle block ${round}/${this.settleBlocks}]`
+                : chainLabel;
+            displayChainEvents(label, blockNumber, events, this.logger, (event) =>
+              describeGovernanceV1Event(governanceV1, event)
+            );
+          } finally {
+            client.destroy();
+          }
+
+          this.logger.info('');
+        } catch (error) {
+          const chainError = error as Error;
+          this.logger.error(`Error collecting events from ${chainLabel}: ${chainError.message}`);
+          this.logger.debug(`Stack trace: ${chainError.stack}`);
+          failed.add(chainLabel);
         }
-
-        this.logger.info('');
-      } catch (error) {
-        const chainError = error as Error;
-        this.logger.error(`Error collecting events from ${chainLabel}: ${chainError.message}`);
-        this.logger.debug(`Stack trace: ${chainError.stack}`);
       }
     }
   }
+
+  /**
+   * A chain still governed by Democracy and councils is announced once, and
+   * its governance events are described from then on. Chains whose metadata
+   * can't be read are treated as having none.
+   */
+  private async detectGovernanceV1(
+    chainLabel: string,
+    request: RpcRequest
+  ): Promise<GovernanceV1Pallets> {
+    const known = this.governanceV1.get(chainLabel);
+    if (known) return known;
+
+    const pallets = await discoverGovernanceV1Pallets(request).catch((error) => {
+      this.logger.debug(`Could not read governance pallets of ${chainLabel}: ${error}`);
+      return NO_GOVERNANCE_V1;
+    });
+    this.governanceV1.set(chainLabel, pallets);
+    const names = governanceV1PalletNames(pallets);
+    if (names.length > 0) {
+      this.logger.info(`${chainLabel} uses governance v1 (${names.join(', ')})`);
+    }
+    return pallets;
+  }
 }
@@ -1,9 +1,78 @@
+import type { NotDispatchedReason } from '../types';
 import { formatDispatchError, interpretDispatchResult } from '../utils/dispatch-result';
 import { type ParsedEvent, serializeEventData } from '../utils/event-serializer';
 import { toHexString } from '../utils/hex';
 import { stringify } from '../utils/json';
 import type { Logger } from '../utils/logger';
 
+/** Scheduler events reporting an agenda task that was dropped instead of dispatched. */
+const NOT_DISPATCHED_EVENTS: Record<string, NotDispatchedReason> = {
+  CallUnavailable: 'call-unavailable',
+  PermanentlyOverweight: 'permanently-overweight',
+};
+
+/** Why the scheduler could not dispatch the task, in plain language. */
+const NOT_DISPATCHED_EXPLANATIONS: Record<NotDispatchedReason, string> = {
+  'call-unavailable':
+    "the proposal's preimage was not available (never noted, or already unrequested), so the scheduler could not load the call. The task is not retried: noting the preimage afterwards does not enact it",
+  'permanently-overweight':
+    "the proposal's weight is higher than the scheduler may ever use in one block, so it is never dispatched. Unlike a task that only missed room in a busy block, it is not postponed to the next block",
+  postponed:
+    'every block ran out of scheduler weight before reaching the task, so it stayed in the agenda undispatched. Tasks ahead of it in the agenda, or heavy on_initialize work, are using up the block',
+};
+
+/** Explain a task the scheduler left in its agenda, in plain language. */
+export function describePostponedTask(block: number, taskIndex: number, retries: number): string {
+  return `Task [${block}, ${taskIndex}] was still scheduled after ${retries + 1} block(s): ${NOT_DISPATCHED_EXPLANATIONS.postponed}`;
+}
+
+/**
+ * The first `Scheduler.CallUnavailable`/`PermanentlyOverweight` event for the
+ * expected task (any task when none is expected), and why it was not dispatched.
+ */
+export function findNotDispatchedTask(
+  events: ParsedEvent[],
+  expectedBlock?: number,
+  expectedTaskIndex?: number
+): { reason: NotDispatchedReason; message: string } | undefined {
+  for (const event of events) {
+    const reason = NOT_DISPATCHED_EVENTS[event.method];
+    if (event.section !== 'Scheduler' || !reason) continue;
+
+    const dataRecord = event.data as Record<string, unknown> | undefined;
+    const eventValue = (dataRecord?.value || event.data) as Record<string, unknown> | undefined;
+    const task = Array.isArray(eventValue?.task) ? eventValue.task : undefined;
+    if (task) {
+      if (expectedBlock !== undefined && Number(task[0]) !== expectedBlock) continue;
+      if (expectedTaskIndex !== undefined && Number(task[1]) !== expectedTaskIndex) continue;
+    }
+
+    const where = task ? ` for task [${task[0]}, ${task[1]}]` : '';
+    return {
+      reason,
+      message: `Scheduler.${event.method}${where}: ${NOT_DISPATCHED_EXPLANATIONS[reason]}`,
+    };
+  }
+  return undefined;
+}
+
+/** A task scheduled for a later block (`Scheduler.Scheduled`), by its agenda slot. */
+export interface ScheduledTask {
+  when: number;
+  index: number;
+}
+
+/** Tasks the block's `Scheduler.Scheduled` events put in the agenda. */
+export function findScheduledTasks(events: ParsedEvent[]): ScheduledTask[] {
+  return events.flatMap((event) => {
+    if (event.section !== 'Scheduler' || event.method !== 'Scheduled') return [];
+    const dataRecord = event.data as Record<string, unknown> | undefined;
+    const eventValue = (dataRecord?.value || event.data) as Record<string, unknown> | undefined;
+    if (!eventValue?.when) return [];
+    return [{ when: Number(eventValue.when), index: Number(eventValue.index ?? 0) }];
+  });
+}
+
 export class ExecutionResultChecker {
   private logger: Logger;
 
@@ -16,23 +85,36 @@ export class ExecutionResultChecker {
     expectedBlock?: number,
     expectedTaskIndex?: number,
     expectedTaskId?: Uint8Array
-  ): { executionSucceeded: boolean; errors?: string[] } {
+  ): { executionSucceeded: boolean; errors?: string[]; notDispatched?: NotDispatchedReason } {
     const { extrinsicFailureMessages } = this.logBlockEvents(events);
 
-    this.logScheduledFutureTasks(events);
+    const notDispatched = findNotDispatchedTask(events, expectedBlock, expectedTaskIndex);
+    if (notDispatched) {
+      return {
+        executionSucceeded: false,
+        errors: [notDispatched.message, ...extrinsicFailureMessages],
+        notDispatched: notDispatched.reason,
+      };
+    }
 
     const dispatchedEvents = events.filter(
       (blockEvent) => blockEvent.section === 'Scheduler' && blockEvent.method === 'Dispatched'
     );
     this.logger.debug(`Found ${dispatchedEvents.length} Scheduler.Dispatched events`);
 
-    return this.interpretResults(
+    const result = this.interpretResults(
       dispatchedEvents,
       extrinsicFailureMessages,
       expectedBlock,
       expectedTaskIndex,
       expectedTaskId
     );
+    // A failed dispatch schedules nothing itself: its tasks are rolled back,
+    // and a task scheduled in its block is another task's, or its retry
+    if (result.executionSucceeded) {
+      this.logScheduledFutureTasks(events);
+    }
+    return result;
   }
 
   private logBlockEvents(events: ParsedEvent[]): { extrinsicFailureMessages: string[] } {
@@ -56,18 +138,10 @@ export class ExecutionResultChecker {
   }
 
   private logScheduledFutureTasks(events: ParsedEvent[]): void {
-    const scheduledEvents = events.filter(
-      (blockEvent) => blockEvent.section === 'Scheduler' && blockEvent.method === 'Scheduled'
-    );
-    for (const scheduledEvent of scheduledEvents) {
-      const eventData = scheduledEvent.data as Record<string, unknown> | undefined;
-      const eventValue = eventData?.value as Record<string, unknown> | undefined;
-      const whenBlock = eventValue?.when || eventData?.when;
-      if (whenBlock) {
-        this.logger.info(
-          `Note: Proposal scheduled a future task at block ${whenBlock} (this is from the proposal content, not the referendum enactment)`
-        );
-      }
+    for (const { when } of findScheduledTasks(events)) {
+      this.logger.info(
+        `Note: Proposal scheduled a future task at block ${when} (this is from the proposal content, not the referendum enactment; --follow-scheduled dispatches it)`
+      );
     }
   }
 
@@ -0,0 +1,154 @@
+import { decAnyMetadata, unifyMetadata } from '@polkadot-api/substrate-bindings';
+import { interpretDispatchResult } from '../utils/dispatch-result';
+import type { ParsedEvent } from '../utils/event-serializer';
+import { toHexString } from '../utils/hex';
+import type { RpcRequest } from './call-drift-detector';
+
+/**
+ * Governance v1 pallets of a runtime: `pallet_democracy` and the
+ * `pallet_collective` instances (Council, Technical Committee, ...) that
+ * propose and fast-track its referenda.
+ *
+ * Chains governed this way can only be attached as additional chains: the
+ * referendum pipeline itself is OpenGov-specific. Their governance events are
+ * described, rather than only listed, so the effect of an XCM message on them
+ * (a motion executed, a referendum started) can be read from the run.
+ */
+export interface GovernanceV1Pallets {
+  democracy?: string;
+  collectives: string[];
+}
+
+/** The subset of (unified) metadata needed to find governance v1 pallets. */
+export interface GovernanceV1Metadata {
+  pallets: {
+    name: string;
+    storage?: { items: { name: string }[] };
+  }[];
+}
+
+export const NO_GOVERNANCE_V1: GovernanceV1Pallets = { collectives: [] };
+
+/**
+ * Find governance v1 pallets by their storage, so renamed instances are
+ * found too: `pallet_democracy` has `PublicProps` and `ReferendumInfoOf`, a
+ * `pallet_collective` instance `ProposalOf`, `Voting` and `Members`.
+ */
+export function findGovernanceV1Pallets(metadata: GovernanceV1Metadata): GovernanceV1Pallets {
+  const found: GovernanceV1Pallets = { collectives: [] };
+  for (const pallet of metadata.pallets) {
+    const items = new Set((pallet.storage?.items ?? []).map((item) => item.name));
+    if (items.has('PublicProps') && items.has('ReferendumInfoOf')) {
+      found.democracy ??= pallet.name;
+    } else if (items.has('ProposalOf') && items.has('Voting') && items.has('Members')) {
+      found.collectives.push(pallet.name);
+    }
+  }
+  return found;
+}
+
+/** Read a fork's metadata and list its governance v1 pallets. */
+export async function discoverGovernanceV1Pallets(
+  request: RpcRequest
+): Promise<GovernanceV1Pallets> {
+  const hex: string = await request('state_getMetadata', []);
+  return findGovernanceV1Pallets(
+    unifyMetadata(decAnyMetadata(hex)) as unknown as GovernanceV1Metadata
+  );
+}
+
+export function governanceV1PalletNames(pallets: GovernanceV1Pallets): string[] {
+  return [...(pallets.democracy ? [pallets.democracy] : []), ...pallets.collectives];
+}
+
+function hash(value: unknown): string {
+  const hex = toHexString(value) ?? '?';
+  return hex.length > 10 ? `${hex.slice(0, 10)}…` : hex;
+}
+
+function outcome(result: unknown): string {
+  const { outcome, message } = interpretDispatchResult(result);
+  if (outcome === 'success') return 'Ok';
+  return outcome === 'failure' ? `failed (${message ?? 'unknown error'})` : 'unknown result';
+}
+
+/** `AccountVote`: a standard vote's aye bit is the top bit of its `vote` byte. */
+function direction(vote: unknown): string {
+  const accountVote = vote as { type?: string; value?: { vote?: number } } | undefined;
+  if (accountVote?.type !== 'Standard') return 'split';
+  return ((accountVote.value?.vote ?? 0) & 0x80) !== 0 ? 'aye' : 'nay';
+}
+
+function describeDemocracyEvent(method: string, data: Record<string, unknown>): string | undefined {
+  const referendum = `referendum #${data.ref_index}`;
+  switch (method) {
+    case 'Proposed':
+      return `public proposal #${data.proposal_index} submitted (deposit ${data.deposit})`;
+    case 'Tabled':
+      return `public proposal #${data.proposal_index} tabled as a referendum`;
+    case 'ExternalTabled':
+      return 'external proposal tabled as a referendum';
+    case 'Started':
+      return `${referendum} started (${(data.threshold as { type?: string })?.type ?? '?'})`;
+    case 'Passed':
+      return `${referendum} passed`;
+    case 'NotPassed':
+      return `${referendum} did not pass`;
+    case 'Cancelled':
+      return `${referendum} cancelled`;
+    case 'Executed':
+      return `${referendum} executed: ${outcome(data.result)}`;
+    case 'Voted':
+      return `${data.voter} voted ${direction(data.vote)} on ${referendum}`;
+    case 'Vetoed':
+      return `external proposal ${hash(data.proposal_hash)} vetoed by ${data.who} until block ${data.until}`;
+    case 'Blacklisted':
+      return `proposal ${hash(data.proposal_hash)} blacklisted`;
+    case 'Delegated':
+      return `${data.who} delegated to ${data.target}`;
+    case 'Undelegated':
+      return `${data.account} undelegated`;
+    default:
+      return undefined;
+  }
+}
+
+function describeCollectiveEvent(
+  method: string,
+  data: Record<string, unknown>
+): string | undefined {
+  const motion = `motion ${hash(data.proposal_hash)}`;
+  switch (method) {
+    case 'Proposed':
+      return `motion #${data.proposal_index} ${hash(data.proposal_hash)} proposed by ${data.account} (threshold ${data.threshold})`;
+    case 'Voted':
+      return `${data.account} voted ${data.voted ? 'aye' : 'nay'} on ${motion} (${data.yes} aye / ${data.no} nay)`;
+    case 'Approved':
+      return `${motion} approved`;
+    case 'Disapproved':
+      return `${motion} disapproved`;
+    case 'Executed':
+      return `${motion} executed: ${outcome(data.result)}`;
+    case 'MemberExecuted':
+      return `${motion} executed by a member: ${outcome(data.result)}`;
+    case 'Closed':
+      return `${motion} closed (${data.yes} aye / ${data.no} nay)`;
+    default:
+      return undefined;
+  }
+}
+
+/** A one-line description of a governance v1 event, or undefined for any other event. */
+export function describeGovernanceV1Event(
+  pallets: GovernanceV1Pallets,
+  event: ParsedEvent
+): string | undefined {
+  const data = (event.data ?? {}) as Record<string, unknown>;
+  if (event.section === pallets.democracy) {
+    return describeDemocracyEvent(event.method, data);
+  }
+  if (pallets.collectives.includes(event.section)) {
+    return describeCollectiveEvent(event.method, data);
+  }
+  return undefined;
+}
@@ -0,0 +1,109 @@
+import { spawn } from 'child_process';
+import * as fs from 'fs';
+import * as path from 'path';
+import { WASI } from 'wasi';
+import { CodedError, ErrorCode } from '../utils/error-codes';
+import type { Logger } from '../utils/logger';
+
+/**
+ * Points in a referendum simulation where user hook scripts run:
+ * - `after-passing`: the referendum was forced into a passing state
+ * - `before-dispatch`: the proposal is scheduled for the next block
+ * - `after-dispatch`: the block executing the proposal was built
+ */
+export type HookPhase = 'after-passing' | 'before-dispatch' | 'after-dispatch';
+
+export const HOOK_PHASES: HookPhase[] = ['after-passing', 'before-dispatch', 'after-dispatch'];
+
+/** Script per phase (`--hook-<phase> <script.js|script.wasm>`). */
+export type HookScripts = Partial<Record<HookPhase, string>>;
+
+/** What a hook learns about the simulation, passed as `HOOK_*` env vars. */
+export interface HookContext {
+  /** WebSocket RPC endpoint of the forked chain */
+  endpoint: string;
+  referendumId: number;
+  isFellowship: boolean;
+  block: number;
+}
+
+const HOOK_EXTENSIONS = new Set(['.js', '.cjs', '.mjs', '.wasm']);
+
+/**
+ * Runs hook scripts with access to the forked chain.
+ *
+ * JavaScript hooks run as a `node` child process and can connect to
+ * `HOOK_RPC_ENDPOINT` to query state or mutate it via Chopsticks' `dev_*` RPCs
+ * before the next block is built. WASM hooks run in-process under WASI with
+ * the same env vars (WASI has no sockets, so they can only assert on them).
+ * A non-zero exit fails the run with `E-HOOK-FAILED`.
+ */
+export class HookRunner {
+  private logger: Logger;
+  private scripts: HookScripts;
+
+  constructor(logger: Logger, scripts: HookScripts) {
+    this.logger = logger;
+    this.scripts = {};
+    for (const phase of HOOK_PHASES) {
+      const script = scripts[phase];
+      if (!script) continue;
+      const resolved = path.resolve(script);
+      if (!HOOK_EXTENSIONS.has(path.extname(resolved)) || !fs.existsSync(resolved)) {
+        throw new CodedError(
+          ErrorCode.InvalidHook,
+          `Invalid --hook-${phase} script: ${script} (expected an existing .js or .wasm file)`
+        );
+      }
+      this.scripts[phase] = resolved;
+    }
+  }
+
+  hasHooks(): boolean {
+    return Object.keys(this.scripts).length > 0;
+  }
+
+  async run(phase: HookPhase, context: HookContext): Promise<void> {
+    const script = this.scripts[phase];
+    if (!script) return;
+
+    const env = {
+      HOOK_PHASE: phase,
+      HOOK_RPC_ENDPOINT: context.endpoint,
+      HOOK_REFERENDUM_ID: String(context.referendumId),
+      HOOK_IS_FELLOWSHIP: String(context.isFellowship),
+      HOOK_BLOCK: String(context.block),
+    };
+
+    this.logger.info(`Running ${phase} hook: ${script}`);
+    const exitCode = script.endsWith('.wasm')
+      ? await this.runWasm(script, env)
+      : await this.runNode(script, env);
+
+    if (exitCode !== 0) {
+      throw new CodedError(
+        ErrorCode.HookFailed,
+        `${phase} hook ${path.basename(script)} exited with code ${exitCode}`
+      );
+    }
+    this.logger.success(`${phase} hook passed`);
+  }
+
+  private runNode(script: string, env: Record<string, string>): Promise<number> {
+    return new Promise((resolve, reject) => {
+      const child = spawn(process.execPath, [script], {
+        env: { ...process.env, ...env },
+        stdio: 'inherit',
+      });
+      child.once('error', reject);
+      child.once('exit', (code) => resolve(code ?? 1));
+    });
+  }
+
+  private async runWasm(script: string, env: Record<string, string>): Promise<number> {
+    const wasi = new WASI({ version: 'preview1', args: [script], env, returnOnExit: true });
+    const module = await WebAssembly.compile(fs.readFileSync(script));
+    const instance = await WebAssembly.instantiate(module, wasi.getImportObject());
+    return wasi.start(instance);
+  }
+}
@@ -1,19 +1,50 @@
 import { setupNetworks } from '@acala-network/chopsticks-testing';
+import * as path from 'path';
 import type { PolkadotClient } from 'polkadot-api';
 import type { ChopsticksConfig, TestOptions } from '../types';
-import type { Logger } from '../utils/logger';
+import { CodedError, ErrorCode } from '../utils/error-codes';
+import type { ChainHeadReport, Logger } from '../utils/logger';
 import { FELLOWSHIP_STORAGE_INJECTION } from '../utils/storage-constants';
-import { createApiForChain, createPolkadotClient, getChainInfo } from './chain-registry';
+import { alignTimeline, BlockTimeline } from './block-timeline';
+import { collectChainHeads, displayChainHeads } from './chain-heads';
+import {
+  createApiForChain,
+  createPolkadotClient,
+  getChainInfo,
+  getReferendaPalletName,
+} from './chain-registry';
 import { ChainTopologyBuilder, type TopologyConfig } from './chain-topology-builder';
 import { type ChopsticksContext, ChopsticksManager } from './chopsticks-manager';
+import { parseConviction } from './conviction-approval';
 import { EventCollector } from './event-collector';
-import { SimulationRunner } from './simulation-runner';
+import {
+  discoverReferendaPallets,
+  discoverSubmittedReferendaPallet,
+  type ReferendaInstance,
+  selectReferendaPallet,
+} from './referenda-pallets';
+import { parseCancelPhase } from './referendum-cancellation';
+import { parseReferendumMetadata } from './referendum-metadata';
+import { displayResourceUsage, measureResourceUsage } from './resource-usage';
+import { type ChainRole, checkRuntimeCapabilities } from './runtime-capabilities';
+import { parseScenario } from './scenarios';
+import { shutdown, STOP_METHOD } from './shutdown';
+import { SimulationRunner, type SimulationRunnerOptions } from './simulation-runner';
+import {
+  applyStorageOverrides,
+  mergeFundOverrides,
+  storageOverridesFor,
+} from './storage-overrides';
+import type { XcmDestination } from './xcm-fee-estimator';
 
 interface SingleChainTestConfig {
   endpoint: string;
   block: number | undefined;
+  label: string | undefined;
   referendumId: number | undefined;
   isFellowship: boolean;
+  /** `--referendum <pallet>:<id>`: the referenda pallet instance to target */
+  referendaPallet?: string;
   storageInjection: 'fellowship' | 'alice-account' | undefined;
   createCallHex: string | undefined;
   createPreimageHex: string | undefined;
@@ -21,17 +52,45 @@ interface SingleChainTestConfig {
   cleanup: boolean;
 }
 
+/** Connect to each additional chain, so XCM sent to it can be estimated there. */
+function connectXcmDestinations(managers: Map<string, ChopsticksManager>): {
+  destinations: XcmDestination[];
+  clients: PolkadotClient[];
+} {
+  const clients: PolkadotClient[] = [];
+  const destinations = Array.from(managers).map(([label, manager]) => {
+    const client = createPolkadotClient(manager.getContext().ws.endpoint);
+    clients.push(client);
+    return { label, api: createApiForChain(client) };
+  });
+  return { destinations, clients };
+}
+
+/** What the coordinator's runs get: the runner's options and the settle blocks. */
+export interface NetworkCoordinatorOptions extends SimulationRunnerOptions {
+  /** `--settle-blocks`: blocks to build on additional chains after dispatch */
+  settleBlocks?: number;
+}
+
 export class NetworkCoordinator {
   private logger: Logger;
   private topology: ChainTopologyBuilder;
   private eventCollector: EventCollector;
+  private timeline: BlockTimeline;
   private runner: SimulationRunner;
-
-  constructor(logger: Logger, endpoints: TopologyConfig) {
+  private heads: ChainHeadReport[] = [];
+
+  constructor(
+    logger: Logger,
+    endpoints: TopologyConfig,
+    options: NetworkCoordinatorOptions = {}
+  ) {
+    const { settleBlocks, ...runnerOptions } = options;
     this.logger = logger;
     this.topology = new ChainTopologyBuilder(logger, endpoints);
-    this.eventCollector = new EventCollector(logger);
-    this.runner = new SimulationRunner(logger);
+    this.eventCollector = new EventCollector(logger, settleBlocks);
+    this.timeline = new BlockTimeline(logger);
+    this.runner = new SimulationRunner(logger, runnerOptions);
 
     const additionalChains = endpoints.additionalChains || [];
     this.logger.debug(`Additional chains configured: ${additionalChains.length}`);
@@ -62,16 +121,29 @@ export class NetworkCoordinator {
     return this.topology.governanceChain?.network || 'unknown';
   }
 
+  /** Where each fork started and ended, once the run has shown its summary. */
+  getHeads(): ChainHeadReport[] {
+    return this.heads;
+  }
+
   async testWithFellowship(
     mainReferendumId: number | undefined,
     fellowshipReferendumId: number | undefined,
     cleanup: boolean = true,
-    options?: TestOptions
+    options?: TestOptions,
+    mainPallet?: string
   ): Promise<void> {
     const hasFellowship =
       fellowshipReferendumId !== undefined || !!options?.callToCreateFellowshipReferendum;
     const hasMain = mainReferendumId !== undefined || !!options?.callToCreateGovernanceReferendum;
 
+    if (mainPallet && hasFellowship) {
+      throw new CodedError(
+        ErrorCode.FlagConflict,
+        `--referendum ${mainPallet}:<id> cannot be combined with a fellowship referendum`
+      );
+    }
+
     if (!hasMain && hasFellowship) {
       if (!this.topology.getFellowshipEndpoint()) {
         throw new Error('Fellowship chain URL must be provided when testing fellowship referendum');
@@ -90,6 +162,7 @@ export class NetworkCoordinator {
       return this.runSingleChainTest({
         endpoint: this.topology.getFellowshipEndpoint()!,
         block: this.topology.getFellowshipBlock(),
+        label: this.topology.getFellowshipLabel(),
         referendumId: fellowshipReferendumId,
         isFellowship: true,
         storageInjection: options?.callToCreateFellowshipReferendum ? 'fellowship' : undefined,
@@ -107,14 +180,22 @@ export class NetworkCoordinator {
 
       if (this.topology.hasAdditionalChains()) {
         await this.topology.detectChainTypes();
-        return this.runSingleChainWithAdditionalChains(mainReferendumId, false, cleanup, options);
+        return this.runSingleChainWithAdditionalChains(
+          mainReferendumId,
+          false,
+          cleanup,
+          options,
+          mainPallet
+        );
       }
 
       return this.runSingleChainTest({
         endpoint: this.topology.getGovernanceEndpoint()!,
         block: this.topology.getGovernanceBlock(),
+        label: this.topology.getGovernanceLabel(),
         referendumId: mainReferendumId,
         isFellowship: false,
+        referendaPallet: mainPallet,
         storageInjection: options?.callToCreateGovernanceReferendum ? 'alice-account' : undefined,
         createCallHex: options?.callToCreateGovernanceReferendum,
         createPreimageHex: options?.callToNotePreimageForGovernanceReferendum,
@@ -145,7 +226,7 @@ export class NetworkCoordinator {
 
     const sameEndpoint =
       this.topology.getGovernanceEndpoint() === this.topology.getFellowshipEndpoint() ||
-      this.topology.governanceChain!.label === this.topology.fellowshipChain!.label;
+      this.topology.governanceChain!.id === this.topology.fellowshipChain!.id;
 
     if (sameEndpoint) {
       return this.testSameChainWithFellowship(mainReferendumId, fellowshipReferendumId, options);
@@ -154,6 +235,77 @@ export class NetworkCoordinator {
     return this.testMultiChain(mainReferendumId, fellowshipReferendumId, cleanup, options);
   }
 
+  /** Print what each fork's runtime supports, failing early on what the run needs. */
+  private async probeCapabilities(
+    chains: { label: string; roles: ChainRole[]; client: PolkadotClient }[],
+    options?: TestOptions
+  ): Promise<void> {
+    await checkRuntimeCapabilities(
+      this.logger,
+      chains.map(({ label, roles, client }) => ({
+        label,
+        roles,
+        request: (method, params) => client._request(method, params),
+      })),
+      options ?? {}
+    );
+  }
+
+  /**
+   * Write `--set-storage`, `--override-parameter`, `--fund` and
+   * `--unlock-call-filter` overrides to the fork hosting the main referendum
+   * (the governance chain when both are simulated), before anything runs on it.
+   */
+  private async writeStorageOverrides(
+    client: PolkadotClient,
+    chopsticks: ChopsticksManager,
+    options?: TestOptions
+  ): Promise<void> {
+    const overrides = await mergeFundOverrides(
+      createApiForChain(client),
+      storageOverridesFor(options ?? {})
+    );
+    await applyStorageOverrides(
+      this.logger,
+      (method, params) => client._request(method, params),
+      chopsticks,
+      overrides
+    );
+  }
+
+  /** Find `--referendum <pallet>:<id>`'s pallet among the fork's referenda instances. */
+  private async findReferendaInstance(
+    client: PolkadotClient,
+    pallet: string
+  ): Promise<ReferendaInstance> {
+    const instances = await discoverReferendaPallets((method, params) =>
+      client._request(method, params)
+    );
+    const names = instances.map((i) => (i.ranked ? `${i.name} (ranked)` : i.name));
+    this.logger.debug(`Referenda pallets: ${names.join(', ')}`);
+    return selectReferendaPallet(instances, pallet);
+  }
+
+  /**
+   * The instance to simulate on: the one named by `--referendum <pallet>:<id>`,
+   * or the one a created referendum is submitted to when that is not the
+   * default instance (e.g. `AmbassadorReferenda.submit`).
+   */
+  private async resolveReferendaInstance(
+    client: PolkadotClient,
+    pallet: string | undefined,
+    createCallHex: string | undefined,
+    isFellowship: boolean
+  ): Promise<ReferendaInstance | undefined> {
+    if (pallet) return this.findReferendaInstance(client, pallet);
+    if (!createCallHex) return undefined;
+    const submitted = await discoverSubmittedReferendaPallet(
+      (method, params) => client._request(method, params),
+      createCallHex
+    );
+    return submitted?.name === getReferendaPalletName(isFellowship) ? undefined : submitted;
+  }
+
   private async runSingleChainTest(config: SingleChainTestConfig): Promise<void> {
     const label = config.isFellowship ? 'Fellowship' : 'Governance';
     this.logger.startSpinner(`Starting Chopsticks for ${label.toLowerCase()} chain...`);
@@ -185,25 +337,50 @@ export class NetworkCoordinator {
       await chopsticks.waitForChainReady(api);
       this.logger.succeedSpinner('Chain is ready');
 
-      const chainInfo = await getChainInfo(api, config.endpoint);
+      const chainInfo = await getChainInfo(client, config.endpoint, config.label);
       if (config.isFellowship) {
         this.topology.fellowshipChain = chainInfo;
       } else {
         this.topology.governanceChain = chainInfo;
       }
       this.logger.info(`Detected chain: ${chainInfo.label} (${chainInfo.specName})`);
+      await this.probeCapabilities(
+        [
+          {
+            label: chainInfo.label,
+            roles: [config.isFellowship ? 'fellowship' : 'governance'],
+            client,
+          },
+        ],
+        config.options
+      );
+      await this.writeStorageOverrides(client, chopsticks, config.options);
 
       await this.runner.fetchAndSimulate({
         api,
         chopsticks,
         referendumId: config.referendumId,
         isFellowship: config.isFellowship,
+        referenda: await this.resolveReferendaInstance(
+          client,
+          config.referendaPallet,
+          config.createCallHex,
+          config.isFellowship
+        ),
         createCallHex: config.createCallHex,
         createPreimageHex: config.createPreimageHex,
+        createMetadata: parseReferendumMetadata(config.options ?? {}, config.isFellowship),
         preCall: config.options?.preCall,
         preOrigin: config.options?.preOrigin,
+        scenario: parseScenario(config.options?.scenario),
+        cancelAt: parseCancelPhase(config.options?.cancelAt),
+        conviction: parseConviction(config.options?.voteConviction),
       });
     } finally {
+      const chain = config.isFellowship
+        ? this.topology.fellowshipChain
+        : this.topology.governanceChain;
+      await this.displaySummary([{ label: chain?.label ?? label, manager: chopsticks }]);
       if (client) {
         client.destroy();
       }
@@ -211,6 +388,7 @@ export class NetworkCoordinator {
         await chopsticks.cleanup();
       } else {
         this.logger.info(`\nChopsticks instance still running for inspection`);
+        await this.logStopEndpoint();
         this.logger.info('Press Ctrl+C to exit');
         await chopsticks.pause();
       }
@@ -221,7 +399,8 @@ export class NetworkCoordinator {
     referendumId: number | undefined,
     isFellowship: boolean,
     cleanup: boolean,
-    options?: TestOptions
+    options?: TestOptions,
+    referendaPallet?: string
   ): Promise<void> {
     const mainChain = isFellowship
       ? this.topology.fellowshipChain!
@@ -232,8 +411,14 @@ export class NetworkCoordinator {
     const mainBlock = isFellowship
       ? this.topology.getFellowshipBlock()
       : this.topology.getGovernanceBlock();
+    const mainLabel = isFellowship
+      ? this.topology.getFellowshipLabel()
+      : this.topology.getGovernanceLabel();
     const mainIsRelay = mainChain.kind === 'relay';
     const label = isFellowship ? 'Fellowship' : 'Governance';
+    const createCallHex = isFellowship
+      ? options?.callToCreateFellowshipReferendum
+      : options?.callToCreateGovernanceReferendum;
 
     const mainKey = mainIsRelay
       ? this.topology.getRelayKey(mainChain.network)
@@ -286,6 +471,7 @@ export class NetworkCoordinator {
     }
 
     const mainClient = createPolkadotClient(mainManager.getContext().ws.endpoint);
+    const xcm = connectXcmDestinations(additionalManagers);
 
     try {
       const api = createApiForChain(mainClient);
@@ -294,32 +480,71 @@ export class NetworkCoordinator {
       await mainManager.waitForChainReady(api);
       this.logger.succeedSpinner('Chain is ready');
 
-      const chainInfo = await getChainInfo(api, mainEndpoint);
+      const chainInfo = await getChainInfo(mainClient, mainEndpoint, mainLabel);
       if (isFellowship) {
         this.topology.fellowshipChain = chainInfo;
       } else {
         this.topology.governanceChain = chainInfo;
       }
       this.logger.info(`Detected chain: ${chainInfo.label} (${chainInfo.specName})`);
+      await this.probeCapabilities(
+        [
+          {
+            label: chainInfo.label,
+            roles: [isFellowship ? 'fellowship' : 'governance'],
+            client: mainClient,
+          },
+        ],
+        options
+      );
+      await this.writeStorageOverrides(mainClient, mainManager, options);
 
       await this.runner.fetchAndSimulate({
         api,
         chopsticks: mainManager,
         referendumId,
         isFellowship,
-        createCallHex: isFellowship
-          ? options?.callToCreateFellowshipReferendum
-          : options?.callToCreateGovernanceReferendum,
+        referenda: await this.resolveReferendaInstance(
+          mainClient,
+          referendaPallet,
+          createCallHex,
+          isFellowship
+        ),
+        createCallHex,
         createPreimageHex: isFellowship
           ? options?.callToNotePreimageForFellowshipReferendum
           : options?.callToNotePreimageForGovernanceReferendum,
+        createMetadata: parseReferendumMetadata(options ?? {}, isFellowship),
         preCall: options?.preCall,
         preOrigin: options?.preOrigin,
+        scenario: parseScenario(options?.scenario),
+        cancelAt: parseCancelPhase(options?.cancelAt),
+        conviction: parseConviction(options?.voteConviction),
+        xcmDestinations: xcm.destinations,
       });
 
       await this.eventCollector.collectAdditionalChainEvents(additionalManagers);
+      if (additionalManagers.size > 0) {
+        await this.displayTimeline([
+          { label: chainInfo.label, manager: mainManager },
+          ...Array.from(additionalManagers).map(([chainLabel, manager]) => ({
+            label: chainLabel,
+            manager,
+          })),
+        ]);
+      }
     } finally {
+      await this.displaySummary([
+        { label: mainChain.label, manager: mainManager },
+        ...Array.from(additionalManagers).map(([chainLabel, manager]) => ({
+          label: chainLabel,
+          manager,
+        })),
+      ]);
       mainClient.destroy();
+      for (const client of xcm.clients) {
+        client.destroy();
+      }
 
       if (cleanup) {
         await Promise.all([
@@ -343,6 +568,8 @@ export class NetworkCoordinator {
   ): Promise<Record<string, unknown>> {
     const chopsticksConfig: Record<string, unknown> = {
       endpoint: config.endpoint,
+      db: this.topology.getDbPath(),
+      port: this.topology.nextPort(),
       'build-block-mode': 'manual',
     };
 
@@ -377,6 +604,8 @@ export class NetworkCoordinator {
 
       const config: Record<string, unknown> = {
         endpoint: chainEndpoint,
+        db: this.topology.getDbPath(),
+        port: this.topology.nextPort(),
         'build-block-mode': 'manual',
       };
 
@@ -402,11 +631,26 @@ export class NetworkCoordinator {
       await chopsticks.waitForChainReady(api);
       this.logger.succeedSpinner('Chain is ready');
 
-      this.topology.governanceChain = await getChainInfo(api, chainEndpoint);
+      this.topology.governanceChain = await getChainInfo(
+        client,
+        chainEndpoint,
+        this.topology.getGovernanceLabel() ?? this.topology.getFellowshipLabel()
+      );
       this.topology.fellowshipChain = this.topology.governanceChain;
       this.logger.info(
         `Detected chain: ${this.topology.governanceChain.label} (${this.topology.governanceChain.specName})`
       );
+      await this.probeCapabilities(
+        [
+          {
+            label: this.topology.governanceChain.label,
+            roles: ['governance', 'fellowship'],
+            client,
+          },
+        ],
+        options
+      );
+      await this.writeStorageOverrides(client, chopsticks, options);
 
       const createdFellowship = await this.runner.createReferendumIfNeeded({
         api,
@@ -414,6 +658,7 @@ export class NetworkCoordinator {
         callHex: options?.callToCreateFellowshipReferendum,
         preimageHex: options?.callToNotePreimageForFellowshipReferendum,
         isFellowship: true,
+        metadata: parseReferendumMetadata(options ?? {}, true),
       });
       const actualFellowshipId = createdFellowship ?? fellowshipReferendumId;
 
@@ -423,6 +668,7 @@ export class NetworkCoordinator {
         callHex: options?.callToCreateGovernanceReferendum,
         preimageHex: options?.callToNotePreimageForGovernanceReferendum,
         isFellowship: false,
+        metadata: parseReferendumMetadata(options ?? {}, false),
       });
       const actualMainId = createdGovernance ?? mainReferendumId;
 
@@ -441,6 +687,9 @@ export class NetworkCoordinator {
         actualMainId
       );
     } finally {
+      await this.displaySummary([
+        { label: this.topology.governanceChain?.label ?? 'Shared', manager: chopsticks },
+      ]);
       if (client) {
         client.destroy();
       }
@@ -459,6 +708,7 @@ export class NetworkCoordinator {
 
     const governanceClient = createPolkadotClient(governanceManager.getContext().ws.endpoint);
     const fellowshipClient = createPolkadotClient(fellowshipManager.getContext().ws.endpoint);
+    const xcm = connectXcmDestinations(additionalManagers);
 
     this.logger.succeedSpinner('Networks ready');
     this.logger.info(`  Governance: ${governanceManager.getContext().ws.endpoint}`);
@@ -476,13 +726,29 @@ export class NetworkCoordinator {
       this.logger.succeedSpinner('Chains are ready');
 
       const [govChainInfo, fellChainInfo] = await Promise.all([
-        getChainInfo(governanceApi, this.topology.getGovernanceEndpoint()!),
-        getChainInfo(fellowshipApi, this.topology.getFellowshipEndpoint()!),
+        getChainInfo(
+          governanceClient,
+          this.topology.getGovernanceEndpoint()!,
+          this.topology.getGovernanceLabel()
+        ),
+        getChainInfo(
+          fellowshipClient,
+          this.topology.getFellowshipEndpoint()!,
+          this.topology.getFellowshipLabel()
+        ),
       ]);
       this.topology.governanceChain = govChainInfo;
       this.topology.fellowshipChain = fellChainInfo;
       this.logger.info(`Governance: ${govChainInfo.label} (${govChainInfo.specName})`);
       this.logger.info(`Fellowship: ${fellChainInfo.label} (${fellChainInfo.specName})`);
+      await this.probeCapabilities(
+        [
+          { label: govChainInfo.label, roles: ['governance'], client: governanceClient },
+          { label: fellChainInfo.label, roles: ['fellowship'], client: fellowshipClient },
+        ],
+        options
+      );
+      await this.writeStorageOverrides(governanceClient, governanceManager, options);
 
       const createdFellowship = await this.runner.createReferendumIfNeeded({
         api: fellowshipApi,
@@ -490,6 +756,7 @@ export class NetworkCoordinator {
         callHex: options?.callToCreateFellowshipReferendum,
         preimageHex: options?.callToNotePreimageForFellowshipReferendum,
         isFellowship: true,
+        metadata: parseReferendumMetadata(options ?? {}, true),
       });
       const actualFellowshipId = createdFellowship ?? fellowshipReferendumId;
 
@@ -499,6 +766,7 @@ export class NetworkCoordinator {
         callHex: options?.callToCreateGovernanceReferendum,
         preimageHex: options?.callToNotePreimageForGovernanceReferendum,
         isFellowship: false,
+        metadata: parseReferendumMetadata(options ?? {}, false),
       });
       const actualMainId = createdGovernance ?? mainReferendumId;
 
@@ -523,6 +791,7 @@ export class NetworkCoordinator {
           referendumId: actualMainId,
           label: this.topology.governanceChain!.label,
         },
+        additionalChains: xcm.destinations,
       });
 
       await this.eventCollector.displayPostExecutionEvents({
@@ -532,9 +801,22 @@ export class NetworkCoordinator {
         governanceLabel: this.topology.governanceChain!.label,
         fellowshipLabel: this.topology.fellowshipChain!.label,
       });
+      await this.displayTimeline([
+        { label: this.topology.governanceChain!.label, manager: governanceManager },
+        { label: this.topology.fellowshipChain!.label, manager: fellowshipManager },
+        ...Array.from(additionalManagers).map(([label, manager]) => ({ label, manager })),
+      ]);
     } finally {
+      await this.displaySummary([
+        { label: this.topology.governanceChain!.label, manager: governanceManager },
+        { label: this.topology.fellowshipChain!.label, manager: fellowshipManager },
+        ...Array.from(additionalManagers).map(([label, manager]) => ({ label, manager })),
+      ]);
       governanceClient.destroy();
       fellowshipClient.destroy();
+      for (const client of xcm.clients) {
+        client.destroy();
+      }
 
       if (cleanup) {
         await Promise.all([
@@ -558,6 +840,49 @@ export class NetworkCoordinator {
     }
   }
 
+  /** Show the blocks built on every fork aligned by relay chain height. */
+  private async displayTimeline(
+    chains: Array<{ label: string; manager: ChopsticksManager }>
+  ): Promise<void> {
+    const blocks = await this.timeline.collect(chains);
+    this.timeline.display(alignTimeline(blocks));
+  }
+
+  /**
+   * Show where every fork started and ended and what the run used, also when
+   * it failed, and keep each fork's runtime in the work dir.
+   */
+  private async displaySummary(
+    chains: Array<{ label: string; manager: ChopsticksManager }>
+  ): Promise<void> {
+    this.heads = collectChainHeads(chains);
+    displayChainHeads(this.logger, this.heads);
+    displayResourceUsage(this.logger, measureResourceUsage());
+    await this.saveRuntimes(chains);
+  }
+
+  /** Write each fork's runtime to `<work dir>/<chain>.wasm`, for inspecting a failed run. */
+  private async saveRuntimes(
+    chains: Array<{ label: string; manager: ChopsticksManager }>
+  ): Promise<void> {
+    const workDir = this.topology.getWorkDir();
+    if (!workDir) return;
+    for (const { label, manager } of chains) {
+      const file = path.join(workDir, `${label.replace(/[^A-Za-z0-9._-]+/g, '_')}.wasm`);
+      try {
+        if (await manager.saveRuntime(file)) this.logger.debug(`Runtime of ${label}: ${file}`);
+      } catch (error) {
+        this.logger.debug(`Could not keep the runtime of ${label}: ${(error as Error).message}`);
+      }
+    }
+  }
+
+  /** Name the `dev_stop` endpoint that stops paused forks without a signal. */
+  private async logStopEndpoint(): Promise<void> {
+    const url = await shutdown.serveControl();
+    this.logger.info(`Stop with a JSON-RPC ${STOP_METHOD} request to ${url}`);
+  }
+
   private async pauseAllManagers(
     managers: Array<{ label: string; manager: ChopsticksManager }>
   ): Promise<void> {
@@ -573,6 +898,7 @@ export class NetworkCoordinator {
       }
     }
 
+    await this.logStopEndpoint();
     this.logger.info('Press Ctrl+C to exit');
     this.logger.info('='.repeat(70));
 
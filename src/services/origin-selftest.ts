@@ -0,0 +1,159 @@
+import { decAnyMetadata, unifyMetadata } from '@polkadot-api/substrate-bindings';
+import { Binary } from 'polkadot-api';
+import type { SubstrateApi } from '../types/substrate-api';
+import { getBlockEvents } from '../utils/event-serializer';
+import type { Logger } from '../utils/logger';
+import type { RpcRequest } from './call-drift-detector';
+import type { ChopsticksManager } from './chopsticks-manager';
+import { ExecutionResultChecker } from './execution-result-checker';
+import { parseOriginString } from './referendum-simulator';
+import { SchedulerManager } from './scheduler-manager';
+
+type Field = { name?: string; type: number };
+type Variant = { name: string; fields: Field[] };
+
+/** The subset of (unified) metadata needed to list the runtime's origins. */
+export interface OriginMetadata {
+  lookup: { id: number; path: string[]; def: { tag: string; value: unknown } }[];
+  pallets: { name: string }[];
+}
+
+/** One variant of the runtime's `OriginCaller`, e.g. `Origins.WhitelistedCaller`. */
+export interface ChainOrigin {
+  /** `Pallet.Variant`, as `--pre-origin` takes it */
+  name: string;
+  /** Fields the origin carries, e.g. the account of `system.Signed` */
+  fields: number;
+}
+
+/** How dispatching a remark from one origin fared. */
+export interface OriginCheck extends ChainOrigin {
+  /** `unsupported`: the origin carries fields, which `Pallet.Variant` can't give */
+  outcome: 'ok' | 'failed' | 'unsupported';
+  reason?: string;
+}
+
+function variantsOf(metadata: OriginMetadata, type: number): Variant[] {
+  const def = metadata.lookup.find((t) => t.id === type)?.def;
+  return def?.tag === 'variant' ? (def.value as Variant[]) : [];
+}
+
+/**
+ * Every origin of the runtime's `OriginCaller` enum: one per variant of each
+ * pallet's origin enum (`system`, `Origins`, `FellowshipOrigins`, ...), in
+ * metadata order.
+ */
+export function findOrigins(metadata: OriginMetadata): ChainOrigin[] {
+  const caller = metadata.lookup.find((t) => t.path.at(-1) === 'OriginCaller');
+  if (!caller) {
+    throw new Error('Runtime metadata has no OriginCaller type');
+  }
+  return variantsOf(metadata, caller.id).flatMap((pallet) => {
+    const inner = pallet.fields[0];
+    if (!inner) return [];
+    return variantsOf(metadata, inner.type).map((origin) => ({
+      name: `${pallet.name}.${origin.name}`,
+      fields: origin.fields.length,
+    }));
+  });
+}
+
+/** Read the fork's metadata for {@link findOrigins}. */
+export async function readOriginMetadata(request: RpcRequest): Promise<OriginMetadata> {
+  const hex: string = await request('state_getMetadata', []);
+  return unifyMetadata(decAnyMetadata(hex)) as unknown as OriginMetadata;
+}
+
+export function formatOriginTable(checks: OriginCheck[]): string[] {
+  const results = { ok: 'OK', failed: 'FAIL', unsupported: 'SKIP' };
+  const rows = checks.map((check) => [check.name, results[check.outcome], check.reason ?? '']);
+  const header = ['Origin', 'Result', 'Reason'];
+  const widths = header.map((title, column) =>
+    Math.max(title.length, ...rows.map((row) => row[column].length))
+  );
+  return [header, ...rows].map((row) =>
+    row
+      .map((cell, column) => (column === row.length - 1 ? cell : cell.padEnd(widths[column])))
+      .join('  ')
+      .trimEnd()
+  );
+}
+
+/**
+ * Dispatches `System.remark` from each origin of the runtime through the same
+ * machinery as `--pre-call`: the origin is parsed from its `Pallet.Variant`
+ * name, injected into the scheduler agenda with the call and dispatched in
+ * the next block. `remark` accepts any origin, so a failure means the tool
+ * could not construct the origin, not that the call rejected it.
+ */
+export class OriginSelfTest {
+  private logger: Logger;
+  private chopsticks: ChopsticksManager;
+  private api: SubstrateApi;
+  private scheduler: SchedulerManager;
+  private resultChecker: ExecutionResultChecker;
+
+  constructor(
+    logger: Logger,
+    chopsticks: ChopsticksManager,
+    api: SubstrateApi,
+    isFellowship: boolean
+  ) {
+    this.logger = logger;
+    this.chopsticks = chopsticks;
+    this.api = api;
+    this.scheduler = new SchedulerManager(logger, chopsticks, api, isFellowship);
+    this.resultChecker = new ExecutionResultChecker(logger);
+  }
+
+  async run(origins: ChainOrigin[]): Promise<OriginCheck[]> {
+    const remark = await this.api.tx.System.remark({
+      remark: Binary.fromText('polkadot-referenda-tester origin selftest'),
+    }).getEncodedData();
+
+    const checks: OriginCheck[] = [];
+    for (const [n, origin] of origins.entries()) {
+      const check = await this.check(origin, remark.asHex());
+      const progress = `[${n + 1}/${origins.length}] ${origin.name}`;
+      if (check.outcome === 'ok') {
+        this.logger.success(`${progress} dispatches`);
+      } else if (check.outcome === 'failed') {
+        this.logger.error(`${progress} fails: ${check.reason}`);
+      } else {
+        this.logger.info(`${progress} skipped: ${check.reason}`);
+      }
+      checks.push(check);
+    }
+    return checks;
+  }
+
+  private async check(origin: ChainOrigin, remarkHex: string): Promise<OriginCheck> {
+    if (origin.fields > 0) {
+      return {
+        ...origin,
+        outcome: 'unsupported',
+        reason: `carries ${origin.fields} field(s), which Pallet.Variant can't give`,
+      };
+    }
+
+    const storageOrigin = parseOriginString(origin.name, this.logger);
+    let block: number;
+    try {
+      block = await this.scheduler.scheduleCallAtNextBlock(remarkHex, storageOrigin);
+    } catch (error) {
+      const reason = `does not encode: ${(error as Error).message}`;
+      return { ...origin, outcome: 'failed', reason };
+    }
+    await this.chopsticks.newBlock();
+
+    const events = await getBlockEvents(this.api.query.System.Events, this.logger);
+    const { executionSucceeded, errors } = this.resultChecker.checkExecutionResults(
+      events,
+      block,
+      0
+    );
+    return executionSucceeded
+      ? { ...origin, outcome: 'ok' }
+      : { ...origin, outcome: 'failed', reason: errors?.join('; ') ?? 'not dispatched' };
+  }
+}
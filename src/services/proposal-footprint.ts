@@ -0,0 +1,81 @@
+import type { Logger } from '../utils/logger';
+import { formatTokenAmount, type NativeToken } from '../utils/token-format';
+
+/** `BoundedInline`: proposals up to this many bytes can be submitted inline. */
+export const INLINE_MAX_LEN = 128;
+
+/** `pallet_preimage::MAX_SIZE`: the largest preimage `note_preimage` accepts. */
+export const PREIMAGE_MAX_SIZE = 4 * 1024 * 1024;
+
+/** Share of a bound above which a proposal is reported as close to it. */
+export const NEAR_BOUND_RATIO = 0.9;
+
+/** How large a created referendum's proposal is, and what noting it cost. */
+export interface ProposalFootprint {
+  /** `Inline`, `Lookup` or `Legacy` */
+  type: string;
+  /** Encoded length of the proposal call, when known */
+  len?: number;
+  /** Bytes stored in `Preimage.PreimageFor` by the preimage call */
+  preimageLen?: number;
+  /** Balance held from the preimage's depositor for storing it */
+  preimageDeposit?: bigint;
+  warnings: string[];
+}
+
+/** The `Bounded` limit a proposal of `type` is subject to, if any. */
+export function proposalBound(type: string): number | undefined {
+  if (type === 'Inline') return INLINE_MAX_LEN;
+  if (type === 'Lookup') return PREIMAGE_MAX_SIZE;
+  return undefined;
+}
+
+/** Warnings for a proposal over, or within `NEAR_BOUND_RATIO` of, its bound. */
+export function boundWarnings(type: string, len: number | undefined): string[] {
+  const bound = proposalBound(type);
+  if (bound === undefined || len === undefined) return [];
+  if (len > bound) {
+    return [`${type} proposal of ${len} bytes exceeds the ${bound}-byte bound`];
+  }
+  if (len >= bound * NEAR_BOUND_RATIO) {
+    const percent = Math.round((len / bound) * 100);
+    return [`${type} proposal of ${len} bytes is at ${percent}% of the ${bound}-byte bound`];
+  }
+  return [];
+}
+
+export function proposalFootprint(
+  proposal: { type: string; len?: number },
+  preimage?: { len: number; deposit?: bigint }
+): ProposalFootprint {
+  return {
+    type: proposal.type,
+    len: proposal.len,
+    preimageLen: preimage?.len,
+    preimageDeposit: preimage?.deposit,
+    warnings: boundWarnings(proposal.type, proposal.len ?? preimage?.len),
+  };
+}
+
+export function displayProposalFootprint(
+  logger: Logger,
+  label: string,
+  footprint: ProposalFootprint,
+  token?: NativeToken
+): void {
+  const bound = proposalBound(footprint.type);
+  logger.info(`${label} proposal size:`);
+  logger.table({
+    Proposal: `${footprint.type} (${footprint.len ?? '?'} bytes)`,
+    Bound: bound === undefined ? 'none' : `${bound} bytes`,
+    Preimage:
+      footprint.preimageLen === undefined ? 'none noted' : `${footprint.preimageLen} bytes`,
+    'Preimage deposit':
+      footprint.preimageDeposit === undefined
+        ? 'unknown'
+        : formatTokenAmount(footprint.preimageDeposit, token),
+  });
+  for (const warning of footprint.warnings) {
+    logger.warn(warning);
+  }
+}
@@ -0,0 +1,258 @@
+import { Binary } from 'polkadot-api';
+import type { LintFinding, LintLevel, ReferendumInfo } from '../types';
+import type { SubstrateApi } from '../types/substrate-api';
+import { CodedError, ErrorCode } from '../utils/error-codes';
+import { stringify } from '../utils/json';
+import type { Logger } from '../utils/logger';
+import { findAccountId32 } from '../utils/xcm-location';
+import type { XcmFeeEstimator } from './xcm-fee-estimator';
+
+/** A call decoded by polkadot-api: `{ type: pallet, value: { type: call, value: args } }` */
+export interface DecodedCall {
+  type: string;
+  value: { type: string; value?: Record<string, unknown> };
+}
+
+/** One call of the proposal tree, with the origin it will be dispatched from. */
+export interface LintTarget {
+  call: DecodedCall;
+  /** Whether the call dispatches as Root (referendum origin, whitelist or dispatch_as) */
+  root: boolean;
+}
+
+interface LintContext {
+  api: SubstrateApi;
+  referendum: ReferendumInfo;
+  /** Set when chains the proposal may send XCM to are forked alongside */
+  xcm?: XcmFeeEstimator;
+}
+
+export interface LintRule {
+  id: string;
+  defaultLevel: LintLevel;
+  check(target: LintTarget, context: LintContext): Promise<string | undefined>;
+}
+
+/** Calls that only Root may dispatch; on any other track they fail with BadOrigin. */
+const ROOT_ONLY_CALLS: Record<string, Set<string> | '*'> = {
+  System: new Set([
+    'set_code',
+    'set_code_without_checks',
+    'set_storage',
+    'kill_storage',
+    'kill_prefix',
+    'set_heap_pages',
+    'authorize_upgrade',
+    'authorize_upgrade_without_checks',
+  ]),
+  Utility: new Set(['dispatch_as']),
+  Scheduler: '*',
+  Sudo: '*',
+  Balances: new Set([
+    'force_transfer',
+    'force_set_balance',
+    'force_unreserve',
+    'force_adjust_total_issuance',
+  ]),
+};
+
+function callName(call: DecodedCall): string {
+  return `${call.type}.${call.value.type}`;
+}
+
+function isRootOrigin(origin: unknown): boolean {
+  const o = origin as { type?: string; value?: { type?: string } } | undefined;
+  return o?.type === 'system' && o.value?.type === 'Root';
+}
+
+export const LINT_RULES: LintRule[] = [
+  {
+    id: 'treasury-spend-beneficiary-proxy',
+    defaultLevel: 'warn',
+    async check({ call }, { api }) {
+      if (call.type !== 'Treasury' || !call.value.type.startsWith('spend')) return undefined;
+      const account = findAccountId32(call.value.value?.beneficiary);
+      if (!account) {
+        return 'beneficiary is not a plain account; its controllers could not be checked';
+      }
+      const proxies = await api.query.Proxy?.Proxies.getValue(account);
+      const delegates = proxies?.[0] ?? [];
+      if (delegates.length === 0) return undefined;
+      const list = delegates.map((p) => `${p.delegate} (${p.proxy_type.type})`).join(', ');
+      return `beneficiary ${account} is controlled through proxies: ${list}`;
+    },
+  },
+  {
+    id: 'set-code-without-authorize-upgrade',
+    defaultLevel: 'warn',
+    async check({ call }) {
+      if (call.type !== 'System' || !call.value.type.startsWith('set_code')) return undefined;
+      return 'runtime upgrade via set_code; prefer System.authorize_upgrade so the code is checked and applied separately';
+    },
+  },
+  {
+    id: 'sudo-like-call-on-non-root-track',
+    defaultLevel: 'warn',
+    async check({ call, root }, { referendum }) {
+      const calls = ROOT_ONLY_CALLS[call.type];
+      if (root || !calls || (calls !== '*' && !calls.has(call.value.type))) return undefined;
+      return `Root-only call on track ${referendum.track} (origin ${stringify(referendum.origin)}); it will fail with BadOrigin`;
+    },
+  },
+  {
+    id: 'xcm-underfunded',
+    defaultLevel: 'warn',
+    async check({ call }, { xcm }) {
+      const estimate = await xcm?.estimate(call);
+      if (!estimate || estimate.problems.length === 0) return undefined;
+      return `XCM to ${estimate.destination}: ${estimate.problems.join('; ')}`;
+    },
+  },
+  {
+    id: 'xcm-sovereign-account-underfunded',
+    defaultLevel: 'warn',
+    async check({ call }, { xcm }) {
+      const estimate = await xcm?.estimate(call);
+      const warnings = estimate?.sovereignAccount?.warnings ?? [];
+      if (!estimate || warnings.length === 0) return undefined;
+      return `XCM to ${estimate.destination}: ${warnings.join('; ')}`;
+    },
+  },
+];
+
+/**
+ * Parse `--lint rule=level,...` and `--deny warnings` into per-rule levels.
+ */
+export function parseLintLevels(lint?: string, deny?: string): Record<string, LintLevel> {
+  const levels: Record<string, LintLevel> = Object.fromEntries(
+    LINT_RULES.map((rule) => [rule.id, rule.defaultLevel])
+  );
+
+  for (const entry of (lint ?? '').split(',').filter((e) => e.trim())) {
+    const [rule, level] = entry.split('=').map((part) => part.trim());
+    if (!(rule in levels) || !['allow', 'warn', 'deny'].includes(level)) {
+      throw new CodedError(
+        ErrorCode.InvalidLintConfig,
+        `Invalid --lint entry: ${entry} (expected <rule>=allow|warn|deny; rules: ${Object.keys(levels).join(', ')})`
+      );
+    }
+    levels[rule] = level as LintLevel;
+  }
+
+  if (deny !== undefined) {
+    if (deny !== 'warnings') {
+      throw new CodedError(ErrorCode.InvalidLintConfig, `Invalid --deny value: ${deny}`);
+    }
+    for (const rule of Object.keys(levels)) {
+      if (levels[rule] === 'warn') levels[rule] = 'deny';
+    }
+  }
+
+  return levels;
+}
+
+/**
+ * Walk a decoded call tree: batches inherit the origin, whitelisted calls
+ * dispatch as Root and `dispatch_as` switches to its `as_origin`.
+ */
+export function collectCalls(call: DecodedCall, root: boolean): LintTarget[] {
+  const targets: LintTarget[] = [{ call, root }];
+  const args = call.value.value ?? {};
+
+  let innerRoot = root;
+  if (call.type === 'Whitelist' && call.value.type.startsWith('dispatch_whitelisted_call')) {
+    innerRoot = true;
+  } else if (call.type === 'Utility' && call.value.type === 'dispatch_as') {
+    innerRoot = isRootOrigin(args.as_origin);
+  }
+
+  const inner = [
+    ...(args.call ? [args.call] : []),
+    ...(Array.isArray(args.calls) ? args.calls : []),
+  ] as DecodedCall[];
+  for (const child of inner) {
+    if (child && typeof child.type === 'string' && child.value) {
+      targets.push(...collectCalls(child, innerRoot));
+    }
+  }
+  return targets;
+}
+
+/** Lint pass over a referendum's decoded proposal. */
+export class ProposalLinter {
+  private logger: Logger;
+  private levels: Record<string, LintLevel>;
+
+  constructor(logger: Logger, levels: Record<string, LintLevel>) {
+    this.logger = logger;
+    this.levels = levels;
+  }
+
+  /**
+   * Decode the proposal (inline, or via `Preimage.PreimageFor` for lookups) and
+   * run every rule that isn't allowed. Findings are logged and returned. `xcm`
+   * estimates the XCM the proposal sends on the chains it is sent to.
+   */
+  async lint(
+    api: SubstrateApi,
+    referendum: ReferendumInfo,
+    xcm?: XcmFeeEstimator
+  ): Promise<LintFinding[]> {
+    const call = await this.decodeProposal(api, referendum);
+    if (!call) return [];
+
+    const findings: LintFinding[] = [];
+    const context = { api, referendum, xcm };
+    for (const target of collectCalls(call, isRootOrigin(referendum.origin))) {
+      for (const rule of LINT_RULES) {
+        const level = this.levels[rule.id] ?? rule.defaultLevel;
+        if (level === 'allow') continue;
+        const message = await rule.check(target, context);
+        if (message) {
+          findings.push({ rule: rule.id, level, message, call: callName(target.call) });
+        }
+      }
+    }
+
+    this.logger.section(`Proposal Lints (referendum #${referendum.id})`);
+    if (findings.length === 0) {
+      this.logger.success('No lint findings');
+    }
+    for (const finding of findings) {
+      const line = `${finding.level}[${finding.rule}] ${finding.call}: ${finding.message}`;
+      if (finding.level === 'deny') {
+        this.logger.error(line);
+      } else {
+        this.logger.warn(line);
+      }
+    }
+    return findings;
+  }
+
+  private async decodeProposal(
+    api: SubstrateApi,
+    referendum: ReferendumInfo
+  ): Promise<DecodedCall | undefined> {
+    const { proposal } = referendum;
+    try {
+      let bytes: Binary | undefined;
+      if (proposal.type === 'Inline' && proposal.call) {
+        bytes = proposal.call as Binary;
+      } else if (proposal.type === 'Lookup' && proposal.len !== undefined) {
+        bytes = await api.query.Preimage?.PreimageFor.getValue([
+          Binary.fromHex(proposal.hash),
+          proposal.len,
+        ]);
+      }
+      if (!bytes) {
+        this.logger.debug(`Proposal of referendum #${referendum.id} unavailable; skipping lints`);
+        return undefined;
+      }
+      const decoded = await api.txFromCallData(bytes);
+      return decoded.decodedCall as DecodedCall;
+    } catch (error) {
+      this.logger.warn(`Could not decode proposal for linting: ${(error as Error).message}`);
+      return undefined;
+    }
+  }
+}
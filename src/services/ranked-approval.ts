@@ -0,0 +1,115 @@
+import type { SS58String } from 'polkadot-api';
+import type { SyntheticVote } from '../types';
+import type { SubstrateApi, UnsafeTransaction } from '../types/substrate-api';
+import { formatDispatchError } from '../utils/dispatch-result';
+import { CodedError, ErrorCode } from '../utils/error-codes';
+import { getBlockEvents } from '../utils/event-serializer';
+import type { Logger } from '../utils/logger';
+import {
+  ALICE_ADDRESS,
+  RANKED_COLLECTIVE_MAX_RANK,
+  rankedCollectiveInjection,
+} from '../utils/storage-constants';
+import type { ChopsticksManager } from './chopsticks-manager';
+import { mockSigner } from './delegated-submission';
+
+/** The tally of a ranked-collective referendum before any synthetic vote. */
+export const EMPTY_RANKED_TALLY = { bare_ayes: 0, ayes: 0, nays: 0 };
+
+/**
+ * The `pallet_ranked_collective` instance voting on a referenda instance,
+ * by the runtimes' naming convention: `FellowshipReferenda` is voted on by
+ * `FellowshipCollective`, `AmbassadorReferenda` by `AmbassadorCollective`.
+ */
+export function collectivePalletName(referendaPallet: string): string {
+  return `${referendaPallet.replace(/Referenda$/, '')}Collective`;
+}
+
+interface VotedEvent {
+  who: SS58String;
+  poll: number;
+  vote: { type: string; value: unknown };
+  tally: { bare_ayes: unknown; ayes: unknown; nays: unknown };
+}
+
+/**
+ * Approves a ranked-collective referendum the way its members would: the
+ * collective is reduced to the injected members, and each of them casts an
+ * aye with `vote`. The runtime scales each vote by the voter's rank above the
+ * track's minimum rank and checks the tally against the track's approval and
+ * support curves, so no tally is written by hand.
+ */
+export class RankedApprover {
+  private logger: Logger;
+  private chopsticks: ChopsticksManager;
+  private api: SubstrateApi;
+  private collective: string;
+
+  constructor(
+    logger: Logger,
+    chopsticks: ChopsticksManager,
+    api: SubstrateApi,
+    referendaPallet: string
+  ) {
+    this.logger = logger;
+    this.chopsticks = chopsticks;
+    this.api = api;
+    this.collective = collectivePalletName(referendaPallet);
+  }
+
+  /**
+   * Make the injected members the collective's only members and forget
+   * earlier votes, which were counted in the tally being reset.
+   */
+  async injectMembers(): Promise<void> {
+    const injection = rankedCollectiveInjection(this.collective);
+    const members = injection[this.collective] as { $removePrefix: string[] };
+    await this.chopsticks.setStorageBatch({
+      ...injection,
+      [this.collective]: { ...members, $removePrefix: [...members.$removePrefix, 'Voting'] },
+    });
+  }
+
+  /** Vote aye on `referendumId` from every injected member and report the votes. */
+  async approve(referendumId: number): Promise<SyntheticVote[]> {
+    const tx = (this.api.tx as unknown as Record<string, Record<string, unknown>>)[
+      this.collective
+    ];
+    if (typeof tx?.vote !== 'function') {
+      throw new CodedError(
+        ErrorCode.ReferendumNotApproved,
+        `Cannot vote on referendum #${referendumId}: chain has no ${this.collective}.vote`
+      );
+    }
+
+    this.logger.startSpinner(`Voting aye from ${this.collective} members...`);
+    const vote = tx.vote as (args: { poll: number; aye: boolean }) => UnsafeTransaction;
+    const signed = await vote({ poll: referendumId, aye: true }).sign(mockSigner(ALICE_ADDRESS));
+    await this.chopsticks.newBlock({ transactions: [signed] });
+
+    const events = await getBlockEvents(this.api.query.System.Events, this.logger);
+    const voted = events.filter((e) => e.section === this.collective && e.method === 'Voted');
+    if (voted.length === 0) {
+      const failed = events.find((e) => e.section === 'System' && e.method === 'ExtrinsicFailed');
+      this.logger.failSpinner('Synthetic vote was not accepted');
+      throw new CodedError(
+        ErrorCode.ReferendumNotApproved,
+        `${this.collective}.vote on referendum #${referendumId} was not accepted${failed ? `: ${formatDispatchError(failed.data)}` : ''}`
+      );
+    }
+
+    const votes = voted.map((event) => {
+      const data = event.data as VotedEvent;
+      return { who: data.who, rank: RANKED_COLLECTIVE_MAX_RANK, votes: Number(data.vote.value) };
+    });
+    const { tally } = voted.at(-1)!.data as VotedEvent;
+    this.logger.succeedSpinner(`${votes.length} synthetic vote(s) cast`);
+    for (const { who, rank, votes: weight } of votes) {
+      this.logger.info(`\u2713 ${who} (rank ${rank}) voted aye with ${weight} vote(s)`);
+    }
+    this.logger.info(
+      `\u2713 Tally: bare_ayes ${tally.bare_ayes}, ayes ${tally.ayes}, nays ${tally.nays}`
+    );
+    return votes;
+  }
+}
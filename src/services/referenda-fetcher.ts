@@ -18,6 +18,7 @@ interface BuildReferendumInfoParams {
   tally: ReferendumInfo['tally'];
   deciding: ReferendumInfo['deciding'];
   useFellowship: boolean;
+  pallet?: string;
 }
 
 export class ReferendaFetcher {
@@ -30,13 +31,14 @@ export class ReferendaFetcher {
   async fetchReferendum(
     api: SubstrateApi,
     referendumId: number,
-    useFellowship: boolean = false
+    useFellowship: boolean = false,
+    pallet?: string
   ): Promise<ReferendumInfo | null> {
-    const palletName = getReferendaPalletName(useFellowship);
+    const palletName = getReferendaPalletName(useFellowship, pallet);
     this.logger.debug(`Fetching referendum #${referendumId} from ${palletName} pallet...`);
 
-    const pallet = getReferendaPallet(api, useFellowship);
-    const refInfo = await pallet.ReferendumInfoFor.getValue(referendumId);
+    const referenda = getReferendaPallet(api, useFellowship, pallet);
+    const refInfo = await referenda.ReferendumInfoFor.getValue(referendumId);
 
     if (!refInfo) {
       this.logger.error(`Referendum #${referendumId} not found in ${palletName} pallet`);
@@ -97,6 +99,7 @@ export class ReferendaFetcher {
       tally,
       deciding,
       useFellowship,
+      pallet,
     });
 
     this.logger.debug(`Parsed referendum info: ${stringify(referendumInfo, 2)}`);
@@ -107,7 +110,7 @@ export class ReferendaFetcher {
   private async buildOngoingReferendumInfo(
     params: BuildReferendumInfoParams
   ): Promise<ReferendumInfo> {
-    const { api, referendumId, ongoing, status, tally, deciding, useFellowship } = params;
+    const { api, referendumId, ongoing, status, tally, deciding, useFellowship, pallet } = params;
 
     const {
       hash: proposalHashHex,
@@ -121,9 +124,11 @@ export class ReferendaFetcher {
     );
 
     const trackId = ongoing.track;
-    const referendaConstants = useFellowship
-      ? api.constants.FellowshipReferenda
-      : api.constants.Referenda;
+    const constants = api.constants as unknown as Record<
+      string,
+      SubstrateApi['constants']['Referenda']
+    >;
+    const referendaConstants = constants[getReferendaPalletName(useFellowship, pallet)];
     const tracks = await referendaConstants.Tracks();
     const track = tracks.find((t: TrackInfo) => t[0] === trackId);
     const trackName = track ? track[1]?.name || `track_${trackId}` : `track_${trackId}`;
@@ -0,0 +1,125 @@
+import { decAnyMetadata, unifyMetadata } from '@polkadot-api/substrate-bindings';
+import { CodedError, ErrorCode } from '../utils/error-codes';
+import type { RpcRequest } from './call-drift-detector';
+
+/** A `pallet_referenda` instance of the runtime. */
+export interface ReferendaInstance {
+  /** Pallet name, e.g. `Referenda`, `FellowshipReferenda`, `AmbassadorReferenda` */
+  name: string;
+  /** Voted on by a ranked collective (tally with `bare_ayes`) rather than by conviction voting */
+  ranked: boolean;
+}
+
+/** A `--referendum` value: `<id>` or `<pallet>:<id>`. */
+export interface ReferendumTarget {
+  pallet?: string;
+  id: number;
+}
+
+type TypeDef = { tag: string; value: unknown };
+type Field = { name?: string; type: number };
+type StorageType = { tag: 'plain'; value: number } | { tag: 'map'; value: { value: number } };
+
+/** The subset of (unified) metadata needed to find referenda pallets. */
+export interface ReferendaMetadata {
+  lookup: { id: number; def: TypeDef }[];
+  pallets: {
+    name: string;
+    index: number;
+    storage?: { items: { name: string; type: StorageType }[] };
+  }[];
+}
+
+export function parseReferendumTarget(value: string): ReferendumTarget {
+  const match = value.trim().match(/^(?:([A-Za-z]\w*):)?(\d+)$/);
+  if (!match) {
+    throw new CodedError(ErrorCode.InvalidReferendumId, `Invalid referendum ID: ${value}`);
+  }
+  return { pallet: match[1], id: parseInt(match[2], 10) };
+}
+
+function fieldsOf(metadata: ReferendaMetadata, type: number, variant?: string): Field[] {
+  const def = metadata.lookup.find((t) => t.id === type)?.def;
+  if (def?.tag === 'composite') return def.value as Field[];
+  if (def?.tag === 'variant' && variant) {
+    const variants = def.value as { name: string; fields: Field[] }[];
+    return variants.find((v) => v.name === variant)?.fields ?? [];
+  }
+  return [];
+}
+
+/**
+ * Whether the instance is voted on by a ranked collective: the `tally` of its
+ * `ReferendumInfoFor::Ongoing` status is `pallet_ranked_collective::Tally`,
+ * the only tally with `bare_ayes`.
+ */
+function hasRankedTally(metadata: ReferendaMetadata, infoType: number): boolean {
+  const status = fieldsOf(metadata, infoType, 'Ongoing')[0];
+  const tally = status && fieldsOf(metadata, status.type).find((f) => f.name === 'tally');
+  return !!tally && fieldsOf(metadata, tally.type).some((f) => f.name === 'bare_ayes');
+}
+
+/**
+ * Every `pallet_referenda` instance of the runtime, in pallet order. Instances
+ * are recognised by their storage (`ReferendumCount` and `ReferendumInfoFor`),
+ * so renamed instances are found as well as the well-known ones.
+ */
+export function findReferendaPallets(metadata: ReferendaMetadata): ReferendaInstance[] {
+  const instances: ReferendaInstance[] = [];
+  for (const pallet of metadata.pallets) {
+    const items = pallet.storage?.items ?? [];
+    const info = items.find((item) => item.name === 'ReferendumInfoFor');
+    if (info?.type.tag !== 'map' || !items.some((item) => item.name === 'ReferendumCount')) {
+      continue;
+    }
+    instances.push({ name: pallet.name, ranked: hasRankedTally(metadata, info.type.value.value) });
+  }
+  return instances;
+}
+
+/** Read the fork's metadata and list its referenda pallets. */
+export async function discoverReferendaPallets(request: RpcRequest): Promise<ReferendaInstance[]> {
+  const hex: string = await request('state_getMetadata', []);
+  return findReferendaPallets(unifyMetadata(decAnyMetadata(hex)) as unknown as ReferendaMetadata);
+}
+
+/**
+ * The instance a `<pallet>.submit` call hex is made to, by the pallet index in
+ * its first byte, or `undefined` when that pallet is not a referenda instance.
+ */
+export function findSubmittedReferendaPallet(
+  metadata: ReferendaMetadata,
+  callHex: string
+): ReferendaInstance | undefined {
+  const index = parseInt(callHex.replace(/^0x/, '').slice(0, 2), 16);
+  const pallet = metadata.pallets.find((p) => p.index === index);
+  return findReferendaPallets(metadata).find((i) => i.name === pallet?.name);
+}
+
+/** Read the fork's metadata and find the instance `callHex` submits to. */
+export async function discoverSubmittedReferendaPallet(
+  request: RpcRequest,
+  callHex: string
+): Promise<ReferendaInstance | undefined> {
+  const hex: string = await request('state_getMetadata', []);
+  return findSubmittedReferendaPallet(
+    unifyMetadata(decAnyMetadata(hex)) as unknown as ReferendaMetadata,
+    callHex
+  );
+}
+
+/** The instance called `pallet`, or an `E-UNKNOWN-REFERENDA-PALLET` error listing the others. */
+export function selectReferendaPallet(
+  instances: ReferendaInstance[],
+  pallet: string
+): ReferendaInstance {
+  const instance = instances.find((i) => i.name === pallet);
+  if (!instance) {
+    const found = instances.map((i) => i.name).join(', ') || 'none';
+    throw new CodedError(
+      ErrorCode.UnknownReferendaPallet,
+      `No referenda pallet named ${pallet} on this chain (found: ${found})`
+    );
+  }
+  return instance;
+}
@@ -0,0 +1,232 @@
+import type { PolkadotClient } from 'polkadot-api';
+import type { ChopsticksConfig } from '../types';
+import type { RawReferendumInfo, SubstrateApi } from '../types/substrate-api';
+import { CodedError, ErrorCode, errorCodeOf } from '../utils/error-codes';
+import { type Logger, QuietLogger } from '../utils/logger';
+import { createApiForChain, createPolkadotClient, getReferendaPallet } from './chain-registry';
+import type { ChainTopologyBuilder } from './chain-topology-builder';
+import { ChopsticksManager } from './chopsticks-manager';
+import { ReferendaFetcher } from './referenda-fetcher';
+import { SimulationRunner } from './simulation-runner';
+
+export const DEFAULT_SWEEP_CONCURRENCY = 2;
+
+/** An ongoing referendum found by the sweep. */
+export interface PendingReferendum {
+  id: number;
+  track: number;
+}
+
+/** How one referendum of the sweep fared. */
+export interface SweepEntry extends PendingReferendum {
+  /** `passed`: executes successfully if enacted today */
+  outcome: 'passed' | 'failed';
+  /** Error code of a failed simulation, e.g. `E-PREIMAGE-MISSING` */
+  code?: ErrorCode;
+  message?: string;
+}
+
+export function parseConcurrency(value?: string): number {
+  if (value === undefined) return DEFAULT_SWEEP_CONCURRENCY;
+  if (!/^\d+$/.test(value.trim()) || parseInt(value, 10) < 1) {
+    throw new CodedError(
+      ErrorCode.InvalidConcurrency,
+      `Invalid --concurrency value: ${value} (expected a positive integer)`
+    );
+  }
+  return parseInt(value, 10);
+}
+
+/** Run `task` over `items`, at most `concurrency` at a time, keeping the input order. */
+export async function mapWithConcurrency<T, R>(
+  items: T[],
+  concurrency: number,
+  task: (item: T) => Promise<R>
+): Promise<R[]> {
+  const results: R[] = new Array(items.length);
+  let next = 0;
+  const worker = async (): Promise<void> => {
+    while (next < items.length) {
+      const index = next++;
+      results[index] = await task(items[index]);
+    }
+  };
+  await Promise.all(Array.from({ length: Math.min(concurrency, items.length) }, worker));
+  return results;
+}
+
+export function formatSweepTable(entries: SweepEntry[]): string[] {
+  const rows = entries.map((entry) => [
+    `#${entry.id}`,
+    `${entry.track}`,
+    entry.outcome === 'passed' ? 'PASS' : 'FAIL',
+    entry.code ? `${entry.code}: ${entry.message}` : '',
+  ]);
+  const header = ['Referendum', 'Track', 'Result', 'Reason'];
+  const widths = header.map((title, column) =>
+    Math.max(title.length, ...rows.map((row) => row[column].length))
+  );
+  return [header, ...rows].map((row) =>
+    row
+      .map((cell, column) => (column === row.length - 1 ? cell : cell.padEnd(widths[column])))
+      .join('  ')
+      .trimEnd()
+  );
+}
+
+/** A running Chopsticks fork and a client connected to it. */
+interface Fork {
+  chopsticks: ChopsticksManager;
+  client: PolkadotClient;
+  api: SubstrateApi;
+}
+
+/**
+ * Simulates every ongoing referendum of one chain and reports which would
+ * execute successfully today.
+ *
+ * State is fetched from the endpoint once: a base fork of the pinned block
+ * is kept idle for the whole sweep, and each referendum runs on its own fork
+ * *of that fork*. The child forks read the block's state from the base
+ * fork, which fetches every key from the endpoint only once, and keep their
+ * own changes in their own storage layers (copy-on-write), so concurrent
+ * simulations never see each other's writes.
+ */
+export class ReferendaSweeper {
+  private logger: Logger;
+  private topology: ChainTopologyBuilder;
+  private isFellowship: boolean;
+  private concurrency: number;
+
+  constructor(
+    logger: Logger,
+    topology: ChainTopologyBuilder,
+    isFellowship: boolean,
+    concurrency: number = DEFAULT_SWEEP_CONCURRENCY
+  ) {
+    this.logger = logger;
+    this.topology = topology;
+    this.isFellowship = isFellowship;
+    this.concurrency = concurrency;
+  }
+
+  async sweep(endpoint: string, block?: number): Promise<SweepEntry[]> {
+    const forkBlock = block ?? (await this.latestBlock(endpoint));
+    const networkKey = this.isFellowship
+      ? await this.topology.detectRelayNetworkKey(endpoint)
+      : undefined;
+
+    this.logger.startSpinner(`Forking ${endpoint} at block ${forkBlock}...`);
+    const baseLogger = new QuietLogger('[base]', this.logger.isVerbose());
+    const base = await this.startFork(baseLogger, endpoint, forkBlock, networkKey);
+    try {
+      const baseEndpoint = base.chopsticks.getContext().ws.endpoint;
+      this.logger.succeedSpinner(`Shared base fork at ${baseEndpoint}`);
+
+      this.logger.startSpinner('Finding ongoing referenda...');
+      const pending = await findOngoingReferenda(base.api, this.isFellowship);
+      this.logger.succeedSpinner(`Found ${pending.length} ongoing referendum(s)`);
+
+      let done = 0;
+      return await mapWithConcurrency(pending, this.concurrency, async (referendum) => {
+        const entry = await this.simulate(baseEndpoint, forkBlock, networkKey, referendum);
+        done++;
+        const progress = `[${done}/${pending.length}] Referendum #${referendum.id}`;
+        if (entry.outcome === 'passed') {
+          this.logger.success(`${progress} executes successfully`);
+        } else {
+          this.logger.error(`${progress} fails: ${entry.code}: ${entry.message}`);
+        }
+        return entry;
+      });
+    } finally {
+      await this.stopFork(baseLogger, base);
+    }
+  }
+
+  private async simulate(
+    baseEndpoint: string,
+    block: number,
+    networkKey: string | undefined,
+    referendum: PendingReferendum
+  ): Promise<SweepEntry> {
+    const logger = new QuietLogger(`[#${referendum.id}]`, this.logger.isVerbose());
+    let fork: Fork | undefined;
+    try {
+      fork = await this.startFork(logger, baseEndpoint, block, networkKey);
+      await new SimulationRunner(logger).fetchAndSimulate({
+        api: fork.api,
+        chopsticks: fork.chopsticks,
+        referendumId: referendum.id,
+        isFellowship: this.isFellowship,
+      });
+      return { ...referendum, outcome: 'passed' };
+    } catch (error) {
+      return {
+        ...referendum,
+        outcome: 'failed',
+        code: errorCodeOf(error),
+        message: (error as Error).message,
+      };
+    } finally {
+      if (fork) await this.stopFork(logger, fork);
+    }
+  }
+
+  private async startFork(
+    logger: Logger,
+    endpoint: string,
+    block: number,
+    networkKey: string | undefined
+  ): Promise<Fork> {
+    const chopsticks = new ChopsticksManager(logger);
+    const config = this.topology.buildConfig(endpoint, block);
+    const context = await chopsticks.setup(config as unknown as ChopsticksConfig, networkKey);
+    const client = createPolkadotClient(context.ws.endpoint);
+    const api = createApiForChain(client);
+    try {
+      await chopsticks.waitForChainReady(api);
+    } catch (error) {
+      await this.stopFork(logger, { chopsticks, client, api });
+      throw error;
+    }
+    return { chopsticks, client, api };
+  }
+
+  private async stopFork(logger: Logger, fork: Fork): Promise<void> {
+    fork.client.destroy();
+    await fork.chopsticks.cleanup().catch((error) => logger.debug(`Cleanup failed: ${error}`));
+  }
+
+  /** Pin the sweep to the live chain's current block, so every fork sees the same state. */
+  private async latestBlock(endpoint: string): Promise<number> {
+    this.logger.startSpinner('Connecting to live network to get latest block...');
+    const client = createPolkadotClient(endpoint);
+    try {
+      const block = await new ReferendaFetcher(this.logger).getLatestBlock(
+        createApiForChain(client)
+      );
+      this.logger.succeedSpinner(`Latest block: ${block}`);
+      return block;
+    } finally {
+      client.destroy();
+    }
+  }
+}
+
+/** Ongoing referenda of the chain's (fellowship) referenda pallet, by id. */
+export async function findOngoingReferenda(
+  api: SubstrateApi,
+  isFellowship: boolean
+): Promise<PendingReferendum[]> {
+  const pallet = getReferendaPallet(api, isFellowship);
+  const count = await pallet.ReferendumCount.getValue();
+  const pending: PendingReferendum[] = [];
+  for (let id = 0; id < count; id++) {
+    const info = (await pallet.ReferendumInfoFor.getValue(id)) as RawReferendumInfo | undefined;
+    if (info?.type === 'Ongoing') {
+      pending.push({ id, track: Number(info.value.track) });
+    }
+  }
+  return pending;
+}
@@ -0,0 +1,202 @@
+import type { CancellationResult, CancelPhase, DepositRefund } from '../types';
+import type {
+  ReferendaPallet,
+  ReferendaTx,
+  SubstrateApi,
+  UnsafeTransaction,
+} from '../types/substrate-api';
+import { interpretDispatchResult } from '../utils/dispatch-result';
+import { CodedError, ErrorCode } from '../utils/error-codes';
+import { getBlockEvents, type ParsedEvent } from '../utils/event-serializer';
+import { stringify } from '../utils/json';
+import type { Logger } from '../utils/logger';
+import { ALICE_ADDRESS } from '../utils/storage-constants';
+import { BalanceTracker } from './balance-tracker';
+import type { ChopsticksManager } from './chopsticks-manager';
+import type { SchedulerManager } from './scheduler-manager';
+
+/**
+ * `--cancel-at <phase>`: where in the simulated lifecycle the referendum is
+ * cancelled instead of dispatched.
+ *
+ * - `ongoing`: as found on the fork, before it is forced into a passing state
+ * - `confirming`: once forced into its confirm period, right before the block
+ *   that would approve it
+ */
+const CANCEL_PHASES: CancelPhase[] = ['ongoing', 'confirming'];
+
+export function parseCancelPhase(value?: string): CancelPhase | undefined {
+  if (value === undefined) return undefined;
+  const phase = CANCEL_PHASES.find((name) => name === value.trim());
+  if (!phase) {
+    throw new CodedError(
+      ErrorCode.InvalidCancelPhase,
+      `Invalid --cancel-at value: ${value} (expected one of: ${CANCEL_PHASES.join(', ')})`
+    );
+  }
+  return phase;
+}
+
+/**
+ * The origin `cancel` is dispatched from, in agenda storage format:
+ * `ReferendumCanceller` for conviction-voting referenda, Root for ranked
+ * collectives, whose cancel origins have no canceller track but accept Root.
+ */
+export function cancelOrigin(ranked: boolean): Record<string, unknown> {
+  return ranked ? { System: 'Root' } : { Origins: 'ReferendumCanceller' };
+}
+
+/**
+ * Cancels a referendum mid-lifecycle with `<pallet>.cancel` and checks the
+ * runtime cleaned up after it: the referendum is `Cancelled` and, as
+ * cancellation (unlike `kill`) slashes nothing, `refund_submission_deposit`
+ * and `refund_decision_deposit` each return the full deposit to its owner.
+ *
+ * Calls are injected into the scheduler agenda, the refunds as signed calls
+ * from Alice (anyone may refund a deposit).
+ */
+export class ReferendumCancellation {
+  private logger: Logger;
+  private chopsticks: ChopsticksManager;
+  private api: SubstrateApi;
+  private scheduler: SchedulerManager;
+  private pallet: string;
+  private ranked: boolean;
+
+  constructor(
+    logger: Logger,
+    chopsticks: ChopsticksManager,
+    api: SubstrateApi,
+    scheduler: SchedulerManager,
+    pallet: string,
+    ranked: boolean
+  ) {
+    this.logger = logger;
+    this.chopsticks = chopsticks;
+    this.api = api;
+    this.scheduler = scheduler;
+    this.pallet = pallet;
+    this.ranked = ranked;
+  }
+
+  async cancel(referendumId: number, phase: CancelPhase): Promise<CancellationResult> {
+    const query = (this.api.query as unknown as Record<string, ReferendaPallet>)[this.pallet];
+    const info = await query.ReferendumInfoFor.getValue(referendumId);
+    if (info?.type !== 'Ongoing') {
+      throw new CodedError(
+        ErrorCode.ReferendumNotOngoing,
+        `Referendum ${referendumId} cannot be cancelled: it is not Ongoing (current state: ${info?.type ?? 'missing'})`
+      );
+    }
+    const confirming = info.value.deciding?.confirming;
+    if (phase === 'confirming') {
+      if (confirming === undefined) {
+        throw new CodedError(
+          ErrorCode.CancellationFailed,
+          `Referendum #${referendumId} is not in its confirm period, so it cannot be cancelled while confirming`
+        );
+      }
+      this.logger.info(
+        `\u2713 Referendum #${referendumId} is confirming (until block ${confirming})`
+      );
+    }
+
+    const origin = cancelOrigin(this.ranked);
+    this.logger.info(`Cancelling referendum #${referendumId} from ${stringify(origin)}`);
+    const events = await this.dispatch(this.tx().cancel({ index: referendumId }), origin, 'cancel');
+    const block = Number(await this.api.query.System.Number.getValue());
+    const cancelled = events.some(
+      (e) =>
+        e.section === this.pallet &&
+        e.method === 'Cancelled' &&
+        Number((e.data as { index?: unknown } | undefined)?.index) === referendumId
+    );
+    if (!cancelled) {
+      throw new CodedError(
+        ErrorCode.CancellationFailed,
+        `${this.pallet}.cancel(${referendumId}) dispatched without a ${this.pallet}.Cancelled event`
+      );
+    }
+
+    const after = await query.ReferendumInfoFor.getValue(referendumId);
+    if (after?.type !== 'Cancelled') {
+      throw new CodedError(
+        ErrorCode.CancellationFailed,
+        `Referendum #${referendumId} is ${after?.type ?? 'missing'} after ${this.pallet}.cancel, expected Cancelled`
+      );
+    }
+    this.logger.success(`\u2713 Referendum #${referendumId} cancelled at block ${block}`);
+
+    const [, submissionDeposit, decisionDeposit] = after.value;
+    const deposits: DepositRefund[] = [];
+    if (submissionDeposit) {
+      deposits.push(await this.refund('submission', referendumId, submissionDeposit));
+    }
+    if (decisionDeposit) {
+      deposits.push(await this.refund('decision', referendumId, decisionDeposit));
+    }
+    return { phase, block, deposits };
+  }
+
+  private tx(): ReferendaTx {
+    const tx = (this.api.tx as unknown as Record<string, ReferendaTx | undefined>)[this.pallet];
+    if (!tx) {
+      throw new CodedError(ErrorCode.CancellationFailed, `Chain has no ${this.pallet}.cancel call`);
+    }
+    return tx;
+  }
+
+  /** Refund a deposit of the cancelled referendum and check its owner got all of it back. */
+  private async refund(
+    kind: DepositRefund['kind'],
+    index: number,
+    deposit: { who: DepositRefund['who']; amount: bigint }
+  ): Promise<DepositRefund> {
+    const { who, amount } = deposit;
+    const tracker = new BalanceTracker(this.api);
+    await tracker.snapshot({ type: 'native' }, who);
+
+    const call =
+      kind === 'submission'
+        ? this.tx().refund_submission_deposit({ index })
+        : this.tx().refund_decision_deposit({ index });
+    try {
+      await this.dispatch(call, { System: { Signed: ALICE_ADDRESS } }, `refund_${kind}_deposit`);
+    } catch (error) {
+      this.logger.error(`  \u2717 ${kind} deposit of ${who}: ${(error as Error).message}`);
+      return { kind, who, amount, refunded: false, error: (error as Error).message };
+    }
+
+    const [{ delta }] = await tracker.deltas();
+    if (delta !== amount) {
+      const error = `${who} received ${delta}, expected ${amount}`;
+      this.logger.error(`  \u2717 ${kind} deposit: ${error}`);
+      return { kind, who, amount, refunded: false, error };
+    }
+    this.logger.success(`  \u2713 ${kind} deposit of ${amount} refunded to ${who}`);
+    return { kind, who, amount, refunded: true };
+  }
+
+  /** Dispatch `call` from `origin` in the next block and return that block's events. */
+  private async dispatch(
+    call: UnsafeTransaction,
+    origin: Record<string, unknown>,
+    name: string
+  ): Promise<ParsedEvent[]> {
+    const callData = await call.getEncodedData();
+    const block = await this.scheduler.scheduleCallAtNextBlock(callData.asHex(), origin);
+    this.logger.debug(`${this.pallet}.${name} scheduled at block ${block}`);
+    await this.chopsticks.newBlock();
+
+    const events = await getBlockEvents(this.api.query.System.Events, this.logger);
+    const dispatched = events.find((e) => e.section === 'Scheduler' && e.method === 'Dispatched');
+    const outcome = interpretDispatchResult((dispatched?.data as { result?: unknown })?.result);
+    if (outcome.outcome !== 'success') {
+      throw new CodedError(
+        ErrorCode.CancellationFailed,
+        `${this.pallet}.${name} did not dispatch: ${outcome.message ?? outcome.outcome}`
+      );
+    }
+    return events;
+  }
+}
@@ -1,11 +1,10 @@
-import { Keyring } from '@polkadot/keyring';
 import { Binary } from '@polkadot-api/substrate-bindings';
 import type { PolkadotSigner } from 'polkadot-api';
-import { getPolkadotSigner } from 'polkadot-api/signer';
-import type { SubstrateApi } from '../types/substrate-api';
-import { formatDispatchError } from '../utils/dispatch-result';
-import { getBlockEvents } from '../utils/event-serializer';
-import { toHexString } from '../utils/hex';
+import type { DecodedTransaction, SubstrateApi, UnsafeTransaction } from '../types/substrate-api';
+import { formatDispatchError, wrappedCallError } from '../utils/dispatch-result';
+import { CodedError, ErrorCode } from '../utils/error-codes';
+import { expectEvent, getBlockEvents } from '../utils/event-serializer';
+import { normalizeCallData } from '../utils/hex';
 import { stringify } from '../utils/json';
 import type { Logger } from '../utils/logger';
 import {
@@ -13,14 +12,31 @@ import {
   ALICE_ADDRESS,
   FELLOWSHIP_STORAGE_INJECTION,
 } from '../utils/storage-constants';
-import { getReferendaPallet } from './chain-registry';
+import { CallDriftDetector, formatCallDrift } from './call-drift-detector';
+import {
+  createPolkadotClient,
+  getReferendaPallet,
+  getReferendaPalletName,
+} from './chain-registry';
 import type { ChopsticksManager } from './chopsticks-manager';
+import {
+  type DelegatedSubmission,
+  DelegatedSubmitter,
+  describeSubmission,
+  mockSigner,
+} from './delegated-submission';
+import { type ProposalFootprint, proposalFootprint } from './proposal-footprint';
+import type { ReferendumMetadata } from './referendum-metadata';
 
 export { ALICE_ADDRESS, FELLOWSHIP_STORAGE_INJECTION, ALICE_ACCOUNT_INJECTION };
 
 export interface ReferendumCreationResult {
   referendumId: number;
   preimageNoted: boolean;
+  /** Size of the submitted proposal and the deposit its preimage took */
+  footprint?: ProposalFootprint;
+  /** Hash `MetadataOf` was set to, when metadata was supplied */
+  metadataHash?: string;
 }
 
 export class ReferendumCreator {
@@ -32,42 +48,105 @@ export class ReferendumCreator {
     this.chopsticks = chopsticks;
   }
 
-  private static validateHex(input: string, paramName: string): string {
-    const hex = toHexString(input) as string;
-    if (!/^0x[0-9a-fA-F]*$/.test(hex)) {
-      throw new Error(`Invalid hex string for ${paramName}: ${input}`);
-    }
-    return hex;
+  static validateHex(input: string, paramName: string): string {
+    return normalizeCallData(input, paramName);
   }
 
   async createReferendum(
     api: SubstrateApi,
     submitCallHex: string,
     preimageCallHex?: string,
-    isFellowship: boolean = false
+    isFellowship: boolean = false,
+    submission?: DelegatedSubmission,
+    metadata?: ReferendumMetadata,
+    pallet?: string
   ): Promise<ReferendumCreationResult> {
     const validatedSubmitHex = ReferendumCreator.validateHex(submitCallHex, 'submitCall');
 
-    const keyring = new Keyring({ type: 'sr25519' });
-    const alice = keyring.addFromUri('//Alice');
-    const signer = getPolkadotSigner(alice.publicKey, 'Sr25519', alice.sign);
+    // sr25519 signatures are randomized; the fork's mock signature keeps the
+    // extrinsics, and so block hashes, identical between runs.
+    const signer = mockSigner(ALICE_ADDRESS);
 
     this.logger.info(
-      `Creating ${isFellowship ? 'fellowship' : 'governance'} referendum using Alice account...`
+      submission
+        ? `Creating ${isFellowship ? 'fellowship' : 'governance'} referendum ${describeSubmission(submission)}...`
+        : `Creating ${isFellowship ? 'fellowship' : 'governance'} referendum using Alice account...`
     );
 
-    const preimageNoted = preimageCallHex
+    const preimage = preimageCallHex
       ? await this.notePreimage(api, signer, preimageCallHex)
-      : false;
+      : undefined;
+    const preimageNoted = preimage !== undefined;
 
     const referendumId = await this.submitAndRetrieveId(
       api,
       signer,
       validatedSubmitHex,
-      isFellowship
+      isFellowship,
+      submission,
+      pallet
     );
 
-    return { referendumId, preimageNoted };
+    const proposal = await this.proposalOf(api, validatedSubmitHex);
+    const footprint = proposal && proposalFootprint(proposal, preimage);
+
+    if (!metadata) return { referendumId, preimageNoted, footprint };
+    const metadataHash = await this.setMetadata(
+      api,
+      signer,
+      referendumId,
+      isFellowship,
+      metadata,
+      submission,
+      pallet
+    );
+    return { referendumId, preimageNoted, footprint, metadataHash };
+  }
+
+  /**
+   * The `Bounded` proposal a `submit` call carries and its encoded length.
+   * `undefined` when the call has no `proposal` argument.
+   */
+  private async proposalOf(
+    api: SubstrateApi,
+    validatedSubmitHex: string
+  ): Promise<{ type: string; len?: number } | undefined> {
+    const decoded = await api
+      .txFromCallData(Binary.fromHex(validatedSubmitHex))
+      .catch(() => undefined);
+    const args = decoded?.decodedCall.value.value as { proposal?: unknown } | undefined;
+    const { type, value } = (args?.proposal ?? {}) as { type?: string; value?: unknown };
+    if (!type) return undefined;
+    if (value instanceof Binary) return { type, len: value.asBytes().length };
+    return { type, len: (value as { len?: number } | undefined)?.len };
+  }
+
+  private async decodeCall(
+    api: SubstrateApi,
+    validatedHex: string,
+    failureLabel: string
+  ): Promise<DecodedTransaction> {
+    const decoded = await api
+      .txFromCallData(Binary.fromHex(validatedHex))
+      .catch(async (e: Error) => {
+        this.logger.failSpinner(`Failed to decode ${failureLabel} call data`);
+        const drift = await this.explainCallDrift(validatedHex);
+        throw new CodedError(
+          ErrorCode.CallDecodeFailed,
+          `Invalid ${failureLabel} call data for this chain's runtime. The hex may have been generated for a different runtime version or chain. Original error: ${e.message}${drift ? `\n${drift}` : ''}`
+        );
+      });
+
+    // A shifted pallet/call index can still decode, as another call that
+    // leaves bytes over; re-encoding then no longer reproduces the input.
+    if (decoded.getEncodedData().asHex() !== validatedHex.toLowerCase()) {
+      const drift = await this.explainCallDrift(validatedHex);
+      this.logger.warn(
+        `Call data for ${failureLabel} decodes as ${decoded.decodedCall.type}.${decoded.decodedCall.value.type} but does not re-encode to the same bytes`
+      );
+      if (drift) this.logger.warn(drift);
+    }
+    return decoded;
   }
 
   private async decodeAndSignCall(
@@ -76,53 +155,162 @@ export class ReferendumCreator {
     validatedHex: string,
     failureLabel: string
   ): Promise<string> {
-    const decoded = await api.txFromCallData(Binary.fromHex(validatedHex)).catch((e: Error) => {
-      this.logger.failSpinner(`Failed to decode ${failureLabel} call data`);
-      throw new Error(
-        `Invalid ${failureLabel} call data for this chain's runtime. The hex may have been generated for a different runtime version or chain. Original error: ${e.message}`
-      );
-    });
-
+    const decoded = await this.decodeCall(api, validatedHex, failureLabel);
     const signedTx = await decoded.sign(signer);
     this.logger.debug(`${failureLabel} transaction signed`);
     return signedTx;
   }
 
+  /** Decode the call's index bytes under the current and previous runtime. */
+  private async explainCallDrift(callHex: string): Promise<string | undefined> {
+    const client = createPolkadotClient(this.chopsticks.getContext().ws.endpoint);
+    try {
+      const detector = new CallDriftDetector(this.logger, (method, params) =>
+        client._request(method, params)
+      );
+      const report = await detector.explain(callHex);
+      return report ? formatCallDrift(report) : undefined;
+    } finally {
+      client.destroy();
+    }
+  }
+
+  /**
+   * Note the preimage as Alice. Returns the length of the bytes it stores and
+   * the deposit held for them, seen as the growth of Alice's reserved balance.
+   */
   private async notePreimage(
     api: SubstrateApi,
     signer: PolkadotSigner,
     preimageCallHex: string
-  ): Promise<boolean> {
+  ): Promise<{ len: number; deposit?: bigint }> {
     const validatedHex = ReferendumCreator.validateHex(preimageCallHex, 'preimageCall');
     this.logger.startSpinner('Noting preimage...');
 
-    const signedPreimageTx = await this.decodeAndSignCall(api, signer, validatedHex, 'preimage');
+    const decoded = await this.decodeCall(api, validatedHex, 'preimage');
+    const signedPreimageTx = await decoded.sign(signer);
+    this.logger.debug('preimage transaction signed');
+    const reservedBefore = await this.reservedOf(api, ALICE_ADDRESS);
 
     await this.chopsticks.newBlock({ transactions: [signedPreimageTx] });
     await this.chopsticks.newBlock();
 
+    const reservedAfter = await this.reservedOf(api, ALICE_ADDRESS);
     this.logger.succeedSpinner('Preimage noted successfully');
-    return true;
+
+    const { bytes } = (decoded.decodedCall.value.value ?? {}) as { bytes?: unknown };
+    const len =
+      bytes instanceof Binary
+        ? bytes.asBytes().length
+        : Binary.fromHex(validatedHex).asBytes().length;
+    const deposit =
+      reservedBefore !== undefined && reservedAfter !== undefined
+        ? reservedAfter - reservedBefore
+        : undefined;
+    return { len, deposit };
+  }
+
+  private async reservedOf(api: SubstrateApi, address: string): Promise<bigint | undefined> {
+    const account = await api.query.System.Account.getValue(address).catch(() => undefined);
+    return account?.data.reserved;
+  }
+
+  /**
+   * Point the referendum's `MetadataOf` at `metadata` with `set_metadata`,
+   * noting a metadata file as a preimage first. Only the submission deposit's
+   * owner may set metadata, so a delegated submission sets it the same way.
+   */
+  private async setMetadata(
+    api: SubstrateApi,
+    signer: PolkadotSigner,
+    referendumId: number,
+    isFellowship: boolean,
+    metadata: ReferendumMetadata,
+    submission?: DelegatedSubmission,
+    referendaPallet?: string
+  ): Promise<string> {
+    const pallet = getReferendaPalletName(isFellowship, referendaPallet);
+    const hash = metadata.hash.asHex();
+
+    if (metadata.kind === 'file') {
+      if (!api.tx.Preimage) {
+        throw new CodedError(
+          ErrorCode.InvalidMetadata,
+          `Cannot note ${metadata.path}: chain has no Preimage pallet`
+        );
+      }
+      this.logger.startSpinner(`Noting metadata ${metadata.path}...`);
+      const note = api.tx.Preimage.note_preimage({ bytes: Binary.fromBytes(metadata.bytes) });
+      await expectEvent(
+        this.chopsticks,
+        api.query.System.Events,
+        await note.sign(signer),
+        {
+          section: 'Preimage',
+          method: 'Noted',
+          step: 'Noting metadata',
+          code: ErrorCode.InvalidMetadata,
+        },
+        this.logger
+      );
+      this.logger.succeedSpinner(`Metadata noted as preimage ${hash}`);
+    }
+
+    this.logger.startSpinner(`Setting metadata of referendum #${referendumId}...`);
+    const tx = (api.tx as unknown as Record<string, Record<string, unknown>>)[pallet];
+    const setMetadata = tx?.set_metadata as
+      | ((args: { index: number; maybe_hash: Binary }) => UnsafeTransaction)
+      | undefined;
+    if (!setMetadata) {
+      throw new CodedError(ErrorCode.InvalidMetadata, `Chain has no ${pallet}.set_metadata`);
+    }
+    const call = setMetadata({ index: referendumId, maybe_hash: metadata.hash });
+    const signedTx = submission
+      ? await new DelegatedSubmitter(this.logger, this.chopsticks).sign(
+          api,
+          signer,
+          await api.txFromCallData(await call.getEncodedData()),
+          submission
+        )
+      : await call.sign(signer);
+    await expectEvent(
+      this.chopsticks,
+      api.query.System.Events,
+      signedTx,
+      {
+        section: pallet,
+        method: 'MetadataSet',
+        step: `${pallet}.set_metadata`,
+        code: ErrorCode.InvalidMetadata,
+      },
+      this.logger
+    );
+    this.logger.succeedSpinner(`Referendum #${referendumId} metadata set to ${hash}`);
+    return hash;
   }
 
   private async submitAndRetrieveId(
     api: SubstrateApi,
     signer: PolkadotSigner,
     validatedSubmitHex: string,
-    isFellowship: boolean
+    isFellowship: boolean,
+    submission?: DelegatedSubmission,
+    pallet?: string
   ): Promise<number> {
     this.logger.startSpinner('Submitting referendum...');
 
-    const palletQuery = getReferendaPallet(api, isFellowship);
+    const palletQuery = getReferendaPallet(api, isFellowship, pallet);
     const countBefore = Number(await palletQuery.ReferendumCount.getValue());
     this.logger.debug(`Referendum count before submit: ${countBefore}`);
 
-    const signedSubmitTx = await this.decodeAndSignCall(
-      api,
-      signer,
-      validatedSubmitHex,
-      'referendum submit'
-    );
+    const signedSubmitTx = submission
+      ? await new DelegatedSubmitter(this.logger, this.chopsticks).sign(
+          api,
+          signer,
+          await this.decodeCall(api, validatedSubmitHex, 'referendum submit'),
+          submission
+        )
+      : await this.decodeAndSignCall(api, signer, validatedSubmitHex, 'referendum submit');
 
     await this.chopsticks.newBlock({ transactions: [signedSubmitTx] });
 
@@ -138,6 +326,10 @@ export class ReferendumCreator {
         }
       }
     }
+    const wrappedError = wrappedCallError(events);
+    if (wrappedError) {
+      this.logger.error(`Delegated referendum submit failed: ${wrappedError}`);
+    }
 
     await this.chopsticks.newBlock();
     this.logger.succeedSpinner('Referendum submitted successfully');
@@ -148,8 +340,11 @@ export class ReferendumCreator {
 
     if (countAfter <= countBefore) {
       this.logger.failSpinner('Referendum count did not increase — submission may have failed');
-      throw new Error(
-        'No new referendum detected after submission. The call data may be invalid or the origin may lack permissions.'
+      throw new CodedError(
+        ErrorCode.ReferendumNotCreated,
+        wrappedError
+          ? `No new referendum detected after submission: the ${submission?.kind === 'multisig' ? 'multisig' : 'proxied'} call failed with ${wrappedError}`
+          : 'No new referendum detected after submission. The call data may be invalid or the origin may lack permissions.'
       );
     }
 
@@ -0,0 +1,62 @@
+import * as fs from 'fs';
+import { Binary, Blake2256 } from '@polkadot-api/substrate-bindings';
+import { CodedError, ErrorCode } from '../utils/error-codes';
+
+/**
+ * `--metadata-for-*-referendum`: what `set_metadata` points the created
+ * referendum's `MetadataOf` at.
+ *
+ * - `file`: a JSON document, noted as a preimage first (`Preimage.note_preimage`)
+ * - `hash`: the hash of a preimage that is already noted on chain
+ */
+export type ReferendumMetadata =
+  | { kind: 'file'; path: string; bytes: Uint8Array; hash: Binary }
+  | { kind: 'hash'; hash: Binary };
+
+interface MetadataOptions {
+  metadataForGovernanceReferendum?: string;
+  metadataForFellowshipReferendum?: string;
+}
+
+/**
+ * Parse `--metadata-for-*-referendum`. That it is only given with its
+ * `--call-to-create-*-referendum` is checked by the flag rules.
+ */
+export function parseReferendumMetadata(
+  options: MetadataOptions,
+  isFellowship: boolean
+): ReferendumMetadata | undefined {
+  const kind = isFellowship ? 'fellowship' : 'governance';
+  const value = isFellowship
+    ? options.metadataForFellowshipReferendum
+    : options.metadataForGovernanceReferendum;
+  if (value === undefined) return undefined;
+
+  const flag = `--metadata-for-${kind}-referendum`;
+
+  const trimmed = value.trim();
+  if (/^0x[0-9a-fA-F]{64}$/.test(trimmed)) {
+    return { kind: 'hash', hash: Binary.fromHex(trimmed) };
+  }
+  if (!fs.existsSync(trimmed)) {
+    throw new CodedError(
+      ErrorCode.InvalidMetadata,
+      `Invalid ${flag} value: ${value} (expected a JSON file or a 0x-prefixed 32-byte preimage hash)`
+    );
+  }
+  const bytes = fs.readFileSync(trimmed);
+  try {
+    JSON.parse(bytes.toString('utf8'));
+  } catch (error) {
+    throw new CodedError(
+      ErrorCode.InvalidMetadata,
+      `Invalid ${flag} file ${trimmed}: ${(error as Error).message}`
+    );
+  }
+  return {
+    kind: 'file',
+    path: trimmed,
+    bytes: new Uint8Array(bytes),
+    hash: Binary.fromBytes(Blake2256(bytes)),
+  };
+}
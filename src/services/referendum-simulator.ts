@@ -1,22 +1,74 @@
-import type { ReferendumInfo, SimulationResult } from '../types';
+import type {
+  BalanceChange,
+  CancelPhase,
+  EnactmentRetries,
+  FollowedTask,
+  NotDispatchedReason,
+  ReferendumInfo,
+  RetryAttempt,
+  RetryConfig,
+  SimulationResult,
+  SyntheticVote,
+  TrackChange,
+} from '../types';
 import type { ReferendaPallet, ReferendumOngoing, SubstrateApi } from '../types/substrate-api';
-import { getBlockEvents, type ParsedEvent } from '../utils/event-serializer';
+import { CodedError, ErrorCode, errorCodeOf } from '../utils/error-codes';
+import {
+  displayChainEvents,
+  getBlockEvents,
+  type ParsedEvent,
+  parseBlockEvent,
+} from '../utils/event-serializer';
 import { toHexString } from '../utils/hex';
 import { stringify } from '../utils/json';
 import type { Logger } from '../utils/logger';
+import { formatDelta, formatTokenAmount, type NativeToken } from '../utils/token-format';
 import {
   convertOriginToStorageFormat,
   convertProposalToStorageFormat,
 } from '../utils/storage-format-converter';
-import { getReferendaPallet, getReferendaPalletName } from './chain-registry';
+import {
+  assetLabel,
+  type BalanceCheck,
+  BalanceTracker,
+  describeAsset,
+} from './balance-tracker';
+import type { RpcRequest } from './call-drift-detector';
+import {
+  createPolkadotClient,
+  getChainProperties,
+  getReferendaPallet,
+  getReferendaPalletName,
+} from './chain-registry';
 import type { ChopsticksManager } from './chopsticks-manager';
-import { ExecutionResultChecker } from './execution-result-checker';
+import { type Conviction, ConvictionApprover } from './conviction-approval';
+import {
+  describePostponedTask,
+  ExecutionResultChecker,
+  findNotDispatchedTask,
+  findScheduledTasks,
+  type ScheduledTask,
+} from './execution-result-checker';
+import type { HookPhase, HookRunner } from './hook-runner';
+import { EMPTY_RANKED_TALLY, RankedApprover } from './ranked-approval';
+import { ReferendumCancellation } from './referendum-cancellation';
+import { MISMATCHED_DISPATCH_ORIGIN, type Scenario } from './scenarios';
+import { runtimePallets } from './runtime-capabilities';
 import { SchedulerManager } from './scheduler-manager';
-
-/** Arbitrary high number of voters for fellowship passing tally */
-const FELLOWSHIP_PASSING_BARE_AYES = 100;
-/** High rank-weighted support for fellowship passing tally */
-const FELLOWSHIP_PASSING_AYES = 1000;
+import {
+  isSessionBoundary,
+  readEpochLength,
+  SESSION_BOUNDARY_LIMITATION,
+} from './session-boundary';
+import { TrackChanges } from './track-changes';
+import { TreasuryPayouts } from './treasury-payouts';
+import { TreasuryPot } from './treasury-pot';
+
+/**
+ * Extra blocks built while the scheduler keeps postponing the enactment task
+ * for lack of weight, before it is reported as never dispatched.
+ */
+const ENACTMENT_RETRY_BLOCKS = 3;
 
 /** Known governance origin variants */
 const GOVERNANCE_ORIGINS = new Set([
@@ -36,39 +88,116 @@ const GOVERNANCE_ORIGINS = new Set([
   'BigSpender',
 ]);
 
+/**
+ * The scheduler origin (storage format) for a `--pre-origin` value: `Root`,
+ * `Pallet.Variant` or a bare governance origin such as `WhitelistedCaller`.
+ */
+export function parseOriginString(originString: string, logger: Logger): Record<string, string> {
+  if (originString === 'Root') {
+    return { System: 'Root' };
+  }
+
+  if (originString.includes('.')) {
+    const [palletOrType, variant] = originString.split('.');
+    return { [palletOrType]: variant };
+  }
+
+  if (GOVERNANCE_ORIGINS.has(originString)) {
+    return { Origins: originString };
+  }
+
+  logger.warn(`Unknown origin format "${originString}", treating as System origin`);
+  return { System: originString };
+}
+
+/** What to do around the forced execution, besides the execution itself. */
+export interface PreExecutionOptions {
+  preCall?: string;
+  preOrigin?: string;
+  scenario?: Scenario;
+  /** `--cancel-at`: cancel the referendum at this phase instead of dispatching it */
+  cancelAt?: CancelPhase;
+  /** `--vote-conviction`: approve conviction-voting referenda by a real vote at this conviction */
+  conviction?: Conviction;
+}
+
+/** What to do once the proposal is dispatched, besides reporting its result. */
+export interface DispatchOptions {
+  /** `--blocks-after-dispatch`: blocks to build past the dispatch block */
+  blocksAfterDispatch?: number;
+  /** `--follow-scheduled`: dispatch the tasks the proposal scheduled for later blocks */
+  followScheduled?: boolean;
+  /** `--enactment-retries`: retries to give an enactment task without a retry configuration */
+  enactmentRetries?: number;
+}
+
+/** What the simulator does besides simulating the referendum's own pallet. */
+export interface ReferendumSimulatorOptions {
+  hooks?: HookRunner;
+  /** `--track-balance` checks, compared across the dispatch */
+  balanceChecks?: BalanceCheck[];
+  /** Referenda pallet instance, when not the default for `isFellowship` */
+  pallet?: string;
+  dispatchOptions?: DispatchOptions;
+}
+
+/** Scheduled tasks followed at most, counting tasks scheduled by followed ones. */
+const MAX_FOLLOWED_TASKS = 8;
+
+/** Retries of a failed enactment task dispatched at most. */
+const MAX_ENACTMENT_RETRIES = 10;
+
 export class ReferendumSimulator {
   private logger: Logger;
   private chopsticks: ChopsticksManager;
   private api: SubstrateApi;
   private isFellowship: boolean;
+  private pallet?: string;
   private scheduler: SchedulerManager;
   private resultChecker: ExecutionResultChecker;
+  private treasuryPayouts: TreasuryPayouts;
+  private hooks?: HookRunner;
+  private balanceChecks: BalanceCheck[];
+  private balanceTracker?: BalanceTracker;
+  private treasuryPot?: TreasuryPot;
+  private trackChanges?: TrackChanges;
+  private nativeTokenLookup?: Promise<NativeToken | undefined>;
+  private convictionApprover?: ConvictionApprover;
+  private dispatchOptions: DispatchOptions;
 
   constructor(
     logger: Logger,
     chopsticks: ChopsticksManager,
     api: SubstrateApi,
-    isFellowship: boolean = false
+    isFellowship: boolean = false,
+    options: ReferendumSimulatorOptions = {}
   ) {
     this.logger = logger;
     this.chopsticks = chopsticks;
     this.api = api;
     this.isFellowship = isFellowship;
-    this.scheduler = new SchedulerManager(logger, chopsticks, api, isFellowship);
+    this.pallet = options.pallet;
+    this.hooks = options.hooks;
+    this.balanceChecks = options.balanceChecks ?? [];
+    this.dispatchOptions = options.dispatchOptions ?? {};
+    this.scheduler = new SchedulerManager(logger, chopsticks, api, isFellowship, this.pallet);
     this.resultChecker = new ExecutionResultChecker(logger);
+    this.treasuryPayouts = new TreasuryPayouts(logger, chopsticks, api, this.scheduler, () =>
+      this.nativeToken()
+    );
   }
 
   private getReferendaPalletName(): string {
-    return getReferendaPalletName(this.isFellowship);
+    return getReferendaPalletName(this.isFellowship, this.pallet);
   }
 
   private getReferendaPalletQuery(): ReferendaPallet {
-    return getReferendaPallet(this.api, this.isFellowship);
+    return getReferendaPallet(this.api, this.isFellowship, this.pallet);
   }
 
   async simulate(
     referendum: ReferendumInfo,
-    preExecutionOptions?: { preCall?: string; preOrigin?: string }
+    preExecutionOptions?: PreExecutionOptions
   ): Promise<SimulationResult> {
     const result: SimulationResult = {
       referendumId: referendum.id,
@@ -77,6 +206,8 @@ export class ReferendumSimulator {
     };
 
     try {
+      const atSessionBoundary = await this.warnIfForkedAtSessionBoundary();
+
       if (referendum.status === 'approved') {
         this.logger.info(`Referendum #${referendum.id} is already approved - skipping simulation`);
         return {
@@ -87,6 +218,14 @@ export class ReferendumSimulator {
         };
       }
 
+      if (preExecutionOptions?.cancelAt) {
+        return await this.simulateCancellation(
+          referendum,
+          preExecutionOptions.cancelAt,
+          preExecutionOptions
+        );
+      }
+
       this.logger.section('Simulating Referendum Execution (Force Approval Strategy)');
 
       const executionResult = await this.forceReferendumExecution(referendum, preExecutionOptions);
@@ -94,57 +233,326 @@ export class ReferendumSimulator {
       result.executionSucceeded = executionResult.executionSucceeded;
       result.events = executionResult.events;
       result.blockExecuted = executionResult.blockExecuted;
+      if (executionResult.syntheticVotes) {
+        result.syntheticVotes = executionResult.syntheticVotes;
+      }
 
       if (executionResult.errors && executionResult.errors.length > 0) {
         result.errors = executionResult.errors;
       }
+      if (executionResult.notDispatched) {
+        result.notDispatched = executionResult.notDispatched;
+      }
+      if (atSessionBoundary && result.notDispatched === 'call-unavailable') {
+        result.errors = [...(result.errors ?? []), SESSION_BOUNDARY_LIMITATION];
+      }
+      if (executionResult.postponedBlocks) {
+        result.postponedBlocks = executionResult.postponedBlocks;
+      }
+      if (executionResult.retries) {
+        result.retries = executionResult.retries;
+      }
+      const { blocksAfterDispatch = 0, followScheduled } = this.dispatchOptions;
+      if (blocksAfterDispatch > 0 && !result.notDispatched) {
+        result.afterDispatch = await this.buildBlocksAfterDispatch(blocksAfterDispatch);
+      }
+      const scheduledTasks = findScheduledTasks(result.events);
+      if (followScheduled && result.executionSucceeded && scheduledTasks.length > 0) {
+        result.scheduledTasks = await this.followScheduledTasks(scheduledTasks);
+        const failed = result.scheduledTasks.filter((task) => !task.succeeded);
+        if (failed.length > 0) {
+          result.executionSucceeded = false;
+          result.errors = failed.flatMap((task) =>
+            (task.errors ?? ['dispatch failed']).map(
+              (error) => `Scheduled task [${task.scheduledAt}, ${task.index}]: ${error}`
+            )
+          );
+        }
+      }
+
+      if (result.executionSucceeded) {
+        const spends = await this.treasuryPayouts.payoutAll(result.events);
+        if (spends.length > 0) {
+          result.treasurySpends = spends;
+        }
+        const balanceChanges = await this.reportBalanceChanges();
+        if (balanceChanges.length > 0) {
+          result.balanceChanges = balanceChanges;
+        }
+        const potChanges = await this.treasuryPot?.report(this.logger, await this.nativeToken());
+        if (potChanges?.some((change) => change.delta !== 0n)) {
+          result.treasuryPot = potChanges;
+        }
+        const trackChanges = await this.reportTrackChanges();
+        if (trackChanges.length > 0) {
+          result.trackChanges = trackChanges;
+        }
+        const convictionLock = await this.convictionApprover?.checkLock();
+        if (convictionLock) {
+          result.convictionLock = convictionLock;
+        }
+      }
 
       return result;
     } catch (error) {
+      // A failing hook is the user's own assertion, and a failed cancellation
+      // the outcome of the one asked for; neither is an execution failure.
+      const code = errorCodeOf(error);
+      if (code === ErrorCode.HookFailed || code === ErrorCode.CancellationFailed) {
+        throw error;
+      }
       this.logger.error('Simulation failed', error as Error);
       result.errors = [(error as Error).message];
       return result;
     }
   }
 
+  /**
+   * Warn when the fork starts on a session boundary, where the scheduler may
+   * not find noted preimages. Returns whether it does.
+   */
+  private async warnIfForkedAtSessionBoundary(): Promise<boolean> {
+    const forkHead = this.chopsticks.getForkHead();
+    if (!forkHead) return false;
+    const epochLength = await readEpochLength(this.api);
+    if (!isSessionBoundary(forkHead.number, epochLength)) return false;
+    this.logger.warn(
+      `Forked at session boundary #${forkHead.number} (epoch ${epochLength} blocks): ${SESSION_BOUNDARY_LIMITATION}`
+    );
+    return true;
+  }
+
+  /**
+   * `--cancel-at`: cancel the referendum at `phase` instead of dispatching it.
+   * The proposal never runs, so there are no dispatch events, treasury
+   * payouts or balance changes to report.
+   */
+  private async simulateCancellation(
+    referendum: ReferendumInfo,
+    phase: CancelPhase,
+    preExecutionOptions: PreExecutionOptions
+  ): Promise<SimulationResult> {
+    this.logger.section(`Simulating Referendum Cancellation (${phase})`);
+    if (preExecutionOptions.preCall) {
+      await this.executePreCall(preExecutionOptions.preCall, preExecutionOptions.preOrigin);
+    }
+    let syntheticVotes: SyntheticVote[] | undefined;
+    if (phase === 'confirming') {
+      syntheticVotes = await this.applyPassingState(referendum);
+      await this.runHook('after-passing', referendum.id);
+    }
+
+    const cancellation = await new ReferendumCancellation(
+      this.logger,
+      this.chopsticks,
+      this.api,
+      this.scheduler,
+      this.getReferendaPalletName(),
+      this.isFellowship
+    ).cancel(referendum.id, phase);
+
+    return {
+      referendumId: referendum.id,
+      executionSucceeded: cancellation.deposits.every((deposit) => deposit.refunded),
+      events: [],
+      blockExecuted: cancellation.block,
+      ...(syntheticVotes ? { syntheticVotes } : {}),
+      cancellation,
+    };
+  }
+
+  private async runHook(phase: HookPhase, referendumId: number): Promise<void> {
+    if (!this.hooks) return;
+    await this.hooks.run(phase, {
+      endpoint: this.chopsticks.getContext().ws.endpoint,
+      referendumId,
+      isFellowship: this.isFellowship,
+      block: Number(await this.api.query.System.Number.getValue()),
+    });
+  }
+
+  /**
+   * Record `--track-balance` balances, the treasury pot and the referenda
+   * tracks before the referendum is forced through.
+   */
+  private async snapshotBalances(): Promise<void> {
+    this.treasuryPot = await this.withForkRequest(runtimePallets)
+      .then((pallets) => TreasuryPot.snapshot(this.api, pallets))
+      .catch((error) => {
+        this.logger.warn(`Could not snapshot the treasury pot: ${(error as Error).message}`);
+        return undefined;
+      });
+    this.trackChanges = await this.snapshotTracks().catch((error) => {
+      this.logger.warn(`Could not snapshot the referenda tracks: ${(error as Error).message}`);
+      return undefined;
+    });
+    if (this.balanceChecks.length === 0) return;
+    this.balanceTracker = new BalanceTracker(this.api);
+    for (const { asset, account } of this.balanceChecks) {
+      await this.balanceTracker.snapshot(asset, account);
+    }
+  }
+
+  /** Print how tracked balances moved across dispatch (and treasury payouts). */
+  private async reportBalanceChanges(): Promise<BalanceChange[]> {
+    if (!this.balanceTracker) return [];
+
+    this.logger.section('Balance Changes');
+    const deltas = await this.balanceTracker.deltas();
+    const changes: BalanceChange[] = [];
+    for (const { asset, account, expectedDelta } of this.balanceChecks) {
+      const tracked = deltas.find(
+        (d) => d.account === account && describeAsset(d.asset) === describeAsset(asset)
+      );
+      if (!tracked) continue;
+      const { before, after, delta } = tracked;
+      const label = await assetLabel(this.api, asset);
+      const token = asset.type === 'native' ? await this.nativeToken() : undefined;
+      const amount = (value: bigint) => formatTokenAmount(value, token);
+      const line = `${account} ${label}: ${amount(before)} -> ${amount(after)} (${formatDelta(delta, token)})`;
+      if (expectedDelta === undefined) {
+        this.logger.info(`  ${line}`);
+      } else if (expectedDelta === delta) {
+        this.logger.success(`  \u2713 ${line}`);
+      } else {
+        this.logger.error(`  \u2717 ${line}, expected ${formatDelta(expectedDelta, token)}`);
+      }
+      changes.push({ account, asset: label, before, after, delta, expectedDelta });
+    }
+    return changes;
+  }
+
+  /** Record the referenda tracks, on chains whose referenda pallet has them. */
+  private async snapshotTracks(): Promise<TrackChanges | undefined> {
+    const constants = this.api.constants as unknown as Record<string, unknown>;
+    if (!constants[this.getReferendaPalletName()]) return undefined;
+    return this.withForkRequest(TrackChanges.snapshot);
+  }
+
+  /** Print how the proposal changed the referenda tracks, if it did. */
+  private async reportTrackChanges(): Promise<TrackChange[]> {
+    const snapshot = this.trackChanges;
+    if (!snapshot) return [];
+    return this.withForkRequest((request) => snapshot.report(this.logger, request)).catch(
+      (error) => {
+        this.logger.warn(`Could not compare the referenda tracks: ${(error as Error).message}`);
+        return [];
+      }
+    );
+  }
+
+  /** The forked chain's native token, read once from `system_properties`. */
+  private nativeToken(): Promise<NativeToken | undefined> {
+    this.nativeTokenLookup ??= this.withForkRequest(async (request) => {
+      const properties = await getChainProperties(request);
+      return properties.token;
+    }).catch(() => undefined);
+    return this.nativeTokenLookup;
+  }
+
+  /** Run `read` with raw RPC access to the fork, on a client of its own. */
+  private async withForkRequest<T>(read: (request: RpcRequest) => Promise<T>): Promise<T> {
+    const client = createPolkadotClient(this.chopsticks.getContext().ws.endpoint);
+    try {
+      return await read((method, params) => client._request(method, params));
+    } finally {
+      client.destroy();
+    }
+  }
+
   private async forceReferendumExecution(
     referendum: ReferendumInfo,
-    preExecutionOptions?: { preCall?: string; preOrigin?: string }
+    preExecutionOptions?: PreExecutionOptions
   ): Promise<{
     executionSucceeded: boolean;
     events: ParsedEvent[];
     errors?: string[];
+    notDispatched?: NotDispatchedReason;
     blockExecuted: number;
+    syntheticVotes?: SyntheticVote[];
+    postponedBlocks?: number;
+    retries?: EnactmentRetries;
   }> {
     if (preExecutionOptions?.preCall) {
       await this.executePreCall(preExecutionOptions.preCall, preExecutionOptions.preOrigin);
     }
+    await this.snapshotBalances();
 
     try {
-      await this.applyPassingState(referendum);
-      const { events, executionBlock, scheduledBlock, scheduledTaskIndex, scheduledTaskId } =
-        await this.scheduleAndExecuteProposal(referendum);
-
-      const { executionSucceeded, errors } = this.resultChecker.checkExecutionResults(
+      const syntheticVotes = await this.applyPassingState(
+        referendum,
+        preExecutionOptions?.conviction
+      );
+      await this.runHook('after-passing', referendum.id);
+      const {
         events,
+        executionBlock,
         scheduledBlock,
         scheduledTaskIndex,
-        scheduledTaskId
-      );
+        scheduledTaskId,
+        postponedBlocks,
+        stillPending,
+        retryConfig,
+      } = await this.scheduleAndExecuteProposal(referendum, preExecutionOptions?.scenario);
+      const postponed = postponedBlocks > 0 ? { postponedBlocks } : {};
+
+      if (stillPending) {
+        return {
+          executionSucceeded: false,
+          events,
+          errors: [describePostponedTask(scheduledBlock, scheduledTaskIndex, postponedBlocks)],
+          notDispatched: 'postponed',
+          blockExecuted: executionBlock,
+          syntheticVotes,
+          ...postponed,
+        };
+      }
+
+      const { executionSucceeded, errors, notDispatched } =
+        this.resultChecker.checkExecutionResults(
+          events,
+          scheduledBlock,
+          scheduledTaskIndex,
+          scheduledTaskId
+        );
+
+      // The scheduler only retries a dispatch that failed, not a task it dropped
+      if (!executionSucceeded && !notDispatched && retryConfig) {
+        return {
+          ...(await this.retryEnactment(retryConfig, events, errors ?? [])),
+          syntheticVotes,
+          ...postponed,
+        };
+      }
 
       return {
         executionSucceeded,
         events,
         errors,
+        notDispatched,
         blockExecuted: executionBlock,
+        syntheticVotes,
+        ...postponed,
       };
     } catch (error) {
       this.logger.failSpinner('Failed to force referendum execution');
+      if (errorCodeOf(error) === ErrorCode.HookFailed) {
+        throw error;
+      }
       throw new Error('Failed to force referendum execution', { cause: error });
     }
   }
 
-  private async applyPassingState(referendum: ReferendumInfo): Promise<void> {
+  /**
+   * Put the referendum into its confirming state. Conviction tallies are set
+   * to the whole issuance, or with `conviction` emptied and then voted on by
+   * a funded voter; ranked tallies are emptied and then voted on by injected
+   * collective members. Synthetic votes are returned.
+   */
+  private async applyPassingState(
+    referendum: ReferendumInfo,
+    conviction?: Conviction
+  ): Promise<SyntheticVote[] | undefined> {
     this.logger.startSpinner('Forcing referendum to passing state...');
 
     const palletName = this.getReferendaPalletName();
@@ -152,7 +560,8 @@ export class ReferendumSimulator {
     const refInfo = await palletQuery.ReferendumInfoFor.getValue(referendum.id);
 
     if (!refInfo) {
-      throw new Error(
+      throw new CodedError(
+        ErrorCode.ReferendumNotFound,
         `Referendum ${referendum.id} not found in ${palletName} pallet in Chopsticks instance`
       );
     }
@@ -167,10 +576,11 @@ export class ReferendumSimulator {
           'Referendum already approved in Chopsticks fork, attempting to execute scheduled call...'
         );
         this.logger.succeedSpinner('Referendum already approved \u2014 skipping state update');
-        return;
+        return undefined;
       }
 
-      throw new Error(
+      throw new CodedError(
+        ErrorCode.ReferendumNotOngoing,
         `Referendum ${referendum.id} is not in Ongoing state (current state: ${refInfo.type})`
       );
     }
@@ -180,10 +590,15 @@ export class ReferendumSimulator {
 
     const { currentBlock } = await this.scheduler.getSchedulingBlocks();
 
+    const voter =
+      conviction && !this.isFellowship
+        ? new ConvictionApprover(this.logger, this.chopsticks, this.api, palletQuery, conviction)
+        : undefined;
     const modifiedRefInfo = this.buildPassingReferendumStorage(
       refInfo.value,
       totalIssuance,
-      currentBlock
+      currentBlock,
+      !!voter
     );
 
     const referendumStorageUpdate = {
@@ -192,22 +607,44 @@ export class ReferendumSimulator {
       },
     };
 
+    const approver = this.isFellowship
+      ? new RankedApprover(this.logger, this.chopsticks, this.api, palletName)
+      : undefined;
+    await approver?.injectMembers();
+    // Twice the issuance leaves the voter enough for fees once the issuance is voted.
+    await voter?.injectVoter(refInfo.value.track, totalIssuance * 2n);
+
     this.logger.debug(
       `Sending storage update to ${palletName} pallet in Chopsticks: ${stringify(modifiedRefInfo, 2)}`
     );
     await this.chopsticks.setStorageBatch(referendumStorageUpdate);
-    this.logger.succeedSpinner('Referendum state updated to passing');
+    this.logger.succeedSpinner(
+      approver || voter
+        ? 'Referendum state updated to deciding'
+        : 'Referendum state updated to passing'
+    );
 
     await this.chopsticks.newBlock();
+    const syntheticVotes =
+      (await approver?.approve(referendum.id)) ??
+      (await voter?.approve(referendum.id, refInfo.value.track, totalIssuance - 1n));
+    this.convictionApprover = voter;
     await this.verifyReferendumModification(referendum.id);
+    return syntheticVotes;
   }
 
-  private async scheduleAndExecuteProposal(referendum: ReferendumInfo): Promise<{
+  private async scheduleAndExecuteProposal(
+    referendum: ReferendumInfo,
+    scenario?: Scenario
+  ): Promise<{
     events: ParsedEvent[];
     executionBlock: number;
     scheduledBlock: number;
     scheduledTaskIndex: number;
     scheduledTaskId: Uint8Array | undefined;
+    postponedBlocks: number;
+    stillPending: boolean;
+    retryConfig: RetryConfig | undefined;
   }> {
     this.logger.startSpinner('Moving nudgeReferendum to next block...');
     await this.scheduler.moveScheduledCallToNextBlock(referendum.id, 'nudge');
@@ -222,6 +659,13 @@ export class ReferendumSimulator {
       referendum.id
     );
 
+    const dispatchOrigin = scenario === 'bad-origin' ? MISMATCHED_DISPATCH_ORIGIN : undefined;
+    if (dispatchOrigin) {
+      this.logger.warn(
+        `Scenario bad-origin: dispatching the proposal from ${stringify(dispatchOrigin)} instead of its referendum origin`
+      );
+    }
+
     this.logger.startSpinner('Moving proposal execution to next block...');
     const proposalHash = referendum.proposal.hash;
     this.logger.debug(`Looking for proposal execution with hash: ${proposalHash}`);
@@ -229,18 +673,186 @@ export class ReferendumSimulator {
       block: scheduledBlock,
       taskIndex: scheduledTaskIndex,
       taskId: scheduledTaskId,
-    } = await this.scheduler.moveScheduledCallToNextBlock(referendum.id, 'execute', proposalHash);
+    } = await this.scheduler.moveScheduledCallToNextBlock(
+      referendum.id,
+      'execute',
+      proposalHash,
+      dispatchOrigin
+    );
     this.logger.succeedSpinner(`Proposal execution scheduled at block ${scheduledBlock}`);
+    const retryConfig = await this.enactmentRetryConfig(scheduledBlock, scheduledTaskIndex);
+    await this.runHook('before-dispatch', referendum.id);
 
     this.logger.startSpinner('Creating block to execute proposal...');
     await this.chopsticks.newBlock();
 
-    const executionBlock = Number(await this.api.query.System.Number.getValue());
+    let executionBlock = Number(await this.api.query.System.Number.getValue());
     this.logger.succeedSpinner(`Proposal executed at block ${executionBlock}`);
 
-    const events = await this.fetchBlockEvents(executionBlock);
+    let events = await this.fetchBlockEvents(executionBlock);
+
+    // A block that runs out of scheduler weight leaves the rest of its agenda
+    // for the next block without any event, which otherwise reads as a missing
+    // Dispatched event. Build more blocks until the scheduler reaches the task.
+    let postponedBlocks = 0;
+    let stillPending = await this.isEnactmentPending(events, scheduledBlock, scheduledTaskIndex);
+    while (stillPending && postponedBlocks < ENACTMENT_RETRY_BLOCKS) {
+      postponedBlocks++;
+      this.logger.warn(
+        `Block ${executionBlock} ran out of scheduler weight before enactment task [${scheduledBlock}, ${scheduledTaskIndex}]; building another block (${postponedBlocks}/${ENACTMENT_RETRY_BLOCKS})`
+      );
+      await this.chopsticks.newBlock();
+      executionBlock = Number(await this.api.query.System.Number.getValue());
+      events = await this.fetchBlockEvents(executionBlock);
+      stillPending = await this.isEnactmentPending(events, scheduledBlock, scheduledTaskIndex);
+    }
+    if (postponedBlocks > 0 && !stillPending) {
+      this.logger.warn(
+        `Enactment was postponed by ${postponedBlocks} block(s) and serviced at block ${executionBlock}`
+      );
+    }
+    await this.runHook('after-dispatch', referendum.id);
+
+    return {
+      events,
+      executionBlock,
+      scheduledBlock,
+      scheduledTaskIndex,
+      scheduledTaskId,
+      postponedBlocks,
+      stillPending,
+      retryConfig,
+    };
+  }
 
-    return { events, executionBlock, scheduledBlock, scheduledTaskIndex, scheduledTaskId };
+  /**
+   * The enactment task's retry configuration: its own, or the one
+   * `--enactment-retries` gives a task without one (retrying every block).
+   */
+  private async enactmentRetryConfig(
+    block: number,
+    taskIndex: number
+  ): Promise<RetryConfig | undefined> {
+    let config = await this.scheduler.getRetryConfig(block, taskIndex);
+    const { enactmentRetries } = this.dispatchOptions;
+    if (!config && enactmentRetries) {
+      config = { totalRetries: enactmentRetries, remaining: enactmentRetries, period: 1 };
+      await this.scheduler.setRetryConfig(block, taskIndex, config);
+    }
+    if (config) {
+      this.logger.info(
+        `Enactment task retry configuration: ${config.remaining} of ${config.totalRetries} retries left, every ${config.period} block(s)`
+      );
+    }
+    return config;
+  }
+
+  /**
+   * After the enactment task failed, dispatch the retries the scheduler
+   * schedules for it, each in the next block rather than `period` blocks
+   * later, until one succeeds, none is scheduled or
+   * {@link MAX_ENACTMENT_RETRIES} were dispatched. The result is that of the
+   * last retry, so a retry that succeeds makes the execution succeed.
+   */
+  private async retryEnactment(
+    config: RetryConfig,
+    failedEvents: ParsedEvent[],
+    firstErrors: string[]
+  ): Promise<{
+    executionSucceeded: boolean;
+    events: ParsedEvent[];
+    errors?: string[];
+    notDispatched?: NotDispatchedReason;
+    blockExecuted: number;
+    retries: EnactmentRetries;
+  }> {
+    this.logger.section('Enactment Retries');
+    this.logger.warn(
+      `Enactment failed (${firstErrors.join('; ')}); the scheduler retries it up to ${config.remaining} time(s)`
+    );
+
+    const attempts: RetryAttempt[] = [];
+    let events = failedEvents;
+    let blockExecuted = Number(await this.api.query.System.Number.getValue());
+    let last: ReturnType<ExecutionResultChecker['checkExecutionResults']> = {
+      executionSucceeded: false,
+    };
+    let retry = await this.findRetryTask(events);
+    while (retry && attempts.length < MAX_ENACTMENT_RETRIES) {
+      const moved = await this.scheduler.moveTaskToNextBlock(retry.when, retry.index);
+      await this.chopsticks.newBlock();
+      blockExecuted = Number(await this.api.query.System.Number.getValue());
+      events = await this.fetchBlockEvents(blockExecuted);
+      last = this.resultChecker.checkExecutionResults(events, moved.block, retry.index);
+      attempts.push({
+        scheduledAt: retry.when,
+        index: retry.index,
+        executedAt: blockExecuted,
+        succeeded: last.executionSucceeded,
+        errors: last.errors,
+      });
+
+      const label = `Retry ${attempts.length} (task [${retry.when}, ${retry.index}])`;
+      if (last.executionSucceeded) {
+        this.logger.success(`\u2713 ${label} executed successfully at block ${blockExecuted}`);
+        break;
+      }
+      this.logger.error(`${label} failed at block ${blockExecuted}: ${last.errors?.join('; ')}`);
+      if (last.notDispatched) break;
+      retry = await this.findRetryTask(events);
+    }
+
+    if (retry && !last.executionSucceeded && !last.notDispatched) {
+      this.logger.warn(
+        `Not dispatching further retries: at most ${MAX_ENACTMENT_RETRIES} are simulated`
+      );
+    } else if (attempts.length === 0) {
+      const retryFailed = events.some(
+        (e) => e.section === 'Scheduler' && e.method === 'RetryFailed'
+      );
+      this.logger.warn(
+        retryFailed
+          ? 'The scheduler could not place a retry of the enactment task (Scheduler.RetryFailed)'
+          : 'The scheduler scheduled no retry of the enactment task'
+      );
+    }
+
+    const retries = { config, firstErrors, attempts };
+    if (last.executionSucceeded) {
+      return { executionSucceeded: true, events, blockExecuted, retries };
+    }
+    return {
+      executionSucceeded: false,
+      events,
+      errors: [
+        ...firstErrors,
+        ...attempts.map(
+          (attempt, n) =>
+            `Retry ${n + 1} at block ${attempt.executedAt}: ${attempt.errors?.join('; ') ?? 'dispatch failed'}`
+        ),
+      ],
+      notDispatched: last.notDispatched,
+      blockExecuted,
+      retries,
+    };
+  }
+
+  /** The task the block's events scheduled that has a retry configuration: the retry. */
+  private async findRetryTask(events: ParsedEvent[]): Promise<ScheduledTask | undefined> {
+    for (const task of findScheduledTasks(events)) {
+      if (await this.scheduler.getRetryConfig(task.when, task.index)) return task;
+    }
+    return undefined;
+  }
+
+  /** The task was neither dispatched nor dropped, and is still in its agenda slot. */
+  private async isEnactmentPending(
+    events: ParsedEvent[],
+    scheduledBlock: number,
+    scheduledTaskIndex: number
+  ): Promise<boolean> {
+    if (findNotDispatchedTask(events, scheduledBlock, scheduledTaskIndex)) return false;
+    return this.scheduler.isTaskPending(scheduledBlock, scheduledTaskIndex);
   }
 
   /**
@@ -258,7 +870,8 @@ export class ReferendumSimulator {
     );
 
     if (!confirmedEvent && !approvedEvent) {
-      throw new Error(
+      throw new CodedError(
+        ErrorCode.ReferendumNotApproved,
         `Referendum #${referendumId} was not confirmed or approved after nudge. ` +
           `Expected ${palletName}.Confirmed or ${palletName}.Approved events but found: ` +
           nudgeEvents.map((e) => `${e.section}.${e.method}`).join(', ')
@@ -272,7 +885,8 @@ export class ReferendumSimulator {
       if (eventData && 'index' in eventData) {
         const eventRefId = Number(eventData.index);
         if (eventRefId !== referendumId) {
-          throw new Error(
+          throw new CodedError(
+            ErrorCode.ReferendumNotApproved,
             `${palletName}.${event.method} fired for referendum #${eventRefId}, ` +
               `but expected referendum #${referendumId}`
           );
@@ -286,7 +900,8 @@ export class ReferendumSimulator {
   private buildPassingReferendumStorage(
     ongoingData: ReferendumOngoing,
     totalIssuance: bigint,
-    currentBlock: number
+    currentBlock: number,
+    voted = false
   ): Record<string, unknown> {
     const originForStorage = convertOriginToStorageFormat(ongoingData.origin);
     const proposalForStorage = convertProposalToStorageFormat(ongoingData.proposal);
@@ -296,13 +911,13 @@ export class ReferendumSimulator {
 
     this.logger.debug('Setting referendum enactment to execute immediately (after: 0 blocks)');
 
+    // A ranked tally starts empty and is filled by the synthetic members' votes,
+    // and so does a conviction tally with `--vote-conviction`.
     let tally: Record<string, unknown>;
     if (this.isFellowship) {
-      tally = {
-        bare_ayes: FELLOWSHIP_PASSING_BARE_AYES,
-        ayes: FELLOWSHIP_PASSING_AYES,
-        nays: 0,
-      };
+      tally = EMPTY_RANKED_TALLY;
+    } else if (voted) {
+      tally = { ayes: '0', nays: '0', support: '0' };
     } else {
       tally = {
         ayes: (totalIssuance - 1n).toString(),
@@ -360,27 +975,8 @@ export class ReferendumSimulator {
     const preOrigin = originString ? this.parseOriginString(originString) : { System: 'Root' };
     this.logger.info(`Pre-call origin: ${stringify(preOrigin)}`);
 
-    const { targetBlock: nextBlock } = await this.scheduler.getSchedulingBlocks();
-
-    this.logger.startSpinner(`Injecting pre-call into Scheduler at block ${nextBlock}...`);
-
-    const storageUpdate = {
-      Scheduler: {
-        agenda: [
-          [
-            [nextBlock],
-            [
-              {
-                call: { Inline: preCallHex },
-                origin: preOrigin,
-              },
-            ],
-          ],
-        ],
-      },
-    };
-
-    await this.chopsticks.setStorageBatch(storageUpdate);
+    this.logger.startSpinner('Injecting pre-call into Scheduler...');
+    const nextBlock = await this.scheduler.scheduleCallAtNextBlock(preCallHex, preOrigin);
     this.logger.succeedSpinner(`Pre-call injected into Scheduler at block ${nextBlock}`);
 
     this.logger.startSpinner('Creating block to execute pre-call...');
@@ -400,21 +996,81 @@ export class ReferendumSimulator {
   }
 
   private parseOriginString(originString: string): Record<string, string> {
-    if (originString === 'Root') {
-      return { System: 'Root' };
-    }
+    return parseOriginString(originString, this.logger);
+  }
 
-    if (originString.includes('.')) {
-      const [palletOrType, variant] = originString.split('.');
-      return { [palletOrType]: variant };
+  /**
+   * `--blocks-after-dispatch`: build blocks past the dispatch block and show
+   * their events, for effects deferred to later blocks (`on_idle` work,
+   * message queue servicing).
+   */
+  private async buildBlocksAfterDispatch(
+    count: number
+  ): Promise<SimulationResult['afterDispatch']> {
+    this.logger.section('Events After Dispatch');
+    const blocks: NonNullable<SimulationResult['afterDispatch']> = [];
+    for (let n = 1; n <= count; n++) {
+      await this.chopsticks.newBlock();
+      const [block, events] = await Promise.all([
+        this.api.query.System.Number.getValue(),
+        this.api.query.System.Events.getValue(),
+      ]);
+      const label = `Governance [after dispatch ${n}/${count}]`;
+      displayChainEvents(label, block, events, this.logger);
+      blocks.push({ block, events: (events ?? []).map(parseBlockEvent) });
     }
+    return blocks;
+  }
 
-    if (GOVERNANCE_ORIGINS.has(originString)) {
-      return { Origins: originString };
+  /**
+   * `--follow-scheduled`: dispatch the tasks the proposal scheduled for later
+   * blocks now rather than at their block, then the tasks those schedule in
+   * turn, up to {@link MAX_FOLLOWED_TASKS}, checking each one's own result.
+   * Moving a task moves its whole agenda, so tasks sharing its block run with it.
+   */
+  private async followScheduledTasks(tasks: ScheduledTask[]): Promise<FollowedTask[]> {
+    this.logger.section('Scheduled Tasks');
+    const queue = [...tasks];
+    const followed: FollowedTask[] = [];
+    while (queue.length > 0 && followed.length < MAX_FOLLOWED_TASKS) {
+      const when = queue[0].when;
+      const slot = queue.filter((task) => task.when === when);
+      queue.splice(0, queue.length, ...queue.filter((task) => task.when !== when));
+
+      const moved = await this.scheduler.moveTaskToNextBlock(when, slot[0].index);
+      await this.chopsticks.newBlock();
+      const executedAt = Number(await this.api.query.System.Number.getValue());
+      const events = await this.fetchBlockEvents(executedAt);
+
+      for (const { index } of slot) {
+        const { executionSucceeded, errors } = this.resultChecker.checkExecutionResults(
+          events,
+          moved.block,
+          index,
+          index === slot[0].index ? moved.taskId : undefined
+        );
+        followed.push({
+          scheduledAt: when,
+          index,
+          executedAt,
+          succeeded: executionSucceeded,
+          errors,
+        });
+        const task = `Task [${when}, ${index}] scheduled by the proposal`;
+        if (executionSucceeded) {
+          this.logger.success(`\u2713 ${task} executed successfully at block ${executedAt}`);
+        } else {
+          this.logger.error(`${task} failed at block ${executedAt}: ${errors?.join('; ')}`);
+        }
+      }
+      queue.push(...findScheduledTasks(events));
     }
-
-    this.logger.warn(`Unknown origin format "${originString}", treating as System origin`);
-    return { System: originString };
+    if (queue.length > 0) {
+      this.logger.warn(
+        `Not following ${queue.length} more scheduled task(s): at most ${MAX_FOLLOWED_TASKS} are followed`
+      );
+    }
+    return followed;
   }
 
   private async fetchBlockEvents(blockNumber: number): Promise<ParsedEvent[]> {
@@ -0,0 +1,44 @@
+import type { Logger } from '../utils/logger';
+
+/** What the run cost so far. Chopsticks runs in-process, so this includes every fork. */
+export interface ResourceUsage {
+  /** Wall-clock time since the process started */
+  wallMs: number;
+  /** User plus system CPU time of the process, all threads */
+  cpuMs: number;
+  /** Peak resident set size */
+  peakRssBytes: number;
+}
+
+/** Measure the run's usage; `process.resourceUsage()` reports µs and KiB. */
+export function measureResourceUsage(
+  usage: NodeJS.ResourceUsage = process.resourceUsage(),
+  uptimeSeconds: number = process.uptime()
+): ResourceUsage {
+  return {
+    wallMs: Math.round(uptimeSeconds * 1000),
+    cpuMs: Math.round((usage.userCPUTime + usage.systemCPUTime) / 1000),
+    peakRssBytes: usage.maxRSS * 1024,
+  };
+}
+
+function formatSeconds(ms: number): string {
+  return `${(ms / 1000).toFixed(1)}s`;
+}
+
+/** `812.4 MiB` */
+export function formatMebibytes(bytes: number): string {
+  return `${(bytes / 1024 / 1024).toFixed(1)} MiB`;
+}
+
+/**
+ * Show how long the run took and how much CPU and memory it used, so memory
+ * growth of multi-chain runs shows up before it exhausts a CI runner. Hook
+ * scripts run as child processes and are not counted.
+ */
+export function displayResourceUsage(logger: Logger, usage: ResourceUsage): void {
+  logger.section('Resource Usage');
+  logger.info(`Wall time: ${formatSeconds(usage.wallMs)}`);
+  logger.info(`CPU time: ${formatSeconds(usage.cpuMs)}`);
+  logger.info(`Peak RSS: ${formatMebibytes(usage.peakRssBytes)}`);
+}
@@ -0,0 +1,277 @@
+import { randomBytes } from 'crypto';
+import * as fs from 'fs';
+import * as path from 'path';
+import type { SimulationResult } from '../types';
+import type { SubstrateApi } from '../types/substrate-api';
+import { interpretDispatchResult } from '../utils/dispatch-result';
+import { serializeEventData } from '../utils/event-serializer';
+import { stringify } from '../utils/json';
+import type { Logger } from '../utils/logger';
+import { getReferendaPalletName } from './chain-registry';
+
+/**
+ * A simulated referendum as stored in the history directory. Results are
+ * stored JSON-shaped: event data serialized, bigints as decimal strings.
+ */
+export interface RunRecord {
+  key: string;
+  recordedAt: string;
+  /** `--run-id` of the run that stored the record */
+  runId?: string;
+  result: SimulationResult;
+}
+
+export interface WeightChange {
+  event: string;
+  before: unknown;
+  after: unknown;
+}
+
+export interface BalanceDeltaChange {
+  account: string;
+  asset: string;
+  before?: string;
+  after?: string;
+}
+
+/** What changed between two runs of the same referendum. */
+export interface RunDiff {
+  executionChanged?: { before: boolean; after: boolean };
+  newFailingEvents: string[];
+  changedWeights: WeightChange[];
+  changedBalances: BalanceDeltaChange[];
+}
+
+/** `<spec name>-<referenda pallet>-<id>`, e.g. `asset-hub-polkadot-Referenda-1234`. */
+export async function runKey(
+  api: SubstrateApi,
+  referendumId: number,
+  isFellowship: boolean,
+  pallet?: string
+): Promise<string> {
+  const version = await api.constants.System.Version();
+  const specName: string = version.spec_name || version.specName || 'unknown';
+  return `${specName}-${getReferendaPalletName(isFellowship, pallet)}-${referendumId}`;
+}
+
+/** The result as it reads back from disk, so stored and fresh results compare equal. */
+export function toStoredResult(result: SimulationResult): SimulationResult {
+  const events = result.events.map((event) => ({
+    ...event,
+    data: serializeEventData(event.data),
+  }));
+  return JSON.parse(stringify({ ...result, events }));
+}
+
+/** Events that report a failure: `*Failed` events and events carrying a failed dispatch result. */
+function failingEvents(result: SimulationResult): string[] {
+  const failing: string[] = [];
+  for (const event of result.events) {
+    const name = `${event.section}.${event.method}`;
+    const data = event.data as Record<string, unknown> | undefined;
+    const outcome = interpretDispatchResult(data?.result);
+    if (outcome.outcome === 'failure') {
+      failing.push(outcome.message ? `${name}: ${outcome.message}` : name);
+    } else if (event.method.endsWith('Failed')) {
+      failing.push(name);
+    }
+  }
+  return failing;
+}
+
+/**
+ * Weights reported by events (e.g. `System.ExtrinsicSuccess` dispatch info),
+ * keyed by `Section.Method#n` in event order and the field path.
+ */
+function eventWeights(result: SimulationResult): Map<string, unknown> {
+  const weights = new Map<string, unknown>();
+  const seen = new Map<string, number>();
+  const visit = (value: unknown, at: string): void => {
+    if (typeof value !== 'object' || value === null) return;
+    for (const [field, inner] of Object.entries(value)) {
+      if (/weight/i.test(field) && typeof inner === 'object' && inner !== null) {
+        weights.set(`${at}.${field}`, inner);
+      } else {
+        visit(inner, `${at}.${field}`);
+      }
+    }
+  };
+  for (const event of result.events) {
+    const name = `${event.section}.${event.method}`;
+    const n = seen.get(name) ?? 0;
+    seen.set(name, n + 1);
+    visit(event.data, `${name}#${n}`);
+  }
+  return weights;
+}
+
+export function diffRuns(previous: SimulationResult, current: SimulationResult): RunDiff {
+  const before = new Set(failingEvents(previous));
+  const previousWeights = eventWeights(previous);
+  const changedWeights: WeightChange[] = [];
+  for (const [event, after] of eventWeights(current)) {
+    const weight = previousWeights.get(event);
+    if (weight !== undefined && stringify(weight) !== stringify(after)) {
+      changedWeights.push({ event, before: weight, after });
+    }
+  }
+
+  const deltaOf = (result: SimulationResult, account: string, asset: string) =>
+    result.balanceChanges?.find((c) => c.account === account && c.asset === asset)?.delta;
+  const balances = new Map<string, { account: string; asset: string }>();
+  for (const { account, asset } of [
+    ...(previous.balanceChanges ?? []),
+    ...(current.balanceChanges ?? []),
+  ]) {
+    balances.set(`${account}@${asset}`, { account, asset });
+  }
+  const changedBalances: BalanceDeltaChange[] = [];
+  for (const { account, asset } of balances.values()) {
+    const was = deltaOf(previous, account, asset);
+    const now = deltaOf(current, account, asset);
+    if (`${was}` !== `${now}`) {
+      changedBalances.push({
+        account,
+        asset,
+        before: was === undefined ? undefined : `${was}`,
+        after: now === undefined ? undefined : `${now}`,
+      });
+    }
+  }
+
+  return {
+    executionChanged:
+      previous.executionSucceeded !== current.executionSucceeded
+        ? { before: previous.executionSucceeded, after: current.executionSucceeded }
+        : undefined,
+    newFailingEvents: failingEvents(current).filter((event) => !before.has(event)),
+    changedWeights,
+    changedBalances,
+  };
+}
+
+export function formatRunDiff(diff: RunDiff): string[] {
+  const outcome = (succeeded: boolean) => (succeeded ? 'succeeded' : 'failed');
+  const lines: string[] = [];
+  if (diff.executionChanged) {
+    const { before, after } = diff.executionChanged;
+    lines.push(`Execution: ${outcome(before)} -> ${outcome(after)}`);
+  }
+  for (const event of diff.newFailingEvents) {
+    lines.push(`New failing event: ${event}`);
+  }
+  for (const { event, before, after } of diff.changedWeights) {
+    lines.push(`Weight ${event}: ${stringify(before)} -> ${stringify(after)}`);
+  }
+  for (const { account, asset, before, after } of diff.changedBalances) {
+    lines.push(`Balance ${account} ${asset}: ${before ?? 'untracked'} -> ${after ?? 'untracked'}`);
+  }
+  return lines;
+}
+
+/** Stored runs in `runDir`, oldest first (files are named by run number). */
+function runFiles(runDir: string): string[] {
+  if (!fs.existsSync(runDir)) return [];
+  return fs
+    .readdirSync(runDir)
+    .filter((file) => file.endsWith('.json'))
+    .sort();
+}
+
+/**
+ * Keeps every simulated referendum's result in `<dir>/<run key>/<n>.json`
+ * (default `.referenda-history` in the cwd) and, with `--diff-with-last`,
+ * reports what changed since the previous run of the same referendum. Only
+ * runs given `--history-dir` or `--diff-with-last` keep a history.
+ */
+export class RunHistory {
+  private logger: Logger;
+  private dir: string;
+  private diffWithLast: boolean;
+  private runId?: string;
+
+  constructor(
+    logger: Logger,
+    options: { dir?: string; diffWithLast?: boolean; runId?: string } = {}
+  ) {
+    this.logger = logger;
+    this.dir = options.dir
+      ? path.resolve(options.dir)
+      : path.join(process.cwd(), '.referenda-history');
+    this.diffWithLast = options.diffWithLast ?? false;
+    this.runId = options.runId;
+  }
+
+  /** The most recent stored run for `key`, if any. */
+  latest(key: string): RunRecord | undefined {
+    const runDir = path.join(this.dir, key);
+    const last = runFiles(runDir).at(-1);
+    return last ? JSON.parse(fs.readFileSync(path.join(runDir, last), 'utf8')) : undefined;
+  }
+
+  save(key: string, result: SimulationResult, recordedAt: Date = new Date()): RunRecord {
+    const record: RunRecord = {
+      key,
+      recordedAt: recordedAt.toISOString(),
+      ...(this.runId && { runId: this.runId }),
+      result: toStoredResult(result),
+    };
+    const runDir = path.join(this.dir, key);
+    fs.mkdirSync(runDir, { recursive: true });
+    // Write, then link under the next free run number: a killed run never
+    // leaves a half-written record, and concurrent runs never share a number
+    const tmp = path.join(runDir, `.${randomBytes(6).toString('hex')}.tmp`);
+    fs.writeFileSync(tmp, `${stringify(record, 2)}\n`, { flag: 'wx' });
+    try {
+      for (let run = runFiles(runDir).length + 1; ; run++) {
+        try {
+          fs.linkSync(tmp, path.join(runDir, `${String(run).padStart(4, '0')}.json`));
+          break;
+        } catch (error) {
+          if ((error as NodeJS.ErrnoException).code !== 'EEXIST') throw error;
+        }
+      }
+    } finally {
+      fs.rmSync(tmp, { force: true });
+    }
+    return record;
+  }
+
+  /**
+   * Store the result and, with `--diff-with-last`, print its differences from
+   * the previous run. History is best-effort: failures only warn.
+   */
+  async record(
+    api: SubstrateApi,
+    isFellowship: boolean,
+    result: SimulationResult,
+    pallet?: string
+  ): Promise<RunDiff | undefined> {
+    try {
+      const key = await runKey(api, result.referendumId, isFellowship, pallet);
+      const previous = this.latest(key);
+      const current = this.save(key, result);
+      if (!this.diffWithLast) return undefined;
+
+      this.logger.section('Changes Since Last Run');
+      if (!previous) {
+        this.logger.info(`No earlier run of ${key} in ${this.dir}`);
+        return undefined;
+      }
+      const diff = diffRuns(previous.result, current.result);
+      const lines = formatRunDiff(diff);
+      if (lines.length === 0) {
+        this.logger.success(`No changes since ${previous.recordedAt}`);
+      } else {
+        this.logger.warn(`${lines.length} change(s) since ${previous.recordedAt}:`);
+        for (const line of lines) {
+          this.logger.info(`  ${line}`);
+        }
+      }
+      return diff;
+    } catch (error) {
+      this.logger.warn(`Could not update run history in ${this.dir}: ${(error as Error).message}`);
+      return undefined;
+    }
+  }
+}
@@ -0,0 +1,100 @@
+import {
+  type ParsedEndpoint,
+  type ReportedEndpoint,
+  reportEndpoint,
+} from '../utils/chain-endpoint-parser';
+import { CodedError, ErrorCode, errorCodeOf, exitCodeOf } from '../utils/error-codes';
+import type { ChainHeadReport } from '../utils/logger';
+import { measureResourceUsage, type ResourceUsage } from './resource-usage';
+
+/** Prefix of the machine-readable result line printed with `--output-format json`. */
+export const RESULT_LINE_PREFIX = '@@result ';
+
+export const OUTPUT_FORMATS = ['text', 'json'] as const;
+
+export type OutputFormat = (typeof OUTPUT_FORMATS)[number];
+
+export function parseOutputFormat(value?: string): OutputFormat {
+  if (value === undefined) return 'text';
+  const format = OUTPUT_FORMATS.find((name) => name === value.trim());
+  if (!format) {
+    throw new CodedError(
+      ErrorCode.InvalidOutputFormat,
+      `Invalid --output-format value: ${value} (expected one of: ${OUTPUT_FORMATS.join(', ')})`
+    );
+  }
+  return format;
+}
+
+/** The chain URLs of a run, as the tool parsed them. */
+export interface RunEndpoints {
+  governance: ReportedEndpoint | null;
+  fellowship: ReportedEndpoint | null;
+  additionalChains: ReportedEndpoint[];
+}
+
+export function reportEndpoints(parsed: {
+  governance?: ParsedEndpoint;
+  fellowship?: ParsedEndpoint;
+  additionalChains: ParsedEndpoint[];
+}): RunEndpoints {
+  return {
+    governance: parsed.governance ? reportEndpoint(parsed.governance) : null,
+    fellowship: parsed.fellowship ? reportEndpoint(parsed.fellowship) : null,
+    additionalChains: parsed.additionalChains.map(reportEndpoint),
+  };
+}
+
+/** How a `test` run ended, as printed with `--output-format json`. */
+export interface RunReport {
+  runId: string;
+  /** The code the process exits with */
+  exitCode: number;
+  /** Why the run failed; `null` when it passed */
+  error: { code: ErrorCode; message: string } | null;
+  /** `null` when the run failed before its chain URLs were parsed */
+  endpoints: RunEndpoints | null;
+  /** Where each fork started and ended, as in `Final Heads`; empty when none came up */
+  heads: ChainHeadReport[];
+  resourceUsage: ResourceUsage;
+}
+
+export function buildRunReport(
+  runId: string,
+  error?: unknown,
+  usage: ResourceUsage = measureResourceUsage(),
+  endpoints?: RunEndpoints,
+  heads: ChainHeadReport[] = []
+): RunReport {
+  return {
+    runId,
+    exitCode: error === undefined ? 0 : exitCodeOf(error),
+    error:
+      error === undefined
+        ? null
+        : {
+            code: errorCodeOf(error),
+            message: error instanceof Error ? error.message : String(error),
+          },
+    endpoints: endpoints ?? null,
+    heads,
+    resourceUsage: usage,
+  };
+}
+
+/**
+ * Print the run's {@link RunReport} as one `@@result {json}` line when the
+ * format is `json`, so scripts can read the outcome without parsing logs.
+ */
+export function emitRunReport(
+  format: OutputFormat,
+  runId: string,
+  error?: unknown,
+  endpoints?: RunEndpoints,
+  heads?: ChainHeadReport[]
+): void {
+  if (format === 'json') {
+    const report = buildRunReport(runId, error, measureResourceUsage(), endpoints, heads);
+    console.log(`${RESULT_LINE_PREFIX}${JSON.stringify(report)}`);
+  }
+}
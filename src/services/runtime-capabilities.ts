@@ -0,0 +1,240 @@
+import { decAnyMetadata, unifyMetadata } from '@polkadot-api/substrate-bindings';
+import type { TestOptions } from '../types';
+import { CodedError, ErrorCode } from '../utils/error-codes';
+import type { Logger } from '../utils/logger';
+import type { RpcRequest } from './call-drift-detector';
+
+/** Which referendum a forked chain hosts; a shared chain hosts both. */
+export type ChainRole = 'governance' | 'fellowship';
+
+/** The options that decide which capabilities a run needs. */
+export type CapabilityOptions = Pick<
+  TestOptions,
+  | 'voteConviction'
+  | 'callToNotePreimageForGovernanceReferendum'
+  | 'callToNotePreimageForFellowshipReferendum'
+>;
+
+/** A pallet or runtime API the tool may depend on. */
+export interface Capability {
+  /** Names it goes by; any one of them being present is enough */
+  names: string[];
+  kind: 'pallet' | 'api';
+  /** Chains it is probed on */
+  roles: ChainRole[];
+  /** What the tool uses it for */
+  usedFor: string;
+  /**
+   * What in this run cannot do without it (`every run`, a flag), or
+   * `undefined` when the feature is only skipped on chains without it.
+   */
+  neededBy?: (options: CapabilityOptions, role: ChainRole) => string | undefined;
+}
+
+const BOTH: ChainRole[] = ['governance', 'fellowship'];
+
+export const CAPABILITIES: Capability[] = [
+  {
+    names: ['Scheduler'],
+    kind: 'pallet',
+    roles: BOTH,
+    usedFor: 'enacting the proposal',
+    neededBy: () => 'every run',
+  },
+  {
+    names: ['Preimage'],
+    kind: 'pallet',
+    roles: BOTH,
+    usedFor: 'noting proposals by hash',
+    neededBy: (options, role) => {
+      const note =
+        role === 'governance'
+          ? options.callToNotePreimageForGovernanceReferendum
+          : options.callToNotePreimageForFellowshipReferendum;
+      return note ? `--call-to-note-preimage-for-${role}-referendum` : undefined;
+    },
+  },
+  {
+    names: ['ConvictionVoting'],
+    kind: 'pallet',
+    roles: ['governance'],
+    usedFor: 'approving by a conviction vote',
+    neededBy: (options) => (options.voteConviction ? '--vote-conviction' : undefined),
+  },
+  {
+    names: ['Whitelist'],
+    kind: 'pallet',
+    roles: ['governance'],
+    usedFor: 'dispatching calls the fellowship whitelisted',
+  },
+  {
+    names: ['Treasury'],
+    kind: 'pallet',
+    roles: ['governance'],
+    usedFor: 'treasury payouts and the pot report',
+  },
+  {
+    names: ['AhMigrator', 'RcMigrator'],
+    kind: 'pallet',
+    roles: ['governance'],
+    usedFor: '--unlock-call-filter',
+  },
+  {
+    names: ['CoreFellowship'],
+    kind: 'pallet',
+    roles: ['fellowship'],
+    usedFor: 'fellowship member params and promotions',
+  },
+  {
+    names: ['Core'],
+    kind: 'api',
+    roles: BOTH,
+    usedFor: 'executing blocks',
+    neededBy: () => 'every run',
+  },
+  {
+    names: ['BlockBuilder'],
+    kind: 'api',
+    roles: BOTH,
+    usedFor: 'building blocks',
+    neededBy: () => 'every run',
+  },
+  {
+    names: ['Metadata'],
+    kind: 'api',
+    roles: BOTH,
+    usedFor: 'decoding calls and events',
+    neededBy: () => 'every run',
+  },
+];
+
+/**
+ * `unknown`: the metadata (v14) does not list runtime APIs, so their
+ * presence cannot be checked before they are called.
+ */
+export type CapabilityStatus = 'present' | 'absent' | 'unknown';
+
+export interface ProbedCapability {
+  name: string;
+  kind: 'pallet' | 'api';
+  usedFor: string;
+  status: CapabilityStatus;
+  /** Set when the run cannot do without it */
+  neededBy?: string;
+}
+
+/** The subset of (unified) metadata needed to probe capabilities. */
+export interface CapabilityMetadata {
+  pallets: { name: string }[];
+  apis?: { name: string }[];
+}
+
+/** Probe a chain hosting `roles` for the capabilities those roles may use. */
+export function probeCapabilities(
+  metadata: CapabilityMetadata,
+  roles: ChainRole[],
+  options: CapabilityOptions,
+  capabilities: Capability[] = CAPABILITIES
+): ProbedCapability[] {
+  const pallets = new Set(metadata.pallets.map((pallet) => pallet.name));
+  const apis = new Set((metadata.apis ?? []).map((api) => api.name));
+  return capabilities
+    .filter((capability) => capability.roles.some((role) => roles.includes(role)))
+    .map((capability) => {
+      const names = capability.kind === 'pallet' ? pallets : apis;
+      const found = capability.names.find((name) => names.has(name));
+      const status: CapabilityStatus =
+        capability.kind === 'api' && apis.size === 0 ? 'unknown' : found ? 'present' : 'absent';
+      const neededBy = roles
+        .filter((role) => capability.roles.includes(role))
+        .map((role) => capability.neededBy?.(options, role))
+        .find((reason) => reason !== undefined);
+      return {
+        name: found ?? capability.names.join('/'),
+        kind: capability.kind,
+        usedFor: capability.usedFor,
+        status,
+        ...(neededBy ? { neededBy } : {}),
+      };
+    });
+}
+
+/** Why the run cannot go on: capabilities it needs that are absent. */
+export function missingCapabilities(label: string, probed: ProbedCapability[]): string[] {
+  return probed
+    .filter((capability) => capability.status === 'absent' && capability.neededBy)
+    .map(
+      (capability) =>
+        `${label} has no ${capability.name} ${capability.kind === 'api' ? 'runtime API' : 'pallet'}, needed by ${capability.neededBy}`
+    );
+}
+
+/** Capability/Status/Used for table; absent features the run can skip are marked so. */
+export function formatCapabilities(probed: ProbedCapability[]): string[] {
+  const rows = probed.map((capability) => [
+    capability.kind === 'api' ? `${capability.name} API` : capability.name,
+    capability.status,
+    capability.status === 'absent' && !capability.neededBy
+      ? `${capability.usedFor} (skipped)`
+      : capability.usedFor,
+  ]);
+  const header = ['Capability', 'Status', 'Used for'];
+  const widths = header.map((title, column) =>
+    Math.max(title.length, ...rows.map((row) => row[column].length))
+  );
+  return [header, ...rows].map((row) =>
+    row
+      .map((cell, column) => (column === row.length - 1 ? cell : cell.padEnd(widths[column])))
+      .join('  ')
+      .trimEnd()
+  );
+}
+
+/** A forked chain to probe, and the referenda it hosts. */
+export interface ProbedChain {
+  label: string;
+  roles: ChainRole[];
+  request: RpcRequest;
+}
+
+/** Names of the pallets in a chain's runtime, read from its metadata. */
+export async function runtimePallets(request: RpcRequest): Promise<string[]> {
+  const hex: string = await request('state_getMetadata', []);
+  const metadata = unifyMetadata(decAnyMetadata(hex)) as unknown as CapabilityMetadata;
+  return metadata.pallets.map((pallet) => pallet.name);
+}
+
+/**
+ * Print a "Runtime Capabilities" matrix for each forked chain before anything
+ * runs on it, and fail with `E-MISSING-CAPABILITY` if the run needs a pallet
+ * or runtime API a chain lacks, rather than partway through the run. Features
+ * the run can do without are skipped on chains without them. A chain whose
+ * metadata cannot be read is not probed.
+ */
+export async function checkRuntimeCapabilities(
+  logger: Logger,
+  chains: ProbedChain[],
+  options: CapabilityOptions
+): Promise<void> {
+  logger.section('Runtime Capabilities');
+  const missing: string[] = [];
+  for (const chain of chains) {
+    let probed: ProbedCapability[];
+    try {
+      const hex: string = await chain.request('state_getMetadata', []);
+      const metadata = unifyMetadata(decAnyMetadata(hex)) as unknown as CapabilityMetadata;
+      probed = probeCapabilities(metadata, chain.roles, options);
+    } catch (error) {
+      logger.warn(`Could not probe ${chain.label}: ${(error as Error).message}`);
+      continue;
+    }
+    logger.info(`${chain.label} (${chain.roles.join(' + ')}):`);
+    for (const line of formatCapabilities(probed)) {
+      logger.info(`  ${line}`);
+    }
+    missing.push(...missingCapabilities(chain.label, probed));
+  }
+  if (missing.length > 0) {
+    throw new CodedError(ErrorCode.MissingCapability, missing.join('; '));
+  }
+}
@@ -0,0 +1,31 @@
+import { CodedError, ErrorCode } from '../utils/error-codes';
+
+/**
+ * `--scenario`: a fault deliberately injected into the simulation, to check
+ * how a failure of that kind is detected and reported.
+ *
+ * - `bad-origin`: the proposal is dispatched from an origin no call accepts,
+ *   and fails with `BadOrigin` (`E-BAD-ORIGIN`)
+ */
+export type Scenario = 'bad-origin';
+
+const SCENARIOS: Scenario[] = ['bad-origin'];
+
+/**
+ * The origin `bad-origin` dispatches the proposal from, in agenda storage
+ * format. `None` is neither signed nor Root nor a custom origin, so every
+ * origin check rejects it.
+ */
+export const MISMATCHED_DISPATCH_ORIGIN = { system: 'None' };
+
+export function parseScenario(value?: string): Scenario | undefined {
+  if (value === undefined) return undefined;
+  const scenario = SCENARIOS.find((name) => name === value.trim());
+  if (!scenario) {
+    throw new CodedError(
+      ErrorCode.InvalidScenario,
+      `Invalid --scenario value: ${value} (expected one of: ${SCENARIOS.join(', ')})`
+    );
+  }
+  return scenario;
+}
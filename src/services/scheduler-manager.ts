@@ -1,4 +1,6 @@
+import type { RetryConfig } from '../types';
 import type { ScheduledCall, ScheduledEntry, SubstrateApi } from '../types/substrate-api';
+import { CodedError, ErrorCode } from '../utils/error-codes';
 import { toHexString } from '../utils/hex';
 import { stringify } from '../utils/json';
 import type { Logger } from '../utils/logger';
@@ -6,22 +8,51 @@ import { convertAgendaToStorageFormat } from '../utils/storage-format-converter'
 import { getReferendaPalletName } from './chain-registry';
 import type { ChopsticksManager } from './chopsticks-manager';
 
+/** `RetryConfig::total_retries` is a `u8`. */
+const MAX_RETRIES = 255;
+
+/** Parse `--enactment-retries`: a number of retries from 1 to 255 (unset: none). */
+export function parseEnactmentRetries(value?: string): number | undefined {
+  if (value === undefined) return undefined;
+  const retries = parseInt(value, 10);
+  if (!/^\d+$/.test(value.trim()) || retries < 1 || retries > MAX_RETRIES) {
+    throw new CodedError(
+      ErrorCode.InvalidEnactmentRetries,
+      `Invalid --enactment-retries value: ${value} (expected an integer from 1 to ${MAX_RETRIES})`
+    );
+  }
+  return retries;
+}
+
+/** A retry configuration as `Scheduler.Retries` stores it. */
+function retryConfigStorage(config: RetryConfig): Record<string, number> {
+  return {
+    total_retries: config.totalRetries,
+    remaining: config.remaining,
+    period: config.period,
+  };
+}
+
 export class SchedulerManager {
   private logger: Logger;
   private chopsticks: ChopsticksManager;
   private api: SubstrateApi;
   private isFellowship: boolean;
+  /** Referenda pallet instance, when not the default for `isFellowship` */
+  private pallet?: string;
 
   constructor(
     logger: Logger,
     chopsticks: ChopsticksManager,
     api: SubstrateApi,
-    isFellowship: boolean
+    isFellowship: boolean,
+    pallet?: string
   ) {
     this.logger = logger;
     this.chopsticks = chopsticks;
     this.api = api;
     this.isFellowship = isFellowship;
+    this.pallet = pallet;
   }
 
   /**
@@ -58,13 +89,61 @@ export class SchedulerManager {
   }
 
   /**
-   * Move scheduled call to next block.
+   * Inject an inline call into the scheduler agenda at the next block, to be
+   * dispatched from `origin` (storage format, e.g. `{ System: 'Root' }`).
+   * Returns the block it was scheduled at.
+   */
+  async scheduleCallAtNextBlock(callHex: string, origin: Record<string, unknown>): Promise<number> {
+    const { targetBlock } = await this.getSchedulingBlocks();
+    await this.chopsticks.setStorageBatch({
+      Scheduler: {
+        agenda: [[[targetBlock], [{ call: { Inline: callHex }, origin }]]],
+      },
+    });
+    return targetBlock;
+  }
+
+  /**
+   * Whether the task is still in its agenda slot. The scheduler removes a task
+   * once it is serviced; one left behind after its block was postponed because
+   * the block ran out of weight, and is retried in the following blocks.
+   */
+  async isTaskPending(block: number, taskIndex: number): Promise<boolean> {
+    const agenda = await this.api.query.Scheduler.Agenda.getValue(block);
+    return Boolean(agenda?.[taskIndex]);
+  }
+
+  /**
+   * The retry configuration of the task at `[block, taskIndex]`, if it has one.
+   * Runtimes whose scheduler predates retries have none.
+   */
+  async getRetryConfig(block: number, taskIndex: number): Promise<RetryConfig | undefined> {
+    const config = await this.api.query.Scheduler.Retries?.getValue([block, taskIndex]);
+    if (!config) return undefined;
+    return {
+      totalRetries: Number(config.total_retries),
+      remaining: Number(config.remaining),
+      period: Number(config.period),
+    };
+  }
+
+  /** Give the task at `[block, taskIndex]` a retry configuration. */
+  async setRetryConfig(block: number, taskIndex: number, config: RetryConfig): Promise<void> {
+    await this.chopsticks.setStorageBatch({
+      Scheduler: { Retries: [[[[block, taskIndex]], retryConfigStorage(config)]] },
+    });
+  }
+
+  /**
+   * Move scheduled call to next block, optionally replacing the origin it is
+   * dispatched from (storage format, e.g. `{ system: 'None' }`).
    * Returns the block number and task index where the call was scheduled.
    */
   async moveScheduledCallToNextBlock(
     referendumId: number,
     callType: 'nudge' | 'execute',
-    proposalHash?: string
+    proposalHash?: string,
+    origin?: Record<string, unknown>
   ): Promise<{ block: number; taskIndex: number; taskId: Uint8Array | undefined }> {
     const { targetBlock } = await this.getSchedulingBlocks();
 
@@ -72,7 +151,8 @@ export class SchedulerManager {
       await this.findMatchingScheduledCall(referendumId, callType, proposalHash);
 
     if (!match) {
-      throw new Error(
+      throw new CodedError(
+        ErrorCode.ScheduledCallNotFound,
         `Scheduled ${callType} call not found for referendum ${referendumId}. ` +
           `Searched ${searchedBlocks} agenda blocks (${searchedItems} total items) ` +
           `at blocks: [${blockNumbers.join(', ')}]. ` +
@@ -85,10 +165,51 @@ export class SchedulerManager {
     this.logger.debug(
       `Found ${callType} call at block ${keyArgs[0]} index ${matchIndex}, moving to block ${targetBlock}`
     );
+    this.logger.info(`\u{1F4CB} Scheduling ${callType} call:`);
+    await this.moveAgenda(keyArgs[0], agendaItems, matchIndex, targetBlock, origin);
+
+    return { block: targetBlock, taskIndex: matchIndex, taskId: scheduledEntry.maybeId };
+  }
+
+  /**
+   * Move the agenda holding the task at `[block, taskIndex]` to the next
+   * block, e.g. a task a proposal scheduled for much later
+   * (`--follow-scheduled`). Returns where the task now is.
+   */
+  async moveTaskToNextBlock(
+    block: number,
+    taskIndex: number
+  ): Promise<{ block: number; taskIndex: number; taskId: Uint8Array | undefined }> {
+    const { targetBlock } = await this.getSchedulingBlocks();
+    const agendaItems = await this.api.query.Scheduler.Agenda.getValue(block);
+    const scheduledEntry = agendaItems?.[taskIndex];
+    if (!agendaItems || !scheduledEntry?.call) {
+      throw new CodedError(
+        ErrorCode.ScheduledCallNotFound,
+        `Scheduled task [${block}, ${taskIndex}] not found in the scheduler agenda`
+      );
+    }
+
+    this.logger.info(`\u{1F4CB} Scheduling task [${block}, ${taskIndex}]:`);
+    await this.moveAgenda(block, agendaItems, taskIndex, targetBlock);
+    return { block: targetBlock, taskIndex, taskId: scheduledEntry.maybeId };
+  }
 
+  /**
+   * Write the agenda of `fromBlock` to `targetBlock`, optionally replacing the
+   * origin of its task at `taskIndex`, and point the task's lookup there.
+   * Retry configurations are keyed by agenda slot, so they move with it.
+   */
+  private async moveAgenda(
+    fromBlock: unknown,
+    agendaItems: ScheduledEntry[],
+    taskIndex: number,
+    targetBlock: number,
+    origin?: Record<string, unknown>
+  ): Promise<void> {
+    const scheduledEntry = agendaItems[taskIndex];
     const callInfo = this.getCallInfo(scheduledEntry.call);
-    this.logger.info(`\u{1F4CB} Scheduling ${callType} call:`);
-    this.logger.info(`   From block: ${keyArgs[0]}`);
+    this.logger.info(`   From block: ${fromBlock}`);
     this.logger.info(`   To block: ${targetBlock}`);
     this.logger.info(`   Call type: ${callInfo.type}`);
     if (callInfo.hex) {
@@ -98,13 +219,30 @@ export class SchedulerManager {
     }
 
     const convertedAgenda = convertAgendaToStorageFormat(agendaItems);
+    if (origin) {
+      this.logger.info(`   Origin: ${stringify(origin)} (overridden)`);
+      (convertedAgenda[taskIndex] as Record<string, unknown>).origin = origin;
+    }
+
+    const retries = await Promise.all(
+      agendaItems.map((_, index) => this.getRetryConfig(Number(fromBlock), index))
+    );
+    const movedRetries = retries.flatMap((config, index) =>
+      config
+        ? [
+            [[[fromBlock, index]], null],
+            [[[targetBlock, index]], retryConfigStorage(config)],
+          ]
+        : []
+    );
 
     await this.chopsticks.setStorageBatch({
       Scheduler: {
         Agenda: [
-          [[keyArgs[0]], null],
+          [[fromBlock], null],
           [[targetBlock], convertedAgenda],
         ],
+        ...(movedRetries.length > 0 && { Retries: movedRetries }),
       },
     });
 
@@ -116,7 +254,7 @@ export class SchedulerManager {
         if (lookup) {
           await this.chopsticks.setStorageBatch({
             Scheduler: {
-              Lookup: [[[lookupId], [targetBlock, 0]]],
+              Lookup: [[[lookupId], [targetBlock, taskIndex]]],
             },
           });
         }
@@ -124,8 +262,6 @@ export class SchedulerManager {
         this.logger.debug(`No lookup found: ${err}`);
       }
     }
-
-    return { block: targetBlock, taskIndex: matchIndex, taskId: scheduledEntry.maybeId };
   }
 
   private async findMatchingScheduledCall(
@@ -249,7 +385,7 @@ export class SchedulerManager {
     referendumId: number
   ): Promise<boolean> {
     try {
-      const palletName = getReferendaPalletName(this.isFellowship);
+      const palletName = getReferendaPalletName(this.isFellowship, this.pallet);
 
       // Strategy 1: Decode inline bytes via the runtime API (most reliable)
       if (callData?.type === 'Inline' && callData?.value) {
@@ -0,0 +1,26 @@
+import type { SubstrateApi } from '../types/substrate-api';
+
+/**
+ * Known Chopsticks limitation: a fork whose starting block is a session (BABE
+ * epoch) boundary may not serve noted preimages to the scheduler, so the
+ * enactment fails with `Scheduler.CallUnavailable` although the preimage is
+ * on chain.
+ */
+export const SESSION_BOUNDARY_LIMITATION =
+  'Chopsticks may not serve noted preimages to a fork that starts on a session boundary, so the proposal can fail with CallUnavailable although its preimage is noted. Fork one block earlier (url,<block - 1>) to avoid it';
+
+/** Whether `block` starts a new epoch (never true for genesis or an unknown length). */
+export function isSessionBoundary(block: number, epochLength: number | undefined): boolean {
+  return epochLength !== undefined && epochLength > 0 && block > 0 && block % epochLength === 0;
+}
+
+/** Epoch length in blocks, from `Babe.EpochDuration`; undefined for chains without BABE. */
+export async function readEpochLength(api: SubstrateApi): Promise<number | undefined> {
+  try {
+    const duration = await api.constants.Babe?.EpochDuration();
+    return duration === undefined ? undefined : Number(duration);
+  } catch {
+    // Parachains have no Babe pallet
+    return undefined;
+  }
+}
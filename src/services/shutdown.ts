@@ -0,0 +1,148 @@
+import * as http from 'http';
+import type { AddressInfo } from 'net';
+import {
+  CodedError,
+  ErrorCode,
+  formatCodedError,
+  INTERRUPTED_EXIT_CODE,
+} from '../utils/error-codes';
+import type { Logger } from '../utils/logger';
+
+/** JSON-RPC method of the control endpoint that stops a kept-open run. */
+export const STOP_METHOD = 'dev_stop';
+
+/** How long teardowns may take before a stop exits anyway. */
+export const SHUTDOWN_TIMEOUT_MS = 15_000;
+
+type Teardown = () => Promise<unknown>;
+
+/**
+ * Tears down every running Chopsticks fork, and the run's work dir, when the
+ * run is interrupted (SIGINT, SIGTERM or a `dev_stop` request), then exits
+ * with {@link INTERRUPTED_EXIT_CODE}. A second signal exits at once.
+ */
+export class Shutdown {
+  private logger?: Logger;
+  private teardowns = new Map<number, { label: string; teardown: Teardown }>();
+  private nextId = 0;
+  private isStopping = false;
+  private control?: http.Server;
+  private report?: (error: CodedError) => void;
+  private exit: (code: number) => void;
+
+  constructor(exit: (code: number) => void = (code) => process.exit(code)) {
+    this.exit = exit;
+  }
+
+  /** Whether a stop is under way; the run must then leave exiting to it. */
+  get stopping(): boolean {
+    return this.isStopping;
+  }
+
+  /** Run `teardown` on stop; call the returned function once it ran normally. */
+  register(label: string, teardown: Teardown): () => void {
+    const id = this.nextId++;
+    this.teardowns.set(id, { label, teardown });
+    return () => {
+      this.teardowns.delete(id);
+    };
+  }
+
+  /**
+   * Stop on SIGINT and SIGTERM; returns a function that removes the handlers.
+   * `report` is given the `E-INTERRUPTED` error of a stop right before it exits.
+   */
+  install(logger: Logger, report?: (error: CodedError) => void): () => void {
+    this.logger = logger;
+    this.report = report;
+    const onSignal = (signal: NodeJS.Signals) => {
+      void this.stop(`Interrupted by ${signal}`);
+    };
+    process.on('SIGINT', onSignal);
+    process.on('SIGTERM', onSignal);
+    return () => {
+      process.off('SIGINT', onSignal);
+      process.off('SIGTERM', onSignal);
+      this.control?.close();
+      this.control = undefined;
+      this.report = undefined;
+    };
+  }
+
+  /**
+   * Serve a JSON-RPC endpoint on localhost whose `dev_stop` method stops the
+   * run like Ctrl+C. Returns its URL.
+   */
+  async serveControl(): Promise<string> {
+    if (!this.control) {
+      const server = http.createServer((request, response) => {
+        let body = '';
+        request.on('data', (chunk) => {
+          body += chunk;
+        });
+        request.on('end', () => {
+          const { id, method } = parseRpcRequest(body);
+          const reply =
+            method === STOP_METHOD
+              ? { jsonrpc: '2.0', id, result: true }
+              : { jsonrpc: '2.0', id, error: { code: -32601, message: 'Method not found' } };
+          response.writeHead(200, { 'Content-Type': 'application/json' });
+          response.end(JSON.stringify(reply), () => {
+            if (method === STOP_METHOD) void this.stop(`Stopped by ${STOP_METHOD}`);
+          });
+        });
+      });
+      await new Promise<void>((resolve) => server.listen(0, '127.0.0.1', resolve));
+      server.unref();
+      this.control = server;
+    }
+    const { port } = this.control.address() as AddressInfo;
+    return `http://127.0.0.1:${port}`;
+  }
+
+  /** Tear down every registered fork and exit with {@link INTERRUPTED_EXIT_CODE}. */
+  async stop(reason: string): Promise<void> {
+    if (this.isStopping) {
+      this.exit(INTERRUPTED_EXIT_CODE);
+      return;
+    }
+    this.isStopping = true;
+    const labels = Array.from(this.teardowns.values()).map(({ label }) => label);
+    this.logger?.warn(`${reason}, tearing down ${labels.join(', ') || 'nothing'}...`);
+
+    const pending = Array.from(this.teardowns.values()).map(({ label, teardown }) =>
+      teardown().catch((error) =>
+        this.logger?.warn(`Failed to tear down ${label}: ${(error as Error).message}`)
+      )
+    );
+    this.teardowns.clear();
+    let timer: NodeJS.Timeout | undefined;
+    const timedOut = new Promise<void>((resolve) => {
+      timer = setTimeout(() => {
+        this.logger?.warn(`Teardown did not finish within ${SHUTDOWN_TIMEOUT_MS / 1000}s`);
+        resolve();
+      }, SHUTDOWN_TIMEOUT_MS);
+      timer.unref();
+    });
+    await Promise.race([Promise.all(pending), timedOut]);
+    clearTimeout(timer);
+    this.control?.close();
+
+    const error = new CodedError(ErrorCode.Interrupted, reason);
+    console.error(formatCodedError(error));
+    this.report?.(error);
+    this.exit(INTERRUPTED_EXIT_CODE);
+  }
+}
+
+function parseRpcRequest(body: string): { id: unknown; method?: string } {
+  try {
+    const request = JSON.parse(body);
+    return { id: request?.id ?? null, method: request?.method };
+  } catch {
+    return { id: null };
+  }
+}
+
+/** The process-wide shutdown every Chopsticks fork registers with. */
+export const shutdown = new Shutdown();
@@ -1,10 +1,28 @@
-import type { SimulationResult, TestOptions } from '../types';
+import type { CancelPhase, ReferendumInfo, SimulationResult, TestOptions } from '../types';
 import type { SubstrateApi } from '../types/substrate-api';
+import { CodedError, ErrorCode, errorCodeOf, formatCodedError } from '../utils/error-codes';
 import type { Logger } from '../utils/logger';
+import type { BalanceCheck } from './balance-tracker';
 import type { ChopsticksManager } from './chopsticks-manager';
+import type { Conviction } from './conviction-approval';
+import type { DelegatedSubmission } from './delegated-submission';
+import { findNotDispatchedTask } from './execution-result-checker';
+import type { HookRunner } from './hook-runner';
+import { displayProposalFootprint } from './proposal-footprint';
+import type { ProposalLinter } from './proposal-linter';
+import { RankedApprover } from './ranked-approval';
 import { ReferendaFetcher } from './referenda-fetcher';
+import type { ReferendaInstance } from './referenda-pallets';
 import { ReferendumCreator } from './referendum-creator';
-import { ReferendumSimulator } from './referendum-simulator';
+import type { ReferendumMetadata } from './referendum-metadata';
+import {
+  type DispatchOptions,
+  type PreExecutionOptions,
+  ReferendumSimulator,
+} from './referendum-simulator';
+import type { RunHistory } from './run-history';
+import type { Scenario } from './scenarios';
+import { type XcmDestination, XcmFeeEstimator } from './xcm-fee-estimator';
 
 /**
  * Runs referendum simulations given already-initialized chain APIs.
@@ -15,17 +33,19 @@ import { ReferendumSimulator } from './referendum-simulator';
  * │  fetchAndSimulate()   — single referendum lifecycle    │
  * │    ├─ createReferendumIfNeeded()                       │
  * │    ├─ ReferendaFetcher.fetchReferendum()               │
- * │    ├─ ReferendumSimulator.simulate()                   │
+ * │    ├─ ProposalLinter.lint() + simulate()               │
  * │    └─ throwIfFailed()                                  │
  * │                                                        │
  * │  simulateSequentialReferenda()                         │
  * │    ├─ [1/2] fellowship via fetchAndSimulate()          │
- * │    └─ [2/2] governance via fetchAndSimulate()          │
+ * │    ├─ [2/2] governance via fetchAndSimulate()          │
+ * │    └─ settleOutcomes()                                 │
  * │                                                        │
  * │  simulateMultiChainReferenda()                         │
  * │    ├─ [1/2] fellowship on fellowship chain             │
  * │    ├─ XCM propagation blocks                           │
- * │    └─ [2/2] governance on governance chain             │
+ * │    ├─ [2/2] governance on governance chain             │
+ * │    └─ settleOutcomes()                                 │
  * └───────────────────────────────────────────────────────┘
  */
 
@@ -35,13 +55,122 @@ export interface CreateReferendumParams {
   callHex: string | undefined;
   preimageHex: string | undefined;
   isFellowship: boolean;
+  /** `--metadata-for-*-referendum`, set on the created referendum */
+  metadata?: ReferendumMetadata;
+  /** Referenda instance the call submits to, if not the default one of `isFellowship` */
+  pallet?: string;
+}
+
+/**
+ * How a referendum that passed every check ended, naming the block that
+ * dispatched it so a follow-up run can attach to the state after it.
+ */
+export function describeOutcome(result: SimulationResult): string {
+  if (result.cancellation) return 'cancelled and its deposits refunded';
+  const retries = result.retries?.attempts.length;
+  return retries
+    ? `executed successfully at block ${result.blockExecuted} on retry ${retries}!`
+    : `executed successfully at block ${result.blockExecuted}!`;
+}
+
+/**
+ * Codes that say how a referendum itself fared. Only these let the other
+ * referendum of a fellowship + governance run go ahead: any other error
+ * (Chopsticks or RPC failures, an interruption) leaves the forks unusable.
+ */
+export const REFERENDUM_OUTCOME_CODES: ReadonlySet<ErrorCode> = new Set<ErrorCode>([
+  ErrorCode.CallDecodeFailed,
+  ErrorCode.ReferendumNotCreated,
+  ErrorCode.ReferendumNotFound,
+  ErrorCode.ReferendumNotOngoing,
+  ErrorCode.ReferendumNotApproved,
+  ErrorCode.ScheduledCallNotFound,
+  ErrorCode.PreimageMissing,
+  ErrorCode.PermanentlyOverweight,
+  ErrorCode.EnactmentPostponed,
+  ErrorCode.ExecutionFailed,
+  ErrorCode.BadOrigin,
+  ErrorCode.HookFailed,
+  ErrorCode.LintDenied,
+  ErrorCode.TreasuryPayoutFailed,
+  ErrorCode.BalanceCheckFailed,
+  ErrorCode.CancellationFailed,
+  ErrorCode.ConvictionLockFailed,
+]);
+
+/** How one referendum of a fellowship + governance run ended. */
+export interface ReferendumOutcome {
+  role: 'fellowship' | 'governance';
+  referendumId: number;
+  /** Why it failed; unset when it passed every check */
+  error?: unknown;
+}
+
+/** `governance #5: failed, E-EXECUTION-FAILED: Main referendum #5 execution failed` */
+export function formatOutcome(outcome: ReferendumOutcome): string {
+  const status =
+    outcome.error === undefined ? 'passed' : `failed, ${formatCodedError(outcome.error)}`;
+  return `${outcome.role} #${outcome.referendumId}: ${status}`;
+}
+
+/**
+ * List each referendum's result in a "Referendum Results" section, then fail
+ * if any failed: with {@link ErrorCode.PartialSuccess} when another one
+ * passed, with the first failure's own error when none did.
+ */
+export function settleOutcomes(logger: Logger, outcomes: ReferendumOutcome[]): void {
+  logger.section('Referendum Results');
+  for (const outcome of outcomes) {
+    if (outcome.error === undefined) {
+      logger.success(formatOutcome(outcome));
+    } else {
+      logger.error(formatOutcome(outcome));
+    }
+  }
+
+  const failed = outcomes.filter((o) => o.error !== undefined);
+  if (failed.length === 0) return;
+  if (failed.length === outcomes.length) throw failed[0].error;
+  const name = (o: ReferendumOutcome) => `${o.role} #${o.referendumId}`;
+  const passed = outcomes.filter((o) => o.error === undefined);
+  throw new CodedError(
+    ErrorCode.PartialSuccess,
+    `${passed.map(name).join(', ')} passed, but ${failed.map((o) => `${name(o)} failed with ${formatCodedError(o.error)}`).join('; ')}`,
+    { cause: failed[0].error }
+  );
+}
+
+/** What every referendum a runner simulates gets, besides its own parameters. */
+export interface SimulationRunnerOptions {
+  hooks?: HookRunner;
+  linter?: ProposalLinter;
+  /** `--track-balance` checks, applied to the last (governance) referendum of a run */
+  balanceChecks?: BalanceCheck[];
+  /** `--submit-via-proxy` / `--submit-via-multisig`: how governance referenda are created */
+  governanceSubmission?: DelegatedSubmission;
+  /** Stores each result and, with `--diff-with-last`, diffs it with the previous run */
+  history?: RunHistory;
+  /** `--blocks-after-dispatch` and `--follow-scheduled`, applied to governance referenda */
+  dispatchOptions?: DispatchOptions;
 }
 
 export class SimulationRunner {
   private logger: Logger;
+  private hooks?: HookRunner;
+  private linter?: ProposalLinter;
+  private balanceChecks: BalanceCheck[];
+  private governanceSubmission?: DelegatedSubmission;
+  private history?: RunHistory;
+  private dispatchOptions: DispatchOptions;
 
-  constructor(logger: Logger) {
+  constructor(logger: Logger, options: SimulationRunnerOptions = {}) {
     this.logger = logger;
+    this.hooks = options.hooks;
+    this.linter = options.linter;
+    this.balanceChecks = options.balanceChecks ?? [];
+    this.governanceSubmission = options.governanceSubmission;
+    this.history = options.history;
+    this.dispatchOptions = options.dispatchOptions ?? {};
   }
 
   /**
@@ -54,18 +183,37 @@ export class SimulationRunner {
     isFellowship: boolean;
     createCallHex?: string;
     createPreimageHex?: string;
+    createMetadata?: ReferendumMetadata;
     preCall?: string;
     preOrigin?: string;
+    scenario?: Scenario;
+    cancelAt?: CancelPhase;
+    conviction?: Conviction;
     label?: string;
+    /** `--referendum <pallet>:<id>`: the instance to simulate on, overriding `isFellowship` */
+    referenda?: ReferendaInstance;
+    /** Forked chains the proposal may send XCM to, for its fee estimates */
+    xcmDestinations?: XcmDestination[];
   }): Promise<SimulationResult> {
-    const label = params.label ?? (params.isFellowship ? 'Fellowship' : 'Governance');
+    const label =
+      params.label ??
+      params.referenda?.name ??
+      (params.isFellowship ? 'Fellowship' : 'Governance');
+    const ranked = params.referenda?.ranked ?? params.isFellowship;
+    const pallet = params.referenda?.name;
 
+    // Only members of a ranked collective may submit to its referenda.
+    if (params.createCallHex && pallet && ranked) {
+      await new RankedApprover(this.logger, params.chopsticks, params.api, pallet).injectMembers();
+    }
     const createdId = await this.createReferendumIfNeeded({
       api: params.api,
       chopsticks: params.chopsticks,
       callHex: params.createCallHex,
       preimageHex: params.createPreimageHex,
       isFellowship: params.isFellowship,
+      metadata: params.createMetadata,
+      pallet,
     });
     const actualReferendumId = createdId ?? params.referendumId;
 
@@ -77,26 +225,44 @@ export class SimulationRunner {
     const referendum = await fetcher.fetchReferendum(
       params.api,
       actualReferendumId,
-      params.isFellowship
+      ranked,
+      pallet
     );
 
     if (!referendum) {
-      throw new Error(`Failed to fetch ${label.toLowerCase()} referendum ${actualReferendumId}`);
+      throw new CodedError(
+        ErrorCode.ReferendumNotFound,
+        `Failed to fetch ${label.toLowerCase()} referendum ${actualReferendumId}`
+      );
     }
 
-    const simulator = new ReferendumSimulator(
-      this.logger,
-      params.chopsticks,
+    const simulator = new ReferendumSimulator(this.logger, params.chopsticks, params.api, ranked, {
+      hooks: this.hooks,
+      balanceChecks: this.balanceChecks,
+      pallet,
+      dispatchOptions: ranked ? {} : this.dispatchOptions,
+    });
+    const result = await this.lintAndSimulate(
+      simulator,
       params.api,
-      params.isFellowship
+      referendum,
+      ranked,
+      {
+        preCall: params.preCall,
+        preOrigin: params.preOrigin,
+        scenario: params.scenario,
+        cancelAt: params.cancelAt,
+        conviction: params.conviction,
+      },
+      pallet,
+      createdId !== undefined,
+      params.xcmDestinations
     );
-    const result = await simulator.simulate(referendum, {
-      preCall: params.preCall,
-      preOrigin: params.preOrigin,
-    });
 
     this.throwIfFailed(result, `${label} referendum #${actualReferendumId}`);
-    this.logger.success(`\n✓ ${label} referendum #${actualReferendumId} executed successfully!`);
+    this.logger.success(
+      `\n✓ ${label} referendum #${actualReferendumId} ${describeOutcome(result)}`
+    );
 
     return result;
   }
@@ -113,28 +279,55 @@ export class SimulationRunner {
     const fetcher = new ReferendaFetcher(this.logger);
 
     this.logger.section(`[1/2] Fellowship Referendum #${fellowshipReferendumId}`);
-    const fellowshipRef = await fetcher.fetchReferendum(api, fellowshipReferendumId, true);
-    if (!fellowshipRef) {
-      throw new Error(`Failed to fetch fellowship referendum ${fellowshipReferendumId}`);
-    }
-    const fellowshipSimulator = new ReferendumSimulator(this.logger, chopsticks, api, true);
-    const fellowshipResult = await fellowshipSimulator.simulate(fellowshipRef);
-    this.throwIfFailed(fellowshipResult, `Fellowship referendum #${fellowshipReferendumId}`);
+    const fellowshipRun = await this.attempt('fellowship', fellowshipReferendumId, async () => {
+      const fellowshipRef = await fetcher.fetchReferendum(api, fellowshipReferendumId, true);
+      if (!fellowshipRef) {
+        throw new CodedError(
+          ErrorCode.ReferendumNotFound,
+          `Failed to fetch fellowship referendum ${fellowshipReferendumId}`
+        );
+      }
+      const fellowshipSimulator = new ReferendumSimulator(this.logger, chopsticks, api, true, {
+        hooks: this.hooks,
+      });
+      const fellowshipResult = await this.lintAndSimulate(
+        fellowshipSimulator,
+        api,
+        fellowshipRef,
+        true
+      );
+      this.throwIfFailed(fellowshipResult, `Fellowship referendum #${fellowshipReferendumId}`);
+      this.logger.success(
+        `✓ Fellowship referendum #${fellowshipReferendumId} ${describeOutcome(fellowshipResult)}`
+      );
+    });
 
     this.logger.section(`[2/2] Main Governance Referendum #${mainReferendumId}`);
-    const mainRef = await fetcher.fetchReferendum(api, mainReferendumId);
-    if (!mainRef) {
-      throw new Error(`Failed to fetch main referendum ${mainReferendumId}`);
-    }
-    const mainSimulator = new ReferendumSimulator(this.logger, chopsticks, api, false);
-    const mainResult = await mainSimulator.simulate(mainRef);
-    this.throwIfFailed(mainResult, `Main referendum #${mainReferendumId}`);
+    const governanceRun = await this.attempt('governance', mainReferendumId, async () => {
+      const mainRef = await fetcher.fetchReferendum(api, mainReferendumId);
+      if (!mainRef) {
+        throw new CodedError(
+          ErrorCode.ReferendumNotFound,
+          `Failed to fetch main referendum ${mainReferendumId}`
+        );
+      }
+      const mainSimulator = new ReferendumSimulator(this.logger, chopsticks, api, false, {
+        hooks: this.hooks,
+        balanceChecks: this.balanceChecks,
+        dispatchOptions: this.dispatchOptions,
+      });
+      const mainResult = await this.lintAndSimulate(mainSimulator, api, mainRef, false);
+      this.throwIfFailed(mainResult, `Main referendum #${mainReferendumId}`);
+      this.logger.success(`✓ Main referendum #${mainReferendumId} ${describeOutcome(mainResult)}`);
+    });
 
+    settleOutcomes(this.logger, [fellowshipRun, governanceRun]);
     this.logger.success('\n✓ Both referenda executed successfully!');
   }
 
   /**
    * Run fellowship then governance on separate chain instances with XCM propagation.
+   * Each proposal's XCM is estimated on the other chain and on `additionalChains`.
    */
   async simulateMultiChainReferenda(chains: {
     fellowship: {
@@ -149,29 +342,49 @@ export class SimulationRunner {
       referendumId: number;
       label: string;
     };
+    /** Other forked chains the proposals may send XCM to */
+    additionalChains?: XcmDestination[];
   }): Promise<void> {
-    const { fellowship, governance } = chains;
+    const { fellowship, governance, additionalChains = [] } = chains;
     const fetcher = new ReferendaFetcher(this.logger);
 
     this.logger.section(
       `[1/2] Fellowship Referendum #${fellowship.referendumId} (${fellowship.label})`
     );
-    const fellowshipRef = await fetcher.fetchReferendum(
-      fellowship.api,
-      fellowship.referendumId,
-      true
-    );
-    if (!fellowshipRef) {
-      throw new Error(`Failed to fetch fellowship referendum ${fellowship.referendumId}`);
-    }
-    const fellowshipSimulator = new ReferendumSimulator(
-      this.logger,
-      fellowship.chopsticks,
-      fellowship.api,
-      true
-    );
-    const fellowshipResult = await fellowshipSimulator.simulate(fellowshipRef);
-    this.throwIfFailed(fellowshipResult, `Fellowship referendum #${fellowship.referendumId}`);
+    const fellowshipRun = await this.attempt('fellowship', fellowship.referendumId, async () => {
+      const fellowshipRef = await fetcher.fetchReferendum(
+        fellowship.api,
+        fellowship.referendumId,
+        true
+      );
+      if (!fellowshipRef) {
+        throw new CodedError(
+          ErrorCode.ReferendumNotFound,
+          `Failed to fetch fellowship referendum ${fellowship.referendumId}`
+        );
+      }
+      const fellowshipSimulator = new ReferendumSimulator(
+        this.logger,
+        fellowship.chopsticks,
+        fellowship.api,
+        true,
+        { hooks: this.hooks }
+      );
+      const fellowshipResult = await this.lintAndSimulate(
+        fellowshipSimulator,
+        fellowship.api,
+        fellowshipRef,
+        true,
+        undefined,
+        undefined,
+        false,
+        [{ label: governance.label, api: governance.api }, ...additionalChains]
+      );
+      this.throwIfFailed(fellowshipResult, `Fellowship referendum #${fellowship.referendumId}`);
+      this.logger.success(
+        `✓ Fellowship referendum #${fellowship.referendumId} ${describeOutcome(fellowshipResult)}`
+      );
+    });
 
     this.logger.startSpinner('Waiting for XCM message propagation...');
     await fellowship.chopsticks.newBlock();
@@ -181,21 +394,96 @@ export class SimulationRunner {
     this.logger.section(
       `[2/2] Main Governance Referendum #${governance.referendumId} (${governance.label})`
     );
-    const mainRef = await fetcher.fetchReferendum(governance.api, governance.referendumId);
-    if (!mainRef) {
-      throw new Error(`Failed to fetch main referendum ${governance.referendumId}`);
-    }
-    const governanceSimulator = new ReferendumSimulator(
-      this.logger,
-      governance.chopsticks,
-      governance.api
-    );
-    const mainResult = await governanceSimulator.simulate(mainRef);
-    this.throwIfFailed(mainResult, `Main referendum #${governance.referendumId}`);
+    const governanceRun = await this.attempt('governance', governance.referendumId, async () => {
+      const mainRef = await fetcher.fetchReferendum(governance.api, governance.referendumId);
+      if (!mainRef) {
+        throw new CodedError(
+          ErrorCode.ReferendumNotFound,
+          `Failed to fetch main referendum ${governance.referendumId}`
+        );
+      }
+      const governanceSimulator = new ReferendumSimulator(
+        this.logger,
+        governance.chopsticks,
+        governance.api,
+        false,
+        {
+          hooks: this.hooks,
+          balanceChecks: this.balanceChecks,
+          dispatchOptions: this.dispatchOptions,
+        }
+      );
+      const mainResult = await this.lintAndSimulate(
+        governanceSimulator,
+        governance.api,
+        mainRef,
+        false,
+        undefined,
+        undefined,
+        false,
+        [{ label: fellowship.label, api: fellowship.api }, ...additionalChains]
+      );
+      this.throwIfFailed(mainResult, `Main referendum #${governance.referendumId}`);
+      this.logger.success(
+        `✓ Main referendum #${governance.referendumId} ${describeOutcome(mainResult)}`
+      );
+    });
 
+    settleOutcomes(this.logger, [fellowshipRun, governanceRun]);
     this.logger.success('\n✓ Both referenda executed successfully!');
   }
 
+  /**
+   * Run one referendum of a fellowship + governance run. A failure of the
+   * referendum itself (see {@link REFERENDUM_OUTCOME_CODES}) is logged and
+   * returned instead of thrown, so the other referendum still runs; any other
+   * error ends the run.
+   */
+  private async attempt(
+    role: ReferendumOutcome['role'],
+    referendumId: number,
+    simulate: () => Promise<void>
+  ): Promise<ReferendumOutcome> {
+    try {
+      await simulate();
+      return { role, referendumId };
+    } catch (error) {
+      if (!REFERENDUM_OUTCOME_CODES.has(errorCodeOf(error))) throw error;
+      this.logger.error(formatCodedError(error), error as Error);
+      return { role, referendumId, error };
+    }
+  }
+
+  /**
+   * Lint the proposal while it is still undispatched, then simulate it. The
+   * result goes to the run history before any failure is thrown. XCM the
+   * proposal sends to one of `xcmDestinations` is estimated there by the lints.
+   */
+  private async lintAndSimulate(
+    simulator: ReferendumSimulator,
+    api: SubstrateApi,
+    referendum: ReferendumInfo,
+    isFellowship: boolean,
+    preExecutionOptions?: PreExecutionOptions,
+    pallet?: string,
+    created = false,
+    xcmDestinations: XcmDestination[] = []
+  ): Promise<SimulationResult> {
+    const xcm =
+      xcmDestinations.length > 0
+        ? new XcmFeeEstimator(this.logger, api, xcmDestinations)
+        : undefined;
+    const lints = this.linter ? await this.linter.lint(api, referendum, xcm) : undefined;
+    const simulated = await simulator.simulate(referendum, preExecutionOptions);
+    const result = {
+      ...simulated,
+      ...(lints && { lints }),
+      ...(created && { created }),
+    };
+    await this.history?.record(api, isFellowship, result, pallet);
+    return result;
+  }
+
   async createReferendumIfNeeded(params: CreateReferendumParams): Promise<number | undefined> {
     if (!params.callHex) return undefined;
 
@@ -206,20 +494,96 @@ export class SimulationRunner {
       params.api,
       params.callHex,
       params.preimageHex,
-      params.isFellowship
+      params.isFellowship,
+      params.isFellowship ? undefined : this.governanceSubmission,
+      params.metadata,
+      params.pallet
     );
+    if (result.footprint) {
+      displayProposalFootprint(this.logger, label, result.footprint);
+    }
     this.logger.success(`${label} referendum #${result.referendumId} created successfully`);
     return result.referendumId;
   }
 
   throwIfFailed(result: SimulationResult, label: string): void {
+    const unrefunded = (result.cancellation?.deposits ?? []).filter((d) => !d.refunded);
+    if (unrefunded.length > 0) {
+      throw new CodedError(
+        ErrorCode.CancellationFailed,
+        `${label} was cancelled, but its deposits were not refunded: ${unrefunded.map((d) => `${d.kind} deposit of ${d.amount} to ${d.who} (${d.error})`).join(', ')}`
+      );
+    }
+
     if (!result.executionSucceeded) {
       if (result.errors) {
         for (const errorMessage of result.errors) {
           this.logger.error(`  ${errorMessage}`);
         }
       }
-      throw new Error(`${label} execution failed`);
+      // A missing preimage, an oversized call or a postponed task never reaches a dispatch.
+      const notDispatched = result.notDispatched ?? findNotDispatchedTask(result.events)?.reason;
+      if (notDispatched === 'call-unavailable') {
+        throw new CodedError(
+          ErrorCode.PreimageMissing,
+          `${label} execution failed: the scheduler could not load the proposal because its preimage is missing (CallUnavailable)`
+        );
+      }
+      if (notDispatched === 'permanently-overweight') {
+        throw new CodedError(
+          ErrorCode.PermanentlyOverweight,
+          `${label} execution failed: the proposal's weight is too high for the scheduler to ever dispatch it (PermanentlyOverweight)`
+        );
+      }
+      if (notDispatched === 'postponed') {
+        throw new CodedError(
+          ErrorCode.EnactmentPostponed,
+          `${label} execution failed: the scheduler kept postponing the proposal because blocks ran out of weight before reaching it`
+        );
+      }
+      if (result.errors?.some((error) => /\bBadOrigin\b/.test(error))) {
+        throw new CodedError(
+          ErrorCode.BadOrigin,
+          `${label} execution failed: the proposal was dispatched from an origin its call does not accept (BadOrigin)`
+        );
+      }
+      throw new CodedError(ErrorCode.ExecutionFailed, `${label} execution failed`);
+    }
+
+    const lints = result.lints ?? [];
+    if (lints.length > 0) {
+      this.logger.warn(`${label}: ${lints.length} lint finding(s)`);
+    }
+
+    const failedSpends = (result.treasurySpends ?? []).filter((s) => s.status === 'failed');
+    if (failedSpends.length > 0) {
+      throw new CodedError(
+        ErrorCode.TreasuryPayoutFailed,
+        `${label} executed, but treasury payout failed: ${failedSpends.map((s) => `spend #${s.index} (${s.error})`).join(', ')}`
+      );
+    }
+    const mismatched = (result.balanceChanges ?? []).filter(
+      (change) => change.expectedDelta !== undefined && change.delta !== change.expectedDelta
+    );
+    if (mismatched.length > 0) {
+      throw new CodedError(
+        ErrorCode.BalanceCheckFailed,
+        `${label} executed, but tracked balances changed unexpectedly: ${mismatched.map((c) => `${c.account} ${c.asset} changed by ${c.delta}, expected ${c.expectedDelta}`).join(', ')}`
+      );
+    }
+    const lockErrors = result.convictionLock?.errors ?? [];
+    if (lockErrors.length > 0) {
+      throw new CodedError(
+        ErrorCode.ConvictionLockFailed,
+        `${label} executed, but the voter's conviction lock is wrong: ${lockErrors.join('; ')}`
+      );
+    }
+    const denied = lints.filter((lint) => lint.level === 'deny');
+    if (denied.length > 0) {
+      throw new CodedError(
+        ErrorCode.LintDenied,
+        `${label} executed, but denied lints fired: ${denied.map((lint) => lint.rule).join(', ')}`
+      );
     }
   }
 }
@@ -0,0 +1,357 @@
+import { decAnyMetadata, unifyMetadata } from '@polkadot-api/substrate-bindings';
+import type { SS58String } from 'polkadot-api';
+import type { SubstrateApi } from '../types/substrate-api';
+import { isAddress } from '../utils/address';
+import { CodedError, ErrorCode } from '../utils/error-codes';
+import { stringify } from '../utils/json';
+import type { Logger } from '../utils/logger';
+import type { RpcRequest } from './call-drift-detector';
+import type { ChopsticksManager } from './chopsticks-manager';
+
+/**
+ * `--set-storage 'Pallet.Item[key]=value'`: a storage item written to the fork
+ * before the simulation starts. Map items take one `[key]` per hasher
+ * (`Pallet.Item[k1][k2]=value`), plain values none.
+ *
+ * Keys and the value are JSON (`{"free":"1000"}`, `[1,2]`, `"Root"`) or, when
+ * not valid JSON, taken as a string (`MigrationDone`, an SS58 address).
+ * Chopsticks encodes them against the fork's metadata, as for `import-storage`.
+ */
+export interface StorageOverride {
+  pallet: string;
+  item: string;
+  keys: unknown[];
+  value: unknown;
+  /** Skipped, rather than an error, on chains without this item */
+  optional?: boolean;
+  /** Set by `--override-parameter`: the `Namespace.Name` it was given as */
+  parameter?: string;
+  /** Set by `--fund`: the free balance, merged into the account's current `AccountInfo` */
+  fund?: string;
+}
+
+/**
+ * `--unlock-call-filter`: the storage behind the call filters of the Asset Hub
+ * migration. Until its stage is `MigrationDone`, `AhMigrator` on Asset Hub and
+ * `RcMigrator` on the relay chain filter most calls, including
+ * `Referenda.submit`. Chains without a migrator are left alone.
+ */
+export const CALL_FILTER_UNLOCKS: StorageOverride[] = [
+  {
+    pallet: 'AhMigrator',
+    item: 'AhMigrationStage',
+    keys: [],
+    value: 'MigrationDone',
+    optional: true,
+  },
+  {
+    pallet: 'RcMigrator',
+    item: 'RcMigrationStage',
+    keys: [],
+    value: 'MigrationDone',
+    optional: true,
+  },
+];
+
+type StorageType =
+  | { tag: 'plain'; value: number }
+  | { tag: 'map'; value: { hashers: unknown[]; key: number; value: number } };
+
+type TypeDef = { tag: string; value: unknown };
+type Variant = { name: string; fields: { type: number }[] };
+
+/** The subset of (unified) metadata needed to resolve storage items. */
+export interface StorageMetadata {
+  /** Needed only to resolve `--override-parameter` against `Parameters.Parameters` */
+  lookup?: { id: number; def: TypeDef }[];
+  pallets: {
+    name: string;
+    storage?: { items: { name: string; type: StorageType }[] };
+  }[];
+}
+
+function invalid(value: string, reason: string): CodedError {
+  return new CodedError(
+    ErrorCode.InvalidStorageOverride,
+    `Invalid --set-storage value: ${value} (${reason})`
+  );
+}
+
+/**
+ * A key or value literal. Integers too large for a JS number stay strings,
+ * which Chopsticks encodes exactly (e.g. u128 balances).
+ */
+function parseLiteral(text: string): unknown {
+  const trimmed = text.trim();
+  if (/^-?\d+$/.test(trimmed)) {
+    const number = Number(trimmed);
+    return Number.isSafeInteger(number) ? number : trimmed;
+  }
+  try {
+    return JSON.parse(trimmed);
+  } catch {
+    return trimmed;
+  }
+}
+
+/** Index just past the `]` closing the `[` at `start`, skipping brackets inside JSON strings. */
+function closingBracket(text: string, start: number): number {
+  let depth = 0;
+  let inString = false;
+  for (let i = start; i < text.length; i++) {
+    const char = text[i];
+    if (inString) {
+      if (char === '\\') i++;
+      else if (char === '"') inString = false;
+    } else if (char === '"') {
+      inString = true;
+    } else if (char === '[') {
+      depth++;
+    } else if (char === ']' && --depth === 0) {
+      return i + 1;
+    }
+  }
+  return -1;
+}
+
+export function parseStorageOverride(value: string): StorageOverride {
+  const name = value.match(/^\s*([A-Za-z]\w*)\.([A-Za-z]\w*)/);
+  if (!name) throw invalid(value, 'expected Pallet.Item[key]=value');
+
+  const keys: unknown[] = [];
+  let rest = value.slice(name[0].length);
+  while (rest.trimStart().startsWith('[')) {
+    rest = rest.trimStart();
+    const end = closingBracket(rest, 0);
+    if (end === -1) throw invalid(value, 'unclosed [');
+    keys.push(parseLiteral(rest.slice(1, end - 1)));
+    rest = rest.slice(end);
+  }
+  rest = rest.trimStart();
+  if (!rest.startsWith('=') || !rest.slice(1).trim()) {
+    throw invalid(value, 'expected Pallet.Item[key]=value');
+  }
+  return { pallet: name[1], item: name[2], keys, value: parseLiteral(rest.slice(1)) };
+}
+
+/**
+ * `--fund <ss58>=<amount>`: `System.Account` of the account with a free
+ * balance of `amount` plancks. The value is that of an account that does not
+ * exist yet; `mergeFundOverrides` keeps the rest of an existing one.
+ */
+export function parseFundOverride(value: string): StorageOverride {
+  const [account, amount, ...rest] = value.split('=').map((part) => part.trim());
+  if (rest.length > 0 || !isAddress(account) || !/^\d+$/.test(amount ?? '')) {
+    throw new CodedError(
+      ErrorCode.InvalidStorageOverride,
+      `Invalid --fund value: ${value} (expected <ss58 address>=<amount in plancks>)`
+    );
+  }
+  return {
+    pallet: 'System',
+    item: 'Account',
+    keys: [account],
+    value: { providers: 1, data: { free: amount } },
+    fund: amount,
+  };
+}
+
+/**
+ * Merge each `--fund` override into the account's current `AccountInfo` on
+ * the fork, so only its free balance changes: its nonce, reference counts and
+ * reserved and frozen balances are kept, as are locks and holds relying on
+ * them. Accounts that do not exist keep the override's fresh account.
+ */
+export async function mergeFundOverrides(
+  api: SubstrateApi,
+  overrides: StorageOverride[]
+): Promise<StorageOverride[]> {
+  return Promise.all(
+    overrides.map(async (override) => {
+      if (override.fund === undefined) return override;
+      const current = await api.query.System.Account.getValue(override.keys[0] as SS58String);
+      const exists = Number(current?.providers ?? 0) > 0 || Number(current?.sufficients ?? 0) > 0;
+      if (!exists) return override;
+      // dev_setStorage takes JSON: bigints become decimal strings
+      const info = JSON.parse(stringify(current)) as { data: Record<string, unknown> };
+      return { ...override, value: { ...info, data: { ...info.data, free: override.fund } } };
+    })
+  );
+}
+
+/**
+ * `--override-parameter 'Namespace.Name=value'`: a runtime parameter of
+ * `pallet_parameters`, e.g. `Treasury.BurnPortion=...`. It becomes a
+ * `Parameters.Parameters` entry once resolved against the fork's metadata, or,
+ * on chains whose runtime has no such parameter, the plain storage item
+ * `Namespace.Name` (a pallet's storage value standing in for a constant).
+ */
+export function parseParameterOverride(value: string): StorageOverride {
+  const match = value.match(/^\s*([A-Za-z]\w*)\.([A-Za-z]\w*)\s*=(.+)$/s);
+  if (!match || !match[3].trim()) {
+    throw new CodedError(
+      ErrorCode.InvalidStorageOverride,
+      `Invalid --override-parameter value: ${value} (expected Namespace.Name=value)`
+    );
+  }
+  return {
+    pallet: match[1],
+    item: match[2],
+    keys: [],
+    value: parseLiteral(match[3]),
+    parameter: `${match[1]}.${match[2]}`,
+  };
+}
+
+/** Every `--set-storage` value, in the order given. */
+export function parseStorageOverrides(values?: string[]): StorageOverride[] {
+  return (values ?? []).map(parseStorageOverride);
+}
+
+interface OverrideOptions {
+  fund?: string[];
+  unlockCallFilter?: boolean;
+  overrideParameter?: string[];
+  setStorage?: string[];
+}
+
+/**
+ * All storage overrides of a run: `--unlock-call-filter`, then `--fund`, then
+ * `--override-parameter`, then `--set-storage`, so an explicit `--set-storage`
+ * of the same item wins.
+ */
+export function storageOverridesFor(options: OverrideOptions): StorageOverride[] {
+  return [
+    ...(options.unlockCallFilter ? CALL_FILTER_UNLOCKS : []),
+    ...(options.fund ?? []).map(parseFundOverride),
+    ...(options.overrideParameter ?? []).map(parseParameterOverride),
+    ...parseStorageOverrides(options.setStorage),
+  ];
+}
+
+function label(override: StorageOverride): string {
+  if (override.parameter) return `parameter ${override.parameter}`;
+  const keys = override.keys.map((key) => `[${JSON.stringify(key)}]`).join('');
+  return `${override.pallet}.${override.item}${keys}`;
+}
+
+const same = (a: string, b: string) => a.toLowerCase() === b.toLowerCase();
+
+function variantOf(metadata: StorageMetadata, type: number, name: string): Variant | undefined {
+  const def = metadata.lookup?.find((t) => t.id === type)?.def;
+  if (def?.tag !== 'variant') return undefined;
+  return (def.value as Variant[]).find((v) => same(v.name, name));
+}
+
+/**
+ * A parameter override as a `Parameters.Parameters` entry, if the runtime has
+ * the parameter. Its key is `RuntimeParametersKey::<Namespace>(<Name>)` and
+ * its value `RuntimeParametersValue::<Namespace>(<Name>(value))`, both spelled
+ * as the runtime does.
+ */
+function resolveParameter(
+  metadata: StorageMetadata,
+  override: StorageOverride
+): StorageOverride | undefined {
+  const pallet = metadata.pallets.find((p) => p.name === 'Parameters');
+  const item = pallet?.storage?.items.find((i) => i.name === 'Parameters');
+  if (item?.type.tag !== 'map') return undefined;
+  const namespace = variantOf(metadata, item.type.value.key, override.pallet);
+  const inner = namespace?.fields[0];
+  const name = inner && variantOf(metadata, inner.type, override.item);
+  if (!namespace || !name) return undefined;
+  return {
+    ...override,
+    pallet: 'Parameters',
+    item: 'Parameters',
+    keys: [{ [namespace.name]: { [name.name]: null } }],
+    value: { [namespace.name]: { [name.name]: override.value } },
+    parameter: `${namespace.name}.${name.name}`,
+  };
+}
+
+/**
+ * Resolve each override against the fork's metadata: pallet and item names
+ * are matched case-insensitively and replaced by the runtime's spelling, and
+ * the number of keys must match the item's hashers. Optional overrides of
+ * items the chain does not have are dropped. Parameter overrides resolve to
+ * `Parameters.Parameters` first and to a storage item of the same name second.
+ */
+export function resolveStorageOverrides(
+  metadata: StorageMetadata,
+  overrides: StorageOverride[]
+): StorageOverride[] {
+  return overrides.flatMap((override) => {
+    const parameter = override.parameter && resolveParameter(metadata, override);
+    if (parameter) return [parameter];
+    const flag = override.parameter ? '--override-parameter' : '--set-storage';
+    const pallet = metadata.pallets.find((p) => same(p.name, override.pallet));
+    const item = pallet?.storage?.items.find((i) => same(i.name, override.item));
+    if (!pallet || !item) {
+      if (override.optional) return [];
+      const what = override.parameter ? 'parameter or storage item' : 'storage item';
+      throw new CodedError(
+        ErrorCode.InvalidStorageOverride,
+        `${flag} ${override.pallet}.${override.item}: no such ${what} on this chain`
+      );
+    }
+    const expected = item.type.tag === 'plain' ? 0 : item.type.value.hashers.length;
+    if (override.keys.length !== expected) {
+      throw new CodedError(
+        ErrorCode.InvalidStorageOverride,
+        `${flag} ${pallet.name}.${item.name} takes ${expected} key(s), got ${override.keys.length}`
+      );
+    }
+    // A parameter that fell back to storage is logged as the item it wrote
+    const { parameter: _parameter, ...storage } = override;
+    return [{ ...storage, pallet: pallet.name, item: item.name }];
+  });
+}
+
+/** The overrides as a `dev_setStorage` update, merging entries of the same map. */
+export function storageOverrideUpdates(overrides: StorageOverride[]): Record<string, unknown> {
+  const updates: Record<string, Record<string, unknown>> = {};
+  for (const override of overrides) {
+    const pallet = (updates[override.pallet] ??= {});
+    if (override.keys.length === 0) {
+      pallet[override.item] = override.value;
+    } else {
+      const entries = (pallet[override.item] ??= []) as unknown[];
+      entries.push([override.keys, override.value]);
+    }
+  }
+  return updates;
+}
+
+/**
+ * Resolve `overrides` against the fork's metadata and write them to its head
+ * state. Chopsticks rejects values that do not encode as the item's type.
+ */
+export async function applyStorageOverrides(
+  logger: Logger,
+  request: RpcRequest,
+  chopsticks: ChopsticksManager,
+  overrides: StorageOverride[]
+): Promise<void> {
+  if (overrides.length === 0) return;
+
+  const hex: string = await request('state_getMetadata', []);
+  const metadata = unifyMetadata(decAnyMetadata(hex)) as unknown as StorageMetadata;
+  const resolved = resolveStorageOverrides(metadata, overrides);
+  if (overrides.some((o) => o.optional) && !resolved.some((o) => o.optional)) {
+    logger.warn('--unlock-call-filter: this chain has no known call filter to unlock');
+  }
+  if (resolved.length === 0) return;
+  try {
+    await chopsticks.setStorageBatch(storageOverrideUpdates(resolved));
+  } catch (error) {
+    throw new CodedError(
+      ErrorCode.InvalidStorageOverride,
+      `Storage overrides could not be applied: ${(error as Error).message}`,
+      { cause: error }
+    );
+  }
+  for (const override of resolved) {
+    logger.info(`Storage override: ${label(override)} = ${JSON.stringify(override.value)}`);
+  }
+}
@@ -0,0 +1,181 @@
+import { Binary } from 'polkadot-api';
+import type { TestOptions } from '../types';
+import type { ParsedEndpoint } from '../utils/chain-endpoint-parser';
+import type { Logger } from '../utils/logger';
+import { formatTokenAmount, type NativeToken } from '../utils/token-format';
+import {
+  buildCreationPreview,
+  type CreationPreview,
+  type CreationTarget,
+  creationTargets,
+  withTargetApi,
+} from './creation-preview';
+import {
+  type DelegatedStep,
+  delegatedSteps,
+  parseDelegatedSubmission,
+} from './delegated-submission';
+
+/** One call of a submission on the live chain. */
+export interface SubmissionPayload {
+  /** e.g. `Referenda.submit on track #1 whitelisted_caller` */
+  title: string;
+  /** Who signs it, when not the proposer */
+  signer?: string;
+  /** Call data; unset when it depends on chain state only known live */
+  hex?: string;
+  note?: string;
+  /** Dispatched by the calls after it rather than submitted itself */
+  wrapped?: boolean;
+}
+
+/**
+ * A polkadot-js statement submitting `calls` from `account`, batched with
+ * `Utility.batch_all` when there is more than one.
+ */
+export function polkadotJsSnippet(calls: string[]): string {
+  const decoded = calls.map((hex) => `api.createType('Call', '${hex}')`);
+  const tx =
+    decoded.length === 1
+      ? `api.tx(${decoded[0]})`
+      : `api.tx.utility.batchAll([${decoded.join(', ')}])`;
+  return `await ${tx}.signAndSend(account);`;
+}
+
+/**
+ * subxt statements submitting `calls` from `signer`, one extrinsic each. subxt
+ * takes raw call data through a `RawCall` payload (see the README).
+ */
+export function subxtSnippet(calls: string[]): string {
+  return calls
+    .map(
+      (hex) =>
+        `api.tx().sign_and_submit_then_watch_default(&RawCall(hex::decode("${hex.slice(2)}")?), &signer).await?;`
+    )
+    .join(' ');
+}
+
+/**
+ * The calls creating `target`'s referendum on the live chain: the preimage,
+ * the submit and, for a delegated submission, the `steps` wrapping the submit.
+ */
+export function submissionPayloads(
+  target: CreationTarget,
+  preview: CreationPreview,
+  steps?: DelegatedStep[]
+): SubmissionPayload[] {
+  const payloads: SubmissionPayload[] = [];
+  if (target.preimage) {
+    const noted = preview.preimage
+      ? ` (${preview.preimage.hash}, ${preview.preimage.len} bytes)`
+      : '';
+    payloads.push({ title: `Preimage.note_preimage${noted}`, hex: target.preimage });
+  }
+  const track = preview.track ? ` on track #${preview.track.id} ${preview.track.name}` : '';
+  payloads.push({
+    title: `${preview.submit.name}${track}${steps ? ', wrapped by the calls below' : ''}`,
+    hex: target.submit,
+    wrapped: !!steps,
+  });
+  for (const step of steps ?? []) {
+    payloads.push({ title: step.name, signer: step.signer, hex: step.hex, note: step.note });
+  }
+  return payloads;
+}
+
+/** Consecutive submitted calls with call data, grouped by who signs them. */
+function signedGroups(payloads: SubmissionPayload[]): { signer?: string; calls: string[] }[] {
+  const groups: { signer?: string; calls: string[] }[] = [];
+  for (const payload of payloads) {
+    if (payload.wrapped) continue;
+    const last = groups[groups.length - 1];
+    if (!payload.hex) {
+      groups.push({ signer: payload.signer, calls: [] });
+    } else if (last && last.signer === payload.signer && last.calls.length > 0) {
+      last.calls.push(payload.hex);
+    } else {
+      groups.push({ signer: payload.signer, calls: [payload.hex] });
+    }
+  }
+  return groups.filter((group) => group.calls.length > 0);
+}
+
+/**
+ * Show what to submit on the live chain to create the simulated referendum:
+ * the call data, the deposits they take and polkadot-js and subxt statements
+ * sending them.
+ */
+export function displaySubmissionPayloads(
+  logger: Logger,
+  target: CreationTarget,
+  preview: CreationPreview,
+  token?: NativeToken,
+  payloads: SubmissionPayload[] = submissionPayloads(target, preview)
+): void {
+  logger.section(`${target.label} Submission Payloads`);
+  const amount = (value?: bigint) =>
+    value === undefined ? 'unknown' : formatTokenAmount(value, token);
+
+  for (const [i, payload] of payloads.entries()) {
+    const signer = payload.signer ? `, signed by ${payload.signer}` : '';
+    const note = payload.note ? ` (${payload.note})` : '';
+    logger.info(`${i + 1}. ${payload.title}${signer}${note}${payload.hex ? ':' : ''}`);
+    if (payload.hex) logger.info(payload.hex);
+  }
+
+  logger.info(
+    `Deposits: ${amount(preview.submissionDeposit)} submission deposit, ${amount(preview.track?.decisionDeposit)} decision deposit (place_decision_deposit)${target.preimage ? ', plus the preimage deposit' : ''}`
+  );
+  for (const group of signedGroups(payloads)) {
+    const signer = group.signer ? ` (${group.signer})` : '';
+    logger.info(`polkadot-js${signer}: ${polkadotJsSnippet(group.calls)}`);
+    logger.info(`subxt${signer}: ${subxtSnippet(group.calls)}`);
+  }
+}
+
+/**
+ * After a passing run, print the submission payloads of every
+ * `--call-to-create-*-referendum`, decoded against the live chain and wrapped
+ * for `--submit-via-proxy`/`--submit-via-multisig` as the run submitted them.
+ * Failing to build them only warns: the simulation already passed.
+ */
+export async function printSubmissionPayloads(
+  logger: Logger,
+  options: TestOptions,
+  endpoints: { governance?: ParsedEndpoint; fellowship?: ParsedEndpoint }
+): Promise<void> {
+  const submission = parseDelegatedSubmission(options);
+  for (const target of creationTargets(options, endpoints)) {
+    try {
+      const { value, token } = await withTargetApi(logger, target, async (api) => {
+        const preview = await buildCreationPreview(
+          api,
+          target.submit,
+          target.preimage,
+          target.isFellowship
+        );
+        // Delegated submission only applies to the governance referendum
+        const steps =
+          submission && !target.isFellowship
+            ? await delegatedSteps(
+                api,
+                await api.txFromCallData(Binary.fromHex(target.submit)),
+                submission
+              )
+            : undefined;
+        return { preview, steps };
+      });
+      displaySubmissionPayloads(
+        logger,
+        target,
+        value.preview,
+        token,
+        submissionPayloads(target, value.preview, value.steps)
+      );
+    } catch (error) {
+      logger.warn(
+        `Could not build the ${target.label.toLowerCase()} submission payloads: ${(error as Error).message}`
+      );
+    }
+  }
+}
@@ -0,0 +1,144 @@
+import { getDynamicBuilder, getLookupFn } from '@polkadot-api/metadata-builders';
+import { decAnyMetadata, unifyMetadata } from '@polkadot-api/substrate-bindings';
+import type { TrackChange } from '../types';
+import { stringify } from '../utils/json';
+import type { Logger } from '../utils/logger';
+import type { RpcRequest } from './call-drift-detector';
+
+/** One track of a `Tracks` constant, with its parameters rendered for display. */
+export interface TrackParams {
+  id: number;
+  name: string;
+  /** `max_deciding`, `decision_period`, `min_approval`, ... */
+  params: Record<string, string>;
+}
+
+/** Every pallet's tracks, keyed by pallet name (`Referenda`, `FellowshipReferenda`, ...). */
+export type TrackTables = Record<string, TrackParams[]>;
+
+type RawTrack = [number, Record<string, unknown>];
+type PalletConstants = { name: string; constants: { name: string; type: number; value: string }[] };
+
+function formatParam(value: unknown): string {
+  return typeof value === 'object' && value !== null ? stringify(value) : String(value);
+}
+
+/** Track names are strings, or `[u8; N]` padded with zeros on newer runtimes. */
+export function trackName(name: unknown, id: number): string {
+  if (typeof name === 'string') return name;
+  const text = (name as { asText?: () => string } | undefined)?.asText?.();
+  return text?.replace(/\0+$/, '') || `track_${id}`;
+}
+
+/** Decode the `Tracks` constant of every pallet that has one. */
+export function tracksFromMetadata(metadataHex: string): TrackTables {
+  const metadata = unifyMetadata(decAnyMetadata(metadataHex));
+  const builder = getDynamicBuilder(getLookupFn(metadata));
+  const tables: TrackTables = {};
+  for (const pallet of metadata.pallets as unknown as PalletConstants[]) {
+    const constant = pallet.constants.find((c) => c.name === 'Tracks');
+    if (!constant) continue;
+    const tracks = builder.buildDefinition(constant.type).dec(constant.value) as RawTrack[];
+    tables[pallet.name] = tracks.map(([id, info]) => {
+      const { name, ...params } = info;
+      return {
+        id,
+        name: trackName(name, id),
+        params: Object.fromEntries(
+          Object.entries(params).map(([key, value]) => [key, formatParam(value)])
+        ),
+      };
+    });
+  }
+  return tables;
+}
+
+/** Tracks added, removed or with changed parameters, per pallet and track id. */
+export function diffTracks(before: TrackTables, after: TrackTables): TrackChange[] {
+  const changes: TrackChange[] = [];
+  const pallets = [...new Set([...Object.keys(before), ...Object.keys(after)])];
+  for (const pallet of pallets) {
+    const old = new Map((before[pallet] ?? []).map((track) => [track.id, track]));
+    const now = new Map((after[pallet] ?? []).map((track) => [track.id, track]));
+    const ids = [...new Set([...old.keys(), ...now.keys()])].sort((a, b) => a - b);
+    for (const id of ids) {
+      const from = old.get(id);
+      const to = now.get(id);
+      const keys = [
+        ...new Set([...Object.keys(from?.params ?? {}), ...Object.keys(to?.params ?? {})]),
+      ];
+      const params = keys
+        .map((param) => ({ param, before: from?.params[param], after: to?.params[param] }))
+        .filter((change) => change.before !== change.after);
+      if (from && to && from.name !== to.name) {
+        params.unshift({ param: 'name', before: from.name, after: to.name });
+      }
+      if (params.length === 0) continue;
+      changes.push({
+        pallet,
+        track: id,
+        name: (to ?? from)?.name ?? `track_${id}`,
+        kind: !from ? 'added' : !to ? 'removed' : 'changed',
+        params,
+      });
+    }
+  }
+  return changes;
+}
+
+/** Side-by-side before/after table, one row per changed parameter. */
+export function formatTrackChanges(changes: TrackChange[]): string[] {
+  const rows = changes.flatMap((change) =>
+    change.params.map((param, n) => [
+      n === 0 ? `${change.pallet} #${change.track} ${change.name} (${change.kind})` : '',
+      param.param,
+      param.before ?? '-',
+      param.after ?? '-',
+    ])
+  );
+  const header = ['Track', 'Parameter', 'Before', 'After'];
+  const widths = header.map((title, column) =>
+    Math.max(title.length, ...rows.map((row) => row[column].length))
+  );
+  return [header, ...rows].map((row) =>
+    row
+      .map((cell, column) => (column === row.length - 1 ? cell : cell.padEnd(widths[column])))
+      .join('  ')
+      .trimEnd()
+  );
+}
+
+/**
+ * The referenda track tables, recorded before a referendum is forced through
+ * so that changes it makes to them (a runtime upgrade, or `Parameters` the
+ * tracks are read from) can be reported after dispatch. The tables are read
+ * from the fork's metadata rather than the API's constants, which are cached
+ * per runtime version.
+ */
+export class TrackChanges {
+  private before: TrackTables;
+
+  private constructor(before: TrackTables) {
+    this.before = before;
+  }
+
+  static async snapshot(request: RpcRequest): Promise<TrackChanges> {
+    return new TrackChanges(tracksFromMetadata(await request('state_getMetadata', [])));
+  }
+
+  /** Print changed tracks in a "Track Changes" section and return them. */
+  async report(logger: Logger, request: RpcRequest): Promise<TrackChange[]> {
+    const after = tracksFromMetadata(await request('state_getMetadata', []));
+    const changes = diffTracks(this.before, after);
+    if (changes.length === 0) {
+      logger.debug('Referenda tracks unchanged');
+      return changes;
+    }
+
+    logger.section('Track Changes');
+    for (const line of formatTrackChanges(changes)) {
+      logger.info(line);
+    }
+    return changes;
+  }
+}
@@ -0,0 +1,237 @@
+import type { SS58String } from 'polkadot-api';
+import type { TreasurySpendResult } from '../types';
+import type { SubstrateApi } from '../types/substrate-api';
+import { interpretDispatchResult } from '../utils/dispatch-result';
+import { getBlockEvents, type ParsedEvent } from '../utils/event-serializer';
+import type { Logger } from '../utils/logger';
+import { ALICE_ADDRESS } from '../utils/storage-constants';
+import { formatTokenAmount, type NativeToken } from '../utils/token-format';
+import {
+  assetIdLocation,
+  findAccountId32,
+  formatLocation,
+  isHere,
+  isLocation,
+  junctions,
+  unwrapVersioned,
+  type XcmLocation,
+} from '../utils/xcm-location';
+import { type AssetRef, assetLabel, BalanceTracker } from './balance-tracker';
+import type { ChopsticksManager } from './chopsticks-manager';
+import type { SchedulerManager } from './scheduler-manager';
+
+/** A spend approved by `Treasury.spend` (asset kind) or `Treasury.spend_local` (native). */
+export interface ApprovedSpend {
+  index: number;
+  kind: 'spend' | 'spend_local';
+  /** `VersionedLocatableAsset`: where the asset is paid out, and which asset */
+  assetKind?: { location: XcmLocation; assetId: XcmLocation };
+  amount: bigint;
+  beneficiary?: SS58String;
+  /** `valid_from` of an asset kind spend */
+  validFrom?: number;
+}
+
+/** Read `Treasury.AssetSpendApproved` and `Treasury.SpendApproved` events. */
+export function parseApprovedSpends(events: ParsedEvent[]): ApprovedSpend[] {
+  const spends: ApprovedSpend[] = [];
+  for (const event of events) {
+    if (event.section !== 'Treasury') continue;
+    const data = (event.data ?? {}) as Record<string, unknown>;
+    if (event.method === 'AssetSpendApproved') {
+      const kind = unwrapVersioned(data.asset_kind) as
+        | { location?: unknown; asset_id?: unknown }
+        | undefined;
+      const assetId = assetIdLocation(kind?.asset_id);
+      spends.push({
+        index: Number(data.index),
+        kind: 'spend',
+        assetKind:
+          isLocation(kind?.location) && assetId
+            ? { location: kind.location, assetId }
+            : undefined,
+        amount: BigInt(data.amount as bigint),
+        beneficiary: findAccountId32(data.beneficiary),
+        ...(data.valid_from !== undefined && { validFrom: Number(data.valid_from) }),
+      });
+    } else if (event.method === 'SpendApproved') {
+      spends.push({
+        index: Number(data.proposal_index),
+        kind: 'spend_local',
+        amount: BigInt(data.amount as bigint),
+        beneficiary: typeof data.beneficiary === 'string' ? data.beneficiary : undefined,
+      });
+    }
+  }
+  return spends;
+}
+
+/**
+ * Map an asset id, as seen from the chain paying it out, to the pallet holding
+ * it: the chain's own token (`Here`, or the relay token `../Here` on system
+ * parachains), an `Assets` asset (`PalletInstance(50)/GeneralIndex(1984)`), or
+ * a `ForeignAssets` asset for any other location outside the chain.
+ */
+export function localAsset(assetId: XcmLocation): AssetRef | undefined {
+  const path = junctions(assetId);
+  if (path.length === 0 && assetId.parents <= 1) {
+    return { type: 'native' };
+  }
+  if (
+    assetId.parents === 0 &&
+    path.length === 2 &&
+    path[0].type === 'PalletInstance' &&
+    path[1].type === 'GeneralIndex'
+  ) {
+    return { type: 'assets', id: Number(path[1].value) };
+  }
+  if (assetId.parents >= 1) {
+    return { type: 'foreign', location: assetId };
+  }
+  return undefined;
+}
+
+/**
+ * Simulates `Treasury.payout` for the asset spends a proposal approved and
+ * checks the beneficiary's balance moved by the spent amount.
+ *
+ * The payout is scheduled as a signed call from Alice (the scheduler
+ * dispatches it without fees). Spends paid out on another chain, and
+ * `spend_local` spends (paid at the next spend period), are only reported.
+ * A spend whose `valid_from` is still ahead fails the payout with
+ * `EarlyPayout`; it is reported as pending rather than failed.
+ */
+export class TreasuryPayouts {
+  private logger: Logger;
+  private chopsticks: ChopsticksManager;
+  private api: SubstrateApi;
+  private scheduler: SchedulerManager;
+  private nativeToken?: () => Promise<NativeToken | undefined>;
+
+  constructor(
+    logger: Logger,
+    chopsticks: ChopsticksManager,
+    api: SubstrateApi,
+    scheduler: SchedulerManager,
+    nativeToken?: () => Promise<NativeToken | undefined>
+  ) {
+    this.logger = logger;
+    this.chopsticks = chopsticks;
+    this.api = api;
+    this.scheduler = scheduler;
+    this.nativeToken = nativeToken;
+  }
+
+  async payoutAll(events: ParsedEvent[]): Promise<TreasurySpendResult[]> {
+    const spends = parseApprovedSpends(events);
+    if (spends.length === 0) return [];
+
+    this.logger.section('Treasury Spends');
+    const results: TreasurySpendResult[] = [];
+    for (const spend of spends) {
+      results.push(await this.payout(spend));
+    }
+    return results;
+  }
+
+  private async payout(spend: ApprovedSpend): Promise<TreasurySpendResult> {
+    const location = spend.assetKind?.location;
+    const asset =
+      location && isHere(location) && spend.assetKind
+        ? localAsset(spend.assetKind.assetId)
+        : undefined;
+    const label = spend.assetKind
+      ? asset
+        ? await assetLabel(this.api, asset)
+        : formatLocation(spend.assetKind.assetId)
+      : 'native';
+    const result = this.describe(spend, label);
+    const token =
+      !spend.assetKind || asset?.type === 'native' ? await this.nativeToken?.() : undefined;
+    this.logger.info(
+      `Spend #${spend.index} (${spend.kind}): ${formatTokenAmount(spend.amount, token)} of ${label} to ${spend.beneficiary ?? 'an unresolved beneficiary'}`
+    );
+
+    if (spend.kind === 'spend_local') {
+      this.logger.info('  Paid from the treasury pot at the next spend period; not simulated');
+      return { ...result, status: 'unchecked' };
+    }
+    if (!asset || !spend.beneficiary || !location) {
+      const where = location ? formatLocation(location) : 'an undecodable location';
+      this.logger.info(`  Paid out on ${where}; balances not checked on this chain`);
+      return { ...result, status: 'unchecked' };
+    }
+    try {
+      const delta = await this.simulatePayout(spend.index, asset, spend.beneficiary);
+      if (delta === undefined) {
+        const from = spend.validFrom === undefined ? 'a later block' : `block ${spend.validFrom}`;
+        this.logger.info(`  Pending: payable from ${from} (Treasury.payout reports EarlyPayout)`);
+        return { ...result, status: 'pending' };
+      }
+      if (delta !== spend.amount) {
+        throw new Error(
+          `beneficiary ${spend.beneficiary} received ${formatTokenAmount(delta, token)} of ${label}, expected ${formatTokenAmount(spend.amount, token)}`
+        );
+      }
+      this.logger.success(
+        `  Paid: beneficiary received ${formatTokenAmount(delta, token)} of ${label}`
+      );
+      return { ...result, status: 'paid' };
+    } catch (error) {
+      const message = (error as Error).message;
+      this.logger.error(`  Spend #${spend.index} payout failed: ${message}`);
+      return { ...result, status: 'failed', error: message };
+    }
+  }
+
+  /**
+   * Dispatch `Treasury.payout(index)` and return the beneficiary's balance
+   * change, or `undefined` if the spend is not valid yet.
+   */
+  private async simulatePayout(
+    index: number,
+    asset: AssetRef,
+    beneficiary: SS58String
+  ): Promise<bigint | undefined> {
+    if (!this.api.tx.Treasury) {
+      throw new Error('Chain has no Treasury.payout call');
+    }
+
+    const tracker = new BalanceTracker(this.api);
+    await tracker.snapshot(asset, beneficiary);
+
+    const call = await this.api.tx.Treasury.payout({ index }).getEncodedData();
+    const block = await this.scheduler.scheduleCallAtNextBlock(call.asHex(), {
+      System: { Signed: ALICE_ADDRESS },
+    });
+    this.logger.debug(`Treasury.payout(${index}) scheduled at block ${block}`);
+    await this.chopsticks.newBlock();
+
+    const events = await getBlockEvents(this.api.query.System.Events, this.logger);
+    const dispatched = events.find((e) => e.section === 'Scheduler' && e.method === 'Dispatched');
+    const outcome = interpretDispatchResult((dispatched?.data as { result?: unknown })?.result);
+    if (outcome.outcome === 'failure' && outcome.message?.includes('EarlyPayout')) {
+      return undefined;
+    }
+    if (outcome.outcome !== 'success') {
+      throw new Error(`Treasury.payout did not dispatch: ${outcome.message ?? outcome.outcome}`);
+    }
+    if (!events.some((e) => e.section === 'Treasury' && e.method === 'Paid')) {
+      throw new Error('Treasury.payout dispatched without a Treasury.Paid event');
+    }
+
+    const [{ delta }] = await tracker.deltas();
+    return delta;
+  }
+
+  private describe(spend: ApprovedSpend, asset: string): Omit<TreasurySpendResult, 'status'> {
+    return {
+      index: spend.index,
+      kind: spend.kind,
+      asset,
+      amount: spend.amount,
+      beneficiary: spend.beneficiary,
+      validFrom: spend.validFrom,
+    };
+  }
+}
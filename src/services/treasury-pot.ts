@@ -0,0 +1,87 @@
+import { AccountId, type SS58String } from 'polkadot-api';
+import type { BalanceChange } from '../types';
+import type { SubstrateApi } from '../types/substrate-api';
+import type { Logger } from '../utils/logger';
+import { formatDelta, formatTokenAmount, type NativeToken } from '../utils/token-format';
+import { type AssetRef, assetLabel, BalanceTracker } from './balance-tracker';
+
+/** `pallet_treasury`'s default `PalletId`, used when the chain does not expose it. */
+const DEFAULT_TREASURY_PALLET_ID = new TextEncoder().encode('py/trsry');
+
+/** Stablecoins the Asset Hub treasury holds: USDT and USDC in the `Assets` pallet. */
+export const TREASURY_STABLE_ASSETS = [1984, 1337];
+
+/** The account a pallet id owns: `modl` ++ id, zero-padded to 32 bytes. */
+export function palletAccount(palletId: Uint8Array): SS58String {
+  const bytes = new Uint8Array(32);
+  bytes.set(new TextEncoder().encode('modl'));
+  bytes.set(palletId, 4);
+  return AccountId().dec(bytes);
+}
+
+/**
+ * Treasury pot balances, native and the stablecoins the chain has, recorded
+ * before a referendum is forced through so its cost to the treasury can be
+ * reported after dispatch and payouts.
+ */
+export class TreasuryPot {
+  private api: SubstrateApi;
+  private tracker: BalanceTracker;
+  readonly account: SS58String;
+  private assets: AssetRef[];
+
+  private constructor(api: SubstrateApi, account: SS58String, assets: AssetRef[]) {
+    this.api = api;
+    this.tracker = new BalanceTracker(api);
+    this.account = account;
+    this.assets = assets;
+  }
+
+  /**
+   * Snapshot the pot; `undefined` on chains without a Treasury pallet. The
+   * runtime's `pallets` come from its metadata, since the unsafe API answers
+   * for every pallet name.
+   */
+  static async snapshot(api: SubstrateApi, pallets: string[]): Promise<TreasuryPot | undefined> {
+    if (!pallets.includes('Treasury')) return undefined;
+    const palletId = await api.constants.Treasury.PalletId()
+      .then((id) => id.asBytes())
+      .catch(() => DEFAULT_TREASURY_PALLET_ID);
+
+    const assets: AssetRef[] = [{ type: 'native' }];
+    for (const id of pallets.includes('Assets') ? TREASURY_STABLE_ASSETS : []) {
+      const exists = await api.query.Assets.Asset.getValue(id).catch(() => undefined);
+      if (exists !== undefined) assets.push({ type: 'assets', id });
+    }
+
+    const pot = new TreasuryPot(api, palletAccount(palletId), assets);
+    for (const asset of assets) {
+      await pot.tracker.snapshot(asset, pot.account);
+    }
+    return pot;
+  }
+
+  /** Print how the pot moved in a "Treasury Pot" section and return the changes. */
+  async report(logger: Logger, token?: NativeToken): Promise<BalanceChange[]> {
+    const changes: BalanceChange[] = [];
+    for (const { asset, before, after, delta } of await this.tracker.deltas()) {
+      const label = await assetLabel(this.api, asset);
+      changes.push({ account: this.account, asset: label, before, after, delta });
+    }
+    if (changes.every((change) => change.delta === 0n)) {
+      logger.debug(`Treasury pot ${this.account} unchanged`);
+      return changes;
+    }
+
+    logger.section('Treasury Pot');
+    logger.info(`Account: ${this.account}`);
+    for (const change of changes) {
+      const native = change.asset === 'native' ? token : undefined;
+      const amount = (value: bigint) => formatTokenAmount(value, native);
+      logger.info(
+        `  ${change.asset}: ${amount(change.before)} -> ${amount(change.after)} (${formatDelta(change.delta, native)})`
+      );
+    }
+    return changes;
+  }
+}
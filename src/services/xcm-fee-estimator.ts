@@ -0,0 +1,413 @@
+import { AccountId, type Binary, type SS58String } from 'polkadot-api';
+import type { ApiResult, SubstrateApi, Weight, XcmOutcome } from '../types/substrate-api';
+import { stringify } from '../utils/json';
+import type { Logger } from '../utils/logger';
+import {
+  assetIdLocation,
+  formatLocation,
+  isLocation,
+  unwrapVersioned,
+  type XcmLocation,
+} from '../utils/xcm-location';
+import type { DecodedCall } from './proposal-linter';
+
+/** A chain forked alongside the referendum's, that its proposal may send XCM to. */
+export interface XcmDestination {
+  label: string;
+  api: SubstrateApi;
+}
+
+/** An XCM instruction as decoded by polkadot-api: `{ type: 'BuyExecution', value }`. */
+export interface XcmInstruction {
+  type: string;
+  value?: Record<string, unknown>;
+}
+
+/** A `send` of the XCM pallet: where to, and the message in its own version. */
+export interface XcmSend {
+  dest: XcmLocation;
+  /** `V3`, `V4`, ...: the version of both `dest` and the message */
+  version: string;
+  instructions: XcmInstruction[];
+}
+
+/** What a destination charges for a sent message, and what the message provides. */
+export interface XcmFeeEstimate {
+  destination: string;
+  /** Weight of the whole message, per `XcmPaymentApi.query_xcm_weight` */
+  weight?: Weight;
+  /** Fee for `weight` in the asset `BuyExecution` pays with */
+  fee?: bigint;
+  /** `DryRunApi.dry_run_xcm` outcome: `Complete`, or the error it stopped at */
+  dryRun?: string;
+  /** Limits and fees the message sets too low, and a failed dry run */
+  problems: string[];
+  /** Set for messages that `Transact` */
+  sovereignAccount?: SovereignFunding;
+}
+
+/** The sending chain's sovereign account on the destination, at the fork block. */
+export interface SovereignFunding {
+  account: SS58String;
+  /** Free balance, in the destination's native token */
+  free: bigint;
+  /** Native token the message's `WithdrawAsset` takes from the account */
+  withdrawn: bigint;
+  /** Why the account may not cover what the message needs */
+  warnings: string[];
+}
+
+const XCM_PALLETS = new Set(['XcmPallet', 'PolkadotXcm']);
+
+/** The message of an `XcmPallet.send` / `PolkadotXcm.send` call. */
+export function xcmSend(call: DecodedCall): XcmSend | undefined {
+  if (!XCM_PALLETS.has(call.type) || call.value.type !== 'send') return undefined;
+  const { dest, message } = call.value.value ?? {};
+  const version = (message as { type?: string } | undefined)?.type;
+  const location = unwrapVersioned(dest);
+  const instructions = unwrapVersioned(message);
+  if (!version || !isLocation(location) || !Array.isArray(instructions)) return undefined;
+  return { dest: location, version, instructions: instructions as XcmInstruction[] };
+}
+
+/**
+ * Location of chain `to` as seen from chain `from`, by para id (`undefined`
+ * for the relay chain). Both are assumed to share one relay chain.
+ */
+export function relativeLocation(from?: number, to?: number): XcmLocation {
+  const parents = from === undefined ? 0 : 1;
+  if (to === undefined) return { parents, interior: { type: 'Here' } };
+  return { parents, interior: { type: 'X1', value: [{ type: 'Parachain', value: to }] } };
+}
+
+/**
+ * The account chain `from` controls on chain `to` (by para id, `undefined`
+ * for the relay chain): `Parent` for the relay chain on a parachain, `para`
+ * + id on the relay chain and `sibl` + id on a sibling, zero-padded.
+ */
+export function sovereignAccount(from?: number, to?: number): SS58String {
+  const bytes = new Uint8Array(32);
+  if (from === undefined) {
+    bytes.set(new TextEncoder().encode('Parent'));
+  } else {
+    bytes.set(new TextEncoder().encode(to === undefined ? 'para' : 'sibl'));
+    new DataView(bytes.buffer).setUint32(4, from, true);
+  }
+  return AccountId().dec(bytes);
+}
+
+/**
+ * Location of chain `to`'s native token as its messages name it. Assumes the
+ * relay chain's token, as on the relay chain and its system parachains.
+ */
+function nativeToken(to?: number): string {
+  return formatLocation({ parents: to === undefined ? 0 : 1, interior: { type: 'Here' } });
+}
+
+/** An XCM `Asset`: `fun` is `Fungible` with an amount, or `NonFungible`. */
+interface XcmAsset {
+  id: unknown;
+  fun: { type: string; value?: unknown };
+}
+
+/** Native token `WithdrawAsset` instructions take from the origin's account. */
+export function withdrawnNative(instructions: XcmInstruction[], to?: number): bigint {
+  let total = 0n;
+  for (const instruction of instructions) {
+    if (instruction.type !== 'WithdrawAsset') continue;
+    const assets = instruction.value as unknown as XcmAsset[];
+    for (const { id, fun } of assets) {
+      const location = assetIdLocation(id);
+      const native = location !== undefined && formatLocation(location) === nativeToken(to);
+      if (native && fun.type === 'Fungible') {
+        total += fun.value as bigint;
+      }
+    }
+  }
+  return total;
+}
+
+/**
+ * Whether the sovereign account can pay for the message: hold what
+ * `WithdrawAsset` takes, and, when a call is `Transact`ed as the account
+ * (`SovereignAccount` origin kind), keep at least `existentialDeposit` after
+ * that for the deposits and fees the call may reserve.
+ */
+export function checkSovereignFunding(
+  instructions: XcmInstruction[],
+  funding: { account: SS58String; free: bigint; withdrawn: bigint },
+  existentialDeposit?: bigint
+): string[] {
+  const { account, free, withdrawn } = funding;
+  if (free < withdrawn) {
+    return [
+      `sovereign account ${account} holds ${free}, less than the ${withdrawn} the message withdraws`,
+    ];
+  }
+  const signed = instructions.some(
+    (instruction) =>
+      instruction.type === 'Transact' &&
+      (instruction.value?.origin_kind as { type?: string } | undefined)?.type === 'SovereignAccount'
+  );
+  if (signed && existentialDeposit !== undefined && free - withdrawn < existentialDeposit) {
+    return [
+      `sovereign account ${account} keeps ${free - withdrawn} after the withdrawal, below the existential deposit (${existentialDeposit}); the Transact'ed call cannot pay deposits or fees from it`,
+    ];
+  }
+  return [];
+}
+
+export function formatWeight(weight: Weight): string {
+  return `ref_time ${weight.ref_time}, proof_size ${weight.proof_size}`;
+}
+
+/** Whether `limit` is below `needed` in either dimension. */
+function weightBelow(limit: Weight, needed: Weight): boolean {
+  return limit.ref_time < needed.ref_time || limit.proof_size < needed.proof_size;
+}
+
+function formatOutcome(outcome: XcmOutcome): string {
+  if (outcome.type === 'Complete') return 'Complete';
+  return `${outcome.type}: ${stringify(outcome.value.error)}`;
+}
+
+/**
+ * Check a message's limits against what its destination reports: the weight
+ * limit of `BuyExecution` / `UnpaidExecution` against the message's weight,
+ * the fees `BuyExecution` buys with against the fee for that weight, and each
+ * `Transact`'s declared weight against its call's.
+ */
+export function checkXcmLimits(
+  instructions: XcmInstruction[],
+  estimate: { weight?: Weight; fee?: bigint; callWeights: (Weight | undefined)[] }
+): string[] {
+  const problems: string[] = [];
+  let transact = 0;
+  for (const instruction of instructions) {
+    const args = instruction.value ?? {};
+    if (instruction.type === 'BuyExecution' || instruction.type === 'UnpaidExecution') {
+      const limit = args.weight_limit as { type: string; value?: Weight } | undefined;
+      if (limit?.type === 'Limited' && limit.value && estimate.weight) {
+        if (weightBelow(limit.value, estimate.weight)) {
+          problems.push(
+            `${instruction.type} weight limit (${formatWeight(limit.value)}) is below the message's weight (${formatWeight(estimate.weight)})`
+          );
+        }
+      }
+    }
+    if (instruction.type === 'BuyExecution' && estimate.fee !== undefined) {
+      const fun = (args.fees as { fun?: { type: string; value?: unknown } } | undefined)?.fun;
+      if (fun?.type === 'Fungible' && (fun.value as bigint) < estimate.fee) {
+        problems.push(
+          `BuyExecution fees (${fun.value}) are below the estimated fee (${estimate.fee})`
+        );
+      }
+    }
+    if (instruction.type === 'Transact') {
+      // `fallback_max_weight` (v5) is only used by destinations on older XCM
+      const declared = (args.require_weight_at_most ?? args.fallback_max_weight) as
+        | Weight
+        | undefined;
+      const needed = estimate.callWeights[transact++];
+      if (declared && needed && weightBelow(declared, needed)) {
+        problems.push(
+          `Transact declares ${formatWeight(declared)}, below its call's weight (${formatWeight(needed)})`
+        );
+      }
+    }
+  }
+  return problems;
+}
+
+/**
+ * Estimate the weight and fees of the XCM a proposal sends, on the forked
+ * destination chain, before the proposal is dispatched: `XcmPaymentApi` weighs
+ * the message and prices it, `DryRunApi` executes it, and
+ * `TransactionPaymentCallApi` weighs the calls it `Transact`s. Runtimes without
+ * one of these APIs only skip what it provides.
+ *
+ * The message is dry-run as sent from the sending chain itself, as with a Root
+ * origin; other origins prefix it with a `DescendOrigin` the dry run lacks.
+ */
+export class XcmFeeEstimator {
+  private logger: Logger;
+  private api: SubstrateApi;
+  private destinations: XcmDestination[];
+  private paraIds = new Map<SubstrateApi, Promise<number | undefined>>();
+  /** By call, so each lint rule reading an estimate shares one */
+  private estimates = new Map<DecodedCall, Promise<XcmFeeEstimate | undefined>>();
+
+  constructor(logger: Logger, api: SubstrateApi, destinations: XcmDestination[]) {
+    this.logger = logger;
+    this.api = api;
+    this.destinations = destinations;
+  }
+
+  /** Estimate an XCM send; `undefined` for other calls and destinations not forked. */
+  estimate(call: DecodedCall): Promise<XcmFeeEstimate | undefined> {
+    let estimate = this.estimates.get(call);
+    if (!estimate) {
+      estimate = this.estimateSend(call);
+      this.estimates.set(call, estimate);
+    }
+    return estimate;
+  }
+
+  private async estimateSend(call: DecodedCall): Promise<XcmFeeEstimate | undefined> {
+    const send = xcmSend(call);
+    if (!send) return undefined;
+    const target = formatLocation(send.dest);
+    const from = await this.paraId(this.api);
+    const destination = await this.findDestination(from, target);
+    if (!destination) {
+      this.logger.debug(`XCM to ${target} not estimated: no forked chain is at that location`);
+      return undefined;
+    }
+
+    const { api, label } = destination;
+    const message = { type: send.version, value: send.instructions };
+    const to = await this.paraId(api);
+    const origin = { type: send.version, value: relativeLocation(to, from) };
+    const problems: string[] = [];
+    const unwrap = <T>(name: string, result: ApiResult<T> | undefined): T | undefined => {
+      if (!result) return undefined;
+      if (result.success) return result.value;
+      problems.push(`${name} on ${label} failed: ${stringify(result.value)}`);
+      return undefined;
+    };
+
+    const weight = unwrap(
+      'query_xcm_weight',
+      await this.call(label, 'XcmPaymentApi', () =>
+        api.apis.XcmPaymentApi.query_xcm_weight(message)
+      )
+    );
+    const buy = send.instructions.find((instruction) => instruction.type === 'BuyExecution');
+    const feeAsset = (buy?.value?.fees as { id?: unknown } | undefined)?.id;
+    const fee =
+      weight && feeAsset !== undefined
+        ? unwrap(
+            'query_weight_to_asset_fee',
+            await this.call(label, 'XcmPaymentApi', () =>
+              api.apis.XcmPaymentApi.query_weight_to_asset_fee(weight, {
+                type: send.version,
+                value: feeAsset,
+              })
+            )
+          )
+        : undefined;
+    const dryRun = unwrap(
+      'dry_run_xcm',
+      await this.call(label, 'DryRunApi', () => api.apis.DryRunApi.dry_run_xcm(origin, message))
+    );
+    const outcome = dryRun && formatOutcome(dryRun.execution_result);
+    if (outcome && outcome !== 'Complete') {
+      problems.push(`dry run on ${label}: ${outcome}`);
+    }
+
+    const callWeights: (Weight | undefined)[] = [];
+    for (const instruction of send.instructions) {
+      if (instruction.type !== 'Transact') continue;
+      callWeights.push(await this.callWeight(destination, instruction.value?.call as Binary));
+    }
+    problems.push(...checkXcmLimits(send.instructions, { weight, fee, callWeights }));
+    const transacts = send.instructions.some((instruction) => instruction.type === 'Transact');
+    const funding = transacts
+      ? await this.sovereignFunding(destination, from, to, send.instructions)
+      : undefined;
+
+    this.logger.info(`XCM to ${label} (${target}):`);
+    this.logger.table({
+      Weight: weight ? formatWeight(weight) : 'unknown',
+      Fee: fee ?? (feeAsset === undefined ? 'none (no BuyExecution)' : 'unknown'),
+      'Dry run': outcome ?? 'not run',
+      ...(funding && {
+        'Sovereign account': `${funding.account}: ${funding.free} free, ${funding.withdrawn} withdrawn`,
+      }),
+    });
+    return {
+      destination: label,
+      weight,
+      fee,
+      dryRun: outcome,
+      problems,
+      ...(funding && { sovereignAccount: funding }),
+    };
+  }
+
+  /** The sender's sovereign account on the destination, and what the message takes from it. */
+  private async sovereignFunding(
+    { api, label }: XcmDestination,
+    from: number | undefined,
+    to: number | undefined,
+    instructions: XcmInstruction[]
+  ): Promise<SovereignFunding | undefined> {
+    const account = sovereignAccount(from, to);
+    const info = await this.call(label, 'System.Account', () =>
+      api.query.System.Account.getValue(account)
+    );
+    if (!info) return undefined;
+    const existentialDeposit = await this.call(label, 'Balances.ExistentialDeposit', async () =>
+      api.constants.Balances?.ExistentialDeposit()
+    );
+    const funding = { account, free: info.data.free, withdrawn: withdrawnNative(instructions, to) };
+    return {
+      ...funding,
+      warnings: checkSovereignFunding(instructions, funding, existentialDeposit),
+    };
+  }
+
+  /** The destination at `target` as seen from the chain with para id `from`. */
+  private async findDestination(
+    from: number | undefined,
+    target: string
+  ): Promise<XcmDestination | undefined> {
+    for (const destination of this.destinations) {
+      const location = relativeLocation(from, await this.paraId(destination.api));
+      if (formatLocation(location) === target) return destination;
+    }
+    return undefined;
+  }
+
+  /** `ParachainInfo.ParachainId`, or `undefined` on a relay chain. */
+  private paraId(api: SubstrateApi): Promise<number | undefined> {
+    let id = this.paraIds.get(api);
+    if (!id) {
+      id = Promise.resolve()
+        .then(() => api.query.ParachainInfo?.ParachainId.getValue())
+        .catch(() => undefined);
+      this.paraIds.set(api, id);
+    }
+    return id;
+  }
+
+  /** Weight of a `Transact`ed call, decoded and weighed on the destination. */
+  private async callWeight(
+    { api, label }: XcmDestination,
+    call: Binary | undefined
+  ): Promise<Weight | undefined> {
+    if (!call) return undefined;
+    const info = await this.call(label, 'TransactionPaymentCallApi', async () => {
+      const decoded = await api.txFromCallData(call);
+      return api.apis.TransactionPaymentCallApi.query_call_info(
+        decoded.decodedCall,
+        call.asBytes().length
+      );
+    });
+    return info?.weight;
+  }
+
+  /** Call a runtime API; a runtime without it, or an RPC failure, yields `undefined`. */
+  private async call<T>(
+    label: string,
+    name: string,
+    request: () => Promise<T>
+  ): Promise<T | undefined> {
+    try {
+      return await request();
+    } catch (error) {
+      this.logger.debug(`${name} unavailable on ${label}: ${(error as Error).message}`);
+      return undefined;
+    }
+  }
+}
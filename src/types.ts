@@ -6,16 +6,47 @@ export interface TestOptions {
   fellowship?: string; // Optional fellowship referendum ID
   fellowshipChainUrl?: string;
   port: string;
+  portRange?: string; // start:len block of ports for Chopsticks; fails fast if any is taken
   preCall?: string; // Hex string of call to execute before main referendum
   preOrigin?: string; // Origin for pre-execution call
+  scenario?: string; // Fault to inject into the simulation, e.g. bad-origin
+  cancelAt?: string; // Phase to cancel the referendum at instead of dispatching it: ongoing, confirming
+  voteConviction?: string; // Approve by a real conviction vote at this conviction, e.g. Locked1x
+  setStorage?: string[]; // Pallet.Item[key]=value overrides written to the fork before simulating
+  overrideParameter?: string[]; // Namespace.Name=value runtime parameters written to the fork before simulating
+  fund?: string[]; // ss58=amount: free balances (plancks) written to the fork before simulating
+  unlockCallFilter?: boolean; // Set the Asset Hub migration stage to MigrationDone if the chain has one
   cleanup: boolean;
   verbose: boolean;
-  additionalChains?: string; // Comma-separated list of additional chain URLs
+  emitBlocks?: boolean; // Print a machine-readable @@block line for every block built
+  outputFormat?: string; // text or json: json also prints an @@result line with the outcome
+  // Hook scripts (.js or .wasm) run at simulation phases with the fork's RPC endpoint
+  hookAfterPassing?: string;
+  hookBeforeDispatch?: string;
+  hookAfterDispatch?: string;
+  lint?: string; // Comma-separated rule=level overrides (allow, warn, deny)
+  deny?: string; // "warnings" promotes every warn-level lint to deny
+  additionalChains?: string[]; // One entry per --additional-chains, each a comma-separated list of chain URLs
+  settleBlocks?: string; // Blocks to build on additional chains after dispatch (default 1)
+  blocksAfterDispatch?: string; // Governance chain blocks to build after dispatch (default 0)
+  followScheduled?: boolean; // Dispatch tasks the proposal scheduled for later blocks
+  enactmentRetries?: string; // Retries to give the enactment task if it has no retry configuration
+  trackBalance?: string; // Comma-separated account@asset[=delta] balances to report across dispatch
+  db?: string; // Chopsticks storage cache (SQLite file) shared across runs
+  workDir?: string; // Parent of the per-run dir for temporary state (default: system temp)
+  runId?: string; // Prefixes every log line and names the work dir (default: generated)
+  historyDir?: string; // Keep each run's result here; off unless this or diffWithLast is set
+  diffWithLast?: boolean; // Report what changed since the previous run of the same referendum
   // Referendum creation options
   callToCreateGovernanceReferendum?: string; // Hex string of call to create governance referendum
   callToNotePreimageForGovernanceReferendum?: string; // Hex string of call to note preimage for governance referendum
+  metadataForGovernanceReferendum?: string; // JSON file or preimage hash to set as the created governance referendum's metadata
+  submitViaProxy?: string; // real[:proxy type[:delay]]: create the governance referendum as `real` via Alice as proxy
+  submitViaMultisig?: string; // threshold:signatory,...: create the governance referendum from a multisig via approve_as_multi/as_multi
   callToCreateFellowshipReferendum?: string; // Hex string of call to create fellowship referendum
   callToNotePreimageForFellowshipReferendum?: string; // Hex string of call to note preimage for fellowship referendum
+  metadataForFellowshipReferendum?: string; // JSON file or preimage hash to set as the created fellowship referendum's metadata
+  preview?: boolean; // Decode and show the creation calls, then exit without forking
 }
 
 export interface ReferendumInfo {
@@ -51,6 +82,8 @@ export interface ReferendumInfo {
 
 export interface SimulationResult {
   referendumId: number;
+  /** Set when the run created the referendum rather than forking an existing one */
+  created?: boolean;
   executionSucceeded: boolean;
   events: Array<{
     section: string;
@@ -58,7 +91,196 @@ export interface SimulationResult {
     data: unknown;
   }>;
   errors?: string[];
+  /** Set when the scheduler dropped the enactment task instead of dispatching it */
+  notDispatched?: NotDispatchedReason;
   blockExecuted?: number;
+  /** Blocks the scheduler postponed the enactment by, when a block ran out of weight */
+  postponedBlocks?: number;
+  lints?: LintFinding[];
+  treasurySpends?: TreasurySpendResult[];
+  balanceChanges?: BalanceChange[];
+  /** How the treasury pot (native and stablecoins) moved; set when it did */
+  treasuryPot?: BalanceChange[];
+  /** Referenda tracks the proposal added, removed or changed; set when it did */
+  trackChanges?: TrackChange[];
+  /** Events of the blocks built after the dispatch block (`--blocks-after-dispatch`) */
+  afterDispatch?: Array<{
+    block: number;
+    events: Array<{
+      section: string;
+      method: string;
+      data: unknown;
+    }>;
+  }>;
+  /** Tasks the proposal scheduled for later blocks, dispatched with `--follow-scheduled` */
+  scheduledTasks?: FollowedTask[];
+  /** Set when the enactment task has a retry configuration and its first dispatch failed */
+  retries?: EnactmentRetries;
+  /** Ranked-collective or `--vote-conviction` votes cast to approve the referendum */
+  syntheticVotes?: SyntheticVote[];
+  /** `--vote-conviction`: the voter's class lock after dispatch */
+  convictionLock?: ConvictionLock;
+  /** Set with `--cancel-at`: the referendum was cancelled instead of dispatched */
+  cancellation?: CancellationResult;
+}
+
+/** A referenda track whose parameters differ after dispatch. */
+export interface TrackChange {
+  /** Referenda pallet of the track, e.g. `Referenda` */
+  pallet: string;
+  track: number;
+  name: string;
+  kind: 'added' | 'removed' | 'changed';
+  /** Changed parameters, rendered as strings; unset on the side without the track */
+  params: { param: string; before?: string; after?: string }[];
+}
+
+/** A task a proposal scheduled for a later block, dispatched early by `--follow-scheduled`. */
+export interface FollowedTask {
+  /** Agenda block the proposal scheduled it at */
+  scheduledAt: number;
+  /** Index in that block's agenda */
+  index: number;
+  /** Block it was dispatched in on the fork */
+  executedAt: number;
+  succeeded: boolean;
+  errors?: string[];
+}
+
+/** A scheduler `RetryConfig` (`Scheduler.Retries`) of an agenda task. */
+export interface RetryConfig {
+  totalRetries: number;
+  /** Retries left; the scheduler schedules no further retry once it is 0 */
+  remaining: number;
+  /** Blocks the scheduler waits after a failed attempt before retrying */
+  period: number;
+}
+
+/** A retry of the enactment task, dispatched in the block after the failed attempt. */
+export interface RetryAttempt {
+  /** Agenda slot the scheduler put the retry in */
+  scheduledAt: number;
+  index: number;
+  /** Block it was dispatched in on the fork */
+  executedAt: number;
+  succeeded: boolean;
+  errors?: string[];
+}
+
+/** The retries of an enactment task whose first dispatch failed. */
+export interface EnactmentRetries {
+  /** The task's retry configuration before its first dispatch */
+  config: RetryConfig;
+  /** Errors of the first dispatch */
+  firstErrors: string[];
+  /** Retries in order; the last one succeeded if any did */
+  attempts: RetryAttempt[];
+}
+
+/**
+ * Why the scheduler did not dispatch an enactment task: `call-unavailable`
+ * (the preimage was missing), `permanently-overweight` (the call can never fit
+ * in a block) or `postponed` (blocks kept running out of weight before
+ * reaching it).
+ */
+export type NotDispatchedReason = 'call-unavailable' | 'permanently-overweight' | 'postponed';
+
+/**
+ * Where `--cancel-at` cancels the referendum: `ongoing` (as forked) or
+ * `confirming` (forced into its confirm period, before approval).
+ */
+export type CancelPhase = 'ongoing' | 'confirming';
+
+export interface CancellationResult {
+  phase: CancelPhase;
+  /** Block `cancel` was dispatched in */
+  block: number;
+  deposits: DepositRefund[];
+}
+
+/** A deposit of a cancelled referendum, and whether refunding it returned all of it. */
+export interface DepositRefund {
+  kind: 'submission' | 'decision';
+  who: SS58String;
+  amount: bigint;
+  refunded: boolean;
+  error?: string;
+}
+
+/**
+ * An aye cast to approve a referendum: by an injected collective member on a
+ * ranked referendum, or by a funded voter with `--vote-conviction`.
+ */
+export interface SyntheticVote {
+  who: SS58String;
+  /** Rank of a collective member */
+  rank?: number;
+  /** Conviction of a conviction vote, and the balance it voted */
+  conviction?: string;
+  balance?: bigint;
+  /** Votes counted in the tally's `ayes`: rank-scaled, or the balance scaled by conviction */
+  votes: number | bigint;
+}
+
+/** The class lock a `--vote-conviction` voter is left with after dispatch. */
+export interface ConvictionLock {
+  who: SS58String;
+  track: number;
+  conviction: string;
+  /** Balance voted, which the track's class lock should hold */
+  balance: bigint;
+  /** `ConvictionVoting.ClassLocksFor` amount for the track after dispatch */
+  classLock: bigint;
+  /** Block the removed vote stays locked until (`prior`); 0 when it does not outlast the poll */
+  unlockAt: number;
+  /** Referendum end plus the conviction's lock periods of `VoteLockingPeriod` */
+  expectedUnlockAt: number;
+  /** Class lock left after `unlock` right after removing the vote */
+  lockedAfterUnlock: bigint;
+  /** Where the lock differs from the expected one; the run fails if set */
+  errors?: string[];
+}
+
+/** A `--track-balance` balance before and after dispatch. */
+export interface BalanceChange {
+  account: string;
+  /** e.g. `native`, `Assets #1984 (USDT)`, `ForeignAssets ../Parachain(2030)/GeneralIndex(0)` */
+  asset: string;
+  before: bigint;
+  after: bigint;
+  delta: bigint;
+  /** Set when the check asserted a change; the run fails if `delta` differs */
+  expectedDelta?: bigint;
+}
+
+/** A treasury spend approved by the proposal, and whether its payout was checked. */
+export interface TreasurySpendResult {
+  index: number;
+  kind: 'spend' | 'spend_local';
+  /** e.g. `native`, `Assets #1984 (USDT)`, or the asset's location if paid elsewhere */
+  asset: string;
+  amount: bigint;
+  beneficiary?: string;
+  /** First block (of the treasury's block number provider) the spend can be paid out at */
+  validFrom?: number;
+  /**
+   * `paid`: `Treasury.payout` dispatched and the beneficiary received `amount`;
+   * `pending`: not payable yet (`EarlyPayout`), as the spend is valid from a later block;
+   * `unchecked`: paid on another chain or at the next spend period
+   */
+  status: 'paid' | 'pending' | 'failed' | 'unchecked';
+  error?: string;
+}
+
+/** `allow` silences a lint rule, `warn` reports it, `deny` also fails the run. */
+export type LintLevel = 'allow' | 'warn' | 'deny';
+
+export interface LintFinding {
+  rule: string;
+  level: LintLevel;
+  message: string;
+  /** `Pallet.call` the finding is about */
+  call: string;
 }
 
 export interface ChopsticksConfig {
@@ -1,5 +1,6 @@
 import type { Binary } from '@polkadot-api/substrate-bindings';
 import type { Enum, HexString, PolkadotSigner, SS58String } from 'polkadot-api';
+import type { XcmLocation } from '../utils/xcm-location';
 
 // --- Storage entry types (mirrors polkadot-api's unsafe API shapes) ---
 
@@ -71,7 +72,7 @@ export interface ScheduledEntry {
   [key: string]: unknown;
 }
 
-// --- Transaction type (returned by txFromCallData) ---
+// --- Transaction types (returned by txFromCallData and api.tx.<Pallet>.<call>) ---
 
 export interface DecodedTransaction {
   sign(from: PolkadotSigner, ...args: unknown[]): Promise<string>;
@@ -79,6 +80,28 @@ export interface DecodedTransaction {
   getEncodedData(): Binary;
 }
 
+export interface UnsafeTransaction {
+  sign(from: PolkadotSigner, ...args: unknown[]): Promise<string>;
+  getEncodedData(): Promise<Binary>;
+}
+
+// --- Runtime API results ---
+
+/** A runtime API's `Result`, as decoded by polkadot-api. */
+export type ApiResult<T> = { success: true; value: T } | { success: false; value: unknown };
+
+export interface Weight {
+  ref_time: bigint;
+  proof_size: bigint;
+}
+
+/** `staging_xcm::v4::Outcome` (`v5` adds nothing the tool reads). */
+export type XcmOutcome = Enum<{
+  Complete: { used: Weight };
+  Incomplete: { used: Weight; error: unknown };
+  Error: { error: unknown };
+}>;
+
 // --- Runtime version ---
 
 export interface RuntimeVersion {
@@ -99,6 +122,80 @@ export interface ReferendaPallet {
   ReferendumCount: StorageValue<number>;
 }
 
+/** Calls of a referenda pallet instance, used by `--cancel-at` */
+export interface ReferendaTx {
+  cancel(args: { index: number }): UnsafeTransaction;
+  refund_submission_deposit(args: { index: number }): UnsafeTransaction;
+  refund_decision_deposit(args: { index: number }): UnsafeTransaction;
+}
+
+// --- Proxy definition (Proxy.Proxies value entries) ---
+
+export interface ProxyDefinition {
+  delegate: SS58String;
+  proxy_type: { type: string };
+  delay: number;
+}
+
+/** A `Proxy.Announcements` entry: a call announced by a delegate for `real` at `height`. */
+export interface ProxyAnnouncement {
+  real: SS58String;
+  call_hash: Binary;
+  height: number;
+}
+
+// --- Multisig (Multisig.Multisigs values) ---
+
+/** Block height and extrinsic index at which a multisig operation was opened. */
+export interface MultisigTimepoint {
+  height: number;
+  index: number;
+}
+
+export interface MultisigOperation {
+  when: MultisigTimepoint;
+  deposit: bigint;
+  depositor: SS58String;
+  approvals: SS58String[];
+}
+
+export interface MultisigWeight {
+  ref_time: bigint;
+  proof_size: bigint;
+}
+
+// --- Balances (System.Account, Assets.Account / Assets.Metadata) ---
+
+export interface AccountInfo {
+  data: { free: bigint; reserved: bigint; [key: string]: unknown };
+  [key: string]: unknown;
+}
+
+export interface AssetMetadata {
+  symbol: Binary;
+  decimals: number;
+  [key: string]: unknown;
+}
+
+// --- Conviction voting (ConvictionVoting.VotingFor values) ---
+
+/** `AccountVote::Standard`: `vote` is the aye bit (0x80) or'ed with the conviction. */
+export type AccountVote = Enum<{
+  Standard: { vote: number; balance: bigint };
+  Split: { aye: bigint; nay: bigint };
+  SplitAbstain: { aye: bigint; nay: bigint; abstain: bigint };
+}>;
+
+export type ConvictionVotingInfo = Enum<{
+  Casting: {
+    votes: [number, AccountVote][];
+    delegations: { votes: bigint; capital: bigint };
+    /** `[unlock block, balance]` still locked from removed votes */
+    prior: [number, bigint];
+  };
+  Delegating: { balance: bigint; target: SS58String; [key: string]: unknown };
+}>;
+
 // --- System event (kept loose — parsed via parseBlockEvent()) ---
 
 export interface SystemEvent {
@@ -114,19 +211,53 @@ export interface SubstrateApi {
     System: {
       Number: StorageValue<number>;
       Events: StorageValue<SystemEvent[]>;
+      Account: StorageMap<SS58String, AccountInfo>;
     };
     Referenda: ReferendaPallet;
     FellowshipReferenda: ReferendaPallet;
     Scheduler: {
       Agenda: StorageEntries<number, ScheduledEntry[]>;
       Lookup: StorageMap<Uint8Array, [number, number]>;
+      /** Retry configurations by agenda slot; unset on runtimes without scheduler retries */
+      Retries?: StorageMap<
+        [number, number],
+        { total_retries: number; remaining: number; period: number }
+      >;
     };
     Balances: {
       TotalIssuance: StorageValue<bigint>;
     };
+    Preimage?: {
+      PreimageFor: StorageMap<[Binary, number], Binary>;
+    };
+    ConvictionVoting?: {
+      VotingFor: StorageMap<[SS58String, number], ConvictionVotingInfo>;
+      /** Locked balance per class (track) the account voted in */
+      ClassLocksFor: StorageMap<SS58String, [number, bigint][]>;
+    };
+    Assets?: {
+      Account: StorageMap<[number, SS58String], { balance: bigint }>;
+      /** Details of an existing asset; unset for ids no asset was created with */
+      Asset: StorageMap<number, unknown>;
+      Metadata: StorageMap<number, AssetMetadata>;
+    };
+    ForeignAssets?: {
+      Account: StorageMap<[XcmLocation, SS58String], { balance: bigint }>;
+      Metadata: StorageMap<XcmLocation, AssetMetadata>;
+    };
+    Proxy?: {
+      Proxies: StorageMap<SS58String, [ProxyDefinition[], bigint]>;
+      Announcements: StorageMap<SS58String, [ProxyAnnouncement[], bigint]>;
+    };
+    Multisig?: {
+      Multisigs: StorageMap<[SS58String, Binary], MultisigOperation>;
+    };
     ParachainSystem?: {
       LastRelayChainBlockNumber: StorageValue<number>;
     };
+    ParachainInfo?: {
+      ParachainId: StorageValue<number>;
+    };
   };
   constants: {
     System: {
@@ -138,6 +269,84 @@ export interface SubstrateApi {
     FellowshipReferenda: {
       Tracks(): Promise<TrackInfo[]>;
     };
+    Balances?: {
+      ExistentialDeposit(): Promise<bigint>;
+    };
+    Babe?: {
+      EpochDuration(): Promise<bigint>;
+    };
+    Treasury?: {
+      /** 8 bytes (`py/trsry`) the treasury account is derived from */
+      PalletId(): Promise<Binary>;
+    };
+    ConvictionVoting?: {
+      /** Blocks a `Locked1x` vote stays locked for after its poll ends */
+      VoteLockingPeriod(): Promise<number>;
+    };
+  };
+  tx: {
+    System: {
+      remark(args: { remark: Binary }): UnsafeTransaction;
+    };
+    Referenda?: ReferendaTx;
+    FellowshipReferenda?: ReferendaTx;
+    Treasury?: {
+      payout(args: { index: number }): UnsafeTransaction;
+    };
+    Preimage?: {
+      note_preimage(args: { bytes: Binary }): UnsafeTransaction;
+    };
+    ConvictionVoting?: {
+      vote(args: { poll_index: number; vote: AccountVote }): UnsafeTransaction;
+      remove_vote(args: { class: number | undefined; index: number }): UnsafeTransaction;
+      unlock(args: { class: number; target: unknown }): UnsafeTransaction;
+    };
+    Proxy?: {
+      proxy(args: { real: unknown; force_proxy_type: unknown; call: unknown }): UnsafeTransaction;
+      announce(args: { real: unknown; call_hash: Binary }): UnsafeTransaction;
+      proxy_announced(args: {
+        delegate: unknown;
+        real: unknown;
+        force_proxy_type: unknown;
+        call: unknown;
+      }): UnsafeTransaction;
+    };
+    Multisig?: {
+      as_multi_threshold_1(args: {
+        other_signatories: SS58String[];
+        call: unknown;
+      }): UnsafeTransaction;
+      approve_as_multi(args: {
+        threshold: number;
+        other_signatories: SS58String[];
+        maybe_timepoint: MultisigTimepoint | undefined;
+        call_hash: Binary;
+        max_weight: MultisigWeight;
+      }): UnsafeTransaction;
+      as_multi(args: {
+        threshold: number;
+        other_signatories: SS58String[];
+        maybe_timepoint: MultisigTimepoint | undefined;
+        call: unknown;
+        max_weight: MultisigWeight;
+      }): UnsafeTransaction;
+    };
+  };
+  /** Runtime APIs; calling one the runtime lacks rejects */
+  apis: {
+    XcmPaymentApi: {
+      query_xcm_weight(message: unknown): Promise<ApiResult<Weight>>;
+      query_weight_to_asset_fee(weight: Weight, asset: unknown): Promise<ApiResult<bigint>>;
+    };
+    DryRunApi: {
+      dry_run_xcm(
+        origin: unknown,
+        message: unknown
+      ): Promise<ApiResult<{ execution_result: XcmOutcome }>>;
+    };
+    TransactionPaymentCallApi: {
+      query_call_info(call: unknown, len: number): Promise<{ weight: Weight }>;
+    };
   };
   txFromCallData(callData: Binary): Promise<DecodedTransaction>;
 }
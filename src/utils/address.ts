@@ -0,0 +1,11 @@
+import { AccountId } from 'polkadot-api';
+
+/** Whether `value` is a valid SS58 address. */
+export function isAddress(value: string): boolean {
+  try {
+    AccountId().enc(value);
+    return true;
+  } catch {
+    return false;
+  }
+}
@@ -1,25 +1,127 @@
+import { CodedError, ErrorCode } from './error-codes';
+
 /**
  * Parsed chain endpoint with optional block number
  */
 export interface ParsedEndpoint {
   url: string;
+  /** Block to fork at; without one, Chopsticks forks the latest finalized block */
   block?: number;
+  /** Name to show for the chain instead of the one derived from its spec name */
+  label?: string;
+}
+
+/** Block numbers are `u32` on every supported chain. */
+export const MAX_BLOCK_NUMBER = 0xffffffff;
+
+/**
+ * Parse an endpoint's block: decimal (`12345`) or `0x`-prefixed hex
+ * (`0x3039`), as RPCs and explorers print them. `input` is the whole
+ * endpoint, quoted in errors.
+ */
+export function parseBlockNumber(block: string, input: string): number {
+  const trimmed = block.trim();
+  const invalid = (reason: string) =>
+    new CodedError(
+      ErrorCode.InvalidEndpoint,
+      `Invalid block number "${trimmed}" in ${input}: ${reason}`
+    );
+  if (trimmed.length === 0) {
+    throw new CodedError(ErrorCode.InvalidEndpoint, `Missing block number in ${input}`);
+  }
+  if (/^-\d+$/.test(trimmed)) {
+    throw invalid('block numbers cannot be negative');
+  }
+  let value: number;
+  if (/^\d+$/.test(trimmed)) {
+    value = Number(trimmed);
+  } else if (/^0x[0-9a-f]+$/i.test(trimmed)) {
+    value = Number.parseInt(trimmed.slice(2), 16);
+  } else {
+    throw invalid('expected a decimal or 0x-prefixed hex number');
+  }
+  if (value > MAX_BLOCK_NUMBER) {
+    throw invalid(`larger than the largest block number ${MAX_BLOCK_NUMBER}`);
+  }
+  return value;
+}
+
+/**
+ * An endpoint as reported in JSON output. `block` is `null` when the latest
+ * finalized block is forked.
+ */
+export interface ReportedEndpoint {
+  url: string;
+  block: number | null;
+  label: string | null;
+}
+
+export function reportEndpoint(endpoint: ParsedEndpoint): ReportedEndpoint {
+  return {
+    url: endpoint.url,
+    block: endpoint.block ?? null,
+    label: endpoint.label ?? null,
+  };
+}
+
+/** Whether `input` uses the `name=...;url=...;block=...` form. */
+function isLabeledEndpoint(input: string): boolean {
+  return /(^|;)\s*url\s*=/.test(input);
+}
+
+/**
+ * Parse the labeled form `name=Relay;url=wss://...;block=123`, where `name`
+ * and `block` are optional.
+ */
+function parseLabeledEndpoint(input: string): ParsedEndpoint {
+  const fields = new Map<string, string>();
+  for (const field of input.split(';').filter((f) => f.trim().length > 0)) {
+    const separator = field.indexOf('=');
+    const key = field.slice(0, separator).trim();
+    if (separator < 0 || !['name', 'url', 'block'].includes(key)) {
+      throw new CodedError(
+        ErrorCode.InvalidEndpoint,
+        `Invalid endpoint field "${field.trim()}" in ${input}. Expected name=, url= or block=`
+      );
+    }
+    fields.set(key, field.slice(separator + 1).trim());
+  }
+
+  const endpoint: ParsedEndpoint = { url: fields.get('url') ?? '' };
+  if (endpoint.url.length === 0) {
+    throw new CodedError(ErrorCode.InvalidEndpoint, `Missing url= in endpoint ${input}`);
+  }
+  const blockStr = fields.get('block');
+  if (blockStr !== undefined) {
+    endpoint.block = parseBlockNumber(blockStr, input);
+  }
+  const label = fields.get('name');
+  if (label) {
+    endpoint.label = label;
+  }
+  return endpoint;
 }
 
 /**
  * Parse a chain endpoint string that may include a block number.
  *
  * Formats supported:
- * - "wss://polkadot.io" -> { url: "wss://polkadot.io" }
+ * - "wss://polkadot.io" -> { url: "wss://polkadot.io" } (latest finalized block)
  * - "wss://polkadot.io,12345" -> { url: "wss://polkadot.io", block: 12345 }
+ * - "wss://polkadot.io,0x3039" -> the same, with the block in hex
+ * - "name=Relay;url=wss://polkadot.io;block=12345" -> the same, labeled "Relay"
  *
- * @param input - The input string in format "url" or "url,block"
+ * @param input - The input string in format "url", "url,block" or "name=...;url=...;block=..."
  * @returns Parsed endpoint with url and optional block number
- * @throws Error if the format is invalid or block number is not a valid integer
+ * @throws Error if the format is invalid or the block is not a valid block number
  */
 export function parseEndpoint(input: string): ParsedEndpoint {
   if (!input || input.trim().length === 0) {
-    throw new Error('Endpoint string cannot be empty');
+    throw new CodedError(ErrorCode.InvalidEndpoint, 'Endpoint string cannot be empty');
+  }
+
+  if (isLabeledEndpoint(input)) {
+    return parseLabeledEndpoint(input);
   }
 
   const parts = input.split(',');
@@ -30,19 +132,14 @@ export function parseEndpoint(input: string): ParsedEndpoint {
   }
 
   if (parts.length === 2) {
-    const url = parts[0].trim();
-    const blockStr = parts[1].trim();
-
-    const blockNum = parseInt(blockStr, 10);
-    if (Number.isNaN(blockNum) || blockNum < 0) {
-      throw new Error(`Invalid block number: ${blockStr}. Must be a non-negative integer.`);
-    }
-
-    return { url, block: blockNum };
+    return { url: parts[0].trim(), block: parseBlockNumber(parts[1], input) };
   }
 
   // More than one comma - this is an error for a single endpoint
-  throw new Error(`Invalid endpoint format: ${input}. Expected "url" or "url,block"`);
+  throw new CodedError(
+    ErrorCode.InvalidEndpoint,
+    `Invalid endpoint format: ${input}. Expected "url" or "url,block"`
+  );
 }
 
 /**
@@ -53,6 +150,7 @@ export function parseEndpoint(input: string): ParsedEndpoint {
  * - "wss://chain1.io,wss://chain2.io"
  * - "wss://chain1.io,12345,wss://chain2.io"
  * - "wss://chain1.io,12345,wss://chain2.io,67890"
+ * - "name=People;url=wss://chain1.io;block=12345,wss://chain2.io"
  *
  * @param input - Comma-separated string of endpoints
  * @returns Array of parsed endpoints
@@ -69,23 +167,30 @@ export function parseMultipleEndpoints(input: string): ParsedEndpoint[] {
   while (i < parts.length) {
     const part = parts[i];
 
+    if (isLabeledEndpoint(part)) {
+      endpoints.push(parseLabeledEndpoint(part));
+      i += 1;
+      continue;
+    }
+
     // Check if this looks like a URL (contains ://)
     if (part.includes('://')) {
-      // Check if next part is a block number
-      if (i + 1 < parts.length && !parts[i + 1].includes('://')) {
-        const blockNum = parseInt(parts[i + 1], 10);
-        if (!Number.isNaN(blockNum) && blockNum >= 0) {
-          endpoints.push({ url: part, block: blockNum });
-          i += 2; // Skip both URL and block
-          continue;
-        }
+      // Anything but another endpoint after a URL is its block number
+      const next = parts[i + 1];
+      if (next !== undefined && !next.includes('://') && !isLabeledEndpoint(next)) {
+        endpoints.push({ url: part, block: parseBlockNumber(next, `${part},${next}`) });
+        i += 2; // Skip both URL and block
+        continue;
       }
 
       // Just URL without block
       endpoints.push({ url: part });
       i += 1;
     } else {
-      throw new Error(`Expected URL at position ${i}, got: ${part}`);
+      throw new CodedError(
+        ErrorCode.InvalidEndpoint,
+        `Expected URL at position ${i}, got: ${part}`
+      );
     }
   }
 
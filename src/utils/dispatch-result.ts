@@ -1,3 +1,4 @@
+import type { ParsedEvent } from './event-serializer';
 import { stringify } from './json';
 
 /**
@@ -143,3 +144,21 @@ export function formatDispatchError(error: unknown): string {
 
   return String(error);
 }
+
+/**
+ * `Proxy.proxy` and `Multisig.as_multi` succeed even when the wrapped call
+ * fails; the call's own result is only in their `*Executed` event.
+ */
+export function wrappedCallError(events: ParsedEvent[]): string | undefined {
+  for (const event of events) {
+    const wrapper =
+      (event.section === 'Proxy' && event.method === 'ProxyExecuted') ||
+      (event.section === 'Multisig' && event.method === 'MultisigExecuted');
+    if (!wrapper) continue;
+    const outcome = interpretDispatchResult((event.data as { result?: unknown }).result);
+    if (outcome.outcome !== 'success') {
+      return outcome.message ?? outcome.outcome;
+    }
+  }
+  return undefined;
+}
@@ -0,0 +1,178 @@
+/**
+ * Stable error codes for fatal tool errors.
+ *
+ * When `test` fails, the code is written to stderr as the first token of a
+ * line (`E-PREIMAGE-MISSING: <message>`). The integration tests parse it via
+ * `ToolOutput::error_code()` instead of matching English phrases. Codes are
+ * part of the CLI contract: add new ones freely, but never rename existing ones.
+ */
+export const ErrorCode = {
+  /** Two mutually exclusive flags were given. */
+  FlagConflict: 'E-FLAG-CONFLICT',
+  /** No referendum was specified or requested for creation. */
+  NoReferendum: 'E-NO-REFERENDUM',
+  /** A flag that another flag depends on is missing. */
+  MissingChainUrl: 'E-MISSING-CHAIN-URL',
+  /** A referendum ID flag was not an integer. */
+  InvalidReferendumId: 'E-INVALID-REFERENDUM-ID',
+  /** A chain endpoint (`url` or `url,block`) could not be parsed. */
+  InvalidEndpoint: 'E-INVALID-ENDPOINT',
+  /** Call data was not a hex string. */
+  InvalidHex: 'E-INVALID-HEX',
+  /** Call data could not be decoded against the chain's runtime. */
+  CallDecodeFailed: 'E-CALL-DECODE-FAILED',
+  /** Submitting a referendum did not create one. */
+  ReferendumNotCreated: 'E-REFERENDUM-NOT-CREATED',
+  /** The referendum does not exist on the forked chain. */
+  ReferendumNotFound: 'E-REFERENDUM-NOT-FOUND',
+  /** The referendum exists but is not Ongoing (or Approved). */
+  ReferendumNotOngoing: 'E-REFERENDUM-NOT-ONGOING',
+  /** Forcing the passing state did not confirm/approve the referendum. */
+  ReferendumNotApproved: 'E-REFERENDUM-NOT-APPROVED',
+  /** The enactment task could not be found in the scheduler agenda. */
+  ScheduledCallNotFound: 'E-SCHEDULED-CALL-NOT-FOUND',
+  /** The proposal's preimage was not available at dispatch time. */
+  PreimageMissing: 'E-PREIMAGE-MISSING',
+  /** The proposal's weight exceeds what the scheduler may use in a block. */
+  PermanentlyOverweight: 'E-PERMANENTLY-OVERWEIGHT',
+  /** Blocks kept running out of weight before the scheduler reached the proposal. */
+  EnactmentPostponed: 'E-ENACTMENT-POSTPONED',
+  /** The proposal was dispatched but failed, or was never dispatched. */
+  ExecutionFailed: 'E-EXECUTION-FAILED',
+  /** `--port-range` was not `start:len` or fell outside 1-65535. */
+  InvalidPortRange: 'E-INVALID-PORT-RANGE',
+  /** A port in `--port-range` was taken, or the range was too small. */
+  PortUnavailable: 'E-PORT-UNAVAILABLE',
+  /** A `--hook-*` script does not exist or has an unsupported extension. */
+  InvalidHook: 'E-INVALID-HOOK',
+  /** A `--hook-*` script exited non-zero. */
+  HookFailed: 'E-HOOK-FAILED',
+  /** `--lint` or `--deny` named an unknown rule or level. */
+  InvalidLintConfig: 'E-INVALID-LINT-CONFIG',
+  /** The proposal executed, but a lint at `deny` level fired. */
+  LintDenied: 'E-LINT-DENIED',
+  /** `--settle-blocks` was not a positive integer. */
+  InvalidSettleBlocks: 'E-INVALID-SETTLE-BLOCKS',
+  /** `--blocks-after-dispatch` was not a non-negative integer. */
+  InvalidBlocksAfterDispatch: 'E-INVALID-BLOCKS-AFTER-DISPATCH',
+  /** `--enactment-retries` was not an integer from 1 to 255. */
+  InvalidEnactmentRetries: 'E-INVALID-ENACTMENT-RETRIES',
+  /** Simulating `Treasury.payout` for an approved spend failed or paid the wrong amount. */
+  TreasuryPayoutFailed: 'E-TREASURY-PAYOUT-FAILED',
+  /** `--track-balance` was not a comma-separated list of `account@asset[=delta]`. */
+  InvalidBalanceCheck: 'E-INVALID-BALANCE-CHECK',
+  /** The proposal executed, but a tracked balance changed by a different amount than expected. */
+  BalanceCheckFailed: 'E-BALANCE-CHECK-FAILED',
+  /** `--submit-via-proxy` was not `real[:proxy type[:delay]]`, or the chain has no Proxy pallet. */
+  InvalidProxy: 'E-INVALID-PROXY',
+  /** `--submit-via-multisig` was not `threshold:signatory,...`, or the chain has no Multisig. */
+  InvalidMultisig: 'E-INVALID-MULTISIG',
+  /** `sweep --concurrency` was not a positive integer. */
+  InvalidConcurrency: 'E-INVALID-CONCURRENCY',
+  /** `selftest origins`: an origin without fields could not be constructed and dispatched. */
+  OriginSelftestFailed: 'E-ORIGIN-SELFTEST-FAILED',
+  /** `--referendum <pallet>:<id>` named a pallet that is not a referenda instance of the chain. */
+  UnknownReferendaPallet: 'E-UNKNOWN-REFERENDA-PALLET',
+  /** `--scenario` named an unknown scenario. */
+  InvalidScenario: 'E-INVALID-SCENARIO',
+  /** The proposal was dispatched from an origin its call does not accept. */
+  BadOrigin: 'E-BAD-ORIGIN',
+  /** `--cancel-at` named an unknown phase. */
+  InvalidCancelPhase: 'E-INVALID-CANCEL-PHASE',
+  /** `--cancel-at`: the referendum was not cancelled, or its deposits were not refunded. */
+  CancellationFailed: 'E-CANCELLATION-FAILED',
+  /** `--vote-conviction` named an unknown conviction. */
+  InvalidConviction: 'E-INVALID-CONVICTION',
+  /** `--vote-conviction`: the voter's class lock or its unlock block was not the expected one. */
+  ConvictionLockFailed: 'E-CONVICTION-LOCK-FAILED',
+  /** Referendum metadata is not a JSON file or preimage hash, or set_metadata failed. */
+  InvalidMetadata: 'E-INVALID-METADATA',
+  /** `--set-storage` or `--fund` was malformed, or does not fit the chain's storage. */
+  InvalidStorageOverride: 'E-INVALID-STORAGE-OVERRIDE',
+  /** `--run-id` contained characters other than letters, digits, `.`, `_` and `-`. */
+  InvalidRunId: 'E-INVALID-RUN-ID',
+  /** `--output-format` was neither `text` nor `json`. */
+  InvalidOutputFormat: 'E-INVALID-OUTPUT-FORMAT',
+  /** Of a fellowship + governance run, one referendum passed and the other failed. */
+  PartialSuccess: 'E-PARTIAL-SUCCESS',
+  /** A forked chain lacks a pallet or runtime API the run needs. */
+  MissingCapability: 'E-MISSING-CAPABILITY',
+  /** Chopsticks failed to start or the forked chain never became ready. */
+  ChopsticksFailed: 'E-CHOPSTICKS-FAILED',
+  /** The run was stopped by SIGINT, SIGTERM or a `dev_stop` request. */
+  Interrupted: 'E-INTERRUPTED',
+  /** Anything not classified above. */
+  Unknown: 'E-UNKNOWN',
+} as const;
+
+export type ErrorCode = (typeof ErrorCode)[keyof typeof ErrorCode];
+
+/** An Error carrying a stable {@link ErrorCode}. */
+export class CodedError extends Error {
+  readonly code: ErrorCode;
+
+  constructor(code: ErrorCode, message: string, options?: { cause?: unknown }) {
+    super(message, options);
+    this.name = 'CodedError';
+    this.code = code;
+  }
+}
+
+/**
+ * Find the code of an error, following the `cause` chain so wrapped errors
+ * (e.g. "Failed to force referendum execution") keep the root cause's code.
+ * The outermost code wins; falls back to `E-UNKNOWN`.
+ */
+export function errorCodeOf(error: unknown): ErrorCode {
+  let current: unknown = error;
+  const seen = new Set<unknown>();
+  while (current instanceof Error && !seen.has(current)) {
+    seen.add(current);
+    if (current instanceof CodedError) {
+      return current.code;
+    }
+    current = current.cause;
+  }
+  return ErrorCode.Unknown;
+}
+
+/** The stderr line for a fatal error: `<CODE>: <message>`. */
+export function formatCodedError(error: unknown): string {
+  const message = error instanceof Error ? error.message : String(error);
+  return `${errorCodeOf(error)}: ${message}`;
+}
+
+/**
+ * Exit code of a `test` run whose proposal the scheduler never dispatched
+ * (`E-PREIMAGE-MISSING`, `E-PERMANENTLY-OVERWEIGHT`, `E-ENACTMENT-POSTPONED`),
+ * so scripts can tell "never enacted" apart from "enacted and failed" (exit
+ * code 1).
+ */
+export const NOT_DISPATCHED_EXIT_CODE = 2;
+
+const NOT_DISPATCHED_CODES: ReadonlySet<ErrorCode> = new Set([
+  ErrorCode.PreimageMissing,
+  ErrorCode.PermanentlyOverweight,
+  ErrorCode.EnactmentPostponed,
+]);
+
+/**
+ * Exit code of a fellowship + governance run where one referendum passed and
+ * the other failed (`E-PARTIAL-SUCCESS`); the "Referendum Results" section
+ * says which.
+ */
+export const PARTIAL_SUCCESS_EXIT_CODE = 3;
+
+/**
+ * Exit code of a run stopped by SIGINT, SIGTERM or `dev_stop` after its forks
+ * were torn down (`E-INTERRUPTED`), the shell convention for Ctrl+C.
+ */
+export const INTERRUPTED_EXIT_CODE = 130;
+
+/** The process exit code for a fatal error. */
+export function exitCodeOf(error: unknown): number {
+  const code = errorCodeOf(error);
+  if (code === ErrorCode.Interrupted) return INTERRUPTED_EXIT_CODE;
+  if (code === ErrorCode.PartialSuccess) return PARTIAL_SUCCESS_EXIT_CODE;
+  return NOT_DISPATCHED_CODES.has(code) ? NOT_DISPATCHED_EXIT_CODE : 1;
+}
@@ -1,3 +1,5 @@
+import { formatDispatchError, wrappedCallError } from './dispatch-result';
+import { CodedError, type ErrorCode } from './error-codes';
 import { toHexString } from './hex';
 import { stringify } from './json';
 import type { Logger } from './logger';
@@ -180,22 +182,50 @@ export async function getBlockEvents(
   }
 }
 
+/**
+ * Build a block with `signedTx` and fail with `code` unless it emitted
+ * `section.method`. The error carries the dispatch error of the extrinsic,
+ * or of the call it wrapped when it went through a proxy or multisig.
+ */
+export async function expectEvent(
+  chain: { newBlock(params: { transactions: string[] }): Promise<void> },
+  eventsQuery: { getValue(): Promise<unknown[]> },
+  signedTx: string,
+  expected: { section: string; method: string; step: string; code: ErrorCode },
+  logger: Logger
+): Promise<void> {
+  const { section, method, step, code } = expected;
+  await chain.newBlock({ transactions: [signedTx] });
+  const events = await getBlockEvents(eventsQuery, logger);
+  if (events.some((e) => e.section === section && e.method === method)) return;
+
+  logger.failSpinner(`${step} failed`);
+  const failed = events.find((e) => e.section === 'System' && e.method === 'ExtrinsicFailed');
+  const reason = wrappedCallError(events) ?? (failed && formatDispatchError(failed.data));
+  throw new CodedError(code, `${step} was not accepted${reason ? `: ${reason}` : ''}`);
+}
+
 /**
  * Display chain events with a label and block number.
  * Shared display logic used by NetworkCoordinator for post-execution event display.
+ * `describe` may add a one-line description to an event, e.g. for governance v1 events.
  */
 export function displayChainEvents(
   label: string,
   blockNumber: number | bigint,
   events: unknown[] | null | undefined,
-  logger: Logger
+  logger: Logger,
+  describe?: (event: ParsedEvent) => string | undefined
 ): void {
   logger.info(`\u{1F4E1} ${label} (Block #${blockNumber})`);
 
   if (events && Array.isArray(events)) {
     events.forEach((event: unknown) => {
       const parsed = parseBlockEvent(event);
-      logger.info(`  \u2022 ${parsed.section}.${parsed.method}`);
+      const description = describe?.(parsed);
+      logger.info(
+        `  \u2022 ${parsed.section}.${parsed.method}${description ? ` \u2014 ${description}` : ''}`
+      );
 
       if (logger.isVerbose() && parsed.data) {
         const serialized = serializeEventData(parsed.data);
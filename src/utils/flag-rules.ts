@@ -0,0 +1,88 @@
+import flagRules from '../flag-rules.json';
+import { CodedError, type ErrorCode } from './error-codes';
+
+/**
+ * CLI flag rule table shared with the integration tests.
+ *
+ * The rules live in `src/flag-rules.json` so the Rust validation suite can
+ * generate one sub-test per rule from the exact same data the tool enforces.
+ */
+export interface FlagDefinition {
+  option: string;
+  sample: string;
+  integer?: {
+    name: string;
+    code: string;
+    invalidSample: string;
+    message: string;
+    /** Also accept `<pallet>:<id>`, e.g. `FellowshipReferenda:12` */
+    palletPrefix?: boolean;
+  };
+}
+
+export interface FlagRuleTable {
+  flags: Record<string, FlagDefinition>;
+  mutuallyExclusive: { name: string; code: string; flags: string[]; message: string }[];
+  atLeastOneOf: { name: string; code: string; flags: string[]; message: string }[];
+  requires: {
+    name: string;
+    code: string;
+    whenAny: string[];
+    requires: string;
+    message: string;
+  }[];
+}
+
+export const FLAG_RULES: FlagRuleTable = flagRules;
+
+function optionValue(options: object, flag: string, rules: FlagRuleTable): unknown {
+  const definition = rules.flags[flag];
+  if (!definition) {
+    throw new Error(`Flag rule table references unknown flag ${flag}`);
+  }
+  return (options as Record<string, unknown>)[definition.option];
+}
+
+function isSet(options: object, flag: string, rules: FlagRuleTable): boolean {
+  return !!optionValue(options, flag, rules);
+}
+
+/**
+ * Validate parsed CLI options against the rule table. Rules are checked in a
+ * fixed order: mutual exclusion, at-least-one-of, dependencies, integer values.
+ * Throws an Error with the rule's message on the first violation.
+ */
+export function validateFlagRules(options: object, rules: FlagRuleTable = FLAG_RULES): void {
+  for (const rule of rules.mutuallyExclusive) {
+    if (rule.flags.filter((flag) => isSet(options, flag, rules)).length > 1) {
+      throw new CodedError(rule.code as ErrorCode, rule.message);
+    }
+  }
+
+  for (const rule of rules.atLeastOneOf) {
+    if (!rule.flags.some((flag) => isSet(options, flag, rules))) {
+      throw new CodedError(rule.code as ErrorCode, rule.message);
+    }
+  }
+
+  for (const rule of rules.requires) {
+    const triggered = rule.whenAny.some((flag) => isSet(options, flag, rules));
+    if (triggered && !isSet(options, rule.requires, rules)) {
+      throw new CodedError(rule.code as ErrorCode, rule.message);
+    }
+  }
+
+  for (const [flag, definition] of Object.entries(rules.flags)) {
+    if (!definition.integer) continue;
+    const value = optionValue(options, flag, rules);
+    const id = definition.integer.palletPrefix
+      ? String(value).replace(/^[A-Za-z]\w*:/, '')
+      : String(value);
+    if (value && Number.isNaN(parseInt(id, 10))) {
+      throw new CodedError(
+        definition.integer.code as ErrorCode,
+        definition.integer.message.replace('{value}', String(value))
+      );
+    }
+  }
+}
@@ -1,3 +1,6 @@
+import type { TestOptions } from '../types';
+import { CodedError, ErrorCode } from './error-codes';
+
 /**
  * Convert a value to a hex string, handling the various polkadot-api
  * binary types (Binary, FixedSizeBinary, Uint8Array, Buffer, etc.)
@@ -53,3 +56,35 @@ export function toHexString(value: unknown): string | undefined {
 
   return undefined;
 }
+
+/**
+ * Normalize call data as pasted from PJS Apps or a terminal: whitespace and
+ * line breaks anywhere are dropped, the `0x` prefix is optional (and may be
+ * `0X`), and digits are lower-cased. Throws `E-INVALID-HEX`, naming `label`,
+ * when what is left is not hex.
+ */
+export function normalizeCallData(input: string, label: string): string {
+  const digits = input.replace(/\s+/g, '').replace(/^0x/i, '');
+  if (!/^[0-9a-fA-F]*$/.test(digits)) {
+    throw new CodedError(ErrorCode.InvalidHex, `Invalid hex string for ${label}: ${input}`);
+  }
+  return `0x${digits.toLowerCase()}`;
+}
+
+const CALL_DATA_FLAGS = {
+  preCall: '--pre-call',
+  callToCreateGovernanceReferendum: '--call-to-create-governance-referendum',
+  callToNotePreimageForGovernanceReferendum: '--call-to-note-preimage-for-governance-referendum',
+  callToCreateFellowshipReferendum: '--call-to-create-fellowship-referendum',
+  callToNotePreimageForFellowshipReferendum: '--call-to-note-preimage-for-fellowship-referendum',
+} as const;
+
+/** Normalize every call-data option in place, before anything reads it. */
+export function normalizeCallDataOptions(options: TestOptions): void {
+  for (const key of Object.keys(CALL_DATA_FLAGS) as (keyof typeof CALL_DATA_FLAGS)[]) {
+    const value = options[key];
+    if (value !== undefined) {
+      options[key] = normalizeCallData(value, CALL_DATA_FLAGS[key]);
+    }
+  }
+}
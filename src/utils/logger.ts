@@ -1,36 +1,130 @@
 import chalk from 'chalk';
+import * as fs from 'fs';
 import { createSpinner } from 'nanospinner';
+import { stripVTControlCharacters } from 'util';
+
+/** Prefix of the machine-readable per-block lines printed with `--emit-blocks`. */
+export const BLOCK_LINE_PREFIX = '@@block ';
+
+/** A block built on a fork, as reported by `--emit-blocks`. */
+export interface BlockReport {
+  /** WebSocket endpoint of the fork the block was built on */
+  endpoint: string;
+  number: number;
+  hash?: string;
+}
+
+/** Prefix of the machine-readable final heads line printed with `--emit-blocks`. */
+export const HEADS_LINE_PREFIX = '@@heads ';
+
+/** A block on a fork, by number and (when Chopsticks knows it) hash. */
+export interface HeadRef {
+  number: number;
+  hash?: string;
+}
+
+/** Where a fork started and where the run left it, as reported by `--emit-blocks`. */
+export interface ChainHeadReport {
+  chain: string;
+  /** WebSocket endpoint of the fork */
+  endpoint: string;
+  fork: HeadRef;
+  final: HeadRef;
+}
 
 export class Logger {
   private verbose: boolean;
+  private emitBlocks: boolean;
+  private runId?: string;
+  private logFile?: string;
   private spinner: ReturnType<typeof createSpinner> | null = null;
 
-  constructor(verbose: boolean = false) {
+  constructor(verbose: boolean = false, emitBlocks: boolean = false, runId?: string) {
     this.verbose = verbose;
+    this.emitBlocks = emitBlocks;
+    this.runId = runId;
+  }
+
+  /**
+   * Also write every human-readable line to `file`, without colors and with
+   * the debug lines `--verbose` would print, e.g. the run's log in its work dir.
+   */
+  writeTo(file: string): void {
+    this.logFile = file;
+  }
+
+  private record(...parts: string[]): void {
+    if (!this.logFile) return;
+    try {
+      fs.appendFileSync(this.logFile, `${stripVTControlCharacters(parts.join(' '))}\n`);
+    } catch {
+      // The work dir is gone, e.g. removed by an interrupted run's stop
+      this.logFile = undefined;
+    }
+  }
+
+  /**
+   * Print a human-readable line, prefixed with `[<run id>]` when the logger
+   * has one so output of runs interleaved in one log stays attributable.
+   * Machine-readable `@@` lines are never prefixed.
+   */
+  private print(...parts: string[]): void {
+    if (this.runId) {
+      console.log(chalk.gray(`[${this.runId}]`), ...parts);
+    } else {
+      console.log(...parts);
+    }
+    this.record(...parts);
+  }
+
+  private tag(message: string): string {
+    return this.runId ? `${chalk.gray(`[${this.runId}]`)} ${message}` : message;
   }
 
   info(message: string): void {
-    console.log(chalk.blue('ℹ'), message);
+    this.print(chalk.blue('ℹ'), message);
   }
 
   success(message: string): void {
-    console.log(chalk.green('✓'), message);
+    this.print(chalk.green('✓'), message);
   }
 
   error(message: string, error?: Error): void {
-    console.log(chalk.red('✖'), message);
+    this.print(chalk.red('✖'), message);
     if (error && this.verbose) {
-      console.log(chalk.red(error.stack || error.message));
+      this.print(chalk.red(error.stack || error.message));
     }
   }
 
   warn(message: string): void {
-    console.log(chalk.yellow('⚠'), message);
+    this.print(chalk.yellow('⚠'), message);
   }
 
   debug(message: string): void {
     if (this.verbose) {
-      console.log(chalk.gray('▸'), chalk.gray(message));
+      this.print(chalk.gray('▸'), chalk.gray(message));
+    } else {
+      this.record('▸', message);
+    }
+  }
+
+  /**
+   * Print one `@@block {json}` line per built block when `--emit-blocks` is set.
+   * Consumed by the Rust `Simulation` API to drive per-block callbacks.
+   */
+  block(report: BlockReport): void {
+    if (this.emitBlocks) {
+      console.log(`${BLOCK_LINE_PREFIX}${JSON.stringify(report)}`);
+    }
+  }
+
+  /**
+   * Print one `@@heads [json]` line with every fork's start and final head
+   * when `--emit-blocks` is set, after the run's last block.
+   */
+  heads(reports: ChainHeadReport[]): void {
+    if (this.emitBlocks) {
+      console.log(`${HEADS_LINE_PREFIX}${JSON.stringify(reports)}`);
     }
   }
 
@@ -38,7 +132,8 @@ export class Logger {
     if (this.spinner) {
       this.spinner.stop();
     }
-    this.spinner = createSpinner(message).start();
+    this.spinner = createSpinner(this.tag(message)).start();
+    this.record('…', message);
   }
 
   isVerbose(): boolean {
@@ -47,21 +142,23 @@ export class Logger {
 
   updateSpinner(message: string): void {
     if (this.spinner) {
-      this.spinner.update({ text: message });
+      this.spinner.update({ text: this.tag(message) });
     }
   }
 
   succeedSpinner(message?: string): void {
     if (this.spinner) {
-      this.spinner.success({ text: message });
+      this.spinner.success({ text: message && this.tag(message) });
       this.spinner = null;
+      if (message) this.record('✓', message);
     }
   }
 
   failSpinner(message?: string): void {
     if (this.spinner) {
-      this.spinner.error({ text: message });
+      this.spinner.error({ text: message && this.tag(message) });
       this.spinner = null;
+      if (message) this.record('✖', message);
     }
   }
 
@@ -73,14 +170,75 @@ export class Logger {
   }
 
   section(title: string): void {
-    console.log(`\n${chalk.bold.cyan(`━━━ ${title} ━━━`)}`);
+    console.log();
+    this.print(chalk.bold.cyan(`━━━ ${title} ━━━`));
   }
 
   table(data: Record<string, unknown>): void {
     console.log();
     for (const [key, value] of Object.entries(data)) {
-      console.log(`  ${chalk.gray(key.padEnd(20))}: ${value}`);
+      this.print(`  ${chalk.gray(key.padEnd(20))}: ${value}`);
     }
     console.log();
   }
 }
+
+/**
+ * Logger for work running alongside other work, like one referendum of a
+ * sweep: progress output is demoted to debug lines, and every line it still
+ * prints is prefixed so concurrent output stays attributable.
+ */
+export class QuietLogger extends Logger {
+  private prefix: string;
+
+  constructor(prefix: string, verbose: boolean = false) {
+    super(verbose);
+    this.prefix = prefix;
+  }
+
+  info(message: string): void {
+    this.debug(message);
+  }
+
+  success(message: string): void {
+    this.debug(message);
+  }
+
+  warn(message: string): void {
+    this.debug(message);
+  }
+
+  error(message: string, error?: Error): void {
+    super.error(`${this.prefix} ${message}`, error);
+  }
+
+  debug(message: string): void {
+    super.debug(`${this.prefix} ${message}`);
+  }
+
+  section(title: string): void {
+    this.debug(title);
+  }
+
+  table(data: Record<string, unknown>): void {
+    for (const [key, value] of Object.entries(data)) {
+      this.debug(`${key}: ${value}`);
+    }
+  }
+
+  startSpinner(message: string): void {
+    this.debug(message);
+  }
+
+  updateSpinner(message: string): void {
+    this.debug(message);
+  }
+
+  succeedSpinner(message?: string): void {
+    if (message) this.debug(message);
+  }
+
+  failSpinner(message?: string): void {
+    if (message) this.debug(message);
+  }
+}
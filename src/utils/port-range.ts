@@ -0,0 +1,71 @@
+import * as net from 'net';
+import { CodedError, ErrorCode } from './error-codes';
+
+/**
+ * A contiguous block of ports the tool may use for its Chopsticks instances,
+ * given as `--port-range start:len`. Callers running the tool in parallel
+ * (e.g. the integration tests' `port_allocator`) hand each run a disjoint
+ * range, and the tool refuses to start if any port in it is already taken.
+ */
+export interface PortRange {
+  start: number;
+  len: number;
+}
+
+/** Parse `start:len`. */
+export function parsePortRange(value: string): PortRange {
+  const match = /^(\d+):(\d+)$/.exec(value.trim());
+  if (!match) {
+    throw new CodedError(
+      ErrorCode.InvalidPortRange,
+      `Invalid port range: ${value} (expected start:len)`
+    );
+  }
+  const start = parseInt(match[1], 10);
+  const len = parseInt(match[2], 10);
+  if (start < 1 || len < 1 || start + len - 1 > 65535) {
+    throw new CodedError(ErrorCode.InvalidPortRange, `Invalid port range: ${value}`);
+  }
+  return { start, len };
+}
+
+function isPortFree(port: number): Promise<boolean> {
+  return new Promise((resolve) => {
+    const server = net.createServer();
+    server.once('error', () => resolve(false));
+    server.listen(port, () => server.close(() => resolve(true)));
+  });
+}
+
+/** Fail fast with `E-PORT-UNAVAILABLE` if any port of the range is in use. */
+export async function assertPortRangeFree(range: PortRange): Promise<void> {
+  for (let port = range.start; port < range.start + range.len; port++) {
+    if (!(await isPortFree(port))) {
+      throw new CodedError(
+        ErrorCode.PortUnavailable,
+        `Port ${port} in range ${range.start}:${range.len} is already in use`
+      );
+    }
+  }
+}
+
+/** Hands out the ports of a range in order, one per Chopsticks instance. */
+export class PortAllocator {
+  private range: PortRange;
+  private next: number;
+
+  constructor(range: PortRange) {
+    this.range = range;
+    this.next = range.start;
+  }
+
+  take(): number {
+    if (this.next >= this.range.start + this.range.len) {
+      throw new CodedError(
+        ErrorCode.PortUnavailable,
+        `Port range ${this.range.start}:${this.range.len} is too small for this topology`
+      );
+    }
+    return this.next++;
+  }
+}
@@ -0,0 +1,26 @@
+/** A chain's native token, as reported by `system_properties`. */
+export interface NativeToken {
+  symbol: string;
+  decimals: number;
+}
+
+/**
+ * Format a planck amount in whole tokens (`1.5 DOT`), trimming trailing zeros.
+ * Without a known token the raw planck amount is returned.
+ */
+export function formatTokenAmount(amount: bigint, token?: NativeToken): string {
+  if (!token) return `${amount}`;
+
+  const sign = amount < 0n ? '-' : '';
+  const abs = amount < 0n ? -amount : amount;
+  const unit = 10n ** BigInt(token.decimals);
+  const fraction = (abs % unit).toString().padStart(token.decimals, '0').replace(/0+$/, '');
+  const whole = `${abs / unit}`;
+  return `${sign}${fraction ? `${whole}.${fraction}` : whole} ${token.symbol}`;
+}
+
+/** A balance change with its sign: `+1.5 DOT`, `-3 DOT`, `+0 DOT`. */
+export function formatDelta(delta: bigint, token?: NativeToken): string {
+  const amount = formatTokenAmount(delta, token);
+  return delta >= 0n ? `+${amount}` : amount;
+}
@@ -0,0 +1,55 @@
+import { randomBytes } from 'crypto';
+import * as fs from 'fs';
+import * as os from 'os';
+import * as path from 'path';
+import { CodedError, ErrorCode } from './error-codes';
+
+/** Parent of the per-run work dirs when `--work-dir` is not given. */
+export const DEFAULT_WORK_ROOT = path.join(os.tmpdir(), 'polkadot-referenda-tester');
+
+/** A run id: start time and a random suffix, e.g. `20261017T101500Z-3fa9c1`. */
+export function newRunId(now: Date = new Date()): string {
+  const time = now.toISOString().replace(/[-:]/g, '').replace(/\.\d+/, '');
+  return `${time}-${randomBytes(3).toString('hex')}`;
+}
+
+const RUN_ID = /^[A-Za-z0-9._-]+$/;
+
+/**
+ * Check a `--run-id` value. Run ids name the work dir, so they are limited to
+ * letters, digits, `.`, `_` and `-`.
+ */
+export function parseRunId(value: string): string {
+  if (!RUN_ID.test(value) || value === '.' || value === '..') {
+    throw new CodedError(
+      ErrorCode.InvalidRunId,
+      `Invalid --run-id '${value}': use letters, digits, '.', '_' and '-' only`
+    );
+  }
+  return value;
+}
+
+/**
+ * The directory holding a run's temporary state (its log and the forks'
+ * runtimes): `<--work-dir or system temp>/<run id>`. It is kept when the run
+ * fails, for inspection, and removed otherwise.
+ */
+export class WorkDir {
+  readonly runId: string;
+  readonly path: string;
+
+  constructor(root: string | undefined, runId: string = newRunId()) {
+    this.runId = runId;
+    this.path = path.join(root ? path.resolve(root) : DEFAULT_WORK_ROOT, runId);
+    fs.mkdirSync(this.path, { recursive: true });
+  }
+
+  /** Path of `name` inside the work dir. */
+  file(name: string): string {
+    return path.join(this.path, name);
+  }
+
+  remove(): void {
+    fs.rmSync(this.path, { recursive: true, force: true });
+  }
+}
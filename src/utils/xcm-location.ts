@@ -0,0 +1,149 @@
+import { AccountId, Binary } from 'polkadot-api';
+
+/** An XCM junction as decoded by polkadot-api: `{ type: 'GeneralIndex', value: 1984n }`. */
+export interface XcmJunction {
+  type: string;
+  value?: unknown;
+}
+
+/** An XCM `Location` (`MultiLocation` before v4). */
+export interface XcmLocation {
+  parents: number;
+  interior: { type: string; value?: unknown };
+}
+
+/** Strip the version of a `Versioned*` XCM value (`{ type: 'V4', value }`). */
+export function unwrapVersioned(value: unknown): unknown {
+  const node = value as { type?: string; value?: unknown } | undefined;
+  return node?.type && /^V\d+$/.test(node.type) ? node.value : value;
+}
+
+/** Unwrap a v3 `AssetId::Concrete(location)`; v4+ asset ids are plain locations. */
+export function assetIdLocation(assetId: unknown): XcmLocation | undefined {
+  const node = assetId as { type?: string; value?: unknown } | undefined;
+  const location = node?.type === 'Concrete' ? node.value : assetId;
+  return isLocation(location) ? location : undefined;
+}
+
+export function isLocation(value: unknown): value is XcmLocation {
+  const node = value as Partial<XcmLocation> | undefined;
+  return typeof node?.parents === 'number' && typeof node.interior?.type === 'string';
+}
+
+/** The junctions of a location's interior, `[]` for `Here`. */
+export function junctions(location: XcmLocation): XcmJunction[] {
+  const { interior } = location;
+  if (interior.type === 'Here' || interior.value === undefined) return [];
+  return (Array.isArray(interior.value) ? interior.value : [interior.value]) as XcmJunction[];
+}
+
+export function isHere(location: XcmLocation): boolean {
+  return location.parents === 0 && junctions(location).length === 0;
+}
+
+function formatJunction(junction: XcmJunction): string {
+  const { type, value } = junction;
+  if (value === undefined) return type;
+  if (typeof value !== 'object' || value === null) return `${type}(${value})`;
+  if (type === 'GlobalConsensus') {
+    const network = value as { type: string; value?: { chain_id?: bigint } };
+    const chainId = network.value?.chain_id;
+    return `${type}(${network.type}${chainId === undefined ? '' : `:${chainId}`})`;
+  }
+  if (type === 'AccountKey20') {
+    const key = (value as { key?: unknown }).key;
+    return key instanceof Binary ? `${type}(${key.asHex()})` : type;
+  }
+  return type;
+}
+
+/** Render a location compactly, e.g. `../Parachain(1000)/PalletInstance(50)`. */
+export function formatLocation(location: XcmLocation): string {
+  const parts = [
+    ...Array.from({ length: location.parents }, () => '..'),
+    ...junctions(location).map(formatJunction),
+  ];
+  return parts.length > 0 ? parts.join('/') : 'Here';
+}
+
+function parseJunction(part: string): XcmJunction {
+  const match = part.match(/^(\w+)(?:\((.*)\))?$/);
+  if (!match) {
+    throw new Error(`invalid junction "${part}"`);
+  }
+  const [, type, arg] = match;
+  if (arg === undefined) {
+    throw new Error(`junction "${type}" needs a value, e.g. ${type}(1000)`);
+  }
+  switch (type) {
+    case 'Parachain':
+    case 'PalletInstance':
+      if (!/^\d+$/.test(arg)) throw new Error(`${type} takes a number, got "${arg}"`);
+      return { type, value: Number(arg) };
+    case 'GeneralIndex':
+      if (!/^\d+$/.test(arg)) throw new Error(`${type} takes a number, got "${arg}"`);
+      return { type, value: BigInt(arg) };
+    case 'GlobalConsensus': {
+      const [network, chainId] = arg.split(':');
+      if (chainId !== undefined && !/^\d+$/.test(chainId)) {
+        throw new Error(`invalid chain id "${chainId}" in ${part}`);
+      }
+      return {
+        type,
+        value:
+          chainId === undefined
+            ? { type: network, value: undefined }
+            : { type: network, value: { chain_id: BigInt(chainId) } },
+      };
+    }
+    case 'AccountKey20':
+      if (!/^0x[0-9a-fA-F]{40}$/.test(arg)) {
+        throw new Error(`${type} takes a 20-byte hex key, got "${arg}"`);
+      }
+      return { type, value: { network: undefined, key: Binary.fromHex(arg) } };
+    default:
+      throw new Error(`unsupported junction "${type}"`);
+  }
+}
+
+/**
+ * Parse the `formatLocation` syntax back into a (v4+) location, e.g.
+ * `../../GlobalConsensus(Ethereum:1)/AccountKey20(0x...)`. Supports the
+ * junctions that identify assets: `Parachain`, `PalletInstance`,
+ * `GeneralIndex`, `GlobalConsensus` and `AccountKey20`.
+ */
+export function parseLocation(path: string): XcmLocation {
+  const parts = path.split('/').filter((part) => part !== 'Here');
+  let parents = 0;
+  while (parts[0] === '..') {
+    parents++;
+    parts.shift();
+  }
+  const interior = parts.map(parseJunction);
+  return {
+    parents,
+    interior:
+      interior.length === 0
+        ? { type: 'Here' }
+        : { type: `X${interior.length}`, value: interior },
+  };
+}
+
+/** Find an account in a MultiAddress or (nested) XCM location, as SS58. */
+export function findAccountId32(value: unknown): string | undefined {
+  if (!value || typeof value !== 'object') return undefined;
+  const node = value as { type?: string; value?: unknown };
+  if (node.type === 'Id' && typeof node.value === 'string') {
+    return node.value;
+  }
+  if (node.type === 'AccountId32') {
+    const id = (node.value as { id?: unknown } | undefined)?.id;
+    const bytes = id instanceof Binary ? id.asBytes() : id;
+    return bytes instanceof Uint8Array ? AccountId().dec(bytes) : undefined;
+  }
+  for (const child of Object.values(node)) {
+    const found = findAccountId32(child);
+    if (found) return found;
+  }
+  return undefined;
+}